@@ -11,10 +11,16 @@
  * `terminal/*` requests. aoe owns the disk; aoe-agent only orchestrates
  * the model.
  *
- * Lifecycle: stdin closes -> exit 0. SIGTERM -> graceful shutdown.
+ * Transport: stdio by default. When `AOE_ACP_SOCKET` is set (sandboxed
+ * cockpit sessions whose runner mounts its agent socket into the
+ * container), connect to that unix socket and speak ACP over it instead.
+ *
+ * Lifecycle: stdin (or the socket) closes -> exit 0. SIGTERM -> graceful
+ * shutdown.
  */
 
 import * as acp from "@agentclientprotocol/sdk";
+import { createConnection } from "node:net";
 import { Readable, Writable } from "node:stream";
 import { streamText, tool, stepCountIs, type ModelMessage } from "ai";
 import { anthropic } from "@ai-sdk/anthropic";
@@ -329,12 +335,30 @@ function randomHexId(): string {
 }
 
 function main() {
-  const input = Writable.toWeb(process.stdout);
-  const output = Readable.toWeb(process.stdin) as ReadableStream<Uint8Array>;
-  const stream = acp.ndJsonStream(input, output);
-  new acp.AgentSideConnection((conn) => new AoeAgent(conn), stream);
+  const socketPath = process.env.AOE_ACP_SOCKET;
+  if (socketPath) {
+    const socket = createConnection(socketPath);
+    socket.on("error", (err) => {
+      console.error(`aoe-agent: ACP socket ${socketPath}: ${err.message}`);
+      process.exit(1);
+    });
+    socket.on("connect", () => {
+      const input = Writable.toWeb(socket);
+      const output = Readable.toWeb(socket) as ReadableStream<Uint8Array>;
+      new acp.AgentSideConnection(
+        (conn) => new AoeAgent(conn),
+        acp.ndJsonStream(input, output),
+      );
+    });
+    socket.on("close", () => process.exit(0));
+  } else {
+    const input = Writable.toWeb(process.stdout);
+    const output = Readable.toWeb(process.stdin) as ReadableStream<Uint8Array>;
+    const stream = acp.ndJsonStream(input, output);
+    new acp.AgentSideConnection((conn) => new AoeAgent(conn), stream);
 
-  process.stdin.on("end", () => process.exit(0));
+    process.stdin.on("end", () => process.exit(0));
+  }
   process.on("SIGTERM", () => process.exit(0));
   process.on("SIGINT", () => process.exit(0));
 }
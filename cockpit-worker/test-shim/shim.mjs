@@ -48,6 +48,11 @@ class ShimAgent {
     if (process.env.SHIM_DELETE_CAPABILITY === "1") {
       agentCapabilities.sessionCapabilities = { delete: {} };
     }
+    // SHIM_MCP_RECORD_FILE advertises HTTP MCP support so remote
+    // servers are sent too; newSession then records what it received.
+    if (process.env.SHIM_MCP_RECORD_FILE) {
+      agentCapabilities.mcpCapabilities = { http: true, sse: false };
+    }
     return {
       protocolVersion: params.protocolVersion ?? acp.PROTOCOL_VERSION,
       agentCapabilities,
@@ -92,7 +97,13 @@ class ShimAgent {
     return {};
   }
 
-  async newSession(_params) {
+  async newSession(params) {
+    const recordFile = process.env.SHIM_MCP_RECORD_FILE;
+    if (recordFile) {
+      const fs = await import("node:fs/promises");
+      const names = (params.mcpServers ?? []).map((s) => s.name);
+      await fs.writeFile(recordFile, names.join("\n") + "\n");
+    }
     const sessionId = "shim-" + Math.random().toString(36).slice(2, 10);
     this.sessions.set(sessionId, {});
     return { sessionId };
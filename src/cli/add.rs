@@ -6,6 +6,7 @@ use std::path::PathBuf;
 
 use crate::containers::{self, ContainerRuntimeInterface};
 use crate::session::builder;
+use crate::session::issues::IssueProvider;
 use crate::session::repo_config;
 use crate::session::{civilizations, GroupTree, Instance, SandboxInfo, Storage};
 
@@ -43,10 +44,17 @@ pub struct AddArgs {
     #[arg(short = 'w', long = "worktree")]
     worktree_branch: Option<String>,
 
-    /// Create a new branch (use with --worktree)
+    /// Create a new branch (use with --worktree, or with --issue to name
+    /// the branch after the issue)
     #[arg(short = 'b', long = "new-branch")]
     create_branch: bool,
 
+    /// Start from a tracker issue (`ABC-123`, `#42`, `owner/repo#42`). The
+    /// issue title becomes the session title and the body is sent as the
+    /// first prompt. Providers are configured under `[issues]`.
+    #[arg(long = "issue")]
+    issue: Option<String>,
+
     /// Branch to base the new worktree branch on (use with --new-branch).
     /// Defaults to the repository's default branch. Useful for stacking
     /// work on top of an in-flight PR branch, hot-fixing a release
@@ -169,8 +177,41 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
         bail!("Path is not a directory: {}", path.display());
     }
 
-    if (!args.extra_repos.is_empty() || !args.projects.is_empty()) && args.worktree_branch.is_none()
-    {
+    // Scratch sessions have no project repo, so repo-scoped config
+    // overrides have nothing to anchor on. Resolving the repo-aware
+    // variant against the launch directory would silently pick up
+    // `.agent-of-empires/config.toml` from whatever folder the user
+    // happened to run `aoe add --scratch` in, which breaks the
+    // project-less contract. Fall back to the profile-only resolver.
+    let config = if args.scratch {
+        crate::session::profile_config::resolve_config_or_warn(profile)
+    } else {
+        repo_config::resolve_config_with_repo_or_warn(profile, &path)
+    };
+
+    // Fetch the issue before any side effects so a bad key or missing
+    // token leaves nothing behind.
+    let issue = match &args.issue {
+        Some(key) => {
+            let provider = IssueProvider::for_key(&config.issues, key, &path)?;
+            let issue = provider
+                .fetch(key)
+                .await
+                .with_context(|| format!("Failed to fetch issue {}", key.trim()))?;
+            println!("Issue {}: {}", issue.key, issue.title);
+            Some(issue)
+        }
+        None => None,
+    };
+
+    // `--issue -b` without `-w` names the new branch after the issue.
+    let worktree_branch = match (&args.worktree_branch, &issue) {
+        (Some(branch), _) => Some(branch.clone()),
+        (None, Some(issue)) if args.create_branch => Some(issue.branch_name()),
+        (None, _) => None,
+    };
+
+    if (!args.extra_repos.is_empty() || !args.projects.is_empty()) && worktree_branch.is_none() {
         bail!("--repo/--project requires --worktree to specify a branch\nTip: aoe add /path --project repoB -w branch-name");
     }
 
@@ -186,18 +227,6 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     all_extra_repos.extend(args.extra_repos.iter().cloned());
     all_extra_repos.extend(resolved_project_paths);
 
-    // Scratch sessions have no project repo, so repo-scoped config
-    // overrides have nothing to anchor on. Resolving the repo-aware
-    // variant against the launch directory would silently pick up
-    // `.agent-of-empires/config.toml` from whatever folder the user
-    // happened to run `aoe add --scratch` in, which breaks the
-    // project-less contract. Fall back to the profile-only resolver.
-    let config = if args.scratch {
-        crate::session::profile_config::resolve_config_or_warn(profile)
-    } else {
-        repo_config::resolve_config_with_repo_or_warn(profile, &path)
-    };
-
     // Preserve the original project path for hook trust checking.
     // `path` gets reassigned to the worktree/workspace directory below,
     // but hooks are defined in the original repo's `.agent-of-empires/config.toml`.
@@ -206,7 +235,7 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     let mut worktree_info_opt = None;
     let mut workspace_info_opt = None;
 
-    if let Some(branch_raw) = &args.worktree_branch {
+    if let Some(branch_raw) = &worktree_branch {
         use crate::git::GitWorktree;
         use crate::session::WorktreeInfo;
         use chrono::Utc;
@@ -364,7 +393,23 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
             return Ok(());
         }
         trimmed_title.to_string()
-    } else if let Some(ref branch) = args.worktree_branch {
+    } else if let Some(issue_title) = issue.as_ref().map(|i| i.session_title()) {
+        if is_duplicate_session(&instances, &issue_title, path.to_str().unwrap_or("")) {
+            println!(
+                "Session already exists with same title and path: {}",
+                issue_title
+            );
+            cleanup_partial_session(
+                &path,
+                worktree_info_opt.as_ref(),
+                workspace_info_opt.as_ref(),
+                args.create_branch,
+                None,
+            );
+            return Ok(());
+        }
+        issue_title
+    } else if let Some(ref branch) = worktree_branch {
         let branch_title = branch.trim().to_string();
         if is_duplicate_session(&instances, &branch_title, path.to_str().unwrap_or("")) {
             println!(
@@ -407,6 +452,8 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
         instance.parent_session_id = Some(parent);
     }
 
+    instance.issue = issue.as_ref().map(|i| i.link());
+
     if let Some(tool) = &args.tool {
         let selection = resolve_named_tool(tool, &config)?;
         if selection.is_custom() && args.cmd_override.is_some() {
@@ -755,6 +802,9 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     println!("  Path:    {}", path.display());
     println!("  Group:   {}", instance.group_path);
     println!("  ID:      {}", instance.id);
+    if let Some(issue) = &instance.issue {
+        println!("  Issue:   {}", issue.url.as_deref().unwrap_or(&issue.key));
+    }
     if let Some(cmd) = &args.command {
         println!("  Cmd:     {}", cmd);
     }
@@ -800,7 +850,20 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                  lifecycle is managed by `aoe serve`.)"
             );
         }
-    } else if args.launch {
+        if let Some(issue) = &issue {
+            // Reaches the worker only when `aoe serve` is already running.
+            match crate::scheduler::deliver(profile, &instance.id, &issue.prompt()).await {
+                Ok(()) => println!("✓ Sent issue {} to the agent", issue.key),
+                Err(e) => eprintln!(
+                    "Warning: could not send issue {} to the agent: {}. \
+                     Paste it from the dashboard once the worker is up.",
+                    issue.key, e
+                ),
+            }
+        }
+    } else if args.launch || issue.is_some() {
+        // `--issue` starts the session so the issue can be sent as its
+        // first prompt; only `--launch` attaches afterwards.
         // Persist Status::Error + last_error on launch failure rather than
         // cleanup_partial_session: row is committed; surface as broken.
         let id = instance.id.clone();
@@ -826,8 +889,42 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
                     );
                 }
 
-                let tmux_session = crate::tmux::Session::new(&instance.id, &instance.title)?;
-                tmux_session.attach()?;
+                if let Some(issue) = &issue {
+                    // Waiting for the agent polls tmux with sleeps, so keep
+                    // it off the async runtime.
+                    let (mut ready, send_profile, send_id, prompt) = (
+                        instance.clone(),
+                        profile.to_string(),
+                        id.clone(),
+                        issue.prompt(),
+                    );
+                    let sent = tokio::task::spawn_blocking(move || {
+                        crate::scheduler::wait_until_ready(&mut ready);
+                        super::send::send_message(&send_profile, &send_id, &prompt, false)
+                    })
+                    .await
+                    .context("issue delivery task panicked")
+                    .and_then(|r| r);
+                    match sent {
+                        Ok(_) => println!("✓ Sent issue {} to the agent", issue.key),
+                        Err(e) => eprintln!(
+                            "Warning: could not send issue {} to the agent: {}",
+                            issue.key, e
+                        ),
+                    }
+                }
+
+                if args.launch {
+                    let tmux_session = crate::tmux::Session::new(&instance.id, &instance.title)?;
+                    tmux_session.attach()?;
+                } else {
+                    println!();
+                    println!("Next steps:");
+                    println!(
+                        "  aoe session attach {}   # Attach to the session",
+                        final_title
+                    );
+                }
             }
             Err(e) => {
                 if let Err(rollback_err) = storage.update(|all_instances, _groups| {
@@ -114,6 +114,63 @@ pub enum CockpitCommands {
         /// Cockpit session id.
         session: String,
     },
+    /// Rewind a cockpit session to just before one of its prompts:
+    /// restore the worktree's files from that turn's checkpoint and
+    /// start a fresh agent session primed with the earlier transcript.
+    /// Without a turn, list the session's checkpoints.
+    Rewind {
+        /// Cockpit session id.
+        session: String,
+        /// Turn number from the checkpoint list.
+        turn: Option<usize>,
+        /// Start the fresh session without sending the transcript recap.
+        #[arg(long)]
+        no_prime: bool,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Hand a cockpit session to a different agent in the same
+    /// worktree. The new agent's first prompt is a recap of the
+    /// transcript and the current plan, followed by the message.
+    Handoff {
+        /// Cockpit session id.
+        session: String,
+        /// Registry key of the agent to hand off to (see `aoe cockpit agents`).
+        agent: String,
+        /// Model override for the new agent.
+        #[arg(long)]
+        model: Option<String>,
+        /// What the new agent should do first. Defaults to the prompt
+        /// the previous agent never got to, or to carrying on.
+        #[arg(long, short)]
+        message: Option<String>,
+        /// Send only the message, without the transcript recap.
+        #[arg(long)]
+        no_prime: bool,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show turns, context, cost, and rate limits across all cockpit
+    /// sessions, grouped by provider, with reset times and an estimate
+    /// of the turns left before the next limit.
+    Usage {
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compact the cockpit transcript database and apply the
+    /// `history_*` retention settings, then report the space reclaimed.
+    /// `aoe serve` also runs this every few hours.
+    Gc {
+        /// Report what would be removed without changing anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
     /// Stream the cockpit broadcast for a session to stdout as JSON
     /// lines (one frame per line). Press Ctrl-C to stop.
     Tail {
@@ -160,6 +217,22 @@ pub async fn run(command: CockpitCommands) -> Result<()> {
             deny,
         } => approve(&session, &nonce, always, deny).await,
         CockpitCommands::Cancel { session } => cancel(&session).await,
+        CockpitCommands::Rewind {
+            session,
+            turn,
+            no_prime,
+            json,
+        } => rewind(&session, turn, !no_prime, json).await,
+        CockpitCommands::Handoff {
+            session,
+            agent,
+            model,
+            message,
+            no_prime,
+            json,
+        } => handoff(&session, agent, model, message, !no_prime, json).await,
+        CockpitCommands::Usage { json } => usage(json).await,
+        CockpitCommands::Gc { dry_run, json } => gc(dry_run, json).await,
         CockpitCommands::Tail { session, since } => tail(&session, since).await,
         CockpitCommands::Attach { session } => attach(&session).await,
     }
@@ -424,6 +497,8 @@ fn ps(json: bool) -> Result<()> {
                     "pid": r.pid,
                     "alive": worker_registry::is_record_live(r),
                     "agent": r.agent_name,
+                    "transport": r.agent_transport.as_str(),
+                    "agent_relaunches": r.agent_relaunches,
                     "socket": r.socket_path,
                     "cwd": r.cwd,
                     "started_at": r.started_at,
@@ -440,8 +515,8 @@ fn ps(json: bool) -> Result<()> {
         return Ok(());
     }
     println!(
-        "{:<24} {:<8} {:<14} {:<10} SOCKET",
-        "SESSION", "PID", "AGENT", "STATE"
+        "{:<24} {:<8} {:<14} {:<10} {:<9} SOCKET",
+        "SESSION", "PID", "AGENT", "STATE", "TRANSPORT"
     );
     for r in &records {
         let state = if !worker_registry::is_record_live(r) {
@@ -454,11 +529,12 @@ fn ps(json: bool) -> Result<()> {
             "attached"
         };
         println!(
-            "{:<24} {:<8} {:<14} {:<10} {}",
+            "{:<24} {:<8} {:<14} {:<10} {:<9} {}",
             truncate(&r.session_id, 24),
             r.pid,
             truncate(&r.agent_name, 14),
             state,
+            r.agent_transport.as_str(),
             r.socket_path.display()
         );
     }
@@ -658,7 +734,8 @@ fn truncate(s: &str, n: usize) -> String {
 // useful read against "no daemon".
 
 use crate::cockpit::client::{require_daemon, HttpClient, HttpError, WsMessage};
-use crate::cockpit::protocol::ApprovalDecisionWire;
+use crate::cockpit::protocol::{ApprovalDecisionWire, HandoffRequest};
+use crate::cockpit::{retention, usage};
 
 async fn history(session: &str, since: u64, json: bool) -> Result<()> {
     let endpoint = require_daemon().await?;
@@ -762,6 +839,206 @@ async fn cancel(session: &str) -> Result<()> {
     Ok(())
 }
 
+async fn rewind(session: &str, turn: Option<usize>, prime: bool, json: bool) -> Result<()> {
+    let endpoint = require_daemon().await?;
+    let client = HttpClient::new(endpoint)?;
+    let Some(turn) = turn else {
+        let checkpoints = client.checkpoints(session).await.map_err(map_http)?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&checkpoints)?);
+        } else if checkpoints.is_empty() {
+            println!("(no checkpoints; the session's worktree may not be a git repository)");
+        } else {
+            for c in &checkpoints {
+                println!(
+                    "{:>4}  {}  seq {:>6}  {}",
+                    c.turn,
+                    c.created_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    c.seq,
+                    truncate(&c.prompt, 60)
+                );
+            }
+        }
+        return Ok(());
+    };
+    let resp = client
+        .rewind(session, turn, prime)
+        .await
+        .map_err(map_http)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&resp)?);
+        return Ok(());
+    }
+    println!(
+        "rewound to before turn {}; files restored{}",
+        resp.turn,
+        if resp.primed {
+            ", recap sent to a fresh agent session"
+        } else {
+            ", fresh agent session started"
+        }
+    );
+    println!("prompt was: {}", truncate(&resp.prompt, 200));
+    Ok(())
+}
+
+async fn handoff(
+    session: &str,
+    target: String,
+    model: Option<String>,
+    message: Option<String>,
+    prime: bool,
+    json: bool,
+) -> Result<()> {
+    let endpoint = require_daemon().await?;
+    let client = HttpClient::new(endpoint)?;
+    let resp = client
+        .handoff(
+            session,
+            &HandoffRequest {
+                target,
+                model,
+                message,
+                prime,
+            },
+        )
+        .await
+        .map_err(map_http)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&resp)?);
+        return Ok(());
+    }
+    println!("handed off from {} to {}", resp.from, resp.agent);
+    if !resp.prompted {
+        println!("no prompt sent; the new agent is waiting for one");
+    } else if resp.included_turns > 0 {
+        println!(
+            "recap of {} turn{} sent as the first prompt",
+            resp.included_turns,
+            if resp.included_turns == 1 { "" } else { "s" }
+        );
+    } else {
+        println!("message sent without a recap");
+    }
+    Ok(())
+}
+
+async fn usage(json: bool) -> Result<()> {
+    let endpoint = require_daemon().await?;
+    let client = HttpClient::new(endpoint)?;
+    let report = client.usage().await.map_err(map_http)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if report.providers.is_empty() {
+        println!("(no cockpit turns recorded yet)");
+        return Ok(());
+    }
+    let now = report.generated_at;
+    for (i, provider) in report.providers.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{} ({})  {}",
+            provider.provider,
+            provider.agents.join(", "),
+            provider.headline(report.window_hours, now)
+        );
+        if let Some(status) = &provider.limit_status {
+            println!("  {}", truncate(status, 100));
+        }
+        for s in &provider.sessions {
+            let context = match (s.context_used, s.context_size) {
+                (Some(used), Some(size)) => usage::format_context(used, size),
+                _ => "-".into(),
+            };
+            let cost = match (s.cost, &s.currency) {
+                (Some(amount), Some(currency)) => format!("{amount:.2} {currency}"),
+                _ => "-".into(),
+            };
+            let last = match (s.limited_until, s.last_turn_at) {
+                (Some(until), _) => {
+                    format!("limited until {}", usage::format_local_time(until, now))
+                }
+                (None, Some(at)) => format!("{} ago", usage::format_span(now - at)),
+                (None, None) => "-".into(),
+            };
+            println!(
+                "  {:<24} {:<10} {:>3} turns  {:>11}  {:>10}  {}",
+                truncate(s.title.as_deref().unwrap_or(&s.session_id), 24),
+                s.agent,
+                s.window_turns,
+                context,
+                cost,
+                last
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn gc(dry_run: bool, json: bool) -> Result<()> {
+    let db_path =
+        crate::session::get_app_dir()?.join(crate::session::transcript::COCKPIT_EVENTS_DB);
+    if !db_path.exists() {
+        println!("(no cockpit transcripts recorded yet)");
+        return Ok(());
+    }
+    // A running `aoe serve` holds the database; let it run the pass on its
+    // own connection instead of contending with its writes.
+    let report = match crate::cockpit::client::discover_local() {
+        Ok(endpoint) => HttpClient::new(endpoint)?
+            .gc(dry_run)
+            .await
+            .map_err(map_http)?,
+        Err(_) => {
+            tokio::task::spawn_blocking(move || {
+                let store = crate::cockpit::event_store::EventStore::open(&db_path, 0)?;
+                retention::run(&store, dry_run)
+            })
+            .await??
+        }
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    let verb = if dry_run { "would remove" } else { "removed" };
+    println!("compacted tool-call updates: {}", report.compacted_events);
+    println!("expired (max age):           {}", report.expired_events);
+    println!("over size budget:            {}", report.over_budget_events);
+    println!(
+        "orphaned sessions:           {} ({} events)",
+        report.orphaned_sessions, report.orphaned_events
+    );
+    if report.events_removed() == 0 {
+        println!(
+            "nothing to remove; database is {}",
+            retention::format_bytes(report.db_bytes_before)
+        );
+    } else if dry_run {
+        println!(
+            "{verb} {} events, about {} of {}",
+            report.events_removed(),
+            retention::format_bytes(report.bytes_reclaimed()),
+            retention::format_bytes(report.db_bytes_before)
+        );
+    } else {
+        println!(
+            "{verb} {} events, reclaimed {} ({} -> {})",
+            report.events_removed(),
+            retention::format_bytes(report.bytes_reclaimed()),
+            retention::format_bytes(report.db_bytes_before),
+            retention::format_bytes(report.db_bytes_after)
+        );
+    }
+    Ok(())
+}
+
 async fn attach(session: &str) -> Result<()> {
     crate::tui::cockpit_view::run_standalone(session).await
 }
@@ -838,6 +1115,7 @@ fn event_kind(event: &crate::cockpit::Event) -> &'static str {
         Event::WakeupScheduled { .. } => "wakeup_scheduled",
         Event::PromptRejected { .. } => "prompt_rejected",
         Event::AgentSwitched { .. } => "agent_switched",
+        Event::Rewound { .. } => "rewound",
     }
 }
 
@@ -15,9 +15,12 @@ use super::list::ListArgs;
 #[cfg(feature = "serve")]
 use super::log_level::LogLevelArgs;
 use super::logs::LogsArgs;
+use super::mcp::McpArgs;
 use super::profile::ProfileCommands;
 use super::project::ProjectCommands;
 use super::remove::RemoveArgs;
+use super::schedule::ScheduleCommands;
+use super::search::SearchArgs;
 use super::send::SendArgs;
 #[cfg(feature = "serve")]
 use super::serve::ServeArgs;
@@ -30,6 +33,7 @@ use super::uninstall::UninstallArgs;
 use super::update::UpdateArgs;
 #[cfg(feature = "serve")]
 use super::url::UrlArgs;
+use super::view::ViewCommands;
 use super::worktree::WorktreeCommands;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -92,6 +96,17 @@ pub enum Commands {
     /// Send a message to a running agent session
     Send(SendArgs),
 
+    /// Full-text search across session transcripts in all profiles
+    /// (cockpit event logs and the agents' own conversation logs)
+    Search(SearchArgs),
+
+    /// Run an MCP server on stdio that exposes sessions as tools
+    /// (list, create, send, capture, wait, diff, result, merge). Point an
+    /// agent's MCP config at `aoe mcp` to let it spawn and coordinate
+    /// worker sessions. Mutating tools are limited by
+    /// `mcp.allowed_mutations`.
+    Mcp(McpArgs),
+
     /// Show session status summary
     Status(StatusArgs),
 
@@ -107,6 +122,12 @@ pub enum Commands {
         command: GroupCommands,
     },
 
+    /// Manage saved views (named filter queries for the session list)
+    View {
+        #[command(subcommand)]
+        command: ViewCommands,
+    },
+
     /// Manage profiles (separate workspaces)
     Profile {
         #[command(subcommand)]
@@ -119,6 +140,17 @@ pub enum Commands {
         command: ProjectCommands,
     },
 
+    /// Manage scheduled prompts: send a prompt to a session at a set time
+    /// or on a cron expression. `aoe serve` or `aoe scheduler` runs them.
+    Schedule {
+        #[command(subcommand)]
+        command: ScheduleCommands,
+    },
+
+    /// Run scheduled prompts in the foreground, for hosts without
+    /// `aoe serve`. Covers every profile.
+    Scheduler,
+
     /// Manage git worktrees for parallel development
     Worktree {
         #[command(subcommand)]
@@ -165,6 +197,12 @@ pub enum Commands {
     #[command(name = "__cockpit-runner", hide = true)]
     CockpitRunner(Box<crate::cockpit::runner::CockpitRunnerArgs>),
 
+    /// Internal: asciicast sink that `tmux pipe-pane` feeds an agent
+    /// pane's output into when `recording.enabled` is set. Hidden from
+    /// help.
+    #[command(name = "__record-pane", hide = true)]
+    RecordPane(crate::session::recording::RecordPaneArgs),
+
     /// Uninstall Agent of Empires
     Uninstall(UninstallArgs),
 
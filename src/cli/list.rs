@@ -4,6 +4,7 @@ use anyhow::Result;
 use clap::Args;
 use serde::Serialize;
 
+use crate::session::filter::Filter;
 use crate::session::{Instance, Storage};
 
 const TABLE_COL_TITLE: usize = 20;
@@ -20,6 +21,11 @@ pub struct ListArgs {
     /// List sessions from all profiles
     #[arg(long)]
     all: bool,
+
+    /// Only list sessions matching a filter query, e.g.
+    /// "status:waiting tool:claude group:payments age>1h" or "@view"
+    #[arg(long, value_name = "QUERY")]
+    filter: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -114,17 +120,43 @@ fn print_table_row(inst: &Instance) {
     );
 }
 
+/// Load a profile's sessions, keeping those `filter` matches.
+fn load_instances(storage: &Storage, filter: &Filter) -> Result<Vec<Instance>> {
+    let (mut instances, _) = storage.load_with_groups()?;
+    for inst in &mut instances {
+        inst.source_profile = storage.profile().to_string();
+    }
+    instances.retain(|inst| filter.matches(inst));
+    Ok(instances)
+}
+
 #[tracing::instrument(target = "cli.list", skip_all, fields(profile = %profile))]
 pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
+    let filter = match &args.filter {
+        Some(query) => {
+            let views = crate::session::resolve_config_or_warn(profile).views;
+            Filter::parse_with_views(query, &views)
+                .map_err(|e| anyhow::anyhow!("Invalid --filter: {e}"))?
+        }
+        None => Filter::default(),
+    };
+
     if args.all {
-        return run_all_profiles(args.json).await;
+        return run_all_profiles(args.json, &filter).await;
     }
 
     let storage = Storage::new(profile)?;
-    let (instances, _) = storage.load_with_groups()?;
+    let instances = load_instances(&storage, &filter)?;
 
     if instances.is_empty() {
-        println!("No sessions found in profile '{}'.", storage.profile());
+        if filter.is_empty() {
+            println!("No sessions found in profile '{}'.", storage.profile());
+        } else {
+            println!(
+                "No sessions in profile '{}' match the filter.",
+                storage.profile()
+            );
+        }
         return Ok(());
     }
 
@@ -160,7 +192,7 @@ pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
     Ok(())
 }
 
-async fn run_all_profiles(json: bool) -> Result<()> {
+async fn run_all_profiles(json: bool, filter: &Filter) -> Result<()> {
     let profiles = crate::session::list_profiles()?;
 
     if profiles.is_empty() {
@@ -172,7 +204,7 @@ async fn run_all_profiles(json: bool) -> Result<()> {
         let mut all_sessions: Vec<SessionJson> = Vec::new();
         for profile_name in &profiles {
             if let Ok(storage) = Storage::new(profile_name) {
-                if let Ok((instances, _)) = storage.load_with_groups() {
+                if let Ok(instances) = load_instances(&storage, filter) {
                     for inst in instances {
                         let workspace_repos = workspace_repos_for(&inst);
                         let worktree = worktree_for(&inst);
@@ -199,7 +231,7 @@ async fn run_all_profiles(json: bool) -> Result<()> {
     let mut total_sessions = 0;
     for profile_name in &profiles {
         if let Ok(storage) = Storage::new(profile_name) {
-            if let Ok((instances, _)) = storage.load_with_groups() {
+            if let Ok(instances) = load_instances(&storage, filter) {
                 if instances.is_empty() {
                     continue;
                 }
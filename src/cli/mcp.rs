@@ -0,0 +1,90 @@
+//! `agent-of-empires mcp` command implementation
+
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::mcp::McpServer;
+use crate::session::{Config, Storage};
+
+#[derive(Args)]
+pub struct McpArgs {
+    /// Coordinator mode: act for this session, which can then create,
+    /// drive, and merge its own sub-sessions and sees no others. Without
+    /// a value, uses the session the agent runs in ($AOE_INSTANCE_ID).
+    #[arg(long, value_name = "SESSION", num_args = 0..=1, default_missing_value = "")]
+    coordinator: Option<String>,
+}
+
+/// Serve MCP over stdio until stdin closes. Messages are newline-delimited
+/// JSON-RPC. Each request runs on its own task so a long `wait_for_status`
+/// does not stall pings or other calls; replies are written as they finish.
+#[tracing::instrument(target = "mcp", skip_all, fields(profile = %profile))]
+pub async fn run(profile: &str, args: McpArgs) -> Result<()> {
+    let config = Config::load_or_warn();
+    let mut server = McpServer::new(profile, &config.mcp);
+    if let Some(identifier) = args.coordinator {
+        let id = resolve_coordinator(profile, &identifier)?;
+        tracing::info!(target: "mcp", coordinator = %id, "serving in coordinator mode");
+        server = server.coordinator(&id);
+    }
+    let server = Arc::new(server);
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(line) = rx.recv().await {
+            if stdout.write_all(line.as_bytes()).await.is_err()
+                || stdout.write_all(b"\n").await.is_err()
+                || stdout.flush().await.is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let server = Arc::clone(&server);
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Some(reply) = server.handle_text(&line).await {
+                let _ = tx.send(reply.to_string());
+            }
+        });
+    }
+
+    // stdin closed. Requests may still be in flight (a piped
+    // `echo ... | aoe mcp` closes stdin immediately), so drop our sender
+    // and let the writer drain their replies before exiting.
+    drop(tx);
+    let _ = writer.await;
+    Ok(())
+}
+
+/// Resolve `--coordinator` to a session id, falling back to the session
+/// aoe launched this agent in.
+fn resolve_coordinator(profile: &str, identifier: &str) -> Result<String> {
+    let identifier = match identifier.trim() {
+        "" => std::env::var(crate::tmux::env::AOE_INSTANCE_ID_KEY)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .context("--coordinator needs a session when not run inside an aoe session")?,
+        id => id.to_string(),
+    };
+    let (instances, _) = Storage::new(profile)?.load_with_groups()?;
+    let inst = super::resolve_session(&identifier, &instances)
+        .with_context(|| format!("coordinator session not found in profile '{profile}'"))?;
+    if inst.is_sub_session() {
+        bail!(
+            "'{}' is a sub-session and cannot coordinate (single level only)",
+            inst.title
+        );
+    }
+    Ok(inst.id.clone())
+}
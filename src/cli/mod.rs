@@ -11,10 +11,13 @@ pub mod list;
 #[cfg(feature = "serve")]
 pub mod log_level;
 pub mod logs;
+pub mod mcp;
 pub mod output;
 pub mod profile;
 pub mod project;
 pub mod remove;
+pub mod schedule;
+pub mod search;
 pub mod send;
 #[cfg(feature = "serve")]
 pub mod serve;
@@ -27,6 +30,7 @@ pub mod uninstall;
 pub mod update;
 #[cfg(feature = "serve")]
 pub mod url;
+pub mod view;
 pub mod worktree;
 
 pub use definition::{Cli, Commands};
@@ -0,0 +1,327 @@
+//! `aoe schedule` subcommands (manage scheduled prompts) and
+//! `aoe scheduler` (run them without `aoe serve`).
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use clap::{Args, Subcommand, ValueHint};
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::session::schedules::{self, JobTarget, ScheduledJob, SessionTemplate, Trigger};
+use crate::session::Storage;
+
+#[derive(Subcommand)]
+pub enum ScheduleCommands {
+    /// List scheduled prompts with their next and last runs
+    #[command(alias = "ls")]
+    List(ScheduleListArgs),
+
+    /// Schedule a prompt for an existing session or a fresh one
+    Add(Box<ScheduleAddArgs>),
+
+    /// Delete a scheduled prompt
+    #[command(alias = "rm")]
+    Remove(ScheduleIdArgs),
+
+    /// Resume a paused scheduled prompt
+    Enable(ScheduleIdArgs),
+
+    /// Pause a scheduled prompt without deleting it
+    Disable(ScheduleIdArgs),
+
+    /// Run a scheduled prompt now, regardless of its schedule
+    Run(ScheduleIdArgs),
+}
+
+#[derive(Args)]
+pub struct ScheduleListArgs {
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct ScheduleAddArgs {
+    /// Name for the schedule (unique within the profile)
+    name: String,
+
+    /// Prompt to send when the schedule fires
+    #[arg(short = 'm', long)]
+    prompt: String,
+
+    /// Cron expression in local time, e.g. "0 7 * * 1-5" for weekdays at 7am
+    #[arg(long, conflicts_with = "at", required_unless_present = "at")]
+    cron: Option<String>,
+
+    /// Run once at this local time: "HH:MM", "YYYY-MM-DD HH:MM", or RFC 3339
+    #[arg(long)]
+    at: Option<String>,
+
+    /// Existing session (ID, ID prefix, or title) to send the prompt to
+    #[arg(long, conflicts_with = "path", required_unless_present = "path")]
+    session: Option<String>,
+
+    /// Create a fresh session in this directory on every run instead
+    #[arg(long, value_hint = ValueHint::DirPath)]
+    path: Option<PathBuf>,
+
+    /// Agent for the fresh session (defaults to the profile's default agent)
+    #[arg(long, requires = "path")]
+    tool: Option<String>,
+
+    /// Title for the fresh session (defaults to the schedule name)
+    #[arg(long, requires = "path")]
+    title: Option<String>,
+
+    /// Group for the fresh session
+    #[arg(long, requires = "path")]
+    group: Option<String>,
+
+    /// Give each fresh session its own worktree on `<PREFIX>-<timestamp>`
+    #[arg(long, value_name = "PREFIX", requires = "path")]
+    worktree_branch: Option<String>,
+
+    /// Run the fresh session in YOLO mode
+    #[arg(long, requires = "path")]
+    yolo: bool,
+}
+
+#[derive(Args)]
+pub struct ScheduleIdArgs {
+    /// Schedule name, ID, or ID prefix
+    identifier: String,
+}
+
+#[derive(Serialize)]
+struct ScheduleInfo<'a> {
+    #[serde(flatten)]
+    job: &'a ScheduledJob,
+    next_run: Option<DateTime<Utc>>,
+}
+
+#[tracing::instrument(target = "cli.schedule", skip_all, fields(profile = %profile))]
+pub async fn run(profile: &str, command: ScheduleCommands) -> Result<()> {
+    match command {
+        ScheduleCommands::List(args) => list(profile, args),
+        ScheduleCommands::Add(args) => add(profile, *args),
+        ScheduleCommands::Remove(args) => {
+            let removed = schedules::remove(profile, &args.identifier)?;
+            println!("✓ Removed schedule '{}'", removed.name);
+            Ok(())
+        }
+        ScheduleCommands::Enable(args) => set_enabled(profile, &args.identifier, true),
+        ScheduleCommands::Disable(args) => set_enabled(profile, &args.identifier, false),
+        ScheduleCommands::Run(args) => run_now(profile, &args.identifier).await,
+    }
+}
+
+fn list(profile: &str, args: ScheduleListArgs) -> Result<()> {
+    let jobs = schedules::load(profile)?;
+    if args.json {
+        let info: Vec<ScheduleInfo> = jobs
+            .iter()
+            .map(|job| ScheduleInfo {
+                job,
+                next_run: job.next_run().filter(|_| job.enabled),
+            })
+            .collect();
+        return super::output::print_json(&info);
+    }
+
+    if jobs.is_empty() {
+        println!("No scheduled prompts.");
+        println!("Add one with: aoe schedule add <name> --cron \"0 7 * * 1-5\" --session <session> -m <prompt>");
+        return Ok(());
+    }
+
+    println!("Scheduled prompts:\n");
+    for job in &jobs {
+        let next = match (job.enabled, job.next_run()) {
+            (false, _) => "paused".to_string(),
+            (true, Some(t)) => format!("next {}", format_local(t)),
+            (true, None) => "done".to_string(),
+        };
+        println!(
+            "  • {} [{}]  {}  →  {}  ({})",
+            job.name,
+            job.id,
+            job.trigger.describe(),
+            job.target_label(),
+            next
+        );
+        if let Some(run) = &job.last_run {
+            let outcome = if run.missed {
+                "missed (no scheduler running)".to_string()
+            } else if let Some(err) = &run.error {
+                format!("failed: {err}")
+            } else {
+                "ok".to_string()
+            };
+            println!("      last {}: {}", format_local(run.at), outcome);
+        }
+    }
+    if !crate::scheduler::is_running() {
+        println!("\nNo scheduler is running; start `aoe serve` or `aoe scheduler` to fire these.");
+    }
+    Ok(())
+}
+
+fn format_local(t: DateTime<Utc>) -> String {
+    t.with_timezone(&Local).format("%F %R").to_string()
+}
+
+fn add(profile: &str, args: ScheduleAddArgs) -> Result<()> {
+    if args.prompt.trim().is_empty() {
+        bail!("Prompt cannot be empty");
+    }
+    let trigger = match (&args.cron, &args.at) {
+        (Some(expr), _) => Trigger::cron(expr)?,
+        (None, Some(at)) => Trigger::At {
+            at: parse_at(at, Local::now())?,
+        },
+        (None, None) => bail!("Pass --cron or --at"),
+    };
+
+    let target = match (args.session, args.path) {
+        (Some(identifier), _) => {
+            let (instances, _) = Storage::new(profile)?.load_with_groups()?;
+            let inst = super::resolve_session(&identifier, &instances)?;
+            JobTarget::Session {
+                session_id: inst.id.clone(),
+            }
+        }
+        (None, Some(path)) => {
+            let path = path
+                .canonicalize()
+                .with_context(|| format!("Path does not exist: {}", path.display()))?;
+            JobTarget::Template(SessionTemplate {
+                path: path.to_string_lossy().to_string(),
+                tool: args.tool,
+                title: args.title,
+                group: args.group,
+                worktree_branch: args.worktree_branch,
+                yolo_mode: args.yolo.then_some(true),
+            })
+        }
+        (None, None) => bail!("Pass --session or --path"),
+    };
+
+    let job = schedules::add(
+        profile,
+        ScheduledJob::new(&args.name, trigger, target, &args.prompt),
+    )?;
+    println!(
+        "✓ Scheduled '{}' ({}) {}",
+        job.name,
+        job.id,
+        job.trigger.describe()
+    );
+    if let Some(next) = job.next_run() {
+        println!("  Next run: {}", format_local(next));
+    }
+    Ok(())
+}
+
+/// Parse `--at`: `HH:MM` (the next such time), `YYYY-MM-DD HH:MM` in
+/// local time, or an RFC 3339 timestamp.
+fn parse_at(value: &str, now: DateTime<Local>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(value) {
+        return Ok(t.with_timezone(&Utc));
+    }
+    let local = if let Ok(t) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M") {
+        t
+    } else if let Ok(time) = NaiveTime::parse_from_str(value, "%H:%M") {
+        let today: NaiveDate = now.date_naive();
+        let candidate = today.and_time(time);
+        if candidate > now.naive_local() {
+            candidate
+        } else {
+            candidate + chrono::Duration::days(1)
+        }
+    } else {
+        bail!("Unrecognized time '{value}'; use HH:MM, \"YYYY-MM-DD HH:MM\", or RFC 3339");
+    };
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+        .with_context(|| format!("'{value}' does not exist in the local time zone"))
+}
+
+fn set_enabled(profile: &str, identifier: &str, enabled: bool) -> Result<()> {
+    let name = schedules::update(profile, |jobs| {
+        let idx = schedules::resolve(identifier, jobs)?;
+        jobs[idx].enabled = enabled;
+        Ok(jobs[idx].name.clone())
+    })?;
+    println!(
+        "✓ {} schedule '{}'",
+        if enabled { "Enabled" } else { "Paused" },
+        name
+    );
+    Ok(())
+}
+
+async fn run_now(profile: &str, identifier: &str) -> Result<()> {
+    let jobs = schedules::load(profile)?;
+    let job = jobs[schedules::resolve(identifier, &jobs)?].clone();
+    let run = crate::scheduler::run_now(profile, &job).await?;
+    if let Some(err) = run.error {
+        bail!("Schedule '{}' failed: {}", job.name, err);
+    }
+    println!(
+        "✓ Ran schedule '{}'{}",
+        job.name,
+        run.session_id
+            .map(|id| format!(" in session {id}"))
+            .unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// `aoe scheduler`: run due prompts in the foreground until Ctrl-C.
+#[tracing::instrument(target = "cli.schedule", skip_all)]
+pub async fn run_scheduler() -> Result<()> {
+    if crate::scheduler::is_running() {
+        println!("Another scheduler is running (`aoe serve` or `aoe scheduler`); standing by until it exits.");
+    }
+    let shutdown = tokio_util::sync::CancellationToken::new();
+    let handle = tokio::spawn(crate::scheduler::run(shutdown.clone()));
+    println!("Scheduler running for all profiles. Press Ctrl-C to stop.");
+    tokio::signal::ctrl_c().await?;
+    shutdown.cancel();
+    let _ = handle.await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn parse_at_accepts_clock_times_dates_and_rfc3339() {
+        let now = Local.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+
+        let later_today = parse_at("18:30", now).unwrap().with_timezone(&Local);
+        assert_eq!(later_today.date_naive(), now.date_naive());
+        assert_eq!((later_today.hour(), later_today.minute()), (18, 30));
+
+        let tomorrow = parse_at("07:00", now).unwrap().with_timezone(&Local);
+        assert_eq!(
+            tomorrow.date_naive(),
+            now.date_naive() + chrono::Duration::days(1)
+        );
+
+        let dated = parse_at("2026-04-01 09:15", now)
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!(dated.format("%F %R").to_string(), "2026-04-01 09:15");
+
+        let rfc = parse_at("2026-04-01T09:15:00Z", now).unwrap();
+        assert_eq!(rfc.to_rfc3339(), "2026-04-01T09:15:00+00:00");
+
+        assert!(parse_at("tomorrow", now).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! `agent-of-empires search` command implementation
+
+use std::io::IsTerminal;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::session::search::{parse_since, search_all_profiles, SearchHit, SearchQuery};
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Words to find. Use "quotes" for a phrase and a trailing * for a
+    /// prefix; every term must match.
+    #[arg(required = true)]
+    query: Vec<String>,
+
+    /// Only search sessions running this agent (claude, codex, ...)
+    #[arg(long)]
+    tool: Option<String>,
+
+    /// Only match messages from this recent window (30m, 12h, 7d, 2w) or
+    /// after a date (2026-01-31)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Maximum number of matches to print
+    #[arg(long, default_value_t = crate::session::search::DEFAULT_LIMIT)]
+    limit: usize,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[tracing::instrument(target = "cli.session", skip_all)]
+pub async fn run(args: SearchArgs) -> Result<()> {
+    let query = SearchQuery {
+        text: args.query.join(" "),
+        tool: args.tool,
+        since: args
+            .since
+            .as_deref()
+            .map(|s| parse_since(s, chrono::Utc::now()))
+            .transpose()?,
+        limit: args.limit,
+    };
+    let hits = tokio::task::spawn_blocking(move || search_all_profiles(&query)).await??;
+
+    if args.json {
+        return super::output::print_json(&hits);
+    }
+    if hits.is_empty() {
+        println!("No matches.");
+        return Ok(());
+    }
+    let color = std::io::stdout().is_terminal();
+    for hit in &hits {
+        print_hit(hit, color);
+    }
+    Ok(())
+}
+
+fn print_hit(hit: &SearchHit, color: bool) {
+    let when = hit
+        .timestamp
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    println!(
+        "{}  [{}] {} · {} · {} {}",
+        hit.title,
+        super::truncate_id(&hit.session_id, 8),
+        hit.profile,
+        hit.tool,
+        hit.kind.as_str(),
+        when
+    );
+    println!("    {}\n", render_snippet(hit, color));
+}
+
+/// Bold the matched terms when writing to a terminal.
+fn render_snippet(hit: &SearchHit, color: bool) -> String {
+    if !color {
+        return hit.snippet.clone();
+    }
+    let mut out = String::new();
+    let mut pos = 0;
+    for &[start, end] in &hit.highlights {
+        if start < pos || end > hit.snippet.len() {
+            continue;
+        }
+        out.push_str(&hit.snippet[pos..start]);
+        out.push_str("\x1b[1m");
+        out.push_str(&hit.snippet[start..end]);
+        out.push_str("\x1b[0m");
+        pos = end;
+    }
+    out.push_str(&hit.snippet[pos..]);
+    out
+}
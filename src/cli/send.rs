@@ -23,14 +23,41 @@ pub struct SendArgs {
 
 #[tracing::instrument(target = "cli.send", skip_all, fields(profile = %profile))]
 pub async fn run(profile: &str, args: SendArgs) -> Result<()> {
+    let sent = send_message(profile, &args.identifier, &args.message, !args.no_revive)?;
+    if let Some(note) = &sent.revive_note {
+        eprintln!("  {}", note);
+    }
+    println!("Sent message to '{}'", sent.title);
+    Ok(())
+}
+
+/// Outcome of a successful [`send_message`].
+#[derive(Debug)]
+pub struct SentMessage {
+    pub session_id: String,
+    pub title: String,
+    /// Human-readable note when the pane had to be respawned or started
+    /// before delivery, e.g. `(respawned dead pane before send)`.
+    pub revive_note: Option<String>,
+}
+
+/// Deliver `message` to a session's agent pane. Shared by `aoe send` and
+/// the MCP `send_message` tool so both surfaces revive, deliver, and
+/// remap status the same way.
+pub fn send_message(
+    profile: &str,
+    identifier: &str,
+    message: &str,
+    revive: bool,
+) -> Result<SentMessage> {
     let storage = Storage::new(profile)?;
     let (mut instances, _) = storage.load_with_groups()?;
 
-    if args.message.trim().is_empty() {
+    if message.trim().is_empty() {
         bail!("Message cannot be empty");
     }
 
-    let inst = super::resolve_session(&args.identifier, &instances)?;
+    let inst = super::resolve_session(identifier, &instances)?;
     let session_id = inst.id.clone();
     let session_title = inst.title.clone();
     let tool = inst.tool.clone();
@@ -38,32 +65,25 @@ pub async fn run(profile: &str, args: SendArgs) -> Result<()> {
     // Revive the pane if needed before delivering keystrokes. Without this,
     // a send to a dead pane silently writes to a corpse with no agent to
     // respond to it.
-    if !args.no_revive {
+    let mut revive_note = None;
+    if revive {
         if let Some(target) = instances.iter_mut().find(|i| i.id == session_id) {
-            match target.ensure_pane_ready() {
-                Ok(EnsureReadyOutcome::Respawned { stale_sid: None }) => {
-                    eprintln!("  (respawned dead pane before send)");
-                }
-                Ok(EnsureReadyOutcome::Respawned {
-                    stale_sid: Some(sid),
-                }) => {
-                    eprintln!(
-                        "  (respawned dead pane before send){}",
-                        stale_history_suffix(&sid),
-                    );
-                }
-                Ok(EnsureReadyOutcome::Started { stale_sid: None }) => {
-                    eprintln!("  (started stopped session before send)");
-                }
-                Ok(EnsureReadyOutcome::Started {
-                    stale_sid: Some(sid),
-                }) => {
-                    eprintln!(
-                        "  (started stopped session before send){}",
-                        stale_history_suffix(&sid),
-                    );
-                }
-                Ok(EnsureReadyOutcome::AlreadyAlive) => {}
+            revive_note = match target.ensure_pane_ready() {
+                Ok(EnsureReadyOutcome::Respawned { stale_sid }) => Some(format!(
+                    "(respawned dead pane before send){}",
+                    stale_sid
+                        .as_deref()
+                        .map(stale_history_suffix)
+                        .unwrap_or_default(),
+                )),
+                Ok(EnsureReadyOutcome::Started { stale_sid }) => Some(format!(
+                    "(started stopped session before send){}",
+                    stale_sid
+                        .as_deref()
+                        .map(stale_history_suffix)
+                        .unwrap_or_default(),
+                )),
+                Ok(EnsureReadyOutcome::AlreadyAlive) => None,
                 Err(EnsureReadyError::Transient(status)) => {
                     bail!("Session is mid-lifecycle ({status:?}); cannot send right now")
                 }
@@ -71,7 +91,7 @@ pub async fn run(profile: &str, args: SendArgs) -> Result<()> {
                     bail!("Cockpit-mode sessions have no tmux pane; send is not supported")
                 }
                 Err(EnsureReadyError::Tmux(e)) => bail!("{}", e),
-            }
+            };
         }
     }
 
@@ -79,12 +99,12 @@ pub async fn run(profile: &str, args: SendArgs) -> Result<()> {
     if !tmux_session.exists() {
         bail!(
             "Session is not running. Start it first with: aoe session start {}",
-            args.identifier
+            identifier
         );
     }
 
     let delay = crate::agents::send_keys_enter_delay(&tool);
-    tmux_session.send_keys_with_delay(&args.message, delay)?;
+    tmux_session.send_keys_with_delay(message, delay)?;
 
     // Stamp last_accessed_at so the "last activity" column reflects user
     // interaction, and remap the status to Running. The agent has just been
@@ -113,6 +133,9 @@ pub async fn run(profile: &str, args: SendArgs) -> Result<()> {
         );
     }
 
-    println!("Sent message to '{}'", session_title);
-    Ok(())
+    Ok(SentMessage {
+        session_id,
+        title: session_title,
+        revive_note,
+    })
 }
@@ -1,10 +1,11 @@
 //! `agent-of-empires session` subcommands implementation
 
 use anyhow::{bail, Result};
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum, ValueHint};
 use serde::Serialize;
 use std::collections::HashSet;
 
+use crate::session::tags::{self, TagEdit};
 use crate::session::{GroupTree, StartOutcome, Storage};
 
 /// Wording used by both single-session and `--all` restart paths when the
@@ -37,6 +38,13 @@ pub enum SessionCommands {
     /// Capture tmux pane output
     Capture(CaptureArgs),
 
+    /// Play back a terminal recording of a session (see `recording.enabled`)
+    Replay(ReplayArgs),
+
+    /// Export a session's transcript, metadata, and diff as a
+    /// self-contained Markdown, HTML, or JSON report
+    Export(ExportArgs),
+
     /// Auto-detect current session
     Current(CurrentArgs),
 
@@ -73,6 +81,21 @@ pub enum SessionCommands {
 
     /// Unarchive a session (restores it to its tier in the Attention sort)
     Unarchive(SessionIdArgs),
+
+    /// Add tags to a session. A `key=value` label replaces any existing
+    /// label with the same key.
+    Tag(TagArgs),
+
+    /// Remove tags from a session. A bare key removes the label with that
+    /// key (`reviewer` removes `reviewer=alice`).
+    Untag(TagArgs),
+
+    /// Print, replace, append to, or clear a session's markdown notes
+    Notes(NotesArgs),
+
+    /// Show a session's status transitions and how long it spent working
+    /// and waiting on you
+    History(HistoryArgs),
 }
 
 #[derive(Args)]
@@ -98,6 +121,44 @@ pub struct ArchiveArgs {
     pub no_kill: bool,
 }
 
+#[derive(Args)]
+pub struct TagArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Tags, e.g. `blocked-ci` or `reviewer=alice`
+    #[arg(required = true)]
+    tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct NotesArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Replace the notes with this text (`-` reads stdin)
+    #[arg(long, conflicts_with_all = ["append", "clear"])]
+    set: Option<String>,
+
+    /// Add this text as a new line at the end of the notes
+    #[arg(long, conflicts_with = "clear")]
+    append: Option<String>,
+
+    /// Delete the notes
+    #[arg(long)]
+    clear: bool,
+}
+
 #[derive(Args)]
 pub struct SessionIdArgs {
     /// Session ID or title
@@ -165,6 +226,53 @@ pub struct CaptureArgs {
     json: bool,
 }
 
+#[derive(Args)]
+pub struct ReplayArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Recording file name from `--list` (defaults to the newest)
+    #[arg(long)]
+    file: Option<String>,
+
+    /// Playback speed multiplier
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Shorten pauses longer than this many seconds (0 keeps them as recorded)
+    #[arg(long, default_value_t = 2.0)]
+    max_idle: f64,
+
+    /// List the session's recordings instead of playing one
+    #[arg(long, conflicts_with = "file")]
+    list: bool,
+
+    /// Output as JSON (with `--list`)
+    #[arg(long, requires = "list")]
+    json: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Md,
+    Html,
+    Json,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Report format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+    format: ExportFormat,
+
+    /// Write the report to this file instead of stdout
+    #[arg(short, long, value_hint = ValueHint::FilePath)]
+    output: Option<std::path::PathBuf>,
+}
+
 #[derive(Args)]
 pub struct CurrentArgs {
     /// Just session name (for scripting)
@@ -220,6 +328,12 @@ struct SessionDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     parent_session_id: Option<String>,
     profile: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issue: Option<crate::session::issues::IssueLink>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    notes: String,
 }
 
 #[tracing::instrument(target = "cli.session", skip_all, fields(profile = %profile))]
@@ -231,6 +345,8 @@ pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
         SessionCommands::Attach(args) => attach_session(profile, args).await,
         SessionCommands::Show(args) => show_session(profile, args).await,
         SessionCommands::Capture(args) => capture_session(profile, args).await,
+        SessionCommands::Replay(args) => replay_session(profile, args).await,
+        SessionCommands::Export(args) => export_session(profile, args).await,
         SessionCommands::Rename(args) => rename_session(profile, args).await,
         SessionCommands::Current(args) => current_session(args).await,
         SessionCommands::SetSessionId(args) => set_session_id(profile, args).await,
@@ -241,6 +357,10 @@ pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
         SessionCommands::Unfavorite(args) => unfavorite_session(profile, args).await,
         SessionCommands::Archive(args) => archive_session(profile, args).await,
         SessionCommands::Unarchive(args) => unarchive_session(profile, args).await,
+        SessionCommands::Tag(args) => tag_session(profile, args, true).await,
+        SessionCommands::Untag(args) => tag_session(profile, args, false).await,
+        SessionCommands::Notes(args) => session_notes(profile, args).await,
+        SessionCommands::History(args) => session_history(profile, args).await,
     }
 }
 
@@ -268,6 +388,144 @@ async fn unfavorite_session(profile: &str, args: SessionIdArgs) -> Result<()> {
     Ok(())
 }
 
+async fn tag_session(profile: &str, args: TagArgs, add: bool) -> Result<()> {
+    let tags = args
+        .tags
+        .iter()
+        .map(String::as_str)
+        .map(tags::normalize)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let edit = if add {
+        TagEdit {
+            add: tags,
+            remove: Vec::new(),
+        }
+    } else {
+        TagEdit {
+            add: Vec::new(),
+            remove: tags,
+        }
+    };
+    let storage = Storage::new(profile)?;
+    let (title, tags) = storage.update(|instances, _groups| {
+        super::patch_instance(instances, &args.identifier, |inst| {
+            edit.apply(&mut inst.tags);
+            Ok((inst.title.clone(), inst.tags.clone()))
+        })
+    })?;
+    if tags.is_empty() {
+        println!("{}: no tags", title);
+    } else {
+        println!("{}: {}", title, tags.join(", "));
+    }
+    Ok(())
+}
+
+async fn session_notes(profile: &str, args: NotesArgs) -> Result<()> {
+    use crate::session::notes;
+
+    let storage = Storage::new(profile)?;
+    let instances = storage.load()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+    let profile = storage.profile();
+
+    let text = match (args.set, args.append, args.clear) {
+        (Some(text), _, _) if text == "-" => {
+            let mut text = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)?;
+            text
+        }
+        (Some(text), _, _) => text,
+        (None, Some(line), _) => match notes::load(profile, &inst.id)? {
+            current if current.is_empty() => line,
+            current => format!("{current}\n{line}"),
+        },
+        (None, None, true) => String::new(),
+        (None, None, false) => {
+            let current = notes::load(profile, &inst.id)?;
+            if !current.is_empty() {
+                println!("{current}");
+            }
+            return Ok(());
+        }
+    };
+    if notes::save(profile, &inst.id, &text)?.is_empty() {
+        println!("✓ Cleared notes for '{}'", inst.title);
+    } else {
+        println!("✓ Saved notes for '{}'", inst.title);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionHistory {
+    id: String,
+    title: String,
+    entries: Vec<crate::session::timeline::TimelineEntry>,
+    metrics: crate::session::timeline::TimelineMetrics,
+}
+
+async fn session_history(profile: &str, args: HistoryArgs) -> Result<()> {
+    use crate::session::timeline::{self, format_secs};
+
+    let storage = Storage::new(profile)?;
+    let instances = storage.load()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+    let entries = timeline::load(storage.profile(), &inst.id)?;
+    let now = chrono::Utc::now();
+    let metrics = timeline::metrics(&entries, inst.status, now);
+
+    if args.json {
+        return super::output::print_json(&SessionHistory {
+            id: inst.id.clone(),
+            title: inst.title.clone(),
+            entries,
+            metrics,
+        });
+    }
+
+    println!("History: {}", inst.title);
+    if entries.is_empty() {
+        println!("  No status transitions recorded yet.");
+        return Ok(());
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        let lasted = match entries.get(i + 1) {
+            Some(next) => format_secs((next.at - entry.at).num_seconds()),
+            None if entry.status == inst.status => {
+                format!("{} (now)", format_secs((now - entry.at).num_seconds()))
+            }
+            None => String::new(),
+        };
+        println!(
+            "  {}  {:<8} {:<8} {}",
+            entry
+                .at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            entry.status.as_str(),
+            entry.source.as_str(),
+            lasted
+        );
+    }
+    println!();
+    println!("  Turns:              {}", metrics.turns);
+    println!(
+        "  Running:            {}",
+        format_secs(metrics.running_secs)
+    );
+    println!(
+        "  Waiting on you:     {}",
+        format_secs(metrics.waiting_secs)
+    );
+    if let Some(secs) = metrics.time_to_first_idle_secs {
+        println!("  Time to first idle: {}", format_secs(secs));
+    }
+    Ok(())
+}
+
 async fn archive_session(profile: &str, args: ArchiveArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
 
@@ -836,6 +1094,7 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
     // rather than the stale persisted value.
     crate::tmux::refresh_session_cache();
     inst.update_status();
+    inst.notes = crate::session::notes::load(storage.profile(), &inst.id)?;
 
     if args.json {
         let details = SessionDetails {
@@ -848,6 +1107,9 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
             status: format!("{:?}", inst.status).to_lowercase(),
             parent_session_id: inst.parent_session_id.clone(),
             profile: storage.profile().to_string(),
+            tags: inst.tags.clone(),
+            issue: inst.issue.clone(),
+            notes: inst.notes.clone(),
         };
         super::output::print_json(&details)?;
     } else {
@@ -859,9 +1121,24 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
         println!("  Command: {}", inst.command);
         println!("  Status:  {:?}", inst.status);
         println!("  Profile: {}", storage.profile());
+        if !inst.tags.is_empty() {
+            println!("  Tags:    {}", inst.tags.join(", "));
+        }
+        if let Some(issue) = &inst.issue {
+            match &issue.url {
+                Some(url) => println!("  Issue:   {} ({})", issue.key, url),
+                None => println!("  Issue:   {}", issue.key),
+            }
+        }
         if let Some(parent_id) = &inst.parent_session_id {
             println!("  Parent:  {}", parent_id);
         }
+        if !inst.notes.is_empty() {
+            println!("  Notes:");
+            for line in inst.notes.lines() {
+                println!("    {line}");
+            }
+        }
     }
 
     Ok(())
@@ -949,6 +1226,93 @@ async fn capture_session(profile: &str, args: CaptureArgs) -> Result<()> {
     Ok(())
 }
 
+async fn replay_session(profile: &str, args: ReplayArgs) -> Result<()> {
+    use crate::session::recording;
+    use std::io::Write;
+
+    if !(args.speed > 0.0 && args.speed.is_finite()) {
+        bail!("--speed must be a positive number");
+    }
+
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?;
+
+    if args.list {
+        let recordings = recording::list_recordings(&inst.id)?;
+        if args.json {
+            return super::output::print_json(&recordings);
+        }
+        if recordings.is_empty() {
+            println!("No recordings for '{}'.", inst.title);
+            return Ok(());
+        }
+        for rec in &recordings {
+            println!(
+                "{}  {:>10}  {}",
+                rec.modified_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+                rec.size_bytes,
+                rec.name
+            );
+        }
+        return Ok(());
+    }
+
+    let path = recording::resolve_recording(&inst.id, args.file.as_deref())?;
+    let (header, events) = recording::read_cast(&path)?;
+    let max_idle = (args.max_idle > 0.0).then_some(args.max_idle);
+
+    eprintln!(
+        "Replaying {} ({}x{}) at {}x. Ctrl-C to stop.",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        header.width,
+        header.height,
+        args.speed
+    );
+    let mut stdout = std::io::stdout().lock();
+    for (delay, data) in recording::playback_schedule(&events, args.speed, max_idle) {
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        stdout.write_all(data.as_bytes())?;
+        stdout.flush()?;
+    }
+    // Leave the terminal with default attributes whatever the recording
+    // ended on.
+    stdout.write_all(b"\x1b[0m\n")?;
+    Ok(())
+}
+
+async fn export_session(profile: &str, args: ExportArgs) -> Result<()> {
+    use crate::session::export::SessionReport;
+
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let inst = super::resolve_session(&args.identifier, &instances)?.clone();
+    let profile = storage.profile().to_string();
+
+    // Reading agent logs and diffing worktrees is blocking I/O.
+    let report =
+        tokio::task::spawn_blocking(move || SessionReport::build(&inst, &profile)).await??;
+    let rendered = match args.format {
+        ExportFormat::Md => report.to_markdown(),
+        ExportFormat::Html => report.to_html(),
+        ExportFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+    };
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {e}", path.display()))?;
+            eprintln!("Exported '{}' to {}", report.title, path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
 async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
     if args.title.is_none() && args.group.is_none() {
         bail!("At least one of --title or --group must be specified");
@@ -0,0 +1,147 @@
+//! `agent-of-empires view` subcommands implementation
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::session::filter::Filter;
+use crate::session::{load_config, save_config};
+
+#[derive(Subcommand)]
+pub enum ViewCommands {
+    /// List saved views
+    #[command(alias = "ls")]
+    List(ViewListArgs),
+
+    /// Save a filter query as a named view (replaces an existing one)
+    Save(ViewSaveArgs),
+
+    /// Remove a saved view
+    #[command(alias = "rm")]
+    Remove(ViewRemoveArgs),
+}
+
+#[derive(Args)]
+pub struct ViewListArgs {
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct ViewSaveArgs {
+    /// View name, used as `@name` in queries
+    name: String,
+
+    /// Filter query, e.g. "status:waiting tool:claude group:payments age>1h"
+    query: String,
+}
+
+#[derive(Args)]
+pub struct ViewRemoveArgs {
+    /// View name
+    name: String,
+}
+
+#[derive(Serialize)]
+struct ViewInfo {
+    name: String,
+    query: String,
+}
+
+#[tracing::instrument(target = "cli.session", skip_all)]
+pub fn run(command: ViewCommands) -> Result<()> {
+    match command {
+        ViewCommands::List(args) => list_views(args),
+        ViewCommands::Save(args) => save_view(args),
+        ViewCommands::Remove(args) => remove_view(args),
+    }
+}
+
+fn list_views(args: ViewListArgs) -> Result<()> {
+    let views = load_config()?.unwrap_or_default().views;
+
+    if args.json {
+        let list: Vec<ViewInfo> = views
+            .into_iter()
+            .map(|(name, query)| ViewInfo { name, query })
+            .collect();
+        super::output::print_json(&list)?;
+        return Ok(());
+    }
+
+    if views.is_empty() {
+        println!("No saved views.");
+        println!("Save one with: aoe view save <name> \"<query>\"");
+        return Ok(());
+    }
+
+    let width = views.keys().map(|n| n.chars().count()).max().unwrap_or(0) + 1;
+    for (name, query) in &views {
+        println!("@{:<width$} {}", name, query, width = width);
+    }
+    println!("\nTotal: {} views", views.len());
+    Ok(())
+}
+
+fn valid_view_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn save_view(args: ViewSaveArgs) -> Result<()> {
+    let name = args.name.trim().trim_start_matches('@').to_string();
+    if !valid_view_name(&name) {
+        bail!(
+            "Invalid view name '{}': use letters, digits, '-' and '_'",
+            args.name
+        );
+    }
+
+    let mut config = load_config()?.unwrap_or_default();
+    let query = args.query.trim().to_string();
+    // Validate against the views as they'd be after saving, so a view may
+    // build on others but not on itself.
+    let mut views = config.views.clone();
+    views.insert(name.clone(), query.clone());
+    if let Err(e) = Filter::parse_with_views(&query, &views) {
+        bail!("Invalid query: {}", e);
+    }
+
+    let replaced = config.views.insert(name.clone(), query).is_some();
+    save_config(&config)?;
+    if replaced {
+        println!("Updated view '@{}'", name);
+    } else {
+        println!("Saved view '@{}'", name);
+    }
+    Ok(())
+}
+
+fn remove_view(args: ViewRemoveArgs) -> Result<()> {
+    let name = args.name.trim().trim_start_matches('@');
+    let mut config = load_config()?.unwrap_or_default();
+    if config.views.remove(name).is_none() {
+        bail!(
+            "View '{}' not found. Run `aoe view list` to see saved views.",
+            name
+        );
+    }
+    let dependents: Vec<String> = config
+        .views
+        .iter()
+        .filter(|(_, q)| Filter::parse_with_views(q, &config.views).is_err())
+        .map(|(n, _)| format!("@{n}"))
+        .collect();
+    save_config(&config)?;
+    println!("Removed view '@{}'", name);
+    if !dependents.is_empty() {
+        eprintln!(
+            "Warning: {} no longer parse without it",
+            dependents.join(", ")
+        );
+    }
+    Ok(())
+}
@@ -43,7 +43,7 @@ use super::agent_profiles;
 use super::agent_registry::AgentSpec;
 use super::approvals::{is_destructive, ApprovalDecision, Nonce};
 use super::fs_handler::{self, FsPolicy, SandboxPathMap};
-use super::permissions::build_approval;
+use super::permissions::{build_approval, evaluate_policy, PolicyInput};
 use super::state::{
     AvailableCommand, CockpitSessionId, ConfigOptionCategory, ConfigOptionChoice,
     ConfigOptionDescriptor, DiffPreview, Event, MemoryRecall, ModeInfo, Plan, PlanStep,
@@ -51,6 +51,8 @@ use super::state::{
     UsageCost,
 };
 use super::terminal_handler::TerminalManager;
+use super::worker_registry::{self, AgentTransport};
+use crate::session::config::ApprovalAction;
 use crate::session::SandboxInfo;
 
 #[derive(Debug, Error)]
@@ -257,11 +259,10 @@ pub struct SpawnConfig {
     pub additional_dirs: Vec<PathBuf>,
     /// Provider env vars to forward (after applying the agent's allowlist).
     pub provider_env: Vec<(String, String)>,
-    /// Reserved for a future agent-in-container that natively speaks
-    /// the socket transport. The current cockpit sandbox path runs
-    /// `docker exec` from the host-side runner (which already holds the
-    /// daemon↔runner socket) and proxies the agent's stdio across the
-    /// container boundary, so no bind-mount is needed today.
+    /// Daemon↔runner socket. When `Some`, the agent runs under a
+    /// detached `aoe __cockpit-runner` bound here. How the runner in
+    /// turn reaches the agent (stdio or the bind-mounted agent socket)
+    /// is decided per spawn; see `agent_transport_for`.
     pub socket_path: Option<PathBuf>,
     /// ACP session id from a previous run, captured during the last
     /// `session/new` and persisted on `Instance.cockpit_acp_session_id`.
@@ -276,10 +277,11 @@ pub struct SpawnConfig {
     /// fs/terminal handlers route across the container boundary using
     /// the container_workdir / mount map.
     pub sandbox_info: Option<SandboxInfo>,
-    /// Source profile of the session. Used together with `sandbox_info`
-    /// to resolve profile-level `sandbox.environment` entries so the
-    /// cockpit sandbox env mirrors the tmux substrate. `None` for
-    /// non-sandboxed sessions.
+    /// Source profile of the session. Resolves the profile and repo
+    /// cockpit settings (approval rules, MCP servers) and, together with
+    /// `sandbox_info`, profile-level `sandbox.environment` entries so the
+    /// cockpit sandbox env mirrors the tmux substrate. `None` only for
+    /// legacy worker records; resolves like the default profile.
     pub source_profile: Option<String>,
 }
 
@@ -1767,12 +1769,14 @@ fn spawn_runner_detached(
     // the container; the container's own PATH resolves it. The
     // container_workdir is reused from the SessionSandbox built upstream
     // so we don't redo `compute_volume_paths`.
+    let transport = agent_transport_for(config, &session_id);
     let sandbox_argv = match (&config.sandbox_info, session_sandbox) {
         (Some(sandbox), Some(handle)) => {
             let argv = build_sandbox_docker_argv(
                 config,
                 sandbox,
                 handle.container_workdir.to_string_lossy().as_ref(),
+                transport,
             )?;
             info!(
                 target: "cockpit.acp.spawn",
@@ -1782,6 +1786,7 @@ fn spawn_runner_detached(
                 image = %sandbox.image,
                 workdir = %handle.container_workdir.display(),
                 docker = %argv.docker_binary,
+                transport = transport.as_str(),
                 "docker wrap applied"
             );
             Some(argv)
@@ -1855,6 +1860,14 @@ fn spawn_runner_detached(
     if let Some(stored) = &config.stored_acp_session_id {
         cmd.arg("--stored-acp-session-id").arg(stored);
     }
+    if transport == AgentTransport::Socket {
+        let agent_socket = worker_registry::agent_socket_path_for(&session_id)
+            .map_err(|e| AcpError::Spawn(format!("agent socket path: {e}")))?;
+        cmd.arg("--agent-transport")
+            .arg(transport.as_str())
+            .arg("--agent-socket")
+            .arg(agent_socket);
+    }
     cmd.arg("--");
     if let Some(s) = &sandbox_argv {
         cmd.arg(&s.docker_binary);
@@ -1945,6 +1958,45 @@ fn spawn_runner_detached(
     Ok(())
 }
 
+/// Pick how the runner reaches the agent. Socket transport needs a
+/// sandboxed session, an agent that speaks ACP over `AOE_ACP_SOCKET`,
+/// and a container created with the agent socket directory mounted
+/// (containers from before the mount existed, or created while the
+/// session was on the tmux substrate, don't have it). Linux only: Docker
+/// Desktop and Apple containers run the container in a VM, and a unix
+/// socket bound on the host is not connectable through their file
+/// sharing. Anything else keeps the `docker exec -i` stdio pipe.
+fn agent_transport_for(config: &SpawnConfig, session_id: &str) -> AgentTransport {
+    let Some(sandbox) = &config.sandbox_info else {
+        return AgentTransport::Stdio;
+    };
+    if !config.spec.acp_socket || !cfg!(target_os = "linux") {
+        return AgentTransport::Stdio;
+    }
+    if let Ok(dir) = worker_registry::agent_socket_dir_for(session_id) {
+        let _ = std::fs::create_dir_all(&dir);
+    }
+    let runtime = crate::containers::get_container_runtime();
+    let mounted = runtime
+        .base
+        .exec(
+            &sandbox.container_name,
+            &["test", "-d", worker_registry::CONTAINER_AGENT_SOCKET_DIR],
+        )
+        .map(|out| out.status.success())
+        .unwrap_or(false);
+    if !mounted {
+        info!(
+            target: "cockpit.acp.spawn",
+            session = %session_id,
+            container = %sandbox.container_name,
+            "container has no agent socket mount; using docker exec stdio"
+        );
+        return AgentTransport::Stdio;
+    }
+    AgentTransport::Socket
+}
+
 /// Result of constructing the `docker exec` argv for a sandboxed cockpit
 /// spawn. `docker_binary` is argv[0] (the docker/podman runtime);
 /// `docker_args` is everything after it (including the container name
@@ -1966,10 +2018,16 @@ struct SandboxArgv {
 /// `container_workdir` is the in-container working directory for the
 /// session, pre-computed by `SessionSandbox::from_info` and passed
 /// through to avoid re-running `compute_volume_paths`.
+///
+/// With `AgentTransport::Socket` the exec is detached (`-d`) and the
+/// agent gets `AOE_ACP_SOCKET` pointing at the mounted agent socket;
+/// `docker exec -d` returns as soon as the agent starts, so the runner
+/// can run the same argv again to relaunch it.
 fn build_sandbox_docker_argv(
     config: &SpawnConfig,
     sandbox: &SandboxInfo,
     container_workdir: &str,
+    transport: AgentTransport,
 ) -> Result<SandboxArgv, AcpError> {
     use crate::containers::container_interface::docker_env_args;
 
@@ -1984,10 +2042,20 @@ fn build_sandbox_docker_argv(
 
     let mut docker_args: Vec<String> = vec![
         "exec".into(),
-        "-i".into(),
+        match transport {
+            AgentTransport::Stdio => "-i".into(),
+            AgentTransport::Socket => "-d".into(),
+        },
         "-w".into(),
         container_workdir.to_string(),
     ];
+    if transport == AgentTransport::Socket {
+        docker_args.push("-e".into());
+        docker_args.push(format!(
+            "AOE_ACP_SOCKET={}",
+            worker_registry::container_agent_socket_path()
+        ));
+    }
     // `collect_environment` already dedupes by key, so the entry list is
     // unique. We still track `seen_keys` so the provider-auth block below
     // can skip keys we've already forwarded.
@@ -3089,7 +3157,9 @@ fn preview_args(raw: &serde_json::Value) -> String {
 
 /// Concat the textual portion of a tool call's `content` array. Drops
 /// non-text content blocks (images, resources, embedded terminals); the
-/// per-tool renderer fall-back path only knows how to display text. Diffs
+/// per-tool renderer fall-back path only knows how to display text.
+/// Embedded terminals stream their output separately, see
+/// `embedded_terminals`. Diffs
 /// are surfaced separately via `extract_diff_from_locations` (and could
 /// later be picked up here too via `ToolCallContent::Diff`).
 fn extract_tool_content_text(blocks: &[agent_client_protocol::schema::ToolCallContent]) -> String {
@@ -3108,6 +3178,26 @@ fn extract_tool_content_text(blocks: &[agent_client_protocol::schema::ToolCallCo
     out
 }
 
+/// `(tool_call_id, terminal_id)` for every terminal an update embeds in a
+/// tool call's content. The connection task attaches each one so the
+/// terminal's live output streams to the tool card.
+fn embedded_terminals(update: &SessionUpdate) -> Vec<(String, String)> {
+    use agent_client_protocol::schema::ToolCallContent;
+    let (id, content) = match update {
+        SessionUpdate::ToolCall(tc) => (&tc.tool_call_id, Some(&tc.content)),
+        SessionUpdate::ToolCallUpdate(u) => (&u.tool_call_id, u.fields.content.as_ref()),
+        _ => return Vec::new(),
+    };
+    content
+        .into_iter()
+        .flatten()
+        .filter_map(|block| match block {
+            ToolCallContent::Terminal(t) => Some((id.0.to_string(), t.terminal_id.0.to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Inspect a `tool_call` payload for the `memory_recall` shape
 /// claude-agent-acp v0.37.0 routes through the tool channel (upstream
 /// #703). The adapter sends `_meta.claudeCode.toolName == "memory_recall"`
@@ -3259,9 +3349,17 @@ async fn run_connection_task<W, R>(
     let ready_tx = Arc::new(Mutex::new(ready_tx));
     let ready_for_block = ready_tx.clone();
     let event_tx_for_notif = event_tx.clone();
+    let terminals_for_notif = resources.terminals.clone();
+    let terminals_for_shutdown = resources.terminals.clone();
     let event_tx_for_perm = event_tx.clone();
     let event_tx_for_block = event_tx.clone();
     let pending_for_perm = pending_responders.clone();
+    let config_scope = ConfigScope {
+        profile: source_profile.clone(),
+        cwd: cwd.clone(),
+    };
+    let config_scope_for_perm = config_scope.clone();
+    let sandbox_map_for_block = resources.fs_policy.sandbox_map.clone();
     let mut cmd_rx = cmd_rx;
     let session_label_for_log = session_label.clone();
 
@@ -3333,6 +3431,7 @@ async fn run_connection_task<W, R>(
         .on_receive_notification(
             move |notification: SessionNotification, _cx| {
                 let event_tx = event_tx_for_notif.clone();
+                let terminals = terminals_for_notif.clone();
                 let suppress = suppress_for_notif.clone();
                 let session_label = session_label_for_notif.clone();
                 let last_event_at = last_event_at_for_notif.clone();
@@ -3377,6 +3476,11 @@ async fn run_connection_task<W, R>(
                     } else {
                         wakeup_lifecycle_signal_from_update(&notification.update, profile)
                     };
+                    let embedded = if suppressing {
+                        Vec::new()
+                    } else {
+                        embedded_terminals(&notification.update)
+                    };
                     let mapped_events = map_update_to_events(notification.update, profile);
                     // Deliver lifecycle signals BEFORE publishing the
                     // user-visible event vector. The watchdog uses
@@ -3434,6 +3538,13 @@ async fn run_connection_task<W, R>(
                             break;
                         }
                     }
+                    // After the tool call's own events so the first
+                    // streamed snapshot never precedes its ToolCallStarted.
+                    for (tool_call_id, terminal_id) in embedded {
+                        terminals
+                            .attach(&terminal_id, tool_call_id, event_tx.clone())
+                            .await;
+                    }
                     Ok(())
                 }
             },
@@ -3445,9 +3556,17 @@ async fn run_connection_task<W, R>(
                   _conn| {
                 let event_tx = event_tx_for_perm.clone();
                 let pending = pending_for_perm.clone();
+                let policy_scope = config_scope_for_perm.clone();
                 async move {
-                    handle_permission_request(request, responder, event_tx, pending, profile)
-                        .await
+                    handle_permission_request(
+                        request,
+                        responder,
+                        event_tx,
+                        pending,
+                        profile,
+                        policy_scope,
+                    )
+                    .await
                 }
             },
             agent_client_protocol::on_receive_request!(),
@@ -3570,6 +3689,12 @@ async fn run_connection_task<W, R>(
             }
 
             let load_session_capable = init.agent_capabilities.load_session;
+            let mcp_servers = super::mcp_servers::to_acp(
+                &config_scope.cockpit().mcp_servers,
+                &init.agent_capabilities.mcp_capabilities,
+                sandbox_map_for_block.as_ref(),
+                &session_label,
+            );
             // Snapshot the watchdog-arming flag before `mode` is moved
             // into the match below.
             let arm_resume_watchdog = matches!(
@@ -3657,7 +3782,8 @@ async fn run_connection_task<W, R>(
                             // key toolCallId-..."). Cleared on Err below if we fall
                             // back to session/new, which has no replay payload.
                             suppress_for_block.store(true, Ordering::Relaxed);
-                            let req = LoadSessionRequest::new(stored.clone(), cwd.clone());
+                            let req = LoadSessionRequest::new(stored.clone(), cwd.clone())
+                                .mcp_servers(mcp_servers.clone());
                             match connection.send_request(req).block_task().await {
                                 Ok(resp) => {
                                     info!(
@@ -3716,7 +3842,7 @@ async fn run_connection_task<W, R>(
                             "creating fresh session via session/new"
                         );
                         let new_session = connection
-                            .send_request(NewSessionRequest::new(cwd))
+                            .send_request(NewSessionRequest::new(cwd).mcp_servers(mcp_servers))
                             .block_task()
                             .await?;
                         let id = new_session.session_id.clone();
@@ -4491,6 +4617,9 @@ async fn run_connection_task<W, R>(
             );
         }
     }
+    // Terminals live in this process either way; a reconnecting agent
+    // gets a fresh manager and could never reach them again.
+    terminals_for_shutdown.kill_all().await;
     // In runner-managed mode (child is None) we deliberately don't kill
     // anything here: the per-worker `aoe __cockpit-runner` shim owns the
     // agent subprocess and outlives this daemon's connection. The socket
@@ -4764,15 +4893,22 @@ async fn handle_create_terminal(
             container_name: s.container_name.clone(),
             env_entries: s.current_env_entries(),
         });
+    let spec = super::terminal_handler::TerminalSpec {
+        command: request.command.clone(),
+        args: request.args.clone(),
+        env: request
+            .env
+            .iter()
+            .map(|var| (var.name.clone(), var.value.clone()))
+            .collect(),
+        cwd,
+        output_byte_limit: request
+            .output_byte_limit
+            .map(|limit| usize::try_from(limit).unwrap_or(usize::MAX)),
+    };
     let result = match res
         .terminals
-        .create_and_run(
-            &res.label,
-            &request.command,
-            request.args.clone(),
-            cwd,
-            terminal_sandbox.as_ref(),
-        )
+        .create(&res.label, spec, terminal_sandbox.as_ref())
         .await
     {
         Ok(id) => responder.respond(CreateTerminalResponse::new(TerminalId::new(id))),
@@ -4790,10 +4926,14 @@ async fn handle_create_terminal(
     result
 }
 
-fn build_exit_status(exit_code: Option<i32>) -> agent_client_protocol::schema::TerminalExitStatus {
+fn build_exit_status(
+    exit: &super::terminal_handler::TerminalExit,
+) -> agent_client_protocol::schema::TerminalExitStatus {
     use agent_client_protocol::schema::TerminalExitStatus;
-    let cast = exit_code.and_then(|c| u32::try_from(c).ok());
-    TerminalExitStatus::new().exit_code(cast)
+    let cast = exit.exit_code.and_then(|c| u32::try_from(c).ok());
+    TerminalExitStatus::new()
+        .exit_code(cast)
+        .signal(exit.signal.clone())
 }
 
 async fn handle_terminal_output(
@@ -4810,13 +4950,10 @@ async fn handle_terminal_output(
         "ACP request handler entered"
     );
     let result = match res.terminals.output(request.terminal_id.0.as_ref()).await {
-        Ok(out) => {
-            let combined = format!("{}{}", out.stdout, out.stderr);
-            responder.respond(
-                TerminalOutputResponse::new(combined, false)
-                    .exit_status(build_exit_status(out.exit_code)),
-            )
-        }
+        Ok(out) => responder.respond(
+            TerminalOutputResponse::new(out.output, out.truncated)
+                .exit_status(out.exit.as_ref().map(build_exit_status)),
+        ),
         Err(e) => {
             responder.respond_with_error(agent_client_protocol::util::internal_error(e.to_string()))
         }
@@ -4844,13 +4981,12 @@ async fn handle_wait_for_terminal_exit(
         enter_ns,
         "ACP request handler entered"
     );
-    // For our one-shot terminal model, the command has already finished by
-    // the time `create_and_run` returns. So `output()` immediately yields
-    // the captured exit status.
-    let result = match res.terminals.output(request.terminal_id.0.as_ref()).await {
-        Ok(out) => responder.respond(WaitForTerminalExitResponse::new(build_exit_status(
-            out.exit_code,
-        ))),
+    let result = match res
+        .terminals
+        .wait_for_exit(request.terminal_id.0.as_ref())
+        .await
+    {
+        Ok(exit) => responder.respond(WaitForTerminalExitResponse::new(build_exit_status(&exit))),
         Err(e) => {
             responder.respond_with_error(agent_client_protocol::util::internal_error(e.to_string()))
         }
@@ -4868,7 +5004,7 @@ async fn handle_wait_for_terminal_exit(
 async fn handle_kill_terminal(
     request: KillTerminalRequest,
     responder: Responder<KillTerminalResponse>,
-    _res: SessionResources,
+    res: SessionResources,
 ) -> agent_client_protocol::Result<()> {
     let enter_ns = enter_timestamp_ns();
     trace!(
@@ -4878,8 +5014,12 @@ async fn handle_kill_terminal(
         enter_ns,
         "ACP request handler entered"
     );
-    // One-shot terminals are already finished; kill is a no-op.
-    let result = responder.respond(KillTerminalResponse::new());
+    let result = match res.terminals.kill(request.terminal_id.0.as_ref()).await {
+        Ok(()) => responder.respond(KillTerminalResponse::new()),
+        Err(e) => {
+            responder.respond_with_error(agent_client_protocol::util::internal_error(e.to_string()))
+        }
+    };
     trace!(
         target: "cockpit.acp.tool_dispatch",
         handler = "kill_terminal",
@@ -4919,12 +5059,36 @@ async fn handle_release_terminal(
     result
 }
 
+/// Where a session's cockpit settings come from: the source profile's
+/// config merged with the repo config at the session's cwd. Applies to
+/// host and sandboxed sessions alike; a missing profile (legacy worker
+/// records) resolves as the default profile.
+#[derive(Clone)]
+struct ConfigScope {
+    profile: Option<String>,
+    cwd: PathBuf,
+}
+
+impl ConfigScope {
+    fn cockpit(&self) -> crate::session::config::CockpitConfig {
+        let profile = self.profile.as_deref().unwrap_or("");
+        crate::session::repo_config::resolve_config_with_repo_or_warn(profile, &self.cwd).cockpit
+    }
+
+    /// Re-read on every request so rule edits apply without restarting
+    /// the worker, like the silent-orphan settings.
+    fn rules(&self) -> Vec<crate::session::config::ApprovalRule> {
+        self.cockpit().approval_rules
+    }
+}
+
 async fn handle_permission_request(
     request: RequestPermissionRequest,
     responder: Responder<RequestPermissionResponse>,
     event_tx: mpsc::Sender<Event>,
     pending: PendingResponders,
     profile: &'static agent_profiles::AgentProfile,
+    policy_scope: ConfigScope,
 ) -> agent_client_protocol::Result<()> {
     let enter_ns = enter_timestamp_ns();
     let tool_call_id = request.tool_call.tool_call_id.0.to_string();
@@ -4964,9 +5128,68 @@ async fn handle_permission_request(
         parent_tool_call_id: profile.parent_tool_use_id_from_meta(&request.tool_call.meta),
         memory_recall: None,
     };
-    let approval = build_approval(tool_call);
+    let locations: Vec<PathBuf> = request
+        .tool_call
+        .fields
+        .locations
+        .as_ref()
+        .map(|locs| locs.iter().map(|l| l.path.clone()).collect())
+        .unwrap_or_default();
+    let policy = evaluate_policy(
+        &policy_scope.rules(),
+        &PolicyInput {
+            tool_call: &tool_call,
+            raw_input: &raw_args,
+            locations: &locations,
+            worktree: &policy_scope.cwd,
+        },
+    );
+    let mut approval = build_approval(tool_call);
     let nonce = approval.nonce.clone();
 
+    // A rule answered: record the request and its resolution, then reply
+    // without parking the agent. Only one-shot options are used so a rule
+    // never installs a permanent permission inside the agent; when the
+    // agent offers none, the user is asked as usual.
+    if let Some((decision, option_id, rule)) = policy.and_then(|p| {
+        let (decision, kind) = match p.action {
+            ApprovalAction::Allow => (ApprovalDecision::Allow, PermissionOptionKind::AllowOnce),
+            ApprovalAction::Deny => (ApprovalDecision::Deny, PermissionOptionKind::RejectOnce),
+            ApprovalAction::Ask => return None,
+        };
+        let option = request.options.iter().find(|o| o.kind == kind)?;
+        Some((decision, option.option_id.clone(), p.rule))
+    }) {
+        approval.policy_rule = Some(rule.clone());
+        debug!(
+            target: "cockpit.permissions",
+            tool_call_id = %tool_call_id,
+            rule = %rule,
+            ?decision,
+            "approval decided by rule"
+        );
+        let _ = event_tx.send(Event::ApprovalRequested { approval }).await;
+        let _ = event_tx
+            .send(Event::ApprovalResolved {
+                nonce,
+                decision,
+                rule: Some(rule),
+            })
+            .await;
+        trace!(
+            target: "cockpit.acp.tool_dispatch",
+            handler = "permission_request",
+            tool_call_id = %tool_call_id,
+            enter_ns,
+            elapsed_ns = enter_timestamp_ns() - enter_ns,
+            outcome = "policy",
+            "ACP request handler exited"
+        );
+        return responder.respond(RequestPermissionResponse::new(
+            RequestPermissionOutcome::Selected(SelectedPermissionOutcome::new(option_id)),
+        ));
+    }
+
     let (resolve_tx, resolve_rx) = oneshot::channel::<ApprovalResolutionMessage>();
     pending.lock().await.insert(
         nonce.clone(),
@@ -5020,6 +5243,7 @@ async fn handle_permission_request(
                     .send(Event::ApprovalResolved {
                         nonce: nonce.clone(),
                         decision,
+                        rule: None,
                     })
                     .await;
                 (
@@ -5505,6 +5729,7 @@ mod tests {
                 args: vec!["--stdio".into()],
                 description: "test".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
             cwd,
             additional_dirs: vec![],
@@ -5514,8 +5739,9 @@ mod tests {
             sandbox_info: Some(sandbox.clone()),
             source_profile: None,
         };
-        let argv = build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj")
-            .expect("docker argv built");
+        let argv =
+            build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj", AgentTransport::Stdio)
+                .expect("docker argv built");
         assert!(
             argv.docker_binary == "docker" || argv.docker_binary == "podman",
             "expected docker/podman binary, got {:?}",
@@ -5546,6 +5772,58 @@ mod tests {
         );
     }
 
+    /// Socket transport launches the agent detached and points it at
+    /// the mounted agent socket; the agent argv is unchanged.
+    #[test]
+    fn build_sandbox_docker_argv_socket_transport_detaches_with_socket_env() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sandbox = SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "alpine:latest".into(),
+            container_name: "aoe-sandbox-sock1234".into(),
+            extra_env: None,
+            custom_instruction: None,
+        };
+        let config = SpawnConfig {
+            agent_key: "aoe-agent".into(),
+            spec: AgentSpec {
+                command: "aoe-agent".into(),
+                args: vec![],
+                description: "test".into(),
+                env_allowlist: None,
+                acp_socket: true,
+            },
+            cwd: tmp.path().to_path_buf(),
+            additional_dirs: vec![],
+            provider_env: vec![],
+            socket_path: None,
+            stored_acp_session_id: None,
+            sandbox_info: Some(sandbox.clone()),
+            source_profile: None,
+        };
+        let argv =
+            build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj", AgentTransport::Socket)
+                .expect("docker argv built");
+        assert_eq!(argv.docker_args[0], "exec");
+        assert_eq!(argv.docker_args[1], "-d");
+        assert!(!argv.docker_args.iter().any(|a| a == "-i"));
+        let socket_env = format!(
+            "AOE_ACP_SOCKET={}",
+            worker_registry::container_agent_socket_path()
+        );
+        let env_idx = argv
+            .docker_args
+            .iter()
+            .position(|a| *a == socket_env)
+            .expect("socket env in argv");
+        assert_eq!(argv.docker_args[env_idx - 1], "-e");
+        assert_eq!(
+            argv.docker_args[argv.docker_args.len() - 2..],
+            ["aoe-sandbox-sock1234".to_string(), "aoe-agent".to_string()]
+        );
+    }
+
     /// Inherit-style env entries (provider auth keys) must lower into a
     /// pair of `-e KEY` (key only) in docker_args plus a `(KEY, VALUE)`
     /// pair in inherit_env so the runner can re-export the value and
@@ -5569,6 +5847,7 @@ mod tests {
                 args: vec![],
                 description: "test".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
             cwd,
             additional_dirs: vec![],
@@ -5579,8 +5858,9 @@ mod tests {
             sandbox_info: Some(sandbox.clone()),
             source_profile: None,
         };
-        let argv = build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj")
-            .expect("docker argv built");
+        let argv =
+            build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj", AgentTransport::Stdio)
+                .expect("docker argv built");
         // The `-e KEY` flag (without value) must appear consecutively.
         let key_flag_idx = argv
             .docker_args
@@ -5637,6 +5917,7 @@ mod tests {
                 args: vec![],
                 description: "test".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
             cwd: tmp.path().to_path_buf(),
             additional_dirs: vec![],
@@ -5646,8 +5927,9 @@ mod tests {
             sandbox_info: Some(sandbox.clone()),
             source_profile: None,
         };
-        let argv = build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj")
-            .expect("docker argv built");
+        let argv =
+            build_sandbox_docker_argv(&config, &sandbox, "/workspace/proj", AgentTransport::Stdio)
+                .expect("docker argv built");
         match prev {
             Some(v) => std::env::set_var("CLAUDE_CONFIG_DIR", v),
             None => std::env::remove_var("CLAUDE_CONFIG_DIR"),
@@ -5681,6 +5963,7 @@ mod tests {
                 args: vec![],
                 description: "test".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
             cwd: std::env::temp_dir(),
             additional_dirs: vec![],
@@ -5711,6 +5994,7 @@ mod tests {
                 args: vec![],
                 description: "test".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
             cwd: missing.clone(),
             additional_dirs: vec![],
@@ -5789,6 +6073,33 @@ mod tests {
         assert_eq!(started.parent_tool_call_id.as_deref(), Some("tc-task-1"),);
     }
 
+    #[test]
+    fn embedded_terminals_pairs_tool_call_with_terminal() {
+        use agent_client_protocol::schema::{
+            SessionUpdate, Terminal, ToolCall as AcpToolCall, ToolCallContent, ToolCallUpdate,
+            ToolCallUpdateFields,
+        };
+        let mut tc = AcpToolCall::new("tc-1", "cargo build");
+        tc.content = vec![ToolCallContent::Terminal(Terminal::new("term-1"))];
+        assert_eq!(
+            embedded_terminals(&SessionUpdate::ToolCall(tc)),
+            vec![("tc-1".to_string(), "term-1".to_string())]
+        );
+
+        let update = ToolCallUpdate::new(
+            "tc-2",
+            ToolCallUpdateFields::new()
+                .content(vec![ToolCallContent::Terminal(Terminal::new("term-2"))]),
+        );
+        assert_eq!(
+            embedded_terminals(&SessionUpdate::ToolCallUpdate(update)),
+            vec![("tc-2".to_string(), "term-2".to_string())]
+        );
+
+        let plain = AcpToolCall::new("tc-3", "Read");
+        assert!(embedded_terminals(&SessionUpdate::ToolCall(plain)).is_empty());
+    }
+
     #[test]
     fn map_update_to_events_leaves_parent_none_when_meta_missing() {
         use agent_client_protocol::schema::{SessionUpdate, ToolCall as AcpToolCall};
@@ -6831,4 +7142,58 @@ mod tests {
             assert_eq!(scrub_stderr_secrets(line), line);
         }
     }
+
+    /// Host sessions resolve approval rules and MCP servers from their
+    /// profile and repo, the same as sandboxed ones.
+    #[test]
+    #[serial_test::serial]
+    fn config_scope_reads_profile_and_repo_for_host_sessions() {
+        let home = tempfile::tempdir().unwrap();
+        // SAFETY: serialised by `#[serial]`, the existing pattern.
+        unsafe {
+            std::env::set_var("HOME", home.path());
+            std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+        }
+        let profile_dir = crate::session::get_profile_dir("rules").unwrap();
+        std::fs::write(
+            profile_dir.join("config.toml"),
+            r#"
+[[cockpit.approval_rules]]
+name = "profile rule"
+action = "allow"
+kind = ["read"]
+
+[[cockpit.mcp_servers]]
+name = "profile-server"
+command = "profile-mcp"
+"#,
+        )
+        .unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(repo.path().join(".agent-of-empires")).unwrap();
+        std::fs::write(
+            repo.path().join(".agent-of-empires/config.toml"),
+            r#"
+[[cockpit.mcp_servers]]
+name = "repo-server"
+url = "https://mcp.example.com/mcp"
+"#,
+        )
+        .unwrap();
+
+        let scope = ConfigScope {
+            profile: Some("rules".into()),
+            cwd: repo.path().to_path_buf(),
+        };
+        let rules = scope.rules();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name.as_deref(), Some("profile rule"));
+        let servers: Vec<String> = scope
+            .cockpit()
+            .mcp_servers
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(servers, ["profile-server", "repo-server"]);
+    }
 }
@@ -18,6 +18,12 @@ pub struct AgentSpec {
     /// `None`, only `PATH`, `HOME`, `LANG`, `TERM`, and provider auth env
     /// (e.g. `ANTHROPIC_API_KEY`) are forwarded.
     pub env_allowlist: Option<Vec<String>>,
+    /// The agent can speak ACP over a unix socket named by
+    /// `AOE_ACP_SOCKET` instead of stdio. Sandboxed sessions use that to
+    /// connect the in-container agent straight to the runner rather
+    /// than through a `docker exec` pipe.
+    #[serde(default)]
+    pub acp_socket: bool,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -66,6 +72,7 @@ impl AgentRegistry {
                     "Anthropic Claude via the official ACP adapter ({claude_install})"
                 ),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         // Legacy alias used by older session records before the
@@ -78,6 +85,7 @@ impl AgentRegistry {
                 args: vec![],
                 description: "Alias for `claude` (legacy name)".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.agents.insert(
@@ -87,6 +95,7 @@ impl AgentRegistry {
                 args: vec!["acp".into()],
                 description: "OpenCode (SST) — native ACP via `opencode acp`".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.agents.insert(
@@ -96,6 +105,7 @@ impl AgentRegistry {
                 args: vec!["--acp".into()],
                 description: "Google Gemini CLI — native ACP via `gemini --acp`".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.agents.insert(
@@ -106,6 +116,7 @@ impl AgentRegistry {
                 description:
                     "OpenAI Codex CLI via Zed adapter (npm i -g @zed-industries/codex-acp)".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.agents.insert(
@@ -115,6 +126,7 @@ impl AgentRegistry {
                 args: vec![],
                 description: "Mistral Vibe — native ACP via the bundled `vibe-acp` binary".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.agents.insert(
@@ -125,6 +137,7 @@ impl AgentRegistry {
                 description: "Pi coding agent (`pi`) via the pi-acp adapter (npm i -g pi-acp)"
                     .into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.agents.insert(
@@ -134,6 +147,7 @@ impl AgentRegistry {
                 args: vec![],
                 description: "aoe's bundled multi-provider agent (Vercel AI SDK 6)".into(),
                 env_allowlist: None,
+                acp_socket: true,
             },
         );
         reg
@@ -179,6 +193,7 @@ mod tests {
                 args: vec![],
                 description: "z".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         reg.upsert(
@@ -188,6 +203,7 @@ mod tests {
                 args: vec![],
                 description: "a".into(),
                 env_allowlist: None,
+                acp_socket: false,
             },
         );
         let names: Vec<&str> = reg.list().iter().map(|(n, _)| n.as_str()).collect();
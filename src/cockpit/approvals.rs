@@ -82,6 +82,10 @@ pub struct Approval {
     pub destructive: bool,
     pub requested_at: DateTime<Utc>,
     pub resolved: Option<ResolvedApproval>,
+    /// Set when an approval rule answers this request without asking;
+    /// the card resolves immediately and no approval push is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_rule: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,413 @@
+//! Per-turn worktree checkpoints for cockpit rewind.
+//!
+//! Before each user prompt is forwarded to the agent, the worktree is
+//! snapshotted into a commit that no branch points at, referenced as
+//! `refs/aoe/checkpoints/<session_id>/<seq>` where `seq` is the
+//! `UserPromptSent` event's seq. The snapshot is built in a per-session
+//! index under the git dir, so tracked changes *and* untracked
+//! (non-ignored) files are captured without touching the user's staging
+//! area or HEAD.
+//!
+//! `restore` writes a checkpoint's tree back over the worktree: files
+//! changed since are rewritten and files created since are removed.
+//! Ignored files are left alone in both directions. Commits the agent
+//! made in the meantime stay in history; only the files go back.
+//!
+//! Only prompts the user wrote are checkpointed; the recaps aoe sends
+//! after a rewind or handoff are not. Turn numbers are 1-based positions in the
+//! session's checkpoint list, in the order of those prompts.
+
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use git2::{Delta, Index, IndexAddOption, Oid, Repository, Signature};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+const REF_PREFIX: &str = "refs/aoe/checkpoints";
+const MESSAGE_PREFIX: &str = "aoe checkpoint: ";
+const MAX_SUMMARY_CHARS: usize = 72;
+
+/// One recorded checkpoint, as listed by `GET .../cockpit/checkpoints`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Checkpoint {
+    pub turn: usize,
+    /// Seq of the `UserPromptSent` this checkpoint precedes.
+    pub seq: u64,
+    /// Snapshot commit id.
+    pub commit: String,
+    /// First line of the prompt, clipped.
+    pub prompt: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Snapshot `worktree` as the checkpoint for the prompt at `seq`.
+/// Returns the snapshot commit id.
+pub fn record(worktree: &Path, session_id: &str, seq: u64, prompt: &str) -> Result<String> {
+    let repo = open(worktree)?;
+    let tree = snapshot_tree(&repo, session_id)?;
+    let sig = repo
+        .signature()
+        .or_else(|_| Signature::now("aoe", "aoe@localhost"))?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let message = format!("{MESSAGE_PREFIX}{}", summarize(prompt));
+    let oid = repo.commit(None, &sig, &sig, &message, &tree, &parents)?;
+    repo.reference(
+        &ref_name(session_id, seq),
+        oid,
+        true,
+        "aoe cockpit checkpoint",
+    )?;
+    debug!(
+        target: "cockpit.checkpoints",
+        session = %session_id,
+        seq,
+        commit = %oid,
+        "recorded checkpoint"
+    );
+    Ok(oid.to_string())
+}
+
+/// All checkpoints for `session_id`, oldest first.
+pub fn list(worktree: &Path, session_id: &str) -> Result<Vec<Checkpoint>> {
+    let repo = open(worktree)?;
+    let prefix = format!("{REF_PREFIX}/{session_id}/");
+    let mut found = Vec::new();
+    for reference in repo.references_glob(&format!("{prefix}*"))? {
+        let reference = reference?;
+        let Some(seq) = reference
+            .name()
+            .and_then(|n| n.strip_prefix(&prefix))
+            .and_then(|s| s.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let commit = reference.peel_to_commit()?;
+        let prompt = commit
+            .summary()
+            .unwrap_or_default()
+            .trim_start_matches(MESSAGE_PREFIX)
+            .to_string();
+        let created_at = Utc
+            .timestamp_opt(commit.time().seconds(), 0)
+            .single()
+            .unwrap_or_default();
+        found.push((seq, commit.id().to_string(), prompt, created_at));
+    }
+    found.sort_by_key(|(seq, ..)| *seq);
+    Ok(found
+        .into_iter()
+        .enumerate()
+        .map(|(i, (seq, commit, prompt, created_at))| Checkpoint {
+            turn: i + 1,
+            seq,
+            commit,
+            prompt,
+            created_at,
+        })
+        .collect())
+}
+
+/// Make the worktree's files match `checkpoint` again.
+pub fn restore(worktree: &Path, session_id: &str, checkpoint: &Checkpoint) -> Result<()> {
+    let repo = open(worktree)?;
+    let root = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("{} has no working directory", worktree.display()))?
+        .to_path_buf();
+    let target = repo
+        .find_commit(Oid::from_str(&checkpoint.commit)?)
+        .context("checkpoint commit is missing")?
+        .tree()?;
+    let current = snapshot_tree(&repo, session_id)?;
+    let diff = repo.diff_tree_to_tree(Some(&current), Some(&target), None)?;
+    // Removals first so a file the checkpoint had where a directory now
+    // stands (or the reverse) has room to be written.
+    for delta in diff.deltas() {
+        if delta.status() == Delta::Deleted {
+            if let Some(path) = delta.old_file().path() {
+                remove_file(&root.join(path))?;
+                prune_empty_dirs(&root, path);
+            }
+        }
+    }
+    for delta in diff.deltas() {
+        if !matches!(
+            delta.status(),
+            Delta::Added | Delta::Modified | Delta::Typechange
+        ) {
+            continue;
+        }
+        let new = delta.new_file();
+        let Some(path) = new.path() else { continue };
+        let blob = match repo.find_blob(new.id()) {
+            Ok(blob) => blob,
+            // Submodule entries point at commits, not blobs.
+            Err(_) => continue,
+        };
+        write_file(&root.join(path), blob.content(), new.mode())?;
+    }
+    debug!(
+        target: "cockpit.checkpoints",
+        seq = checkpoint.seq,
+        commit = %checkpoint.commit,
+        changed = diff.deltas().len(),
+        "restored checkpoint"
+    );
+    Ok(())
+}
+
+/// Delete the session's checkpoints with `seq >= from_seq`: the turns a
+/// rewind discarded. `from_seq = 0` deletes them all.
+pub fn discard_from(worktree: &Path, session_id: &str, from_seq: u64) -> Result<()> {
+    let repo = open(worktree)?;
+    let prefix = format!("{REF_PREFIX}/{session_id}/");
+    let names: Vec<String> = repo
+        .references_glob(&format!("{prefix}*"))?
+        .filter_map(|r| r.ok())
+        .filter_map(|r| r.name().map(str::to_string))
+        .filter(|name| {
+            name.strip_prefix(&prefix)
+                .and_then(|s| s.parse::<u64>().ok())
+                .is_some_and(|seq| seq >= from_seq)
+        })
+        .collect();
+    for name in names {
+        repo.find_reference(&name)?.delete()?;
+    }
+    if from_seq == 0 {
+        remove_file(&index_path(&repo, session_id))?;
+    }
+    Ok(())
+}
+
+fn open(worktree: &Path) -> Result<Repository> {
+    crate::git::open_repo_at(worktree)
+        .with_context(|| format!("{} is not a git repository", worktree.display()))
+}
+
+/// Write the current worktree, untracked files included, as a tree.
+/// Builds on a per-session index kept beside the repo's own, so files
+/// unchanged since the last prompt hit its stat cache instead of being
+/// rehashed. The first snapshot seeds it from the real index, which is
+/// never written.
+fn snapshot_tree<'r>(repo: &'r Repository, session_id: &str) -> Result<git2::Tree<'r>> {
+    let path = index_path(repo, session_id);
+    let seeded = path.exists();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    let mut index = Index::open(&path)?;
+    if !seeded {
+        for entry in repo.index()?.iter() {
+            index.add(&entry)?;
+        }
+    }
+    repo.set_index(&mut index)?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    let oid = index.write_tree()?;
+    index.write()?;
+    Ok(repo.find_tree(oid)?)
+}
+
+/// The session's snapshot index, under the git dir so it's never tracked.
+fn index_path(repo: &Repository, session_id: &str) -> PathBuf {
+    repo.path()
+        .join("aoe")
+        .join("checkpoints")
+        .join(format!("{session_id}.index"))
+}
+
+fn ref_name(session_id: &str, seq: u64) -> String {
+    format!("{REF_PREFIX}/{session_id}/{seq}")
+}
+
+fn summarize(prompt: &str) -> String {
+    let line = prompt.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let line = line.trim();
+    if line.chars().count() > MAX_SUMMARY_CHARS {
+        let clipped: String = line.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+        format!("{clipped}…")
+    } else {
+        line.to_string()
+    }
+}
+
+fn remove_file(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("remove {}", path.display())),
+    }
+}
+
+/// Remove the now-empty directories a deleted `rel` path leaves behind,
+/// stopping at the first non-empty one.
+fn prune_empty_dirs(root: &Path, rel: &Path) {
+    let mut dir = rel.parent();
+    while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+        if std::fs::remove_dir(root.join(d)).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+}
+
+fn write_file(path: &Path, content: &[u8], mode: git2::FileMode) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    // Replace rather than write through: the path may currently be a
+    // symlink (or a file where the checkpoint had a symlink).
+    if std::fs::symlink_metadata(path).is_ok() {
+        remove_file(path)?;
+    }
+    #[cfg(unix)]
+    if mode == git2::FileMode::Link {
+        let target = std::ffi::OsStr::from_bytes(content);
+        return std::os::unix::fs::symlink(target, path)
+            .with_context(|| format!("symlink {}", path.display()));
+    }
+    std::fs::write(path, content).with_context(|| format!("write {}", path.display()))?;
+    #[cfg(unix)]
+    if mode == git2::FileMode::BlobExecutable {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("chmod {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn init_repo() -> (TempDir, Repository) {
+        let tmp = TempDir::new().unwrap();
+        let repo = Repository::init(tmp.path()).unwrap();
+        fs::write(tmp.path().join("a.txt"), "one\n").unwrap();
+        fs::write(tmp.path().join(".gitignore"), "target/\n").unwrap();
+        {
+            let mut index = repo.index().unwrap();
+            index.add_all(["*"], IndexAddOption::DEFAULT, None).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = Signature::now("t", "t@example.com").unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+                .unwrap();
+        }
+        (tmp, repo)
+    }
+
+    #[test]
+    fn restore_undoes_edits_creations_and_deletions() {
+        let (tmp, repo) = init_repo();
+        let root = tmp.path();
+        fs::write(root.join("untracked.txt"), "keep me\n").unwrap();
+        let head_before = repo.head().unwrap().target().unwrap();
+
+        record(root, "s1", 4, "refactor everything").unwrap();
+
+        fs::write(root.join("a.txt"), "wrecked\n").unwrap();
+        fs::remove_file(root.join("untracked.txt")).unwrap();
+        fs::create_dir_all(root.join("new/dir")).unwrap();
+        fs::write(root.join("new/dir/b.txt"), "junk\n").unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("target/out.bin"), "build\n").unwrap();
+
+        let checkpoints = list(root, "s1").unwrap();
+        restore(root, "s1", &checkpoints[0]).unwrap();
+
+        assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "one\n");
+        assert_eq!(
+            fs::read_to_string(root.join("untracked.txt")).unwrap(),
+            "keep me\n"
+        );
+        assert!(!root.join("new").exists());
+        // Ignored files are outside the snapshot and left alone.
+        assert!(root.join("target/out.bin").exists());
+        // HEAD and the real index are untouched.
+        assert_eq!(repo.head().unwrap().target().unwrap(), head_before);
+        let index = repo.index().unwrap();
+        assert!(index.get_path(Path::new("untracked.txt"), 0).is_none());
+    }
+
+    #[test]
+    fn snapshot_index_persists_and_tracks_later_edits() {
+        let (tmp, repo) = init_repo();
+        let root = tmp.path();
+        fs::write(root.join("untracked.txt"), "v1\n").unwrap();
+        record(root, "s1", 1, "first").unwrap();
+
+        let cached = Index::open(&index_path(&repo, "s1")).unwrap();
+        assert!(cached.get_path(Path::new("untracked.txt"), 0).is_some());
+
+        fs::write(root.join("untracked.txt"), "version two\n").unwrap();
+        fs::remove_file(root.join("a.txt")).unwrap();
+        record(root, "s1", 2, "second").unwrap();
+        let latest = list(root, "s1").unwrap().pop().unwrap();
+        let tree = repo
+            .find_commit(Oid::from_str(&latest.commit).unwrap())
+            .unwrap()
+            .tree()
+            .unwrap();
+        let blob = tree
+            .get_path(Path::new("untracked.txt"))
+            .unwrap()
+            .to_object(&repo)
+            .unwrap()
+            .peel_to_blob()
+            .unwrap();
+        assert_eq!(blob.content(), b"version two\n");
+        assert!(tree.get_path(Path::new("a.txt")).is_err());
+
+        discard_from(root, "s1", 0).unwrap();
+        assert!(!index_path(&repo, "s1").exists());
+    }
+
+    #[test]
+    fn list_numbers_turns_by_seq_and_discard_drops_later_turns() {
+        let (tmp, _repo) = init_repo();
+        let root = tmp.path();
+        record(root, "s1", 12, "second\nmore detail").unwrap();
+        record(root, "s1", 3, "first").unwrap();
+        record(root, "s1", 20, "third").unwrap();
+        record(root, "other", 5, "elsewhere").unwrap();
+
+        let checkpoints = list(root, "s1").unwrap();
+        let summary: Vec<(usize, u64, &str)> = checkpoints
+            .iter()
+            .map(|c| (c.turn, c.seq, c.prompt.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(1, 3, "first"), (2, 12, "second"), (3, 20, "third")]
+        );
+
+        discard_from(root, "s1", 12).unwrap();
+        let seqs: Vec<u64> = list(root, "s1").unwrap().iter().map(|c| c.seq).collect();
+        assert_eq!(seqs, vec![3]);
+        assert_eq!(list(root, "other").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn summarize_clips_to_first_nonblank_line() {
+        assert_eq!(summarize("\n  fix the bug  \nsecond"), "fix the bug");
+        let long = "x".repeat(100);
+        assert_eq!(summarize(&long).chars().count(), MAX_SUMMARY_CHARS);
+    }
+
+    #[test]
+    fn record_outside_a_repo_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        assert!(record(tmp.path(), "s1", 1, "hi").is_err());
+    }
+}
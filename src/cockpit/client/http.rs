@@ -12,12 +12,21 @@ use reqwest::{header, StatusCode};
 use thiserror::Error;
 
 use super::discovery::DaemonEndpoint;
+use crate::cockpit::checkpoints::Checkpoint;
 use crate::cockpit::protocol::{
-    ApprovalDecisionWire, ContextPrimerResponse, PromptRequest, ReplayResponse,
-    ResolveApprovalRequest,
+    ApprovalDecisionWire, ContextPrimerResponse, HandoffRequest, HandoffResponse, PromptRequest,
+    ReplayResponse, ResolveApprovalRequest, RewindRequest, RewindResponse,
 };
+use crate::cockpit::retention::GcReport;
+use crate::cockpit::usage::UsageReport;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Rewind and handoff stop and respawn the worker, which alone can
+/// take most of `DEFAULT_TIMEOUT`.
+const RESPAWN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A gc pass over a large event log can run for minutes.
+const GC_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 
 /// Cockpit daemon HTTP client. Cheap to clone; the underlying
 /// `reqwest::Client` is reference-counted.
@@ -99,6 +108,59 @@ impl HttpClient {
         Ok(())
     }
 
+    /// `GET /api/sessions/{id}/cockpit/checkpoints`.
+    pub async fn checkpoints(&self, session_id: &str) -> Result<Vec<Checkpoint>, HttpError> {
+        let url = format!(
+            "{}/api/sessions/{}/cockpit/checkpoints",
+            self.endpoint.base_url, session_id
+        );
+        let res = self.auth(self.http.get(&url)).send().await?;
+        let res = check_status(res, session_id).await?;
+        Ok(res.json::<Vec<Checkpoint>>().await?)
+    }
+
+    /// `POST /api/sessions/{id}/cockpit/rewind`.
+    pub async fn rewind(
+        &self,
+        session_id: &str,
+        turn: usize,
+        prime: bool,
+    ) -> Result<RewindResponse, HttpError> {
+        let url = format!(
+            "{}/api/sessions/{}/cockpit/rewind",
+            self.endpoint.base_url, session_id
+        );
+        let body = RewindRequest { turn, prime };
+        let res = self
+            .auth(self.http.post(&url))
+            .timeout(RESPAWN_TIMEOUT)
+            .json(&body)
+            .send()
+            .await?;
+        let res = check_status(res, session_id).await?;
+        Ok(res.json::<RewindResponse>().await?)
+    }
+
+    /// `POST /api/sessions/{id}/cockpit/handoff`.
+    pub async fn handoff(
+        &self,
+        session_id: &str,
+        body: &HandoffRequest,
+    ) -> Result<HandoffResponse, HttpError> {
+        let url = format!(
+            "{}/api/sessions/{}/cockpit/handoff",
+            self.endpoint.base_url, session_id
+        );
+        let res = self
+            .auth(self.http.post(&url))
+            .timeout(RESPAWN_TIMEOUT)
+            .json(body)
+            .send()
+            .await?;
+        let res = check_status(res, session_id).await?;
+        Ok(res.json::<HandoffResponse>().await?)
+    }
+
     /// `POST /api/sessions/{id}/cockpit/cancel`.
     pub async fn cancel(&self, session_id: &str) -> Result<(), HttpError> {
         let url = format!(
@@ -139,6 +201,27 @@ impl HttpClient {
         Ok(res.json::<Vec<T>>().await?)
     }
 
+    /// `GET /api/cockpit/usage`.
+    pub async fn usage(&self) -> Result<UsageReport, HttpError> {
+        let url = format!("{}/api/cockpit/usage", self.endpoint.base_url);
+        let res = self.auth(self.http.get(&url)).send().await?;
+        let res = check_status(res, "<usage>").await?;
+        Ok(res.json::<UsageReport>().await?)
+    }
+
+    /// `POST /api/cockpit/gc`.
+    pub async fn gc(&self, dry_run: bool) -> Result<GcReport, HttpError> {
+        let url = format!("{}/api/cockpit/gc", self.endpoint.base_url);
+        let res = self
+            .auth(self.http.post(&url))
+            .timeout(GC_TIMEOUT)
+            .json(&serde_json::json!({ "dry_run": dry_run }))
+            .send()
+            .await?;
+        let res = check_status(res, "<gc>").await?;
+        Ok(res.json::<GcReport>().await?)
+    }
+
     /// Lightweight reachability probe used by `require_daemon` (when
     /// `AOE_DAEMON_URL` is set, we fail loud before falling into raw
     /// reqwest transport errors) and `aoe serve --status` (renders
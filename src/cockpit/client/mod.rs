@@ -28,6 +28,6 @@ pub mod http;
 pub mod ws;
 
 pub use daemon_manager::{require_daemon, ManagerError};
-pub use discovery::{discover, DaemonEndpoint, DiscoveryError, Source};
+pub use discovery::{discover, discover_local, DaemonEndpoint, DiscoveryError, Source};
 pub use http::{HttpClient, HttpError};
 pub use ws::{connect as ws_connect, WsError, WsHandle, WsMessage};
@@ -18,7 +18,7 @@
 //!   - Drop `ThinkingStarted`/`ThinkingEnded`/`UsageUpdated`/mode events
 //!     and other ambient noise.
 
-use super::state::{Event, ToolCall};
+use super::state::{Event, Plan, PlanStepStatus, ToolCall};
 
 pub const DEFAULT_MAX_PRIMER_CHARS: usize = 24_000;
 pub const DEFAULT_MAX_PRIMER_TURNS: usize = 20;
@@ -68,6 +68,10 @@ pub struct PrimerOptions {
     pub before_seq: Option<u64>,
     pub max_chars: usize,
     pub max_turns: usize,
+    pub framing: PrimerFraming,
+    /// Latest plan, rendered after the transcript so it survives even
+    /// when the turn that produced it was dropped for budget.
+    pub current_plan: Option<Plan>,
 }
 
 impl Default for PrimerOptions {
@@ -76,10 +80,30 @@ impl Default for PrimerOptions {
             before_seq: None,
             max_chars: DEFAULT_MAX_PRIMER_CHARS,
             max_turns: DEFAULT_MAX_PRIMER_TURNS,
+            framing: PrimerFraming::default(),
+            current_plan: None,
         }
     }
 }
 
+/// Why the agent is being primed; picks the header and footer wording.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PrimerFraming {
+    /// `session/load` failed; the recap is prefilled into the composer
+    /// ahead of the user's next request.
+    #[default]
+    LoadFailed,
+    /// The session was rewound to a checkpoint; the recap is sent on its
+    /// own, so the agent should acknowledge it and wait.
+    Rewind,
+    /// The session moved to a different agent. `request` is what the
+    /// new agent should do next; None asks it to carry on.
+    Handoff {
+        from: String,
+        request: Option<String>,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct ContextPrimer {
     pub text: String,
@@ -342,8 +366,13 @@ pub fn build_context_primer(events: &[(u64, Event)], opts: PrimerOptions) -> Con
         bodies.push(render_turn_body(turn));
     }
 
-    let header = render_primer_header();
-    let footer = render_primer_footer();
+    let header = render_primer_header(&opts.framing);
+    let plan_section = opts
+        .current_plan
+        .as_ref()
+        .map(render_plan_section)
+        .unwrap_or_default();
+    let footer = format!("{plan_section}{}", render_primer_footer(&opts.framing));
     let transcript_heading = "## Transcript\n\n";
     let truncation_notice = "_Older transcript entries were omitted to fit the primer budget._\n\n";
     // Reserve space for every fixed-shape string we will write before
@@ -463,19 +492,61 @@ fn push_tool_start(turn: &mut Turn, tool: &ToolCall) {
     turn.tools.insert(tool.id.clone(), summary);
 }
 
-fn render_primer_header() -> String {
-    String::from(
+fn render_primer_header(framing: &PrimerFraming) -> String {
+    let why = match framing {
+        PrimerFraming::LoadFailed => "The previous ACP session could not be loaded".to_string(),
+        PrimerFraming::Rewind => {
+            "The conversation was rewound to an earlier point and the workspace files were restored to match".to_string()
+        }
+        PrimerFraming::Handoff { from, .. } => format!(
+            "You are taking over this session from another coding agent ({from}) working in the same workspace"
+        ),
+    };
+    format!(
         "# Prior cockpit context\n\
          \n\
-         The previous ACP session could not be loaded, so you have no memory of the conversation below. \
+         {why}, so you have no memory of the conversation below. \
          Use the transcript excerpt as background context for the current request. \
          Do not repeat it back unless asked.\n\
-         \n",
+         \n"
     )
 }
 
-fn render_primer_footer() -> String {
-    String::from("\n---\n\n## Current request\n\nContinue from where we left off.\n")
+fn render_primer_footer(framing: &PrimerFraming) -> String {
+    match framing {
+        PrimerFraming::LoadFailed => {
+            String::from("\n---\n\n## Current request\n\nContinue from where we left off.\n")
+        }
+        PrimerFraming::Rewind => String::from(
+            "\n---\n\nReply with a one-line acknowledgement and wait for the next request.\n",
+        ),
+        PrimerFraming::Handoff { request, .. } => {
+            let request = request
+                .as_deref()
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .unwrap_or("Continue the work from where the previous agent left off.");
+            format!("\n---\n\n## Current request\n\n{request}\n")
+        }
+    }
+}
+
+fn render_plan_section(plan: &Plan) -> String {
+    if plan.steps.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("## Current plan\n\n");
+    for step in &plan.steps {
+        let marker = match step.status {
+            PlanStepStatus::Done => "[x]",
+            PlanStepStatus::InProgress => "[~]",
+            PlanStepStatus::Pending => "[ ]",
+            PlanStepStatus::Cancelled => "[/]",
+        };
+        out.push_str(&format!("- {marker} {}\n", step.title.trim()));
+    }
+    out.push('\n');
+    out
 }
 
 fn render_turn_body(turn: &Turn) -> String {
@@ -777,6 +848,63 @@ mod tests {
         assert!(!primer.truncated);
     }
 
+    #[test]
+    fn rewind_framing_asks_the_agent_to_wait() {
+        let events = vec![
+            user_event(1, "first"),
+            assistant_event(2, "first reply"),
+            stopped_event(3),
+        ];
+        let opts = PrimerOptions {
+            framing: PrimerFraming::Rewind,
+            ..PrimerOptions::default()
+        };
+        let primer = build_context_primer(&events, opts);
+        assert!(primer.text.contains("rewound to an earlier point"));
+        assert!(primer.text.contains("wait for the next request"));
+        assert!(!primer.text.contains("## Current request"));
+        assert!(primer.text.contains("first reply"));
+    }
+
+    #[test]
+    fn handoff_framing_names_the_previous_agent_and_carries_the_plan() {
+        let events = vec![
+            user_event(1, "port the parser"),
+            assistant_event(2, "halfway there"),
+            stopped_event(3),
+        ];
+        let step = |id: &str, title: &str, status| PlanStep {
+            id: id.into(),
+            title: title.into(),
+            detail: None,
+            status,
+        };
+        let opts = PrimerOptions {
+            framing: PrimerFraming::Handoff {
+                from: "claude".into(),
+                request: Some("Review the diff before continuing.".into()),
+            },
+            current_plan: Some(Plan {
+                plan_id: "p".into(),
+                version: 2,
+                steps: vec![
+                    step("1", "lexer", PlanStepStatus::Done),
+                    step("2", "parser", PlanStepStatus::InProgress),
+                ],
+            }),
+            ..PrimerOptions::default()
+        };
+        let primer = build_context_primer(&events, opts);
+        assert!(primer.text.contains("another coding agent (claude)"));
+        assert!(primer
+            .text
+            .contains("## Current plan\n\n- [x] lexer\n- [~] parser\n"));
+        assert!(primer
+            .text
+            .ends_with("## Current request\n\nReview the diff before continuing.\n"));
+        assert!(primer.text.find("halfway there") < primer.text.find("## Current plan"));
+    }
+
     #[test]
     fn before_seq_filters_out_post_reset_events() {
         let events = vec![
@@ -936,6 +1064,7 @@ mod tests {
                         destructive: false,
                         requested_at: Utc::now(),
                         resolved: None,
+                        policy_rule: None,
                     },
                 },
             ),
@@ -45,17 +45,53 @@
 //! `cockpit_disable` (the master switch turning off, or a per-session
 //! opt-out). The connection has WAL mode enabled so the publish path
 //! and the replay endpoint don't block each other under load.
+//!
+//! ## Retention
+//!
+//! Past the per-session row cap, the log is trimmed by [`EventStore::gc`]:
+//! tool-call compaction, orphaned sessions, and the per-profile age and
+//! size limits described in [`super::retention`]. New databases use
+//! incremental auto-vacuum so a pass gives the freed pages back to the
+//! filesystem; older ones are converted once in the background after
+//! `aoe serve` starts ([`EventStore::migrate_auto_vacuum`]), never by a
+//! gc pass that may run next to a live daemon.
+//!
+//! ## Usage time series
+//!
+//! The `cockpit_usage` table holds the samples behind
+//! `GET /api/cockpit/usage` (see [`super::usage`]). They outlive the
+//! session they came from, because a deleted session's turns still
+//! count against the provider's quota, and are pruned by age instead.
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
-use tracing::{debug, trace, warn};
+use tracing::{debug, info, trace, warn};
 
 use super::approvals::Nonce;
+use super::retention::{GcPlan, GcReport, ORPHAN_GRACE};
 use super::state::{Event, Plan};
+use super::usage::{SampleKind, UsageSample, RETENTION};
+
+/// Snapshot events the agent sends once per session lifecycle; the row
+/// cap and retention never drop them (see `EventStore::record`). Each
+/// clause matches the externally-tagged JSON discriminant of a variant.
+const UNPINNED: &str = "event_json NOT LIKE '{\"AvailableCommandsUpdated\":%'
+       AND event_json NOT LIKE '{\"ModesAvailable\":%'
+       AND event_json NOT LIKE '{\"CurrentModeChanged\":%'
+       AND event_json NOT LIKE '{\"AcpSessionAssigned\":%'";
+
+/// Rows a gc pass deletes or rewrites per transaction.
+const GC_BATCH: usize = 500;
+
+/// How long a writer waits for another connection's lock (a gc pass
+/// from `aoe cockpit gc` while the daemon records) before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// SQLite-backed cockpit event log. One row per (session_id, seq).
 pub struct EventStore {
@@ -65,6 +101,8 @@ pub struct EventStore {
     /// (the in-memory ring still has a byte cap); the row count keeps
     /// the on-disk size bounded.
     max_events_per_session: usize,
+    /// Held for a whole gc pass, which releases `conn` between batches.
+    gc_pass: Mutex<()>,
 }
 
 impl EventStore {
@@ -82,6 +120,12 @@ impl EventStore {
         }
         let conn = Connection::open(db_path)
             .with_context(|| format!("open cockpit DB at {}", db_path.display()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .context("set busy timeout")?;
+        // Only takes effect before the first table is created; existing
+        // databases are converted by `migrate_auto_vacuum`.
+        conn.pragma_update(None, "auto_vacuum", "INCREMENTAL")
+            .context("enable incremental auto-vacuum")?;
         conn.pragma_update(None, "journal_mode", "WAL")
             .context("enable WAL mode")?;
         conn.pragma_update(None, "synchronous", "NORMAL")
@@ -95,7 +139,22 @@ impl EventStore {
                 PRIMARY KEY (session_id, seq)
             );
             CREATE INDEX IF NOT EXISTS idx_cockpit_events_session_seq
-                ON cockpit_events(session_id, seq);",
+                ON cockpit_events(session_id, seq);
+            CREATE TABLE IF NOT EXISTS cockpit_usage (
+                session_id    TEXT    NOT NULL,
+                agent         TEXT    NOT NULL,
+                provider      TEXT    NOT NULL,
+                recorded_at   INTEGER NOT NULL,
+                kind          TEXT    NOT NULL,
+                context_used  INTEGER,
+                context_size  INTEGER,
+                cost          REAL,
+                currency      TEXT,
+                status        TEXT,
+                resets_at     INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_cockpit_usage_recorded_at
+                ON cockpit_usage(recorded_at);",
         )
         .context("create cockpit_events schema")?;
         debug!(
@@ -107,6 +166,7 @@ impl EventStore {
         Ok(Self {
             conn: Mutex::new(conn),
             max_events_per_session,
+            gc_pass: Mutex::new(()),
         })
     }
 
@@ -167,22 +227,20 @@ impl EventStore {
         // session lifecycle, near the start of the seq range, so a long
         // session blows past the cap and evicts them; leaving the
         // composer's `/` palette and the mode picker empty on reconnect.
-        // See #1049. The `event_json NOT LIKE` clauses match the
-        // externally-tagged JSON discriminant for each pinned variant.
+        // See #1049. [`UNPINNED`] lists the pinned variants.
         if self.max_events_per_session > 0 {
             match conn.execute(
-                "DELETE FROM cockpit_events
-                 WHERE session_id = ?1
-                   AND seq <= (
-                     SELECT seq FROM cockpit_events
+                &format!(
+                    "DELETE FROM cockpit_events
                      WHERE session_id = ?1
-                     ORDER BY seq DESC
-                     LIMIT 1 OFFSET ?2
-                   )
-                   AND event_json NOT LIKE '{\"AvailableCommandsUpdated\":%'
-                   AND event_json NOT LIKE '{\"ModesAvailable\":%'
-                   AND event_json NOT LIKE '{\"CurrentModeChanged\":%'
-                   AND event_json NOT LIKE '{\"AcpSessionAssigned\":%'",
+                       AND seq <= (
+                         SELECT seq FROM cockpit_events
+                         WHERE session_id = ?1
+                         ORDER BY seq DESC
+                         LIMIT 1 OFFSET ?2
+                       )
+                       AND {UNPINNED}"
+                ),
                 params![session_id, self.max_events_per_session as i64],
             ) {
                 Ok(0) => {}
@@ -806,6 +864,550 @@ impl EventStore {
             }
         }
     }
+
+    /// Append one usage sample and prune samples older than
+    /// [`RETENTION`].
+    pub fn record_usage(&self, sample: &UsageSample) -> Result<()> {
+        let conn = match self.conn.lock() {
+            Ok(g) => g,
+            Err(p) => p.into_inner(),
+        };
+        conn.execute(
+            "INSERT INTO cockpit_usage (session_id, agent, provider, recorded_at, kind,
+                 context_used, context_size, cost, currency, status, resets_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                sample.session_id,
+                sample.agent,
+                sample.provider,
+                sample.recorded_at.timestamp_millis(),
+                sample.kind.as_str(),
+                sample.context_used.map(|n| n as i64),
+                sample.context_size.map(|n| n as i64),
+                sample.cost,
+                sample.currency,
+                sample.status,
+                sample.resets_at.map(|t| t.timestamp_millis()),
+            ],
+        )
+        .with_context(|| format!("insert usage sample for {}", sample.session_id))?;
+        let cutoff = (sample.recorded_at - RETENTION).timestamp_millis();
+        if let Err(e) = conn.execute(
+            "DELETE FROM cockpit_usage WHERE recorded_at < ?1",
+            params![cutoff],
+        ) {
+            warn!(target: "cockpit.event_store", "prune usage samples: {e}");
+        }
+        Ok(())
+    }
+
+    /// Every usage sample recorded after `since`, oldest first.
+    pub fn usage_since(&self, since: DateTime<Utc>) -> Vec<UsageSample> {
+        let conn = match self.conn.lock() {
+            Ok(g) => g,
+            Err(p) => p.into_inner(),
+        };
+        let mut stmt = match conn.prepare(
+            "SELECT session_id, agent, provider, recorded_at, kind, context_used,
+                    context_size, cost, currency, status, resets_at
+             FROM cockpit_usage
+             WHERE recorded_at > ?1
+             ORDER BY recorded_at ASC",
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!(target: "cockpit.event_store", "prepare usage query: {e}");
+                return Vec::new();
+            }
+        };
+        let rows = stmt.query_map(params![since.timestamp_millis()], |row| {
+            let recorded_at: i64 = row.get(3)?;
+            let kind: String = row.get(4)?;
+            let (Some(recorded_at), Some(kind)) = (
+                DateTime::from_timestamp_millis(recorded_at),
+                SampleKind::parse(&kind),
+            ) else {
+                return Ok(None);
+            };
+            let resets_at: Option<i64> = row.get(10)?;
+            Ok(Some(UsageSample {
+                session_id: row.get(0)?,
+                agent: row.get(1)?,
+                provider: row.get(2)?,
+                recorded_at,
+                kind,
+                context_used: row.get::<_, Option<i64>>(5)?.map(|n| n as u64),
+                context_size: row.get::<_, Option<i64>>(6)?.map(|n| n as u64),
+                cost: row.get(7)?,
+                currency: row.get(8)?,
+                status: row.get(9)?,
+                resets_at: resets_at.and_then(DateTime::from_timestamp_millis),
+            }))
+        });
+        match rows {
+            Ok(rows) => rows.filter_map(|r| r.ok().flatten()).collect(),
+            Err(e) => {
+                warn!(target: "cockpit.event_store", "query usage samples: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Switch a database created before incremental auto-vacuum over to
+    /// it with a one-off full `VACUUM`. Rewrites the whole file and holds
+    /// the connection throughout, so events recorded meanwhile wait; `aoe
+    /// serve` runs it once on a blocking task after startup, and nothing
+    /// else calls it. A no-op once converted.
+    pub fn migrate_auto_vacuum(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let mode: i64 = conn.pragma_query_value(None, "auto_vacuum", |row| row.get(0))?;
+        if mode == 2 {
+            return Ok(());
+        }
+        info!(target: "cockpit.event_store", "converting cockpit event log to incremental auto-vacuum");
+        conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;")
+            .context("vacuum cockpit event log")?;
+        Ok(())
+    }
+
+    /// Compact and trim the log as `plan` says (see
+    /// [`super::retention`]). Each step plans its changes with reads,
+    /// then applies them [`GC_BATCH`] rows per transaction, releasing the
+    /// connection in between so live sessions keep recording. With
+    /// `dry_run` nothing is written; later steps skip the rows earlier
+    /// ones would have removed, so the report shows what a real pass
+    /// would remove.
+    pub fn gc(&self, plan: &GcPlan, now: DateTime<Utc>, dry_run: bool) -> Result<GcReport> {
+        let _pass = self.gc_pass.lock().unwrap_or_else(|e| e.into_inner());
+        let mut report = GcReport {
+            dry_run,
+            db_bytes_before: db_bytes(&self.lock_conn()),
+            ..Default::default()
+        };
+        let mut pending = Pending::default();
+
+        if let Some(known) = &plan.known_sessions {
+            let cutoff = (now - ORPHAN_GRACE).timestamp_millis();
+            let orphans: Vec<String> = {
+                let conn = self.lock_conn();
+                let mut stmt = conn.prepare(
+                    "SELECT session_id, MAX(created_at) FROM cockpit_events GROUP BY session_id",
+                )?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })?;
+                rows.filter_map(|r| r.ok())
+                    .filter(|(id, newest)| !known.contains(id) && *newest < cutoff)
+                    .map(|(id, _)| id)
+                    .collect()
+            };
+            for id in orphans {
+                let prune =
+                    select_rows(&self.lock_conn(), "session_id = ?1", params![id], &pending)?;
+                report.orphaned_sessions += 1;
+                report.orphaned_events += prune.rows();
+                report.event_bytes_removed += prune.bytes();
+                self.apply(prune, dry_run, &mut pending)?;
+            }
+        }
+
+        let prune = compact_tool_calls(&self.lock_conn(), &pending)?;
+        report.compacted_events = prune.rows();
+        report.event_bytes_removed += prune.bytes();
+        self.apply(prune, dry_run, &mut pending)?;
+
+        for profile in &plan.profiles {
+            {
+                let conn = self.lock_conn();
+                conn.execute_batch(
+                    "CREATE TEMP TABLE IF NOT EXISTS gc_scope (session_id TEXT PRIMARY KEY);
+                     DELETE FROM temp.gc_scope;",
+                )?;
+                let mut insert =
+                    conn.prepare("INSERT OR IGNORE INTO temp.gc_scope (session_id) VALUES (?1)")?;
+                for id in &profile.sessions {
+                    insert.execute(params![id])?;
+                }
+            }
+            if profile.max_age_days > 0 {
+                let cutoff = (now - chrono::Duration::days(i64::from(profile.max_age_days)))
+                    .timestamp_millis();
+                let prune = select_rows(
+                    &self.lock_conn(),
+                    &format!(
+                        "session_id IN (SELECT session_id FROM temp.gc_scope)
+                         AND created_at < ?1 AND {UNPINNED}"
+                    ),
+                    params![cutoff],
+                    &pending,
+                )?;
+                report.expired_events += prune.rows();
+                report.event_bytes_removed += prune.bytes();
+                self.apply(prune, dry_run, &mut pending)?;
+            }
+            if profile.max_bytes > 0 {
+                let prune = trim_to_budget(&self.lock_conn(), profile.max_bytes, &pending)?;
+                report.over_budget_events += prune.rows();
+                report.event_bytes_removed += prune.bytes();
+                self.apply(prune, dry_run, &mut pending)?;
+            }
+            debug!(
+                target: "cockpit.event_store",
+                profile = %profile.profile,
+                sessions = profile.sessions.len(),
+                "applied retention limits"
+            );
+        }
+
+        let conn = self.lock_conn();
+        if !dry_run && report.events_removed() > 0 {
+            reclaim_space(&conn);
+        }
+        report.db_bytes_after = db_bytes(&conn);
+        debug!(
+            target: "cockpit.event_store",
+            dry_run,
+            removed = report.events_removed(),
+            bytes = report.event_bytes_removed,
+            "gc pass finished"
+        );
+        Ok(report)
+    }
+
+    /// Write one gc step's changes in [`GC_BATCH`]-row transactions, or
+    /// with `dry_run` just note them in `pending`.
+    fn apply(&self, prune: Prune, dry_run: bool, pending: &mut Pending) -> Result<()> {
+        if dry_run {
+            pending
+                .removed
+                .extend(prune.delete.iter().map(|(rowid, _)| *rowid));
+            pending.saved.extend(
+                prune
+                    .rewrite
+                    .iter()
+                    .map(|(rowid, _, saved)| (*rowid, *saved)),
+            );
+            return Ok(());
+        }
+        for batch in prune.delete.chunks(GC_BATCH) {
+            let mut conn = self.lock_conn();
+            let tx = conn.transaction().context("begin gc batch")?;
+            {
+                let mut delete = tx.prepare("DELETE FROM cockpit_events WHERE rowid = ?1")?;
+                for (rowid, _) in batch {
+                    delete.execute(params![rowid])?;
+                }
+            }
+            tx.commit().context("commit gc batch")?;
+        }
+        for batch in prune.rewrite.chunks(GC_BATCH) {
+            let mut conn = self.lock_conn();
+            let tx = conn.transaction().context("begin gc batch")?;
+            {
+                let mut update =
+                    tx.prepare("UPDATE cockpit_events SET event_json = ?2 WHERE rowid = ?1")?;
+                for (rowid, json, _) in batch {
+                    update.execute(params![rowid, json])?;
+                }
+            }
+            tx.commit().context("commit gc batch")?;
+        }
+        Ok(())
+    }
+
+    fn lock_conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Rows one gc step removes or rewrites.
+#[derive(Default)]
+struct Prune {
+    /// `(rowid, bytes)` of rows to delete.
+    delete: Vec<(i64, u64)>,
+    /// `(rowid, event_json, bytes saved)` of rows to rewrite.
+    rewrite: Vec<(i64, String, u64)>,
+}
+
+impl Prune {
+    /// Events the step removes.
+    fn rows(&self) -> u64 {
+        self.delete.len() as u64
+    }
+
+    fn bytes(&self) -> u64 {
+        self.delete.iter().map(|(_, bytes)| bytes).sum::<u64>()
+            + self.rewrite.iter().map(|(_, _, saved)| saved).sum::<u64>()
+    }
+}
+
+/// What the earlier steps of a dry run would have changed. Always empty
+/// on a real pass, where those changes are already written.
+#[derive(Default)]
+struct Pending {
+    removed: HashSet<i64>,
+    /// Bytes saved per rewritten row.
+    saved: HashMap<i64, u64>,
+}
+
+impl Pending {
+    /// Stored size of a row after the earlier steps, `None` if removed.
+    fn bytes(&self, rowid: i64, bytes: u64) -> Option<u64> {
+        if self.removed.contains(&rowid) {
+            return None;
+        }
+        Some(bytes.saturating_sub(self.saved.get(&rowid).copied().unwrap_or(0)))
+    }
+}
+
+/// The rows matching `filter`, with their stored size.
+fn select_rows<P: rusqlite::Params>(
+    conn: &Connection,
+    filter: &str,
+    params: P,
+    pending: &Pending,
+) -> Result<Prune> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT rowid, LENGTH(CAST(event_json AS BLOB)) FROM cockpit_events WHERE {filter}"
+    ))?;
+    let mut rows = stmt.query(params)?;
+    let mut prune = Prune::default();
+    while let Some(row) = rows.next()? {
+        let rowid: i64 = row.get(0)?;
+        if let Some(bytes) = pending.bytes(rowid, row.get::<_, i64>(1)? as u64) {
+            prune.delete.push((rowid, bytes));
+        }
+    }
+    Ok(prune)
+}
+
+/// The oldest unpinned events of the sessions in `temp.gc_scope` that
+/// have to go for their stored size to fit in `max_bytes`.
+fn trim_to_budget(conn: &Connection, max_bytes: u64, pending: &Pending) -> Result<Prune> {
+    let mut total = 0u64;
+    {
+        let mut stmt = conn.prepare(
+            "SELECT rowid, LENGTH(CAST(event_json AS BLOB)) FROM cockpit_events
+             WHERE session_id IN (SELECT session_id FROM temp.gc_scope)",
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            total += pending
+                .bytes(row.get(0)?, row.get::<_, i64>(1)? as u64)
+                .unwrap_or(0);
+        }
+    }
+    let mut excess = total.saturating_sub(max_bytes);
+    let mut prune = Prune::default();
+    if excess == 0 {
+        return Ok(prune);
+    }
+    let mut stmt = conn.prepare(&format!(
+        "SELECT rowid, LENGTH(CAST(event_json AS BLOB)) FROM cockpit_events
+         WHERE session_id IN (SELECT session_id FROM temp.gc_scope) AND {UNPINNED}
+         ORDER BY created_at ASC, seq ASC"
+    ))?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        if excess == 0 {
+            break;
+        }
+        let rowid: i64 = row.get(0)?;
+        let Some(bytes) = pending.bytes(rowid, row.get::<_, i64>(1)? as u64) else {
+            continue;
+        };
+        prune.delete.push((rowid, bytes));
+        excess = excess.saturating_sub(bytes);
+    }
+    Ok(prune)
+}
+
+/// Rows of one completed tool call that compaction looks at.
+#[derive(Default)]
+struct ToolCallRows {
+    /// Seq of the `ToolCallCompleted`, and whether it carried content.
+    completed: Option<(i64, bool)>,
+    /// `ToolCallContent` rows as `(seq, rowid, bytes)`.
+    contents: Vec<(i64, i64, u64)>,
+    /// `ToolCallUpdated` rows as `(rowid, bytes)`.
+    updates: Vec<(i64, u64)>,
+}
+
+/// Collapse the update streams of completed tool calls.
+///
+/// `ToolCallContent` carries the full output so far, so only the last
+/// snapshot before completion matters, and none does when the
+/// completion ships its own content (clients prefer it). Snapshots after
+/// the completion are left alone. `ToolCallUpdated` patches overwrite
+/// the card field by field, so they merge into the last one, keeping its
+/// seq. Calls still running are untouched: a client may be rendering
+/// them live.
+fn compact_tool_calls(conn: &Connection, pending: &Pending) -> Result<Prune> {
+    let mut calls: HashMap<(String, String), ToolCallRows> = HashMap::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT rowid, session_id, seq, LENGTH(CAST(event_json AS BLOB)),
+                    json_extract(event_json, '$.ToolCallContent.tool_call_id'),
+                    json_extract(event_json, '$.ToolCallUpdated.tool_call_id'),
+                    json_extract(event_json, '$.ToolCallCompleted.tool_call_id'),
+                    json_extract(event_json, '$.ToolCallCompleted.content') <> ''
+             FROM cockpit_events
+             WHERE event_json LIKE '{\"ToolCallContent\":%'
+                OR event_json LIKE '{\"ToolCallUpdated\":%'
+                OR event_json LIKE '{\"ToolCallCompleted\":%'
+             ORDER BY session_id, seq",
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let rowid: i64 = row.get(0)?;
+            let Some(bytes) = pending.bytes(rowid, row.get::<_, i64>(3)? as u64) else {
+                continue;
+            };
+            let session: String = row.get(1)?;
+            let seq: i64 = row.get(2)?;
+            if let Some(id) = row.get::<_, Option<String>>(4)? {
+                calls
+                    .entry((session, id))
+                    .or_default()
+                    .contents
+                    .push((seq, rowid, bytes));
+            } else if let Some(id) = row.get::<_, Option<String>>(5)? {
+                calls
+                    .entry((session, id))
+                    .or_default()
+                    .updates
+                    .push((rowid, bytes));
+            } else if let Some(id) = row.get::<_, Option<String>>(6)? {
+                let has_content = row.get::<_, Option<bool>>(7)?.unwrap_or(false);
+                calls.entry((session, id)).or_default().completed = Some((seq, has_content));
+            }
+        }
+    }
+
+    let mut doomed: Vec<(i64, u64)> = Vec::new();
+    let mut merged: Vec<(i64, String, u64)> = Vec::new();
+    for call in calls.into_values() {
+        let Some((completed_seq, has_content)) = call.completed else {
+            continue;
+        };
+        let before: Vec<_> = call
+            .contents
+            .iter()
+            .filter(|(seq, _, _)| *seq < completed_seq)
+            .collect();
+        let keep = if has_content { 0 } else { 1 };
+        let drop = before.len().saturating_sub(keep);
+        doomed.extend(
+            before[..drop]
+                .iter()
+                .map(|(_, rowid, bytes)| (*rowid, *bytes)),
+        );
+
+        if let Some(((last, last_bytes), earlier)) = call.updates.split_last() {
+            if earlier.is_empty() {
+                continue;
+            }
+            let rowids: Vec<i64> = call.updates.iter().map(|(rowid, _)| *rowid).collect();
+            if let Some(json) = merge_tool_call_updates(conn, &rowids)? {
+                let new_bytes = json.len() as u64;
+                merged.push((*last, json, last_bytes.saturating_sub(new_bytes)));
+                doomed.extend(earlier.iter().copied());
+            }
+        }
+    }
+
+    Ok(Prune {
+        delete: doomed,
+        rewrite: merged,
+    })
+}
+
+/// Fold a tool call's `ToolCallUpdated` rows (in seq order) into one
+/// event. `None` when a patch blanks a field an earlier one set: the
+/// web client applies the blank while the TUI skips it, so merging
+/// could change what one of them shows.
+fn merge_tool_call_updates(conn: &Connection, rowids: &[i64]) -> Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT event_json FROM cockpit_events WHERE rowid = ?1")?;
+    let mut merged = None;
+    for rowid in rowids {
+        let json: String = stmt.query_row(params![rowid], |row| row.get(0))?;
+        let Ok(Event::ToolCallUpdated {
+            tool_call_id,
+            title,
+            args_preview,
+            started_at,
+        }) = serde_json::from_str::<Event>(&json)
+        else {
+            return Ok(None);
+        };
+        if title.as_deref() == Some("") || args_preview.as_deref() == Some("") {
+            return Ok(None);
+        }
+        let patch = merged.get_or_insert_with(|| ToolCallPatch {
+            tool_call_id,
+            ..Default::default()
+        });
+        patch.title = title.or(patch.title.take());
+        patch.args_preview = args_preview.or(patch.args_preview.take());
+        patch.started_at = started_at.or(patch.started_at);
+    }
+    let Some(patch) = merged else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::to_string(&Event::ToolCallUpdated {
+        tool_call_id: patch.tool_call_id,
+        title: patch.title,
+        args_preview: patch.args_preview,
+        started_at: patch.started_at,
+    })?))
+}
+
+/// Accumulated fields of merged `ToolCallUpdated` events.
+#[derive(Default)]
+struct ToolCallPatch {
+    tool_call_id: String,
+    title: Option<String>,
+    args_preview: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+}
+
+/// Size of the main database file, from its page count.
+fn db_bytes(conn: &Connection) -> u64 {
+    let pages: i64 = conn
+        .pragma_query_value(None, "page_count", |row| row.get(0))
+        .unwrap_or(0);
+    let page_size: i64 = conn
+        .pragma_query_value(None, "page_size", |row| row.get(0))
+        .unwrap_or(0);
+    (pages * page_size) as u64
+}
+
+/// Give freed pages back to the filesystem. Databases not yet converted
+/// by [`EventStore::migrate_auto_vacuum`] keep their free pages for reuse
+/// instead: a full `VACUUM` here could stall a live daemon's writes past
+/// the busy timeout.
+fn reclaim_space(conn: &Connection) {
+    let mode: i64 = conn
+        .pragma_query_value(None, "auto_vacuum", |row| row.get(0))
+        .unwrap_or(0);
+    if mode == 2 {
+        if let Err(e) = drain(conn, "PRAGMA incremental_vacuum") {
+            warn!(target: "cockpit.event_store", "reclaim space: {e}");
+        }
+    } else {
+        debug!(target: "cockpit.event_store", "auto-vacuum not enabled yet; free pages kept for reuse");
+    }
+    if let Err(e) = drain(conn, "PRAGMA wal_checkpoint(TRUNCATE)") {
+        warn!(target: "cockpit.event_store", "checkpoint WAL: {e}");
+    }
+}
+
+/// Run a statement that may return rows, discarding them.
+fn drain(conn: &Connection, sql: &str) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare(sql)?;
+    let mut rows = stmt.query([])?;
+    while rows.next()?.is_some() {}
+    Ok(())
 }
 
 /// Cheap discriminant string for `Event` so debug logs don't dump the
@@ -847,6 +1449,7 @@ fn event_kind(event: &Event) -> &'static str {
         Event::WakeupScheduled { .. } => "wakeup_scheduled",
         Event::PromptRejected { .. } => "prompt_rejected",
         Event::AgentSwitched { .. } => "agent_switched",
+        Event::Rewound { .. } => "rewound",
     }
 }
 
@@ -1042,7 +1645,7 @@ mod tests {
 
     #[test]
     fn snapshot_event_json_discriminators_match_prune_clauses() {
-        // The retention prune query in `Self::record` excludes four event
+        // `UNPINNED` (used by the row cap and by gc) excludes four event
         // variants via `WHERE event_json NOT LIKE '{"<Variant>":%'`. If the
         // `Event` enum is ever refactored to a different serde shape
         // (`#[serde(tag = "...")]`, a rename, or another adjacency), the
@@ -1418,6 +2021,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn usage_samples_roundtrip_and_age_out() {
+        use crate::cockpit::state::{RateLimitInfo, SessionUsage, UsageCost};
+        let (_tmp, store) = open_store(1000);
+        let now = Utc::now();
+        let usage = UsageSample::from_event(
+            "s-1",
+            "claude",
+            "anthropic",
+            now - chrono::Duration::minutes(5),
+            &Event::UsageUpdated {
+                usage: SessionUsage {
+                    used: 10,
+                    size: 100,
+                    cost: Some(UsageCost {
+                        amount: 0.5,
+                        currency: "USD".into(),
+                    }),
+                },
+            },
+        )
+        .unwrap();
+        let limit = UsageSample::from_event(
+            "s-2",
+            "claude",
+            "anthropic",
+            now,
+            &Event::RateLimit {
+                info: RateLimitInfo {
+                    status: "limit".into(),
+                    resets_at: now + chrono::Duration::hours(1),
+                    kind: "rate_limit".into(),
+                },
+            },
+        )
+        .unwrap();
+        let stale = UsageSample {
+            recorded_at: now - RETENTION - chrono::Duration::hours(1),
+            ..usage.clone()
+        };
+        store.record_usage(&stale).unwrap();
+        store.record_usage(&usage).unwrap();
+        store.record_usage(&limit).unwrap();
+
+        let all = store.usage_since(now - RETENTION * 2);
+        assert_eq!(all.len(), 2, "stale sample pruned on insert");
+        assert_eq!(all[0].kind, SampleKind::Usage);
+        assert_eq!(all[0].context_used, Some(10));
+        assert_eq!(all[0].cost, Some(0.5));
+        assert_eq!(all[1].status.as_deref(), Some("limit"));
+        assert!(all[1].resets_at.is_some());
+        assert_eq!(
+            store.usage_since(now - chrono::Duration::minutes(1)).len(),
+            1
+        );
+    }
+
     #[test]
     fn store_persists_across_reopen() {
         let tmp = TempDir::new().unwrap();
@@ -1518,6 +2178,7 @@ mod tests {
             destructive: false,
             requested_at: Utc::now(),
             resolved: None,
+            policy_rule: None,
         };
         let approval_b = Approval {
             nonce: nonce_b.clone(),
@@ -1525,6 +2186,7 @@ mod tests {
             destructive: false,
             requested_at: Utc::now(),
             resolved: None,
+            policy_rule: None,
         };
         let approval_c = Approval {
             nonce: nonce_c.clone(),
@@ -1532,6 +2194,7 @@ mod tests {
             destructive: false,
             requested_at: Utc::now(),
             resolved: None,
+            policy_rule: None,
         };
         // A is requested and resolved. B and C are requested but never
         // resolved (orphans).
@@ -1551,6 +2214,7 @@ mod tests {
                 &Event::ApprovalResolved {
                     nonce: nonce_a,
                     decision: ApprovalDecision::Allow,
+                    rule: None,
                 },
             )
             .unwrap();
@@ -1580,4 +2244,258 @@ mod tests {
         // Unrelated session must not bleed into the query.
         assert!(store.unresolved_approval_nonces("s-2").is_empty());
     }
+
+    fn content(id: &str, text: &str) -> Event {
+        Event::ToolCallContent {
+            tool_call_id: id.into(),
+            content: text.into(),
+        }
+    }
+
+    fn completed(id: &str, text: &str) -> Event {
+        Event::ToolCallCompleted {
+            tool_call_id: id.into(),
+            is_error: false,
+            content: text.into(),
+            completed_at: Utc::now(),
+        }
+    }
+
+    fn updated(id: &str, title: Option<&str>, args: Option<&str>) -> Event {
+        Event::ToolCallUpdated {
+            tool_call_id: id.into(),
+            title: title.map(Into::into),
+            args_preview: args.map(Into::into),
+            started_at: None,
+        }
+    }
+
+    #[test]
+    fn gc_compacts_completed_tool_call_streams() {
+        let (_tmp, store) = open_store(0);
+        let events = [
+            updated("t1", Some("Terminal"), None),
+            content("t1", "a"),
+            content("t1", "ab"),
+            updated("t1", None, Some("cargo test")),
+            content("t1", "abc"),
+            completed("t1", ""),
+            // Completion with its own content makes every snapshot moot.
+            content("t2", "x"),
+            content("t2", "xy"),
+            completed("t2", "final"),
+            // Still running: a client may be rendering it live.
+            content("t3", "1"),
+            content("t3", "12"),
+        ];
+        for (i, event) in events.iter().enumerate() {
+            store.record("s-1", i as u64 + 1, event).unwrap();
+        }
+
+        let report = store.gc(&GcPlan::default(), Utc::now(), false).unwrap();
+        assert_eq!(report.compacted_events, 5);
+
+        let replay = store.replay_from("s-1", 0);
+        let seqs: Vec<u64> = replay.iter().map(|(s, _)| *s).collect();
+        assert_eq!(seqs, vec![4, 5, 6, 9, 10, 11]);
+        match &replay[0].1 {
+            Event::ToolCallUpdated {
+                title,
+                args_preview,
+                ..
+            } => {
+                assert_eq!(title.as_deref(), Some("Terminal"));
+                assert_eq!(args_preview.as_deref(), Some("cargo test"));
+            }
+            other => panic!("expected merged ToolCallUpdated, got {other:?}"),
+        }
+        assert!(matches!(&replay[1].1, Event::ToolCallContent { content, .. } if content == "abc"));
+
+        // A second pass has nothing left to do.
+        let again = store.gc(&GcPlan::default(), Utc::now(), false).unwrap();
+        assert_eq!(again.events_removed(), 0);
+    }
+
+    #[test]
+    fn legacy_db_is_vacuumed_by_migration_not_gc() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("cockpit.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE legacy (x INTEGER);")
+            .unwrap();
+        let store = EventStore::open(&path, 0).unwrap();
+        let mode = |store: &EventStore| -> i64 {
+            let conn = store.conn.lock().unwrap();
+            conn.pragma_query_value(None, "auto_vacuum", |row| row.get(0))
+                .unwrap()
+        };
+        store.record("s-1", 1, &content("t1", "a")).unwrap();
+        store.record("s-1", 2, &content("t1", "ab")).unwrap();
+        store.record("s-1", 3, &completed("t1", "")).unwrap();
+        let report = store.gc(&GcPlan::default(), Utc::now(), false).unwrap();
+        assert_eq!(report.compacted_events, 1);
+        assert_eq!(mode(&store), 0);
+
+        store.migrate_auto_vacuum().unwrap();
+        assert_eq!(mode(&store), 2);
+    }
+
+    #[test]
+    fn gc_dry_run_reports_without_removing() {
+        let (_tmp, store) = open_store(0);
+        store.record("s-1", 1, &content("t1", "a")).unwrap();
+        store.record("s-1", 2, &content("t1", "ab")).unwrap();
+        store.record("s-1", 3, &completed("t1", "")).unwrap();
+
+        let report = store.gc(&GcPlan::default(), Utc::now(), true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.compacted_events, 1);
+        assert!(report.bytes_reclaimed() > 0);
+        assert_eq!(store.replay_from("s-1", 0).len(), 3);
+    }
+
+    #[test]
+    fn gc_expires_old_events_and_orphans_but_keeps_pinned_and_exempt() {
+        let (_tmp, store) = open_store(0);
+        for session in ["s-live", "s-archived", "s-gone"] {
+            store
+                .record(
+                    session,
+                    1,
+                    &Event::AcpSessionAssigned {
+                        acp_session_id: "acp".into(),
+                    },
+                )
+                .unwrap();
+            store.record(session, 2, &Event::ThinkingStarted).unwrap();
+        }
+        let plan = GcPlan {
+            profiles: vec![crate::cockpit::retention::ProfileRetention {
+                profile: "default".into(),
+                max_age_days: 7,
+                max_bytes: 0,
+                // s-archived is exempt, so the plan leaves it out.
+                sessions: vec!["s-live".into()],
+            }],
+            known_sessions: Some(
+                ["s-live", "s-archived"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ),
+        };
+        let later = Utc::now() + chrono::Duration::days(30);
+
+        let report = store.gc(&plan, later, false).unwrap();
+        assert_eq!(report.orphaned_sessions, 1);
+        assert_eq!(report.orphaned_events, 2);
+        assert_eq!(report.expired_events, 1);
+        assert!(store.replay_from("s-gone", 0).is_empty());
+        let live: Vec<u64> = store
+            .replay_from("s-live", 0)
+            .iter()
+            .map(|(s, _)| *s)
+            .collect();
+        assert_eq!(live, vec![1], "pinned snapshot must survive expiry");
+        assert_eq!(store.replay_from("s-archived", 0).len(), 2);
+
+        // A recent orphan is within the grace period.
+        store.record("s-new", 1, &Event::ThinkingStarted).unwrap();
+        let report = store.gc(&plan, Utc::now(), false).unwrap();
+        assert_eq!(report.orphaned_sessions, 0);
+    }
+
+    #[test]
+    fn gc_trims_oldest_events_to_the_byte_budget() {
+        let (_tmp, store) = open_store(0);
+        for seq in 1..=10 {
+            store
+                .record(
+                    if seq % 2 == 0 { "s-a" } else { "s-b" },
+                    seq,
+                    &Event::AgentMessageChunk {
+                        text: "x".repeat(100),
+                    },
+                )
+                .unwrap();
+        }
+        let one = serde_json::to_string(&Event::AgentMessageChunk {
+            text: "x".repeat(100),
+        })
+        .unwrap()
+        .len() as u64;
+        let plan = GcPlan {
+            profiles: vec![crate::cockpit::retention::ProfileRetention {
+                profile: "default".into(),
+                max_age_days: 0,
+                max_bytes: one * 4,
+                sessions: vec!["s-a".into(), "s-b".into()],
+            }],
+            known_sessions: None,
+        };
+
+        let report = store.gc(&plan, Utc::now(), false).unwrap();
+        assert_eq!(report.over_budget_events, 6);
+        assert_eq!(report.event_bytes_removed, one * 6);
+        let mut kept: Vec<u64> = store
+            .replay_from("s-a", 0)
+            .into_iter()
+            .chain(store.replay_from("s-b", 0))
+            .map(|(s, _)| s)
+            .collect();
+        kept.sort();
+        assert_eq!(kept, vec![7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn gc_dry_run_matches_a_batched_real_pass() {
+        let (_tmp, store) = open_store(0);
+        // More orphaned rows than one batch holds.
+        for seq in 1..=(GC_BATCH as u64 * 2 + 10) {
+            store
+                .record("s-gone", seq, &Event::ThinkingStarted)
+                .unwrap();
+        }
+        // Compaction shrinks s-live before the byte budget is applied.
+        for (i, event) in [
+            content("t1", &"a".repeat(200)),
+            content("t1", &"a".repeat(300)),
+            completed("t1", "done"),
+            Event::AgentMessageChunk {
+                text: "x".repeat(100),
+            },
+        ]
+        .iter()
+        .enumerate()
+        {
+            store.record("s-live", i as u64 + 1, event).unwrap();
+        }
+        let plan = GcPlan {
+            profiles: vec![crate::cockpit::retention::ProfileRetention {
+                profile: "default".into(),
+                max_age_days: 0,
+                max_bytes: 1,
+                sessions: vec!["s-live".into(), "s-gone".into()],
+            }],
+            known_sessions: Some(["s-live".to_string()].into_iter().collect()),
+        };
+        let later = Utc::now() + chrono::Duration::days(1);
+
+        let dry = store.gc(&plan, later, true).unwrap();
+        assert_eq!(store.replay_from("s-gone", 0).len(), GC_BATCH * 2 + 10);
+        let real = store.gc(&plan, later, false).unwrap();
+        assert_eq!(real.orphaned_events, GC_BATCH as u64 * 2 + 10);
+        assert_eq!(real.compacted_events, 2);
+        assert_eq!(real.over_budget_events, 2);
+        for (d, r) in [
+            (dry.orphaned_events, real.orphaned_events),
+            (dry.compacted_events, real.compacted_events),
+            (dry.over_budget_events, real.over_budget_events),
+            (dry.event_bytes_removed, real.event_bytes_removed),
+        ] {
+            assert_eq!(d, r);
+        }
+        assert!(store.replay_from("s-gone", 0).is_empty());
+    }
 }
@@ -62,29 +62,48 @@ impl SandboxPathMap {
     /// `path` unchanged. Longest container prefix wins so a nested
     /// mount doesn't get masked by a shallower one.
     pub fn translate_to_host(&self, path: &Path) -> PathBuf {
-        let mut best: Option<(&Path, &Path)> = None;
-        for (container, host) in &self.mounts {
-            if path.starts_with(container)
-                && best
-                    .map(|(c, _)| container.as_os_str().len() > c.as_os_str().len())
-                    .unwrap_or(true)
-            {
-                best = Some((container, host));
-            }
+        rebase(
+            path,
+            self.mounts
+                .iter()
+                .map(|(container, host)| (container.as_path(), host.as_path())),
+        )
+    }
+
+    /// The inverse of [`translate_to_host`](Self::translate_to_host), for
+    /// host paths aoe hands to an agent running in the container.
+    pub fn translate_to_container(&self, path: &Path) -> PathBuf {
+        rebase(
+            path,
+            self.mounts
+                .iter()
+                .map(|(container, host)| (host.as_path(), container.as_path())),
+        )
+    }
+}
+
+/// Replace the longest matching `from` prefix of `path` with its `to`.
+fn rebase<'a>(path: &Path, pairs: impl Iterator<Item = (&'a Path, &'a Path)>) -> PathBuf {
+    let mut best: Option<(&Path, &Path)> = None;
+    for (from, to) in pairs {
+        if path.starts_with(from)
+            && best
+                .map(|(f, _)| from.as_os_str().len() > f.as_os_str().len())
+                .unwrap_or(true)
+        {
+            best = Some((from, to));
         }
-        match best {
-            Some((container, host)) => {
-                let rel = path
-                    .strip_prefix(container)
-                    .unwrap_or_else(|_| Path::new(""));
-                if rel.as_os_str().is_empty() {
-                    host.to_path_buf()
-                } else {
-                    host.join(rel)
-                }
+    }
+    match best {
+        Some((from, to)) => {
+            let rel = path.strip_prefix(from).unwrap_or_else(|_| Path::new(""));
+            if rel.as_os_str().is_empty() {
+                to.to_path_buf()
+            } else {
+                to.join(rel)
             }
-            None => path.to_path_buf(),
         }
+        None => path.to_path_buf(),
     }
 }
 
@@ -358,6 +377,25 @@ mod tests {
         assert_eq!(translated, PathBuf::from("/etc/hosts"));
     }
 
+    #[test]
+    fn sandbox_path_map_translates_host_to_container() {
+        let map = SandboxPathMap::new(vec![
+            (PathBuf::from("/workspace"), PathBuf::from("/Users/me")),
+            (
+                PathBuf::from("/workspace/proj"),
+                PathBuf::from("/Users/me/src/proj"),
+            ),
+        ]);
+        assert_eq!(
+            map.translate_to_container(Path::new("/Users/me/src/proj/tools/mcp.js")),
+            PathBuf::from("/workspace/proj/tools/mcp.js")
+        );
+        assert_eq!(
+            map.translate_to_container(Path::new("/usr/bin/node")),
+            PathBuf::from("/usr/bin/node")
+        );
+    }
+
     /// FsPolicy with a sandbox path map: agent reports container paths;
     /// the policy must rewrite to the host side before the inside-roots
     /// check (host-rooted).
@@ -0,0 +1,241 @@
+//! `cockpit.mcp_servers` → ACP `McpServer` list for `session/new` and
+//! `session/load`.
+//!
+//! Sandboxed agents run inside the container, so the stdio servers they
+//! spawn do too: no extra `docker exec` wrapping is needed, but absolute
+//! host paths in `command`/`args` that fall under a mounted volume are
+//! rewritten to their container-side location.
+//!
+//! Entries are dropped (with a warning) when they are malformed, when the
+//! agent didn't advertise the transport, or when a repo config declares a
+//! stdio server for an unsandboxed session: a checked-out repo shouldn't
+//! get to run arbitrary commands on the host just by being opened.
+
+use std::path::Path;
+
+use agent_client_protocol::schema::{
+    EnvVariable, HttpHeader, McpCapabilities, McpServer, McpServerHttp, McpServerSse,
+    McpServerStdio,
+};
+use tracing::warn;
+
+use super::fs_handler::SandboxPathMap;
+use crate::session::config::{McpServerConfig, McpTransport};
+
+/// Convert the resolved config list into what the agent is sent.
+/// `sandbox` is the session's path map when it runs in a container.
+pub fn to_acp(
+    servers: &[McpServerConfig],
+    caps: &McpCapabilities,
+    sandbox: Option<&SandboxPathMap>,
+    session_label: &str,
+) -> Vec<McpServer> {
+    servers
+        .iter()
+        .filter_map(|server| match convert(server, caps, sandbox) {
+            Ok(converted) => Some(converted),
+            Err(reason) => {
+                warn!(
+                    target: "cockpit.acp",
+                    session = %session_label,
+                    server = %server.name,
+                    "skipping MCP server: {reason}"
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+fn convert(
+    server: &McpServerConfig,
+    caps: &McpCapabilities,
+    sandbox: Option<&SandboxPathMap>,
+) -> Result<McpServer, &'static str> {
+    if server.name.trim().is_empty() {
+        return Err("missing name");
+    }
+    match (&server.command, &server.url) {
+        (Some(command), None) => {
+            if server.from_repo && sandbox.is_none() {
+                return Err("repo-declared stdio servers only run in sandboxed sessions");
+            }
+            let command = match sandbox {
+                Some(map) => map
+                    .translate_to_container(Path::new(command))
+                    .to_string_lossy()
+                    .into_owned(),
+                None => command.clone(),
+            };
+            let args = server
+                .args
+                .iter()
+                .map(|arg| match sandbox {
+                    Some(map) if Path::new(arg).is_absolute() => map
+                        .translate_to_container(Path::new(arg))
+                        .to_string_lossy()
+                        .into_owned(),
+                    _ => arg.clone(),
+                })
+                .collect();
+            let env = server
+                .env
+                .iter()
+                .map(|(k, v)| EnvVariable::new(k, v))
+                .collect();
+            Ok(McpServer::Stdio(
+                McpServerStdio::new(&server.name, command)
+                    .args(args)
+                    .env(env),
+            ))
+        }
+        (None, Some(url)) => {
+            let headers = server
+                .headers
+                .iter()
+                .map(|(k, v)| HttpHeader::new(k, v))
+                .collect();
+            match server.transport {
+                McpTransport::Http if caps.http => Ok(McpServer::Http(
+                    McpServerHttp::new(&server.name, url).headers(headers),
+                )),
+                McpTransport::Sse if caps.sse => Ok(McpServer::Sse(
+                    McpServerSse::new(&server.name, url).headers(headers),
+                )),
+                McpTransport::Http => Err("agent does not support HTTP MCP servers"),
+                McpTransport::Sse => Err("agent does not support SSE MCP servers"),
+            }
+        }
+        (Some(_), Some(_)) => Err("set either `command` or `url`, not both"),
+        (None, None) => Err("needs a `command` or a `url`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn stdio(name: &str, command: &str, args: &[&str]) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some(command.to_string()),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn remote(name: &str, url: &str, transport: McpTransport) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            url: Some(url.to_string()),
+            transport,
+            ..Default::default()
+        }
+    }
+
+    fn names(servers: &[McpServer]) -> Vec<String> {
+        servers
+            .iter()
+            .map(|s| match s {
+                McpServer::Stdio(s) => s.name.clone(),
+                McpServer::Http(s) => s.name.clone(),
+                McpServer::Sse(s) => s.name.clone(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stdio_server_keeps_args_and_env() {
+        let mut server = stdio("fs", "npx", &["-y", "@mcp/fs"]);
+        server.env.insert("TOKEN".into(), "abc".into());
+        let out = to_acp(&[server], &McpCapabilities::new(), None, "s");
+        let McpServer::Stdio(s) = &out[0] else {
+            panic!("expected stdio, got {out:?}");
+        };
+        assert_eq!(s.command, PathBuf::from("npx"));
+        assert_eq!(s.args, vec!["-y".to_string(), "@mcp/fs".to_string()]);
+        assert_eq!(s.env, vec![EnvVariable::new("TOKEN", "abc")]);
+    }
+
+    #[test]
+    fn sandboxed_stdio_paths_are_rewritten_to_container() {
+        let map = SandboxPathMap::new(vec![(
+            PathBuf::from("/workspace/proj"),
+            PathBuf::from("/home/me/proj"),
+        )]);
+        let server = stdio(
+            "local",
+            "/home/me/proj/bin/server",
+            &["--root", "/home/me/proj/docs", "/etc/config"],
+        );
+        let out = to_acp(&[server], &McpCapabilities::new(), Some(&map), "s");
+        let McpServer::Stdio(s) = &out[0] else {
+            panic!("expected stdio, got {out:?}");
+        };
+        assert_eq!(s.command, PathBuf::from("/workspace/proj/bin/server"));
+        assert_eq!(
+            s.args,
+            vec![
+                "--root".to_string(),
+                "/workspace/proj/docs".to_string(),
+                "/etc/config".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn repo_stdio_server_requires_sandbox() {
+        let mut server = stdio("repo", "./tools/mcp", &[]);
+        server.from_repo = true;
+        let caps = McpCapabilities::new();
+        assert!(to_acp(std::slice::from_ref(&server), &caps, None, "s").is_empty());
+        let map = SandboxPathMap::default();
+        assert_eq!(names(&to_acp(&[server], &caps, Some(&map), "s")), ["repo"]);
+    }
+
+    #[test]
+    fn remote_servers_follow_agent_capabilities() {
+        let mut http = remote("docs", "https://mcp.example.com", McpTransport::Http);
+        http.headers
+            .insert("Authorization".into(), "Bearer x".into());
+        let sse = remote("legacy", "https://old.example.com/sse", McpTransport::Sse);
+        let servers = [http, sse];
+
+        assert!(to_acp(&servers, &McpCapabilities::new(), None, "s").is_empty());
+
+        let out = to_acp(&servers, &McpCapabilities::new().http(true), None, "s");
+        let McpServer::Http(s) = &out[0] else {
+            panic!("expected http, got {out:?}");
+        };
+        assert_eq!(
+            s.headers,
+            vec![HttpHeader::new("Authorization", "Bearer x")]
+        );
+        assert_eq!(out.len(), 1);
+
+        let caps = McpCapabilities::new().http(true).sse(true);
+        assert_eq!(
+            names(&to_acp(&servers, &caps, None, "s")),
+            ["docs", "legacy"]
+        );
+    }
+
+    #[test]
+    fn malformed_entries_are_skipped() {
+        let mut both = stdio("both", "npx", &[]);
+        both.url = Some("https://x".into());
+        let servers = [
+            both,
+            stdio("", "npx", &[]),
+            McpServerConfig {
+                name: "neither".into(),
+                ..Default::default()
+            },
+            stdio("ok", "npx", &[]),
+        ];
+        let caps = McpCapabilities::new().http(true);
+        assert_eq!(names(&to_acp(&servers, &caps, None, "s")), ["ok"]);
+    }
+}
@@ -18,20 +18,24 @@ pub mod agent_compat;
 pub mod agent_profiles;
 pub mod agent_registry;
 pub mod approvals;
+pub mod checkpoints;
 pub mod client;
 pub mod context_primer;
 pub mod event_store;
 pub mod fs_handler;
 pub mod install_hints;
+pub mod mcp_servers;
 pub mod node;
 pub mod permissions;
 pub mod protocol;
+pub mod retention;
 pub mod runner;
 #[cfg(feature = "serve")]
 pub mod sandbox;
 pub mod state;
 pub mod supervisor;
 pub mod terminal_handler;
+pub mod usage;
 pub mod worker_registry;
 
 pub use agent_registry::{AgentRegistry, AgentSpec};
@@ -8,6 +8,7 @@
 
 use std::sync::Arc;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::approvals::ApprovalDecision;
@@ -64,14 +65,39 @@ impl<'de> Deserialize<'de> for CockpitBroadcastFrame {
     }
 }
 
+impl JsonSchema for CockpitBroadcastFrame {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "CockpitBroadcastFrame".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        // `Event` is a large internally-tagged enum whose variants carry
+        // ACP payloads; the OpenAPI document describes it as an opaque
+        // object rather than pulling the whole cockpit state model into
+        // the schema surface.
+        schemars::json_schema!({
+            "type": "object",
+            "required": ["session_id", "seq", "event"],
+            "properties": {
+                "session_id": { "type": "string" },
+                "seq": { "type": "integer", "format": "uint64", "minimum": 0 },
+                "event": {
+                    "type": "object",
+                    "description": "Cockpit event (`cockpit::state::Event`), externally tagged by variant name."
+                }
+            }
+        })
+    }
+}
+
 /// `POST /api/sessions/{id}/cockpit/prompt` body.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PromptRequest {
     pub text: String,
 }
 
 /// `POST /api/sessions/{id}/cockpit/approvals/{nonce}` body.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ResolveApprovalRequest {
     pub decision: ApprovalDecisionWire,
 }
@@ -82,7 +108,7 @@ pub struct ResolveApprovalRequest {
 /// orphaned approvals on attach, see #1099); clients never POST it but
 /// it can appear in `Event::ApprovalResolved` payloads broadcast back
 /// over WS, and the wire enum mirrors the internal one for symmetry.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "PascalCase")]
 pub enum ApprovalDecisionWire {
     Allow,
@@ -114,7 +140,7 @@ impl From<ApprovalDecision> for ApprovalDecisionWire {
 }
 
 /// `GET /api/sessions/{id}/cockpit/replay?since=N` query string.
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ReplayQuery {
     /// Last seq the client has applied. The endpoint returns frames
     /// strictly newer than this. Defaults to 0 (full replay).
@@ -123,7 +149,7 @@ pub struct ReplayQuery {
 }
 
 /// `GET /api/sessions/{id}/cockpit/replay` response.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ReplayResponse {
     /// Frames the client missed, in publish order. Empty when the
     /// client is already caught up.
@@ -144,7 +170,7 @@ pub struct ReplayResponse {
 }
 
 /// `GET /api/sessions/{id}/cockpit/context-primer?before_seq=N` query.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ContextPrimerQuery {
     /// `seq` of the `SessionContextReset` event. The primer only
     /// includes events with `seq < before_seq` so post-reset noise
@@ -153,7 +179,7 @@ pub struct ContextPrimerQuery {
 }
 
 /// `GET /api/sessions/{id}/cockpit/context-primer` response.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ContextPrimerResponse {
     /// Rendered markdown primer ready to drop into the composer.
     /// Empty string when there is no prior transcript to recap.
@@ -176,7 +202,7 @@ pub struct ContextPrimerResponse {
 }
 
 /// `POST /api/sessions/{id}/cockpit/switch-agent` body.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SwitchAgentRequest {
     /// Registry key of the target ACP agent (e.g. `"codex"`,
     /// `"opencode"`). Must exist in the cockpit agent registry; an
@@ -189,7 +215,7 @@ pub struct SwitchAgentRequest {
 }
 
 /// `POST /api/sessions/{id}/cockpit/switch-agent` response.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SwitchAgentResponse {
     pub session_id: String,
     /// Registry key the session is now running.
@@ -207,6 +233,74 @@ pub struct SwitchAgentResponse {
     pub status: &'static str,
 }
 
+/// `POST /api/sessions/{id}/cockpit/rewind` body.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RewindRequest {
+    /// 1-based turn to rewind to, as listed by `/cockpit/checkpoints`.
+    /// The worktree goes back to how it was just before that prompt.
+    pub turn: usize,
+    /// Send a recap of the turns before `turn` as the fresh session's
+    /// first prompt. Defaults to true.
+    #[serde(default = "default_true")]
+    pub prime: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// `POST /api/sessions/{id}/cockpit/rewind` response.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RewindResponse {
+    pub session_id: String,
+    pub turn: usize,
+    /// Seq of the rewound-to `UserPromptSent`.
+    pub checkpoint_seq: u64,
+    /// Seq of the `Rewound` event.
+    pub rewound_seq: u64,
+    /// The rewound turn's prompt, so a client can offer it for editing
+    /// and resending.
+    pub prompt: String,
+    /// True when the recap was sent to the fresh session.
+    pub primed: bool,
+}
+
+/// `POST /api/sessions/{id}/cockpit/handoff` body.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HandoffRequest {
+    /// Registry key of the agent to hand the session to.
+    pub target: String,
+    /// Optional model override for the new agent.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// What the new agent should do first (e.g. "review the changes so
+    /// far"). Defaults to the prompt the previous agent never got to,
+    /// if it was rate-limited, else to carrying on with its work.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Send the transcript recap and current plan as the new agent's
+    /// first prompt. When false only the request is sent, if there is
+    /// one.
+    #[serde(default = "default_true")]
+    pub prime: bool,
+}
+
+/// `POST /api/sessions/{id}/cockpit/handoff` response.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HandoffResponse {
+    pub session_id: String,
+    /// Registry key of the agent that was replaced.
+    pub from: String,
+    /// Registry key the session is now running.
+    pub agent: String,
+    /// Seq of the `AgentSwitched` event.
+    pub switch_seq: u64,
+    /// Turns of the previous agent's transcript included in the recap.
+    pub included_turns: usize,
+    /// True when a first prompt was sent to the new agent.
+    pub prompted: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
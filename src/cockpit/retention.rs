@@ -0,0 +1,241 @@
+//! Retention for the cockpit event log.
+//!
+//! `EventStore::record` enforces the per-session row cap as events are
+//! written. Everything else runs as a garbage-collection pass
+//! (`EventStore::gc`), from `aoe cockpit gc` and every [`GC_INTERVAL`]
+//! while `aoe serve` is up:
+//!
+//! - **Compaction.** Once a tool call has completed, the streamed
+//!   `ToolCallContent` snapshots its completion superseded are dropped,
+//!   and its `ToolCallUpdated` patches are merged into the last one.
+//!   Replaying the compacted log renders the same tool cards.
+//! - **Orphans.** Events of sessions no profile knows about (deleted
+//!   from the TUI or CLI while `aoe serve` was down) are dropped once
+//!   they are [`ORPHAN_GRACE`] old.
+//! - **Age and size.** `cockpit.history_max_age_days` and
+//!   `cockpit.history_max_bytes`, resolved per profile, drop the oldest
+//!   events of that profile's sessions. Archived sessions are exempt
+//!   (and don't count toward the size budget) while
+//!   `cockpit.history_keep_archived` is on.
+//!
+//! The snapshot events the row cap pins (slash commands, modes, the ACP
+//! session id) are never dropped by age or size either.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::event_store::EventStore;
+
+/// How often `aoe serve` runs a pass. The first one runs shortly after
+/// startup.
+pub const GC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+const FIRST_GC_DELAY: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Events of an unknown session are kept this long after its newest
+/// event, so a session whose first events land before its record is
+/// saved isn't mistaken for a deleted one.
+pub const ORPHAN_GRACE: Duration = Duration::hours(1);
+
+/// Age and size limits for one profile's sessions.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileRetention {
+    pub profile: String,
+    /// 0 = no age limit.
+    pub max_age_days: u32,
+    /// 0 = no size budget.
+    pub max_bytes: u64,
+    /// Sessions the limits apply to (archived ones left out when they
+    /// are exempt).
+    pub sessions: Vec<String>,
+}
+
+/// Input for one pass.
+#[derive(Debug, Clone, Default)]
+pub struct GcPlan {
+    /// Profiles with an age or size limit set.
+    pub profiles: Vec<ProfileRetention>,
+    /// Every session ID across all profiles. `None` when a profile
+    /// failed to load (or none has sessions), which turns the orphan
+    /// sweep off rather than dropping transcripts that may be live.
+    pub known_sessions: Option<HashSet<String>>,
+}
+
+/// What a pass removed, or would remove with `--dry-run`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GcReport {
+    pub dry_run: bool,
+    /// Superseded tool-call updates removed by compaction.
+    pub compacted_events: u64,
+    /// Events older than the profile's max age.
+    pub expired_events: u64,
+    /// Events dropped to fit the profile's size budget.
+    pub over_budget_events: u64,
+    pub orphaned_sessions: u64,
+    pub orphaned_events: u64,
+    /// Stored event bytes removed.
+    pub event_bytes_removed: u64,
+    pub db_bytes_before: u64,
+    pub db_bytes_after: u64,
+}
+
+impl GcReport {
+    pub fn events_removed(&self) -> u64 {
+        self.compacted_events + self.expired_events + self.over_budget_events + self.orphaned_events
+    }
+
+    /// Disk space given back. A dry run changes nothing on disk, so it
+    /// reports the event bytes a real pass would free instead.
+    pub fn bytes_reclaimed(&self) -> u64 {
+        if self.dry_run {
+            self.event_bytes_removed
+        } else {
+            self.db_bytes_before.saturating_sub(self.db_bytes_after)
+        }
+    }
+}
+
+/// `1.4 GB`-style size for reports.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+/// Build the plan from every profile's sessions and resolved config.
+pub fn plan() -> GcPlan {
+    let mut plan = GcPlan::default();
+    let profiles = match crate::session::list_profiles() {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            warn!(target: "cockpit.event_store", "list profiles: {e:#}");
+            return plan;
+        }
+    };
+    let mut known = HashSet::new();
+    let mut complete = true;
+    for profile in profiles {
+        let instances = match crate::session::Storage::new(&profile)
+            .and_then(|storage| storage.load_with_groups())
+        {
+            Ok((instances, _)) => instances,
+            Err(e) => {
+                warn!(target: "cockpit.event_store", profile, "load sessions: {e:#}");
+                complete = false;
+                continue;
+            }
+        };
+        known.extend(instances.iter().map(|i| i.id.clone()));
+        let cockpit = match crate::session::resolve_config(&profile) {
+            Ok(config) => config.cockpit,
+            Err(e) => {
+                warn!(target: "cockpit.event_store", profile, "resolve config: {e:#}");
+                continue;
+            }
+        };
+        if cockpit.history_max_age_days == 0 && cockpit.history_max_bytes == 0 {
+            continue;
+        }
+        let sessions = instances
+            .iter()
+            .filter(|i| !(cockpit.history_keep_archived && i.archived_at.is_some()))
+            .map(|i| i.id.clone())
+            .collect();
+        plan.profiles.push(ProfileRetention {
+            profile,
+            max_age_days: cockpit.history_max_age_days,
+            max_bytes: cockpit.history_max_bytes,
+            sessions,
+        });
+    }
+    // No sessions at all more likely means the wrong data directory than
+    // a user who deleted everything; don't sweep on that.
+    if complete && !known.is_empty() {
+        plan.known_sessions = Some(known);
+    }
+    plan
+}
+
+/// Run one pass against `store` with the current config.
+pub fn run(store: &EventStore, dry_run: bool) -> Result<GcReport> {
+    store.gc(&plan(), Utc::now(), dry_run)
+}
+
+/// Run a pass every [`GC_INTERVAL`] until `shutdown` fires. First
+/// converts an older database to incremental auto-vacuum, off the
+/// startup path since the `VACUUM` rewrites the whole file.
+pub async fn run_periodic(store: Arc<EventStore>, shutdown: CancellationToken) {
+    let migrating = store.clone();
+    match tokio::task::spawn_blocking(move || migrating.migrate_auto_vacuum()).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => warn!(target: "cockpit.event_store", "auto-vacuum migration: {e:#}"),
+        Err(e) => warn!(target: "cockpit.event_store", "auto-vacuum migration panicked: {e}"),
+    }
+    let mut delay = FIRST_GC_DELAY;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.cancelled() => break,
+        }
+        delay = GC_INTERVAL;
+        let store = store.clone();
+        match tokio::task::spawn_blocking(move || run(&store, false)).await {
+            Ok(Ok(report)) if report.events_removed() > 0 => info!(
+                target: "cockpit.event_store",
+                removed = report.events_removed(),
+                reclaimed = report.bytes_reclaimed(),
+                "cockpit event log trimmed"
+            ),
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => warn!(target: "cockpit.event_store", "cockpit gc failed: {e:#}"),
+            Err(e) => warn!(target: "cockpit.event_store", "cockpit gc task panicked: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_bytes_picks_a_readable_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn dry_run_reclaims_the_event_bytes_it_would_remove() {
+        let report = GcReport {
+            dry_run: true,
+            event_bytes_removed: 4096,
+            db_bytes_before: 10_000,
+            db_bytes_after: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(report.bytes_reclaimed(), 4096);
+        let real = GcReport {
+            dry_run: false,
+            ..report
+        };
+        assert_eq!(real.bytes_reclaimed(), 0);
+    }
+}
@@ -7,10 +7,13 @@
 //! 1. Writes a registry entry at
 //!    `<app_dir>/cockpit-workers/<session_id>.json` with its PID, socket
 //!    path, and agent metadata.
-//! 2. Spawns the configured ACP agent as a child over stdio.
+//! 2. Spawns the configured ACP agent as a child over stdio, or, with
+//!    `--agent-transport socket`, launches it detached inside the
+//!    sandbox container and waits for it to connect to `--agent-socket`
+//!    (see "Socket transport" below).
 //! 3. Binds a Unix listener at `<app_dir>/cockpit-workers/<session_id>.sock`
 //!    and accepts connections in a loop, proxying bytes between the
-//!    currently-connected aoe daemon and the agent's stdio.
+//!    currently-connected aoe daemon and the agent.
 //! 4. Buffers agent → daemon traffic (line-oriented ndjson) in a ring
 //!    buffer while no daemon is attached, so the next reattach replays
 //!    the gap.
@@ -41,23 +44,38 @@
 //! to them directly. The wire protocol is just newline-delimited
 //! JSON-RPC (ACP), no shim-specific framing, so collapsing this
 //! process is purely an agent-side change.
+//!
+//! ## Socket transport
+//!
+//! For sandboxed sessions whose agent speaks ACP over a socket, the
+//! runner listens on a socket in a per-session directory that is
+//! bind-mounted into the container, runs the agent with `docker exec -d`
+//! and `AOE_ACP_SOCKET` set, and proxies the agent's connection instead
+//! of a `docker exec -i` pipe. The pipe dies with the container; the
+//! socket does not. When the agent's connection drops (container
+//! restart, agent crash) the runner keeps the daemon attached, fails the
+//! requests the lost agent will never answer, relaunches the agent until
+//! the container is back, replays `initialize` + `session/load` for the
+//! stored ACP session, and then resumes proxying. Daemon lines sent in
+//! the meantime are held and delivered after the reload.
 
 use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::Args;
 use serde::Deserialize;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 
-use super::worker_registry::{self, WorkerRecord};
+use super::worker_registry::{self, AgentTransport, WorkerRecord};
 
 /// Cap on agent → daemon notification lines stored while detached.
 /// Each entry is at most one ndjson line (a few KB). Past this, oldest
@@ -68,6 +86,25 @@ const NOTIFICATION_BUFFER_LINES: usize = 256;
 /// pipe size on macOS/Linux.
 const STDOUT_READ_BUF: usize = 64 * 1024;
 
+/// How long a freshly launched socket agent has to connect back.
+const AGENT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long the runner keeps relaunching a lost socket agent, which
+/// covers a container restart, before giving up and exiting.
+const AGENT_RELAUNCH_WINDOW: Duration = Duration::from_secs(120);
+
+/// Pause between relaunch attempts while the container is down.
+const AGENT_RELAUNCH_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Cap on each request the runner makes of a relaunched agent while
+/// reloading its session. `session/load` replays the transcript, so
+/// this is generous.
+const AGENT_RELOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// JSON-RPC error code for requests failed because the agent that
+/// received them went away (`Internal error`).
+const AGENT_LOST_ERROR_CODE: i64 = -32603;
+
 #[derive(Args, Debug, Clone)]
 pub struct CockpitRunnerArgs {
     #[arg(long)]
@@ -108,6 +145,15 @@ pub struct CockpitRunnerArgs {
     /// profile, matching pre-persistence behavior.
     #[arg(long, default_value = "")]
     pub source_profile: String,
+    /// How to reach the agent. `socket` runs the agent argv (a detached
+    /// `docker exec`) and waits for the agent to connect to
+    /// `--agent-socket`; `stdio` owns the agent's stdin/stdout.
+    #[arg(long, value_enum, default_value_t = AgentTransport::Stdio)]
+    pub agent_transport: AgentTransport,
+    /// Host path the runner listens on for the agent under `socket`
+    /// transport. Its directory is mounted into the sandbox container.
+    #[arg(long, required_if_eq("agent_transport", "socket"))]
+    pub agent_socket: Option<PathBuf>,
     /// Agent program + args after `--`.
     #[arg(last = true, required = true)]
     pub agent_argv: Vec<String>,
@@ -157,11 +203,36 @@ pub async fn run(args: CockpitRunnerArgs) -> Result<()> {
         let _ = std::fs::set_permissions(&args.socket, std::fs::Permissions::from_mode(0o600));
     }
 
-    let (mut agent_child, agent_stdin, agent_stdout, agent_stderr) =
-        spawn_agent(&args).with_context(|| format!("spawning agent {:?}", args.agent_argv))?;
+    // Stdio: the agent is our child. Socket: bind the agent listener now
+    // and launch the agent from the socket task below, after the
+    // registry entry exists.
+    let (mut agent_child, agent_input, agent_output, agent_stderr) = match args.agent_transport {
+        AgentTransport::Stdio => {
+            let (child, stdin, stdout, stderr) = spawn_agent(&args)
+                .with_context(|| format!("spawning agent {:?}", args.agent_argv))?;
+            (
+                Some(child),
+                AgentInput::stdio(stdin),
+                AgentOutput::Stdout(stdout),
+                stderr,
+            )
+        }
+        AgentTransport::Socket => {
+            let path = args
+                .agent_socket
+                .as_deref()
+                .ok_or_else(|| anyhow!("--agent-transport socket needs --agent-socket"))?;
+            (
+                None,
+                AgentInput::socket(),
+                AgentOutput::Listener(bind_agent_socket(path)?),
+                None,
+            )
+        }
+    };
 
     let our_pid = std::process::id();
-    let record = WorkerRecord::new(
+    let mut record = WorkerRecord::new(
         args.session_id.clone(),
         our_pid,
         args.socket.clone(),
@@ -178,6 +249,8 @@ pub async fn run(args: CockpitRunnerArgs) -> Result<()> {
             Some(args.source_profile.clone())
         },
     );
+    record.agent_transport = args.agent_transport;
+    record.agent_socket_path = args.agent_socket.clone();
     worker_registry::save(&record).context("writing registry record")?;
 
     // Drain agent stderr into the per-session log file. Without this the
@@ -204,20 +277,30 @@ pub async fn run(args: CockpitRunnerArgs) -> Result<()> {
 
     let shared = Arc::new(RunnerShared::new());
 
-    // Fan-out task: reads agent stdout and either forwards to the
-    // currently-attached daemon or buffers in the ring. Single owner of
-    // the read half of the agent's stdout pipe.
-    let agent_stdout_task = tokio::spawn(fanout_agent_stdout(
-        agent_stdout,
-        Arc::clone(&shared),
-        args.session_id.clone(),
-    ));
+    // Wrap the agent's input in a tokio Mutex so the accept loop can
+    // hand it to one connection at a time. Wrapping (not splitting)
+    // keeps stdin alive across reconnects — closing it would cause
+    // aoe-agent to `process.exit(0)`.
+    let agent_stdin = Arc::new(Mutex::new(agent_input));
 
-    // Wrap agent stdin in a tokio Mutex so the accept loop can hand it
-    // to one connection at a time. Wrapping (not splitting) keeps stdin
-    // alive across reconnects — closing it would cause aoe-agent to
-    // `process.exit(0)`.
-    let agent_stdin = Arc::new(Mutex::new(agent_stdin));
+    // Fan-out task: reads agent output and either forwards to the
+    // currently-attached daemon or buffers in the ring. Single owner of
+    // the agent's read side. Under socket transport it also launches
+    // the agent and relaunches it when the connection drops, and only
+    // returns once the agent is gone for good.
+    let mut agent_task = match agent_output {
+        AgentOutput::Stdout(stdout) => tokio::spawn(fanout_agent_output(
+            BufReader::with_capacity(STDOUT_READ_BUF, stdout),
+            Arc::clone(&shared),
+            args.session_id.clone(),
+        )),
+        AgentOutput::Listener(listener) => tokio::spawn(run_socket_agent(
+            args.clone(),
+            listener,
+            Arc::clone(&shared),
+            Arc::clone(&agent_stdin),
+        )),
+    };
 
     // Signal handling: SIGTERM/SIGINT → kill agent, cleanup, exit.
     let shutdown_signal = wait_for_shutdown();
@@ -259,20 +342,34 @@ pub async fn run(args: CockpitRunnerArgs) -> Result<()> {
 
     // Wait for: agent exit, signal, or accept loop death (latter is
     // unreachable but kept for symmetry).
+    let agent_done = async {
+        match agent_child.as_mut() {
+            Some(child) => Some(child.wait().await),
+            None => {
+                let _ = (&mut agent_task).await;
+                None
+            }
+        }
+    };
     tokio::select! {
-        status = agent_child.wait() => {
+        status = agent_done => {
             match status {
-                Ok(s) => info!(
+                Some(Ok(s)) => info!(
                     target: "cockpit.runner",
                     session = %session_id,
                     status = ?s,
                     "agent exited; runner shutting down"
                 ),
-                Err(e) => warn!(
+                Some(Err(e)) => warn!(
                     target: "cockpit.runner",
                     session = %session_id,
                     "agent wait error: {e}"
                 ),
+                None => info!(
+                    target: "cockpit.runner",
+                    session = %session_id,
+                    "socket agent gone; runner shutting down"
+                ),
             }
         }
         _ = shutdown_signal => {
@@ -281,19 +378,109 @@ pub async fn run(args: CockpitRunnerArgs) -> Result<()> {
                 session = %session_id,
                 "shutdown signal received; terminating agent"
             );
-            let _ = agent_child.start_kill();
-            let _ = agent_child.wait().await;
+            // A socket agent exits when its connection closes, which
+            // aborting the agent task below does.
+            if let Some(child) = agent_child.as_mut() {
+                let _ = child.start_kill();
+                let _ = child.wait().await;
+            }
         }
         _ = accept_loop => {
             warn!(target: "cockpit.runner", session = %session_id, "accept loop exited unexpectedly");
         }
     }
 
-    agent_stdout_task.abort();
+    agent_task.abort();
     worker_registry::delete(&session_id).ok();
     Ok(())
 }
 
+/// Where agent output comes from: the child's stdout, or connections
+/// accepted on the agent socket.
+enum AgentOutput {
+    Stdout(tokio::process::ChildStdout),
+    Listener(UnixListener),
+}
+
+/// Write side of the agent: the child's stdin, or the write half of the
+/// agent's socket connection.
+type AgentWriter = Pin<Box<dyn AsyncWrite + Send>>;
+
+/// Daemon → agent half of the proxy, shared by the daemon connections
+/// and (under socket transport) the task that reconnects the agent.
+struct AgentInput {
+    writer: Option<AgentWriter>,
+    /// Daemon lines held while a socket agent is away, delivered once
+    /// the relaunched agent has reloaded the session.
+    held: VecDeque<Vec<u8>>,
+    /// Socket agents come back after losing the writer; a stdio agent
+    /// whose stdin is gone has exited.
+    hold_on_loss: bool,
+}
+
+impl AgentInput {
+    fn stdio(stdin: tokio::process::ChildStdin) -> Self {
+        Self {
+            writer: Some(Box::pin(stdin)),
+            held: VecDeque::new(),
+            hold_on_loss: false,
+        }
+    }
+
+    fn socket() -> Self {
+        Self {
+            writer: None,
+            held: VecDeque::new(),
+            hold_on_loss: true,
+        }
+    }
+
+    /// Write one line to the agent, or hold it while a socket agent is
+    /// away. Returns false once the agent is gone for good.
+    async fn write_line(&mut self, line: &[u8]) -> bool {
+        if let Some(writer) = self.writer.as_mut() {
+            if writer.write_all(line).await.is_ok() && writer.flush().await.is_ok() {
+                return true;
+            }
+            self.writer = None;
+        }
+        if !self.hold_on_loss {
+            return false;
+        }
+        while self.held.len() >= NOTIFICATION_BUFFER_LINES {
+            self.held.pop_front();
+        }
+        self.held.push_back(line.to_vec());
+        true
+    }
+
+    /// Install a reconnected agent's writer and deliver the held lines.
+    async fn attach(&mut self, writer: AgentWriter) {
+        self.writer = Some(writer);
+        while let Some(line) = self.held.pop_front() {
+            let Some(writer) = self.writer.as_mut() else {
+                self.held.push_front(line);
+                return;
+            };
+            if writer.write_all(&line).await.is_err() || writer.flush().await.is_err() {
+                self.writer = None;
+                self.held.push_front(line);
+                return;
+            }
+        }
+    }
+}
+
+/// Daemon requests replayed against a relaunched agent so it reloads
+/// the session the daemon is still talking to.
+#[derive(Default)]
+struct Handshake {
+    /// Params of the daemon's latest `initialize`.
+    initialize: Option<serde_json::Value>,
+    /// Params of the daemon's latest `session/new` or `session/load`.
+    session: Option<serde_json::Value>,
+}
+
 /// State the accept loop and the agent-stdout fanout share. The active
 /// connection is the daemon's write-half of the socket; only one daemon
 /// is attached at a time.
@@ -313,6 +500,12 @@ struct RunnerShared {
     /// can't answer (the responder oneshot died with the old daemon's
     /// `pending_responders` map). See #1099.
     outstanding_requests: Mutex<HashMap<i64, String>>,
+    /// JSON-RPC request ids the daemon issued to the agent that have not
+    /// yet seen a response. When a socket agent's connection drops the
+    /// runner fails these so the daemon isn't left waiting on, say, a
+    /// `session/prompt` the lost agent will never finish.
+    daemon_requests: Mutex<HashMap<i64, String>>,
+    handshake: Mutex<Handshake>,
 }
 
 /// JSON-RPC peek for outstanding-request tracking. Pulls only the
@@ -348,6 +541,8 @@ impl RunnerShared {
             active_outbound: Mutex::new(None),
             pending: Mutex::new(VecDeque::with_capacity(NOTIFICATION_BUFFER_LINES)),
             outstanding_requests: Mutex::new(HashMap::new()),
+            daemon_requests: Mutex::new(HashMap::new()),
+            handshake: Mutex::new(Handshake::default()),
         }
     }
 
@@ -373,6 +568,8 @@ impl RunnerShared {
                 );
             }
             map.insert(id, method);
+        } else if let Some(id) = parse_response_id(line) {
+            self.daemon_requests.lock().await.remove(&id);
         }
 
         let mut guard = self.active_outbound.lock().await;
@@ -401,6 +598,77 @@ impl RunnerShared {
         }
     }
 
+    /// Peek-parse a daemon → agent line: track requests so they can be
+    /// failed if a socket agent goes away, and remember the handshake
+    /// requests a relaunched agent needs replayed.
+    async fn note_daemon_request(&self, line: &[u8]) {
+        let Some((id, method)) = parse_request(line) else {
+            return;
+        };
+        if matches!(
+            method.as_str(),
+            "initialize" | "session/new" | "session/load"
+        ) {
+            if let Ok(value) = serde_json::from_slice::<serde_json::Value>(line) {
+                let params = value
+                    .get("params")
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null);
+                let mut handshake = self.handshake.lock().await;
+                if method == "initialize" {
+                    handshake.initialize = Some(params);
+                } else {
+                    handshake.session = Some(params);
+                }
+            }
+        }
+        let mut map = self.daemon_requests.lock().await;
+        if map.len() >= MAX_OUTSTANDING_REQUESTS {
+            // Only a misbehaving agent leaves this many requests
+            // unanswered; forgetting them just means they aren't failed
+            // on agent loss.
+            warn!(
+                target: "cockpit.runner",
+                dropped = map.len(),
+                "daemon_requests soft cap reached; forgetting unanswered ids"
+            );
+            map.clear();
+        }
+        map.insert(id, method);
+    }
+
+    /// A socket agent's connection dropped. Its own requests died with
+    /// it, and the daemon's requests to it will never be answered, so
+    /// fail those with an error response.
+    async fn agent_lost(&self, session_id: &str) {
+        self.outstanding_requests.lock().await.clear();
+        let lost: Vec<(i64, String)> = self.daemon_requests.lock().await.drain().collect();
+        if lost.is_empty() {
+            return;
+        }
+        info!(
+            target: "cockpit.runner",
+            session = %session_id,
+            count = lost.len(),
+            "failing requests sent to the lost agent"
+        );
+        for (id, method) in lost {
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": AGENT_LOST_ERROR_CODE,
+                    "message": format!("agent connection lost during {method}; the runner is relaunching it"),
+                }
+            });
+            let Ok(mut bytes) = serde_json::to_vec(&response) else {
+                continue;
+            };
+            bytes.push(b'\n');
+            self.deliver_line(&bytes).await;
+        }
+    }
+
     /// On daemon disconnect, synthesize a cancellation response for
     /// every outstanding `session/request_permission` request so the
     /// agent's blocked stdio loop unblocks instead of waiting on a
@@ -410,7 +678,7 @@ impl RunnerShared {
     /// state machine.
     async fn cancel_outstanding_permission_requests(
         &self,
-        agent_stdin: &Mutex<tokio::process::ChildStdin>,
+        agent_stdin: &Mutex<AgentInput>,
         session_id: &str,
     ) {
         let drained: Vec<(i64, String)> = {
@@ -466,7 +734,7 @@ impl RunnerShared {
                 }
             };
             bytes.push(b'\n');
-            if stdin.write_all(&bytes).await.is_err() || stdin.flush().await.is_err() {
+            if !stdin.write_line(&bytes).await {
                 warn!(
                     target: "cockpit.runner",
                     session = %session_id,
@@ -529,14 +797,14 @@ fn parse_response_id(line: &[u8]) -> Option<i64> {
     peek.id?.as_i64()
 }
 
-/// Read agent stdout line-by-line (ndjson) and either forward to the
-/// daemon or buffer.
-async fn fanout_agent_stdout(
-    stdout: tokio::process::ChildStdout,
+/// Read agent output line-by-line (ndjson) and either forward to the
+/// daemon or buffer. Returns at EOF: the agent exited or its socket
+/// connection closed.
+async fn fanout_agent_output<R: AsyncBufRead + Unpin>(
+    mut reader: R,
     shared: Arc<RunnerShared>,
     session_id: String,
 ) {
-    let mut reader = BufReader::with_capacity(STDOUT_READ_BUF, stdout);
     let mut line = Vec::with_capacity(4096);
     loop {
         line.clear();
@@ -544,7 +812,7 @@ async fn fanout_agent_stdout(
         // consumers (the daemon's ACP transport) need.
         match reader.read_until(b'\n', &mut line).await {
             Ok(0) => {
-                debug!(target: "cockpit.runner", session = %session_id, "agent stdout EOF");
+                debug!(target: "cockpit.runner", session = %session_id, "agent output EOF");
                 break;
             }
             Ok(_) => {
@@ -566,7 +834,7 @@ async fn fanout_agent_stdout(
 async fn handle_connection(
     stream: UnixStream,
     shared: Arc<RunnerShared>,
-    agent_stdin: Arc<Mutex<tokio::process::ChildStdin>>,
+    agent_stdin: Arc<Mutex<AgentInput>>,
     session_id: String,
 ) {
     let (read_half, write_half) = stream.into_split();
@@ -587,8 +855,9 @@ async fn handle_connection(
             Ok(0) => break, // EOF: daemon closed the connection.
             Ok(_) => {
                 shared.note_daemon_response(&line).await;
+                shared.note_daemon_request(&line).await;
                 let mut stdin = agent_stdin.lock().await;
-                if stdin.write_all(&line).await.is_err() || stdin.flush().await.is_err() {
+                if !stdin.write_line(&line).await {
                     warn!(
                         target: "cockpit.runner",
                         session = %session_id,
@@ -648,6 +917,248 @@ fn spawn_agent(
     Ok((child, stdin, stdout, stderr))
 }
 
+/// Bind the agent listener for socket transport. The directory is the
+/// container's mount source, so it must exist before the container
+/// starts; creating it here covers a runner that beat the container
+/// setup to it.
+fn bind_agent_socket(path: &Path) -> Result<UnixListener> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("creating agent socket dir {}", parent.display()))?;
+    }
+    if path.exists() {
+        let _ = std::fs::remove_file(path);
+    }
+    let listener = UnixListener::bind(path).with_context(|| format!("bind {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(listener)
+}
+
+/// Socket transport: launch the agent, proxy its connection, and when
+/// the connection drops, relaunch it and reload the session. Returns
+/// once the agent can't be brought back.
+async fn run_socket_agent(
+    args: CockpitRunnerArgs,
+    listener: UnixListener,
+    shared: Arc<RunnerShared>,
+    agent_input: Arc<Mutex<AgentInput>>,
+) {
+    let session_id = args.session_id.clone();
+    let mut relaunch = false;
+    loop {
+        let stream = match connect_agent(&args, &listener, relaunch).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!(
+                    target: "cockpit.runner",
+                    session = %session_id,
+                    "socket agent unavailable: {e:#}"
+                );
+                return;
+            }
+        };
+        let (read_half, mut write_half) = stream.into_split();
+        let mut reader = BufReader::with_capacity(STDOUT_READ_BUF, read_half);
+        if relaunch {
+            let handshake = {
+                let h = shared.handshake.lock().await;
+                (h.initialize.clone(), h.session.clone())
+            };
+            let acp_session_id = worker_registry::load(&session_id)
+                .ok()
+                .flatten()
+                .and_then(|r| r.stored_acp_session_id);
+            if let Err(e) =
+                reload_agent_session(&mut reader, &mut write_half, handshake, acp_session_id).await
+            {
+                warn!(
+                    target: "cockpit.runner",
+                    session = %session_id,
+                    "relaunched agent could not resume the session: {e:#}"
+                );
+                return;
+            }
+            worker_registry::note_agent_relaunch(&session_id);
+            info!(
+                target: "cockpit.runner",
+                session = %session_id,
+                "agent relaunched and session reloaded"
+            );
+        } else {
+            info!(target: "cockpit.runner", session = %session_id, "agent connected");
+        }
+        agent_input.lock().await.attach(Box::pin(write_half)).await;
+        fanout_agent_output(reader, Arc::clone(&shared), session_id.clone()).await;
+        agent_input.lock().await.writer = None;
+        shared.agent_lost(&session_id).await;
+        info!(
+            target: "cockpit.runner",
+            session = %session_id,
+            "agent connection closed; relaunching"
+        );
+        relaunch = true;
+    }
+}
+
+/// Launch the agent and wait for it to connect. A relaunch retries
+/// while the container comes back, for up to `AGENT_RELAUNCH_WINDOW`.
+async fn connect_agent(
+    args: &CockpitRunnerArgs,
+    listener: &UnixListener,
+    relaunch: bool,
+) -> Result<UnixStream> {
+    let deadline = tokio::time::Instant::now()
+        + if relaunch {
+            AGENT_RELAUNCH_WINDOW
+        } else {
+            AGENT_CONNECT_TIMEOUT
+        };
+    loop {
+        let attempt = async {
+            launch_detached_agent(args).await?;
+            let (stream, _addr) = tokio::time::timeout(AGENT_CONNECT_TIMEOUT, listener.accept())
+                .await
+                .map_err(|_| {
+                    anyhow!(
+                        "agent did not connect within {}s",
+                        AGENT_CONNECT_TIMEOUT.as_secs()
+                    )
+                })?
+                .context("accepting agent connection")?;
+            Ok::<_, anyhow::Error>(stream)
+        };
+        match attempt.await {
+            Ok(stream) => return Ok(stream),
+            Err(e)
+                if relaunch && tokio::time::Instant::now() + AGENT_RELAUNCH_BACKOFF < deadline =>
+            {
+                debug!(
+                    target: "cockpit.runner",
+                    session = %args.session_id,
+                    "agent relaunch attempt failed, retrying: {e:#}"
+                );
+                tokio::time::sleep(AGENT_RELAUNCH_BACKOFF).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Run the agent argv, a `docker exec -d`, which returns once the agent
+/// has started inside the container. Fails while the container is down.
+async fn launch_detached_agent(args: &CockpitRunnerArgs) -> Result<()> {
+    let mut argv = args.agent_argv.iter();
+    let program = argv
+        .next()
+        .ok_or_else(|| anyhow!("agent_argv empty; expected `-- <command> [args...]`"))?;
+    let output = Command::new(program)
+        .args(argv)
+        .current_dir(&args.cwd)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .with_context(|| format!("running {program}"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Bring a relaunched agent back to the session the daemon is attached
+/// to: replay the daemon's `initialize`, then `session/load` the stored
+/// ACP session with the daemon's original session params. Notifications
+/// the agent sends meanwhile (the transcript replay) are dropped; the
+/// daemon already has them.
+async fn reload_agent_session<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    (initialize, session): (Option<serde_json::Value>, Option<serde_json::Value>),
+    acp_session_id: Option<String>,
+) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let initialize =
+        initialize.ok_or_else(|| anyhow!("the daemon never initialized the lost agent"))?;
+    let acp_session_id =
+        acp_session_id.ok_or_else(|| anyhow!("no stored ACP session id to reload"))?;
+    let mut params = session.ok_or_else(|| anyhow!("the daemon never opened a session"))?;
+    let Some(fields) = params.as_object_mut() else {
+        bail!("unexpected session params shape");
+    };
+    fields.insert("sessionId".into(), acp_session_id.into());
+
+    let init = runner_request(reader, writer, "aoe-runner-init", "initialize", initialize).await?;
+    let can_load = init
+        .pointer("/agentCapabilities/loadSession")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    if !can_load {
+        bail!("agent does not support session/load");
+    }
+    runner_request(reader, writer, "aoe-runner-load", "session/load", params).await?;
+    Ok(())
+}
+
+/// Send one request of the runner's own to the agent and wait for its
+/// result. The string id keeps it apart from the daemon's numeric ids.
+async fn runner_request<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    id: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": params,
+    });
+    let mut bytes = serde_json::to_vec(&request)?;
+    bytes.push(b'\n');
+    writer.write_all(&bytes).await?;
+    writer.flush().await?;
+
+    let wait = async {
+        let mut line = Vec::with_capacity(4096);
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line).await? == 0 {
+                bail!("agent closed the connection during {method}");
+            }
+            let Ok(value) = serde_json::from_slice::<serde_json::Value>(&line) else {
+                continue;
+            };
+            if value.get("id").and_then(serde_json::Value::as_str) != Some(id)
+                || value.get("method").is_some()
+            {
+                continue;
+            }
+            if let Some(error) = value.get("error") {
+                bail!("{method} failed: {error}");
+            }
+            return Ok(value.get("result").cloned().unwrap_or_default());
+        }
+    };
+    tokio::time::timeout(AGENT_RELOAD_TIMEOUT, wait)
+        .await
+        .map_err(|_| anyhow!("{method} timed out"))?
+}
+
 #[cfg(unix)]
 async fn wait_for_shutdown() {
     use tokio::signal::unix::{signal, SignalKind};
@@ -889,4 +1400,173 @@ mod tests {
             "map stays within the cap after eviction"
         );
     }
+
+    /// Socket transport: daemon lines sent while the agent is away are
+    /// held, then delivered in order once it reconnects.
+    #[tokio::test]
+    async fn agent_input_holds_lines_until_the_socket_agent_reconnects() {
+        let mut input = AgentInput::socket();
+        assert!(input.write_line(b"one\n").await);
+        assert!(input.write_line(b"two\n").await);
+
+        let (agent_side, runner_side) = tokio::io::duplex(1024);
+        input.attach(Box::pin(runner_side)).await;
+        assert!(input.held.is_empty());
+        assert!(input.write_line(b"three\n").await);
+        drop(input);
+
+        let mut received = String::new();
+        let mut reader = BufReader::new(agent_side);
+        tokio::io::AsyncReadExt::read_to_string(&mut reader, &mut received)
+            .await
+            .unwrap();
+        assert_eq!(received, "one\ntwo\nthree\n");
+    }
+
+    /// A stdio agent without stdin has exited; nothing is held.
+    #[tokio::test]
+    async fn stdio_agent_input_reports_a_lost_agent() {
+        let mut input = AgentInput {
+            writer: None,
+            held: VecDeque::new(),
+            hold_on_loss: false,
+        };
+        assert!(!input.write_line(b"line\n").await);
+        assert!(input.held.is_empty());
+    }
+
+    /// Requests the daemon sent a socket agent that went away get an
+    /// error response, and an agent reply clears the bookkeeping.
+    #[tokio::test]
+    async fn agent_lost_fails_unanswered_daemon_requests() {
+        let shared = RunnerShared::new();
+        shared
+            .note_daemon_request(
+                br#"{"jsonrpc":"2.0","id":3,"method":"session/set_mode","params":{}}"#,
+            )
+            .await;
+        shared
+            .deliver_line(b"{\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{}}\n")
+            .await;
+        shared
+            .note_daemon_request(
+                br#"{"jsonrpc":"2.0","id":4,"method":"session/prompt","params":{}}"#,
+            )
+            .await;
+
+        shared.agent_lost("s-lost").await;
+        assert!(shared.daemon_requests.lock().await.is_empty());
+        let pending = shared.pending.lock().await;
+        let errors: Vec<serde_json::Value> = pending
+            .iter()
+            .map(|l| serde_json::from_slice(l).unwrap())
+            .filter(|v: &serde_json::Value| v.get("error").is_some())
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["id"], 4);
+        assert_eq!(errors[0]["error"]["code"], AGENT_LOST_ERROR_CODE);
+    }
+
+    /// A relaunched agent is re-initialized with the daemon's params and
+    /// loads the stored session; notifications it sends meanwhile (the
+    /// transcript replay) are skipped.
+    #[tokio::test]
+    async fn reload_agent_session_replays_the_daemon_handshake() {
+        let shared = RunnerShared::new();
+        shared
+            .note_daemon_request(
+                br#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{"protocolVersion":1}}"#,
+            )
+            .await;
+        shared
+            .note_daemon_request(br#"{"jsonrpc":"2.0","id":1,"method":"session/new","params":{"cwd":"/workspace/proj","mcpServers":[]}}"#)
+            .await;
+        let handshake = {
+            let h = shared.handshake.lock().await;
+            (h.initialize.clone(), h.session.clone())
+        };
+
+        let (runner_side, agent_side) = UnixStream::pair().unwrap();
+        let agent = tokio::spawn(async move {
+            let (read_half, mut write_half) = agent_side.into_split();
+            let mut lines = BufReader::new(read_half).lines();
+            let mut seen = Vec::new();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                let reply = match request["method"].as_str().unwrap() {
+                    "initialize" => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": {"protocolVersion": 1, "agentCapabilities": {"loadSession": true}}
+                    }),
+                    _ => {
+                        let update =
+                            "{\"jsonrpc\":\"2.0\",\"method\":\"session/update\",\"params\":{}}\n";
+                        write_half.write_all(update.as_bytes()).await.unwrap();
+                        serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": {}})
+                    }
+                };
+                seen.push(request);
+                let mut bytes = serde_json::to_vec(&reply).unwrap();
+                bytes.push(b'\n');
+                write_half.write_all(&bytes).await.unwrap();
+                if seen.len() == 2 {
+                    break;
+                }
+            }
+            seen
+        });
+
+        let (read_half, mut write_half) = runner_side.into_split();
+        let mut reader = BufReader::new(read_half);
+        reload_agent_session(
+            &mut reader,
+            &mut write_half,
+            handshake,
+            Some("acp-123".into()),
+        )
+        .await
+        .unwrap();
+
+        let seen = agent.await.unwrap();
+        assert_eq!(seen[0]["method"], "initialize");
+        assert_eq!(seen[0]["params"]["protocolVersion"], 1);
+        assert_eq!(seen[1]["method"], "session/load");
+        assert_eq!(seen[1]["params"]["sessionId"], "acp-123");
+        assert_eq!(seen[1]["params"]["cwd"], "/workspace/proj");
+    }
+
+    #[tokio::test]
+    async fn reload_agent_session_needs_load_support() {
+        let (runner_side, agent_side) = UnixStream::pair().unwrap();
+        tokio::spawn(async move {
+            let (read_half, mut write_half) = agent_side.into_split();
+            let mut lines = BufReader::new(read_half).lines();
+            if let Ok(Some(line)) = lines.next_line().await {
+                let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+                let reply = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": {"protocolVersion": 1, "agentCapabilities": {"loadSession": false}}
+                });
+                let mut bytes = serde_json::to_vec(&reply).unwrap();
+                bytes.push(b'\n');
+                write_half.write_all(&bytes).await.unwrap();
+            }
+        });
+        let (read_half, mut write_half) = runner_side.into_split();
+        let mut reader = BufReader::new(read_half);
+        let err = reload_agent_session(
+            &mut reader,
+            &mut write_half,
+            (
+                Some(serde_json::json!({"protocolVersion": 1})),
+                Some(serde_json::json!({"cwd": "/w", "mcpServers": []})),
+            ),
+            Some("acp-1".into()),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("session/load"), "{err}");
+    }
 }
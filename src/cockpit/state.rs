@@ -452,6 +452,10 @@ pub enum Event {
     ApprovalResolved {
         nonce: Nonce,
         decision: ApprovalDecision,
+        /// The `cockpit.approval_rules` entry that decided this approval
+        /// without asking. `None` for decisions made by a user.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rule: Option<String>,
     },
     DiffEmitted {
         diff: DiffPreview,
@@ -645,6 +649,17 @@ pub enum Event {
         to: String,
         reason: String,
     },
+    /// The session was rewound to the checkpoint taken before user turn
+    /// `turn` (the `UserPromptSent` at `checkpoint_seq`): the worktree's
+    /// files were restored from the checkpoint's git snapshot and a
+    /// fresh ACP session was started. Emitted by the `/cockpit/rewind`
+    /// endpoint after the new worker has spawned. The model has
+    /// forgotten everything, so the reducer clears the same state as
+    /// `SessionCleared` and the UI folds earlier rows the same way.
+    Rewound {
+        turn: usize,
+        checkpoint_seq: u64,
+    },
 }
 
 impl CockpitState {
@@ -837,6 +852,15 @@ impl CockpitState {
                     switched_at: Utc::now(),
                 });
             }
+            Event::Rewound { .. } => {
+                self.usage = None;
+                self.available_commands = Vec::new();
+                self.current_plan = None;
+                self.mode = SessionMode::Default;
+                self.pending_approvals = Vec::new();
+                self.in_flight_tool = None;
+                self.thinking = None;
+            }
         }
         self.last_seq = self.last_seq.saturating_add(1);
         self.updated_at = Utc::now();
@@ -886,6 +910,7 @@ mod tests {
         let result = s.apply_event(Event::ApprovalResolved {
             nonce: Nonce::new(),
             decision: ApprovalDecision::Allow,
+            rule: None,
         });
         assert!(matches!(result, Err(StateError::UnknownApprovalNonce(_))));
     }
@@ -243,7 +243,7 @@ type SeqMap = std::sync::Mutex<HashMap<String, u64>>;
 /// or mid-attach. Deliberately not persisted to the cockpit event log:
 /// daemon lifecycle is ephemeral, transcript replay should not carry
 /// it. See #1088.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CockpitWorkerState {
     /// No worker for this session and no resume in flight.
@@ -381,12 +381,12 @@ pub struct SpawnRequest {
     /// `Instance`'s container_config. `None` keeps the legacy host
     /// spawn behavior.
     pub sandbox_info: Option<SandboxInfo>,
-    /// Source profile of the session. Used (with `sandbox_info`) to
-    /// resolve profile-level `sandbox.environment` so cockpit-sandbox
-    /// env matches the tmux substrate. `None` for non-sandboxed
-    /// sessions; falls back to the user's default profile when set
-    /// to `Some("")`.
-    pub source_profile: Option<String>,
+    /// Source profile of the session. Resolves the profile and repo
+    /// cockpit settings (approval rules, MCP servers) for every session
+    /// and, with `sandbox_info`, profile-level `sandbox.environment` so
+    /// cockpit-sandbox env matches the tmux substrate. Empty falls back
+    /// to the user's default profile.
+    pub source_profile: String,
     /// When true, switch the session to `bypassPermissions` mode
     /// immediately after `session/new` succeeds, so a profile with
     /// `yolo_mode_default = true` skips permission prompts in cockpit
@@ -571,6 +571,21 @@ impl<S: BroadcastSink> Supervisor<S> {
         seq
     }
 
+    /// Publish a `Rewound` event after a successful `/cockpit/rewind`,
+    /// once the fresh worker is up. Returns its seq.
+    pub fn publish_rewound(&self, session_id: &str, turn: usize, checkpoint_seq: u64) -> u64 {
+        let seq = next_seq(&self.next_seqs, session_id);
+        self.sink.publish(
+            session_id,
+            seq,
+            &Event::Rewound {
+                turn,
+                checkpoint_seq,
+            },
+        );
+        seq
+    }
+
     /// Like `shutdown` but waits for the runner process to actually exit
     /// before returning, so a subsequent `spawn` for the same session id
     /// doesn't race the SIGTERM and collide on the worker socket file.
@@ -679,7 +694,10 @@ impl<S: BroadcastSink> Supervisor<S> {
     /// Adapters don't emit a structured signal for these, so detection
     /// is text-based but routed through the session's `AgentProfile`
     /// so each agent's aliases match the right surface. See #1101.
-    pub async fn publish_user_prompt(&self, session_id: &str, text: String) {
+    ///
+    /// Returns the `UserPromptSent` seq, which names the turn's
+    /// checkpoint.
+    pub async fn publish_user_prompt(&self, session_id: &str, text: String) -> u64 {
         let agent_key = self.agent_key_for_session(session_id).await;
         let profile = super::agent_profiles::resolve(&agent_key);
         let is_clear = profile.is_clear_command(&text);
@@ -690,6 +708,7 @@ impl<S: BroadcastSink> Supervisor<S> {
             let seq = next_seq(&self.next_seqs, session_id);
             self.sink.publish(session_id, seq, &Event::SessionCleared);
         }
+        seq
     }
 
     /// Resolve the agent registry key for a session. Reads the live
@@ -875,7 +894,7 @@ impl<S: BroadcastSink> Supervisor<S> {
             socket_path: Some(socket_path),
             stored_acp_session_id: stored_acp_session_id.clone(),
             sandbox_info,
-            source_profile,
+            source_profile: Some(source_profile),
         };
 
         debug!(
@@ -1844,6 +1863,8 @@ impl<S: BroadcastSink> Supervisor<S> {
             session = %session_id,
             socket = %record.socket_path.display(),
             pid = record.pid,
+            transport = record.agent_transport.as_str(),
+            agent_relaunches = record.agent_relaunches,
             "reattached to existing cockpit worker"
         );
         drop(workers);
@@ -1890,6 +1911,7 @@ impl<S: BroadcastSink> Supervisor<S> {
                 &Event::ApprovalResolved {
                     nonce,
                     decision: ApprovalDecision::Cancelled,
+                    rule: None,
                 },
             );
         }
@@ -2277,7 +2299,7 @@ mod tests {
                 model: None,
                 stored_acp_session_id: None,
                 sandbox_info: None,
-                source_profile: None,
+                source_profile: String::new(),
                 yolo_mode: false,
             })
             .await;
@@ -2315,7 +2337,7 @@ mod tests {
                 model: None,
                 stored_acp_session_id: None,
                 sandbox_info: None,
-                source_profile: None,
+                source_profile: String::new(),
                 yolo_mode: false,
             })
             .await;
@@ -2357,6 +2379,7 @@ mod tests {
             args: vec![],
             description: "test fixture".into(),
             env_allowlist: None,
+            acp_socket: false,
         };
         let socket_path = tmp.path().join("budget.sock");
         let dummy_config = SpawnConfig {
@@ -2447,6 +2470,7 @@ mod tests {
             args: vec![],
             description: "test fixture".into(),
             env_allowlist: None,
+            acp_socket: false,
         };
         let dummy_config = SpawnConfig {
             agent_key: "claude".into(),
@@ -2519,6 +2543,7 @@ mod tests {
             args: vec![],
             description: "test fixture".into(),
             env_allowlist: None,
+            acp_socket: false,
         };
         let dummy_config = SpawnConfig {
             agent_key: "claude".into(),
@@ -2591,6 +2616,7 @@ mod tests {
             args: vec![],
             description: "test fixture".into(),
             env_allowlist: None,
+            acp_socket: false,
         };
         let dummy_config = SpawnConfig {
             agent_key: "claude".into(),
@@ -2717,6 +2743,7 @@ mod tests {
             args: vec![],
             description: "test fixture".into(),
             env_allowlist: None,
+            acp_socket: false,
         };
         let dummy_config = SpawnConfig {
             agent_key: "claude".into(),
@@ -3334,7 +3361,7 @@ mod tests {
                 model: None,
                 stored_acp_session_id: None,
                 sandbox_info: None,
-                source_profile: None,
+                source_profile: String::new(),
                 yolo_mode: false,
             })
             .await;
@@ -3406,7 +3433,7 @@ mod tests {
                 model: None,
                 stored_acp_session_id: None,
                 sandbox_info: None,
-                source_profile: None,
+                source_profile: String::new(),
                 yolo_mode: false,
             })
             .await;
@@ -3624,7 +3651,7 @@ mod tests {
                 model: None,
                 stored_acp_session_id: None,
                 sandbox_info: None,
-                source_profile: None,
+                source_profile: String::new(),
                 yolo_mode: false,
             })
             .await;
@@ -3675,7 +3702,7 @@ mod tests {
                 model: None,
                 stored_acp_session_id: None,
                 sandbox_info: None,
-                source_profile: None,
+                source_profile: String::new(),
                 yolo_mode: false,
             })
             .await;
@@ -3715,14 +3742,18 @@ mod tests {
             "expected 2 ApprovalResolved + 1 Stopped, got {frames:?}"
         );
         match &frames[0].2 {
-            Event::ApprovalResolved { nonce, decision } => {
+            Event::ApprovalResolved {
+                nonce, decision, ..
+            } => {
                 assert_eq!(nonce.0, "nonce-a");
                 assert!(matches!(decision, ApprovalDecision::Cancelled));
             }
             other => panic!("frame 0: expected ApprovalResolved, got {other:?}"),
         }
         match &frames[1].2 {
-            Event::ApprovalResolved { nonce, decision } => {
+            Event::ApprovalResolved {
+                nonce, decision, ..
+            } => {
                 assert_eq!(nonce.0, "nonce-b");
                 assert!(matches!(decision, ApprovalDecision::Cancelled));
             }
@@ -6,21 +6,57 @@
 //! the place where the existing aoe sandbox/worktree security applies to
 //! the agent's command execution.
 //!
-//! For the MVP we keep the surface narrow: spawn a one-shot command,
-//! capture stdout+stderr to a string buffer, and return on exit. Long-
-//! running terminals (e.g. live `tail -f`) are a follow-up.
+//! `terminal/create` returns as soon as the process is spawned. Output is
+//! read concurrently from stdout and stderr into one bounded buffer that
+//! drops from the front once `output_byte_limit` is reached, so
+//! `terminal/output` can be polled while a dev server, `tail -f`, or
+//! watch-mode test runner keeps running. `wait_for_exit` parks on a watch
+//! channel instead of holding the manager lock, so other terminals stay
+//! readable meanwhile. When the agent embeds a terminal in a tool call,
+//! [`TerminalManager::attach`] streams the live output to cockpit clients
+//! as `ToolCallContent` events.
 
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use nix::sys::signal::Signal;
 use thiserror::Error;
-use tokio::process::Command;
-use tokio::sync::Mutex;
-use tracing::info;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command};
+use tokio::sync::{mpsc, watch, Mutex, Notify};
+use tracing::{debug, info, warn};
 
+use super::state::Event;
 use crate::containers::container_interface::{docker_env_args, EnvEntry};
 
+/// Output retained per terminal when the agent doesn't send
+/// `output_byte_limit`.
+pub const DEFAULT_OUTPUT_BYTE_LIMIT: usize = 1024 * 1024;
+
+/// Tail of the output carried by each streamed `ToolCallContent` event.
+/// Every event is a full snapshot and lands in the event store, so this
+/// stays well below the agent-facing buffer.
+const STREAM_SNAPSHOT_BYTES: usize = 16 * 1024;
+
+/// Minimum gap between streamed snapshots of one terminal.
+const STREAM_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long `kill` waits after SIGTERM before sending SIGKILL.
+const KILL_GRACE: Duration = Duration::from_secs(3);
+
+/// How long to keep draining pipes after the process exits. A backgrounded
+/// grandchild (`sh -c "server &"`) can hold them open indefinitely; the
+/// exit status is published after this even if they haven't closed.
+const DRAIN_GRACE: Duration = Duration::from_millis(500);
+
+/// How often `watch_group` checks whether an exited host command's process
+/// group still has members.
+const GROUP_POLL: Duration = Duration::from_millis(250);
+
 /// Routing target for a terminal command. Built once per session in
 /// `SessionResources::sandbox` and consulted on every `terminal/create`.
 #[derive(Debug, Clone)]
@@ -47,21 +83,44 @@ pub enum TerminalError {
 /// Identifier returned to the agent on `terminal/create`.
 pub type TerminalId = String;
 
-/// One terminal's captured output and exit status.
+/// Everything `terminal/create` needs besides the session label.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalSpec {
+    pub command: String,
+    pub args: Vec<String>,
+    /// Extra environment variables from the request, on top of the
+    /// inherited (host) or forwarded (sandbox) environment.
+    pub env: Vec<(String, String)>,
+    /// Working directory; the caller is responsible for passing a path
+    /// inside the session's roots.
+    pub cwd: PathBuf,
+    /// Bytes of output to retain; `None` uses [`DEFAULT_OUTPUT_BYTE_LIMIT`].
+    pub output_byte_limit: Option<usize>,
+}
+
+/// How a terminal's process ended.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TerminalExit {
+    pub exit_code: Option<i32>,
+    /// Signal name (`SIGTERM`, ...) when the process was killed by one.
+    pub signal: Option<String>,
+}
+
+/// A terminal's retained output and, once it has exited, its exit status.
 ///
-/// `stdout` and `stderr` are decoded from raw child bytes via
-/// `String::from_utf8_lossy`, so invalid UTF-8 from misbehaving tools is
-/// replaced with U+FFFD rather than failing the whole capture (the agent
-/// still gets partial output instead of an error).
+/// Output is stdout and stderr interleaved in arrival order, decoded as
+/// UTF-8 with invalid sequences replaced by U+FFFD, so misbehaving tools
+/// still give the agent partial output instead of an error.
 #[derive(Debug, Clone)]
 pub struct TerminalOutput {
-    pub stdout: String,
-    pub stderr: String,
-    pub exit_code: Option<i32>,
+    pub output: String,
+    /// True once bytes were dropped from the front to respect the limit.
+    pub truncated: bool,
+    pub exit: Option<TerminalExit>,
 }
 
-/// Per-session terminal manager. Holds outputs of completed terminals so
-/// the agent can fetch them via `terminal/output` even after exit.
+/// Per-session terminal manager. Holds running and finished terminals
+/// until the agent releases them.
 #[derive(Debug, Clone, Default)]
 pub struct TerminalManager {
     inner: Arc<Mutex<TerminalManagerInner>>,
@@ -69,11 +128,183 @@ pub struct TerminalManager {
 
 #[derive(Debug, Default)]
 struct TerminalManagerInner {
-    outputs: std::collections::HashMap<TerminalId, TerminalOutput>,
+    terminals: HashMap<TerminalId, Arc<Terminal>>,
+}
+
+#[derive(Debug)]
+struct Terminal {
+    output: std::sync::Mutex<OutputBuffer>,
+    /// Bumped after every chunk so the stream task can wait for new output.
+    version: watch::Sender<u64>,
+    exit: watch::Sender<Option<TerminalExit>>,
+    kill: Notify,
+    /// Woken by `release`; after the command exits, `supervise` then
+    /// signals whatever it left running in its process group.
+    released: Notify,
+    /// Process group of a host command (its leader's pid), cleared once the
+    /// leader is reaped and the group is found empty. From then on the id
+    /// can belong to an unrelated group, so it must not be signalled.
+    pgid: std::sync::Mutex<Option<u32>>,
+    /// Set once a stream task is running; agents repeat the embedded
+    /// terminal on every update of its tool call.
+    streaming: AtomicBool,
+}
+
+impl Terminal {
+    /// Wake `supervise` to kill the command if it is still running, and
+    /// then whatever it left running in its process group. `supervise`
+    /// does the signalling because only it knows whether the group id is
+    /// still safe to use.
+    fn kill_group(&self) {
+        self.kill.notify_one();
+        self.released.notify_one();
+    }
+
+    fn pgid(&self) -> Option<u32> {
+        *self.pgid.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn snapshot(&self) -> TerminalOutput {
+        let buf = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        TerminalOutput {
+            output: buf.text(),
+            truncated: buf.truncated,
+            exit: self.exit.borrow().clone(),
+        }
+    }
+
+    fn push(&self, stream: usize, bytes: &[u8]) {
+        self.output
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(stream, bytes);
+        self.version.send_modify(|v| *v += 1);
+    }
+
+    fn finish_stream(&self, stream: usize) {
+        self.output
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .flush(stream);
+        self.version.send_modify(|v| *v += 1);
+    }
+
+    fn exited(&self) -> bool {
+        self.exit.borrow().is_some()
+    }
+}
+
+/// Retained terminal output, bounded to `limit` bytes.
+#[derive(Debug)]
+struct OutputBuffer {
+    /// Decoded UTF-8, kept as a ring so trimming the front doesn't move
+    /// the rest.
+    text: VecDeque<u8>,
+    truncated: bool,
+    limit: usize,
+    /// Incomplete UTF-8 sequence at the end of the last read, per stream
+    /// (0 = stdout, 1 = stderr).
+    pending: [Vec<u8>; 2],
+}
+
+impl OutputBuffer {
+    fn new(limit: usize) -> Self {
+        Self {
+            text: VecDeque::new(),
+            truncated: false,
+            limit,
+            pending: [Vec::new(), Vec::new()],
+        }
+    }
+
+    fn push(&mut self, stream: usize, bytes: &[u8]) {
+        let mut pending = std::mem::take(&mut self.pending[stream]);
+        pending.extend_from_slice(bytes);
+        let mut rest = pending.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(s) => {
+                    self.push_str(s);
+                    rest = &[];
+                    break;
+                }
+                Err(e) => {
+                    let (valid, tail) = rest.split_at(e.valid_up_to());
+                    self.text.extend(valid);
+                    match e.error_len() {
+                        Some(n) => {
+                            self.push_str(char::REPLACEMENT_CHARACTER.encode_utf8(&mut [0; 4]));
+                            rest = &tail[n..];
+                        }
+                        // Sequence split across reads; finish it next time.
+                        None => {
+                            rest = tail;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending[stream] = rest.to_vec();
+        self.trim();
+    }
+
+    /// Decode whatever is left of a stream that hit EOF mid-sequence.
+    fn flush(&mut self, stream: usize) {
+        let pending = std::mem::take(&mut self.pending[stream]);
+        if !pending.is_empty() {
+            self.push_str(&String::from_utf8_lossy(&pending));
+            self.trim();
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        self.text.extend(s.as_bytes());
+    }
+
+    /// The retained output as one string.
+    fn text(&self) -> String {
+        let (front, back) = self.text.as_slices();
+        let mut bytes = Vec::with_capacity(self.text.len());
+        bytes.extend_from_slice(front);
+        bytes.extend_from_slice(back);
+        String::from_utf8(bytes)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+    }
+
+    fn trim(&mut self) {
+        if self.text.len() <= self.limit {
+            return;
+        }
+        let mut cut = self.text.len() - self.limit;
+        // Skip UTF-8 continuation bytes so the cut lands on a char start.
+        while self.text.get(cut).is_some_and(|b| b & 0xC0 == 0x80) {
+            cut += 1;
+        }
+        self.text.drain(..cut);
+        self.truncated = true;
+    }
+}
+
+/// How `kill` reaches the process.
+#[derive(Debug, Clone)]
+enum KillTarget {
+    /// Host process, spawned as the leader of its own process group so
+    /// shells and whatever they started go down together.
+    Host { pid: Option<u32> },
+    /// Process inside a sandbox container. Killing the local `docker exec`
+    /// client would leave it running, so it starts under `setsid`, records
+    /// its pid (which is then its process group id) in the container, and
+    /// `kill` signals that group through a second exec.
+    Sandbox {
+        binary: &'static str,
+        container: String,
+        pid_file: String,
+    },
 }
 
 /// Build the `docker exec` argv and inherit-env pairs for a sandboxed
-/// `terminal/create` request. Pulled out of `create_and_run` so the wiring
+/// `terminal/create` request. Pulled out of `create` so the wiring
 /// can be unit tested without spawning docker. The runtime binary is
 /// intentionally not in the result, because the caller picks it based on
 /// the active runtime.
@@ -96,28 +327,52 @@ pub(crate) fn build_sandbox_exec_args(
     (full_args, inherit_pairs)
 }
 
+/// Wrap a sandboxed command so it runs as the leader of a new session,
+/// writing its pid to `pid_file` before `exec`ing so the recorded pid is
+/// both the command's and its process group's. The trailing `exit` keeps
+/// the outer shell from `exec`ing `setsid` in place: in a forked child,
+/// which is never a group leader, `setsid` doesn't fork again, and the
+/// outer shell waits for the command's exit status.
+fn pid_file_wrapper(pid_file: &str, command: &str, args: &[String]) -> Vec<String> {
+    let mut wrapped = vec![
+        "-c".to_string(),
+        format!("setsid sh -c 'echo $$ > {pid_file}; exec \"$@\"' sh \"$@\"; exit $?"),
+        "sh".to_string(),
+        command.to_string(),
+    ];
+    wrapped.extend(args.iter().cloned());
+    wrapped
+}
+
+/// Shell script that sends `signal` to the process group recorded in
+/// `pid_file`. The SIGKILL pass is the last one, so it also removes the
+/// pid file. dash's `kill` rejects `--`, so the group is the bare `-pid`.
+fn sandbox_kill_script(pid_file: &str, signal: Signal) -> String {
+    let name = signal.as_str().trim_start_matches("SIG");
+    let mut script =
+        format!("pid=$(cat {pid_file} 2>/dev/null) && kill -{name} -\"$pid\" 2>/dev/null");
+    if signal == Signal::SIGKILL {
+        script.push_str(&format!("; rm -f {pid_file}"));
+    }
+    script
+}
+
 impl TerminalManager {
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Spawn a one-shot terminal: run a command, wait for exit, capture
-    /// stdout/stderr. The terminal id is generated from a counter. Returns
-    /// the id immediately; the caller should `wait_for_exit` (or trust
-    /// `output` after a brief delay) for results.
+    /// Spawn a terminal and return its id without waiting for the command
+    /// to finish. The id is a random UUID.
     ///
-    /// `cwd` is the working directory; the caller is responsible for
-    /// passing the session's worktree path. When `sandbox` is `Some`
-    /// the command is routed through `docker exec` so it runs inside
-    /// the session's sandbox container; `cwd` is interpreted as a
-    /// container path in that case (the agent already speaks in
-    /// container paths).
-    pub async fn create_and_run(
+    /// When `sandbox` is `Some` the command is routed through `docker exec`
+    /// so it runs inside the session's sandbox container; `spec.cwd` is
+    /// interpreted as a container path in that case (the agent already
+    /// speaks in container paths).
+    pub async fn create(
         &self,
         session_id: &str,
-        command: &str,
-        args: Vec<String>,
-        cwd: PathBuf,
+        spec: TerminalSpec,
         sandbox: Option<&TerminalSandbox>,
     ) -> Result<TerminalId, TerminalError> {
         let id = format!("term-{}", uuid::Uuid::new_v4().simple());
@@ -125,155 +380,755 @@ impl TerminalManager {
             target: "cockpit.terminal",
             session = %session_id,
             terminal = %id,
-            command = %command,
-            cwd = %cwd.display(),
+            command = %spec.command,
+            cwd = %spec.cwd.display(),
             sandboxed = sandbox.is_some(),
             "terminal/create"
         );
 
-        let child = match sandbox {
+        let (mut child, kill_target) = match sandbox {
             Some(s) => {
                 let runtime = crate::containers::get_container_runtime();
                 let binary = runtime.base.binary;
-                let (full_args, inherit_pairs) = build_sandbox_exec_args(s, &cwd, command, &args);
+                let pid_file = format!("/tmp/aoe-{id}.pid");
+                let mut sandbox = s.clone();
+                sandbox
+                    .env_entries
+                    .extend(spec.env.iter().map(|(key, value)| EnvEntry::Literal {
+                        key: key.clone(),
+                        value: value.clone(),
+                    }));
+                let (full_args, inherit_pairs) = build_sandbox_exec_args(
+                    &sandbox,
+                    &spec.cwd,
+                    "sh",
+                    &pid_file_wrapper(&pid_file, &spec.command, &spec.args),
+                );
                 let mut cmd = Command::new(binary);
                 cmd.args(&full_args)
                     .stdin(Stdio::null())
                     .stdout(Stdio::piped())
-                    .stderr(Stdio::piped());
+                    .stderr(Stdio::piped())
+                    .kill_on_drop(true);
                 for (k, v) in inherit_pairs {
                     cmd.env(k, v);
                 }
-                cmd.spawn()?
+                (
+                    cmd.spawn()?,
+                    KillTarget::Sandbox {
+                        binary,
+                        container: s.container_name.clone(),
+                        pid_file,
+                    },
+                )
+            }
+            None => {
+                let child = Command::new(&spec.command)
+                    .args(&spec.args)
+                    .envs(spec.env.iter().map(|(k, v)| (k, v)))
+                    .current_dir(&spec.cwd)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .process_group(0)
+                    .kill_on_drop(true)
+                    .spawn()?;
+                let pid = child.id();
+                (child, KillTarget::Host { pid })
             }
-            None => Command::new(command)
-                .args(&args)
-                .current_dir(&cwd)
-                .stdin(Stdio::null())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()?,
         };
 
-        // Drain stdout, stderr, and wait() concurrently. Reading
-        // stdout to EOF before stderr deadlocks when the child fills
-        // its stderr pipe buffer first (~64 KiB Linux, ~16 KiB macOS):
-        // the child blocks on the next stderr write, stdout never
-        // closes, this task hangs forever holding the child. Reachable
-        // via the agent-exposed ACP `terminal/create`. `from_utf8_lossy`
-        // keeps non-UTF8 output from failing the whole capture.
-        let raw = child.wait_with_output().await?;
-        let output = TerminalOutput {
-            stdout: String::from_utf8_lossy(&raw.stdout).into_owned(),
-            stderr: String::from_utf8_lossy(&raw.stderr).into_owned(),
-            exit_code: raw.status.code(),
+        let limit = spec.output_byte_limit.unwrap_or(DEFAULT_OUTPUT_BYTE_LIMIT);
+        let pgid = match kill_target {
+            KillTarget::Host { pid } => pid,
+            KillTarget::Sandbox { .. } => None,
         };
+        let terminal = Arc::new(Terminal {
+            output: std::sync::Mutex::new(OutputBuffer::new(limit)),
+            version: watch::Sender::new(0),
+            exit: watch::Sender::new(None),
+            kill: Notify::new(),
+            released: Notify::new(),
+            pgid: std::sync::Mutex::new(pgid),
+            streaming: AtomicBool::new(false),
+        });
+
+        // Drain stdout and stderr on their own tasks. Reading one to EOF
+        // before the other deadlocks when the child fills the other pipe's
+        // buffer first (~64 KiB Linux, ~16 KiB macOS): the child blocks on
+        // its next write and the first pipe never closes.
+        let readers = [
+            child
+                .stdout
+                .take()
+                .map(|r| spawn_reader(terminal.clone(), 0, r)),
+            child
+                .stderr
+                .take()
+                .map(|r| spawn_reader(terminal.clone(), 1, r)),
+        ];
+        tokio::spawn(supervise(
+            terminal.clone(),
+            child,
+            kill_target,
+            readers,
+            id.clone(),
+        ));
 
-        self.inner.lock().await.outputs.insert(id.clone(), output);
+        self.inner
+            .lock()
+            .await
+            .terminals
+            .insert(id.clone(), terminal);
         Ok(id)
     }
 
-    /// Returns the captured output of a terminal. Implements ACP
-    /// `terminal/output` and `terminal/wait_for_exit` for the one-shot
-    /// case where the terminal has already finished.
-    pub async fn output(&self, terminal_id: &str) -> Result<TerminalOutput, TerminalError> {
-        let inner = self.inner.lock().await;
-        inner
-            .outputs
+    async fn get(&self, terminal_id: &str) -> Result<Arc<Terminal>, TerminalError> {
+        self.inner
+            .lock()
+            .await
+            .terminals
             .get(terminal_id)
             .cloned()
             .ok_or_else(|| TerminalError::UnknownTerminal(terminal_id.into()))
     }
 
-    /// Implements ACP `terminal/release` by dropping the captured output.
+    /// Implements ACP `terminal/output`: the output retained so far, and
+    /// the exit status if the command has finished.
+    pub async fn output(&self, terminal_id: &str) -> Result<TerminalOutput, TerminalError> {
+        Ok(self.get(terminal_id).await?.snapshot())
+    }
+
+    /// Implements ACP `terminal/wait_for_exit`. Only this terminal's watch
+    /// channel is held while waiting.
+    pub async fn wait_for_exit(&self, terminal_id: &str) -> Result<TerminalExit, TerminalError> {
+        let terminal = self.get(terminal_id).await?;
+        let mut rx = terminal.exit.subscribe();
+        let exit = rx
+            .wait_for(Option::is_some)
+            .await
+            .map(|exit| exit.clone().unwrap_or_default())
+            .unwrap_or_default();
+        Ok(exit)
+    }
+
+    /// Implements ACP `terminal/kill`. The terminal stays readable until
+    /// released; killing one that already exited is a no-op.
+    pub async fn kill(&self, terminal_id: &str) -> Result<(), TerminalError> {
+        self.get(terminal_id).await?.kill.notify_one();
+        Ok(())
+    }
+
+    /// Implements ACP `terminal/release`: kill the command if it is still
+    /// running and forget the terminal.
     pub async fn release(&self, terminal_id: &str) -> Result<(), TerminalError> {
-        let mut inner = self.inner.lock().await;
-        if inner.outputs.remove(terminal_id).is_none() {
-            return Err(TerminalError::UnknownTerminal(terminal_id.into()));
-        }
+        let terminal = self
+            .inner
+            .lock()
+            .await
+            .terminals
+            .remove(terminal_id)
+            .ok_or_else(|| TerminalError::UnknownTerminal(terminal_id.into()))?;
+        terminal.kill_group();
         Ok(())
     }
+
+    /// Kill and forget every terminal. Called when the agent connection
+    /// ends. Host terminals have their process group signalled too, so
+    /// backgrounded processes don't outlive the session even when the
+    /// command that started them has already exited.
+    pub async fn kill_all(&self) {
+        let terminals: Vec<_> = self.inner.lock().await.terminals.drain().collect();
+        for (_, terminal) in terminals {
+            terminal.kill_group();
+        }
+    }
+
+    /// Stream a terminal's output to cockpit clients as `ToolCallContent`
+    /// snapshots for `tool_call_id`, called when the agent embeds the
+    /// terminal in a tool call. Snapshots carry the tail of the output and
+    /// are sent at most every [`STREAM_INTERVAL`], plus once after exit.
+    /// Only the first attach per terminal starts a stream; unknown ids are
+    /// ignored.
+    pub async fn attach(
+        &self,
+        terminal_id: &str,
+        tool_call_id: String,
+        events: mpsc::Sender<Event>,
+    ) {
+        let Ok(terminal) = self.get(terminal_id).await else {
+            return;
+        };
+        if terminal.streaming.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        debug!(
+            target: "cockpit.terminal",
+            terminal = %terminal_id,
+            tool_call = %tool_call_id,
+            "streaming terminal output to tool call"
+        );
+        tokio::spawn(stream_output(terminal, tool_call_id, events));
+    }
+}
+
+fn spawn_reader<R>(
+    terminal: Arc<Terminal>,
+    stream: usize,
+    mut reader: R,
+) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buf = vec![0u8; 8192];
+        loop {
+            match reader.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => terminal.push(stream, &buf[..n]),
+            }
+        }
+        terminal.finish_stream(stream);
+    })
+}
+
+/// Own the child until it exits (or is killed), then publish its exit
+/// status once the pipes have drained. The command's process group is
+/// signalled before the leader is reaped; after that `watch_group` (host)
+/// or `release_sandbox_group` takes over.
+async fn supervise(
+    terminal: Arc<Terminal>,
+    mut child: Child,
+    kill_target: KillTarget,
+    readers: [Option<tokio::task::JoinHandle<()>>; 2],
+    id: TerminalId,
+) {
+    let status = tokio::select! {
+        status = child.wait() => status,
+        _ = terminal.kill.notified() => {
+            info!(target: "cockpit.terminal", terminal = %id, "terminal/kill");
+            terminate(&kill_target, Signal::SIGTERM).await;
+            match tokio::time::timeout(KILL_GRACE, child.wait()).await {
+                Ok(status) => status,
+                Err(_) => {
+                    terminate(&kill_target, Signal::SIGKILL).await;
+                    let _ = child.start_kill();
+                    child.wait().await
+                }
+            }
+        }
+    };
+    let _ = tokio::time::timeout(DRAIN_GRACE, async {
+        for reader in readers.into_iter().flatten() {
+            let _ = reader.await;
+        }
+    })
+    .await;
+    let exit = match status {
+        Ok(status) => exit_from_status(status),
+        Err(e) => {
+            warn!(target: "cockpit.terminal", terminal = %id, "wait failed: {e}");
+            TerminalExit::default()
+        }
+    };
+    debug!(
+        target: "cockpit.terminal",
+        terminal = %id,
+        exit_code = ?exit.exit_code,
+        signal = ?exit.signal,
+        "terminal exited"
+    );
+    terminal.exit.send_replace(Some(exit));
+    match &kill_target {
+        KillTarget::Host { .. } => watch_group(&terminal, &id).await,
+        KillTarget::Sandbox { .. } => release_sandbox_group(&terminal, &kill_target).await,
+    }
+}
+
+/// Once the leader is reaped its pid, which is the group id, goes back into
+/// the pool as soon as the rest of the group exits. Poll the group until it
+/// is empty and forget it then; on release, signal it only while it still
+/// has members.
+async fn watch_group(terminal: &Terminal, id: &str) {
+    use nix::errno::Errno;
+    use nix::sys::signal::killpg;
+    use nix::unistd::Pid;
+
+    let Some(pgid) = terminal.pgid() else {
+        return;
+    };
+    let group = Pid::from_raw(pgid as i32);
+    loop {
+        if killpg(group, None) == Err(Errno::ESRCH) {
+            debug!(target: "cockpit.terminal", terminal = %id, "process group empty");
+            break;
+        }
+        tokio::select! {
+            _ = terminal.released.notified() => {
+                if killpg(group, None) != Err(Errno::ESRCH) {
+                    let _ = killpg(group, Signal::SIGTERM);
+                }
+                break;
+            }
+            _ = tokio::time::sleep(GROUP_POLL) => {}
+        }
+    }
+    *terminal.pgid.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// The container's pid namespace can't be polled from the host, so a
+/// sandboxed command's group is only cleaned up on release: SIGTERM, then
+/// SIGKILL after [`KILL_GRACE`] for whatever ignored it.
+async fn release_sandbox_group(terminal: &Terminal, target: &KillTarget) {
+    terminal.released.notified().await;
+    terminate(target, Signal::SIGTERM).await;
+    tokio::time::sleep(KILL_GRACE).await;
+    terminate(target, Signal::SIGKILL).await;
+}
+
+/// Send `signal` to the command's process group. `supervise` sends
+/// SIGTERM first and SIGKILL after [`KILL_GRACE`].
+async fn terminate(target: &KillTarget, signal: Signal) {
+    match target {
+        KillTarget::Host { pid: Some(pid) } => {
+            use nix::sys::signal::killpg;
+            use nix::unistd::Pid;
+            let _ = killpg(Pid::from_raw(*pid as i32), signal);
+        }
+        KillTarget::Host { pid: None } => {}
+        KillTarget::Sandbox {
+            binary,
+            container,
+            pid_file,
+        } => {
+            let script = sandbox_kill_script(pid_file, signal);
+            let result = Command::new(binary)
+                .args(["exec", container.as_str(), "sh", "-c", &script])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await;
+            if let Err(e) = result {
+                warn!(target: "cockpit.terminal", container = %container, "kill in sandbox failed: {e}");
+            }
+        }
+    }
+}
+
+fn exit_from_status(status: std::process::ExitStatus) -> TerminalExit {
+    use std::os::unix::process::ExitStatusExt;
+    TerminalExit {
+        exit_code: status.code(),
+        signal: status.signal().map(|sig| {
+            nix::sys::signal::Signal::try_from(sig)
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_else(|_| sig.to_string())
+        }),
+    }
+}
+
+async fn stream_output(terminal: Arc<Terminal>, tool_call_id: String, events: mpsc::Sender<Event>) {
+    let mut version = terminal.version.subscribe();
+    let mut exit = terminal.exit.subscribe();
+    let mut last_sent: Option<u64> = None;
+    loop {
+        let done = terminal.exited();
+        let current = *version.borrow_and_update();
+        if last_sent != Some(current) {
+            let content = stream_snapshot(&terminal.snapshot());
+            if !content.is_empty() {
+                let event = Event::ToolCallContent {
+                    tool_call_id: tool_call_id.clone(),
+                    content,
+                };
+                if events.send(event).await.is_err() {
+                    return;
+                }
+            }
+            last_sent = Some(current);
+        }
+        if done {
+            return;
+        }
+        tokio::select! {
+            _ = version.changed() => {}
+            _ = exit.changed() => {}
+        }
+        tokio::time::sleep(STREAM_INTERVAL).await;
+    }
+}
+
+/// Tail of the output for a streamed snapshot, marked when anything
+/// before it was dropped.
+fn stream_snapshot(out: &TerminalOutput) -> String {
+    let text = out.output.as_str();
+    if text.len() <= STREAM_SNAPSHOT_BYTES && !out.truncated {
+        return text.to_string();
+    }
+    let mut cut = text.len().saturating_sub(STREAM_SNAPSHOT_BYTES);
+    while !text.is_char_boundary(cut) {
+        cut += 1;
+    }
+    format!("[earlier output truncated]\n{}", &text[cut..])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn spec(command: &str, args: &[&str]) -> TerminalSpec {
+        TerminalSpec {
+            command: command.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: std::env::temp_dir(),
+            ..TerminalSpec::default()
+        }
+    }
+
+    /// Wait for exit with a timeout so a regression fails fast instead of
+    /// stalling the test run.
+    async fn wait(mgr: &TerminalManager, id: &str) -> TerminalExit {
+        tokio::time::timeout(Duration::from_secs(10), mgr.wait_for_exit(id))
+            .await
+            .expect("terminal never exited")
+            .unwrap()
+    }
+
     #[tokio::test]
     async fn create_runs_and_captures_output() {
         let mgr = TerminalManager::new();
-        let cwd = std::env::temp_dir();
         let id = mgr
-            .create_and_run("s-1", "echo", vec!["hello".into()], cwd, None)
+            .create("s-1", spec("echo", &["hello"]), None)
             .await
             .unwrap();
+        assert_eq!(wait(&mgr, &id).await.exit_code, Some(0));
         let out = mgr.output(&id).await.unwrap();
-        assert!(out.stdout.contains("hello"));
-        assert_eq!(out.exit_code, Some(0));
+        assert!(out.output.contains("hello"));
+        assert!(!out.truncated);
+        assert_eq!(out.exit.unwrap().exit_code, Some(0));
+    }
+
+    #[tokio::test]
+    async fn create_passes_request_env() {
+        let mgr = TerminalManager::new();
+        let mut spec = spec("sh", &["-c", "echo $AOE_TERMINAL_TEST"]);
+        spec.env = vec![("AOE_TERMINAL_TEST".into(), "from-request".into())];
+        let id = mgr.create("s-env", spec, None).await.unwrap();
+        wait(&mgr, &id).await;
+        assert!(mgr
+            .output(&id)
+            .await
+            .unwrap()
+            .output
+            .contains("from-request"));
     }
 
     #[tokio::test]
     async fn release_removes_terminal() {
         let mgr = TerminalManager::new();
-        let cwd = std::env::temp_dir();
+        let id = mgr.create("s-1", spec("true", &[]), None).await.unwrap();
+        mgr.release(&id).await.unwrap();
+        let result = mgr.output(&id).await;
+        assert!(matches!(result, Err(TerminalError::UnknownTerminal(_))));
+    }
+
+    #[tokio::test]
+    async fn output_is_readable_while_running() {
+        let mgr = TerminalManager::new();
+        let id = mgr
+            .create("s-live", spec("sh", &["-c", "echo ready; sleep 30"]), None)
+            .await
+            .unwrap();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            let out = mgr.output(&id).await.unwrap();
+            if out.output.contains("ready") {
+                assert!(out.exit.is_none(), "still running");
+                break;
+            }
+            assert!(tokio::time::Instant::now() < deadline, "no live output");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        mgr.kill(&id).await.unwrap();
+        let exit = wait(&mgr, &id).await;
+        assert_eq!(exit.signal.as_deref(), Some("SIGTERM"));
+        // Killed terminals stay readable until released.
+        assert!(mgr.output(&id).await.unwrap().output.contains("ready"));
+    }
+
+    #[tokio::test]
+    async fn kill_reaches_backgrounded_children() {
+        let mgr = TerminalManager::new();
+        let id = mgr
+            .create(
+                "s-bg",
+                spec("sh", &["-c", "sleep 30 & echo $!; wait"]),
+                None,
+            )
+            .await
+            .unwrap();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        let pid = loop {
+            let out = mgr.output(&id).await.unwrap();
+            if let Ok(pid) = out.output.trim().parse::<i32>() {
+                break pid;
+            }
+            assert!(tokio::time::Instant::now() < deadline, "no pid printed");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+        mgr.kill(&id).await.unwrap();
+        wait(&mgr, &id).await;
+        // The background sleep is in the terminal's process group, so it
+        // got the SIGTERM too.
+        wait_gone(pid).await;
+    }
+
+    #[tokio::test]
+    async fn release_reaches_children_of_an_exited_command() {
+        let mgr = TerminalManager::new();
         let id = mgr
-            .create_and_run("s-1", "true", vec![], cwd, None)
+            .create(
+                "s-orphan",
+                spec("sh", &["-c", "sleep 30 >/dev/null 2>&1 & echo $!"]),
+                None,
+            )
+            .await
+            .unwrap();
+        wait(&mgr, &id).await;
+        let pid: i32 = mgr
+            .output(&id)
             .await
+            .unwrap()
+            .output
+            .trim()
+            .parse()
             .unwrap();
         mgr.release(&id).await.unwrap();
-        let result = mgr.output(&id).await;
-        assert!(matches!(result, Err(TerminalError::UnknownTerminal(_))));
+        wait_gone(pid).await;
+    }
+
+    #[tokio::test]
+    async fn process_group_is_forgotten_once_empty() {
+        let mgr = TerminalManager::new();
+        let id = mgr
+            .create(
+                "s-group",
+                spec("sh", &["-c", "sleep 1 >/dev/null 2>&1 &"]),
+                None,
+            )
+            .await
+            .unwrap();
+        wait(&mgr, &id).await;
+        let terminal = mgr.get(&id).await.unwrap();
+        // The background sleep keeps the group, and so its id, alive.
+        assert!(terminal.pgid().is_some());
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while terminal.pgid().is_some() {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "group id kept after the group emptied"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    /// Wait for `pid` to exit. It may linger briefly as a zombie.
+    async fn wait_gone(pid: i32) {
+        let gone = || {
+            std::fs::read_to_string(format!("/proc/{pid}/stat"))
+                .map(|stat| stat.contains(") Z"))
+                .unwrap_or(true)
+        };
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while !gone() {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "background child survived kill"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn output_byte_limit_truncates_from_the_front() {
+        let mgr = TerminalManager::new();
+        let mut spec = spec("sh", &["-c", "printf 'abcdefghij'"]);
+        spec.output_byte_limit = Some(4);
+        let id = mgr.create("s-limit", spec, None).await.unwrap();
+        wait(&mgr, &id).await;
+        let out = mgr.output(&id).await.unwrap();
+        assert_eq!(out.output, "ghij");
+        assert!(out.truncated);
+    }
+
+    #[test]
+    fn output_buffer_keeps_utf8_split_across_reads() {
+        let mut buf = OutputBuffer::new(1024);
+        let bytes = "héllo".as_bytes();
+        buf.push(0, &bytes[..2]);
+        buf.push(0, &bytes[2..]);
+        buf.push(1, b"\xffok");
+        assert_eq!(buf.text(), "héllo\u{FFFD}ok");
+    }
+
+    #[test]
+    fn output_buffer_trims_on_char_boundary() {
+        let mut buf = OutputBuffer::new(3);
+        buf.push(0, "aéb".as_bytes());
+        buf.push(0, b"c");
+        // "aébc" is 5 bytes; dropping 2 would split "é", so it drops 3.
+        assert_eq!(buf.text(), "bc");
+        assert!(buf.truncated);
+    }
+
+    #[tokio::test]
+    async fn attach_streams_snapshots_until_exit() {
+        let mgr = TerminalManager::new();
+        let id = mgr
+            .create(
+                "s-stream",
+                spec("sh", &["-c", "echo one; sleep 0.4; echo two"]),
+                None,
+            )
+            .await
+            .unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        mgr.attach(&id, "tc-1".into(), tx).await;
+        let mut last = String::new();
+        while let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(10), rx.recv()).await {
+            match event {
+                Event::ToolCallContent {
+                    tool_call_id,
+                    content,
+                } => {
+                    assert_eq!(tool_call_id, "tc-1");
+                    last = content;
+                }
+                other => panic!("unexpected event {other:?}"),
+            }
+        }
+        // The stream task ends after exit, closing the channel, and its
+        // last snapshot holds the complete output.
+        assert_eq!(last, "one\ntwo\n");
+    }
+
+    #[test]
+    fn stream_snapshot_marks_dropped_output() {
+        let long = "x".repeat(STREAM_SNAPSHOT_BYTES + 10);
+        let snap = stream_snapshot(&TerminalOutput {
+            output: long,
+            truncated: false,
+            exit: None,
+        });
+        assert!(snap.starts_with("[earlier output truncated]\n"));
+        assert_eq!(
+            snap.len(),
+            "[earlier output truncated]\n".len() + STREAM_SNAPSHOT_BYTES
+        );
+    }
+
+    #[test]
+    fn pid_file_wrapper_execs_the_command() {
+        let argv = pid_file_wrapper("/tmp/aoe-t.pid", "npm", &["run".into(), "dev".into()]);
+        assert_eq!(
+            argv,
+            [
+                "-c",
+                "setsid sh -c 'echo $$ > /tmp/aoe-t.pid; exec \"$@\"' sh \"$@\"; exit $?",
+                "sh",
+                "npm",
+                "run",
+                "dev"
+            ]
+        );
+    }
+
+    /// Runs the sandbox wrapper and kill scripts with the host's `sh`, as
+    /// `docker exec` would inside the container.
+    #[tokio::test]
+    async fn sandbox_kill_reaches_backgrounded_grandchildren() {
+        use tokio::io::AsyncBufReadExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let pid_file = tmp.path().join("term.pid").to_string_lossy().into_owned();
+        // The grandchild ignores SIGTERM, so only the SIGKILL pass stops it.
+        let command = "sh -c 'trap \"\" TERM; sleep 30' & echo $!; wait";
+        let mut child = Command::new("sh")
+            .args(pid_file_wrapper(
+                &pid_file,
+                "sh",
+                &["-c".into(), command.into()],
+            ))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            // Like a `docker exec` process, the outer shell leads a group.
+            .process_group(0)
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let mut lines = tokio::io::BufReader::new(child.stdout.take().unwrap()).lines();
+        let pid: i32 = tokio::time::timeout(Duration::from_secs(5), lines.next_line())
+            .await
+            .expect("no pid printed")
+            .unwrap()
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        let run = |signal| {
+            std::process::Command::new("sh")
+                .args(["-c", &sandbox_kill_script(&pid_file, signal)])
+                .status()
+                .unwrap()
+        };
+        run(Signal::SIGTERM);
+        tokio::time::timeout(Duration::from_secs(5), child.wait())
+            .await
+            .expect("command survived SIGTERM")
+            .unwrap();
+        assert!(
+            std::fs::read_to_string(format!("/proc/{pid}/stat")).is_ok_and(|s| !s.contains(") Z")),
+            "grandchild should have ignored SIGTERM"
+        );
+        run(Signal::SIGKILL);
+        wait_gone(pid).await;
+        assert!(!std::path::Path::new(&pid_file).exists());
     }
 
     // Regression: pipe-buffer deadlock when stderr exceeds the
     // OS pipe buffer (~64 KiB Linux, ~16 KiB macOS) before stdout
     // closes. 200 KiB on stderr clears both thresholds. Pre-fix
-    // this test hangs forever; the outer timeout makes a regression
+    // this test hangs forever; the timeout in `wait` makes a regression
     // surface as a fast failure instead of a stalled CI job.
     #[tokio::test]
     async fn large_stderr_does_not_deadlock() {
         let mgr = TerminalManager::new();
-        let cwd = std::env::temp_dir();
         let script = "head -c 204800 /dev/zero | tr '\\0' 'x' >&2; echo done";
-        let run = mgr.create_and_run(
-            "s-large-stderr",
-            "sh",
-            vec!["-c".into(), script.into()],
-            cwd,
-            None,
-        );
-        let id = tokio::time::timeout(std::time::Duration::from_secs(5), run)
+        let id = mgr
+            .create("s-large-stderr", spec("sh", &["-c", script]), None)
             .await
-            .expect("create_and_run hung; pipe deadlock regressed")
-            .expect("create_and_run failed");
+            .expect("create failed");
+        assert_eq!(wait(&mgr, &id).await.exit_code, Some(0));
         let out = mgr.output(&id).await.unwrap();
-        assert_eq!(out.stderr.len(), 204_800);
-        assert!(out.stdout.contains("done"));
-        assert_eq!(out.exit_code, Some(0));
+        assert_eq!(out.output.matches('x').count(), 204_800);
+        assert!(out.output.contains("done"));
     }
 
     #[tokio::test]
     async fn large_stdout_and_stderr_drain_concurrently() {
         let mgr = TerminalManager::new();
-        let cwd = std::env::temp_dir();
         let script = "head -c 204800 /dev/zero | tr '\\0' 'o' \
                       & head -c 204800 /dev/zero | tr '\\0' 'e' >&2 \
                       & wait";
-        let run = mgr.create_and_run(
-            "s-both-pipes",
-            "sh",
-            vec!["-c".into(), script.into()],
-            cwd,
-            None,
-        );
-        let id = tokio::time::timeout(std::time::Duration::from_secs(5), run)
+        let id = mgr
+            .create("s-both-pipes", spec("sh", &["-c", script]), None)
             .await
-            .expect("create_and_run hung; pipe deadlock regressed")
-            .expect("create_and_run failed");
+            .expect("create failed");
+        assert_eq!(wait(&mgr, &id).await.exit_code, Some(0));
         let out = mgr.output(&id).await.unwrap();
-        assert_eq!(out.stdout.len(), 204_800);
-        assert_eq!(out.stderr.len(), 204_800);
-        assert_eq!(out.exit_code, Some(0));
+        assert_eq!(out.output.matches('o').count(), 204_800);
+        assert_eq!(out.output.matches('e').count(), 204_800);
     }
 
     #[test]
@@ -0,0 +1,587 @@
+//! Usage and rate-limit time series across cockpit sessions.
+//!
+//! The server's cockpit event listener turns three events into samples
+//! stored next to the event log (see `EventStore::record_usage`):
+//! finished turns (`Stopped` with `prompt_complete` or `cancelled`),
+//! context/cost snapshots (`UsageUpdated`), and quota hits
+//! (`RateLimit`). Each sample carries the agent and the provider it
+//! bills against, so sessions that share one subscription (six Claude
+//! sessions on one plan) are read together. [`summarize`] folds the
+//! samples into the `GET /api/cockpit/usage` report that
+//! `aoe cockpit usage` and the TUI usage panel render.
+//!
+//! Agents don't report remaining quota until they hit the limit, so
+//! the headroom per provider is an estimate: the turns that fit in the
+//! [`QUOTA_WINDOW`] before the most recent limit, minus the turns taken
+//! in the current window.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::state::Event;
+
+/// Rolling window turns are counted over. Matches the five-hour
+/// session window of Anthropic's subscription plans, the quota most
+/// cockpit users share across sessions.
+pub const QUOTA_WINDOW: Duration = Duration::hours(5);
+
+/// Samples older than this are pruned on insert.
+pub const RETENTION: Duration = Duration::days(7);
+
+/// Hourly buckets in [`ProviderUsage::hourly_turns`].
+const HISTORY_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleKind {
+    /// A prompt ran to completion (or was cancelled).
+    Turn,
+    /// Context-window and cost snapshot from `UsageUpdated`.
+    Usage,
+    /// The provider refused a prompt for quota.
+    RateLimit,
+}
+
+impl SampleKind {
+    /// The sample an event produces, if any.
+    pub fn for_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Stopped { reason } if reason == "prompt_complete" || reason == "cancelled" => {
+                Some(Self::Turn)
+            }
+            Event::UsageUpdated { .. } => Some(Self::Usage),
+            Event::RateLimit { .. } => Some(Self::RateLimit),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Turn => "turn",
+            Self::Usage => "usage",
+            Self::RateLimit => "rate_limit",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "turn" => Some(Self::Turn),
+            "usage" => Some(Self::Usage),
+            "rate_limit" => Some(Self::RateLimit),
+            _ => None,
+        }
+    }
+}
+
+/// One row of the usage time series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageSample {
+    pub session_id: String,
+    pub agent: String,
+    pub provider: String,
+    pub recorded_at: DateTime<Utc>,
+    pub kind: SampleKind,
+    pub context_used: Option<u64>,
+    pub context_size: Option<u64>,
+    pub cost: Option<f64>,
+    pub currency: Option<String>,
+    /// Rate-limit message from the agent.
+    pub status: Option<String>,
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+impl UsageSample {
+    /// Build the sample for `event`, or `None` when the event isn't one
+    /// the time series tracks.
+    pub fn from_event(
+        session_id: &str,
+        agent: &str,
+        provider: &str,
+        recorded_at: DateTime<Utc>,
+        event: &Event,
+    ) -> Option<Self> {
+        let mut sample = Self {
+            session_id: session_id.to_string(),
+            agent: agent.to_string(),
+            provider: provider.to_string(),
+            recorded_at,
+            kind: SampleKind::for_event(event)?,
+            context_used: None,
+            context_size: None,
+            cost: None,
+            currency: None,
+            status: None,
+            resets_at: None,
+        };
+        match event {
+            Event::UsageUpdated { usage } => {
+                sample.context_used = Some(usage.used);
+                sample.context_size = Some(usage.size);
+                if let Some(cost) = &usage.cost {
+                    sample.cost = Some(cost.amount);
+                    sample.currency = Some(cost.currency.clone());
+                }
+            }
+            Event::RateLimit { info } => {
+                sample.status = Some(info.status.clone());
+                sample.resets_at = Some(info.resets_at);
+            }
+            _ => {}
+        }
+        Some(sample)
+    }
+}
+
+/// The provider whose quota `agent` draws on. Single-vendor agents map
+/// to their vendor; multi-provider agents (`aoe-agent`, `opencode`, …)
+/// use the `provider/model` or `provider:model` prefix of the session's
+/// model when there is one, and fall back to the agent name.
+pub fn provider_for(agent: &str, model: Option<&str>) -> String {
+    let fixed = match agent {
+        "claude" | "claude-code" => Some("anthropic"),
+        "codex" => Some("openai"),
+        "gemini" => Some("google"),
+        "vibe" => Some("mistral"),
+        _ => None,
+    };
+    if let Some(provider) = fixed {
+        return provider.to_string();
+    }
+    model
+        .and_then(|m| m.split_once(['/', ':']))
+        .map(|(provider, _)| provider.trim().to_ascii_lowercase())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| agent.to_string())
+}
+
+/// `GET /api/cockpit/usage` response.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageReport {
+    pub generated_at: DateTime<Utc>,
+    /// Length of the rolling window `window_turns` counts over.
+    pub window_hours: i64,
+    pub providers: Vec<ProviderUsage>,
+}
+
+/// Usage of one provider across every session that bills against it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderUsage {
+    pub provider: String,
+    /// Agents seen drawing on this provider.
+    pub agents: Vec<String>,
+    /// Set while the provider is throttled: the reset time of the
+    /// latest rate limit, if no turn has completed since.
+    pub limited_until: Option<DateTime<Utc>>,
+    /// The agent's message for that rate limit.
+    pub limit_status: Option<String>,
+    /// When the most recent rate limit was hit.
+    pub last_limit_at: Option<DateTime<Utc>>,
+    /// Turns finished across all sessions in the current window.
+    pub window_turns: u32,
+    /// Turns that fit in the window before the most recent rate limit.
+    /// `None` until the provider has been limited once.
+    pub estimated_capacity: Option<u32>,
+    /// `estimated_capacity - window_turns`, or 0 while limited.
+    pub estimated_remaining: Option<u32>,
+    /// Turns per hour over the last day, oldest first.
+    pub hourly_turns: Vec<u32>,
+    /// Sessions active in the last day, most recent first.
+    pub sessions: Vec<SessionUsageEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionUsageEntry {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub agent: String,
+    pub window_turns: u32,
+    pub last_turn_at: Option<DateTime<Utc>>,
+    /// Latest context-window snapshot.
+    pub context_used: Option<u64>,
+    pub context_size: Option<u64>,
+    /// Cumulative session cost, when the agent reports it.
+    pub cost: Option<f64>,
+    pub currency: Option<String>,
+    /// Reset time of a rate limit this session hit that is still
+    /// ahead, unless the session has finished a turn since.
+    pub limited_until: Option<DateTime<Utc>>,
+}
+
+impl ProviderUsage {
+    /// One-line quota status shared by `aoe cockpit usage` and the TUI
+    /// panel, e.g. "limited until 14:05 (in 1h 12m)" or
+    /// "12 turns in 5h, ~8 left (est.)".
+    pub fn headline(&self, window_hours: i64, now: DateTime<Utc>) -> String {
+        if let Some(until) = self.limited_until {
+            return format!(
+                "limited until {} (in {})",
+                format_local_time(until, now),
+                format_span(until - now)
+            );
+        }
+        let used = format!(
+            "{} turn{} in {window_hours}h",
+            self.window_turns,
+            if self.window_turns == 1 { "" } else { "s" }
+        );
+        match self.estimated_remaining {
+            Some(left) => format!("{used}, ~{left} left (est.)"),
+            None => used,
+        }
+    }
+}
+
+/// `14:05` today, `Tue 14:05` within the week, a date beyond that.
+pub fn format_local_time(t: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let local = t.with_timezone(&chrono::Local);
+    let today = now.with_timezone(&chrono::Local).date_naive();
+    if local.date_naive() == today {
+        local.format("%H:%M").to_string()
+    } else if (local.date_naive() - today).num_days().abs() < 7 {
+        local.format("%a %H:%M").to_string()
+    } else {
+        local.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// `1h 12m`, `45m`, `2d 3h`; never negative.
+pub fn format_span(d: Duration) -> String {
+    let minutes = d.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+/// `40k/200k`, the context-window fill of a session.
+pub fn format_context(used: u64, size: u64) -> String {
+    fn short(n: u64) -> String {
+        if n >= 1_000_000 {
+            format!("{:.1}M", n as f64 / 1_000_000.0)
+        } else if n >= 1_000 {
+            format!("{}k", n / 1_000)
+        } else {
+            n.to_string()
+        }
+    }
+    format!("{}/{}", short(used), short(size))
+}
+
+/// Fold `samples` (any order) into the per-provider report. `titles`
+/// maps session ids to display titles.
+pub fn summarize(
+    samples: &[UsageSample],
+    titles: &HashMap<String, String>,
+    now: DateTime<Utc>,
+) -> UsageReport {
+    let mut by_provider: BTreeMap<&str, Vec<&UsageSample>> = BTreeMap::new();
+    for sample in samples {
+        by_provider
+            .entry(sample.provider.as_str())
+            .or_default()
+            .push(sample);
+    }
+    let providers = by_provider
+        .into_iter()
+        .map(|(provider, mut samples)| {
+            samples.sort_by_key(|s| s.recorded_at);
+            summarize_provider(provider, &samples, titles, now)
+        })
+        .collect();
+    UsageReport {
+        generated_at: now,
+        window_hours: QUOTA_WINDOW.num_hours(),
+        providers,
+    }
+}
+
+/// `samples` is sorted oldest first.
+fn summarize_provider(
+    provider: &str,
+    samples: &[&UsageSample],
+    titles: &HashMap<String, String>,
+    now: DateTime<Utc>,
+) -> ProviderUsage {
+    let window_start = now - QUOTA_WINDOW;
+    let turns = |from: DateTime<Utc>, to: DateTime<Utc>| {
+        samples
+            .iter()
+            .filter(|s| s.kind == SampleKind::Turn && s.recorded_at > from && s.recorded_at <= to)
+            .count() as u32
+    };
+
+    let last_limit = samples
+        .iter()
+        .rev()
+        .find(|s| s.kind == SampleKind::RateLimit);
+    let limited_until = last_limit.and_then(|limit| still_limited(limit, samples, now));
+    let window_turns = turns(window_start, now);
+    let estimated_capacity = last_limit
+        .map(|limit| turns(limit.recorded_at - QUOTA_WINDOW, limit.recorded_at))
+        .filter(|&n| n > 0);
+    let estimated_remaining = estimated_capacity.map(|capacity| {
+        if limited_until.is_some() {
+            0
+        } else {
+            capacity.saturating_sub(window_turns)
+        }
+    });
+
+    let history_start = now - Duration::hours(HISTORY_HOURS);
+    let mut hourly_turns = vec![0u32; HISTORY_HOURS as usize];
+    for sample in samples.iter().filter(|s| s.kind == SampleKind::Turn) {
+        if sample.recorded_at <= history_start || sample.recorded_at > now {
+            continue;
+        }
+        let bucket = ((sample.recorded_at - history_start).num_seconds() / 3600) as usize;
+        hourly_turns[bucket.min(HISTORY_HOURS as usize - 1)] += 1;
+    }
+
+    let mut agents: Vec<String> = samples.iter().map(|s| s.agent.clone()).collect();
+    agents.sort();
+    agents.dedup();
+
+    let mut session_ids: Vec<&str> = samples
+        .iter()
+        .filter(|s| s.recorded_at > history_start)
+        .map(|s| s.session_id.as_str())
+        .collect();
+    session_ids.sort();
+    session_ids.dedup();
+    let mut sessions: Vec<SessionUsageEntry> = session_ids
+        .into_iter()
+        .map(|id| {
+            let own: Vec<&UsageSample> = samples
+                .iter()
+                .copied()
+                .filter(|s| s.session_id == id)
+                .collect();
+            summarize_session(id, &own, titles, window_start, now)
+        })
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_turn_at));
+
+    ProviderUsage {
+        provider: provider.to_string(),
+        agents,
+        limited_until,
+        limit_status: limited_until
+            .and(last_limit)
+            .and_then(|limit| limit.status.clone()),
+        last_limit_at: last_limit.map(|limit| limit.recorded_at),
+        window_turns,
+        estimated_capacity,
+        estimated_remaining,
+        hourly_turns,
+        sessions,
+    }
+}
+
+/// `samples` is this session's samples, oldest first.
+fn summarize_session(
+    session_id: &str,
+    samples: &[&UsageSample],
+    titles: &HashMap<String, String>,
+    window_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> SessionUsageEntry {
+    let latest_usage = samples.iter().rev().find(|s| s.kind == SampleKind::Usage);
+    let last_limit = samples
+        .iter()
+        .rev()
+        .find(|s| s.kind == SampleKind::RateLimit);
+    SessionUsageEntry {
+        session_id: session_id.to_string(),
+        title: titles.get(session_id).cloned(),
+        agent: samples.last().map(|s| s.agent.clone()).unwrap_or_default(),
+        window_turns: samples
+            .iter()
+            .filter(|s| s.kind == SampleKind::Turn && s.recorded_at > window_start)
+            .count() as u32,
+        last_turn_at: samples
+            .iter()
+            .rev()
+            .find(|s| s.kind == SampleKind::Turn)
+            .map(|s| s.recorded_at),
+        context_used: latest_usage.and_then(|s| s.context_used),
+        context_size: latest_usage.and_then(|s| s.context_size),
+        cost: latest_usage.and_then(|s| s.cost),
+        currency: latest_usage.and_then(|s| s.currency.clone()),
+        limited_until: last_limit.and_then(|limit| still_limited(limit, samples, now)),
+    }
+}
+
+/// The limit's reset time if it's still ahead and no turn has finished
+/// since it was hit (a finished turn means the quota came back early,
+/// or the agent's reset estimate was pessimistic).
+fn still_limited(
+    limit: &UsageSample,
+    samples: &[&UsageSample],
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let resets_at = limit.resets_at.filter(|&t| t > now)?;
+    let recovered = samples
+        .iter()
+        .any(|s| s.kind == SampleKind::Turn && s.recorded_at > limit.recorded_at);
+    (!recovered).then_some(resets_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cockpit::state::{RateLimitInfo, SessionUsage, UsageCost};
+
+    fn sample(session: &str, minutes_ago: i64, event: &Event, now: DateTime<Utc>) -> UsageSample {
+        UsageSample::from_event(
+            session,
+            "claude",
+            "anthropic",
+            now - Duration::minutes(minutes_ago),
+            event,
+        )
+        .expect("tracked event")
+    }
+
+    fn turn() -> Event {
+        Event::Stopped {
+            reason: "prompt_complete".into(),
+        }
+    }
+
+    fn rate_limit(resets_at: DateTime<Utc>) -> Event {
+        Event::RateLimit {
+            info: RateLimitInfo {
+                status: "usage limit reached".into(),
+                resets_at,
+                kind: "rate_limit".into(),
+            },
+        }
+    }
+
+    #[test]
+    fn only_finished_turns_usage_and_rate_limits_are_sampled() {
+        assert_eq!(SampleKind::for_event(&turn()), Some(SampleKind::Turn));
+        assert_eq!(
+            SampleKind::for_event(&Event::Stopped {
+                reason: "rate_limited".into()
+            }),
+            None
+        );
+        assert_eq!(SampleKind::for_event(&Event::ThinkingStarted), None);
+    }
+
+    #[test]
+    fn provider_comes_from_the_agent_or_the_model_prefix() {
+        assert_eq!(provider_for("claude", None), "anthropic");
+        assert_eq!(provider_for("codex", Some("gpt-5")), "openai");
+        assert_eq!(
+            provider_for("aoe-agent", Some("OpenRouter/qwen3-coder")),
+            "openrouter"
+        );
+        assert_eq!(provider_for("aoe-agent", Some("sonnet")), "aoe-agent");
+        assert_eq!(provider_for("opencode", None), "opencode");
+    }
+
+    #[test]
+    fn summary_tracks_window_turns_and_capacity_across_sessions() {
+        let now = Utc::now();
+        let usage = Event::UsageUpdated {
+            usage: SessionUsage {
+                used: 40_000,
+                size: 200_000,
+                cost: Some(UsageCost {
+                    amount: 1.25,
+                    currency: "USD".into(),
+                }),
+            },
+        };
+        let samples = vec![
+            // Yesterday: 3 turns, then the limit.
+            sample("a", 26 * 60, &turn(), now),
+            sample("b", 25 * 60, &turn(), now),
+            sample("a", 24 * 60 + 30, &turn(), now),
+            sample("b", 24 * 60, &rate_limit(now - Duration::hours(20)), now),
+            // Today: two turns so far, split over two sessions.
+            sample("a", 90, &turn(), now),
+            sample("a", 89, &usage, now),
+            sample("b", 30, &turn(), now),
+        ];
+        let titles = HashMap::from([("a".to_string(), "audit".to_string())]);
+        let report = summarize(&samples, &titles, now);
+
+        assert_eq!(report.providers.len(), 1);
+        let p = &report.providers[0];
+        assert_eq!(p.provider, "anthropic");
+        assert_eq!(p.window_turns, 2);
+        assert_eq!(p.estimated_capacity, Some(3));
+        assert_eq!(p.estimated_remaining, Some(1));
+        assert!(p.limited_until.is_none());
+        assert_eq!(p.hourly_turns.iter().sum::<u32>(), 2);
+
+        assert_eq!(p.sessions[0].session_id, "b");
+        let a = &p.sessions[1];
+        assert_eq!(a.title.as_deref(), Some("audit"));
+        assert_eq!(a.window_turns, 1);
+        assert_eq!(a.context_used, Some(40_000));
+        assert_eq!(a.cost, Some(1.25));
+    }
+
+    #[test]
+    fn headline_prefers_the_limit_then_the_estimate() {
+        let now = Utc::now();
+        let mut p = ProviderUsage {
+            provider: "anthropic".into(),
+            agents: vec!["claude".into()],
+            limited_until: None,
+            limit_status: None,
+            last_limit_at: None,
+            window_turns: 1,
+            estimated_capacity: None,
+            estimated_remaining: None,
+            hourly_turns: Vec::new(),
+            sessions: Vec::new(),
+        };
+        assert_eq!(p.headline(5, now), "1 turn in 5h");
+        p.estimated_remaining = Some(8);
+        assert_eq!(p.headline(5, now), "1 turn in 5h, ~8 left (est.)");
+        p.limited_until = Some(now + Duration::minutes(72) + Duration::seconds(30));
+        assert!(p.headline(5, now).ends_with("(in 1h 12m)"));
+
+        assert_eq!(format_span(Duration::hours(51)), "2d 3h");
+        assert_eq!(format_span(Duration::minutes(-5)), "0m");
+        assert_eq!(format_context(40_500, 1_000_000), "40k/1.0M");
+    }
+
+    #[test]
+    fn limit_clears_on_reset_or_a_later_turn() {
+        let now = Utc::now();
+        let resets_at = now + Duration::hours(1);
+        let limited = vec![
+            sample("a", 20, &turn(), now),
+            sample("b", 10, &rate_limit(resets_at), now),
+        ];
+        let report = summarize(&limited, &HashMap::new(), now);
+        let p = &report.providers[0];
+        assert_eq!(p.limited_until, Some(resets_at));
+        assert_eq!(p.limit_status.as_deref(), Some("usage limit reached"));
+        assert_eq!(p.estimated_remaining, Some(0));
+        assert_eq!(p.sessions[0].session_id, "a");
+        assert!(p.sessions[0].limited_until.is_none());
+        assert_eq!(p.sessions[1].limited_until, Some(resets_at));
+
+        let mut recovered = limited.clone();
+        recovered.push(sample("a", 1, &turn(), now));
+        let report = summarize(&recovered, &HashMap::new(), now);
+        assert!(report.providers[0].limited_until.is_none());
+
+        let report = summarize(&limited, &HashMap::new(), resets_at + Duration::minutes(1));
+        assert!(report.providers[0].limited_until.is_none());
+    }
+}
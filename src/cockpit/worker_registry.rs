@@ -14,6 +14,13 @@
 //! File mode is 0600 because `provider_env_keys` and `socket_path` may
 //! leak metadata about which agents/providers a user runs.
 //!
+//! Sandboxed sessions whose agent speaks ACP over a socket also get a
+//! per-session directory at `<app_dir>/cockpit-workers/agents/<session_id>/`,
+//! bind-mounted into the container at [`CONTAINER_AGENT_SOCKET_DIR`]. The
+//! runner listens on `acp.sock` inside it and the in-container agent
+//! connects there instead of talking through a `docker exec` pipe; see
+//! [`AgentTransport`].
+//!
 //! Layout note: the runner *and* the daemon both write to entries
 //! (runner: `pid`/`started_at` on boot; daemon:
 //! `last_attached_at`/`detached_at` on attach/detach). We accept the
@@ -32,6 +39,39 @@ use tracing::{debug, warn};
 /// a smaller `runner_version` are swept on startup instead of dialed.
 pub const RUNNER_VERSION: u32 = 1;
 
+/// Where the per-session agent socket directory is mounted inside a
+/// sandbox container. Short and fixed so the in-container socket path
+/// stays well under the `sun_path` limit regardless of the host layout.
+pub const CONTAINER_AGENT_SOCKET_DIR: &str = "/run/aoe-acp";
+
+/// File name of the agent socket inside the per-session directory.
+const AGENT_SOCKET_NAME: &str = "acp.sock";
+
+/// How the runner talks to its agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentTransport {
+    /// The runner owns the agent's stdin/stdout: a local child process,
+    /// or `docker exec -i` for sandboxed sessions. The agent dies with
+    /// the pipe, so a container restart ends the worker.
+    #[default]
+    Stdio,
+    /// The runner listens on the session's agent socket and the agent,
+    /// launched detached inside the sandbox container, connects to it.
+    /// When the container restarts the runner relaunches the agent and
+    /// reloads its ACP session, keeping the daemon connection up.
+    Socket,
+}
+
+impl AgentTransport {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stdio => "stdio",
+            Self::Socket => "socket",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkerRecord {
     pub runner_version: u32,
@@ -72,6 +112,18 @@ pub struct WorkerRecord {
     /// back to the default profile, matching pre-persistence behavior.
     #[serde(default)]
     pub source_profile: Option<String>,
+    /// How the runner reaches the agent. Legacy records predate socket
+    /// transport and load as `Stdio`.
+    #[serde(default)]
+    pub agent_transport: AgentTransport,
+    /// Host path of the agent socket for `Socket` transport.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_socket_path: Option<PathBuf>,
+    /// Times the runner relaunched the agent after losing its socket
+    /// connection (container restart, agent crash). Always 0 for
+    /// `Stdio` transport, where losing the agent ends the worker.
+    #[serde(default)]
+    pub agent_relaunches: u32,
     pub started_at: u64,
     pub last_attached_at: Option<u64>,
     pub detached_at: Option<u64>,
@@ -105,6 +157,9 @@ impl WorkerRecord {
             provider_env_keys,
             stored_acp_session_id,
             source_profile,
+            agent_transport: AgentTransport::Stdio,
+            agent_socket_path: None,
+            agent_relaunches: 0,
             started_at: now_secs(),
             last_attached_at: None,
             detached_at: None,
@@ -181,6 +236,42 @@ pub fn socket_path_for(session_id: &str) -> Result<PathBuf> {
     Ok(workers_dir()?.join(format!("{session_id}.sock")))
 }
 
+/// `<workers_dir>/agents/<session_id>/`: the directory bind-mounted into
+/// the session's sandbox container at [`CONTAINER_AGENT_SOCKET_DIR`].
+/// Not created here; the container setup and the runner create it.
+pub fn agent_socket_dir_for(session_id: &str) -> Result<PathBuf> {
+    validate_session_id(session_id)?;
+    Ok(workers_dir()?.join("agents").join(session_id))
+}
+
+/// Host path of the agent socket for `Socket` transport.
+pub fn agent_socket_path_for(session_id: &str) -> Result<PathBuf> {
+    Ok(agent_socket_dir_for(session_id)?.join(AGENT_SOCKET_NAME))
+}
+
+/// The same socket as seen from inside the container.
+pub fn container_agent_socket_path() -> String {
+    format!("{CONTAINER_AGENT_SOCKET_DIR}/{AGENT_SOCKET_NAME}")
+}
+
+/// Remove the session's agent socket directory. Called when the session
+/// is deleted; a running container keeps the directory as a mount
+/// source, so stopping a worker must not remove it.
+pub fn remove_agent_socket_dir(session_id: &str) {
+    let Ok(dir) = agent_socket_dir_for(session_id) else {
+        return;
+    };
+    match std::fs::remove_dir_all(&dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!(
+            target: "cockpit.registry",
+            path = %dir.display(),
+            "failed to remove agent socket dir: {e}"
+        ),
+    }
+}
+
 /// `<workers_dir>/<session_id>.log` is the runner-side stderr drain
 /// consumed by `aoe cockpit logs --session <id>`.
 pub fn log_path_for(session_id: &str) -> Result<PathBuf> {
@@ -301,9 +392,10 @@ pub fn list() -> Result<Vec<WorkerRecord>> {
     Ok(out)
 }
 
-/// Remove the JSON entry and the unix socket file (if present). The
+/// Remove the JSON entry and the unix socket files (if present). The
 /// `.log` file is intentionally left behind so the user can read it
-/// after the worker exits.
+/// after the worker exits, and the agent socket directory stays because
+/// the sandbox container still mounts it.
 pub fn delete(session_id: &str) -> Result<()> {
     if let Ok(p) = record_path(session_id) {
         let _ = std::fs::remove_file(&p);
@@ -311,6 +403,9 @@ pub fn delete(session_id: &str) -> Result<()> {
     if let Ok(p) = socket_path_for(session_id) {
         let _ = std::fs::remove_file(&p);
     }
+    if let Ok(p) = agent_socket_path_for(session_id) {
+        let _ = std::fs::remove_file(&p);
+    }
     Ok(())
 }
 
@@ -382,6 +477,21 @@ pub fn update_stored_acp_session_id(session_id: &str, acp_id: Option<&str>) {
     }
 }
 
+/// Bump `agent_relaunches` in place after the runner reconnected a
+/// socket-transport agent. Best-effort, like `mark_attached`.
+pub fn note_agent_relaunch(session_id: &str) {
+    if let Ok(Some(mut rec)) = load(session_id) {
+        rec.agent_relaunches += 1;
+        if let Err(e) = save(&rec) {
+            debug!(
+                target: "cockpit.registry",
+                session = %session_id,
+                "failed to update agent_relaunches: {e}"
+            );
+        }
+    }
+}
+
 /// Probe the recorded socket path. A worker registry entry is "live"
 /// only if both the PID is alive AND the socket file still exists; a
 /// stale entry where the runner died before deleting its files would
@@ -502,6 +612,8 @@ mod tests {
             let loaded = load("legacy-1").unwrap().unwrap();
             assert_eq!(loaded.agent_name, "claude-agent-acp");
             assert_eq!(loaded.agent_key, "");
+            assert_eq!(loaded.agent_transport, AgentTransport::Stdio);
+            assert_eq!(loaded.agent_relaunches, 0);
         });
     }
 
@@ -620,6 +732,46 @@ mod tests {
         });
     }
 
+    /// The agent socket goes with the worker, but its directory is a
+    /// container mount source and must survive until the session is
+    /// deleted.
+    #[test]
+    #[serial]
+    fn delete_keeps_agent_socket_dir_until_session_removal() {
+        with_temp_home(|| {
+            let agent_sock = agent_socket_path_for("sess-sock").unwrap();
+            std::fs::create_dir_all(agent_sock.parent().unwrap()).unwrap();
+            std::fs::write(&agent_sock, b"").unwrap();
+            let mut rec = WorkerRecord::new(
+                "sess-sock".into(),
+                1,
+                socket_path_for("sess-sock").unwrap(),
+                "aoe-agent".into(),
+                "aoe-agent".into(),
+                PathBuf::from("/repo"),
+                None,
+                vec![],
+                vec![],
+                None,
+                None,
+            );
+            rec.agent_transport = AgentTransport::Socket;
+            rec.agent_socket_path = Some(agent_sock.clone());
+            save(&rec).unwrap();
+            note_agent_relaunch("sess-sock");
+            let loaded = load("sess-sock").unwrap().unwrap();
+            assert_eq!(loaded.agent_transport, AgentTransport::Socket);
+            assert_eq!(loaded.agent_relaunches, 1);
+
+            delete("sess-sock").unwrap();
+            assert!(!agent_sock.exists());
+            let dir = agent_socket_dir_for("sess-sock").unwrap();
+            assert!(dir.exists());
+            remove_agent_socket_dir("sess-sock");
+            assert!(!dir.exists());
+        });
+    }
+
     #[test]
     #[serial]
     fn mark_attached_clears_detached() {
@@ -9,6 +9,8 @@ use std::path::Path;
 use std::process::{Command, Output};
 use std::time::Instant;
 
+use super::error::{GitError, Result};
+
 /// Run `git <args>` in `cwd`, instrumented with target `git.command`.
 /// Logs a debug line before, then debug (success) or warn (failure)
 /// after with exit code, duration, and a sanitized stderr summary.
@@ -54,6 +56,33 @@ where
     Ok(output)
 }
 
+/// [`run_git`], returning stdout and mapping a non-zero exit to `err`
+/// with git's message (see [`stderr_message`]).
+pub fn git_ok<I, S>(cwd: &Path, args: I, err: fn(String) -> GitError) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = run_git(cwd, args)?;
+    if !output.status.success() {
+        return Err(err(stderr_message(&output)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// git's explanation of a failed command: stderr, or stdout when stderr is
+/// empty (`commit` reports "nothing to commit" there).
+pub fn stderr_message(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let text = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    text.to_string()
+}
+
 fn redact(arg: &OsStr) -> String {
     let s = arg.to_string_lossy();
     if let Some(scheme_end) = s.find("://") {
@@ -50,6 +50,38 @@ impl FileStatus {
     }
 }
 
+/// Which pair of snapshots a diff compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffMode {
+    /// Merge-base of HEAD and the base branch vs the working directory
+    #[default]
+    Base,
+    /// Index vs working directory (what `git add` would stage)
+    Unstaged,
+    /// HEAD vs index (what the next commit contains)
+    Staged,
+}
+
+impl DiffMode {
+    /// Cycle base -> unstaged -> staged -> base
+    pub fn next(self) -> Self {
+        match self {
+            DiffMode::Base => DiffMode::Unstaged,
+            DiffMode::Unstaged => DiffMode::Staged,
+            DiffMode::Staged => DiffMode::Base,
+        }
+    }
+
+    /// Returns a human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffMode::Base => "base",
+            DiffMode::Unstaged => "unstaged",
+            DiffMode::Staged => "staged",
+        }
+    }
+}
+
 /// Represents a file that has changed
 #[derive(Debug, Clone)]
 pub struct DiffFile {
@@ -102,23 +134,46 @@ pub struct FileDiff {
     pub hunks: Vec<DiffHunk>,
     /// Whether this is a binary file
     pub is_binary: bool,
+    /// Git mode of the old side (`0o100644`, `0o100755`, `0o120000`),
+    /// 0 when the file doesn't exist there
+    pub old_mode: u32,
+    /// Git mode of the new side, 0 when the file doesn't exist there
+    pub new_mode: u32,
 }
 
 /// Compute the list of changed files between a base branch and the working directory.
 /// Uses the merge-base of HEAD and the base branch, so only changes introduced
 /// on the current branch are shown (matching GitHub PR diff behavior).
 pub fn compute_changed_files(repo_path: &Path, base_branch: &str) -> Result<Vec<DiffFile>> {
-    let repo = super::open_repo_at(repo_path)?;
+    compute_changed_files_in(repo_path, base_branch, DiffMode::Base)
+}
 
-    let base_tree = get_merge_base_tree(&repo, base_branch)?;
+/// Compute the list of changed files for the given diff mode. `base_branch`
+/// only matters for `DiffMode::Base`.
+pub fn compute_changed_files_in(
+    repo_path: &Path,
+    base_branch: &str,
+    mode: DiffMode,
+) -> Result<Vec<DiffFile>> {
+    let repo = super::open_repo_at(repo_path)?;
 
     // Create diff options
     let mut opts = git2::DiffOptions::new();
     opts.include_untracked(true);
     opts.recurse_untracked_dirs(true);
 
-    // Get diff from base tree to working directory (includes index)
-    let diff = repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))?;
+    let diff = match mode {
+        DiffMode::Base => {
+            // Get diff from base tree to working directory (includes index)
+            let base_tree = get_merge_base_tree(&repo, base_branch)?;
+            repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))?
+        }
+        DiffMode::Unstaged => repo.diff_index_to_workdir(None, Some(&mut opts))?,
+        DiffMode::Staged => {
+            let head_tree = get_head_tree(&repo);
+            repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts))?
+        }
+    };
 
     // Find renames/copies
     let mut find_opts = git2::DiffFindOptions::new();
@@ -181,7 +236,7 @@ pub fn compute_changed_files(repo_path: &Path, base_branch: &str) -> Result<Vec<
 
     // Append conflicted files from the index (not visible via diff_tree_to_workdir_with_index)
     let index = repo.index()?;
-    if index.has_conflicts() {
+    if mode != DiffMode::Staged && index.has_conflicts() {
         for conflict in index.conflicts()? {
             let conflict = conflict?;
             let path = conflict
@@ -259,6 +314,49 @@ fn get_merge_base_tree<'a>(repo: &'a git2::Repository, reference: &str) -> Resul
     Ok(base_commit.tree()?)
 }
 
+/// HEAD's tree, or None on an unborn branch
+fn get_head_tree(repo: &git2::Repository) -> Option<git2::Tree<'_>> {
+    repo.head().ok()?.peel_to_tree().ok()
+}
+
+/// Raw bytes of the stage-0 index entry for a path
+fn get_index_bytes(repo: &git2::Repository, index: &git2::Index, path: &Path) -> Option<Vec<u8>> {
+    let entry = index.get_path(path, 0)?;
+    let blob = repo.find_blob(entry.id).ok()?;
+    Some(blob.content().to_vec())
+}
+
+/// Git mode of a tree entry, 0 when the path isn't in the tree
+fn tree_mode(tree: &git2::Tree, path: &Path) -> u32 {
+    tree.get_path(path)
+        .map(|entry| entry.filemode() as u32)
+        .unwrap_or(0)
+}
+
+/// Git mode of the stage-0 index entry, 0 when the path isn't staged
+fn index_mode(index: &git2::Index, path: &Path) -> u32 {
+    index.get_path(path, 0).map(|entry| entry.mode).unwrap_or(0)
+}
+
+/// Git mode a file in the working tree would be staged with, 0 when it
+/// doesn't exist
+fn workdir_mode(full_path: &Path) -> u32 {
+    let Ok(meta) = std::fs::symlink_metadata(full_path) else {
+        return 0;
+    };
+    if meta.file_type().is_symlink() {
+        return 0o120000;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if meta.permissions().mode() & 0o111 != 0 {
+            return 0o100755;
+        }
+    }
+    0o100644
+}
+
 /// Check whether the merge-base between HEAD and the given base branch can
 /// be computed. Returns `Some(warning)` if the diff will fall back to
 /// comparing against the branch tip directly (which includes unrelated
@@ -323,26 +421,123 @@ pub fn compute_file_diff(
     file_path: &Path,
     base_branch: &str,
     context_lines: usize,
+) -> Result<FileDiff> {
+    compute_file_diff_in(
+        repo_path,
+        file_path,
+        base_branch,
+        context_lines,
+        DiffMode::Base,
+    )
+}
+
+/// Compute the full diff for a specific file in the given diff mode.
+/// `base_branch` only matters for `DiffMode::Base`.
+pub fn compute_file_diff_in(
+    repo_path: &Path,
+    file_path: &Path,
+    base_branch: &str,
+    context_lines: usize,
+    mode: DiffMode,
+) -> Result<FileDiff> {
+    diff_paths(
+        repo_path,
+        file_path,
+        file_path,
+        base_branch,
+        context_lines,
+        mode,
+    )
+}
+
+/// Like [`compute_file_diff_in`], but for an entry from
+/// [`compute_changed_files_in`]: a renamed file reads its old side from
+/// `old_path`, so the diff holds only the edits made alongside the rename.
+pub fn compute_file_diff_of(
+    repo_path: &Path,
+    file: &DiffFile,
+    base_branch: &str,
+    context_lines: usize,
+    mode: DiffMode,
+) -> Result<FileDiff> {
+    let old_path = match (&file.status, &file.old_path) {
+        (FileStatus::Renamed, Some(old_path)) => old_path.as_path(),
+        _ => file.path.as_path(),
+    };
+    diff_paths(
+        repo_path,
+        old_path,
+        &file.path,
+        base_branch,
+        context_lines,
+        mode,
+    )
+}
+
+fn diff_paths(
+    repo_path: &Path,
+    old_path: &Path,
+    file_path: &Path,
+    base_branch: &str,
+    context_lines: usize,
+    mode: DiffMode,
 ) -> Result<FileDiff> {
     let repo = super::open_repo_at(repo_path)?;
     let workdir = repo.workdir().ok_or(GitError::NotAGitRepo)?;
+    let index = repo.index()?;
+    let full_path = workdir.join(file_path);
 
-    let base_tree = get_merge_base_tree(&repo, base_branch)?;
+    // Get old and new content as bytes first to check for binary
+    let read_workdir = || {
+        if full_path.exists() {
+            std::fs::read(&full_path).ok()
+        } else {
+            None
+        }
+    };
+    let ((old_bytes, old_mode), (new_bytes, new_mode)) = match mode {
+        DiffMode::Base => {
+            let base_tree = get_merge_base_tree(&repo, base_branch)?;
+            (
+                (
+                    get_blob_bytes(&repo, &base_tree, old_path),
+                    tree_mode(&base_tree, old_path),
+                ),
+                (read_workdir(), workdir_mode(&full_path)),
+            )
+        }
+        DiffMode::Unstaged => (
+            (
+                get_index_bytes(&repo, &index, old_path),
+                index_mode(&index, old_path),
+            ),
+            (read_workdir(), workdir_mode(&full_path)),
+        ),
+        DiffMode::Staged => {
+            let head_tree = get_head_tree(&repo);
+            (
+                (
+                    head_tree
+                        .as_ref()
+                        .and_then(|tree| get_blob_bytes(&repo, tree, old_path)),
+                    head_tree
+                        .as_ref()
+                        .map(|tree| tree_mode(tree, old_path))
+                        .unwrap_or(0),
+                ),
+                (
+                    get_index_bytes(&repo, &index, file_path),
+                    index_mode(&index, file_path),
+                ),
+            )
+        }
+    };
+    let new_exists = new_bytes.is_some();
 
-    // Get old content from base tree (as bytes first to check for binary)
-    let old_bytes = get_blob_bytes(&repo, &base_tree, file_path);
     let old_is_binary = old_bytes
         .as_ref()
         .map(|b| is_binary_bytes(b))
         .unwrap_or(false);
-
-    // Get new content from working directory (as bytes first to check for binary)
-    let full_path = workdir.join(file_path);
-    let new_bytes = if full_path.exists() {
-        std::fs::read(&full_path).ok()
-    } else {
-        None
-    };
     let new_is_binary = new_bytes
         .as_ref()
         .map(|b| is_binary_bytes(b))
@@ -359,7 +554,6 @@ pub fn compute_file_diff(
         .unwrap_or_default();
 
     // Determine file status
-    let index = repo.index()?;
     let is_conflicted = index.has_conflicts()
         && index.conflicts()?.any(|c| {
             c.ok()
@@ -374,25 +568,31 @@ pub fn compute_file_diff(
 
     let status = if is_conflicted {
         FileStatus::Conflicted
+    } else if old_path != file_path {
+        FileStatus::Renamed
     } else if old_content.is_empty() && !new_content.is_empty() {
         FileStatus::Added
-    } else if !old_content.is_empty() && new_content.is_empty() && !full_path.exists() {
+    } else if !old_content.is_empty() && new_content.is_empty() && !new_exists {
         FileStatus::Deleted
     } else {
         FileStatus::Modified
     };
 
+    let renamed_from = (status == FileStatus::Renamed).then(|| old_path.to_path_buf());
+
     if is_binary {
         return Ok(FileDiff {
             file: DiffFile {
                 path: file_path.to_path_buf(),
-                old_path: None,
+                old_path: renamed_from,
                 status,
                 additions: 0,
                 deletions: 0,
             },
             hunks: Vec::new(),
             is_binary: true,
+            old_mode,
+            new_mode,
         });
     }
 
@@ -461,16 +661,136 @@ pub fn compute_file_diff(
     Ok(FileDiff {
         file: DiffFile {
             path: file_path.to_path_buf(),
-            old_path: None,
+            old_path: renamed_from,
             status,
             additions,
             deletions,
         },
         hunks,
         is_binary: false,
+        old_mode,
+        new_mode,
     })
 }
 
+impl FileDiff {
+    /// Render as a `git diff`-style unified patch (`---`/`+++` headers plus
+    /// `@@` hunks). Binary files collapse to a single "Binary files differ"
+    /// line. Used where the diff leaves aoe as plain text (MCP `get_diff`).
+    pub fn to_unified(&self) -> String {
+        let (mut out, old_header, new_header) = self.patch_header();
+        if self.is_binary {
+            out.push_str(&format!(
+                "Binary files {old_header} and {new_header} differ\n"
+            ));
+            return out;
+        }
+        out.push_str(&format!("--- {old_header}\n+++ {new_header}\n"));
+        for hunk in &self.hunks {
+            push_hunk(&mut out, hunk, hunk.old_start, hunk.new_start);
+        }
+        out
+    }
+
+    /// A patch containing only hunk `idx`, for `git apply`. Zero-length
+    /// ranges use git's convention of pointing at the line before the
+    /// change (`-0,0` for a new file), which `git apply` insists on.
+    /// None for binary files or an out-of-range index.
+    pub fn hunk_patch(&self, idx: usize) -> Option<String> {
+        if self.is_binary {
+            return None;
+        }
+        let hunk = self.hunks.get(idx)?;
+        let (mut out, old_header, new_header) = self.patch_header();
+        // `git apply` reads a `/dev/null` side as a path unless the
+        // extended header says the file is created or deleted, and
+        // applies a renamed file's hunk to its new path only when told
+        // where it came from.
+        let mode_or_regular = |mode: u32| if mode == 0 { 0o100644 } else { mode };
+        match self.file.status {
+            FileStatus::Added => out.push_str(&format!(
+                "new file mode {:o}\n",
+                mode_or_regular(self.new_mode)
+            )),
+            FileStatus::Deleted => out.push_str(&format!(
+                "deleted file mode {:o}\n",
+                mode_or_regular(self.old_mode)
+            )),
+            FileStatus::Renamed => {
+                if let Some(old_path) = &self.file.old_path {
+                    out.push_str(&format!(
+                        "rename from {}\nrename to {}\n",
+                        old_path.to_string_lossy(),
+                        self.file.path.to_string_lossy()
+                    ));
+                }
+            }
+            _ => {}
+        }
+        out.push_str(&format!("--- {old_header}\n+++ {new_header}\n"));
+        let git_start = |start: usize, lines: usize| {
+            if lines == 0 {
+                start.saturating_sub(1)
+            } else {
+                start
+            }
+        };
+        push_hunk(
+            &mut out,
+            hunk,
+            git_start(hunk.old_start, hunk.old_lines),
+            git_start(hunk.new_start, hunk.new_lines),
+        );
+        Some(out)
+    }
+
+    /// The `diff --git` line plus the old/new header paths.
+    fn patch_header(&self) -> (String, String, String) {
+        let path = self.file.path.to_string_lossy();
+        let old_path = self
+            .file
+            .old_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string());
+        let old_header = if self.file.status == FileStatus::Added {
+            "/dev/null".to_string()
+        } else {
+            format!("a/{old_path}")
+        };
+        let new_header = if self.file.status == FileStatus::Deleted {
+            "/dev/null".to_string()
+        } else {
+            format!("b/{path}")
+        };
+        (
+            format!("diff --git a/{old_path} b/{path}\n"),
+            old_header,
+            new_header,
+        )
+    }
+}
+
+/// Append one `@@` hunk to a unified patch
+fn push_hunk(out: &mut String, hunk: &DiffHunk, old_start: usize, new_start: usize) {
+    out.push_str(&format!(
+        "@@ -{},{} +{},{} @@\n",
+        old_start, hunk.old_lines, new_start, hunk.new_lines
+    ));
+    for line in &hunk.lines {
+        let sign = match line.tag {
+            ChangeTag::Delete => '-',
+            ChangeTag::Insert => '+',
+            ChangeTag::Equal => ' ',
+        };
+        out.push(sign);
+        out.push_str(&line.content);
+        if !line.content.ends_with('\n') {
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
+
 /// Get raw bytes of a blob from a tree by path
 fn get_blob_bytes(repo: &git2::Repository, tree: &git2::Tree, path: &Path) -> Option<Vec<u8>> {
     let entry = tree.get_path(path).ok()?;
@@ -619,6 +939,23 @@ pub fn get_default_base_ref(repo_path: &Path) -> Result<String> {
     Ok(git_wt.detect_default_branch_info()?.qualified_ref())
 }
 
+/// Resolve the diff base for one repo path. Override (per-session)
+/// wins over the profile's `DiffConfig.default_branch`, which wins
+/// over auto-detection (`get_default_base_ref`). See #970.
+pub fn resolve_diff_base(
+    override_value: Option<&str>,
+    config_default: Option<&str>,
+    repo_path: &Path,
+) -> String {
+    if let Some(v) = override_value.map(str::trim).filter(|v| !v.is_empty()) {
+        return v.to_string();
+    }
+    if let Some(v) = config_default.map(str::trim).filter(|v| !v.is_empty()) {
+        return v.to_string();
+    }
+    get_default_base_ref(repo_path).unwrap_or_else(|_| "main".to_string())
+}
+
 /// Returns `Ok(())` when `reference` resolves to a commit in the repo at
 /// `repo_path` using the same resolution chain (`local branch`,
 /// `origin/<ref>` tracking branch, `revparse_single`) that
@@ -738,6 +1075,29 @@ mod tests {
         (dir, repo)
     }
 
+    #[test]
+    fn resolve_diff_base_prefers_override_then_config_then_auto() {
+        let tmp = tempfile::tempdir().unwrap();
+        // Override wins over everything.
+        assert_eq!(
+            resolve_diff_base(Some("release-1.2"), Some("develop"), tmp.path()),
+            "release-1.2"
+        );
+        // Config wins when no override; empty / whitespace override falls
+        // through to the next layer.
+        assert_eq!(
+            resolve_diff_base(Some("   "), Some("develop"), tmp.path()),
+            "develop"
+        );
+        assert_eq!(
+            resolve_diff_base(None, Some("develop"), tmp.path()),
+            "develop"
+        );
+        // Auto-detect when neither is set. The tmp dir is not a repo so
+        // `get_default_base_ref` returns Err -> "main" fallback.
+        assert_eq!(resolve_diff_base(None, None, tmp.path()), "main");
+    }
+
     #[test]
     fn test_merge_base_excludes_main_only_changes() {
         let (dir, _repo) = setup_branching_repo();
@@ -973,6 +1333,106 @@ mod tests {
         assert!(diff.file.additions > 0);
     }
 
+    #[test]
+    fn test_file_diff_to_unified() {
+        let (dir, _repo) = setup_test_repo();
+
+        let file_path = dir.path().join("test.txt");
+        fs::write(&file_path, "line 1 modified\nline 2\nline 3\n").unwrap();
+
+        let diff = compute_file_diff(dir.path(), Path::new("test.txt"), "HEAD", 3).unwrap();
+        assert_eq!(
+            diff.to_unified(),
+            "diff --git a/test.txt b/test.txt\n\
+             --- a/test.txt\n\
+             +++ b/test.txt\n\
+             @@ -1,3 +1,3 @@\n\
+             -line 1\n\
+             +line 1 modified\n \
+             line 2\n \
+             line 3\n"
+        );
+    }
+
+    #[test]
+    fn test_hunk_patch_uses_git_ranges_for_new_file() {
+        let (dir, _repo) = setup_test_repo();
+        fs::write(dir.path().join("new.txt"), "a\nb\n").unwrap();
+
+        let diff = compute_file_diff_in(
+            dir.path(),
+            Path::new("new.txt"),
+            "HEAD",
+            3,
+            DiffMode::Unstaged,
+        )
+        .unwrap();
+        assert_eq!(
+            diff.hunk_patch(0).unwrap(),
+            "diff --git a/new.txt b/new.txt\n\
+             new file mode 100644\n\
+             --- /dev/null\n\
+             +++ b/new.txt\n\
+             @@ -0,0 +1,2 @@\n\
+             +a\n\
+             +b\n"
+        );
+        assert!(diff.hunk_patch(1).is_none());
+    }
+
+    #[test]
+    fn test_staged_and_unstaged_modes_split_the_change() {
+        let (dir, repo) = setup_test_repo();
+        fs::write(
+            dir.path().join("test.txt"),
+            "line 1\nline 2 staged\nline 3\n",
+        )
+        .unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("test.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(dir.path().join("test.txt"), "line 1\nline 2 both\nline 3\n").unwrap();
+
+        let staged = compute_file_diff_in(
+            dir.path(),
+            Path::new("test.txt"),
+            "HEAD",
+            3,
+            DiffMode::Staged,
+        )
+        .unwrap();
+        let staged_lines: Vec<&str> = staged.hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.tag == ChangeTag::Insert)
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(staged_lines, vec!["line 2 staged\n"]);
+
+        let unstaged = compute_file_diff_in(
+            dir.path(),
+            Path::new("test.txt"),
+            "HEAD",
+            3,
+            DiffMode::Unstaged,
+        )
+        .unwrap();
+        let removed: Vec<&str> = unstaged.hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.tag == ChangeTag::Delete)
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(removed, vec!["line 2 staged\n"]);
+
+        assert_eq!(
+            compute_changed_files_in(dir.path(), "HEAD", DiffMode::Staged)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
     #[test]
     fn test_list_branches() {
         let (dir, repo) = setup_test_repo();
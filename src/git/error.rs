@@ -27,6 +27,18 @@ pub enum GitError {
     #[error("Git clone failed: {0}")]
     CloneFailed(String),
 
+    #[error("Git commit failed: {0}")]
+    CommitFailed(String),
+
+    #[error("Git merge failed: {0}")]
+    MergeFailed(String),
+
+    #[error("Git apply failed: {0}")]
+    ApplyFailed(String),
+
+    #[error("Git staging failed: {0}")]
+    StageFailed(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
@@ -0,0 +1,175 @@
+//! Folding a sub-session's work back into its parent.
+//!
+//! Coordinator sessions (see `crate::session::coordinator`) hand tasks to
+//! child sessions running in their own worktrees. Agents often leave
+//! their work uncommitted, so [`commit_pending`] snapshots the child's
+//! worktree first and [`merge_branch`] then merges the child's branch
+//! into whatever the parent has checked out. Both shell out to `git` so
+//! the user's hooks, identity, and merge configuration apply.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::command::{git_ok, run_git, stderr_message};
+use super::error::{GitError, Result};
+
+/// Result of [`merge_branch`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum MergeOutcome {
+    /// A merge commit was created.
+    Merged { commit: String },
+    /// The branch had nothing the target didn't already contain.
+    UpToDate,
+    /// The merge stopped on conflicts and was aborted; the target is
+    /// back where it started.
+    Conflicted { files: Vec<String> },
+}
+
+/// Stage and commit every pending change in `worktree` (tracked and
+/// untracked, respecting `.gitignore`). Returns the new commit id, or
+/// `None` when the worktree was already clean.
+pub fn commit_pending(worktree: &Path, message: &str) -> Result<Option<String>> {
+    let status = git_ok(worktree, ["status", "--porcelain"], GitError::CommitFailed)?;
+    if status.trim().is_empty() {
+        return Ok(None);
+    }
+    git_ok(worktree, ["add", "-A"], GitError::CommitFailed)?;
+    git_ok(worktree, ["commit", "-m", message], GitError::CommitFailed)?;
+    head(worktree).map(Some)
+}
+
+/// Merge `branch` into the branch checked out at `target` with a merge
+/// commit. Conflicts abort the merge and are reported, not returned as
+/// errors; any other failure (a dirty target the merge would overwrite,
+/// an unknown branch) is an error with git's message.
+pub fn merge_branch(target: &Path, branch: &str, message: &str) -> Result<MergeOutcome> {
+    let before = head(target)?;
+    let output = run_git(target, ["merge", "--no-ff", "-m", message, branch])?;
+    if output.status.success() {
+        let after = head(target)?;
+        return Ok(if after == before {
+            MergeOutcome::UpToDate
+        } else {
+            MergeOutcome::Merged { commit: after }
+        });
+    }
+
+    let conflicted = git_ok(
+        target,
+        ["diff", "--name-only", "--diff-filter=U"],
+        GitError::MergeFailed,
+    )
+    .unwrap_or_default();
+    let files: Vec<String> = conflicted
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    if files.is_empty() {
+        return Err(GitError::MergeFailed(stderr_message(&output)));
+    }
+    git_ok(target, ["merge", "--abort"], GitError::MergeFailed)?;
+    Ok(MergeOutcome::Conflicted { files })
+}
+
+fn head(repo: &Path) -> Result<String> {
+    Ok(git_ok(repo, ["rev-parse", "HEAD"], GitError::MergeFailed)?
+        .trim()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = run_git(dir, args).unwrap();
+        assert!(
+            out.status.success(),
+            "git {args:?}: {}",
+            stderr_message(&out)
+        );
+    }
+
+    /// Repo on `main` with one commit and a `child` branch checked out
+    /// in a second worktree.
+    fn setup() -> (TempDir, std::path::PathBuf, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let main = dir.path().join("main");
+        std::fs::create_dir(&main).unwrap();
+        git(&main, &["init", "-q", "-b", "main"]);
+        git(&main, &["config", "user.name", "Test"]);
+        git(&main, &["config", "user.email", "test@example.com"]);
+        std::fs::write(main.join("a.txt"), "one\n").unwrap();
+        git(&main, &["add", "-A"]);
+        git(&main, &["commit", "-q", "-m", "init"]);
+        let child = dir.path().join("child");
+        git(
+            &main,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "child",
+                child.to_str().unwrap(),
+            ],
+        );
+        (dir, main, child)
+    }
+
+    #[test]
+    fn uncommitted_child_work_is_committed_and_merged() {
+        let (_dir, main, child) = setup();
+        assert_eq!(commit_pending(&child, "nothing").unwrap(), None);
+
+        std::fs::write(child.join("b.txt"), "two\n").unwrap();
+        assert!(commit_pending(&child, "child work").unwrap().is_some());
+
+        let outcome = merge_branch(&main, "child", "Merge child").unwrap();
+        assert!(
+            matches!(outcome, MergeOutcome::Merged { .. }),
+            "{outcome:?}"
+        );
+        assert!(main.join("b.txt").exists());
+
+        assert_eq!(
+            merge_branch(&main, "child", "Merge child").unwrap(),
+            MergeOutcome::UpToDate
+        );
+    }
+
+    #[test]
+    fn conflicts_are_reported_and_aborted() {
+        let (_dir, main, child) = setup();
+        std::fs::write(child.join("a.txt"), "child\n").unwrap();
+        commit_pending(&child, "child edit").unwrap();
+        std::fs::write(main.join("a.txt"), "parent\n").unwrap();
+        commit_pending(&main, "parent edit").unwrap();
+
+        let outcome = merge_branch(&main, "child", "Merge child").unwrap();
+        assert_eq!(
+            outcome,
+            MergeOutcome::Conflicted {
+                files: vec!["a.txt".to_string()]
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(main.join("a.txt")).unwrap(),
+            "parent\n"
+        );
+    }
+
+    #[test]
+    fn unknown_branch_is_an_error() {
+        let (_dir, main, _child) = setup();
+        assert!(matches!(
+            merge_branch(&main, "nope", "Merge nope"),
+            Err(GitError::MergeFailed(_))
+        ));
+    }
+}
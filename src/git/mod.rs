@@ -5,6 +5,8 @@
 //!   - `worktree` — `GitWorktree` lifecycle, branch ops, template paths
 //!   - `diff`     — diff rendering for the UI
 //!   - `cleanup`  — stale-worktree cleanup
+//!   - `merge`    — folding a sub-session's branch back into its parent
+//!   - `stage`    — hunk/file staging and commits for the diff view
 //!   - `template` — path-template expansion
 //!   - this file  — module declarations, re-exports, and the shared
 //!     `open_repo_at` helper used by sibling submodules.
@@ -19,7 +21,9 @@ pub mod cleanup;
 pub(crate) mod command;
 pub mod diff;
 pub mod error;
+pub mod merge;
 mod remote;
+pub mod stage;
 pub mod template;
 mod worktree;
 
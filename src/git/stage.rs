@@ -0,0 +1,324 @@
+//! Index and commit operations behind the diff view's staging keys.
+//!
+//! Hunks travel as single-hunk patches built by `FileDiff::hunk_patch` and
+//! are applied with `git apply`, so the index and working tree end up
+//! exactly where the `git` CLI would put them (filters, hooks, and
+//! `core.autocrlf` included).
+
+use std::io::Write;
+use std::path::Path;
+
+use super::command::git_ok;
+use super::error::{GitError, Result};
+
+/// Where `apply_hunk` writes the patch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HunkAction {
+    /// Apply an unstaged hunk to the index
+    Stage,
+    /// Reverse a staged hunk out of the index
+    Unstage,
+    /// Reverse an unstaged hunk out of the working tree
+    Revert,
+}
+
+/// Apply a single-hunk patch for `action`.
+pub fn apply_hunk(repo_path: &Path, patch: &str, action: HunkAction) -> Result<()> {
+    let mut file = tempfile::NamedTempFile::new()?;
+    file.write_all(patch.as_bytes())?;
+    file.flush()?;
+
+    let mut args = vec!["apply", "--whitespace=nowarn"];
+    match action {
+        HunkAction::Stage => args.push("--cached"),
+        HunkAction::Unstage => args.extend(["--cached", "--reverse"]),
+        HunkAction::Revert => args.push("--reverse"),
+    }
+    let patch_path = file.path().to_string_lossy().to_string();
+    args.push(&patch_path);
+
+    git_ok(repo_path, &args, GitError::ApplyFailed)?;
+    Ok(())
+}
+
+/// Stage every change to `file_path`, deletions included.
+pub fn stage_file(repo_path: &Path, file_path: &Path) -> Result<()> {
+    git_ok(
+        repo_path,
+        [
+            "add".as_ref(),
+            "-A".as_ref(),
+            "--".as_ref(),
+            file_path.as_os_str(),
+        ],
+        GitError::StageFailed,
+    )?;
+    Ok(())
+}
+
+/// Reset the index entry for `file_path` to HEAD, keeping the working
+/// tree. On an unborn branch there is no HEAD, so the entry is dropped.
+pub fn unstage_file(repo_path: &Path, file_path: &Path) -> Result<()> {
+    let repo = super::open_repo_at(repo_path)?;
+    let unborn = repo.head().is_err();
+    let args: Vec<&std::ffi::OsStr> = if unborn {
+        vec![
+            "rm".as_ref(),
+            "--cached".as_ref(),
+            "-r".as_ref(),
+            "-q".as_ref(),
+            "--".as_ref(),
+            file_path.as_os_str(),
+        ]
+    } else {
+        vec![
+            "reset".as_ref(),
+            "-q".as_ref(),
+            "HEAD".as_ref(),
+            "--".as_ref(),
+            file_path.as_os_str(),
+        ]
+    };
+    git_ok(repo_path, args, GitError::StageFailed)?;
+    Ok(())
+}
+
+/// Commit the index with `message`, running the repo's hooks. Returns the
+/// new commit's short hash.
+pub fn commit(repo_path: &Path, message: &str) -> Result<String> {
+    git_ok(
+        repo_path,
+        ["commit", "-q", "-m", message],
+        GitError::CommitFailed,
+    )?;
+    let hash = git_ok(
+        repo_path,
+        ["rev-parse", "--short", "HEAD"],
+        GitError::CommitFailed,
+    )?;
+    Ok(hash.trim().to_string())
+}
+
+/// The staged changes as a unified patch (`git diff --cached`).
+pub fn staged_patch(repo_path: &Path) -> Result<String> {
+    git_ok(
+        repo_path,
+        ["diff", "--cached", "--no-color"],
+        GitError::StageFailed,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{
+        compute_changed_files_in, compute_file_diff_in, compute_file_diff_of, DiffMode, FileStatus,
+    };
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {args:?} failed");
+    }
+
+    /// Repo with one committed 20-line file, so edits at the top and the
+    /// bottom land in separate hunks.
+    fn setup_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "user.name", "Test"]);
+        git(dir.path(), &["config", "commit.gpgsign", "false"]);
+        let body: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        fs::write(dir.path().join("file.txt"), body).unwrap();
+        git(dir.path(), &["add", "file.txt"]);
+        git(dir.path(), &["commit", "-q", "-m", "initial"]);
+        dir
+    }
+
+    fn edit_top_and_bottom(dir: &Path) {
+        let body: String = (1..=20)
+            .map(|i| match i {
+                1 => "line one\n".to_string(),
+                20 => "line twenty\n".to_string(),
+                _ => format!("line {i}\n"),
+            })
+            .collect();
+        fs::write(dir.join("file.txt"), body).unwrap();
+    }
+
+    fn hunk_count(dir: &Path, mode: DiffMode) -> usize {
+        compute_file_diff_in(dir, Path::new("file.txt"), "HEAD", 3, mode)
+            .unwrap()
+            .hunks
+            .len()
+    }
+
+    #[test]
+    fn stage_and_unstage_a_single_hunk() {
+        let dir = setup_repo();
+        edit_top_and_bottom(dir.path());
+        let diff = compute_file_diff_in(
+            dir.path(),
+            Path::new("file.txt"),
+            "HEAD",
+            3,
+            DiffMode::Unstaged,
+        )
+        .unwrap();
+        assert_eq!(diff.hunks.len(), 2);
+
+        apply_hunk(dir.path(), &diff.hunk_patch(0).unwrap(), HunkAction::Stage).unwrap();
+        assert_eq!(hunk_count(dir.path(), DiffMode::Staged), 1);
+        assert_eq!(hunk_count(dir.path(), DiffMode::Unstaged), 1);
+
+        let staged = compute_file_diff_in(
+            dir.path(),
+            Path::new("file.txt"),
+            "HEAD",
+            3,
+            DiffMode::Staged,
+        )
+        .unwrap();
+        apply_hunk(
+            dir.path(),
+            &staged.hunk_patch(0).unwrap(),
+            HunkAction::Unstage,
+        )
+        .unwrap();
+        assert_eq!(hunk_count(dir.path(), DiffMode::Staged), 0);
+        assert_eq!(hunk_count(dir.path(), DiffMode::Unstaged), 2);
+    }
+
+    #[test]
+    fn revert_hunk_restores_the_working_tree() {
+        let dir = setup_repo();
+        edit_top_and_bottom(dir.path());
+        let diff = compute_file_diff_in(
+            dir.path(),
+            Path::new("file.txt"),
+            "HEAD",
+            3,
+            DiffMode::Unstaged,
+        )
+        .unwrap();
+
+        apply_hunk(dir.path(), &diff.hunk_patch(1).unwrap(), HunkAction::Revert).unwrap();
+        let content = fs::read_to_string(dir.path().join("file.txt")).unwrap();
+        assert!(content.starts_with("line one\n"));
+        assert!(content.ends_with("line 20\n"));
+    }
+
+    #[test]
+    fn stage_hunk_of_untracked_file() {
+        let dir = setup_repo();
+        fs::write(dir.path().join("new.txt"), "a\nb\n").unwrap();
+        let diff = compute_file_diff_in(
+            dir.path(),
+            Path::new("new.txt"),
+            "HEAD",
+            3,
+            DiffMode::Unstaged,
+        )
+        .unwrap();
+
+        apply_hunk(dir.path(), &diff.hunk_patch(0).unwrap(), HunkAction::Stage).unwrap();
+        let staged = compute_changed_files_in(dir.path(), "HEAD", DiffMode::Staged).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].path, Path::new("new.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stage_hunk_keeps_the_executable_bit_of_a_new_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = setup_repo();
+        let script = dir.path().join("run.sh");
+        fs::write(&script, "#!/bin/sh\necho hi\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let diff = compute_file_diff_in(
+            dir.path(),
+            Path::new("run.sh"),
+            "HEAD",
+            3,
+            DiffMode::Unstaged,
+        )
+        .unwrap();
+        assert!(diff
+            .hunk_patch(0)
+            .unwrap()
+            .contains("new file mode 100755\n"));
+
+        apply_hunk(dir.path(), &diff.hunk_patch(0).unwrap(), HunkAction::Stage).unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let entry = repo
+            .index()
+            .unwrap()
+            .get_path(Path::new("run.sh"), 0)
+            .unwrap();
+        assert_eq!(entry.mode, 0o100755);
+    }
+
+    #[test]
+    fn unstage_hunk_of_a_staged_rename() {
+        let dir = setup_repo();
+        git(dir.path(), &["mv", "file.txt", "moved.txt"]);
+        let body: String = (1..=20)
+            .map(|i| match i {
+                1 => "line one\n".to_string(),
+                _ => format!("line {i}\n"),
+            })
+            .collect();
+        fs::write(dir.path().join("moved.txt"), body).unwrap();
+        git(dir.path(), &["add", "moved.txt"]);
+
+        let files = compute_changed_files_in(dir.path(), "HEAD", DiffMode::Staged).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].status, FileStatus::Renamed);
+        let diff =
+            compute_file_diff_of(dir.path(), &files[0], "HEAD", 3, DiffMode::Staged).unwrap();
+        assert_eq!(diff.hunks.len(), 1);
+        assert_eq!((diff.file.additions, diff.file.deletions), (1, 1));
+        let patch = diff.hunk_patch(0).unwrap();
+        assert!(patch.contains("rename from file.txt\nrename to moved.txt\n"));
+
+        apply_hunk(dir.path(), &patch, HunkAction::Unstage).unwrap();
+        assert!(staged_patch(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn stage_file_then_commit() {
+        let dir = setup_repo();
+        edit_top_and_bottom(dir.path());
+        fs::remove_file(dir.path().join("file.txt")).unwrap();
+        stage_file(dir.path(), Path::new("file.txt")).unwrap();
+        assert!(staged_patch(dir.path()).unwrap().contains("deleted file"));
+
+        unstage_file(dir.path(), Path::new("file.txt")).unwrap();
+        assert!(staged_patch(dir.path()).unwrap().is_empty());
+
+        stage_file(dir.path(), Path::new("file.txt")).unwrap();
+        let sha = commit(dir.path(), "remove file").unwrap();
+        assert!(!sha.is_empty());
+        assert!(
+            compute_changed_files_in(dir.path(), "HEAD", DiffMode::Staged)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn commit_with_nothing_staged_fails() {
+        let dir = setup_repo();
+        assert!(matches!(
+            commit(dir.path(), "empty"),
+            Err(GitError::CommitFailed(_))
+        ));
+    }
+}
@@ -9,8 +9,10 @@ pub mod containers;
 pub mod git;
 pub mod hooks;
 pub mod logging;
+pub mod mcp;
 pub mod migrations;
 pub mod process;
+pub mod scheduler;
 #[cfg(feature = "serve")]
 pub mod server;
 pub mod session;
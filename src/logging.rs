@@ -27,13 +27,15 @@ use crate::session::config::{LoggingConfig, RotationKind};
 /// Which context the running process is in. Drives whether `[logging].output`
 /// is honored or coerced to `File`. Contexts where the stdout sink would
 /// corrupt the UI (TUI alt-screen) or get discarded (daemon child's
-/// detached stdio, cockpit runner) force the file sink.
+/// detached stdio, cockpit runner, `aoe mcp`'s JSON-RPC stream) force the
+/// file sink.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProcessContext {
     Tui,
     ServeDaemonChild,
     ServeForeground,
     Runner,
+    McpStdio,
     OneShotCli,
 }
 
@@ -65,7 +67,10 @@ pub fn resolve_sink(cfg: &LoggingConfig, app_dir: &Path, ctx: ProcessContext) ->
 
     let force_file = matches!(
         ctx,
-        ProcessContext::Tui | ProcessContext::ServeDaemonChild | ProcessContext::Runner
+        ProcessContext::Tui
+            | ProcessContext::ServeDaemonChild
+            | ProcessContext::Runner
+            | ProcessContext::McpStdio
     );
     let want_stdout = matches!(cfg.output, SinkKind::Stdout);
 
@@ -1179,6 +1184,17 @@ mod tests {
         assert!(r.warning.is_some(), "coercion should surface a warning");
     }
 
+    #[test]
+    fn resolve_sink_mcp_stdio_coerces_to_file() {
+        // stdout carries the MCP JSON-RPC stream; a log line there would
+        // corrupt the client's framing.
+        let mut cfg = make_cfg(RotationKind::Size, 50, 5);
+        cfg.output = crate::session::config::SinkKind::Stdout;
+        let dir = std::path::PathBuf::from("/tmp/aoe-test");
+        let r = resolve_sink(&cfg, &dir, ProcessContext::McpStdio);
+        assert!(matches!(r.target, SubscriberTarget::File(_, _)));
+    }
+
     #[test]
     fn resolve_sink_serve_foreground_honors_stdout() {
         let mut cfg = make_cfg(RotationKind::Size, 50, 5);
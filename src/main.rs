@@ -85,6 +85,7 @@ async fn main() -> Result<()> {
     let is_serve = is_serve_command(&cli);
     let is_daemon_child = is_serve_daemon_child(&cli);
     let is_tui = cli.command.is_none();
+    let is_mcp = matches!(cli.command, Some(Commands::Mcp(_)));
 
     let ctx = if is_daemon_child {
         ProcessContext::ServeDaemonChild
@@ -92,6 +93,8 @@ async fn main() -> Result<()> {
         ProcessContext::ServeForeground
     } else if is_tui {
         ProcessContext::Tui
+    } else if is_mcp {
+        ProcessContext::McpStdio
     } else {
         ProcessContext::OneShotCli
     };
@@ -223,6 +226,9 @@ async fn main() -> Result<()> {
                 ThemeCommands::Dir => cli::theme::run_dir(),
             };
         }
+        Some(Commands::RecordPane(args)) => {
+            return agent_of_empires::session::recording::run_pane_sink(args)
+        }
         Some(Commands::Uninstall(args)) => return cli::uninstall::run(args).await,
         Some(Commands::Update(args)) => return cli::update::run(args).await,
         _ => {}
@@ -269,13 +275,18 @@ async fn main() -> Result<()> {
         Some(Commands::List(args)) => cli::list::run(&profile, args).await,
         Some(Commands::Remove(args)) => cli::remove::run(&profile, args).await,
         Some(Commands::Send(args)) => cli::send::run(&profile, args).await,
+        Some(Commands::Search(args)) => cli::search::run(args).await,
+        Some(Commands::Mcp(args)) => cli::mcp::run(&profile, args).await,
         Some(Commands::Status(args)) => cli::status::run(&profile, args).await,
         Some(Commands::Session { command }) => cli::session::run(&profile, command).await,
         Some(Commands::Group { command }) => cli::group::run(&profile, command).await,
+        Some(Commands::View { command }) => cli::view::run(command),
         Some(Commands::Profile { command }) => cli::profile::run(command).await,
         Some(Commands::Project { command }) => {
             cli::project::run(&profile, profile_explicit, command).await
         }
+        Some(Commands::Schedule { command }) => cli::schedule::run(&profile, command).await,
+        Some(Commands::Scheduler) => cli::schedule::run_scheduler().await,
         Some(Commands::Worktree { command }) => cli::worktree::run(&profile, command).await,
         #[cfg(feature = "serve")]
         Some(Commands::Serve(args)) => cli::serve::run(&profile, args).await,
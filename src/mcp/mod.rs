@@ -0,0 +1,335 @@
+//! Model Context Protocol server exposing aoe sessions as tools.
+//!
+//! Lets a "manager" agent spawn worker sessions in their own worktrees,
+//! message them, read their panes, and inspect their diffs through aoe
+//! instead of shell glue. Two transports share [`McpServer::handle`]:
+//!
+//! - `aoe mcp`: newline-delimited JSON-RPC on stdin/stdout (`cli::mcp`).
+//! - `POST /api/mcp` on `aoe serve`, one JSON-RPC message per request,
+//!   mounted only when `mcp.http_enabled` is set.
+//!
+//! Only the `tools` capability is implemented. Mutating tools are gated
+//! by `mcp.allowed_mutations` (see [`crate::session::McpConfig`]).
+//!
+//! `aoe mcp --coordinator` binds the server to one session: tools only
+//! see that session's sub-sessions and `create_session` makes new ones
+//! (see [`crate::session::coordinator`]).
+
+pub mod tools;
+
+use serde_json::{json, Value};
+
+use crate::session::McpConfig;
+
+/// Newest MCP revision this server speaks. `initialize` echoes the
+/// client's requested revision when it is one of
+/// [`SUPPORTED_PROTOCOL_VERSIONS`], and answers with this one otherwise.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC 2.0 error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+const INSTRUCTIONS: &str = "Tools for driving Agent of Empires (aoe) sessions. \
+    Each session is an AI coding agent in its own tmux pane, optionally in its own git worktree. \
+    Typical loop: create_session with a worktree_branch, send_message with the task, \
+    wait_for_status for idle or waiting, then capture_output and get_diff to review the result.";
+
+const COORDINATOR_INSTRUCTIONS: &str = "Tools for splitting your task across sub-sessions: \
+    other AI coding agents, each in its own git worktree branched from your repository. \
+    Typical loop: create_session once per subtask with a distinct worktree_branch, \
+    send_message with each subtask, wait_for_status for idle or waiting on each, \
+    then get_result and get_diff to review, send_message with follow-ups, and \
+    merge_session to merge each finished branch into your checkout. \
+    Only your own sub-sessions are visible.";
+
+pub struct McpServer {
+    scope: tools::Scope,
+    allowed_mutations: Vec<String>,
+}
+
+impl McpServer {
+    pub fn new(profile: &str, config: &McpConfig) -> Self {
+        Self {
+            scope: tools::Scope::profile(profile),
+            allowed_mutations: config.allowed_mutations.clone(),
+        }
+    }
+
+    /// Coordinator mode: act for the session `parent_id`, limited to its
+    /// sub-sessions.
+    pub fn coordinator(mut self, parent_id: &str) -> Self {
+        self.scope.coordinator = Some(parent_id.to_string());
+        self
+    }
+
+    /// Disable every mutating tool regardless of the allowlist. Used when
+    /// `aoe serve` runs with `--read-only`.
+    pub fn read_only(mut self) -> Self {
+        self.allowed_mutations.clear();
+        self
+    }
+
+    fn is_enabled(&self, tool: &tools::Tool) -> bool {
+        !tool.mutating || self.allowed_mutations.iter().any(|n| n == tool.name)
+    }
+
+    /// Parse one raw message and dispatch it. Returns `None` when no
+    /// reply is due (notifications).
+    pub async fn handle_text(&self, text: &str) -> Option<Value> {
+        match serde_json::from_str::<Value>(text) {
+            Ok(msg) => self.handle(msg).await,
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("Parse error: {e}"),
+            )),
+        }
+    }
+
+    /// Dispatch one JSON-RPC message. Returns `None` when no reply is due
+    /// (notifications, and responses to requests we never send).
+    pub async fn handle(&self, msg: Value) -> Option<Value> {
+        let Some(obj) = msg.as_object() else {
+            return Some(error_response(
+                Value::Null,
+                INVALID_REQUEST,
+                "Expected a JSON-RPC request object",
+            ));
+        };
+        let id = obj.get("id").cloned();
+        let Some(method) = obj.get("method").and_then(Value::as_str) else {
+            if obj.contains_key("result") || obj.contains_key("error") {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "Missing method",
+            ));
+        };
+        let Some(id) = id else {
+            // notifications/initialized, notifications/cancelled, ...
+            tracing::debug!(target: "mcp", method, "ignoring notification");
+            return None;
+        };
+        let params = obj.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(self.initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(self.list_tools()),
+            "tools/call" => self.call_tool(&params).await,
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let version = params
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+            .unwrap_or(PROTOCOL_VERSION);
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": {
+                "name": "aoe",
+                "title": "Agent of Empires",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "instructions": if self.scope.coordinator.is_some() {
+                COORDINATOR_INSTRUCTIONS
+            } else {
+                INSTRUCTIONS
+            },
+        })
+    }
+
+    fn list_tools(&self) -> Value {
+        let tools: Vec<Value> = tools::TOOLS
+            .iter()
+            .filter(|t| self.is_enabled(t))
+            .map(tools::Tool::descriptor)
+            .collect();
+        json!({ "tools": tools })
+    }
+
+    async fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let tool = tools::TOOLS
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| (INVALID_PARAMS, format!("Unknown tool: {name}")))?;
+        if !self.is_enabled(tool) {
+            return Err((
+                INVALID_PARAMS,
+                format!("Tool '{name}' is not enabled (see mcp.allowed_mutations)"),
+            ));
+        }
+        let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+        // Tool failures are results with `isError` rather than JSON-RPC
+        // errors, so the calling model sees the message and can react.
+        Ok(match tools::call(&self.scope, tool.name, args).await {
+            Ok(value) => json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&value).unwrap_or_default(),
+                }],
+                "structuredContent": value,
+                "isError": false,
+            }),
+            Err(e) => {
+                tracing::debug!(target: "mcp", tool = name, "tool call failed: {e:#}");
+                json!({
+                    "content": [{ "type": "text", "text": format!("{e:#}") }],
+                    "isError": true,
+                })
+            }
+        })
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(allowed: &[&str]) -> McpServer {
+        McpServer::new(
+            "default",
+            &McpConfig {
+                allowed_mutations: allowed.iter().map(|s| s.to_string()).collect(),
+                http_enabled: false,
+            },
+        )
+    }
+
+    fn tool_names(resp: Value) -> Vec<String> {
+        resp["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn initialize_negotiates_protocol_version() {
+        let s = server(&[]);
+        let resp = s
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2025-03-26", "capabilities": {} }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(resp["id"], 1);
+        assert_eq!(resp["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(resp["result"]["serverInfo"]["name"], "aoe");
+
+        let resp = s
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 2, "method": "initialize",
+                "params": { "protocolVersion": "1999-01-01" }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(resp["result"]["protocolVersion"], PROTOCOL_VERSION);
+    }
+
+    #[tokio::test]
+    async fn notifications_get_no_reply() {
+        let s = server(&[]);
+        assert!(s
+            .handle(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn malformed_messages_get_jsonrpc_errors() {
+        let s = server(&[]);
+        let resp = s.handle_text("{not json").await.unwrap();
+        assert_eq!(resp["error"]["code"], PARSE_ERROR);
+        assert_eq!(resp["id"], Value::Null);
+
+        let resp = s
+            .handle(json!({ "jsonrpc": "2.0", "id": 7, "method": "resources/list" }))
+            .await
+            .unwrap();
+        assert_eq!(resp["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(resp["id"], 7);
+    }
+
+    #[tokio::test]
+    async fn allowlist_filters_mutating_tools() {
+        let req = json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+
+        let all = server(&["create_session", "send_message", "merge_session"]);
+        let names = tool_names(all.handle(req.clone()).await.unwrap());
+        assert!(names.iter().any(|n| n == "create_session"));
+        assert!(names.iter().any(|n| n == "send_message"));
+
+        let send_only = server(&["send_message"]);
+        let names = tool_names(send_only.handle(req.clone()).await.unwrap());
+        assert!(!names.iter().any(|n| n == "create_session"));
+        assert!(names.iter().any(|n| n == "send_message"));
+        assert!(names.iter().any(|n| n == "list_sessions"));
+
+        let read_only = server(&["create_session", "send_message"]).read_only();
+        let names = tool_names(read_only.handle(req).await.unwrap());
+        assert!(!names.iter().any(|n| n == "create_session"));
+        assert!(!names.iter().any(|n| n == "send_message"));
+        assert!(names.iter().any(|n| n == "get_diff"));
+    }
+
+    #[tokio::test]
+    async fn disallowed_tool_call_is_rejected() {
+        let s = server(&[]);
+        let resp = s
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                "params": { "name": "send_message", "arguments": { "session": "x", "message": "hi" } }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(resp["error"]["code"], INVALID_PARAMS);
+        assert!(resp["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("allowed_mutations"));
+    }
+
+    #[tokio::test]
+    async fn tool_failures_are_reported_in_band() {
+        let s = server(&[]);
+        let resp = s
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 4, "method": "tools/call",
+                "params": { "name": "wait_for_status", "arguments": { "session": "x" } }
+            }))
+            .await
+            .unwrap();
+        assert!(resp.get("error").is_none());
+        assert_eq!(resp["result"]["isError"], true);
+    }
+}
@@ -0,0 +1,896 @@
+//! Tool catalogue for the MCP server.
+//!
+//! Each tool is a thin adapter over the code path the CLI or web API
+//! already uses for the same operation, so an agent driving aoe through
+//! MCP sees the same validation, revive, and status semantics as a human
+//! at the terminal.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::session::builder::{self, validate_no_shell_injection, InstanceParams};
+use crate::session::coordinator::{self, ChildStatusCounts};
+use crate::session::{Instance, Status, Storage};
+
+/// One entry in `tools/list`.
+pub struct Tool {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Mutating tools are gated by `mcp.allowed_mutations`.
+    pub mutating: bool,
+    input_schema: fn() -> Value,
+}
+
+impl Tool {
+    /// The `tools/list` descriptor for this tool.
+    pub fn descriptor(&self) -> Value {
+        json!({
+            "name": self.name,
+            "description": self.description,
+            "inputSchema": (self.input_schema)(),
+            "annotations": { "readOnlyHint": !self.mutating },
+        })
+    }
+}
+
+pub const TOOLS: &[Tool] = &[
+    Tool {
+        name: "list_sessions",
+        description: "List aoe sessions in the active profile with their live status \
+                      (running, waiting, idle, stopped, error, ...).",
+        mutating: false,
+        input_schema: list_sessions_schema,
+    },
+    Tool {
+        name: "create_session",
+        description: "Create and start a new agent session. Pass worktree_branch to give \
+                      the session its own git worktree, and parent to make it a \
+                      sub-session. In coordinator mode every new session is a \
+                      sub-session of the coordinator and needs a worktree_branch.",
+        mutating: true,
+        input_schema: create_session_schema,
+    },
+    Tool {
+        name: "send_message",
+        description: "Send a message to a session's agent, reviving the pane first if it \
+                      has died or been stopped.",
+        mutating: true,
+        input_schema: send_message_schema,
+    },
+    Tool {
+        name: "capture_output",
+        description: "Capture the most recent lines of a session's terminal pane.",
+        mutating: false,
+        input_schema: capture_output_schema,
+    },
+    Tool {
+        name: "wait_for_status",
+        description: "Block until a session reaches one of the given statuses or the \
+                      timeout elapses. Typical use: wait for a worker to go idle or \
+                      waiting after send_message.",
+        mutating: false,
+        input_schema: wait_for_status_schema,
+    },
+    Tool {
+        name: "get_diff",
+        description: "Unified diff of a session's changes against its diff base (the \
+                      per-session override, then diff.default_branch, then the \
+                      detected default branch).",
+        mutating: false,
+        input_schema: get_diff_schema,
+    },
+    Tool {
+        name: "get_result",
+        description: "A session's final message: the agent's last reply from its \
+                      transcript, or the tail of its pane when aoe can't read the \
+                      agent's log. Use after wait_for_status reports idle.",
+        mutating: false,
+        input_schema: get_result_schema,
+    },
+    Tool {
+        name: "merge_session",
+        description: "Merge a sub-session's worktree branch into its parent session's \
+                      checkout. Uncommitted work in the sub-session is committed \
+                      first. Conflicts abort the merge and are reported.",
+        mutating: true,
+        input_schema: merge_session_schema,
+    },
+];
+
+/// Statuses `wait_for_status` accepts. Transitional states (`starting`,
+/// `creating`, `deleting`) are excluded: they are not worth waiting for.
+const WAITABLE_STATUSES: &[&str] = &["running", "waiting", "idle", "stopped", "error"];
+
+const DEFAULT_CAPTURE_LINES: usize = 50;
+const MAX_CAPTURE_LINES: usize = 2000;
+/// Geometry handed to `Instance::capture_output_with_size`. tmux captures
+/// at the pane's real size; these only matter if that ever changes.
+const CAPTURE_WIDTH: u16 = 200;
+const CAPTURE_HEIGHT: u16 = 50;
+
+const DEFAULT_WAIT_SECS: u64 = 300;
+const MAX_WAIT_SECS: u64 = 3600;
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Cap on the patch text `get_diff` returns, so a vendored directory or
+/// lockfile churn cannot blow through the client's context window.
+const MAX_DIFF_BYTES: usize = 200_000;
+
+/// Who a tool call acts for.
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub profile: String,
+    /// Coordinator mode: the parent session's id. Session arguments must
+    /// name one of its sub-sessions, and new sessions become its
+    /// children.
+    pub coordinator: Option<String>,
+}
+
+impl Scope {
+    pub fn profile(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            coordinator: None,
+        }
+    }
+
+    /// Resolve a session argument, limited to the coordinator's
+    /// sub-sessions in coordinator mode.
+    fn resolve<'a>(&self, identifier: &str, instances: &'a [Instance]) -> Result<&'a Instance> {
+        let Some(parent) = self.coordinator.as_deref() else {
+            return crate::cli::resolve_session(identifier, instances);
+        };
+        let children: Vec<Instance> = coordinator::children(parent, instances).cloned().collect();
+        let id = crate::cli::resolve_session(identifier, &children)
+            .map_err(|_| {
+                anyhow::anyhow!("No sub-session of this coordinator matches '{identifier}'")
+            })?
+            .id
+            .clone();
+        instances
+            .iter()
+            .find(|i| i.id == id)
+            .context("Sub-session disappeared while resolving")
+    }
+}
+
+/// Run the named tool. `name` must come from [`TOOLS`]; the allowlist is
+/// enforced by the caller.
+pub async fn call(scope: &Scope, name: &str, args: Value) -> Result<Value> {
+    let scope = scope.clone();
+    match name {
+        "wait_for_status" => wait_for_status(&scope, parse_args(args)?).await,
+        "list_sessions" => blocking(move || list_sessions(&scope, parse_args(args)?)).await,
+        "create_session" => blocking(move || create_session(&scope, parse_args(args)?)).await,
+        "send_message" => blocking(move || send_message(&scope, parse_args(args)?)).await,
+        "capture_output" => blocking(move || capture_output(&scope, parse_args(args)?)).await,
+        "get_diff" => blocking(move || get_diff(&scope, parse_args(args)?)).await,
+        "get_result" => blocking(move || get_result(&scope, parse_args(args)?)).await,
+        "merge_session" => blocking(move || merge_session(&scope, parse_args(args)?)).await,
+        _ => bail!("Unknown tool: {name}"),
+    }
+}
+
+fn parse_args<T: DeserializeOwned>(args: Value) -> Result<T> {
+    // Clients may send `null` for tools without parameters.
+    let args = if args.is_null() { json!({}) } else { args };
+    serde_json::from_value(args).context("Invalid arguments")
+}
+
+/// Tools shell out to tmux and git2; keep them off the async runtime.
+async fn blocking<F>(f: F) -> Result<Value>
+where
+    F: FnOnce() -> Result<Value> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .context("MCP tool task panicked")?
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Load the profile's sessions with freshly detected status, the same
+/// refresh `aoe status` performs.
+fn load_live_instances(profile: &str) -> Result<Vec<Instance>> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, _) = storage.load_with_groups()?;
+    crate::tmux::refresh_session_cache();
+    for inst in &mut instances {
+        inst.update_status();
+    }
+    Ok(instances)
+}
+
+fn load_live_instance(scope: &Scope, identifier: &str) -> Result<Instance> {
+    let instances = load_live_instances(&scope.profile)?;
+    scope.resolve(identifier, &instances).cloned()
+}
+
+// list_sessions
+
+fn list_sessions_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "group": {
+                "type": "string",
+                "description": "Only list sessions in this group (and its subgroups). \
+                                In coordinator mode only sub-sessions are listed."
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct ListSessionsArgs {
+    #[serde(default)]
+    group: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SessionSummary {
+    id: String,
+    title: String,
+    status: Status,
+    tool: String,
+    path: String,
+    group: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
+    /// Roll-up of this session's sub-sessions, when it has any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub_sessions: Option<ChildStatusCounts>,
+}
+
+impl SessionSummary {
+    fn new(inst: &Instance, instances: &[Instance]) -> Self {
+        let sub_sessions = ChildStatusCounts::of(&inst.id, instances);
+        Self {
+            id: inst.id.clone(),
+            title: inst.title.clone(),
+            status: inst.status,
+            tool: inst.tool.clone(),
+            path: inst.project_path.clone(),
+            group: inst.group_path.clone(),
+            branch: inst.worktree_info.as_ref().map(|w| w.branch.clone()),
+            parent: inst.parent_session_id.clone(),
+            sub_sessions: (!sub_sessions.is_empty()).then_some(sub_sessions),
+        }
+    }
+}
+
+fn list_sessions(scope: &Scope, args: ListSessionsArgs) -> Result<Value> {
+    let instances = load_live_instances(&scope.profile)?;
+    let sessions: Vec<SessionSummary> = instances
+        .iter()
+        .filter(|i| match scope.coordinator.as_deref() {
+            Some(parent) => i.parent_session_id.as_deref() == Some(parent),
+            None => true,
+        })
+        .filter(|i| match args.group.as_deref() {
+            Some(g) => i.group_path == g || i.group_path.starts_with(&format!("{g}/")),
+            None => true,
+        })
+        .map(|i| SessionSummary::new(i, &instances))
+        .collect();
+    to_value(json!({ "sessions": sessions }))
+}
+
+// create_session
+
+fn create_session_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": {
+                "type": "string",
+                "description": "Project directory (for worktree sessions, any path inside the repository). \
+                                Required unless parent is set or in coordinator mode, where it defaults \
+                                to the parent's repository."
+            },
+            "parent": {
+                "type": "string",
+                "description": "Make the new session a sub-session of this session (ID, ID prefix, or title). \
+                                Implied in coordinator mode."
+            },
+            "title": { "type": "string", "description": "Session title. Derived from the branch or directory when omitted." },
+            "tool": { "type": "string", "description": "Agent to run (e.g. claude, codex, opencode). Defaults to session.default_tool." },
+            "group": { "type": "string", "description": "Group path, e.g. \"workers/api\"." },
+            "worktree_branch": { "type": "string", "description": "Run the session in a git worktree on this branch." },
+            "create_new_branch": {
+                "type": "boolean",
+                "default": true,
+                "description": "Create worktree_branch instead of checking out an existing branch."
+            },
+            "base_branch": { "type": "string", "description": "Branch the new worktree branch starts from. Defaults to the repository's default branch." },
+            "yolo_mode": { "type": "boolean", "description": "Skip the agent's permission prompts. Defaults to session.yolo_mode_default." }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct CreateSessionArgs {
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    tool: Option<String>,
+    #[serde(default)]
+    group: Option<String>,
+    #[serde(default)]
+    worktree_branch: Option<String>,
+    #[serde(default = "default_true")]
+    create_new_branch: bool,
+    #[serde(default)]
+    base_branch: Option<String>,
+    #[serde(default)]
+    yolo_mode: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn create_session(scope: &Scope, args: CreateSessionArgs) -> Result<Value> {
+    let profile = scope.profile.as_str();
+    // Agent-supplied fields end up on tmux / git command lines, so apply
+    // the same metacharacter guard as `POST /api/sessions`. Sandbox,
+    // extra args, and command overrides are deliberately not exposed.
+    let fields = [
+        (args.path.as_deref(), "path"),
+        (args.title.as_deref(), "title"),
+        (args.tool.as_deref(), "tool"),
+        (args.group.as_deref(), "group"),
+        (args.worktree_branch.as_deref(), "worktree_branch"),
+        (args.base_branch.as_deref(), "base_branch"),
+    ];
+    for (value, name) in fields {
+        if let Some(value) = value {
+            validate_no_shell_injection(value, name).map_err(anyhow::Error::msg)?;
+        }
+    }
+
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let parent = match (scope.coordinator.as_deref(), args.parent.as_deref()) {
+        (Some(_), Some(_)) => bail!("parent is implied in coordinator mode; omit it"),
+        (Some(id), None) => Some(
+            instances
+                .iter()
+                .find(|i| i.id == id)
+                .context("Coordinator session no longer exists")?,
+        ),
+        (None, Some(identifier)) => Some(crate::cli::resolve_session(identifier, &instances)?),
+        (None, None) => None,
+    };
+    if let Some(parent) = parent {
+        if parent.is_sub_session() {
+            bail!("Cannot create sub-session of a sub-session (single level only)");
+        }
+    }
+    if scope.coordinator.is_some()
+        && args
+            .worktree_branch
+            .as_deref()
+            .is_none_or(|b| b.trim().is_empty())
+    {
+        bail!("worktree_branch is required: sub-sessions must not share the coordinator's working tree");
+    }
+
+    let path_arg = match (args.path, parent) {
+        (Some(path), _) => path,
+        (None, Some(parent)) => parent
+            .worktree_info
+            .as_ref()
+            .map(|w| w.main_repo_path.clone())
+            .unwrap_or_else(|| parent.project_path.clone()),
+        (None, None) => bail!("path is required"),
+    };
+    let path = std::path::Path::new(&path_arg);
+    if !path.is_dir() {
+        bail!("Path does not exist or is not a directory: {}", path_arg);
+    }
+    let config = crate::session::repo_config::resolve_config_with_repo(profile, path)
+        .unwrap_or_else(|e| {
+            tracing::warn!(target: "mcp", "Failed to load config, using defaults: {}", e);
+            crate::session::Config::default()
+        });
+
+    let tool = match args
+        .tool
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        Some(name) if config.session.custom_agents.contains_key(name) => name.to_string(),
+        Some(name) => crate::agents::resolve_tool_name(name)
+            .with_context(|| format!("Unknown agent '{name}'"))?
+            .to_string(),
+        None if parent.is_some() => parent.map(|p| p.tool.clone()).unwrap_or_default(),
+        None => config
+            .session
+            .default_tool
+            .as_deref()
+            .and_then(|name| {
+                if config.session.custom_agents.contains_key(name) {
+                    Some(name)
+                } else {
+                    crate::agents::resolve_tool_name(name)
+                }
+            })
+            .unwrap_or("claude")
+            .to_string(),
+    };
+
+    let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
+    let existing_branches: Vec<&str> = instances
+        .iter()
+        .filter_map(|i| i.worktree_info.as_ref().map(|w| w.branch.as_str()))
+        .collect();
+
+    let worktree_branch = args
+        .worktree_branch
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+    let group = match (args.group, parent) {
+        (Some(group), _) => group,
+        (None, Some(parent)) => parent.group_path.clone(),
+        (None, None) => String::new(),
+    };
+    let params = InstanceParams {
+        title: args.title.unwrap_or_default(),
+        path: path_arg,
+        group,
+        tool,
+        worktree_enabled: worktree_branch.is_some(),
+        worktree_branch,
+        create_new_branch: args.create_new_branch,
+        base_branch: if args.create_new_branch {
+            args.base_branch
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        } else {
+            None
+        },
+        sandbox: false,
+        sandbox_image: String::new(),
+        yolo_mode: args.yolo_mode.unwrap_or(config.session.yolo_mode_default),
+        extra_env: Vec::new(),
+        extra_args: String::new(),
+        command_override: String::new(),
+        extra_repo_paths: Vec::new(),
+        scratch: false,
+    };
+
+    let mut build_result =
+        builder::build_instance(params, &existing_titles, &existing_branches, profile)?;
+    build_result.instance.source_profile = profile.to_string();
+    build_result.instance.parent_session_id = parent.map(|p| p.id.clone());
+    let build_result = builder::start_and_persist(&storage, build_result)?;
+    let instance = &build_result.instance;
+
+    to_value(json!({
+        "id": instance.id,
+        "title": instance.title,
+        "path": instance.project_path,
+        "tool": instance.tool,
+        "branch": instance.worktree_info.as_ref().map(|w| w.branch.clone()),
+        "parent": instance.parent_session_id,
+        "warnings": build_result.warnings,
+    }))
+}
+
+// send_message
+
+fn send_message_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["session", "message"],
+        "properties": {
+            "session": { "type": "string", "description": "Session ID, ID prefix, or title." },
+            "message": { "type": "string", "description": "Text to type into the agent, followed by Enter." }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct SendMessageArgs {
+    session: String,
+    message: String,
+}
+
+fn send_message(scope: &Scope, args: SendMessageArgs) -> Result<Value> {
+    let session = match scope.coordinator {
+        Some(_) => {
+            let (instances, _) = Storage::new(&scope.profile)?.load_with_groups()?;
+            scope.resolve(&args.session, &instances)?.id.clone()
+        }
+        None => args.session,
+    };
+    let sent = crate::cli::send::send_message(&scope.profile, &session, &args.message, true)?;
+    to_value(json!({
+        "id": sent.session_id,
+        "title": sent.title,
+        "note": sent.revive_note,
+    }))
+}
+
+// capture_output
+
+fn capture_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["session"],
+        "properties": {
+            "session": { "type": "string", "description": "Session ID, ID prefix, or title." },
+            "lines": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": MAX_CAPTURE_LINES,
+                "default": DEFAULT_CAPTURE_LINES,
+                "description": "Number of trailing lines to capture."
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct CaptureOutputArgs {
+    session: String,
+    #[serde(default)]
+    lines: Option<usize>,
+}
+
+fn capture_output(scope: &Scope, args: CaptureOutputArgs) -> Result<Value> {
+    let inst = load_live_instance(scope, &args.session)?;
+    let lines = args
+        .lines
+        .unwrap_or(DEFAULT_CAPTURE_LINES)
+        .clamp(1, MAX_CAPTURE_LINES);
+    let raw = inst.capture_output_with_size(lines, CAPTURE_WIDTH, CAPTURE_HEIGHT)?;
+    to_value(json!({
+        "id": inst.id,
+        "title": inst.title,
+        "status": inst.status,
+        "content": crate::tmux::utils::strip_ansi(&raw),
+    }))
+}
+
+// wait_for_status
+
+fn wait_for_status_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["session", "status"],
+        "properties": {
+            "session": { "type": "string", "description": "Session ID, ID prefix, or title." },
+            "status": {
+                "type": "array",
+                "items": { "type": "string", "enum": WAITABLE_STATUSES },
+                "minItems": 1,
+                "description": "Return as soon as the session is in any of these statuses."
+            },
+            "timeout_secs": {
+                "type": "integer",
+                "minimum": 0,
+                "maximum": MAX_WAIT_SECS,
+                "default": DEFAULT_WAIT_SECS
+            }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct WaitForStatusArgs {
+    session: String,
+    #[serde(deserialize_with = "crate::session::serde_helpers::string_or_vec")]
+    status: Vec<String>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+async fn wait_for_status(scope: &Scope, args: WaitForStatusArgs) -> Result<Value> {
+    if args.status.is_empty() {
+        bail!(
+            "status must list at least one of: {}",
+            WAITABLE_STATUSES.join(", ")
+        );
+    }
+    for s in &args.status {
+        if !WAITABLE_STATUSES.contains(&s.as_str()) {
+            bail!(
+                "Unknown status '{s}'; expected one of: {}",
+                WAITABLE_STATUSES.join(", ")
+            );
+        }
+    }
+    let timeout = Duration::from_secs(
+        args.timeout_secs
+            .unwrap_or(DEFAULT_WAIT_SECS)
+            .min(MAX_WAIT_SECS),
+    );
+
+    let started = Instant::now();
+    loop {
+        let scope = scope.clone();
+        let session = args.session.clone();
+        let inst = tokio::task::spawn_blocking(move || load_live_instance(&scope, &session))
+            .await
+            .context("MCP tool task panicked")??;
+        let matched = args.status.iter().any(|s| s == inst.status.as_str());
+        let elapsed = started.elapsed();
+        if matched || elapsed >= timeout {
+            return to_value(json!({
+                "id": inst.id,
+                "title": inst.title,
+                "status": inst.status,
+                "matched": matched,
+                "waited_secs": elapsed.as_secs(),
+            }));
+        }
+        tokio::time::sleep(WAIT_POLL_INTERVAL.min(timeout - elapsed)).await;
+    }
+}
+
+// get_diff
+
+fn get_diff_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["session"],
+        "properties": {
+            "session": { "type": "string", "description": "Session ID, ID prefix, or title." },
+            "path": { "type": "string", "description": "Only diff this file (relative to the repository root)." },
+            "context_lines": { "type": "integer", "minimum": 0, "description": "Defaults to diff.context_lines." }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct GetDiffArgs {
+    session: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    context_lines: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct DiffFileSummary {
+    path: String,
+    status: &'static str,
+    additions: usize,
+    deletions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repo: Option<String>,
+}
+
+fn get_diff(scope: &Scope, args: GetDiffArgs) -> Result<Value> {
+    use crate::git::diff;
+
+    let profile = scope.profile.as_str();
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let inst = scope.resolve(&args.session, &instances)?;
+
+    // Workspace sessions diff every member repo; everything else diffs
+    // the project path. Mirrors the web diff view.
+    let repos: Vec<(Option<String>, String)> = match inst.workspace_info.as_ref() {
+        Some(ws) => ws
+            .repos
+            .iter()
+            .map(|r| (Some(r.name.clone()), r.worktree_path.clone()))
+            .collect(),
+        None => vec![(None, inst.project_path.clone())],
+    };
+
+    let config = crate::session::repo_config::resolve_config_with_repo(
+        profile,
+        std::path::Path::new(&inst.project_path),
+    )
+    .unwrap_or_default();
+    let context_lines = args.context_lines.unwrap_or(config.diff.context_lines);
+
+    let mut files = Vec::new();
+    let mut bases = Vec::new();
+    let mut patch = String::new();
+    let mut truncated = false;
+    for (name, repo_path) in &repos {
+        let repo_path = std::path::Path::new(repo_path);
+        let base = diff::resolve_diff_base(
+            inst.base_branch_override.as_deref(),
+            config.diff.default_branch.as_deref(),
+            repo_path,
+        );
+        let changed = diff::compute_changed_files(repo_path, &base)?;
+        bases.push(json!({ "repo": name, "base_branch": base }));
+
+        for file in changed {
+            if let Some(only) = args.path.as_deref() {
+                if file.path != std::path::Path::new(only) {
+                    continue;
+                }
+            }
+            if !truncated {
+                let file_diff =
+                    diff::compute_file_diff(repo_path, &file.path, &base, context_lines)?;
+                let text = file_diff.to_unified();
+                if patch.len() + text.len() > MAX_DIFF_BYTES {
+                    truncated = true;
+                } else {
+                    patch.push_str(&text);
+                }
+            }
+            files.push(DiffFileSummary {
+                path: file.path.to_string_lossy().to_string(),
+                status: file.status.label(),
+                additions: file.additions,
+                deletions: file.deletions,
+                repo: name.clone(),
+            });
+        }
+    }
+
+    to_value(json!({
+        "id": inst.id,
+        "bases": bases,
+        "files": files,
+        "patch": patch,
+        "truncated": truncated,
+    }))
+}
+
+// get_result
+
+fn get_result_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["session"],
+        "properties": {
+            "session": { "type": "string", "description": "Session ID, ID prefix, or title." }
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct SessionArgs {
+    session: String,
+}
+
+fn get_result(scope: &Scope, args: SessionArgs) -> Result<Value> {
+    let inst = load_live_instance(scope, &args.session)?;
+    let message = coordinator::final_message(&inst).unwrap_or_else(|e| {
+        tracing::debug!(target: "mcp", session = %inst.id, "reading transcript failed: {e:#}");
+        None
+    });
+    // Without a readable transcript, the pane tail is the closest thing
+    // to a final message. Cockpit sessions have no pane.
+    let output = match &message {
+        None if !inst.is_cockpit_mode() => inst
+            .capture_output_with_size(DEFAULT_CAPTURE_LINES, CAPTURE_WIDTH, CAPTURE_HEIGHT)
+            .ok()
+            .map(|raw| crate::tmux::utils::strip_ansi(&raw)),
+        _ => None,
+    };
+    to_value(json!({
+        "id": inst.id,
+        "title": inst.title,
+        "status": inst.status,
+        "message": message,
+        "output": output,
+    }))
+}
+
+// merge_session
+
+fn merge_session_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["session"],
+        "properties": {
+            "session": { "type": "string", "description": "Sub-session ID, ID prefix, or title." }
+        }
+    })
+}
+
+fn merge_session(scope: &Scope, args: SessionArgs) -> Result<Value> {
+    let instances = load_live_instances(&scope.profile)?;
+    let child = scope.resolve(&args.session, &instances)?;
+    let parent_id = child
+        .parent_session_id
+        .as_deref()
+        .with_context(|| format!("'{}' is not a sub-session", child.title))?;
+    let parent = instances
+        .iter()
+        .find(|i| i.id == parent_id)
+        .with_context(|| format!("Parent of '{}' no longer exists", child.title))?;
+    let report = coordinator::merge_into_parent(child, parent)?;
+    let mut value = to_value(&report)?;
+    value["id"] = json!(child.id);
+    value["into"] = json!(parent.id);
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_names_are_unique() {
+        let mut names: Vec<&str> = TOOLS.iter().map(|t| t.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), TOOLS.len());
+    }
+
+    #[test]
+    fn mutating_tools_match_config_default() {
+        // The default allowlist should enable every mutating tool; a new
+        // mutating tool that is not added there would be silently hidden.
+        let mutating: Vec<String> = TOOLS
+            .iter()
+            .filter(|t| t.mutating)
+            .map(|t| t.name.to_string())
+            .collect();
+        assert_eq!(
+            mutating,
+            crate::session::McpConfig::default().allowed_mutations
+        );
+    }
+
+    #[test]
+    fn every_schema_is_an_object_with_known_required_fields() {
+        for tool in TOOLS {
+            let schema = (tool.input_schema)();
+            assert_eq!(schema["type"], "object", "{}", tool.name);
+            let props = schema["properties"].as_object().unwrap();
+            for req in schema["required"].as_array().into_iter().flatten() {
+                assert!(
+                    props.contains_key(req.as_str().unwrap()),
+                    "{} requires undeclared property {req}",
+                    tool.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wait_for_status_accepts_single_status_string() {
+        let args: WaitForStatusArgs =
+            parse_args(json!({ "session": "abc", "status": "idle" })).unwrap();
+        assert_eq!(args.status, vec!["idle".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn wait_for_status_rejects_unknown_status() {
+        let err = call(
+            &Scope::profile("default"),
+            "wait_for_status",
+            json!({ "session": "abc", "status": ["deleting"] }),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown status"), "{err}");
+    }
+
+    #[test]
+    fn create_session_rejects_shell_metacharacters() {
+        let err = create_session(
+            &Scope::profile("default"),
+            parse_args(json!({ "path": "/tmp", "title": "x; rm -rf ~" })).unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("title"), "{err}");
+    }
+}
@@ -0,0 +1,363 @@
+//! Runs scheduled prompts (see [`crate::session::schedules`]).
+//!
+//! One scheduler per machine covers every profile. `aoe serve` starts it
+//! in the background; `aoe scheduler` runs the same loop in the
+//! foreground for hosts that do not run the dashboard. Whichever starts
+//! first holds `<app_dir>/.scheduler.lock` and the other stands by, so a
+//! job never fires twice.
+//!
+//! Runs due while no scheduler was up are recorded as missed rather than
+//! replayed, unless they are less than [`MISSED_GRACE`] late: a 7am
+//! weekday job should not fire at noon because the laptop was asleep.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::session::builder::{self, InstanceParams};
+use crate::session::schedules::{self, JobRun, JobTarget, ScheduledJob, SessionTemplate};
+use crate::session::{Instance, Status, Storage};
+
+/// How often due jobs are checked. Cron resolution is one minute.
+const TICK: Duration = Duration::from_secs(20);
+
+/// A run this late still fires; anything later is recorded as missed.
+pub const MISSED_GRACE: chrono::Duration = chrono::Duration::minutes(10);
+
+/// How long a template job waits for its fresh agent to finish booting
+/// before typing the prompt.
+const READY_TIMEOUT: Duration = Duration::from_secs(90);
+const READY_POLL: Duration = Duration::from_secs(2);
+
+/// Held by the process that runs the scheduler. Released on drop.
+struct SchedulerLock {
+    _file: std::fs::File,
+}
+
+/// Claim the scheduler for this process. `None` when another `aoe serve`
+/// or `aoe scheduler` already runs it.
+fn try_acquire_lock() -> Result<Option<SchedulerLock>> {
+    let path = crate::session::get_app_dir()?.join(".scheduler.lock");
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    match file.try_lock_exclusive() {
+        Ok(()) => Ok(Some(SchedulerLock { _file: file })),
+        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// True when some process on this machine holds the scheduler.
+pub fn is_running() -> bool {
+    matches!(try_acquire_lock(), Ok(None))
+}
+
+/// What to do with a job at `now`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Due {
+    Run,
+    /// Due, but more than [`MISSED_GRACE`] ago.
+    Missed,
+}
+
+pub fn due(job: &ScheduledJob, now: DateTime<Utc>) -> Option<Due> {
+    if !job.enabled {
+        return None;
+    }
+    let next = job.next_run()?;
+    if next > now {
+        None
+    } else if now - next > MISSED_GRACE {
+        Some(Due::Missed)
+    } else {
+        Some(Due::Run)
+    }
+}
+
+/// Check every profile's jobs until `shutdown` fires. While another
+/// process holds the scheduler this waits and retries, taking over when
+/// that process exits.
+pub async fn run(shutdown: CancellationToken) {
+    let mut lock = None;
+    let mut logged_wait = false;
+    loop {
+        if lock.is_none() {
+            match try_acquire_lock() {
+                Ok(Some(acquired)) => {
+                    tracing::info!(target: "scheduler", "scheduler started");
+                    lock = Some(acquired);
+                }
+                Ok(None) if !logged_wait => {
+                    tracing::info!(target: "scheduler", "another process runs the scheduler; standing by");
+                    logged_wait = true;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(target: "scheduler", "scheduler lock failed: {e:#}"),
+            }
+        }
+        if lock.is_some() {
+            check_all().await;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(TICK) => {}
+            _ = shutdown.cancelled() => break,
+        }
+    }
+}
+
+async fn check_all() {
+    for profile in crate::session::list_profiles().unwrap_or_default() {
+        if let Err(e) = tick(&profile, Utc::now()).await {
+            tracing::warn!(target: "scheduler", profile, "schedule check failed: {e:#}");
+        }
+    }
+}
+
+async fn tick(profile: &str, now: DateTime<Utc>) -> Result<()> {
+    let jobs = schedules::load(profile)?;
+    for job in jobs {
+        let Some(due) = due(&job, now) else { continue };
+        let run = match due {
+            Due::Missed => {
+                tracing::info!(target: "scheduler", profile, job = job.name, "skipping missed run");
+                JobRun {
+                    at: now,
+                    session_id: None,
+                    error: None,
+                    missed: true,
+                }
+            }
+            Due::Run => run_job(profile, &job).await,
+        };
+        record(profile, &job.id, run)?;
+    }
+    Ok(())
+}
+
+fn record(profile: &str, job_id: &str, run: JobRun) -> Result<()> {
+    schedules::update(profile, |jobs| {
+        // The job may have been removed while it ran.
+        if let Some(job) = jobs.iter_mut().find(|j| j.id == job_id) {
+            job.last_run = Some(run);
+        }
+        Ok(())
+    })
+}
+
+/// Fire `job` now and record the outcome as its last run. Used by the
+/// loop and by `aoe schedule run`.
+pub async fn run_now(profile: &str, job: &ScheduledJob) -> Result<JobRun> {
+    let run = run_job(profile, job).await;
+    record(profile, &job.id, run.clone())?;
+    Ok(run)
+}
+
+async fn run_job(profile: &str, job: &ScheduledJob) -> JobRun {
+    let at = Utc::now();
+    tracing::info!(target: "scheduler", profile, job = job.name, "running scheduled prompt");
+    let (session_id, result) = match &job.target {
+        JobTarget::Session { session_id } => {
+            let result = deliver(profile, session_id, &job.prompt).await;
+            (Some(session_id.clone()), result)
+        }
+        JobTarget::Template(template) => {
+            let (profile_owned, name, template) =
+                (profile.to_string(), job.name.clone(), template.clone());
+            match tokio::task::spawn_blocking(move || {
+                create_from_template(&profile_owned, &name, &template)
+            })
+            .await
+            .context("scheduler task panicked")
+            .and_then(|r| r)
+            {
+                Ok(id) => {
+                    let result = deliver(profile, &id, &job.prompt).await;
+                    (Some(id), result)
+                }
+                Err(e) => (None, Err(e)),
+            }
+        }
+    };
+    if let Err(e) = &result {
+        tracing::warn!(target: "scheduler", profile, job = job.name, "scheduled prompt failed: {e:#}");
+    }
+    JobRun {
+        at,
+        session_id,
+        error: result.err().map(|e| format!("{e:#}")),
+        missed: false,
+    }
+}
+
+/// Send `prompt` to a session: through the dashboard for cockpit
+/// sessions, by typing into the pane otherwise (reviving it first).
+/// Also used by `aoe add --issue` for the issue prompt.
+pub(crate) async fn deliver(profile: &str, session_id: &str, prompt: &str) -> Result<()> {
+    #[cfg(feature = "serve")]
+    {
+        let (instances, _) = Storage::new(profile)?.load_with_groups()?;
+        if instances
+            .iter()
+            .any(|i| i.id == session_id && i.cockpit_mode)
+        {
+            return deliver_cockpit(session_id, prompt).await;
+        }
+    }
+    let (profile, id, prompt) = (
+        profile.to_string(),
+        session_id.to_string(),
+        prompt.to_string(),
+    );
+    tokio::task::spawn_blocking(move || {
+        crate::cli::send::send_message(&profile, &id, &prompt, true).map(|_| ())
+    })
+    .await
+    .context("scheduler task panicked")?
+}
+
+#[cfg(feature = "serve")]
+async fn deliver_cockpit(session_id: &str, prompt: &str) -> Result<()> {
+    use crate::cockpit::client::{discover, HttpClient};
+    let endpoint = discover()?;
+    HttpClient::new(endpoint)?
+        .prompt(session_id, prompt)
+        .await?;
+    Ok(())
+}
+
+/// Create and start the session a template job runs in (on_create hooks
+/// included), then wait for its agent to come up. Returns the new session's ID.
+fn create_from_template(
+    profile: &str,
+    job_name: &str,
+    template: &SessionTemplate,
+) -> Result<String> {
+    let path = std::path::Path::new(&template.path);
+    if !path.is_dir() {
+        bail!("Template path does not exist: {}", template.path);
+    }
+    let config = crate::session::repo_config::resolve_config_with_repo(profile, path)
+        .unwrap_or_else(|e| {
+            tracing::warn!(target: "scheduler", "Failed to load config, using defaults: {}", e);
+            crate::session::Config::default()
+        });
+    let tool = match template.tool.as_deref() {
+        Some(name) if config.session.custom_agents.contains_key(name) => name.to_string(),
+        Some(name) => crate::agents::resolve_tool_name(name)
+            .with_context(|| format!("Unknown agent '{name}'"))?
+            .to_string(),
+        None => config
+            .session
+            .default_tool
+            .as_deref()
+            .and_then(|name| {
+                if config.session.custom_agents.contains_key(name) {
+                    Some(name)
+                } else {
+                    crate::agents::resolve_tool_name(name)
+                }
+            })
+            .unwrap_or("claude")
+            .to_string(),
+    };
+    let worktree_branch = template
+        .worktree_branch
+        .as_deref()
+        .map(|prefix| format!("{prefix}-{}", chrono::Local::now().format("%Y%m%d-%H%M")));
+
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
+    let existing_branches: Vec<&str> = instances
+        .iter()
+        .filter_map(|i| i.worktree_info.as_ref().map(|w| w.branch.as_str()))
+        .collect();
+    let params = InstanceParams {
+        title: template
+            .title
+            .clone()
+            .unwrap_or_else(|| job_name.to_string()),
+        path: template.path.clone(),
+        group: template.group.clone().unwrap_or_default(),
+        tool,
+        worktree_enabled: worktree_branch.is_some(),
+        worktree_branch,
+        create_new_branch: true,
+        base_branch: None,
+        sandbox: false,
+        sandbox_image: String::new(),
+        yolo_mode: template
+            .yolo_mode
+            .unwrap_or(config.session.yolo_mode_default),
+        extra_env: Vec::new(),
+        extra_args: String::new(),
+        command_override: String::new(),
+        extra_repo_paths: Vec::new(),
+        scratch: false,
+    };
+
+    let mut build_result =
+        builder::build_instance(params, &existing_titles, &existing_branches, profile)?;
+    build_result.instance.source_profile = profile.to_string();
+    let mut instance = builder::start_and_persist(&storage, build_result)?.instance;
+
+    wait_until_ready(&mut instance);
+    Ok(instance.id)
+}
+
+/// Poll until the agent leaves its startup states, so the prompt is not
+/// typed into a half-drawn TUI. Gives up quietly after [`READY_TIMEOUT`];
+/// the prompt is sent either way.
+pub(crate) fn wait_until_ready(instance: &mut Instance) {
+    let started = std::time::Instant::now();
+    while started.elapsed() < READY_TIMEOUT {
+        std::thread::sleep(READY_POLL);
+        crate::tmux::refresh_session_cache();
+        instance.update_status();
+        if matches!(instance.status, Status::Idle | Status::Waiting) {
+            return;
+        }
+    }
+    tracing::warn!(target: "scheduler", session = instance.id, "agent not ready; sending anyway");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::schedules::Trigger;
+
+    fn job(at: DateTime<Utc>) -> ScheduledJob {
+        ScheduledJob::new(
+            "audit",
+            Trigger::At { at },
+            JobTarget::Session {
+                session_id: "abc".to_string(),
+            },
+            "run the audit",
+        )
+    }
+
+    #[test]
+    fn due_distinguishes_on_time_late_and_missed_runs() {
+        let now = Utc::now();
+        assert_eq!(due(&job(now + chrono::Duration::minutes(1)), now), None);
+        assert_eq!(
+            due(&job(now - chrono::Duration::minutes(2)), now),
+            Some(Due::Run)
+        );
+        assert_eq!(
+            due(&job(now - chrono::Duration::hours(5)), now),
+            Some(Due::Missed)
+        );
+
+        let mut disabled = job(now);
+        disabled.enabled = false;
+        assert_eq!(due(&disabled, now), None);
+    }
+}
@@ -13,6 +13,7 @@ use std::sync::Arc;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::Json;
+use schemars::JsonSchema;
 use serde::Deserialize;
 
 use super::AppState;
@@ -24,7 +25,7 @@ const MAX_PATH: usize = 512;
 const MAX_USER_AGENT: usize = 512;
 const MAX_TARGET: usize = 64;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ClientLogEntry {
     pub level: String,
@@ -42,7 +43,7 @@ pub struct ClientLogEntry {
     pub dropped: Option<u64>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ClientLogBatch {
     pub entries: Vec<ClientLogEntry>,
@@ -10,17 +10,22 @@ use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::cockpit::approvals::Nonce;
+use crate::cockpit::checkpoints::{self, Checkpoint};
 use crate::cockpit::protocol::{
-    ContextPrimerQuery, ContextPrimerResponse, PromptRequest, ReplayQuery, ReplayResponse,
-    ResolveApprovalRequest, SwitchAgentRequest, SwitchAgentResponse,
+    ContextPrimerQuery, ContextPrimerResponse, HandoffRequest, HandoffResponse, PromptRequest,
+    ReplayQuery, ReplayResponse, ResolveApprovalRequest, RewindRequest, RewindResponse,
+    SwitchAgentRequest, SwitchAgentResponse,
 };
+use crate::cockpit::retention::{self, GcReport};
 use crate::cockpit::supervisor::SupervisorError;
+use crate::cockpit::usage::{self, UsageReport};
 use crate::server::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct SpawnCockpitRequest {
     /// Optional override; falls back to the cockpit_default_agent
     /// setting / aoe-agent.
@@ -38,13 +43,13 @@ pub struct SpawnCockpitRequest {
     pub provider_env: Vec<EnvPair>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct EnvPair {
     pub key: String,
     pub value: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SpawnCockpitResponse {
     pub session_id: String,
     pub agent: String,
@@ -146,9 +151,7 @@ pub async fn spawn_cockpit(
                 .into_response();
         }
     };
-    let source_profile = sandbox_info
-        .as_ref()
-        .map(|_| instance.source_profile.clone());
+    let source_profile = instance.source_profile.clone();
     let agent_for_response = agent.clone();
     match state
         .cockpit_supervisor
@@ -212,7 +215,7 @@ pub async fn shutdown_cockpit(
 /// One entry in the cockpit ACP registry. Names match the `target`
 /// field accepted by `/cockpit/switch-agent`. Used by the rate-limit
 /// recovery modal to list available backends. See #1282.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CockpitAgentInfo {
     pub name: String,
     pub description: String,
@@ -239,6 +242,63 @@ pub async fn list_cockpit_agents(State(state): State<Arc<AppState>>) -> impl Int
     Json(entries).into_response()
 }
 
+/// `GET /api/cockpit/usage`: turns, context, cost, and rate limits
+/// across every cockpit session, grouped by the provider they bill
+/// against, so the user can tell which shared quota is about to run
+/// out before starting a big task.
+pub async fn cockpit_usage(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let now = chrono::Utc::now();
+    let store = state.cockpit_event_store.clone();
+    let samples = tokio::task::spawn_blocking(move || store.usage_since(now - usage::RETENTION))
+        .await
+        .unwrap_or_default();
+    let titles: std::collections::HashMap<String, String> = state
+        .instances
+        .read()
+        .await
+        .iter()
+        .map(|i| (i.id.clone(), i.title.clone()))
+        .collect();
+    let report: UsageReport = usage::summarize(&samples, &titles, now);
+    Json(report).into_response()
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct GcRequest {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// `POST /api/cockpit/gc`: run a retention pass on the daemon's own
+/// connection. `aoe cockpit gc` comes here while `aoe serve` is up rather
+/// than opening the database next to it.
+pub async fn cockpit_gc(
+    State(state): State<Arc<AppState>>,
+    req: Result<Json<GcRequest>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Some(resp) = read_only_block(&state) {
+        return resp;
+    }
+    let Json(req) = match req {
+        Ok(j) => j,
+        Err(rej) => return rej.into_response(),
+    };
+    let store = state.cockpit_event_store.clone();
+    match tokio::task::spawn_blocking(move || retention::run(&store, req.dry_run)).await {
+        Ok(Ok(report)) => Json::<GcReport>(report).into_response(),
+        Ok(Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("cockpit gc failed: {e:#}"),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("cockpit gc task panicked: {e}"),
+        )
+            .into_response(),
+    }
+}
+
 /// Atomically move a cockpit session from one ACP backend to another.
 /// Used by the rate-limit recovery flow (#1282) so the user can
 /// continue a Claude-rate-limited session in `codex` (or another
@@ -274,24 +334,54 @@ pub async fn switch_cockpit_agent(
     if let Err(reason) = cockpit_gate(&state) {
         return reason.into_response();
     }
+    match switch_agent(&state, &id, &req.target, req.model, "rate_limited").await {
+        Ok(switched) => Json(SwitchAgentResponse {
+            session_id: id,
+            agent: switched.to,
+            before_seq: switched.before_seq,
+            switch_seq: switched.switch_seq,
+            status: "running",
+        })
+        .into_response(),
+        Err(resp) => resp,
+    }
+}
 
-    let target = req.target.trim().to_string();
+/// Outcome of [`switch_agent`].
+struct AgentSwitch {
+    from: String,
+    to: String,
+    before_seq: u64,
+    switch_seq: u64,
+}
+
+/// Steps 1-6 of [`switch_cockpit_agent`], shared with
+/// [`cockpit_handoff`]. The error is the response to return as-is.
+async fn switch_agent(
+    state: &Arc<AppState>,
+    id: &str,
+    target: &str,
+    model: Option<String>,
+    reason: &str,
+) -> Result<AgentSwitch, axum::response::Response> {
+    let id = id.to_string();
+    let target = target.trim().to_string();
     if target.is_empty() {
-        return (StatusCode::BAD_REQUEST, "target is required").into_response();
+        return Err((StatusCode::BAD_REQUEST, "target is required").into_response());
     }
     if !state.cockpit_supervisor.registry_has_agent(&target).await {
-        return (
+        return Err((
             StatusCode::BAD_REQUEST,
             format!("unknown cockpit agent: {target}"),
         )
-            .into_response();
+            .into_response());
     }
 
     let instance = {
         let instances = state.instances.read().await;
         match instances.iter().find(|i| i.id == id).cloned() {
             Some(inst) => inst,
-            None => return (StatusCode::NOT_FOUND, "session not found").into_response(),
+            None => return Err((StatusCode::NOT_FOUND, "session not found").into_response()),
         }
     };
     let from_agent = state
@@ -299,11 +389,11 @@ pub async fn switch_cockpit_agent(
         .pick_agent_for_tool(&instance.tool, instance.cockpit_agent.as_deref())
         .await;
     if from_agent == target {
-        return (
+        return Err((
             StatusCode::BAD_REQUEST,
             format!("session is already using {target}"),
         )
-            .into_response();
+            .into_response());
     }
     let before_seq = state.cockpit_event_store.highest_seq(&id);
 
@@ -312,11 +402,11 @@ pub async fn switch_cockpit_agent(
         .shutdown_and_wait(&id, std::time::Duration::from_secs(5))
         .await
     {
-        return (
+        return Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("shutdown failed before agent switch: {e}"),
         )
-            .into_response();
+            .into_response());
     }
 
     let cwd = PathBuf::from(&instance.project_path);
@@ -331,18 +421,16 @@ pub async fn switch_cockpit_agent(
     {
         Ok(info) => info,
         Err(e) => {
-            return (
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("sandbox container ensure failed: {e}"),
             )
-                .into_response();
+                .into_response());
         }
     };
-    let source_profile = sandbox_info
-        .as_ref()
-        .map(|_| instance.source_profile.clone());
+    let source_profile = instance.source_profile.clone();
 
-    let model = req.model.clone().or(instance.cockpit_model.clone());
+    let model = model.or(instance.cockpit_model.clone());
     let spawn_result = state
         .cockpit_supervisor
         .spawn(crate::cockpit::supervisor::SpawnRequest {
@@ -361,7 +449,7 @@ pub async fn switch_cockpit_agent(
         })
         .await;
     if let Err(e) = spawn_result {
-        return match e {
+        return Err(match e {
             SupervisorError::UnknownAgent(name) => (
                 StatusCode::BAD_REQUEST,
                 format!("unknown cockpit agent: {name}"),
@@ -380,7 +468,7 @@ pub async fn switch_cockpit_agent(
                 format!("spawn failed: {e}"),
             )
                 .into_response(),
-        };
+        });
     }
 
     // Persist the agent change AFTER spawn succeeded. The new agent's
@@ -419,19 +507,133 @@ pub async fn switch_cockpit_agent(
         &id,
         from_agent.clone(),
         target.clone(),
-        "rate_limited".into(),
+        reason.to_string(),
     );
 
-    Json(SwitchAgentResponse {
-        session_id: id,
-        agent: target,
+    Ok(AgentSwitch {
+        from: from_agent,
+        to: target,
         before_seq,
         switch_seq,
-        status: "running",
+    })
+}
+
+/// Hand a session to a different agent and carry the context across:
+/// switch as [`switch_cockpit_agent`] does, then send the new agent a
+/// recap of the previous agent's transcript plus the current plan as
+/// its first prompt. Unlike the rate-limit flow, nothing goes through
+/// the composer; the recap shows up in the log as a user prompt.
+pub async fn cockpit_handoff(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    req: Result<Json<HandoffRequest>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Some(resp) = read_only_block(&state) {
+        return resp;
+    }
+    let Json(req) = match req {
+        Ok(j) => j,
+        Err(rej) => return rej.into_response(),
+    };
+    if let Err(reason) = cockpit_gate(&state) {
+        return reason.into_response();
+    }
+    let switched = match switch_agent(&state, &id, &req.target, req.model, "handoff").await {
+        Ok(switched) => switched,
+        Err(resp) => return resp,
+    };
+
+    let history = state
+        .cockpit_event_store
+        .replay_before(&id, switched.before_seq.saturating_add(1));
+    let message = req
+        .message
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty());
+    let plan = state.cockpit_event_store.latest_plan(&id);
+    let (prompt, included_turns) =
+        handoff_prompt(&history, &switched.from, message, plan, req.prime);
+
+    let mut prompted = false;
+    if !prompt.is_empty() {
+        // Like the rewind recap, the handoff prompt is aoe's, so it gets
+        // no checkpoint and doesn't count as a turn.
+        state
+            .cockpit_supervisor
+            .publish_user_prompt(&id, prompt.clone())
+            .await;
+        match state.cockpit_supervisor.send_prompt(&id, &prompt).await {
+            Ok(()) => prompted = true,
+            Err(e) => tracing::warn!(
+                target: "http.api.cockpit",
+                session = %id,
+                "sending handoff prompt failed: {e}"
+            ),
+        }
+    }
+
+    Json(HandoffResponse {
+        session_id: id,
+        from: switched.from,
+        agent: switched.to,
+        switch_seq: switched.switch_seq,
+        included_turns,
+        prompted,
     })
     .into_response()
 }
 
+/// First prompt for the agent taking over: the transcript since the
+/// last rewind, the plan (unless a rewind made it stale), and the
+/// user's request. With no request, a prompt the previous agent never
+/// got to (it was rate-limited) is carried over as the request. Empty
+/// when there is nothing to send.
+fn handoff_prompt(
+    history: &[(u64, crate::cockpit::Event)],
+    from: &str,
+    request: Option<String>,
+    plan: Option<crate::cockpit::state::Plan>,
+    prime: bool,
+) -> (String, usize) {
+    use crate::cockpit::context_primer::{build_context_primer, PrimerFraming, PrimerOptions};
+    use crate::cockpit::Event;
+
+    let start = after_last_rewind(history, u64::MAX);
+    let history = &history[start..];
+    let plan = plan.filter(|_| {
+        history
+            .iter()
+            .any(|(_, e)| matches!(e, Event::PlanUpdated { .. }))
+    });
+    let build = |request: Option<String>| {
+        build_context_primer(
+            history,
+            PrimerOptions {
+                framing: PrimerFraming::Handoff {
+                    from: from.to_string(),
+                    request,
+                },
+                current_plan: plan.clone(),
+                ..Default::default()
+            },
+        )
+    };
+    let mut primer = build(request.clone());
+    let request = match (request, primer.unprocessed_prompt.take()) {
+        (Some(request), _) => Some(request),
+        (None, Some(unprocessed)) => {
+            primer = build(Some(unprocessed.clone()));
+            Some(unprocessed)
+        }
+        (None, None) => None,
+    };
+    if prime && primer.included_turn_count > 0 {
+        (primer.text, primer.included_turn_count)
+    } else {
+        (request.unwrap_or_default(), 0)
+    }
+}
+
 pub async fn cockpit_prompt(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -519,10 +721,11 @@ pub async fn cockpit_prompt(
     // to the agent so the replay buffer / on-disk store captures it
     // even if the agent forward fails. The frontend treats UserPromptSent
     // as authoritative and dedupes against its own optimistic row.
-    state
+    let seq = state
         .cockpit_supervisor
         .publish_user_prompt(&id, req.text.clone())
         .await;
+    record_checkpoint(&state, &id, seq, &req.text).await;
     match state.cockpit_supervisor.send_prompt(&id, &req.text).await {
         Ok(()) => StatusCode::ACCEPTED.into_response(),
         Err(SupervisorError::UnknownSession(_)) => {
@@ -536,6 +739,362 @@ pub async fn cockpit_prompt(
     }
 }
 
+/// Snapshot the worktree before the agent sees the prompt at `seq`, so
+/// `/cockpit/rewind` can put the files back. Best-effort: a session
+/// outside a git repo just has no checkpoints.
+async fn record_checkpoint(state: &AppState, id: &str, seq: u64, prompt: &str) {
+    let Some(worktree) = project_path(state, id).await else {
+        return;
+    };
+    let session_id = id.to_string();
+    let prompt = prompt.to_string();
+    match tokio::task::spawn_blocking(move || {
+        checkpoints::record(&worktree, &session_id, seq, &prompt)
+    })
+    .await
+    {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => tracing::debug!(
+            target: "http.api.cockpit",
+            session = %id,
+            seq,
+            "no checkpoint recorded: {e:#}"
+        ),
+        Err(join_err) => tracing::warn!(
+            target: "http.api.cockpit",
+            session = %id,
+            "spawn_blocking join error while recording checkpoint: {join_err}"
+        ),
+    }
+}
+
+async fn project_path(state: &AppState, id: &str) -> Option<PathBuf> {
+    let instances = state.instances.read().await;
+    instances
+        .iter()
+        .find(|i| i.id == id)
+        .map(|i| PathBuf::from(&i.project_path))
+}
+
+/// Drop a session's checkpoint refs. Called on cockpit disable;
+/// session delete does the same in `perform_deletion`.
+pub(crate) async fn discard_checkpoints(project_path: &str, id: &str) {
+    let worktree = PathBuf::from(project_path);
+    let session_id = id.to_string();
+    let _ =
+        tokio::task::spawn_blocking(move || checkpoints::discard_from(&worktree, &session_id, 0))
+            .await;
+}
+
+/// `GET /api/sessions/{id}/cockpit/checkpoints`: the session's turn
+/// checkpoints, oldest first. Empty when the worktree isn't a git repo.
+pub async fn cockpit_checkpoints(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Some(worktree) = project_path(&state, &id).await else {
+        return (StatusCode::NOT_FOUND, "session not found").into_response();
+    };
+    let list: Vec<Checkpoint> =
+        tokio::task::spawn_blocking(move || checkpoints::list(&worktree, &id).unwrap_or_default())
+            .await
+            .unwrap_or_default();
+    Json(list).into_response()
+}
+
+/// Rewind a cockpit session to the checkpoint taken before `turn`:
+///
+///   1. Stop the worker so the agent can't write mid-restore.
+///   2. Restore the worktree's files from the checkpoint and drop the
+///      checkpoints of the discarded turns.
+///   3. Spawn a fresh worker with no stored ACP session id, so the
+///      agent starts a new `session/new`, and clear the persisted id.
+///   4. Emit `Rewound`, then (unless `prime` is false) send a
+///      `context_primer` recap of the turns before `turn` as the new
+///      session's first prompt.
+///
+/// The event log is append-only: the discarded turns stay on record
+/// above the `Rewound` divider, and the recap only covers turns since
+/// the previous rewind (whose own recap covers the ones before it).
+pub async fn cockpit_rewind(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    req: Result<Json<RewindRequest>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Some(resp) = read_only_block(&state) {
+        return resp;
+    }
+    let Json(req) = match req {
+        Ok(j) => j,
+        Err(rej) => return rej.into_response(),
+    };
+    if let Err(reason) = cockpit_gate(&state) {
+        return reason.into_response();
+    }
+    let instance = {
+        let instances = state.instances.read().await;
+        match instances.iter().find(|i| i.id == id).cloned() {
+            Some(inst) => inst,
+            None => return (StatusCode::NOT_FOUND, "session not found").into_response(),
+        }
+    };
+    let worktree = PathBuf::from(&instance.project_path);
+
+    let listed = {
+        let worktree = worktree.clone();
+        let id = id.clone();
+        tokio::task::spawn_blocking(move || checkpoints::list(&worktree, &id)).await
+    };
+    let checkpoint = match listed {
+        Ok(Ok(list)) => {
+            let recorded = list.len();
+            match list.into_iter().find(|c| c.turn == req.turn) {
+                Some(c) => c,
+                None => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        format!("no checkpoint for turn {} ({recorded} recorded)", req.turn),
+                    )
+                        .into_response()
+                }
+            }
+        }
+        Ok(Err(e)) => {
+            return (StatusCode::CONFLICT, format!("no checkpoints: {e:#}")).into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("checkpoint lookup failed: {e}"),
+            )
+                .into_response()
+        }
+    };
+
+    if let Err(e) = state
+        .cockpit_supervisor
+        .shutdown_and_wait(&id, std::time::Duration::from_secs(5))
+        .await
+    {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("shutdown failed before rewind: {e}"),
+        )
+            .into_response();
+    }
+
+    let restored = {
+        let worktree = worktree.clone();
+        let id = id.clone();
+        let checkpoint = checkpoint.clone();
+        tokio::task::spawn_blocking(move || {
+            checkpoints::restore(&worktree, &id, &checkpoint)?;
+            checkpoints::discard_from(&worktree, &id, checkpoint.seq)
+        })
+        .await
+    };
+    match restored {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("restoring checkpoint failed: {e:#}"),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("restoring checkpoint failed: {e}"),
+            )
+                .into_response()
+        }
+    }
+
+    let history = state
+        .cockpit_event_store
+        .replay_before(&id, checkpoint.seq.saturating_add(1));
+    let (prompt, primer) = rewind_primer(&history, &checkpoint);
+
+    let agent = state
+        .cockpit_supervisor
+        .pick_agent_for_tool(&instance.tool, instance.cockpit_agent.as_deref())
+        .await;
+    let inst_lock = state.instance_lock(&id).await;
+    let sandbox_info = match crate::cockpit::sandbox::ensure_container_for_session(
+        &state.instances,
+        &inst_lock,
+        &id,
+        false,
+    )
+    .await
+    {
+        Ok(info) => info,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("sandbox container ensure failed: {e}"),
+            )
+                .into_response();
+        }
+    };
+    let source_profile = instance.source_profile.clone();
+    if let Err(e) = state
+        .cockpit_supervisor
+        .spawn(crate::cockpit::supervisor::SpawnRequest {
+            session_id: id.clone(),
+            agent,
+            cwd: worktree,
+            additional_dirs: vec![],
+            provider_env: vec![],
+            model: instance.cockpit_model.clone(),
+            // The old ACP session remembers the discarded turns.
+            stored_acp_session_id: None,
+            sandbox_info,
+            source_profile,
+            yolo_mode: instance.yolo_mode,
+        })
+        .await
+    {
+        return spawn_error_response(e);
+    }
+
+    {
+        let mut instances = state.instances.write().await;
+        if let Some(inst) = instances.iter_mut().find(|i| i.id == id) {
+            inst.cockpit_acp_session_id = None;
+        }
+    }
+    let persisted = {
+        let profile = instance.source_profile.clone();
+        let id = id.clone();
+        tokio::task::spawn_blocking(move || {
+            crate::session::Storage::new(&profile)?.update(|instances, _groups| {
+                if let Some(inst) = instances.iter_mut().find(|i| i.id == id) {
+                    inst.cockpit_acp_session_id = None;
+                }
+                Ok(())
+            })
+        })
+        .await
+    };
+    match persisted {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::error!(
+            target: "http.api.cockpit",
+            session = %id,
+            "failed to persist cleared acp session id after rewind: {e}"
+        ),
+        Err(join_err) => tracing::error!(
+            target: "http.api.cockpit",
+            session = %id,
+            "spawn_blocking join error persisting cleared acp session id: {join_err}"
+        ),
+    }
+
+    let rewound_seq =
+        state
+            .cockpit_supervisor
+            .publish_rewound(&id, checkpoint.turn, checkpoint.seq);
+
+    let mut primed = false;
+    if req.prime && !primer.is_empty() {
+        // The recap is aoe's, not the user's, so it gets no checkpoint:
+        // rewinding "to before the recap" would be the turn just restored.
+        state
+            .cockpit_supervisor
+            .publish_user_prompt(&id, primer.clone())
+            .await;
+        match state.cockpit_supervisor.send_prompt(&id, &primer).await {
+            Ok(()) => primed = true,
+            Err(e) => tracing::warn!(
+                target: "http.api.cockpit",
+                session = %id,
+                "sending rewind recap failed: {e}"
+            ),
+        }
+    }
+
+    Json(RewindResponse {
+        session_id: id,
+        turn: checkpoint.turn,
+        checkpoint_seq: checkpoint.seq,
+        rewound_seq,
+        prompt,
+        primed,
+    })
+    .into_response()
+}
+
+/// The rewound turn's full prompt text and the recap of what came
+/// before it. `history` is every event up to and including the
+/// checkpoint's `UserPromptSent`.
+fn rewind_primer(
+    history: &[(u64, crate::cockpit::Event)],
+    checkpoint: &Checkpoint,
+) -> (String, String) {
+    use crate::cockpit::context_primer::{build_context_primer, PrimerFraming, PrimerOptions};
+    use crate::cockpit::Event;
+
+    let prompt = history
+        .iter()
+        .rev()
+        .find_map(|(seq, event)| match event {
+            Event::UserPromptSent { text } if *seq == checkpoint.seq => Some(text.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| checkpoint.prompt.clone());
+    let start = after_last_rewind(history, checkpoint.seq);
+    let primer = build_context_primer(
+        &history[start..],
+        PrimerOptions {
+            before_seq: Some(checkpoint.seq),
+            framing: PrimerFraming::Rewind,
+            ..Default::default()
+        },
+    );
+    let text = if primer.included_turn_count == 0 {
+        String::new()
+    } else {
+        primer.text
+    };
+    (prompt, text)
+}
+
+/// Index just past the last `Rewound` event with seq below `before`.
+/// Turns discarded by a rewind are still in the log; the recap prompt
+/// sent after the rewind already covers the turns before them.
+fn after_last_rewind(history: &[(u64, crate::cockpit::Event)], before: u64) -> usize {
+    history
+        .iter()
+        .rposition(|(seq, event)| {
+            *seq < before && matches!(event, crate::cockpit::Event::Rewound { .. })
+        })
+        .map(|i| i + 1)
+        .unwrap_or(0)
+}
+
+fn spawn_error_response(e: SupervisorError) -> axum::response::Response {
+    match e {
+        SupervisorError::AlreadyRunning(_) => {
+            (StatusCode::CONFLICT, "cockpit already running for session").into_response()
+        }
+        SupervisorError::UnknownAgent(name) => (
+            StatusCode::BAD_REQUEST,
+            format!("unknown cockpit agent: {name}"),
+        )
+            .into_response(),
+        e @ SupervisorError::CapacityFull { .. } => {
+            (StatusCode::SERVICE_UNAVAILABLE, format!("{e}")).into_response()
+        }
+        e => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("spawn failed: {e}"),
+        )
+            .into_response(),
+    }
+}
+
 pub async fn cockpit_cancel(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -572,7 +1131,7 @@ pub async fn cockpit_force_end_turn(
     StatusCode::ACCEPTED.into_response()
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct FilesResponse {
     pub files: Vec<String>,
     pub truncated: bool,
@@ -615,14 +1174,14 @@ const WORKER_LOG_MAX_TAIL: usize = 2000;
 /// while keeping memory predictable.
 const WORKER_LOG_MAX_READ_BYTES: u64 = 4 * 1024 * 1024;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct WorkerLogQuery {
     /// Number of trailing lines to return. Clamped to
     /// [1, `WORKER_LOG_MAX_TAIL`]; defaults to `WORKER_LOG_DEFAULT_TAIL`.
     pub tail: Option<usize>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct WorkerLogResponse {
     pub path: String,
     pub exists: bool,
@@ -802,7 +1361,7 @@ fn list_files(root: &std::path::Path, cap: usize) -> std::io::Result<(Vec<String
 
 /* ── Substrate switching: cockpit ↔ tmux ─────────────────────── */
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SubstrateSwitchResponse {
     pub session_id: String,
     pub cockpit_mode: bool,
@@ -933,7 +1492,6 @@ pub async fn cockpit_enable(
                 return;
             }
         };
-        let source_profile = sandbox_info.as_ref().map(|_| profile_for_spawn);
         if let Err(e) = supervisor
             .spawn(crate::cockpit::supervisor::SpawnRequest {
                 session_id: session_id.clone(),
@@ -944,7 +1502,7 @@ pub async fn cockpit_enable(
                 model,
                 stored_acp_session_id,
                 sandbox_info,
-                source_profile,
+                source_profile: profile_for_spawn,
                 yolo_mode,
             })
             .await
@@ -1011,6 +1569,7 @@ pub async fn cockpit_disable(
     // with a row already on disk and INSERT OR IGNORE would silently
     // drop it.
     state.cockpit_event_store.delete_session(&id);
+    discard_checkpoints(&instance.project_path, &id).await;
     instance.cockpit_mode = false;
     // Clear the stored ACP session id: the agent's transcript is
     // tied to the cockpit-mode lifecycle. If the user re-enables
@@ -1083,7 +1642,7 @@ pub async fn cockpit_disable(
     .into_response()
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct SetModeRequest {
     pub mode_id: String,
 }
@@ -1115,7 +1674,7 @@ pub async fn cockpit_set_mode(
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct SetConfigOptionRequest {
     pub config_id: String,
     pub value: String,
@@ -1266,12 +1825,12 @@ pub async fn cockpit_replay(
     .into_response()
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct SetMasterRequest {
     pub enabled: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct MasterStateResponse {
     pub master_enabled: bool,
 }
@@ -1426,4 +1985,43 @@ mod tests {
         assert_eq!(lines.last().map(String::as_str), Some("real second"));
         assert!(!lines.iter().any(|l| l == &big_line));
     }
+
+    #[test]
+    fn handoff_prompt_carries_an_unsent_prompt_and_skips_rewound_turns() {
+        use crate::cockpit::Event;
+        let prompt = |text: &str| Event::UserPromptSent { text: text.into() };
+        let reply = |text: &str| Event::AgentMessageChunk { text: text.into() };
+        let stopped = |reason: &str| Event::Stopped {
+            reason: reason.into(),
+        };
+        let history = vec![
+            (1, prompt("discarded idea")),
+            (2, reply("discarded reply")),
+            (3, stopped("prompt_complete")),
+            (
+                4,
+                Event::Rewound {
+                    turn: 1,
+                    checkpoint_seq: 1,
+                },
+            ),
+            (5, prompt("add a parser")),
+            (6, reply("parser added")),
+            (7, stopped("prompt_complete")),
+            (8, prompt("now the tests")),
+            (9, stopped("rate_limited")),
+        ];
+
+        let (text, turns) = handoff_prompt(&history, "claude", None, None, true);
+        assert_eq!(turns, 1);
+        assert!(text.contains("parser added"));
+        assert!(!text.contains("discarded"));
+        assert!(text.ends_with("## Current request\n\nnow the tests\n"));
+
+        let (text, turns) = handoff_prompt(&history, "claude", None, None, false);
+        assert_eq!((text.as_str(), turns), ("now the tests", 0));
+
+        let (text, _) = handoff_prompt(&history, "claude", Some("review it".into()), None, true);
+        assert!(text.ends_with("## Current request\n\nreview it\n"));
+    }
 }
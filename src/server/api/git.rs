@@ -3,13 +3,14 @@
 use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::AppState;
 
 // --- Clone repository ---
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct CloneRepoBody {
     pub url: String,
     pub destination: Option<String>,
@@ -186,7 +187,7 @@ pub async fn clone_repo(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct BranchesQuery {
     pub path: String,
     /// Include remote-only branches alongside local ones. Used by the
@@ -196,7 +197,7 @@ pub struct BranchesQuery {
     pub include_remote: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct BranchInfo {
     pub name: String,
     pub is_current: bool,
@@ -14,27 +14,28 @@ use std::sync::Arc;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::Json;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::logging::{self, LogFilterError, LogLevel};
 
 use super::AppState;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct PatchRequest {
     pub level: Option<String>,
     pub filter: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct LogLevelResponse {
     pub previous: String,
     pub current: String,
     pub ephemeral: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct LogLevelStatus {
     pub current: Option<String>,
     pub reloadable: bool,
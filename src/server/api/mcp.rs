@@ -0,0 +1,41 @@
+//! `POST /api/mcp`: the MCP tool server over HTTP.
+//!
+//! Same dispatcher as `aoe mcp` (see `crate::mcp`), one JSON-RPC message
+//! per request with the reply in the response body. Off unless
+//! `mcp.http_enabled` is set, since it hands session control to any
+//! client holding a dashboard token. Read-only servers expose only the
+//! read-only tools.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+use crate::mcp::McpServer;
+
+use super::AppState;
+
+pub async fn mcp_rpc(State(state): State<Arc<AppState>>, body: String) -> Response {
+    if !state.mcp_config.http_enabled {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "not_found",
+                "message": "MCP endpoint is disabled (set mcp.http_enabled = true)"
+            })),
+        )
+            .into_response();
+    }
+
+    let mut server = McpServer::new(&state.profile, &state.mcp_config);
+    if state.read_only {
+        server = server.read_only();
+    }
+    match server.handle_text(&body).await {
+        Some(reply) => (StatusCode::OK, Json(reply)).into_response(),
+        // Notifications: MCP's streamable HTTP transport answers 202.
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
@@ -5,6 +5,9 @@
 //!   - `git`      — repo cloning and branch listing
 //!   - `system`   — agents, settings, themes, profiles, filesystem,
 //!     groups, docker, about, devices
+//!   - `mcp`      — Model Context Protocol endpoint for agents
+//!   - `search`   — full-text search over session transcripts
+//!   - `reviews`  — diff review comments and sending them to the agent
 //!   - this file  — shared validation helpers + module declarations and
 //!     re-exports so external callers keep `api::*` paths.
 
@@ -16,29 +19,39 @@ mod client_log;
 mod cockpit;
 mod git;
 mod log_level;
+mod mcp;
+mod openapi;
 mod projects;
+mod reviews;
+mod search;
 mod sessions;
 mod system;
 
 #[cfg(feature = "serve")]
 pub use cockpit::{
-    cockpit_cancel, cockpit_context_primer, cockpit_disable, cockpit_enable, cockpit_files,
-    cockpit_force_end_turn, cockpit_prompt, cockpit_replay, cockpit_set_config_option,
-    cockpit_set_mode, cockpit_worker_log, list_cockpit_agents, resolve_approval,
-    set_cockpit_master, shutdown_cockpit, spawn_cockpit, switch_cockpit_agent,
+    cockpit_cancel, cockpit_checkpoints, cockpit_context_primer, cockpit_disable, cockpit_enable,
+    cockpit_files, cockpit_force_end_turn, cockpit_gc, cockpit_handoff, cockpit_prompt,
+    cockpit_replay, cockpit_rewind, cockpit_set_config_option, cockpit_set_mode, cockpit_usage,
+    cockpit_worker_log, list_cockpit_agents, resolve_approval, set_cockpit_master,
+    shutdown_cockpit, spawn_cockpit, switch_cockpit_agent,
 };
 
 #[cfg(feature = "serve")]
 pub use client_log::post_client_log;
 pub use git::{clone_repo, list_branches};
 pub use log_level::{get_log_level, patch_log_level};
+pub use mcp::mcp_rpc;
+pub use openapi::openapi_json;
 pub use projects::{create_project, delete_project, list_projects};
+pub use reviews::{add_review_comment, delete_review_comment, list_review_comments, send_review};
+pub use search::search_transcripts;
 pub use sessions::{
     create_session, delete_session, ensure_container_terminal, ensure_session, ensure_terminal,
-    list_sessions, read_output, rename_session, send_message, session_diff_file,
-    session_diff_files, update_session_archive, update_session_diff_base,
-    update_session_notifications, update_session_pin, update_session_snooze,
-    update_workspace_ordering, CleanupDefaults, OutputQuery, SendMessageRequest, SessionResponse,
+    get_recording, list_recordings, list_sessions, read_output, rename_session, send_message,
+    session_diff_file, session_diff_files, session_timeline, update_session_archive,
+    update_session_diff_base, update_session_notes, update_session_notifications,
+    update_session_pin, update_session_snooze, update_session_tags, update_workspace_ordering,
+    CleanupDefaults, OutputQuery, SendMessageRequest, SessionResponse,
 };
 pub use system::{
     browse_filesystem, create_profile, default_profile, delete_profile, docker_status,
@@ -48,20 +61,9 @@ pub use system::{
     update_settings,
 };
 
-const SHELL_METACHARACTERS: &[char] = &[
-    ';', '&', '|', '$', '`', '(', ')', '{', '}', '<', '>', '\n', '\r', '\\', '"', '\'', '!', '#',
-    '*', '?', '[', ']', '~', '\t', '\0',
-];
-
-pub(super) fn validate_no_shell_injection(value: &str, field_name: &str) -> Result<(), String> {
-    if let Some(c) = value.chars().find(|c| SHELL_METACHARACTERS.contains(c)) {
-        return Err(format!(
-            "Invalid character '{}' in {}. Shell metacharacters are not allowed.",
-            c, field_name
-        ));
-    }
-    Ok(())
-}
+pub(super) use crate::session::builder::validate_no_shell_injection;
+#[cfg(test)]
+use crate::session::builder::SHELL_METACHARACTERS;
 
 /// Sections that PATCH /api/settings (global config) may write.
 ///
@@ -239,6 +241,8 @@ mod tests {
                     "update_session_pin",
                     "update_session_archive",
                     "update_session_snooze",
+                    "update_session_tags",
+                    "update_session_notes",
                     "update_workspace_ordering",
                 ],
             ),
@@ -280,6 +284,7 @@ mod tests {
                     "cockpit_set_config_option",
                     "resolve_approval",
                     "set_cockpit_master",
+                    "cockpit_gc",
                 ],
             ),
             (
@@ -369,6 +374,8 @@ mod tests {
                     "update_session_pin",
                     "update_session_archive",
                     "update_session_snooze",
+                    "update_session_tags",
+                    "update_session_notes",
                     "update_workspace_ordering",
                 ],
             ),
@@ -410,6 +417,7 @@ mod tests {
                     "cockpit_set_config_option",
                     "resolve_approval",
                     "set_cockpit_master",
+                    "cockpit_gc",
                 ],
             ),
             (
@@ -0,0 +1,1033 @@
+//! Generated OpenAPI 3.1 document for the dashboard's HTTP surface.
+//!
+//! Served at `GET /api/openapi.json` so orchestrators can generate typed
+//! clients instead of scraping `docs/api.md`. Schemas come from the Rust
+//! request/response types via `schemars`; the `OPERATIONS` table below
+//! maps every route registered in `server::build_router` onto those types.
+//! Handlers that build their body with `serde_json::json!` are described by
+//! the small `*Body` shapes at the bottom of this file, which exist only for
+//! documentation.
+//!
+//! The `every_registered_route_is_documented` test compares the document's
+//! paths with the routes `server::build_router` registers and fails when a
+//! route is added (or removed) without updating this table.
+
+use std::sync::OnceLock;
+
+use axum::Json;
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use super::client_log::ClientLogBatch;
+use super::cockpit::{
+    CockpitAgentInfo, FilesResponse, GcRequest, MasterStateResponse, SetConfigOptionRequest,
+    SetMasterRequest, SetModeRequest, SpawnCockpitRequest, SpawnCockpitResponse,
+    SubstrateSwitchResponse, WorkerLogQuery, WorkerLogResponse,
+};
+use super::git::{BranchInfo, BranchesQuery, CloneRepoBody};
+use super::log_level::{LogLevelResponse, LogLevelStatus, PatchRequest};
+use super::projects::{CreateProjectBody, DeleteQuery, ListQuery, ProjectResponse};
+use super::reviews::{AddReviewCommentBody, SendReviewBody, SendReviewResponse};
+use super::search::SearchParams;
+use super::sessions::{
+    CreateSessionBody, DeleteSessionBody, FileDiffQuery, ListSessionsQuery, OutputQuery,
+    RenameSessionBody, RichDiffFilesResponse, RichFileDiffResponse, SendMessageRequest,
+    SessionResponse, SessionTimelineResponse, SessionsEnvelope, UpdateArchiveBody,
+    UpdateDiffBaseBody, UpdateNotesBody, UpdateNotificationsBody, UpdatePinBody, UpdateSnoozeBody,
+    UpdateTagsBody, UpdateWorkspaceOrderingBody,
+};
+use super::system::{
+    AgentInfo, BrowseQuery, BrowseResponse, CreateProfileBody, DefaultProfileBody, DockerStatus,
+    GroupInfo, ProfileInfo, RenameProfileBody, ServerAbout, SettingsQuery, UpdateStatusResponse,
+};
+use crate::cockpit::checkpoints::Checkpoint;
+use crate::cockpit::protocol::{
+    ContextPrimerQuery, ContextPrimerResponse, HandoffRequest, HandoffResponse, PromptRequest,
+    ReplayQuery, ReplayResponse, ResolveApprovalRequest, RewindRequest, RewindResponse,
+    SwitchAgentRequest, SwitchAgentResponse,
+};
+use crate::cockpit::retention::GcReport;
+use crate::cockpit::usage::UsageReport;
+use crate::server::login::{ElevateRequest, LoginRequest};
+use crate::server::push::{EndpointBody, SubscribeBody, TestResult};
+use crate::server::DeviceInfo;
+use crate::session::recording::RecordingInfo;
+use crate::session::reviews::ReviewComment;
+use crate::session::search::SearchHit;
+use crate::tui::styles::ResolvedTheme;
+
+/// Produces the (usually `$ref`) schema for one request or response type,
+/// registering any named definitions on the generator as a side effect.
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+/// One documented `(method, path)` pair.
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    query: Option<SchemaFn>,
+    body: Option<SchemaFn>,
+    status: u16,
+    response: Option<SchemaFn>,
+    content_type: &'static str,
+}
+
+impl Operation {
+    const fn new(
+        method: &'static str,
+        path: &'static str,
+        tag: &'static str,
+        summary: &'static str,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            tag,
+            summary,
+            query: None,
+            body: None,
+            status: 200,
+            response: None,
+            content_type: "application/json",
+        }
+    }
+
+    const fn query(mut self, query: SchemaFn) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    const fn body(mut self, body: SchemaFn) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    const fn returns(mut self, status: u16, response: SchemaFn) -> Self {
+        self.status = status;
+        self.response = Some(response);
+        self
+    }
+
+    /// Success without a response body (`202 Accepted`, `204 No Content`).
+    const fn empty(mut self, status: u16) -> Self {
+        self.status = status;
+        self.response = None;
+        self
+    }
+
+    const fn raw(mut self, content_type: &'static str) -> Self {
+        self.content_type = content_type;
+        self
+    }
+}
+
+const fn get(path: &'static str, tag: &'static str, summary: &'static str) -> Operation {
+    Operation::new("get", path, tag, summary)
+}
+
+const fn post(path: &'static str, tag: &'static str, summary: &'static str) -> Operation {
+    Operation::new("post", path, tag, summary)
+}
+
+const fn put(path: &'static str, tag: &'static str, summary: &'static str) -> Operation {
+    Operation::new("put", path, tag, summary)
+}
+
+const fn patch(path: &'static str, tag: &'static str, summary: &'static str) -> Operation {
+    Operation::new("patch", path, tag, summary)
+}
+
+const fn delete(path: &'static str, tag: &'static str, summary: &'static str) -> Operation {
+    Operation::new("delete", path, tag, summary)
+}
+
+/// WebSocket upgrade endpoints. Documented so clients can discover them;
+/// the frame format is not expressible in OpenAPI.
+const fn websocket(path: &'static str, summary: &'static str) -> Operation {
+    Operation::new("get", path, "websocket", summary).empty(101)
+}
+
+static OPERATIONS: &[Operation] = &[
+    // Sessions
+    get(
+        "/api/sessions",
+        "sessions",
+        "List sessions with the persisted workspace ordering",
+    )
+    .query(schema::<ListSessionsQuery>)
+    .returns(200, schema::<SessionsEnvelope>),
+    post("/api/sessions", "sessions", "Create a session")
+        .body(schema::<CreateSessionBody>)
+        .returns(201, schema::<SessionResponse>),
+    put(
+        "/api/workspace-ordering",
+        "sessions",
+        "Persist the sidebar workspace order",
+    )
+    .body(schema::<UpdateWorkspaceOrderingBody>)
+    .returns(200, schema::<WorkspaceOrderingBody>),
+    patch("/api/sessions/{id}", "sessions", "Rename a session")
+        .body(schema::<RenameSessionBody>)
+        .returns(200, schema::<SessionResponse>),
+    delete(
+        "/api/sessions/{id}",
+        "sessions",
+        "Delete a session and its managed resources",
+    )
+    .body(schema::<DeleteSessionBody>)
+    .returns(200, schema::<DeletedBody>),
+    get(
+        "/api/sessions/{id}/diff/files",
+        "sessions",
+        "List changed files against the diff base",
+    )
+    .returns(200, schema::<RichDiffFilesResponse>),
+    get(
+        "/api/sessions/{id}/diff/file",
+        "sessions",
+        "Structured diff for one file",
+    )
+    .query(schema::<FileDiffQuery>)
+    .returns(200, schema::<RichFileDiffResponse>),
+    get(
+        "/api/sessions/{id}/review-comments",
+        "sessions",
+        "List pending diff review comments",
+    )
+    .returns(200, schema::<Vec<ReviewComment>>),
+    post(
+        "/api/sessions/{id}/review-comments",
+        "sessions",
+        "Add a line-anchored review comment",
+    )
+    .body(schema::<AddReviewCommentBody>)
+    .returns(201, schema::<ReviewComment>),
+    post(
+        "/api/sessions/{id}/review-comments/send",
+        "sessions",
+        "Send pending review comments to the agent as one message",
+    )
+    .body(schema::<SendReviewBody>)
+    .returns(200, schema::<SendReviewResponse>),
+    delete(
+        "/api/sessions/{id}/review-comments/{comment_id}",
+        "sessions",
+        "Delete a review comment",
+    )
+    .empty(204),
+    post(
+        "/api/sessions/{id}/ensure",
+        "sessions",
+        "Restart the agent if its pane is gone",
+    )
+    .returns(200, schema::<EnsureBody>),
+    post(
+        "/api/sessions/{id}/send",
+        "sessions",
+        "Type a message into the agent",
+    )
+    .body(schema::<SendMessageRequest>)
+    .returns(200, schema::<SentBody>),
+    get(
+        "/api/sessions/{id}/output",
+        "sessions",
+        "Capture the agent pane",
+    )
+    .query(schema::<OutputQuery>)
+    .returns(200, schema::<OutputBody>),
+    get(
+        "/api/sessions/{id}/recordings",
+        "sessions",
+        "List terminal recordings",
+    )
+    .returns(200, schema::<Vec<RecordingInfo>>),
+    get(
+        "/api/sessions/{id}/recordings/{name}",
+        "sessions",
+        "Download a recording as asciicast v2",
+    )
+    .raw("application/x-asciicast"),
+    get(
+        "/api/sessions/{id}/timeline",
+        "sessions",
+        "Status history with waiting time, turns, and time to first idle",
+    )
+    .returns(200, schema::<SessionTimelineResponse>),
+    get(
+        "/api/search",
+        "sessions",
+        "Full-text search across session transcripts",
+    )
+    .query(schema::<SearchParams>)
+    .returns(200, schema::<Vec<SearchHit>>),
+    patch(
+        "/api/sessions/{id}/notifications",
+        "sessions",
+        "Set per-session push overrides",
+    )
+    .body(schema::<UpdateNotificationsBody>)
+    .returns(200, schema::<SessionResponse>),
+    patch(
+        "/api/sessions/{id}/diff-base",
+        "sessions",
+        "Override the diff base ref",
+    )
+    .body(schema::<UpdateDiffBaseBody>)
+    .returns(200, schema::<SessionResponse>),
+    patch(
+        "/api/sessions/{id}/pin",
+        "sessions",
+        "Pin or unpin a session",
+    )
+    .body(schema::<UpdatePinBody>)
+    .returns(200, schema::<SessionResponse>),
+    patch(
+        "/api/sessions/{id}/tags",
+        "sessions",
+        "Add or remove session tags",
+    )
+    .body(schema::<UpdateTagsBody>)
+    .returns(200, schema::<SessionResponse>),
+    put(
+        "/api/sessions/{id}/notes",
+        "sessions",
+        "Replace a session's notes (blank text clears them)",
+    )
+    .body(schema::<UpdateNotesBody>)
+    .returns(200, schema::<SessionResponse>),
+    patch(
+        "/api/sessions/{id}/archive",
+        "sessions",
+        "Archive or unarchive a session",
+    )
+    .body(schema::<UpdateArchiveBody>)
+    .returns(200, schema::<SessionResponse>),
+    patch(
+        "/api/sessions/{id}/snooze",
+        "sessions",
+        "Snooze or wake a session",
+    )
+    .body(schema::<UpdateSnoozeBody>)
+    .returns(200, schema::<SessionResponse>),
+    post(
+        "/api/sessions/{id}/terminal",
+        "sessions",
+        "Ensure the paired host terminal exists",
+    )
+    .returns(200, schema::<StatusBody>),
+    post(
+        "/api/sessions/{id}/container-terminal",
+        "sessions",
+        "Ensure the paired container terminal exists",
+    )
+    .returns(200, schema::<StatusBody>),
+    // Cockpit
+    post(
+        "/api/sessions/{id}/cockpit/spawn",
+        "cockpit",
+        "Spawn the ACP worker",
+    )
+    .body(schema::<SpawnCockpitRequest>)
+    .returns(200, schema::<SpawnCockpitResponse>),
+    delete(
+        "/api/sessions/{id}/cockpit",
+        "cockpit",
+        "Shut down the ACP worker",
+    )
+    .empty(204),
+    post(
+        "/api/sessions/{id}/cockpit/switch-agent",
+        "cockpit",
+        "Switch the ACP agent",
+    )
+    .body(schema::<SwitchAgentRequest>)
+    .returns(200, schema::<SwitchAgentResponse>),
+    post(
+        "/api/sessions/{id}/cockpit/handoff",
+        "cockpit",
+        "Hand the session to another agent with a recap",
+    )
+    .body(schema::<HandoffRequest>)
+    .returns(200, schema::<HandoffResponse>),
+    post(
+        "/api/sessions/{id}/cockpit/prompt",
+        "cockpit",
+        "Send a prompt",
+    )
+    .body(schema::<PromptRequest>)
+    .empty(202),
+    post(
+        "/api/sessions/{id}/cockpit/cancel",
+        "cockpit",
+        "Cancel the running turn",
+    )
+    .empty(202),
+    post(
+        "/api/sessions/{id}/cockpit/force_end_turn",
+        "cockpit",
+        "Force-end a stuck turn",
+    )
+    .empty(202),
+    get(
+        "/api/sessions/{id}/cockpit/files",
+        "cockpit",
+        "List worktree files for mentions",
+    )
+    .returns(200, schema::<FilesResponse>),
+    get(
+        "/api/sessions/{id}/cockpit/worker-log",
+        "cockpit",
+        "Tail the worker stderr log",
+    )
+    .query(schema::<WorkerLogQuery>)
+    .returns(200, schema::<WorkerLogResponse>),
+    get(
+        "/api/sessions/{id}/cockpit/replay",
+        "cockpit",
+        "Replay events newer than a seq",
+    )
+    .query(schema::<ReplayQuery>)
+    .returns(200, schema::<ReplayResponse>),
+    get(
+        "/api/sessions/{id}/cockpit/context-primer",
+        "cockpit",
+        "Render a transcript recap",
+    )
+    .query(schema::<ContextPrimerQuery>)
+    .returns(200, schema::<ContextPrimerResponse>),
+    get(
+        "/api/sessions/{id}/cockpit/checkpoints",
+        "cockpit",
+        "List turn checkpoints",
+    )
+    .returns(200, schema::<Vec<Checkpoint>>),
+    post(
+        "/api/sessions/{id}/cockpit/rewind",
+        "cockpit",
+        "Rewind files and conversation to a turn",
+    )
+    .body(schema::<RewindRequest>)
+    .returns(200, schema::<RewindResponse>),
+    post(
+        "/api/sessions/{id}/cockpit/mode",
+        "cockpit",
+        "Set the agent mode",
+    )
+    .body(schema::<SetModeRequest>)
+    .empty(202),
+    post(
+        "/api/sessions/{id}/cockpit/config-option",
+        "cockpit",
+        "Set an agent config option",
+    )
+    .body(schema::<SetConfigOptionRequest>)
+    .empty(202),
+    post(
+        "/api/sessions/{id}/cockpit/enable",
+        "cockpit",
+        "Switch a tmux session to cockpit",
+    )
+    .returns(200, schema::<SubstrateSwitchResponse>),
+    post(
+        "/api/sessions/{id}/cockpit/disable",
+        "cockpit",
+        "Switch a cockpit session to tmux",
+    )
+    .returns(200, schema::<SubstrateSwitchResponse>),
+    post(
+        "/api/sessions/{id}/cockpit/approvals/{nonce}",
+        "cockpit",
+        "Resolve a permission request",
+    )
+    .body(schema::<ResolveApprovalRequest>)
+    .empty(204),
+    patch(
+        "/api/cockpit/master",
+        "cockpit",
+        "Toggle the cockpit master switch",
+    )
+    .body(schema::<SetMasterRequest>)
+    .returns(200, schema::<MasterStateResponse>),
+    get(
+        "/api/cockpit/agents",
+        "cockpit",
+        "List registered ACP agents",
+    )
+    .returns(200, schema::<Vec<CockpitAgentInfo>>),
+    get(
+        "/api/cockpit/usage",
+        "cockpit",
+        "Usage and rate limits across sessions",
+    )
+    .returns(200, schema::<UsageReport>),
+    post(
+        "/api/cockpit/gc",
+        "cockpit",
+        "Trim the cockpit event log now",
+    )
+    .body(schema::<GcRequest>)
+    .returns(200, schema::<GcReport>),
+    // Agents, profiles, projects
+    get("/api/agents", "agents", "List supported agents").returns(200, schema::<Vec<AgentInfo>>),
+    get("/api/profiles", "profiles", "List profiles").returns(200, schema::<Vec<ProfileInfo>>),
+    post("/api/profiles", "profiles", "Create a profile")
+        .body(schema::<CreateProfileBody>)
+        .returns(201, schema::<OkBody>),
+    delete("/api/profiles/{name}", "profiles", "Delete a profile").returns(200, schema::<OkBody>),
+    get(
+        "/api/profiles/{name}/settings",
+        "profiles",
+        "Read a profile's settings overrides",
+    )
+    .returns(200, schema::<Value>),
+    patch(
+        "/api/profiles/{name}/settings",
+        "profiles",
+        "Update a profile's settings overrides",
+    )
+    .body(schema::<Value>)
+    .returns(200, schema::<Value>),
+    patch(
+        "/api/profiles/{name}/rename",
+        "profiles",
+        "Rename a profile",
+    )
+    .body(schema::<RenameProfileBody>)
+    .returns(200, schema::<OkBody>),
+    patch(
+        "/api/default-profile",
+        "profiles",
+        "Set the default profile",
+    )
+    .body(schema::<DefaultProfileBody>)
+    .returns(200, schema::<OkBody>),
+    get("/api/projects", "projects", "List registered projects")
+        .query(schema::<ListQuery>)
+        .returns(200, schema::<Vec<ProjectResponse>>),
+    post("/api/projects", "projects", "Register a project")
+        .body(schema::<CreateProjectBody>)
+        .returns(201, schema::<ProjectResponse>),
+    delete("/api/projects/{name}", "projects", "Unregister a project")
+        .query(schema::<DeleteQuery>)
+        .returns(200, schema::<ProjectResponse>),
+    get("/api/groups", "projects", "List session groups").returns(200, schema::<Vec<GroupInfo>>),
+    // Filesystem and git
+    get("/api/filesystem/browse", "filesystem", "List a directory")
+        .query(schema::<BrowseQuery>)
+        .returns(200, schema::<BrowseResponse>),
+    get(
+        "/api/filesystem/home",
+        "filesystem",
+        "Resolve the home directory",
+    )
+    .returns(200, schema::<PathBody>),
+    get("/api/git/branches", "git", "List branches of a repository")
+        .query(schema::<BranchesQuery>)
+        .returns(200, schema::<Vec<BranchInfo>>),
+    post("/api/git/clone", "git", "Clone a repository")
+        .body(schema::<CloneRepoBody>)
+        .returns(201, schema::<PathBody>),
+    get(
+        "/api/docker/status",
+        "system",
+        "Container runtime availability",
+    )
+    .returns(200, schema::<DockerStatus>),
+    // Settings, themes, sounds
+    get("/api/settings", "settings", "Read the effective settings")
+        .query(schema::<SettingsQuery>)
+        .returns(200, schema::<Value>),
+    patch("/api/settings", "settings", "Update global settings")
+        .body(schema::<Value>)
+        .returns(200, schema::<Value>),
+    get("/api/themes", "settings", "List theme names").returns(200, schema::<Vec<String>>),
+    get(
+        "/api/themes/{name}",
+        "settings",
+        "Resolve a theme for the web",
+    )
+    .returns(200, schema::<ResolvedTheme>),
+    get("/api/theme/current", "settings", "Resolve the active theme")
+        .returns(200, schema::<ResolvedTheme>),
+    get("/api/sounds", "settings", "List sound names").returns(200, schema::<Vec<String>>),
+    get(
+        "/api/sounds/file/{name}",
+        "settings",
+        "Download a sound file",
+    )
+    .raw("audio/*"),
+    // Push notifications
+    get(
+        "/api/push/status",
+        "push",
+        "Whether push is enabled server-wide",
+    )
+    .returns(200, schema::<PushStatusBody>),
+    get(
+        "/api/push/vapid-public-key",
+        "push",
+        "VAPID application server key",
+    )
+    .returns(200, schema::<VapidKeyBody>),
+    post(
+        "/api/push/subscribe",
+        "push",
+        "Register a browser push subscription",
+    )
+    .body(schema::<SubscribeBody>)
+    .empty(204),
+    post(
+        "/api/push/unsubscribe",
+        "push",
+        "Remove a browser push subscription",
+    )
+    .body(schema::<EndpointBody>)
+    .empty(204),
+    post("/api/push/test", "push", "Send a test notification")
+        .body(schema::<EndpointBody>)
+        .returns(200, schema::<TestResult>),
+    // Login
+    post(
+        "/api/login",
+        "login",
+        "Exchange the passphrase for a login session",
+    )
+    .body(schema::<LoginRequest>)
+    .returns(200, schema::<OkBody>),
+    post(
+        "/api/login/elevate",
+        "login",
+        "Re-enter the passphrase for step-up access",
+    )
+    .body(schema::<ElevateRequest>)
+    .returns(200, schema::<ElevateBody>),
+    post("/api/logout", "login", "End the login session").returns(200, schema::<OkBody>),
+    get("/api/login/status", "login", "Login and elevation state")
+        .returns(200, schema::<LoginStatusBody>),
+    // System
+    get("/api/devices", "system", "Devices seen by this server")
+        .returns(200, schema::<Vec<DeviceInfo>>),
+    get("/api/about", "system", "Server version and mode").returns(200, schema::<ServerAbout>),
+    get(
+        "/api/system/update-status",
+        "system",
+        "Latest release check",
+    )
+    .returns(200, schema::<UpdateStatusResponse>),
+    get("/api/log-level", "system", "Current tracing filter")
+        .returns(200, schema::<LogLevelStatus>),
+    patch("/api/log-level", "system", "Change the tracing filter")
+        .body(schema::<PatchRequest>)
+        .returns(200, schema::<LogLevelResponse>),
+    post("/api/client-log", "system", "Forward browser log lines")
+        .body(schema::<ClientLogBatch>)
+        .empty(204),
+    post(
+        "/api/mcp",
+        "system",
+        "MCP JSON-RPC message (202 for notifications; 404 unless mcp.http_enabled)",
+    )
+    .body(schema::<Value>)
+    .returns(200, schema::<Value>),
+    get("/api/openapi.json", "system", "This document").returns(200, schema::<Value>),
+    // WebSockets
+    websocket("/sessions/{id}/ws", "Agent pane terminal stream"),
+    websocket("/sessions/{id}/terminal/ws", "Paired host terminal stream"),
+    websocket(
+        "/sessions/{id}/container-terminal/ws",
+        "Paired container terminal stream",
+    ),
+    websocket("/sessions/{id}/cockpit/ws", "Cockpit event stream"),
+];
+
+/// `GET /api/openapi.json`. Built once per process; the document only
+/// depends on compiled-in types.
+pub async fn openapi_json() -> Json<Value> {
+    static SPEC: OnceLock<Value> = OnceLock::new();
+    Json(SPEC.get_or_init(build_spec).clone())
+}
+
+fn build_spec() -> Value {
+    let mut generator = SchemaSettings::draft2020_12()
+        .with(|s| s.definitions_path = "/components/schemas".into())
+        .into_generator();
+    let error = schema::<ErrorBody>(&mut generator);
+
+    let mut paths = Map::new();
+    for op in OPERATIONS {
+        let item = paths
+            .entry(op.path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        item[op.method] = operation_json(op, &mut generator, &error);
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Agent of Empires",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "HTTP API exposed by `aoe serve`. See docs/api.md for usage notes.",
+        },
+        "security": [{ "bearer": [] }, { "cookie": [] }, { "query": [] }],
+        "paths": paths,
+        "components": {
+            "schemas": generator.take_definitions(true),
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+                "cookie": { "type": "apiKey", "in": "cookie", "name": "aoe_token" },
+                "query": { "type": "apiKey", "in": "query", "name": "token" },
+            },
+        },
+    })
+}
+
+fn operation_json(op: &Operation, generator: &mut SchemaGenerator, error: &Schema) -> Value {
+    let mut parameters: Vec<Value> = path_params(op.path)
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })
+        })
+        .collect();
+    if let Some(query) = op.query {
+        let query = query(generator);
+        parameters.extend(query_params(&query, generator));
+    }
+
+    let mut success = json!({ "description": status_description(op.status) });
+    if let Some(response) = op.response {
+        success["content"] = json!({ op.content_type: { "schema": response(generator) } });
+    } else if op.content_type != "application/json" {
+        success["content"] = json!({ op.content_type: {} });
+    }
+    let mut responses = Map::new();
+    responses.insert(op.status.to_string(), success);
+    responses.insert(
+        "default".into(),
+        json!({
+            "description": "Error",
+            "content": { "application/json": { "schema": error } },
+        }),
+    );
+
+    let mut out = json!({
+        "tags": [op.tag],
+        "summary": op.summary,
+        "operationId": operation_id(op),
+        "responses": responses,
+    });
+    if !parameters.is_empty() {
+        out["parameters"] = Value::Array(parameters);
+    }
+    if let Some(body) = op.body {
+        out["requestBody"] = json!({
+            "required": op.method != "delete",
+            "content": { "application/json": { "schema": body(generator) } },
+        });
+    }
+    out
+}
+
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|seg| seg.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+}
+
+/// Flatten a query struct's properties into OpenAPI `in: query`
+/// parameters. The struct itself stays in `components` (harmless) but
+/// callers address the individual fields.
+fn query_params(query: &Schema, generator: &SchemaGenerator) -> Vec<Value> {
+    let resolved = query
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.rsplit('/').next())
+        .and_then(|name| generator.definitions().get(name))
+        .cloned()
+        .unwrap_or_else(|| query.as_value().clone());
+    let required: Vec<&str> = resolved["required"]
+        .as_array()
+        .map(|a| a.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(properties) = resolved["properties"].as_object() else {
+        return Vec::new();
+    };
+    properties
+        .iter()
+        .map(|(name, schema)| {
+            let mut param = json!({
+                "name": name,
+                "in": "query",
+                "required": required.contains(&name.as_str()),
+                "schema": schema,
+            });
+            if let Some(description) = schema.get("description") {
+                param["description"] = description.clone();
+            }
+            param
+        })
+        .collect()
+}
+
+/// `get /api/sessions/{id}/diff/files` -> `get_api_sessions_id_diff_files`.
+fn operation_id(op: &Operation) -> String {
+    let mut id = String::from(op.method);
+    for seg in op.path.split('/').filter(|s| !s.is_empty()) {
+        id.push('_');
+        id.extend(seg.chars().filter(|c| !matches!(c, '{' | '}')).map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c
+            } else {
+                '_'
+            }
+        }));
+    }
+    id
+}
+
+fn status_description(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        _ => "Success",
+    }
+}
+
+// ── Documentation-only shapes for `json!`-built bodies ─────────────────────
+
+/// Error body. Older handlers send `{"message"}`, newer ones a stable
+/// `error` code plus a human-readable `message`.
+#[derive(Serialize, JsonSchema)]
+struct ErrorBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct OkBody {
+    ok: bool,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct StatusBody {
+    status: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct PathBody {
+    path: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct WorkspaceOrderingBody {
+    order: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct DeletedBody {
+    status: String,
+    messages: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct EnsureBody {
+    /// `alive` when the pane was running, `restarted` after a respawn.
+    status: String,
+    /// `resumed`, `restarted` or `fresh`; only set when `status` is `restarted`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resume_outcome: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stale_session_id: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct SentBody {
+    sent: bool,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct OutputBody {
+    id: String,
+    lines: u32,
+    format: String,
+    content: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct PushStatusBody {
+    enabled: bool,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct VapidKeyBody {
+    public_key: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct ElevateBody {
+    ok: bool,
+    elevated_until_secs: u64,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct LoginStatusBody {
+    required: bool,
+    authenticated: bool,
+    elevated: bool,
+    elevated_until_secs: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// `(method, path)` pairs of the API and WebSocket routes, read from
+    /// the `.route(...)` calls in `server/mod.rs`. axum can't list a
+    /// router's routes, so the source is the closest thing to asking it.
+    fn registered_routes() -> BTreeSet<(String, String)> {
+        let source = include_str!("../mod.rs");
+        let method = regex::Regex::new(r"\b(get|post|put|patch|delete)\(").unwrap();
+        let mut routes = BTreeSet::new();
+        for call in source.split(".route(").skip(1) {
+            // The call's arguments end at the paren that closes `.route(`.
+            let mut depth = 1;
+            let end = call
+                .char_indices()
+                .find_map(|(i, c)| {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    (depth == 0).then_some(i)
+                })
+                .unwrap_or(call.len());
+            let args = &call[..end];
+            let Some(path) = args.split('"').nth(1) else {
+                continue;
+            };
+            if !path.starts_with("/api/") && !path.starts_with("/sessions/") {
+                continue;
+            }
+            for m in method.captures_iter(args) {
+                routes.insert((m[1].to_string(), path.to_string()));
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn every_registered_route_is_documented() {
+        let registered = registered_routes();
+        assert!(
+            registered.len() > 50,
+            "server/mod.rs registers only {} API routes",
+            registered.len()
+        );
+        let spec = build_spec();
+        let documented: BTreeSet<(String, String)> = spec["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect();
+
+        let missing: Vec<_> = registered.difference(&documented).collect();
+        assert!(
+            missing.is_empty(),
+            "routes registered in server/mod.rs without an OpenAPI entry in \
+             server/api/openapi.rs: {missing:?}"
+        );
+        let stale: Vec<_> = documented.difference(&registered).collect();
+        assert!(
+            stale.is_empty(),
+            "OpenAPI entries for routes that server/mod.rs no longer registers: {stale:?}"
+        );
+    }
+
+    #[test]
+    fn operations_are_unique() {
+        let mut seen = BTreeSet::new();
+        for op in OPERATIONS {
+            assert!(
+                seen.insert((op.method, op.path)),
+                "duplicate OpenAPI entry for {} {}",
+                op.method,
+                op.path
+            );
+        }
+    }
+
+    fn collect_refs(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(r)) = map.get("$ref") {
+                    out.push(r.clone());
+                }
+                map.values().for_each(|v| collect_refs(v, out));
+            }
+            Value::Array(items) => items.iter().for_each(|v| collect_refs(v, out)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn every_ref_resolves_to_a_component() {
+        let spec = build_spec();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&spec, &mut refs);
+        assert!(!refs.is_empty());
+        for r in refs {
+            let name = r
+                .strip_prefix("#/components/schemas/")
+                .unwrap_or_else(|| panic!("unexpected $ref target {r}"));
+            assert!(schemas.contains_key(name), "dangling $ref {r}");
+        }
+    }
+
+    #[test]
+    fn spec_describes_typed_bodies_and_params() {
+        let spec = build_spec();
+        let send = &spec["paths"]["/api/sessions/{id}/send"]["post"];
+        assert_eq!(
+            send["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/SendMessageRequest"
+        );
+        assert_eq!(send["parameters"][0]["name"], "id");
+        assert_eq!(send["parameters"][0]["in"], "path");
+
+        let output = &spec["paths"]["/api/sessions/{id}/output"]["get"];
+        let names: Vec<&str> = output["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["id", "format", "lines"]);
+
+        let session = &spec["components"]["schemas"]["SessionResponse"];
+        assert!(session["properties"]["project_path"].is_object());
+        assert_eq!(spec["openapi"], "3.1.0");
+    }
+}
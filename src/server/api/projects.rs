@@ -9,6 +9,7 @@ use axum::{
     response::IntoResponse,
     Json,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::git::GitWorktree;
@@ -17,7 +18,7 @@ use crate::session::{Project, ProjectScope};
 
 use super::AppState;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct ProjectResponse {
     pub name: String,
     pub path: String,
@@ -34,7 +35,7 @@ impl From<Project> for ProjectResponse {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct ListQuery {
     /// Optional scope filter: "global", "profile", or omitted (= all).
     #[serde(default)]
@@ -84,7 +85,7 @@ pub async fn list_projects(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct CreateProjectBody {
     pub path: String,
     #[serde(default)]
@@ -201,7 +202,7 @@ pub async fn create_project(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct DeleteQuery {
     /// "global" (default) or "profile".
     #[serde(default)]
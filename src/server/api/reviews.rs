@@ -0,0 +1,284 @@
+//! Review comments on a session's diff. The store lives in
+//! `session::reviews` and is shared with the TUI diff view; sending
+//! delivers every pending comment as one message through the same paths
+//! as `/send` (tmux agents) and `/cockpit/prompt` (cockpit sessions),
+//! then clears them.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::session::reviews::{self, DiffSide, ReviewComment};
+
+use super::cockpit::read_only_block;
+use super::AppState;
+
+/// `POST /api/sessions/{id}/review-comments` body. Same shape as the web
+/// client's `DiffCommentDraft`; the server fills in `id` and `createdAt`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddReviewCommentBody {
+    /// Workspace member the file belongs to. Omit for single-repo sessions.
+    #[serde(default)]
+    pub repo_name: Option<String>,
+    pub file_path: String,
+    pub side: DiffSide,
+    /// Inclusive, 1-based, on `side`.
+    pub start_line: usize,
+    /// Inclusive; defaults to `startLine`.
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    pub body: String,
+    /// The code being commented on, quoted in the message sent to the agent.
+    #[serde(default)]
+    pub captured_snippet: String,
+    /// Code-fence language; guessed from the file extension when omitted.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema, Default)]
+pub struct SendReviewBody {
+    /// Text placed before the comments.
+    #[serde(default)]
+    pub intro: String,
+    /// Text placed after the comments. Defaults to "Please address these
+    /// comments."
+    #[serde(default)]
+    pub outro: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct SendReviewResponse {
+    /// Number of comments delivered (and cleared).
+    pub sent: usize,
+    /// Set when the agent had to be restarted without its history; see
+    /// `POST /api/sessions/{id}/send`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_session_id: Option<String>,
+}
+
+fn error(status: StatusCode, error: &str, message: String) -> axum::response::Response {
+    (
+        status,
+        Json(serde_json::json!({ "error": error, "message": message })),
+    )
+        .into_response()
+}
+
+/// The session's profile, whether it runs in cockpit mode, and whether it
+/// is a multi-repo workspace.
+async fn session_info(
+    state: &AppState,
+    id: &str,
+) -> Result<(String, bool, bool), axum::response::Response> {
+    let instances = state.instances.read().await;
+    instances
+        .iter()
+        .find(|i| i.id == id)
+        .map(|i| {
+            (
+                i.source_profile.clone(),
+                i.is_cockpit_mode(),
+                i.workspace_info.is_some(),
+            )
+        })
+        .ok_or_else(|| {
+            error(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "Session not found".into(),
+            )
+        })
+}
+
+/// Run a blocking store operation, mapping its error to a 500.
+async fn with_store<T: Send + 'static>(
+    f: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> Result<T, axum::response::Response> {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(Ok(v)) => Ok(v),
+        Ok(Err(e)) => Err(error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "review_store",
+            e.to_string(),
+        )),
+        Err(e) => Err(error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal",
+            e.to_string(),
+        )),
+    }
+}
+
+/// `GET /api/sessions/{id}/review-comments`: every pending comment, in
+/// review order.
+pub async fn list_review_comments(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let (profile, _, _) = match session_info(&state, &id).await {
+        Ok(info) => info,
+        Err(resp) => return resp,
+    };
+    match with_store(move || reviews::comments_for(&profile, &id)).await {
+        Ok(comments) => Json(comments).into_response(),
+        Err(resp) => resp,
+    }
+}
+
+pub async fn add_review_comment(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    req: Result<Json<AddReviewCommentBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Some(resp) = read_only_block(&state) {
+        return resp;
+    }
+    let Json(req) = match req {
+        Ok(j) => j,
+        Err(rej) => return rej.into_response(),
+    };
+    if req.body.trim().is_empty() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "comment_empty",
+            "Comment is empty".into(),
+        );
+    }
+    if req.start_line == 0 || req.end_line == Some(0) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "bad_range",
+            "Line numbers start at 1".into(),
+        );
+    }
+    let (profile, _, _) = match session_info(&state, &id).await {
+        Ok(info) => info,
+        Err(resp) => return resp,
+    };
+    let mut comment = ReviewComment::new(
+        &req.file_path,
+        req.side,
+        req.start_line,
+        req.end_line.unwrap_or(req.start_line),
+        &req.body,
+        &req.captured_snippet,
+    );
+    comment.repo_name = req.repo_name;
+    if let Some(language) = req.language {
+        comment.language = Some(language).filter(|l| !l.is_empty());
+    }
+    match with_store(move || reviews::add(&profile, &id, comment)).await {
+        Ok(comment) => (StatusCode::CREATED, Json(comment)).into_response(),
+        Err(resp) => resp,
+    }
+}
+
+pub async fn delete_review_comment(
+    State(state): State<Arc<AppState>>,
+    Path((id, comment_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    if let Some(resp) = read_only_block(&state) {
+        return resp;
+    }
+    let (profile, _, _) = match session_info(&state, &id).await {
+        Ok(info) => info,
+        Err(resp) => return resp,
+    };
+    match with_store(move || reviews::remove(&profile, &id, &comment_id)).await {
+        Ok(Some(_)) => StatusCode::NO_CONTENT.into_response(),
+        Ok(None) => error(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "Review comment not found".into(),
+        ),
+        Err(resp) => resp,
+    }
+}
+
+/// `POST /api/sessions/{id}/review-comments/send`: deliver the review as
+/// one message and clear it. Comments stay put when delivery fails, and
+/// the underlying send error is returned unchanged.
+pub async fn send_review(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    req: Option<Json<SendReviewBody>>,
+) -> impl IntoResponse {
+    if let Some(resp) = read_only_block(&state) {
+        return resp;
+    }
+    let Json(req) = req.unwrap_or_default();
+    let (profile, cockpit, multi_repo) = match session_info(&state, &id).await {
+        Ok(info) => info,
+        Err(resp) => return resp,
+    };
+    let comments = {
+        let (profile, id) = (profile.clone(), id.clone());
+        match with_store(move || reviews::comments_for(&profile, &id)).await {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        }
+    };
+    if comments.is_empty() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "no_comments",
+            "No review comments to send".into(),
+        );
+    }
+
+    let mut stale_session_id = None;
+    if cockpit {
+        let text = reviews::build_cockpit_prompt(&comments, &req.intro, &req.outro, multi_repo);
+        let resp = super::cockpit_prompt(
+            State(state.clone()),
+            Path(id.clone()),
+            Ok(Json(crate::cockpit::protocol::PromptRequest { text })),
+        )
+        .await
+        .into_response();
+        if !resp.status().is_success() {
+            return resp;
+        }
+    } else {
+        let message = reviews::build_prompt(&comments, &req.intro, &req.outro, multi_repo);
+        let resp = super::send_message(
+            State(state.clone()),
+            Path(id.clone()),
+            Ok(Json(super::SendMessageRequest {
+                message,
+                revive: true,
+            })),
+        )
+        .await
+        .into_response();
+        if !resp.status().is_success() {
+            return resp;
+        }
+        if let Ok(bytes) = axum::body::to_bytes(resp.into_body(), 64 * 1024).await {
+            stale_session_id = serde_json::from_slice::<serde_json::Value>(&bytes)
+                .ok()
+                .and_then(|v| v["stale_session_id"].as_str().map(str::to_string));
+        }
+    }
+
+    let sent = comments.len();
+    if let Err(resp) = with_store(move || reviews::remove_sent(&profile, &id, &comments)).await {
+        // Delivered but not cleared: surfacing a 500 would invite a
+        // duplicate send, so log and report success.
+        tracing::warn!(target: "http.api.reviews", "Failed to clear sent review: {:?}", resp.status());
+    }
+    Json(SendReviewResponse {
+        sent,
+        stale_session_id,
+    })
+    .into_response()
+}
@@ -0,0 +1,103 @@
+//! Full-text transcript search. Backs `GET /api/search`; the index and
+//! query syntax live in `session::search` and are shared with `aoe search`
+//! and the TUI's Ctrl+F dialog.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::session::search::{parse_since, SearchIndex, SearchQuery, DEFAULT_LIMIT};
+
+use super::AppState;
+
+/// Upper bound on `limit`, so one request can't page the whole index.
+const MAX_LIMIT: usize = 500;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SearchParams {
+    /// Words to find. `"quoted phrases"` and `prefix*` are supported;
+    /// every term must match.
+    pub q: String,
+    /// Only sessions running this agent (`claude`, `codex`, ...).
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// Relative window (`30m`, `12h`, `7d`, `2w`), date (`2026-01-31`),
+    /// or RFC 3339 timestamp.
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Maximum hits to return (default 50, max 500).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+fn bad_request(error: &str, message: String) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({ "error": error, "message": message })),
+    )
+        .into_response()
+}
+
+#[tracing::instrument(target = "http.api.search", skip_all)]
+pub async fn search_transcripts(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> impl IntoResponse {
+    if params.q.trim().is_empty() {
+        return bad_request("empty_query", "Query parameter 'q' is empty".into());
+    }
+    let since = match params
+        .since
+        .as_deref()
+        .map(|s| parse_since(s, chrono::Utc::now()))
+        .transpose()
+    {
+        Ok(since) => since,
+        Err(e) => return bad_request("bad_since", e.to_string()),
+    };
+    let query = SearchQuery {
+        text: params.q,
+        tool: params.tool,
+        since,
+        limit: params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+    };
+    // `state.instances` spans every profile, which is what `refresh`
+    // needs to prune deleted sessions safely.
+    let instances = state.instances.read().await.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let mut index = SearchIndex::open_default()?;
+        index.refresh(&instances)?;
+        index.search(&query, &instances)
+    })
+    .await;
+    match result {
+        Ok(Ok(hits)) => {
+            tracing::debug!(target: "http.api.search", count = hits.len(), "searched transcripts");
+            Json(hits).into_response()
+        }
+        Ok(Err(e)) => {
+            tracing::error!(target: "http.api.search", "search failed: {e:#}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(target: "http.api.search", "search: blocking task panicked: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal"})),
+            )
+                .into_response()
+        }
+    }
+}
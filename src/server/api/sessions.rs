@@ -8,15 +8,17 @@ use axum::{
     response::IntoResponse,
     Json,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::git::error::GitError;
+use crate::session::tags::{self, TagEdit};
 use crate::session::{EnsureReadyError, EnsureReadyOutcome, Instance, Status, Storage};
 
 use super::validate_no_shell_injection;
 use super::AppState;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct SessionResponse {
     pub id: String,
     pub title: String,
@@ -67,6 +69,18 @@ pub struct SessionResponse {
     /// #1581.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pinned_at: Option<String>,
+    /// Session tags: free-form (`blocked-ci`) or `key=value` labels
+    /// (`reviewer=alice`). Edited via `PATCH /api/sessions/{id}/tags`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Tracker issue the session was started from (`aoe add --issue`).
+    /// The dashboard links the key to `url` when one is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<crate::session::issues::IssueLink>,
+    /// Markdown notes. Stored outside `sessions.json`; edited via
+    /// `PUT /api/sessions/{id}/notes`.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub notes: String,
     /// RFC3339 timestamp at which the session was archived, or omitted
     /// when not archived. The web sidebar sinks archived workspaces into
     /// the "Snoozed & archived" collapsible section. See #1581.
@@ -85,6 +99,11 @@ pub struct SessionResponse {
     pub profile: String,
     pub cleanup_defaults: CleanupDefaults,
     pub remote_owner: Option<String>,
+    /// Coordinator session this one was spawned under, omitted for
+    /// top-level sessions. The web sidebar nests sub-sessions below
+    /// their parent and rolls their statuses up onto the parent's row.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_session_id: Option<String>,
     /// Per-session push-notification overrides. None means the session
     /// inherits the server-wide default (`web.notify_on_*`) for that
     /// event type; Some(true)/Some(false) is an explicit toggle.
@@ -141,7 +160,7 @@ pub struct SessionResponse {
     pub next_wakeup_reason: Option<String>,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, JsonSchema)]
 pub struct PlanSummary {
     /// First non-completed step's title, truncated to ~80 chars so the
     /// sidebar row doesn't overflow.
@@ -152,14 +171,14 @@ pub struct PlanSummary {
     pub total: u32,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, JsonSchema)]
 pub struct WorkspaceRepoSummary {
     pub name: String,
     pub source_path: String,
     pub branch: String,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, JsonSchema)]
 pub struct CleanupDefaults {
     pub delete_worktree: bool,
     pub delete_branch: bool,
@@ -223,6 +242,9 @@ impl SessionResponse {
             scratch: inst.scratch,
             favorited: inst.is_favorited(),
             pinned_at: inst.pinned_at.map(|t| t.to_rfc3339()),
+            tags: inst.tags.clone(),
+            issue: inst.issue.clone(),
+            notes: inst.notes.clone(),
             archived_at: inst.archived_at.map(|t| t.to_rfc3339()),
             // Surface `snoozed_until` only when the snooze is still
             // active. `is_snoozed()` returns false once the timestamp
@@ -248,6 +270,7 @@ impl SessionResponse {
                 delete_sandbox: true,
             },
             remote_owner: None,
+            parent_session_id: inst.parent_session_id.clone(),
             notify_on_waiting: inst.notify_on_waiting,
             notify_on_idle: inst.notify_on_idle,
             notify_on_error: inst.notify_on_error,
@@ -315,21 +338,57 @@ fn truncate_title(s: &str, max: usize) -> String {
 // sidebar in the requested order on the first paint, with no extra
 // round-trip. The order is a list of workspace ids; ids not present
 // fall back to the client's default newest-first ordering. See #1169.
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, JsonSchema)]
 pub struct SessionsEnvelope {
     pub sessions: Vec<SessionResponse>,
     pub workspace_ordering: Vec<String>,
 }
 
-pub async fn list_sessions(State(state): State<Arc<AppState>>) -> Json<SessionsEnvelope> {
+#[derive(Deserialize, JsonSchema)]
+pub struct ListSessionsQuery {
+    /// Filter query (see `aoe list --filter`), e.g.
+    /// `status:waiting tool:claude age>1h` or `@view`.
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+pub async fn list_sessions(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Query(query): axum::extract::Query<ListSessionsQuery>,
+) -> impl IntoResponse {
+    let filter = match query.filter.as_deref().filter(|q| !q.trim().is_empty()) {
+        Some(q) => {
+            let views = crate::session::load_config()
+                .ok()
+                .flatten()
+                .map(|c| c.views)
+                .unwrap_or_default();
+            match crate::session::filter::Filter::parse_with_views(q, &views) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(serde_json::json!({
+                            "error": "bad_filter",
+                            "message": e.to_string(),
+                        })),
+                    )
+                        .into_response();
+                }
+            }
+        }
+        None => crate::session::filter::Filter::default(),
+    };
     let instances = state.instances.read().await;
     let claude_fullscreen = crate::claude_settings::read_tui_fullscreen();
     // Snapshot the supervisor's worker lifecycle map once per request
     // rather than locking it per row. See #1088.
     #[cfg(feature = "serve")]
     let worker_states = state.cockpit_supervisor.worker_states_snapshot().await;
+    let now = chrono::Utc::now();
     let mut sessions: Vec<SessionResponse> = instances
         .iter()
+        .filter(|inst| filter.matches_at(inst, now))
         .map(|inst| {
             let plan_summary = if inst.cockpit_mode {
                 state
@@ -457,6 +516,7 @@ pub async fn list_sessions(State(state): State<Arc<AppState>>) -> Json<SessionsE
         sessions,
         workspace_ordering,
     })
+    .into_response()
 }
 
 // Workspace id derivation. Mirrors the client logic in `useWorkspaces.ts`:
@@ -542,7 +602,7 @@ fn merge_workspace_ordering(
 const MAX_ORDER_ENTRIES: usize = 4096;
 const MAX_ORDER_ENTRY_LEN: usize = 1024;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct UpdateWorkspaceOrderingBody {
     pub order: Vec<String>,
 }
@@ -607,7 +667,7 @@ pub async fn update_workspace_ordering(
 
 // --- Rename session ---
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct RenameSessionBody {
     pub title: String,
 }
@@ -700,13 +760,16 @@ pub async fn rename_session(
 /// override." We represent that as an untagged enum below so the
 /// caller can send `{"notify_on_idle": true}`, `{"notify_on_idle": false}`,
 /// or `{"notify_on_idle": null}` and each means what you'd expect.
-#[derive(Deserialize, Default)]
+#[derive(Deserialize, Default, JsonSchema)]
 pub struct UpdateNotificationsBody {
     #[serde(default, deserialize_with = "deserialize_tristate")]
+    #[schemars(with = "Option<bool>")]
     pub notify_on_waiting: Tristate,
     #[serde(default, deserialize_with = "deserialize_tristate")]
+    #[schemars(with = "Option<bool>")]
     pub notify_on_idle: Tristate,
     #[serde(default, deserialize_with = "deserialize_tristate")]
+    #[schemars(with = "Option<bool>")]
     pub notify_on_error: Tristate,
 }
 
@@ -821,7 +884,7 @@ pub async fn update_session_notifications(
 // through this endpoint so the override is persisted alongside the
 // session record and survives restart. See #970.
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct UpdateDiffBaseBody {
     /// New override. `Some(non-empty)` sets the override; `Some("")` or
     /// `None` clears it (the diff then falls back to the profile default
@@ -916,12 +979,12 @@ pub async fn update_session_diff_base(
 // pin clears archive+snooze) live in the `Instance` methods, so the
 // handlers never set fields directly. See #1581.
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct UpdatePinBody {
     pub pinned: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct UpdateArchiveBody {
     pub archived: bool,
     /// When `archived = true`, kill the tmux pane (parity with the TUI's
@@ -936,7 +999,7 @@ fn default_kill_pane() -> bool {
     true
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct UpdateSnoozeBody {
     /// `Some(positive minutes)` snoozes for that duration. `None` (or a
     /// missing field) unsnoozes. Validated against
@@ -1021,6 +1084,193 @@ pub async fn update_session_pin(
     (StatusCode::OK, Json(serde_json::json!(response))).into_response()
 }
 
+/// Tags to add and remove, applied removals first so a body can swap one
+/// label value for another. Each entry is validated by
+/// `crate::session::tags::normalize`, the same check the TUI and CLI use.
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateTagsBody {
+    #[serde(default)]
+    pub add: Vec<String>,
+    /// A bare key removes the label with that key.
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+impl UpdateTagsBody {
+    fn into_edit(self) -> Result<TagEdit, String> {
+        let normalize_all = |list: Vec<String>| {
+            list.iter()
+                .map(String::as_str)
+                .map(tags::normalize)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(TagEdit {
+            add: normalize_all(self.add)?,
+            remove: normalize_all(self.remove)?,
+        })
+    }
+}
+
+pub async fn update_session_tags(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    body: Result<Json<UpdateTagsBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if state.read_only {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": "read_only",
+                "message": "Server is in read-only mode"
+            })),
+        )
+            .into_response();
+    }
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(rej) => return rej.into_response(),
+    };
+    let edit = match body.into_edit() {
+        Ok(edit) => edit,
+        Err(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "bad_tag", "message": message })),
+            )
+                .into_response();
+        }
+    };
+
+    let lock = state.instance_lock(&id).await;
+    let _guard = lock.lock().await;
+
+    let mut instances = state.instances.write().await;
+    let Some(inst) = instances.iter_mut().find(|i| i.id == id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "message": "Session not found" })),
+        )
+            .into_response();
+    };
+
+    edit.apply(&mut inst.tags);
+
+    let response =
+        SessionResponse::from_instance(&*inst, crate::claude_settings::read_tui_fullscreen());
+    let profile = inst.source_profile.clone();
+    drop(instances);
+
+    // Re-apply the edit on disk rather than writing the in-memory list, so
+    // a concurrent CLI `aoe session tag` is not overwritten.
+    if let Ok(storage) = Storage::new(&profile) {
+        let id_clone = id.clone();
+        match tokio::task::spawn_blocking(move || {
+            storage.update(|instances, _groups| {
+                if let Some(inst) = instances.iter_mut().find(|i| i.id == id_clone) {
+                    edit.apply(&mut inst.tags);
+                }
+                Ok(())
+            })
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => tracing::error!(
+                target: "http.api.sessions",
+                "Failed to save after tags update: {e}"
+            ),
+            Err(e) => tracing::error!(
+                target: "http.api.sessions",
+                "Tags persist join failed: {e}"
+            ),
+        }
+    }
+
+    (StatusCode::OK, Json(serde_json::json!(response))).into_response()
+}
+
+/// Replacement notes for a session. Blank text deletes them.
+#[derive(Deserialize, JsonSchema)]
+pub struct UpdateNotesBody {
+    pub notes: String,
+}
+
+pub async fn update_session_notes(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    body: Result<Json<UpdateNotesBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if state.read_only {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": "read_only",
+                "message": "Server is in read-only mode"
+            })),
+        )
+            .into_response();
+    }
+    let Json(body) = match body {
+        Ok(b) => b,
+        Err(rej) => return rej.into_response(),
+    };
+
+    let lock = state.instance_lock(&id).await;
+    let _guard = lock.lock().await;
+
+    let profile = {
+        let instances = state.instances.read().await;
+        match instances.iter().find(|i| i.id == id) {
+            Some(inst) => inst.source_profile.clone(),
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({ "message": "Session not found" })),
+                )
+                    .into_response();
+            }
+        }
+    };
+
+    // Notes have their own file, so there is no sessions.json write to
+    // merge with; the in-memory copy is updated once the file is saved.
+    let id_clone = id.clone();
+    let saved = match tokio::task::spawn_blocking(move || {
+        crate::session::notes::save(&profile, &id_clone, &body.notes)
+    })
+    .await
+    {
+        Ok(Ok(saved)) => saved,
+        Ok(Err(e)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "notes_not_saved", "message": e.to_string() })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({ "error": "internal", "message": e.to_string() })),
+            )
+                .into_response();
+        }
+    };
+
+    let mut instances = state.instances.write().await;
+    let Some(inst) = instances.iter_mut().find(|i| i.id == id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "message": "Session not found" })),
+        )
+            .into_response();
+    };
+    inst.notes = saved;
+    let response =
+        SessionResponse::from_instance(&*inst, crate::claude_settings::read_tui_fullscreen());
+    (StatusCode::OK, Json(serde_json::json!(response))).into_response()
+}
+
 pub async fn update_session_archive(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -1325,7 +1575,7 @@ pub async fn update_session_snooze(
 
 // --- Delete session ---
 
-#[derive(Default, Deserialize)]
+#[derive(Default, Deserialize, JsonSchema)]
 pub struct DeleteSessionBody {
     #[serde(default)]
     pub delete_worktree: bool,
@@ -1542,7 +1792,7 @@ pub async fn delete_session(
 
 // --- Create session ---
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct CreateSessionBody {
     pub title: Option<String>,
     pub path: String,
@@ -2005,8 +2255,6 @@ pub async fn create_session(
                             return;
                         }
                     };
-                    let source_profile_for_spawn =
-                        sandbox_info.as_ref().map(|_| source_profile.clone());
                     if let Err(e) = supervisor
                         .spawn(crate::cockpit::supervisor::SpawnRequest {
                             session_id: id.clone(),
@@ -2017,7 +2265,7 @@ pub async fn create_session(
                             model,
                             stored_acp_session_id,
                             sandbox_info,
-                            source_profile: source_profile_for_spawn,
+                            source_profile,
                             yolo_mode,
                         })
                         .await
@@ -2092,6 +2340,10 @@ fn public_create_session_error(e: &anyhow::Error) -> String {
             // Raw command output / libgit2 / IO: not safe to expose.
             GitError::WorktreeCommandFailed(_)
             | GitError::CloneFailed(_)
+            | GitError::CommitFailed(_)
+            | GitError::MergeFailed(_)
+            | GitError::ApplyFailed(_)
+            | GitError::StageFailed(_)
             | GitError::WorktreeNotFound(_)
             | GitError::Git2Error(_)
             | GitError::IoError(_) => {}
@@ -2538,7 +2790,7 @@ pub async fn ensure_container_terminal(
 
 // --- Rich Diff (per-file, merge-base aware) ---
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct RichDiffFileInfo {
     pub path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2554,7 +2806,7 @@ pub struct RichDiffFileInfo {
     pub repo_name: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct RepoBase {
     /// None for single-repo sessions; Some for each workspace member.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2562,7 +2814,7 @@ pub struct RepoBase {
     pub base_branch: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct RichDiffFilesResponse {
     pub files: Vec<RichDiffFileInfo>,
     /// One entry per repo whose diff was computed. Single-repo
@@ -2575,7 +2827,7 @@ pub struct RichDiffFilesResponse {
     pub warning: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct RichDiffLine {
     #[serde(rename = "type")]
     pub change_type: String,
@@ -2584,7 +2836,7 @@ pub struct RichDiffLine {
     pub content: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct RichDiffHunk {
     pub old_start: usize,
     pub old_lines: usize,
@@ -2593,13 +2845,16 @@ pub struct RichDiffHunk {
     pub lines: Vec<RichDiffLine>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct RichFileDiffResponse {
     pub file: RichDiffFileInfo,
     pub hunks: Vec<RichDiffHunk>,
     pub is_binary: bool,
     /// True if the file was too large to diff and hunks were omitted.
     pub truncated: bool,
+    /// Pending review comments on this file (see
+    /// `/api/sessions/{id}/review-comments`), in review order.
+    pub comments: Vec<crate::session::reviews::ReviewComment>,
 }
 
 /// Max combined bytes of old+new content before we bail on diffing.
@@ -2683,6 +2938,8 @@ struct DiffContext {
     /// CLI, or the TUI diff view's `b` keybind). Wins over the
     /// profile-level default and the auto-detected ref. See #970.
     base_branch_override: Option<String>,
+    /// Profile whose `reviews.json` holds the session's review comments
+    profile: String,
 }
 
 /// Expand a session into the list of repos whose diffs the sidebar
@@ -2719,26 +2976,10 @@ async fn resolve_diff_repos(
     Ok(DiffContext {
         repos,
         base_branch_override: inst.base_branch_override.clone(),
+        profile: inst.source_profile.clone(),
     })
 }
 
-/// Resolve the diff base for one repo path. Override (per-session)
-/// wins over the profile's `DiffConfig.default_branch`, which wins
-/// over auto-detection (`get_default_base_ref`). See #970.
-fn resolve_diff_base(
-    override_value: Option<&str>,
-    config_default: Option<&str>,
-    repo_path: &std::path::Path,
-) -> String {
-    if let Some(v) = override_value.map(str::trim).filter(|v| !v.is_empty()) {
-        return v.to_string();
-    }
-    if let Some(v) = config_default.map(str::trim).filter(|v| !v.is_empty()) {
-        return v.to_string();
-    }
-    crate::git::diff::get_default_base_ref(repo_path).unwrap_or_else(|_| "main".to_string())
-}
-
 pub async fn session_diff_files(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
@@ -2761,7 +3002,7 @@ pub async fn session_diff_files(
 
         for repo in &ctx.repos {
             let path = std::path::Path::new(&repo.path);
-            let base_branch = resolve_diff_base(
+            let base_branch = diff::resolve_diff_base(
                 ctx.base_branch_override.as_deref(),
                 config_default.as_deref(),
                 path,
@@ -2820,7 +3061,7 @@ pub async fn session_diff_files(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct FileDiffQuery {
     pub path: String,
     /// Workspace repo name when the session is a multi-repo workspace.
@@ -2876,6 +3117,7 @@ pub async fn session_diff_file(
     let project_path = selected_repo.path;
     let selected_repo_name = selected_repo.name;
     let base_branch_override = ctx.base_branch_override.clone();
+    let profile = ctx.profile.clone();
 
     let result =
         tokio::task::spawn_blocking(move || -> Result<RichFileDiffResponse, DiffFileError> {
@@ -2889,7 +3131,7 @@ pub async fn session_diff_file(
                 .diff
                 .default_branch
                 .clone();
-            let base_branch = resolve_diff_base(
+            let base_branch = diff::resolve_diff_base(
                 base_branch_override.as_deref(),
                 config_default.as_deref(),
                 repo_path,
@@ -2926,6 +3168,16 @@ pub async fn session_diff_file(
                 repo_name: selected_repo_name.clone(),
             };
 
+            // A broken reviews.json shouldn't take the diff down with it.
+            let comments = crate::session::reviews::comments_for(&profile, &id)
+                .unwrap_or_else(|e| {
+                    tracing::warn!(target: "http.api.sessions", "Failed to load review comments: {e}");
+                    Vec::new()
+                })
+                .into_iter()
+                .filter(|c| c.file_path == file.path && c.repo_name == file.repo_name)
+                .collect();
+
             // Size cap: avoid OOM'ing the browser on huge files (minified bundles,
             // generated code, data blobs that slipped past .gitignore).
             let total_line_count: usize = file_diff.hunks.iter().map(|h| h.lines.len()).sum();
@@ -2941,6 +3193,7 @@ pub async fn session_diff_file(
                     hunks: Vec::new(),
                     is_binary: file_diff.is_binary,
                     truncated: true,
+                    comments,
                 });
             }
 
@@ -2974,6 +3227,7 @@ pub async fn session_diff_file(
                 hunks,
                 is_binary: file_diff.is_binary,
                 truncated: false,
+                comments,
             })
         })
         .await;
@@ -3148,29 +3402,6 @@ mod tests {
         assert_eq!(resp.base_branch_override.as_deref(), Some("upstream/main"));
     }
 
-    #[test]
-    fn resolve_diff_base_prefers_override_then_config_then_auto() {
-        let tmp = tempfile::tempdir().unwrap();
-        // Override wins over everything.
-        assert_eq!(
-            resolve_diff_base(Some("release-1.2"), Some("develop"), tmp.path()),
-            "release-1.2"
-        );
-        // Config wins when no override; empty / whitespace override falls
-        // through to the next layer.
-        assert_eq!(
-            resolve_diff_base(Some("   "), Some("develop"), tmp.path()),
-            "develop"
-        );
-        assert_eq!(
-            resolve_diff_base(None, Some("develop"), tmp.path()),
-            "develop"
-        );
-        // Auto-detect when neither is set. The tmp dir is not a repo so
-        // `get_default_base_ref` returns Err -> "main" fallback.
-        assert_eq!(resolve_diff_base(None, None, tmp.path()), "main");
-    }
-
     #[test]
     fn session_response_surfaces_base_branch_when_set() {
         let mut inst = make_test_instance();
@@ -3304,6 +3535,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn update_tags_body_normalizes_and_rejects_bad_tags() {
+        let body: UpdateTagsBody =
+            serde_json::from_str(r#"{"add": ["reviewer = alice"]}"#).unwrap();
+        let edit = body.into_edit().unwrap();
+        assert_eq!(edit.add, vec!["reviewer=alice"]);
+        assert!(edit.remove.is_empty());
+        let body: UpdateTagsBody = serde_json::from_str(r#"{"remove": ["a,b"]}"#).unwrap();
+        assert!(body.into_edit().is_err());
+    }
+
     #[test]
     fn update_pin_body_parses() {
         let body: UpdatePinBody = serde_json::from_str(r#"{"pinned": true}"#).unwrap();
@@ -3865,7 +4107,7 @@ mod tests {
 // without requiring keyboard or websocket attach.
 // ============================================================================
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct SendMessageRequest {
     pub message: String,
     /// Whether to auto-revive a dead/stopped session before sending. Defaults
@@ -4136,7 +4378,7 @@ pub async fn send_message(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct OutputQuery {
     #[serde(default = "default_output_lines")]
     pub lines: u32,
@@ -4239,6 +4481,154 @@ pub async fn read_output(
     }
 }
 
+/// GET /api/sessions/{id}/recordings: the session's terminal recordings,
+/// newest first. Empty when recording is off or nothing was recorded yet.
+pub async fn list_recordings(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if !state.instances.read().await.iter().any(|i| i.id == id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not_found"})),
+        )
+            .into_response();
+    }
+    let lookup_id = id.clone();
+    match tokio::task::spawn_blocking(move || {
+        crate::session::recording::list_recordings(&lookup_id)
+    })
+    .await
+    {
+        Ok(Ok(recordings)) => (StatusCode::OK, Json(recordings)).into_response(),
+        Ok(Err(e)) => {
+            tracing::error!(target: "http.api.sessions", "list_recordings failed for {id}: {e:#}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(target: "http.api.sessions", "list_recordings: blocking task panicked for {id}: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// A session's recorded status transitions plus the figures derived from
+/// them.
+#[derive(Serialize, JsonSchema)]
+pub struct SessionTimelineResponse {
+    pub id: String,
+    /// Oldest first.
+    pub entries: Vec<crate::session::timeline::TimelineEntry>,
+    pub metrics: crate::session::timeline::TimelineMetrics,
+}
+
+/// GET /api/sessions/{id}/timeline: the session's status history (when it
+/// was running, waiting on a human, idle, or errored, and whether a hook,
+/// pane detection, or the cockpit reported it).
+pub async fn session_timeline(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Some((profile, status)) = state
+        .instances
+        .read()
+        .await
+        .iter()
+        .find(|i| i.id == id)
+        .map(|i| (i.source_profile.clone(), i.status))
+    else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not_found"})),
+        )
+            .into_response();
+    };
+    let lookup_id = id.clone();
+    match tokio::task::spawn_blocking(move || crate::session::timeline::load(&profile, &lookup_id))
+        .await
+    {
+        Ok(Ok(entries)) => {
+            let metrics = crate::session::timeline::metrics(&entries, status, chrono::Utc::now());
+            (
+                StatusCode::OK,
+                Json(SessionTimelineResponse {
+                    id,
+                    entries,
+                    metrics,
+                }),
+            )
+                .into_response()
+        }
+        Ok(Err(e)) => {
+            tracing::error!(target: "http.api.sessions", "session_timeline failed for {id}: {e:#}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(target: "http.api.sessions", "session_timeline: blocking task panicked for {id}: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "internal"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// GET /api/sessions/{id}/recordings/{name}: one recording as raw
+/// asciicast v2, ready for an asciinema player.
+pub async fn get_recording(
+    State(state): State<Arc<AppState>>,
+    Path((id, name)): Path<(String, String)>,
+) -> impl IntoResponse {
+    if !state.instances.read().await.iter().any(|i| i.id == id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not_found"})),
+        )
+            .into_response();
+    }
+    let path = match tokio::task::spawn_blocking(move || {
+        crate::session::recording::resolve_recording(&id, Some(&name))
+    })
+    .await
+    {
+        Ok(Ok(path)) => path,
+        Ok(Err(_)) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "recording_not_found"})),
+            )
+                .into_response();
+        }
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    match tokio::fs::read(&path).await {
+        Ok(bytes) => (
+            StatusCode::OK,
+            [(axum::http::header::CONTENT_TYPE, "application/x-asciicast")],
+            bytes,
+        )
+            .into_response(),
+        Err(_) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "recording_not_found"})),
+        )
+            .into_response(),
+    }
+}
+
 #[cfg(test)]
 mod workspace_ordering_tests {
     use super::*;
@@ -4279,6 +4669,7 @@ mod workspace_ordering_tests {
                 delete_sandbox: false,
             },
             remote_owner: None,
+            parent_session_id: None,
             notify_on_waiting: None,
             notify_on_idle: None,
             notify_on_error: None,
@@ -4294,6 +4685,9 @@ mod workspace_ordering_tests {
             next_wakeup_reason: None,
             favorited: false,
             pinned_at: None,
+            tags: Vec::new(),
+            issue: None,
+            notes: String::new(),
             archived_at: None,
             snoozed_until: None,
         }
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::AppState;
@@ -16,7 +17,7 @@ use crate::server::auth::AuthenticatedSession;
 
 // --- Agents ---
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct AgentInfo {
     pub kind: String,
     pub name: String,
@@ -78,7 +79,7 @@ pub async fn list_agents(State(state): State<Arc<AppState>>) -> Json<Vec<AgentIn
 
 // --- Settings ---
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct SettingsQuery {
     pub profile: Option<String>,
 }
@@ -301,7 +302,7 @@ pub async fn get_current_theme(
 
 // --- Wizard support ---
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct ProfileInfo {
     pub name: String,
     pub is_default: bool,
@@ -351,14 +352,14 @@ pub async fn list_profiles(State(state): State<Arc<AppState>>) -> Json<Vec<Profi
     Json(result)
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct BrowseQuery {
     pub path: String,
     pub limit: Option<usize>,
     pub filter: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct DirEntry {
     pub name: String,
     pub path: String,
@@ -366,8 +367,8 @@ pub struct DirEntry {
     pub is_git_repo: bool,
 }
 
-#[derive(Serialize)]
-struct BrowseResponse {
+#[derive(Serialize, JsonSchema)]
+pub(super) struct BrowseResponse {
     entries: Vec<DirEntry>,
     has_more: bool,
 }
@@ -457,7 +458,7 @@ pub async fn browse_filesystem(
     }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct GroupInfo {
     pub path: String,
     pub session_count: usize,
@@ -482,7 +483,7 @@ pub async fn list_groups(State(state): State<Arc<AppState>>) -> impl IntoRespons
     Json(groups)
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct DockerStatus {
     pub available: bool,
     pub runtime: Option<String>,
@@ -517,7 +518,7 @@ pub async fn docker_status() -> Json<DockerStatus> {
     Json(result)
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct ServerAbout {
     pub version: String,
     pub auth_required: bool,
@@ -625,7 +626,7 @@ pub async fn get_about(State(state): State<Arc<AppState>>) -> Json<ServerAbout>
 /// `web_poll_interval_minutes` echoes the configured frontend re-poll cadence
 /// so the dashboard doesn't need a second settings round-trip. See #984 and
 /// #1140.
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct UpdateStatusResponse {
     pub update_check_mode: crate::session::config::UpdateCheckMode,
     pub current_version: String,
@@ -692,7 +693,7 @@ pub async fn get_update_status(State(state): State<Arc<AppState>>) -> Json<Updat
 
 // --- Profile management ---
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct CreateProfileBody {
     pub name: String,
 }
@@ -778,7 +779,7 @@ pub async fn delete_profile(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct RenameProfileBody {
     pub new_name: String,
 }
@@ -832,7 +833,7 @@ pub async fn rename_profile(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct DefaultProfileBody {
     pub name: String,
 }
@@ -345,14 +345,17 @@ async fn resume_one(state: Arc<AppState>, target: ResumeTarget) -> ResumeOutcome
                     if in_flight_turn {
                         if let Some(event) = state.cockpit_event_store.latest_status_event(&id) {
                             if let Some(intent) = crate::server::derive_cockpit_status(&event) {
-                                let mut instances = state.instances.write().await;
-                                if let Some(inst) = instances.iter_mut().find(|i| i.id == id) {
-                                    crate::server::apply_status_intent(
-                                        inst,
-                                        Some(intent),
-                                        &state.status_tx,
-                                    );
-                                }
+                                let transition = {
+                                    let mut instances = state.instances.write().await;
+                                    instances.iter_mut().find(|i| i.id == id).and_then(|inst| {
+                                        crate::server::apply_status_intent(
+                                            inst,
+                                            Some(intent),
+                                            &state.status_tx,
+                                        )
+                                    })
+                                };
+                                crate::server::record_status_transition(transition).await;
                             }
                         }
                     }
@@ -422,7 +425,6 @@ async fn resume_one(state: Arc<AppState>, target: ResumeTarget) -> ResumeOutcome
         }
     };
 
-    let source_profile_for_spawn = sandbox_info.as_ref().map(|_| source_profile.clone());
     let spawn_result = supervisor
         .spawn(crate::cockpit::supervisor::SpawnRequest {
             session_id: id.clone(),
@@ -433,7 +435,7 @@ async fn resume_one(state: Arc<AppState>, target: ResumeTarget) -> ResumeOutcome
             model,
             stored_acp_session_id,
             sandbox_info,
-            source_profile: source_profile_for_spawn,
+            source_profile,
             yolo_mode,
         })
         .await;
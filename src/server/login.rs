@@ -26,6 +26,7 @@ use axum::extract::{FromRequest, State};
 use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
 use subtle::ConstantTimeEq;
@@ -397,7 +398,7 @@ pub fn check_passphrase_strength(passphrase: &str) -> Option<String> {
 
 // ── Handlers ───────────────────────────────────────────────────────────────
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct LoginRequest {
     passphrase: String,
     /// Base64url encoding of 32 random bytes the client persists in
@@ -533,7 +534,7 @@ pub async fn login_handler(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct ElevateRequest {
     passphrase: String,
 }
@@ -23,6 +23,7 @@ use std::time::Duration;
 use anyhow::Context;
 use axum::Router;
 use rust_embed::Embed;
+use schemars::JsonSchema;
 use serde::Serialize;
 use tokio::sync::{broadcast, RwLock};
 use tokio_util::sync::CancellationToken;
@@ -53,7 +54,7 @@ struct StaticAssets;
 // ── DeviceInfo ──────────────────────────────────────────────────────────────
 
 /// A device that has connected to the dashboard.
-#[derive(Clone, Serialize)]
+#[derive(Clone, Serialize, JsonSchema)]
 pub struct DeviceInfo {
     pub ip: String,
     pub user_agent: String,
@@ -288,6 +289,10 @@ pub struct AppState {
     /// Snapshot of the resolved WebConfig at startup. Consumed by the
     /// push consumer task to evaluate per-event-type defaults.
     pub web_config: crate::session::config::WebConfig,
+    /// Snapshot of `[mcp]` at startup. `mcp.http_enabled` decides whether
+    /// `POST /api/mcp` answers at all; the allowlist seeds each request's
+    /// `McpServer`. Changes require a server restart.
+    pub mcp_config: crate::session::McpConfig,
     /// Broadcasts cockpit events to subscribed WebSocket clients. The
     /// channel carries `(session_id, serialized event JSON)` frames so
     /// clients can filter by session. Empty when no clients are
@@ -461,7 +466,8 @@ pub async fn start_server(config: ServerConfig<'_>) -> anyhow::Result<()> {
 
     raise_fd_limit();
 
-    let instances = load_all_instances()?;
+    let mut instances = load_all_instances()?;
+    crate::session::notes::hydrate(&mut instances);
 
     // Load or generate auth token
     let auth_token = if no_auth {
@@ -538,14 +544,13 @@ pub async fn start_server(config: ServerConfig<'_>) -> anyhow::Result<()> {
     let cockpit_event_store = {
         let app_dir =
             crate::session::get_app_dir().context("cockpit event store: resolve app dir")?;
-        let db_path = app_dir.join("cockpit_events.db");
-        Arc::new(
-            crate::cockpit::event_store::EventStore::open(
-                &db_path,
-                config.cockpit.replay_events as usize,
-            )
-            .context("cockpit event store: open")?,
+        let db_path = app_dir.join(crate::session::transcript::COCKPIT_EVENTS_DB);
+        let store = crate::cockpit::event_store::EventStore::open(
+            &db_path,
+            config.cockpit.replay_events as usize,
         )
+        .context("cockpit event store: open")?;
+        Arc::new(store)
     };
     #[cfg(feature = "serve")]
     let cockpit_supervisor = {
@@ -603,6 +608,7 @@ pub async fn start_server(config: ServerConfig<'_>) -> anyhow::Result<()> {
         push: push_state,
         push_enabled,
         web_config: config.web.clone(),
+        mcp_config: config.mcp.clone(),
         last_web_activity: std::sync::atomic::AtomicI64::new(0),
         shutdown: CancellationToken::new(),
     });
@@ -879,6 +885,31 @@ pub async fn start_server(config: ServerConfig<'_>) -> anyhow::Result<()> {
     // (feature disabled via web.notifications_enabled=false).
     push::spawn_consumer(state.clone());
 
+    // Scheduled prompts (`aoe schedule`). Stands by while a foreground
+    // `aoe scheduler` holds the machine-wide scheduler lock.
+    {
+        let shutdown = state.shutdown.clone();
+        crate::task_util::spawn_supervised(
+            "server.scheduler",
+            crate::task_util::PanicPolicy::Log,
+            crate::scheduler::run(shutdown),
+        );
+    }
+
+    // Cockpit transcript compaction and `history_*` retention, plus the
+    // one-off auto-vacuum conversion of an older database. See
+    // `cockpit::retention`.
+    #[cfg(feature = "serve")]
+    {
+        let store = state.cockpit_event_store.clone();
+        let shutdown = state.shutdown.clone();
+        crate::task_util::spawn_supervised(
+            "server.cockpit_gc",
+            crate::task_util::PanicPolicy::Log,
+            crate::cockpit::retention::run_periodic(store, shutdown),
+        );
+    }
+
     rate_limiter.spawn_cleanup_task(state.shutdown.clone());
     login_manager.spawn_cleanup_task(state.shutdown.clone());
 
@@ -1095,9 +1126,28 @@ fn build_router(state: Arc<AppState>) -> Router {
             get(api::session_diff_files),
         )
         .route("/api/sessions/{id}/diff/file", get(api::session_diff_file))
+        .route(
+            "/api/sessions/{id}/review-comments",
+            get(api::list_review_comments).post(api::add_review_comment),
+        )
+        .route(
+            "/api/sessions/{id}/review-comments/send",
+            post(api::send_review),
+        )
+        .route(
+            "/api/sessions/{id}/review-comments/{comment_id}",
+            delete(api::delete_review_comment),
+        )
         .route("/api/sessions/{id}/ensure", post(api::ensure_session))
         .route("/api/sessions/{id}/send", post(api::send_message))
         .route("/api/sessions/{id}/output", get(api::read_output))
+        .route("/api/sessions/{id}/recordings", get(api::list_recordings))
+        .route("/api/sessions/{id}/timeline", get(api::session_timeline))
+        .route(
+            "/api/sessions/{id}/recordings/{name}",
+            get(api::get_recording),
+        )
+        .route("/api/search", get(api::search_transcripts))
         .route(
             "/api/sessions/{id}/notifications",
             patch(api::update_session_notifications),
@@ -1107,6 +1157,8 @@ fn build_router(state: Arc<AppState>) -> Router {
             patch(api::update_session_diff_base),
         )
         .route("/api/sessions/{id}/pin", patch(api::update_session_pin))
+        .route("/api/sessions/{id}/tags", patch(api::update_session_tags))
+        .route("/api/sessions/{id}/notes", put(api::update_session_notes))
         .route(
             "/api/sessions/{id}/archive",
             patch(api::update_session_archive),
@@ -1180,6 +1232,10 @@ fn build_router(state: Arc<AppState>) -> Router {
             get(api::get_log_level).patch(api::patch_log_level),
         )
         .route("/api/client-log", post(api::post_client_log))
+        // MCP tools for agents (404 unless mcp.http_enabled)
+        .route("/api/mcp", post(api::mcp_rpc))
+        // Machine-readable contract (see api/openapi.rs)
+        .route("/api/openapi.json", get(api::openapi_json))
         // Terminal WebSockets
         .route("/sessions/{id}/ws", get(ws::terminal_ws))
         .route("/sessions/{id}/terminal/ws", get(ws::paired_terminal_ws))
@@ -1197,6 +1253,10 @@ fn build_router(state: Arc<AppState>) -> Router {
             "/api/sessions/{id}/cockpit/switch-agent",
             post(api::switch_cockpit_agent),
         )
+        .route(
+            "/api/sessions/{id}/cockpit/handoff",
+            post(api::cockpit_handoff),
+        )
         .route(
             "/api/sessions/{id}/cockpit/prompt",
             post(api::cockpit_prompt),
@@ -1222,6 +1282,14 @@ fn build_router(state: Arc<AppState>) -> Router {
             "/api/sessions/{id}/cockpit/context-primer",
             get(api::cockpit_context_primer),
         )
+        .route(
+            "/api/sessions/{id}/cockpit/checkpoints",
+            get(api::cockpit_checkpoints),
+        )
+        .route(
+            "/api/sessions/{id}/cockpit/rewind",
+            post(api::cockpit_rewind),
+        )
         .route(
             "/api/sessions/{id}/cockpit/mode",
             post(api::cockpit_set_mode),
@@ -1243,7 +1311,9 @@ fn build_router(state: Arc<AppState>) -> Router {
             post(api::resolve_approval),
         )
         .route("/api/cockpit/master", patch(api::set_cockpit_master))
-        .route("/api/cockpit/agents", get(api::list_cockpit_agents));
+        .route("/api/cockpit/agents", get(api::list_cockpit_agents))
+        .route("/api/cockpit/usage", get(api::cockpit_usage))
+        .route("/api/cockpit/gc", post(api::cockpit_gc));
 
     app
         // Static assets (Vite build output: assets/, manifest.json, sw.js, icons)
@@ -1578,6 +1648,32 @@ fn load_all_instances() -> anyhow::Result<Vec<Instance>> {
     Ok(all)
 }
 
+/// Fill in notes on a fresh disk load. They live in side files, so they
+/// are re-read only when some profile's notes directory changed since the
+/// last tick (a save here, in the TUI, or from the CLI); otherwise the
+/// copies already in memory carry over.
+fn refresh_notes(
+    instances: &mut [Instance],
+    stamps: &mut std::collections::HashMap<String, Option<std::time::SystemTime>>,
+    mut prior: std::collections::HashMap<String, String>,
+) {
+    let mut profiles: Vec<String> = instances.iter().map(|i| i.source_profile.clone()).collect();
+    profiles.sort_unstable();
+    profiles.dedup();
+    let mut changed = false;
+    for profile in profiles {
+        let stamp = crate::session::notes::stamp(&profile);
+        changed |= stamps.insert(profile, stamp) != Some(stamp);
+    }
+    if changed {
+        crate::session::notes::hydrate(instances);
+    } else {
+        for inst in instances {
+            inst.notes = prior.remove(&inst.id).unwrap_or_default();
+        }
+    }
+}
+
 /// Carry over the in-memory-only fields from the prior `state.instances`
 /// entry into the freshly-loaded one. These fields are `#[serde(skip)]`
 /// on `Instance` and would otherwise be reset to default every 2 s when
@@ -1604,14 +1700,25 @@ async fn status_poll_loop(state: Arc<AppState>) {
     #[cfg(feature = "serve")]
     let mut attempted_cockpit_spawns: std::collections::HashSet<String> =
         std::collections::HashSet::new();
+    let mut notes_stamps = std::collections::HashMap::new();
     loop {
         interval.tick().await;
 
         // Snapshot prior statuses so we can detect transitions without
         // holding the lock across the blocking tmux work.
-        let prev: std::collections::HashMap<String, crate::session::Status> = {
+        let (prev, prior_notes): (
+            std::collections::HashMap<String, crate::session::Status>,
+            std::collections::HashMap<String, String>,
+        ) = {
             let instances = state.instances.read().await;
-            instances.iter().map(|i| (i.id.clone(), i.status)).collect()
+            (
+                instances.iter().map(|i| (i.id.clone(), i.status)).collect(),
+                instances
+                    .iter()
+                    .filter(|i| !i.notes.is_empty())
+                    .map(|i| (i.id.clone(), i.notes.clone()))
+                    .collect(),
+            )
         };
 
         // Run blocking tmux subprocess calls in a dedicated thread.
@@ -1622,8 +1729,10 @@ async fn status_poll_loop(state: Arc<AppState>) {
         // exists to prevent.
         let suppressed_ids =
             crate::session::recovery::snapshot_recently_restarted(&state.recently_restarted);
+        let mut stamps = std::mem::take(&mut notes_stamps);
         let updated = tokio::task::spawn_blocking(move || {
             let mut instances = load_all_instances().unwrap_or_default();
+            refresh_notes(&mut instances, &mut stamps, prior_notes);
 
             crate::tmux::refresh_session_cache();
             let pane_metadata = crate::tmux::batch_pane_metadata().unwrap_or_default();
@@ -1645,11 +1754,12 @@ async fn status_poll_loop(state: Arc<AppState>) {
                 inst.update_status_with_metadata(metadata);
             }
 
-            instances
+            (instances, stamps)
         })
         .await;
 
-        if let Ok(mut instances) = updated {
+        if let Ok((mut instances, stamps)) = updated {
+            notes_stamps = stamps;
             // The poll loop refreshes from disk every tick, but the
             // cockpit_status_listener's status writes are in-memory only
             // (status is derived from live ACP events, not persisted
@@ -2145,8 +2255,16 @@ async fn cockpit_event_listener(state: Arc<AppState>) {
         // status-change pushes in `push.rs`, approvals do NOT honour
         // the TUI/web active-session suppression; the service worker
         // still routes focused clients to an in-app toast via the
-        // existing `aoe-push` postMessage path. See #1038.
-        if let crate::cockpit::state::Event::ApprovalRequested { approval } = frame.event.as_ref() {
+        // existing `aoe-push` postMessage path. See #1038. Approvals an
+        // approval rule already answered need no one's attention.
+        if let Some(approval) = match frame.event.as_ref() {
+            crate::cockpit::state::Event::ApprovalRequested { approval }
+                if approval.policy_rule.is_none() =>
+            {
+                Some(approval)
+            }
+            _ => None,
+        } {
             let state_for_push = state.clone();
             let session_id = frame.session_id.clone();
             let approval_title = approval.tool_call.name.clone();
@@ -2162,6 +2280,10 @@ async fn cockpit_event_listener(state: Arc<AppState>) {
             });
         }
 
+        if crate::cockpit::usage::SampleKind::for_event(frame.event.as_ref()).is_some() {
+            record_cockpit_usage(&state, &frame).await;
+        }
+
         let status_intent = derive_cockpit_status(frame.event.as_ref());
         let acp_change = derive_acp_session_change(frame.event.as_ref());
         if status_intent.is_none() && acp_change.is_none() {
@@ -2170,7 +2292,7 @@ async fn cockpit_event_listener(state: Arc<AppState>) {
 
         // Acquire `instances` once for both branches. Releases before
         // the (potentially blocking) sessions.json save.
-        let profile_to_save = {
+        let (transition, profile_to_save) = {
             let mut instances = state.instances.write().await;
             let Some(inst) = instances.iter_mut().find(|i| i.id == frame.session_id) else {
                 continue;
@@ -2179,9 +2301,12 @@ async fn cockpit_event_listener(state: Arc<AppState>) {
                 continue;
             }
 
-            apply_status_intent(inst, status_intent, &state.status_tx);
-            apply_acp_session_change(inst, &frame.session_id, acp_change.as_ref())
+            (
+                apply_status_intent(inst, status_intent, &state.status_tx),
+                apply_acp_session_change(inst, &frame.session_id, acp_change.as_ref()),
+            )
         };
+        record_status_transition(transition).await;
 
         // Persist `cockpit_acp_session_id` to disk if the field changed.
         // Sync FS (file copy + JSON write) goes through spawn_blocking
@@ -2227,6 +2352,58 @@ async fn cockpit_event_listener(state: Arc<AppState>) {
     }
 }
 
+/// Append a usage sample for a finished turn, usage snapshot, or rate
+/// limit to the time series behind `GET /api/cockpit/usage`. The agent
+/// is resolved the way the supervisor picked it at spawn, so a session
+/// handed from Claude to Codex bills the right provider from then on.
+#[cfg(feature = "serve")]
+async fn record_cockpit_usage(state: &Arc<AppState>, frame: &CockpitBroadcastFrame) {
+    let Some((tool, cockpit_agent, model)) = state
+        .instances
+        .read()
+        .await
+        .iter()
+        .find(|i| i.id == frame.session_id)
+        .map(|i| {
+            (
+                i.tool.clone(),
+                i.cockpit_agent.clone(),
+                i.cockpit_model.clone(),
+            )
+        })
+    else {
+        return;
+    };
+    let agent = state
+        .cockpit_supervisor
+        .pick_agent_for_tool(&tool, cockpit_agent.as_deref())
+        .await;
+    let provider = crate::cockpit::usage::provider_for(&agent, model.as_deref());
+    let Some(sample) = crate::cockpit::usage::UsageSample::from_event(
+        &frame.session_id,
+        &agent,
+        &provider,
+        chrono::Utc::now(),
+        frame.event.as_ref(),
+    ) else {
+        return;
+    };
+    let store = state.cockpit_event_store.clone();
+    match tokio::task::spawn_blocking(move || store.record_usage(&sample)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::warn!(
+            target: "cockpit.event_listener",
+            session = %frame.session_id,
+            "record usage sample: {e}"
+        ),
+        Err(join_err) => tracing::warn!(
+            target: "cockpit.event_listener",
+            session = %frame.session_id,
+            "spawn_blocking join error recording usage: {join_err}"
+        ),
+    }
+}
+
 /// Seed each cockpit-enabled session's `Instance.status` from the most
 /// recent lifecycle event in the on-disk event log. Runs once at
 /// daemon startup, before the status poll loop and the cockpit event
@@ -2256,24 +2433,30 @@ pub(crate) async fn seed_cockpit_statuses(state: Arc<AppState>) {
         if intent.is_none() {
             continue;
         }
-        let mut instances = state.instances.write().await;
-        if let Some(inst) = instances.iter_mut().find(|i| i.id == id) {
-            apply_status_intent(inst, intent, &state.status_tx);
-        }
+        let transition = {
+            let mut instances = state.instances.write().await;
+            instances
+                .iter_mut()
+                .find(|i| i.id == id)
+                .and_then(|inst| apply_status_intent(inst, intent, &state.status_tx))
+        };
+        record_status_transition(transition).await;
     }
 }
 
 /// Fold a derived `StatusIntent` into an `Instance`. Pure mutation;
 /// callers hold the write lock. Sends a `StatusChange` on
 /// `status_tx` so push notifications and the dashboard see the
-/// transition like any tmux-driven one.
+/// transition like any tmux-driven one. Returns the timeline line for
+/// the new status, which callers pass to [`record_status_transition`]
+/// once the lock is dropped.
 #[cfg(feature = "serve")]
 pub(crate) fn apply_status_intent(
     inst: &mut Instance,
     intent: Option<StatusIntent>,
     status_tx: &broadcast::Sender<StatusChange>,
-) {
-    let Some(intent) = intent else { return };
+) -> Option<crate::session::timeline::PendingTransition> {
+    let intent = intent?;
     // Don't fight terminal lifecycle states. Cockpit events keep
     // arriving for a few ticks after a Stop/Delete, and we don't
     // want the spinner to flicker back to Running.
@@ -2281,7 +2464,7 @@ pub(crate) fn apply_status_intent(
         inst.status,
         Status::Stopped | Status::Deleting | Status::Creating
     ) {
-        return;
+        return None;
     }
     let target = match intent {
         StatusIntent::Set(s) => s,
@@ -2290,13 +2473,13 @@ pub(crate) fn apply_status_intent(
         // Running turn doesn't stop the spinner.
         StatusIntent::HealError => {
             if inst.status != Status::Error {
-                return;
+                return None;
             }
             Status::Idle
         }
     };
     if inst.status == target {
-        return;
+        return None;
     }
     let prev = inst.status;
     inst.status = target;
@@ -2314,6 +2497,31 @@ pub(crate) fn apply_status_intent(
         new: target,
         at: now,
     });
+    inst.status_transition()
+}
+
+/// Append a transition from [`apply_status_intent`] to the timeline. The
+/// append takes a file lock and may trim the log, so it runs on the
+/// blocking pool; callers drop the `instances` lock first.
+#[cfg(feature = "serve")]
+pub(crate) async fn record_status_transition(
+    transition: Option<crate::session::timeline::PendingTransition>,
+) {
+    let Some(transition) = transition else { return };
+    let session_id = transition.session_id().to_string();
+    match tokio::task::spawn_blocking(move || transition.record()).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::debug!(
+            target: "cockpit.event_listener",
+            session = %session_id,
+            "record status transition: {e}"
+        ),
+        Err(join_err) => tracing::warn!(
+            target: "cockpit.event_listener",
+            session = %session_id,
+            "spawn_blocking join error recording status transition: {join_err}"
+        ),
+    }
 }
 
 /// Fold a derived `AcpSessionChange` into an `Instance`. Returns the
@@ -2394,6 +2602,9 @@ pub(crate) fn derive_cockpit_status(event: &crate::cockpit::Event) -> Option<Sta
         Event::UserPromptSent { .. } | Event::ApprovalResolved { .. } => {
             Some(StatusIntent::Set(Status::Running))
         }
+        // A rule-decided approval is resolved in the same breath; flipping
+        // to Waiting would only flicker the sidebar.
+        Event::ApprovalRequested { approval } if approval.policy_rule.is_some() => None,
         Event::ApprovalRequested { .. } => Some(StatusIntent::Set(Status::Waiting)),
         // All Stopped reasons surface as Idle, including the
         // rate-limit park: the worker is not crashed, the user just
@@ -2447,6 +2658,7 @@ mod tests {
             derive_cockpit_status(&Event::ApprovalResolved {
                 nonce: Nonce("x".into()),
                 decision: ApprovalDecision::Allow,
+                rule: None,
             }),
             Some(StatusIntent::Set(Status::Running))
         );
@@ -839,6 +839,7 @@ pub fn sha256_token(token: &str) -> [u8; 32] {
 use axum::extract::{Extension, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::Json;
+use schemars::JsonSchema;
 use std::sync::Arc;
 
 use super::auth::AuthenticatedTokenHash;
@@ -846,24 +847,24 @@ use super::AppState;
 
 /// Body accepted by POST /api/push/subscribe. Mirrors the browser's
 /// `PushSubscription.toJSON()` output.
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct SubscribeBody {
     pub endpoint: String,
     pub keys: SubscribeKeys,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct SubscribeKeys {
     pub p256dh: String,
     pub auth: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct EndpointBody {
     pub endpoint: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct TestResult {
     pub delivered: u32,
     pub failed: u32,
@@ -132,6 +132,7 @@ pub async fn paired_terminal_ws(
                 primaries,
                 pause_counts,
                 shutdown,
+                None,
             )
         })
         .into_response()
@@ -254,6 +255,7 @@ pub async fn container_terminal_ws(
                 primaries,
                 pause_counts,
                 shutdown,
+                None,
             )
         })
         .into_response()
@@ -318,10 +320,12 @@ pub async fn terminal_ws(
     debug!(target: "terminal.ws", session = %id, kind = "agent", "ws route entered");
     // Verify session exists before upgrading
     let instances = state.instances.read().await;
-    let session_info = instances
-        .iter()
-        .find(|i| i.id == id)
-        .map(|inst| crate::tmux::Session::generate_name(&inst.id, &inst.title));
+    let session_info = instances.iter().find(|i| i.id == id).map(|inst| {
+        (
+            crate::tmux::Session::generate_name(&inst.id, &inst.title),
+            inst.title.clone(),
+        )
+    });
     drop(instances);
 
     let read_only = state.read_only;
@@ -335,9 +339,16 @@ pub async fn terminal_ws(
         // middleware validates the token from the same header, and the
         // server echoes back "aoe-auth" to satisfy the WS spec. The token
         // itself is not echoed, only the marker.
-        Some(tmux_name) => ws
+        Some((tmux_name, title)) => ws
             .protocols(["aoe-auth"])
-            .on_upgrade(move |socket| {
+            .on_upgrade(move |socket| async move {
+                // Read-only viewers cannot type, and the pane recording
+                // already has everything they can see.
+                let recorder = if read_only {
+                    None
+                } else {
+                    start_web_recording(id, title).await
+                };
                 handle_terminal_ws(
                     socket,
                     tmux_name,
@@ -345,7 +356,9 @@ pub async fn terminal_ws(
                     primaries,
                     pause_counts,
                     shutdown,
+                    recorder,
                 )
+                .await
             })
             .into_response(),
         None => {
@@ -392,6 +405,50 @@ type SessionPrimaries = Arc<RwLock<std::collections::HashMap<String, String>>>;
 /// `resume_output` un-pausing the other's scrollback.
 type SessionPauseCounts = Arc<tokio::sync::Mutex<std::collections::HashMap<String, u32>>>;
 
+/// asciicast writer teeing one agent terminal connection, shared by the
+/// PTY reader thread (output) and the receive task (input, resize).
+type SharedRecorder = Arc<std::sync::Mutex<crate::session::recording::CastWriter>>;
+
+/// Open a `web` recording for session `id` when `recording.enabled` is
+/// set. Failures are logged and leave the connection unrecorded.
+async fn start_web_recording(id: String, title: String) -> Option<SharedRecorder> {
+    use crate::session::recording::{self, CastWriter, RecordingSource};
+
+    tokio::task::spawn_blocking(move || {
+        let config = crate::session::Config::load_or_warn().recording;
+        if !config.enabled {
+            return None;
+        }
+        let writer =
+            recording::prepare_recording(&id, RecordingSource::Web, &config).and_then(|path| {
+                // The PTY opens at 80x24; the browser's first resize
+                // follows as an "r" event.
+                CastWriter::create(&path, 80, 24, Some(&title), config.max_file_bytes())
+            });
+        match writer {
+            Ok(writer) => Some(Arc::new(std::sync::Mutex::new(writer))),
+            Err(e) => {
+                warn!(target: "terminal.ws", session = %id, "failed to start web recording: {e:#}");
+                None
+            }
+        }
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+fn record(
+    recorder: &Option<SharedRecorder>,
+    f: impl FnOnce(&mut crate::session::recording::CastWriter),
+) {
+    if let Some(recorder) = recorder {
+        if let Ok(mut writer) = recorder.lock() {
+            f(&mut writer);
+        }
+    }
+}
+
 async fn handle_terminal_ws(
     mut socket: WebSocket,
     tmux_name: String,
@@ -399,6 +456,7 @@ async fn handle_terminal_ws(
     primaries: SessionPrimaries,
     pause_counts: SessionPauseCounts,
     shutdown: CancellationToken,
+    recorder: Option<SharedRecorder>,
 ) {
     use futures_util::{SinkExt, StreamExt};
 
@@ -558,6 +616,7 @@ async fn handle_terminal_ws(
     // Task 1: PTY stdout -> channel (blocking read in dedicated thread)
     let reader_client = client_id.clone();
     let reader_tmux = tmux_name.clone();
+    let reader_recorder = recorder.clone();
     tokio::task::spawn_blocking(move || {
         let mut buf = [0u8; 4096];
         let mut total_bytes: u64 = 0;
@@ -580,6 +639,7 @@ async fn handle_terminal_ws(
                             "pty read"
                         );
                     }
+                    record(&reader_recorder, |r| r.output(&buf[..n]));
                     if output_tx.blocking_send(buf[..n].to_vec()).is_err() {
                         exit_reason = "ws_closed";
                         break; // receiver dropped (WebSocket closed)
@@ -747,6 +807,7 @@ async fn handle_terminal_ws(
     // Task 3: WebSocket receiver -> PTY stdin (and resize)
     let writer_for_input = writer.clone();
     let master_for_resize = master.clone();
+    let recorder_for_recv = recorder.clone();
     let primaries_for_recv = primaries.clone();
     let client_id_for_recv = client_id.clone();
     let tmux_name_for_recv = tmux_name.clone();
@@ -868,7 +929,7 @@ async fn handle_terminal_ws(
                             "claimed primary on binary input"
                         );
                         if let Some((cols, rows)) = pending_size {
-                            resize_pty(&master_for_resize, cols, rows).await;
+                            resize_pty(&master_for_resize, &recorder_for_recv, cols, rows).await;
                         }
                         let _ = ctrl_tx_for_recv
                             .send(r#"{"type":"primary_status","is_primary":true}"#.into())
@@ -876,11 +937,13 @@ async fn handle_terminal_ws(
                     }
 
                     let writer = writer_for_input.clone();
+                    let recorder = recorder_for_recv.clone();
                     let _ = tokio::task::spawn_blocking(move || {
                         if let Ok(mut w) = writer.lock() {
                             let _ = w.write_all(&data);
                             let _ = w.flush();
                         }
+                        record(&recorder, |r| r.input(&data));
                     })
                     .await;
                 }
@@ -906,7 +969,8 @@ async fn handle_terminal_ws(
                                 )
                                 .await;
                                 if dominated {
-                                    resize_pty(&master_for_resize, cols, rows).await;
+                                    resize_pty(&master_for_resize, &recorder_for_recv, cols, rows)
+                                        .await;
                                 } else {
                                     let _ = ctrl_tx_for_recv
                                         .send(
@@ -947,7 +1011,13 @@ async fn handle_terminal_ws(
                                 .await;
                                 if became_primary {
                                     if let Some((cols, rows)) = pending_size {
-                                        resize_pty(&master_for_resize, cols, rows).await;
+                                        resize_pty(
+                                            &master_for_resize,
+                                            &recorder_for_recv,
+                                            cols,
+                                            rows,
+                                        )
+                                        .await;
                                     }
                                     let _ = ctrl_tx_for_recv
                                         .send(
@@ -1022,7 +1092,8 @@ async fn handle_terminal_ws(
                         .await;
                         if became_primary {
                             if let Some((cols, rows)) = pending_size {
-                                resize_pty(&master_for_resize, cols, rows).await;
+                                resize_pty(&master_for_resize, &recorder_for_recv, cols, rows)
+                                    .await;
                             }
                             let _ = ctrl_tx_for_recv
                                 .send(r#"{"type":"primary_status","is_primary":true}"#.into())
@@ -1030,12 +1101,14 @@ async fn handle_terminal_ws(
                         }
 
                         let writer = writer_for_input.clone();
+                        let recorder = recorder_for_recv.clone();
                         let bytes: Vec<u8> = text.as_bytes().to_vec();
                         let _ = tokio::task::spawn_blocking(move || {
                             if let Ok(mut w) = writer.lock() {
                                 let _ = w.write_all(&bytes);
                                 let _ = w.flush();
                             }
+                            record(&recorder, |r| r.input(&bytes));
                         })
                         .await;
                     }
@@ -1220,13 +1293,16 @@ async fn release_primary(primaries: &SessionPrimaries, session: &str, client_id:
     }
 }
 
-/// Resize the PTY master to the given dimensions.
+/// Resize the PTY master to the given dimensions, noting the new size
+/// in the connection's recording if there is one.
 async fn resize_pty(
     master: &Arc<std::sync::Mutex<Box<dyn portable_pty::MasterPty + Send>>>,
+    recorder: &Option<SharedRecorder>,
     cols: u16,
     rows: u16,
 ) {
     let master = master.clone();
+    let recorder = recorder.clone();
     let _ = tokio::task::spawn_blocking(move || {
         if let Ok(m) = master.lock() {
             let _ = m.resize(PtySize {
@@ -1236,6 +1312,7 @@ async fn resize_pty(
                 pixel_height: 0,
             });
         }
+        record(&recorder, |r| r.resize(cols, rows));
     })
     .await;
 }
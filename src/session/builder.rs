@@ -3,7 +3,7 @@
 //! This module provides shared logic for building new session instances,
 //! used by both synchronous (TUI operations) and asynchronous (background poller) code paths.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
 use chrono::Utc;
@@ -12,8 +12,10 @@ use crate::containers::{self, ContainerRuntimeInterface};
 use crate::git::error::GitError;
 use crate::git::GitWorktree;
 
+use super::repo_config;
 use super::{
-    civilizations, Config, Instance, SandboxInfo, WorkspaceInfo, WorkspaceRepo, WorktreeInfo,
+    civilizations, Config, GroupTree, Instance, SandboxInfo, Storage, WorkspaceInfo, WorkspaceRepo,
+    WorktreeInfo,
 };
 
 /// Parameters for creating a new session instance.
@@ -50,6 +52,25 @@ pub struct InstanceParams {
     pub scratch: bool,
 }
 
+/// Characters rejected in user-supplied session fields that end up on a
+/// shell command line (title, path, group, branch, extra args). Shared by
+/// the web API and the MCP server, the two surfaces that take these
+/// fields from a remote or agent-driven caller.
+pub const SHELL_METACHARACTERS: &[char] = &[
+    ';', '&', '|', '$', '`', '(', ')', '{', '}', '<', '>', '\n', '\r', '\\', '"', '\'', '!', '#',
+    '*', '?', '[', ']', '~', '\t', '\0',
+];
+
+pub fn validate_no_shell_injection(value: &str, field_name: &str) -> Result<(), String> {
+    if let Some(c) = value.chars().find(|c| SHELL_METACHARACTERS.contains(c)) {
+        return Err(format!(
+            "Invalid character '{}' in {}. Shell metacharacters are not allowed.",
+            c, field_name
+        ));
+    }
+    Ok(())
+}
+
 /// Result of building an instance, tracking what was created for cleanup purposes.
 pub struct BuildResult {
     pub instance: Instance,
@@ -660,6 +681,84 @@ pub fn cleanup_instance(
     let _ = instance.kill();
 }
 
+/// Finish a session built with no user at the keyboard (MCP tools,
+/// scheduled jobs): run its `on_create` hooks, start it, and only then add
+/// it to `storage`. Any failure cleans up what `build_instance` created,
+/// so a failed start never leaves a stored record pointing at a removed
+/// worktree. Repo hooks that haven't been trusted are skipped, since
+/// there's nobody to ask; global and profile hooks still run.
+pub fn start_and_persist(storage: &Storage, mut build: BuildResult) -> Result<BuildResult> {
+    let result = (|| -> Result<()> {
+        run_unattended_on_create(storage.profile(), &mut build.instance)?;
+        build.instance.start()?;
+        let instance = build.instance.clone();
+        storage.update(|all, groups| {
+            all.push(instance.clone());
+            if !instance.group_path.is_empty() {
+                let mut group_tree = GroupTree::new_with_groups(all, groups);
+                group_tree.create_group(&instance.group_path);
+                *groups = group_tree.get_all_groups();
+            }
+            Ok(())
+        })
+    })();
+    if let Err(e) = result {
+        cleanup_instance(
+            &build.instance,
+            build.created_worktree.as_ref(),
+            &build.created_workspace_worktrees,
+        );
+        return Err(e);
+    }
+    Ok(build)
+}
+
+fn run_unattended_on_create(profile: &str, instance: &mut Instance) -> Result<()> {
+    let project_path = Path::new(&instance.project_path);
+    let hooks = match repo_config::check_hook_trust(project_path) {
+        Ok(repo_config::HookTrustStatus::Trusted(repo_hooks)) => {
+            repo_config::merge_hooks_with_config(profile, repo_hooks)
+        }
+        Ok(repo_config::HookTrustStatus::NeedsTrust { .. }) => {
+            tracing::warn!(
+                target: "session.create",
+                "skipping untrusted repo hooks for '{}'; trust them from the TUI or `aoe add`",
+                instance.title
+            );
+            repo_config::resolve_global_profile_hooks(profile)
+        }
+        Ok(repo_config::HookTrustStatus::NoHooks) => {
+            repo_config::resolve_global_profile_hooks(profile)
+        }
+        Err(e) => {
+            tracing::warn!(target: "session.create", "Failed to check repo hooks: {}", e);
+            repo_config::resolve_global_profile_hooks(profile)
+        }
+    };
+    let Some(hooks) = hooks.filter(|h| !h.on_create.is_empty()) else {
+        return Ok(());
+    };
+    let hook_env = repo_config::lifecycle_env_vars(instance);
+    let container_name = instance
+        .sandbox_info
+        .as_ref()
+        .filter(|s| s.enabled)
+        .map(|s| s.container_name.clone());
+    match container_name {
+        Some(container_name) => {
+            instance.get_container_for_instance()?;
+            repo_config::execute_hooks_in_container(
+                &hooks.on_create,
+                &container_name,
+                &instance.container_workdir(),
+                &hook_env,
+            )
+        }
+        None => repo_config::execute_hooks(&hooks.on_create, project_path, &hook_env),
+    }
+    .map_err(|e| anyhow::anyhow!("on_create hook failed: {:#}", e))
+}
+
 /// Resolve the session title: use the provided title, then an explicit worktree
 /// branch name, then fall back to a random civilization name.
 pub(crate) fn resolve_title(
@@ -2014,6 +2014,116 @@ pub(crate) fn hermes_poll_fn_sandboxed(
     }
 }
 
+// ─── Transcript location ──────────────────────────────────────────────────────
+
+/// Where an agent keeps the conversation log for one captured session id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AgentLog {
+    /// A single JSON or JSONL file (Claude, Codex, Gemini).
+    File(PathBuf),
+    /// A session inside opencode's SQLite store.
+    OpenCode { db: PathBuf, session_id: String },
+}
+
+/// Locate the on-host conversation log for `agent_session_id`.
+///
+/// Reverses the capture paths above: the captured id names the file (or
+/// row) the agent writes, so this only has to find it again. Returns
+/// `None` for agents without a readable log and for logs that live inside
+/// a sandbox container.
+pub(crate) fn locate_agent_log(
+    tool: &str,
+    project_path: &str,
+    agent_session_id: &str,
+) -> Option<AgentLog> {
+    if !is_valid_session_id(agent_session_id) {
+        return None;
+    }
+    match tool {
+        "claude" => locate_claude_log(project_path, agent_session_id).map(AgentLog::File),
+        "codex" => locate_codex_log(agent_session_id).map(AgentLog::File),
+        "gemini" => locate_gemini_log(project_path, agent_session_id).map(AgentLog::File),
+        "opencode" => {
+            let db = opencode_db_path().ok()?;
+            db.exists().then(|| AgentLog::OpenCode {
+                db,
+                session_id: agent_session_id.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// `~/.claude/projects/<encoded>/<id>.jsonl`, falling back to any project
+/// dir holding that id (the session may have moved between worktrees).
+fn locate_claude_log(project_path: &str, id: &str) -> Option<PathBuf> {
+    let projects = resolve_agent_home(Some("CLAUDE_CONFIG_DIR"), ".claude")
+        .ok()?
+        .join("projects");
+    let file_name = format!("{id}.jsonl");
+    let encoded = encode_claude_project_path(&canonicalize_or_raw(project_path).to_string_lossy());
+    let direct = projects.join(encoded).join(&file_name);
+    if direct.is_file() {
+        return Some(direct);
+    }
+    let found = resilient_read_dir(&projects)
+        .ok()?
+        .map(|e| e.path().join(&file_name))
+        .find(|p| p.is_file());
+    found
+}
+
+fn locate_codex_log(id: &str) -> Option<PathBuf> {
+    let sessions_dir = resolve_agent_home(Some("CODEX_HOME"), ".codex")
+        .ok()?
+        .join("sessions");
+    let mut entries = Vec::new();
+    collect_codex_sessions(&sessions_dir, &mut entries).ok()?;
+    entries
+        .into_iter()
+        .map(|(path, _)| path)
+        .find(|p| extract_codex_uuid_from_filename(p).as_deref() == Some(id))
+}
+
+/// Gemini names chat files by start time, so the id has to be read back
+/// out of each candidate. The project's own hash dir is checked first.
+fn locate_gemini_log(project_path: &str, id: &str) -> Option<PathBuf> {
+    use sha2::{Digest, Sha256};
+
+    let tmp_dir = resolve_agent_home(Some("GEMINI_CLI_HOME"), ".gemini")
+        .ok()?
+        .join("tmp");
+    let digest = Sha256::digest(
+        canonicalize_or_raw(project_path)
+            .to_string_lossy()
+            .as_bytes(),
+    );
+    let hash = digest
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    let exact = tmp_dir.join(&hash);
+    let mut dirs = vec![exact.clone()];
+    if let Ok(entries) = resilient_read_dir(&tmp_dir) {
+        dirs.extend(entries.map(|e| e.path()).filter(|p| *p != exact));
+    }
+    dirs.into_iter().find_map(|dir| {
+        resilient_read_dir(&dir.join("chats"))
+            .ok()?
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("session-"))
+                    && matches!(
+                        p.extension().and_then(|e| e.to_str()),
+                        Some("json") | Some("jsonl")
+                    )
+            })
+            .find(|p| extract_gemini_fields(p).and_then(|(sid, _)| sid).as_deref() == Some(id))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
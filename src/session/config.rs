@@ -4,7 +4,7 @@ use super::get_app_dir;
 use super::repo_config::HooksConfig;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -55,6 +55,15 @@ pub struct Config {
     #[serde(default)]
     pub logging: LoggingConfig,
 
+    #[serde(default)]
+    pub mcp: McpConfig,
+
+    #[serde(default)]
+    pub recording: RecordingConfig,
+
+    #[serde(default)]
+    pub issues: IssuesConfig,
+
     /// Environment variables injected into the host command line for every
     /// session spawned at global scope. Entries are `KEY=value`, `KEY=$VAR`
     /// (read VAR from the host env), `KEY=$$literal` (escape a `$`), or
@@ -76,7 +85,29 @@ pub struct Config {
     /// palette (Ctrl+K).
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub tools: HashMap<String, ToolSessionConfig>,
-}
+
+    /// Home-view keybinding overrides: action id (the command palette id,
+    /// e.g. `next-waiting`) -> key sequences. An entry replaces the action's
+    /// built-in keys in both hotkey modes; an empty list unbinds it. The
+    /// `dialog-*` ids add keys for confirm / cancel / up / down in dialogs.
+    /// Parsed and checked for conflicts by `tui::home::bindings::Keymap`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<String, KeySequences>,
+
+    /// Saved session views: name -> filter query (see `session::filter`).
+    /// Listed in the TUI's group-by picker and usable as `@name` in any
+    /// filter query.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, String>,
+}
+
+/// One `[keybindings]` value: a key sequence (`"ctrl+w"`, `"g w"`) or a
+/// list of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeySequences(
+    #[serde(deserialize_with = "super::serde_helpers::string_or_vec")] pub Vec<String>,
+);
 
 /// Configuration for a user-defined tool session (lazygit, yazi, tig, etc.)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -225,6 +256,20 @@ pub struct CockpitConfig {
     /// Replay buffer byte cap (per session).
     #[serde(default = "default_replay_bytes")]
     pub replay_bytes: u64,
+    /// Drop cockpit transcript events older than this many days. 0
+    /// keeps them forever (default). Applied by `aoe cockpit gc` and
+    /// the daemon's periodic pass (see `cockpit::retention`).
+    #[serde(default)]
+    pub history_max_age_days: u32,
+    /// Size budget for this profile's share of the cockpit event
+    /// database, in bytes of stored events. Oldest events go first once
+    /// it is exceeded. 0 = no budget (default).
+    #[serde(default)]
+    pub history_max_bytes: u64,
+    /// Exempt archived sessions from `history_max_age_days` and
+    /// `history_max_bytes`, so archiving a session keeps its transcript.
+    #[serde(default = "default_true")]
+    pub history_keep_archived: bool,
     /// Optional path to the Node runtime used to spawn aoe-agent. If
     /// empty, aoe resolves Node via PATH then bundled fallback.
     #[serde(default)]
@@ -303,6 +348,16 @@ pub struct CockpitConfig {
     /// has no effect. See #1240.
     #[serde(default = "default_silent_orphan_fast_grace_secs")]
     pub silent_orphan_fast_grace_secs: u32,
+    /// Ordered auto-approval rules for agent permission requests. The
+    /// first matching rule decides; `ask` (or no match) surfaces the
+    /// approval card as usual. Evaluated in `cockpit::permissions`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approval_rules: Vec<ApprovalRule>,
+    /// MCP servers handed to the agent in ACP `session/new` (and
+    /// `session/load`), so cockpit sessions get the same tools a tmux
+    /// agent would load from its own config.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_servers: Vec<McpServerConfig>,
 }
 
 fn default_max_concurrent_resumes() -> u32 {
@@ -321,6 +376,151 @@ fn default_silent_orphan_fast_grace_secs() -> u32 {
     20
 }
 
+/// What an [`ApprovalRule`] does with a matching permission request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalAction {
+    /// Approve once without asking.
+    Allow,
+    /// Reject without asking.
+    Deny,
+    /// Show the approval card. Useful ahead of broader `allow` rules.
+    #[default]
+    Ask,
+}
+
+impl ApprovalAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApprovalAction::Allow => "allow",
+            ApprovalAction::Deny => "deny",
+            ApprovalAction::Ask => "ask",
+        }
+    }
+}
+
+/// One `[[cockpit.approval_rules]]` entry. Every condition that is set
+/// must match; a list condition matches when any of its entries does.
+/// A rule with no conditions matches every request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    pub action: ApprovalAction,
+    /// Label recorded with every decision this rule makes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// ACP tool kinds: `read`, `edit`, `delete`, `move`, `search`,
+    /// `execute`, `think`, `fetch`, `switch_mode`, `other`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kind: Vec<String>,
+    /// Shell command prefixes, matched on whole words. Every command in
+    /// a `&&` / `||` / `;` / `|` chain must match one of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command_prefix: Vec<String>,
+    /// Regex matched against the shell command. For `allow` rules it
+    /// must match every command in the chain, and lines with command
+    /// substitution or file redirects never match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_regex: Option<CommandRegex>,
+    /// Globs relative to the session's worktree (`src/**`, `*.md`).
+    /// Every path the tool touches must match one of them; paths outside
+    /// the worktree never match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Match only requests the destructive heuristic flags (`true`) or
+    /// only those it doesn't (`false`). `allow` rules never approve a
+    /// destructive request unless this is explicitly `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive: Option<bool>,
+}
+
+/// An [`ApprovalRule::command_regex`] pattern, compiled when the config
+/// is parsed so an invalid pattern is a load error rather than a
+/// per-request warning.
+#[derive(Debug, Clone)]
+pub struct CommandRegex(regex::Regex);
+
+impl CommandRegex {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        regex::Regex::new(pattern).map(Self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.0.is_match(haystack)
+    }
+}
+
+impl PartialEq for CommandRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for CommandRegex {}
+
+impl std::fmt::Display for CommandRegex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for CommandRegex {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CommandRegex {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(|e| {
+            serde::de::Error::custom(format!("invalid command_regex {pattern:?}: {e}"))
+        })
+    }
+}
+
+/// One `[[cockpit.mcp_servers]]` entry: either a stdio server (`command`)
+/// or a remote one (`url`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    /// Name the agent shows the server's tools under.
+    pub name: String,
+    /// Executable for a stdio server. In sandboxed sessions the agent
+    /// runs inside the container, so this runs there too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Endpoint for a remote server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Protocol for a `url` server.
+    #[serde(default)]
+    pub transport: McpTransport,
+    /// Set while merging when the entry came from a repo's
+    /// `.agent-of-empires/config.toml`; never read from or written to disk.
+    #[serde(skip)]
+    pub from_repo: bool,
+}
+
+/// Transport for a remote MCP server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    /// Streamable HTTP.
+    #[default]
+    Http,
+    /// Legacy server-sent events.
+    Sse,
+}
+
 /// Drain strategy for the cockpit composer's client-side prompt queue.
 /// See `CockpitConfig::queue_drain_mode`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -363,6 +563,9 @@ impl Default for CockpitConfig {
             max_concurrent_workers: default_max_workers(),
             replay_events: default_replay_events(),
             replay_bytes: default_replay_bytes(),
+            history_max_age_days: 0,
+            history_max_bytes: 0,
+            history_keep_archived: true,
             node_path: String::new(),
             show_tool_durations: true,
             queue_drain_mode: QueueDrainMode::default(),
@@ -370,6 +573,8 @@ impl Default for CockpitConfig {
             force_end_turn_threshold_secs: default_force_end_turn_threshold_secs(),
             silent_orphan_grace_secs: default_silent_orphan_grace_secs(),
             silent_orphan_fast_grace_secs: default_silent_orphan_fast_grace_secs(),
+            approval_rules: Vec::new(),
+            mcp_servers: Vec::new(),
         }
     }
 }
@@ -447,13 +652,17 @@ pub enum GroupByMode {
     #[default]
     Manual,
     Project,
+    /// One group per tag; `key=value` labels nest as `key/value`. A session
+    /// with several tags is listed under each.
+    Tag,
 }
 
 impl GroupByMode {
     pub fn cycle(self) -> Self {
         match self {
             GroupByMode::Manual => GroupByMode::Project,
-            GroupByMode::Project => GroupByMode::Manual,
+            GroupByMode::Project => GroupByMode::Tag,
+            GroupByMode::Tag => GroupByMode::Manual,
         }
     }
 
@@ -461,6 +670,7 @@ impl GroupByMode {
         match self {
             GroupByMode::Manual => "Manual",
             GroupByMode::Project => "Project",
+            GroupByMode::Tag => "Tag",
         }
     }
 }
@@ -505,6 +715,10 @@ pub struct AppStateConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_by: Option<GroupByMode>,
 
+    /// Saved view (a `[views]` name) narrowing the session list, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_view: Option<String>,
+
     /// Last directory the user navigated to in the new-session dir picker.
     /// Restored on subsequent opens so users don't re-navigate every time.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -843,6 +1057,13 @@ pub struct DiffConfig {
     /// Number of context lines to show around changes
     #[serde(default = "default_context_lines")]
     pub context_lines: usize,
+
+    /// Shell command that writes a commit message for the diff view's
+    /// commit dialog (Ctrl+G). It gets a prompt plus the staged diff on
+    /// stdin; the first non-empty line of its output becomes the message.
+    /// Unset disables generation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_message_command: Option<String>,
 }
 
 impl Default for DiffConfig {
@@ -850,6 +1071,7 @@ impl Default for DiffConfig {
         Self {
             default_branch: None,
             context_lines: 3,
+            commit_message_command: None,
         }
     }
 }
@@ -906,6 +1128,175 @@ impl Default for WebConfig {
     }
 }
 
+/// MCP server configuration (`aoe mcp`, and `POST /api/mcp` on
+/// `aoe serve`). Global only: the allowlist decides what an agent
+/// driving aoe may change, so it is deliberately not overridable per
+/// profile, per repo, or through `PATCH /api/settings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpConfig {
+    /// Mutating tools (`create_session`, `send_message`,
+    /// `merge_session`) an MCP client
+    /// may call. Read-only tools are always available. Tools missing
+    /// from this list are hidden from `tools/list` and rejected by
+    /// `tools/call`; an empty list makes the server read-only.
+    #[serde(default = "default_mcp_allowed_mutations")]
+    pub allowed_mutations: Vec<String>,
+
+    /// Mount the MCP endpoint at `POST /api/mcp` on `aoe serve`. Off by
+    /// default: the HTTP endpoint hands session control to any client
+    /// holding a dashboard token. `aoe mcp` (stdio) ignores this flag.
+    #[serde(default)]
+    pub http_enabled: bool,
+}
+
+impl Default for McpConfig {
+    fn default() -> Self {
+        Self {
+            allowed_mutations: default_mcp_allowed_mutations(),
+            http_enabled: false,
+        }
+    }
+}
+
+fn default_mcp_allowed_mutations() -> Vec<String> {
+    vec![
+        "create_session".to_string(),
+        "send_message".to_string(),
+        "merge_session".to_string(),
+    ]
+}
+
+/// Which tracker `aoe add --issue` asks for a bare key such as `ABC-123`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueProviderKind {
+    /// GitHub Issues, through the `gh` CLI.
+    Github,
+    /// Jira Cloud or Server, through its REST API.
+    Jira,
+    /// Linear, through its GraphQL API.
+    Linear,
+    /// A folder of `<KEY>.md` files, for offline use.
+    Local,
+}
+
+/// Issue-tracker settings for `aoe add --issue` (see `session::issues`).
+/// Tokens are read from environment variables named here, never stored in
+/// the config file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuesConfig {
+    /// Tracker for keys that don't name one themselves. GitHub references
+    /// (`#123`, `owner/repo#123`, an issue URL) always go to GitHub.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<IssueProviderKind>,
+
+    /// `owner/repo` for bare GitHub issue numbers. By default `gh` uses
+    /// the repository of the session's project directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_repo: Option<String>,
+
+    /// Jira base URL, e.g. `https://acme.atlassian.net`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_url: Option<String>,
+
+    /// Account email for Jira Cloud basic auth. When unset, the token is
+    /// sent as a bearer token (Jira Server/Data Center personal tokens).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_email: Option<String>,
+
+    /// Environment variable holding the Jira API token.
+    #[serde(default = "default_jira_token_env")]
+    pub jira_token_env: String,
+
+    /// Environment variable holding the Linear API key.
+    #[serde(default = "default_linear_token_env")]
+    pub linear_token_env: String,
+
+    /// Folder of `<KEY>.md` issue files for the `local` provider. The
+    /// first `# ` heading is the title and the rest is the body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_dir: Option<PathBuf>,
+}
+
+fn default_jira_token_env() -> String {
+    "JIRA_API_TOKEN".to_string()
+}
+
+fn default_linear_token_env() -> String {
+    "LINEAR_API_KEY".to_string()
+}
+
+impl Default for IssuesConfig {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            github_repo: None,
+            jira_url: None,
+            jira_email: None,
+            jira_token_env: default_jira_token_env(),
+            linear_token_env: default_linear_token_env(),
+            local_dir: None,
+        }
+    }
+}
+
+/// Terminal recording configuration (`aoe session replay`). Global
+/// only: recordings capture everything an agent pane shows and, for web
+/// terminals, everything typed into it, so turning them on is an
+/// operator decision rather than a per-profile one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingConfig {
+    /// Record agent panes to asciicast v2 files under
+    /// `<app_dir>/recordings/<session-id>/`. Off by default.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Recordings kept per session; the oldest are removed when a new
+    /// recording starts. 0 keeps every recording.
+    #[serde(default = "default_recording_max_per_session")]
+    pub max_per_session: usize,
+
+    /// Remove recordings older than this many days when a new recording
+    /// starts. 0 disables age-based cleanup.
+    #[serde(default = "default_recording_max_age_days")]
+    pub max_age_days: u64,
+
+    /// Stop writing a recording once it reaches this size in megabytes.
+    /// 0 removes the cap.
+    #[serde(default = "default_recording_max_file_mb")]
+    pub max_file_mb: u64,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_per_session: default_recording_max_per_session(),
+            max_age_days: default_recording_max_age_days(),
+            max_file_mb: default_recording_max_file_mb(),
+        }
+    }
+}
+
+impl RecordingConfig {
+    /// `max_file_mb` in bytes, with 0 meaning "no cap".
+    pub fn max_file_bytes(&self) -> u64 {
+        self.max_file_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+fn default_recording_max_per_session() -> usize {
+    20
+}
+
+fn default_recording_max_age_days() -> u64 {
+    30
+}
+
+fn default_recording_max_file_mb() -> u64 {
+    50
+}
+
 /// Serde default for `Config.default_profile`. Empty means "not explicitly
 /// chosen"; the active profile is then resolved at runtime by
 /// `resolve_default_profile`, which picks the first existing profile or
@@ -974,6 +1365,7 @@ fn default_idle_decay_minutes() -> u64 {
 /// default: shows the TUI banner and, when `notify_in_cli` is true, the
 /// CLI eprintln nag. `Off` suppresses every check, banner, and fetch.
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum UpdateCheckMode {
     /// Silently install detected updates; user picks them up next launch.
@@ -1565,6 +1957,23 @@ mod tests {
         assert!(!config.worktree.enabled);
     }
 
+    #[test]
+    fn test_keybindings_accept_a_string_or_a_list() {
+        let toml = r#"
+            [keybindings]
+            next-waiting = "ctrl+w"
+            send-message = ["m", "g m"]
+            quit = []
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.keybindings["next-waiting"].0, vec!["ctrl+w"]);
+        assert_eq!(config.keybindings["send-message"].0, vec!["m", "g m"]);
+        assert!(config.keybindings["quit"].0.is_empty());
+
+        let round_trip: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(round_trip.keybindings, config.keybindings);
+    }
+
     // Tests for ThemeConfig
     #[test]
     fn test_theme_config_default() {
@@ -0,0 +1,217 @@
+//! Coordinator sessions: a parent that splits work across sub-sessions
+//! (`Instance::parent_session_id`), then reviews and merges what they did.
+//!
+//! Sub-sessions are one level deep, like `aoe add --parent`. The agent
+//! side of the workflow is the MCP server in coordinator mode
+//! (`aoe mcp --coordinator`, see `crate::mcp`); this module holds the
+//! pieces it shares with the TUI list and the web API: finding a
+//! parent's children, rolling their statuses up for the parent's row,
+//! reading a child's final message, and merging a child's branch back.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::transcript::{load_transcript, EntryKind};
+use super::{Instance, Status};
+use crate::git::merge::{commit_pending, merge_branch, MergeOutcome};
+
+/// Sub-sessions of `parent_id`, in storage order.
+pub fn children<'a>(
+    parent_id: &'a str,
+    instances: &'a [Instance],
+) -> impl Iterator<Item = &'a Instance> + 'a {
+    instances
+        .iter()
+        .filter(move |i| i.parent_session_id.as_deref() == Some(parent_id))
+}
+
+/// Sub-session statuses rolled up for the parent's row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChildStatusCounts {
+    pub total: usize,
+    /// Running, starting, or being created.
+    pub running: usize,
+    pub waiting: usize,
+    /// Idle, or status not yet known.
+    pub idle: usize,
+    pub error: usize,
+    /// Stopped or being deleted.
+    pub stopped: usize,
+}
+
+impl ChildStatusCounts {
+    pub fn of(parent_id: &str, instances: &[Instance]) -> Self {
+        let mut counts = Self::default();
+        for child in children(parent_id, instances) {
+            counts.add(child.status);
+        }
+        counts
+    }
+
+    /// Counts for every parent in `instances` in one pass, keyed by parent
+    /// id. Parents without sub-sessions have no entry.
+    pub fn by_parent(instances: &[Instance]) -> HashMap<String, Self> {
+        let mut by_parent: HashMap<String, Self> = HashMap::new();
+        for inst in instances {
+            if let Some(parent_id) = &inst.parent_session_id {
+                by_parent
+                    .entry(parent_id.clone())
+                    .or_default()
+                    .add(inst.status);
+            }
+        }
+        by_parent
+    }
+
+    fn add(&mut self, status: Status) {
+        self.total += 1;
+        match status {
+            Status::Running | Status::Starting | Status::Creating => self.running += 1,
+            Status::Waiting => self.waiting += 1,
+            Status::Idle | Status::Unknown => self.idle += 1,
+            Status::Error => self.error += 1,
+            Status::Stopped | Status::Deleting => self.stopped += 1,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// True once no child is still working: what a coordinator waits
+    /// for before reviewing results.
+    pub fn all_settled(&self) -> bool {
+        self.running == 0
+    }
+
+    /// Compact row summary, most urgent first, e.g. `1 waiting, 2 running`.
+    pub fn summary(&self) -> String {
+        [
+            (self.error, "error"),
+            (self.waiting, "waiting"),
+            (self.running, "running"),
+            (self.idle, "idle"),
+            (self.stopped, "stopped"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, label)| format!("{n} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// The agent's last reply in `inst`'s transcript, if aoe can read one.
+/// Agents without a readable log (sandboxed tmux sessions, unsupported
+/// tools) return `None`; callers fall back to the pane contents.
+pub fn final_message(inst: &Instance) -> Result<Option<String>> {
+    let entries = load_transcript(inst)?;
+    Ok(entries
+        .into_iter()
+        .rev()
+        .take_while(|e| e.kind != EntryKind::User)
+        .find(|e| e.kind == EntryKind::Assistant && !e.text.trim().is_empty())
+        .map(|e| e.text))
+}
+
+/// What [`merge_into_parent`] did.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeReport {
+    pub branch: String,
+    /// Commit made from the child's uncommitted changes before merging.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committed: Option<String>,
+    #[serde(flatten)]
+    pub outcome: MergeOutcome,
+}
+
+/// Merge `child`'s worktree branch into whatever `parent` has checked
+/// out. Uncommitted work in the child's worktree is committed first, so
+/// the child must not be mid-turn.
+pub fn merge_into_parent(child: &Instance, parent: &Instance) -> Result<MergeReport> {
+    if child.parent_session_id.as_deref() != Some(parent.id.as_str()) {
+        bail!(
+            "'{}' is not a sub-session of '{}'",
+            child.title,
+            parent.title
+        );
+    }
+    let Some(worktree) = child.worktree_info.as_ref() else {
+        bail!(
+            "'{}' has no worktree branch to merge; only worktree sub-sessions can be merged",
+            child.title
+        );
+    };
+    if matches!(
+        child.status,
+        Status::Running | Status::Starting | Status::Creating
+    ) {
+        bail!(
+            "'{}' is still {}; wait for it to finish before merging",
+            child.title,
+            child.status.as_str()
+        );
+    }
+
+    let committed = commit_pending(
+        std::path::Path::new(&child.project_path),
+        &format!("{} (aoe sub-session)", child.title),
+    )?;
+    let outcome = merge_branch(
+        std::path::Path::new(&parent.project_path),
+        &worktree.branch,
+        &format!("Merge sub-session '{}' ({})", child.title, worktree.branch),
+    )?;
+    Ok(MergeReport {
+        branch: worktree.branch.clone(),
+        committed,
+        outcome,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(parent: &str, status: Status) -> Instance {
+        let mut inst = Instance::new("child", "/tmp");
+        inst.parent_session_id = Some(parent.to_string());
+        inst.status = status;
+        inst
+    }
+
+    #[test]
+    fn counts_roll_up_only_the_parents_children() {
+        let parent = Instance::new("parent", "/tmp");
+        let instances = vec![
+            parent.clone(),
+            child(&parent.id, Status::Running),
+            child(&parent.id, Status::Starting),
+            child(&parent.id, Status::Waiting),
+            child(&parent.id, Status::Idle),
+            child("someone-else", Status::Error),
+        ];
+        let counts = ChildStatusCounts::of(&parent.id, &instances);
+        assert_eq!(counts.total, 4);
+        assert_eq!(counts.summary(), "1 waiting, 2 running, 1 idle");
+        assert!(!counts.all_settled());
+        assert!(ChildStatusCounts::of("nobody", &instances).is_empty());
+        let by_parent = ChildStatusCounts::by_parent(&instances);
+        assert_eq!(by_parent[&parent.id], counts);
+        assert_eq!(by_parent["someone-else"].error, 1);
+        assert!(!by_parent.contains_key("nobody"));
+    }
+
+    #[test]
+    fn merge_refuses_non_worktree_and_unrelated_sessions() {
+        let parent = Instance::new("parent", "/tmp");
+        let plain = child(&parent.id, Status::Idle);
+        let err = merge_into_parent(&plain, &parent).unwrap_err();
+        assert!(err.to_string().contains("no worktree"), "{err}");
+
+        let stranger = child("someone-else", Status::Idle);
+        let err = merge_into_parent(&stranger, &parent).unwrap_err();
+        assert!(err.to_string().contains("not a sub-session"), "{err}");
+    }
+}
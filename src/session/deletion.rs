@@ -401,6 +401,51 @@ pub fn perform_deletion(request: &DeletionRequest) -> DeletionResult {
     // Stage 6: hook status cleanup
     tracing::debug!(target: "session.delete", session_id = %request.session_id, stage = "hook_status_cleanup", "perform_deletion: stage");
     crate::hooks::cleanup_hook_status_dir(&request.instance.id);
+    #[cfg(feature = "serve")]
+    crate::cockpit::worker_registry::remove_agent_socket_dir(&request.instance.id);
+
+    // Stage 7: terminal recordings go with the session
+    tracing::debug!(target: "session.delete", session_id = %request.session_id, stage = "recordings_cleanup", "perform_deletion: stage");
+    super::recording::remove_recordings(&request.instance.id);
+
+    // Stage 8: notes live in their own file under the profile dir
+    tracing::debug!(target: "session.delete", session_id = %request.session_id, stage = "notes_cleanup", "perform_deletion: stage");
+    if let Err(e) = super::notes::remove(&request.instance.source_profile, &request.instance.id) {
+        tracing::warn!(target: "session.delete", session_id = %request.session_id, "failed to remove notes: {e}");
+    }
+
+    // Stage 9: status history lives in the profile's shared timeline log
+    tracing::debug!(target: "session.delete", session_id = %request.session_id, stage = "timeline_cleanup", "perform_deletion: stage");
+    if let Err(e) = super::timeline::remove(&request.instance.source_profile, &request.instance.id)
+    {
+        tracing::warn!(target: "session.delete", session_id = %request.session_id, "failed to remove status timeline: {e}");
+    }
+
+    // Stage 10: unsent review comments share the profile's reviews file
+    tracing::debug!(target: "session.delete", session_id = %request.session_id, stage = "reviews_cleanup", "perform_deletion: stage");
+    if let Err(e) = super::reviews::clear(&request.instance.source_profile, &request.instance.id) {
+        tracing::warn!(target: "session.delete", session_id = %request.session_id, "failed to remove review comments: {e}");
+    }
+
+    // Stage 11: cockpit rewind checkpoints are refs in the repository, so
+    // they outlive the worktree. Look them up through the main repo, which
+    // shares refs with every worktree and is still there after stage 4.
+    #[cfg(feature = "serve")]
+    {
+        tracing::debug!(target: "session.delete", session_id = %request.session_id, stage = "checkpoints_cleanup", "perform_deletion: stage");
+        let repo_path = request
+            .instance
+            .worktree_info
+            .as_ref()
+            .map_or(request.instance.project_path.as_str(), |w| {
+                w.main_repo_path.as_str()
+            });
+        if let Err(e) =
+            crate::cockpit::checkpoints::discard_from(Path::new(repo_path), &request.instance.id, 0)
+        {
+            tracing::debug!(target: "session.delete", session_id = %request.session_id, "no checkpoints removed: {e:#}");
+        }
+    }
 
     if !errors.is_empty() {
         tracing::debug!(target: "session.delete",
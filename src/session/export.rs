@@ -0,0 +1,573 @@
+//! Self-contained session reports for `aoe session export`.
+//!
+//! Unlike the cockpit's context primer, which squeezes recent turns into a
+//! prompt-sized budget, a report carries the whole transcript (see
+//! [`super::transcript`]) and the full diff against the session's base, so
+//! it can be attached to a PR or an incident review as-is.
+
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::transcript::{load_transcript, EntryKind, TranscriptEntry};
+use super::Instance;
+use crate::git::diff;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    pub id: String,
+    pub title: String,
+    pub profile: String,
+    pub tool: String,
+    pub project_path: String,
+    pub group: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Branch the worktree was created from, when aoe created it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_from: Option<String>,
+    pub exported_at: DateTime<Utc>,
+    /// The session's markdown notes (see [`super::notes`]); empty when it
+    /// has none.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    /// False when aoe found no log it can read for this session (sandboxed
+    /// agents, agents without a captured session id).
+    pub transcript_available: bool,
+    pub transcript: Vec<TranscriptEntry>,
+    /// Why the transcript could not be read (log unreadable or corrupt).
+    /// The rest of the report is still written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript_error: Option<String>,
+    /// One entry per repository: the project path, or every member of a
+    /// multi-repo workspace.
+    pub repos: Vec<RepoDiff>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub path: String,
+    pub base_branch: String,
+    pub files: Vec<ChangedFile>,
+    /// Unified patch for every changed file.
+    pub patch: String,
+    /// Why the diff could not be computed (worktree deleted, not a git
+    /// repo, unknown base). The rest of the report is still written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedFile {
+    pub path: String,
+    pub status: &'static str,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl SessionReport {
+    /// Collect everything the report needs for `inst`. Transcript and diff
+    /// failures are recorded in the report instead of failing the export.
+    pub fn build(inst: &Instance, profile: &str) -> Result<Self> {
+        let transcript_available =
+            super::transcript::TranscriptSource::for_instance(inst).is_some();
+        let (transcript, transcript_error) = match load_transcript(inst) {
+            Ok(transcript) => (transcript, None),
+            Err(e) => (Vec::new(), Some(format!("{e:#}"))),
+        };
+
+        let config =
+            super::repo_config::resolve_config_with_repo(profile, Path::new(&inst.project_path))
+                .unwrap_or_default();
+        // Workspace sessions diff every member repo, mirroring the diff
+        // view and the MCP `get_diff` tool.
+        let repos: Vec<(Option<String>, String)> = match inst.workspace_info.as_ref() {
+            Some(ws) => ws
+                .repos
+                .iter()
+                .map(|r| (Some(r.name.clone()), r.worktree_path.clone()))
+                .collect(),
+            None => vec![(None, inst.project_path.clone())],
+        };
+        let repos = repos
+            .into_iter()
+            .map(|(name, path)| {
+                let repo_path = Path::new(&path);
+                let base_branch = diff::resolve_diff_base(
+                    inst.base_branch_override.as_deref(),
+                    config.diff.default_branch.as_deref(),
+                    repo_path,
+                );
+                let mut repo = RepoDiff {
+                    name,
+                    path: path.clone(),
+                    base_branch,
+                    files: Vec::new(),
+                    patch: String::new(),
+                    error: None,
+                };
+                if let Err(e) = collect_diff(&mut repo, config.diff.context_lines) {
+                    repo.error = Some(format!("{e:#}"));
+                }
+                repo
+            })
+            .collect();
+
+        Ok(Self {
+            id: inst.id.clone(),
+            title: inst.title.clone(),
+            profile: profile.to_string(),
+            tool: inst.tool.clone(),
+            project_path: inst.project_path.clone(),
+            group: inst.group_path.clone(),
+            created_at: inst.created_at,
+            branch: inst
+                .worktree_info
+                .as_ref()
+                .map(|w| w.branch.clone())
+                .or_else(|| inst.workspace_info.as_ref().map(|w| w.branch.clone())),
+            created_from: inst
+                .worktree_info
+                .as_ref()
+                .and_then(|w| w.base_branch.clone()),
+            exported_at: Utc::now(),
+            notes: super::notes::load(profile, &inst.id).unwrap_or_default(),
+            transcript_available,
+            transcript,
+            transcript_error,
+            repos,
+        })
+    }
+
+    fn count(&self, kind: EntryKind) -> usize {
+        self.transcript.iter().filter(|e| e.kind == kind).count()
+    }
+
+    fn totals(&self) -> (usize, usize, usize) {
+        self.repos
+            .iter()
+            .flat_map(|r| &r.files)
+            .fold((0, 0, 0), |(n, add, del), f| {
+                (n + 1, add + f.additions, del + f.deletions)
+            })
+    }
+
+    /// Metadata rows shared by the Markdown and HTML renderers.
+    fn metadata(&self) -> Vec<(&'static str, String)> {
+        let mut rows = vec![
+            ("Session", self.id.clone()),
+            ("Profile", self.profile.clone()),
+            ("Agent", self.tool.clone()),
+            ("Project", self.project_path.clone()),
+        ];
+        if !self.group.is_empty() {
+            rows.push(("Group", self.group.clone()));
+        }
+        if let Some(branch) = &self.branch {
+            rows.push(("Branch", branch.clone()));
+        }
+        if let Some(from) = &self.created_from {
+            rows.push(("Created from", from.clone()));
+        }
+        let bases: Vec<String> = self
+            .repos
+            .iter()
+            .map(|r| match &r.name {
+                Some(name) => format!("{} ({name})", r.base_branch),
+                None => r.base_branch.clone(),
+            })
+            .collect();
+        rows.push(("Diff base", bases.join(", ")));
+        rows.push(("Created", format_ts(self.created_at)));
+        rows.push(("Exported", format_ts(self.exported_at)));
+        let (files, add, del) = self.totals();
+        rows.push((
+            "Activity",
+            format!(
+                "{} prompts, {} tool calls, {} approval events, {files} files changed (+{add} -{del})",
+                self.count(EntryKind::User),
+                self.count(EntryKind::ToolCall),
+                self.count(EntryKind::Approval),
+            ),
+        ));
+        rows
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.title);
+        out.push_str("| | |\n| --- | --- |\n");
+        for (label, value) in self.metadata() {
+            out.push_str(&format!("| {label} | {} |\n", value.replace('|', "\\|")));
+        }
+
+        if !self.notes.is_empty() {
+            out.push_str("\n## Notes\n\n");
+            out.push_str(&self.notes);
+            out.push('\n');
+        }
+
+        out.push_str("\n## Transcript\n\n");
+        if let Some(error) = &self.transcript_error {
+            out.push_str(&format!("_Transcript unavailable: {error}_\n\n"));
+        } else if !self.transcript_available {
+            out.push_str("_No transcript is available for this session._\n\n");
+        }
+        for entry in &self.transcript {
+            let when = entry.timestamp.map(format_ts).unwrap_or_default();
+            out.push_str(&format!("### {}", entry_label(entry.kind)));
+            if !when.is_empty() {
+                out.push_str(&format!(" · {when}"));
+            }
+            out.push_str("\n\n");
+            match entry.kind {
+                EntryKind::User | EntryKind::Assistant | EntryKind::Approval => {
+                    out.push_str(entry.text.trim_end());
+                    out.push_str("\n\n");
+                }
+                EntryKind::ToolCall | EntryKind::ToolResult => {
+                    out.push_str(&fenced(&entry.text, ""));
+                }
+            }
+        }
+
+        out.push_str("## Changes\n\n");
+        for repo in &self.repos {
+            if let Some(name) = &repo.name {
+                out.push_str(&format!("### {name}\n\n"));
+            }
+            if let Some(error) = &repo.error {
+                out.push_str(&format!("_Diff unavailable: {error}_\n\n"));
+                continue;
+            }
+            if repo.files.is_empty() {
+                out.push_str(&format!("No changes against `{}`.\n\n", repo.base_branch));
+                continue;
+            }
+            out.push_str("| File | Status | + | - |\n| --- | --- | --: | --: |\n");
+            for f in &repo.files {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} |\n",
+                    f.path.replace('|', "\\|"),
+                    f.status,
+                    f.additions,
+                    f.deletions
+                ));
+            }
+            out.push('\n');
+            out.push_str(&fenced(&repo.patch, "diff"));
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out =
+            String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", escape_html(&self.title)));
+        out.push_str(HTML_STYLE);
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!(
+            "<h1>{}</h1>\n<table class=\"meta\">\n",
+            escape_html(&self.title)
+        ));
+        for (label, value) in self.metadata() {
+            out.push_str(&format!(
+                "<tr><th>{label}</th><td>{}</td></tr>\n",
+                escape_html(&value)
+            ));
+        }
+        out.push_str("</table>\n");
+        if !self.notes.is_empty() {
+            out.push_str(&format!(
+                "<h2>Notes</h2>\n<div class=\"notes\">{}</div>\n",
+                escape_html(&self.notes)
+            ));
+        }
+        out.push_str("<h2>Transcript</h2>\n");
+        if let Some(error) = &self.transcript_error {
+            out.push_str(&format!(
+                "<p class=\"note\">Transcript unavailable: {}</p>\n",
+                escape_html(error)
+            ));
+        } else if !self.transcript_available {
+            out.push_str("<p class=\"note\">No transcript is available for this session.</p>\n");
+        }
+        for entry in &self.transcript {
+            let when = entry.timestamp.map(format_ts).unwrap_or_default();
+            let class = entry.kind.as_str();
+            let label = entry_label(entry.kind);
+            match entry.kind {
+                EntryKind::ToolCall | EntryKind::ToolResult => {
+                    let summary = entry.text.lines().next().unwrap_or("");
+                    out.push_str(&format!(
+                        "<details class=\"entry {class}\"><summary><b>{label}</b> <span class=\"ts\">{when}</span> <code>{}</code></summary><pre>{}</pre></details>\n",
+                        escape_html(&clip(summary, 120)),
+                        escape_html(&entry.text)
+                    ));
+                }
+                _ => {
+                    out.push_str(&format!(
+                        "<div class=\"entry {class}\"><div class=\"head\"><b>{label}</b> <span class=\"ts\">{when}</span></div><div class=\"text\">{}</div></div>\n",
+                        escape_html(entry.text.trim_end())
+                    ));
+                }
+            }
+        }
+
+        out.push_str("<h2>Changes</h2>\n");
+        for repo in &self.repos {
+            if let Some(name) = &repo.name {
+                out.push_str(&format!("<h3>{}</h3>\n", escape_html(name)));
+            }
+            if let Some(error) = &repo.error {
+                out.push_str(&format!(
+                    "<p class=\"note\">Diff unavailable: {}</p>\n",
+                    escape_html(error)
+                ));
+                continue;
+            }
+            if repo.files.is_empty() {
+                out.push_str(&format!(
+                    "<p class=\"note\">No changes against <code>{}</code>.</p>\n",
+                    escape_html(&repo.base_branch)
+                ));
+                continue;
+            }
+            out.push_str("<table class=\"files\"><tr><th>File</th><th>Status</th><th>+</th><th>-</th></tr>\n");
+            for f in &repo.files {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td class=\"add\">{}</td><td class=\"del\">{}</td></tr>\n",
+                    escape_html(&f.path),
+                    f.status,
+                    f.additions,
+                    f.deletions
+                ));
+            }
+            out.push_str("</table>\n<pre class=\"diff\">");
+            for line in repo.patch.lines() {
+                let class = if line.starts_with("diff --git")
+                    || line.starts_with("+++ ")
+                    || line.starts_with("--- ")
+                {
+                    "file"
+                } else if line.starts_with("@@") {
+                    "hunk"
+                } else if line.starts_with('+') {
+                    "add"
+                } else if line.starts_with('-') {
+                    "del"
+                } else {
+                    ""
+                };
+                if class.is_empty() {
+                    out.push_str(&escape_html(line));
+                } else {
+                    out.push_str(&format!(
+                        "<span class=\"{class}\">{}</span>",
+                        escape_html(line)
+                    ));
+                }
+                out.push('\n');
+            }
+            out.push_str("</pre>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn collect_diff(repo: &mut RepoDiff, context_lines: usize) -> Result<()> {
+    let repo_path = Path::new(&repo.path);
+    for file in diff::compute_changed_files(repo_path, &repo.base_branch)? {
+        let file_diff =
+            diff::compute_file_diff(repo_path, &file.path, &repo.base_branch, context_lines)?;
+        repo.patch.push_str(&file_diff.to_unified());
+        repo.files.push(ChangedFile {
+            path: file.path.to_string_lossy().to_string(),
+            status: file.status.label(),
+            additions: file.additions,
+            deletions: file.deletions,
+        });
+    }
+    Ok(())
+}
+
+fn entry_label(kind: EntryKind) -> &'static str {
+    match kind {
+        EntryKind::User => "User",
+        EntryKind::Assistant => "Agent",
+        EntryKind::ToolCall => "Tool call",
+        EntryKind::ToolResult => "Tool output",
+        EntryKind::Approval => "Approval",
+    }
+}
+
+fn format_ts(ts: DateTime<Utc>) -> String {
+    ts.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+/// Wrap `text` in a code fence from [`fence_for`], so tool output that
+/// itself contains fences can't end the block early.
+fn fenced(text: &str, lang: &str) -> String {
+    let fence = fence_for(text);
+    format!(
+        "{fence}{lang}\n{}\n{fence}\n\n",
+        text.trim_end_matches('\n')
+    )
+}
+
+/// A code fence longer than the longest backtick run in `text` (and at
+/// least three backticks), so the text can't close it early.
+pub(crate) fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn clip(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &s[..i]),
+        None => s.to_string(),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = r#"<style>
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #1f2328; }
+table { border-collapse: collapse; margin-bottom: 1rem; }
+th, td { text-align: left; padding: 0.2rem 0.8rem 0.2rem 0; vertical-align: top; }
+.meta th { color: #59636e; font-weight: normal; }
+pre { background: #f6f8fa; padding: 0.6rem; overflow-x: auto; font-size: 0.85rem; }
+.entry { margin: 0.6rem 0; }
+.entry .text, .notes { white-space: pre-wrap; }
+.user { border-left: 3px solid #0969da; padding-left: 0.6rem; }
+.assistant { border-left: 3px solid #8250df; padding-left: 0.6rem; }
+.approval { border-left: 3px solid #bf8700; padding-left: 0.6rem; }
+.ts, .note { color: #59636e; font-size: 0.85rem; }
+summary { cursor: pointer; }
+.diff .add, td.add { color: #1a7f37; }
+.diff .del, td.del { color: #cf222e; }
+.diff .hunk { color: #8250df; }
+.diff .file { font-weight: bold; }
+</style>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn report() -> SessionReport {
+        let ts = Utc.with_ymd_and_hms(2026, 10, 17, 10, 0, 0).unwrap();
+        let entry = |kind, text: &str| TranscriptEntry {
+            kind,
+            text: text.to_string(),
+            timestamp: Some(ts),
+        };
+        SessionReport {
+            id: "abc123".into(),
+            title: "Fix <parser>".into(),
+            profile: "default".into(),
+            tool: "claude".into(),
+            project_path: "/src/app".into(),
+            group: String::new(),
+            created_at: ts,
+            branch: Some("fix-parser".into()),
+            created_from: Some("main".into()),
+            exported_at: ts,
+            notes: "Waiting on infra to merge the <schema> PR.".into(),
+            transcript_available: true,
+            transcript: vec![
+                entry(EntryKind::User, "why does it panic?"),
+                entry(EntryKind::ToolCall, "Bash {\"command\":\"cat README.md\"}"),
+                entry(EntryKind::ToolResult, "```rust\nfn main() {}\n```"),
+                entry(EntryKind::Approval, "Decision: allow_once"),
+                entry(EntryKind::Assistant, "Fixed the <slice> bounds."),
+            ],
+            transcript_error: None,
+            repos: vec![RepoDiff {
+                name: None,
+                path: "/src/app".into(),
+                base_branch: "main".into(),
+                files: vec![ChangedFile {
+                    path: "src/lib.rs".into(),
+                    status: "modified",
+                    additions: 1,
+                    deletions: 1,
+                }],
+                patch: "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,1 +1,1 @@\n-old\n+new\n".into(),
+                error: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn markdown_has_metadata_transcript_and_diff() {
+        let md = report().to_markdown();
+        assert!(md.starts_with("# Fix <parser>\n"));
+        assert!(md.contains("| Branch | fix-parser |"));
+        assert!(md.contains("| Diff base | main |"));
+        assert!(md.contains("1 prompts, 1 tool calls, 1 approval events, 1 files changed (+1 -1)"));
+        assert!(md.contains("## Notes\n\nWaiting on infra to merge the <schema> PR.\n"));
+        assert!(md.contains("### User · 2026-10-17 10:00:00 UTC\n\nwhy does it panic?"));
+        assert!(md.contains("| `src/lib.rs` | modified | 1 | 1 |"));
+        assert!(md.contains("```diff\ndiff --git"));
+    }
+
+    #[test]
+    fn fence_outgrows_backticks_in_content() {
+        let md = report().to_markdown();
+        assert!(md.contains("````\n```rust\nfn main() {}\n```\n````"));
+        assert_eq!(fenced("plain", ""), "```\nplain\n```\n\n");
+    }
+
+    #[test]
+    fn html_escapes_and_marks_diff_lines() {
+        let html = report().to_html();
+        assert!(html.contains("<title>Fix &lt;parser&gt;</title>"));
+        assert!(html.contains("Fixed the &lt;slice&gt; bounds."));
+        assert!(html.contains(
+            "<div class=\"notes\">Waiting on infra to merge the &lt;schema&gt; PR.</div>"
+        ));
+        assert!(!html.contains("<slice>"));
+        assert!(html.contains("<span class=\"add\">+new</span>"));
+        assert!(html.contains("<span class=\"hunk\">@@ -1,1 +1,1 @@</span>"));
+    }
+
+    #[test]
+    fn diff_errors_are_reported_not_fatal() {
+        let mut r = report();
+        r.repos[0].files.clear();
+        r.repos[0].error = Some("not a git repository".into());
+        r.transcript_available = false;
+        r.transcript.clear();
+        r.notes.clear();
+        let md = r.to_markdown();
+        assert!(!md.contains("## Notes"));
+        assert!(md.contains("_No transcript is available for this session._"));
+        assert!(md.contains("_Diff unavailable: not a git repository_"));
+
+        r.transcript_available = true;
+        r.transcript_error = Some("permission denied".into());
+        assert!(r
+            .to_markdown()
+            .contains("_Transcript unavailable: permission denied_"));
+    }
+}
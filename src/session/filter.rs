@@ -0,0 +1,534 @@
+//! Session filter queries: a small language over session fields, shared by
+//! the TUI search bar, `aoe list --filter`, `GET /api/sessions?filter=`, and
+//! the named saved views in the `[views]` config section.
+//!
+//! A query is whitespace-separated terms, all of which must match:
+//!
+//! ```text
+//! status:waiting tool:claude group:payments age>1h tag:reviewer=alice
+//! ```
+//!
+//! - `field:value` compares a text or yes/no field. A comma lists
+//!   alternatives (`status:waiting,idle`) and `*` is a wildcard
+//!   (`branch:feat/*`). `group:` also matches subgroups, and `tag:key`
+//!   matches any `key=value` label.
+//! - `age` and `idle_age` take `>`, `>=`, `<`, or `<=` and a duration
+//!   (`90s`, `30m`, `1h`, `2d`, `1w`). Only Idle sessions have an idle age.
+//! - `-` in front of a term negates it.
+//! - `@name` expands to the saved view `name`.
+//! - Any other word must appear in the title (case-insensitive).
+//!
+//! Fields: `status`, `tool`, `group`, `profile`, `branch`, `tag`,
+//! `sandboxed`, `favorited`, `age`, `idle_age`, `title`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::{Instance, Status};
+
+/// Fields a term can name, in the order the parse error lists them.
+pub const FIELDS: &[&str] = &[
+    "status",
+    "tool",
+    "group",
+    "profile",
+    "branch",
+    "tag",
+    "sandboxed",
+    "favorited",
+    "age",
+    "idle_age",
+    "title",
+];
+
+/// Saved views can reference each other; this bounds the expansion so a
+/// cycle is an error rather than a stack overflow.
+const MAX_VIEW_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError(String);
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+fn err<T>(msg: impl Into<String>) -> Result<T, FilterError> {
+    Err(FilterError(msg.into()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cmp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Cmp {
+    fn holds(self, actual: Duration, bound: Duration) -> bool {
+        match self {
+            Cmp::Gt => actual > bound,
+            Cmp::Ge => actual >= bound,
+            Cmp::Lt => actual < bound,
+            Cmp::Le => actual <= bound,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Status(Vec<Status>),
+    Tool(Vec<String>),
+    Group(Vec<String>),
+    Profile(Vec<String>),
+    Branch(Vec<String>),
+    Tag(Vec<String>),
+    Title(Vec<String>),
+    Sandboxed(bool),
+    Favorited(bool),
+    Age(Cmp, Duration),
+    IdleAge(Cmp, Duration),
+    /// A bare word: substring of the title.
+    Word(String),
+    /// Every term of an expanded `@view`.
+    All(Vec<Term>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negated: bool,
+    predicate: Predicate,
+}
+
+/// A parsed query. The empty query matches everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    terms: Vec<Term>,
+}
+
+impl Filter {
+    /// Parse `query` without saved views; `@name` is an error.
+    pub fn parse(query: &str) -> Result<Self, FilterError> {
+        Self::parse_with_views(query, &BTreeMap::new())
+    }
+
+    /// Parse `query`, expanding `@name` from `views`.
+    pub fn parse_with_views(
+        query: &str,
+        views: &BTreeMap<String, String>,
+    ) -> Result<Self, FilterError> {
+        Ok(Self {
+            terms: parse_terms(query, views, 0)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, inst: &Instance) -> bool {
+        self.matches_at(inst, Utc::now())
+    }
+
+    /// `matches` against a fixed clock, for the age fields.
+    pub fn matches_at(&self, inst: &Instance, now: DateTime<Utc>) -> bool {
+        self.terms.iter().all(|t| t.matches(inst, now))
+    }
+}
+
+/// Whether `query` uses any filter syntax (a known `field:`/comparison, a
+/// negation, or an `@view`), as opposed to plain text. The TUI search bar
+/// filters on structured queries and fuzzy-matches plain ones.
+pub fn is_structured(query: &str) -> bool {
+    tokenize(query).iter().any(|token| {
+        let token = token.strip_prefix('-').unwrap_or(token);
+        token.starts_with('@') || split_field(token).is_some()
+    })
+}
+
+impl Term {
+    fn matches(&self, inst: &Instance, now: DateTime<Utc>) -> bool {
+        self.predicate.matches(inst, now) != self.negated
+    }
+}
+
+impl Predicate {
+    fn matches(&self, inst: &Instance, now: DateTime<Utc>) -> bool {
+        match self {
+            Predicate::Status(any) => any.contains(&inst.status),
+            Predicate::Tool(any) => any.iter().any(|p| glob_match(p, &inst.tool)),
+            Predicate::Group(any) => any.iter().any(|p| group_matches(p, &inst.group_path)),
+            Predicate::Profile(any) => any.iter().any(|p| glob_match(p, &inst.source_profile)),
+            Predicate::Branch(any) => branches(inst).any(|b| any.iter().any(|p| glob_match(p, b))),
+            Predicate::Tag(any) => inst
+                .tags
+                .iter()
+                .any(|t| any.iter().any(|p| tag_matches(p, t))),
+            Predicate::Title(any) => any.iter().any(|p| glob_match(p, &inst.title)),
+            Predicate::Sandboxed(want) => inst.is_sandboxed() == *want,
+            Predicate::Favorited(want) => inst.is_favorited() == *want,
+            Predicate::Age(cmp, bound) => (now - inst.created_at)
+                .to_std()
+                .is_ok_and(|age| cmp.holds(age, *bound)),
+            Predicate::IdleAge(cmp, bound) => {
+                if inst.status != Status::Idle {
+                    return false;
+                }
+                inst.idle_entered_at
+                    .and_then(|since| (now - since).to_std().ok())
+                    .is_some_and(|age| cmp.holds(age, *bound))
+            }
+            Predicate::Word(word) => inst.title.to_lowercase().contains(word),
+            Predicate::All(terms) => terms.iter().all(|t| t.matches(inst, now)),
+        }
+    }
+}
+
+/// The worktree branch, or every workspace repo's branch.
+fn branches(inst: &Instance) -> impl Iterator<Item = &str> {
+    let worktree = inst.worktree_info.iter().map(|w| w.branch.as_str());
+    let workspace = inst
+        .workspace_info
+        .iter()
+        .flat_map(|w| w.repos.iter().map(|r| r.branch.as_str()));
+    worktree.chain(workspace)
+}
+
+fn group_matches(pattern: &str, group_path: &str) -> bool {
+    if pattern.contains('*') {
+        return glob_match(pattern, group_path);
+    }
+    let pattern = pattern.trim_matches('/');
+    group_path.eq_ignore_ascii_case(pattern)
+        || group_path
+            .to_lowercase()
+            .starts_with(&format!("{}/", pattern.to_lowercase()))
+}
+
+/// `pattern` matches the whole tag, or (without an `=`) a label's key.
+fn tag_matches(pattern: &str, tag: &str) -> bool {
+    glob_match(pattern, tag)
+        || (!pattern.contains('=') && glob_match(pattern, super::tags::key(tag)))
+}
+
+/// Case-insensitive match where `*` stands for any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    // Classic two-pointer wildcard match with backtracking to the last `*`.
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Split on whitespace, keeping double-quoted runs (`title:"fix login"`)
+/// together with the quotes removed.
+fn tokenize(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// `field`, operator, and value of a `field<op>value` token naming a known
+/// field; None for anything else.
+fn split_field(token: &str) -> Option<(&str, &str, &str)> {
+    let end = token.find([':', '>', '<'])?;
+    let field = &token[..end];
+    if !FIELDS.contains(&field) {
+        return None;
+    }
+    let rest = &token[end..];
+    let op_len = if rest.starts_with(">=") || rest.starts_with("<=") {
+        2
+    } else {
+        1
+    };
+    Some((field, &rest[..op_len], &rest[op_len..]))
+}
+
+fn parse_terms(
+    query: &str,
+    views: &BTreeMap<String, String>,
+    depth: usize,
+) -> Result<Vec<Term>, FilterError> {
+    if depth > MAX_VIEW_DEPTH {
+        return err("saved views nest too deeply (is one referencing itself?)");
+    }
+    tokenize(query)
+        .iter()
+        .map(|token| {
+            let (negated, token) = match token.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, token.as_str()),
+            };
+            let predicate = if let Some(name) = token.strip_prefix('@') {
+                let Some(view) = views.get(name) else {
+                    return err(format!("unknown view '@{name}'"));
+                };
+                Predicate::All(parse_terms(view, views, depth + 1)?)
+            } else if let Some((field, op, value)) = split_field(token) {
+                parse_field(field, op, value)?
+            } else {
+                Predicate::Word(token.to_lowercase())
+            };
+            Ok(Term { negated, predicate })
+        })
+        .collect()
+}
+
+fn parse_field(field: &str, op: &str, value: &str) -> Result<Predicate, FilterError> {
+    if value.is_empty() {
+        return err(format!("'{field}{op}' needs a value"));
+    }
+    match field {
+        "age" | "idle_age" => {
+            let cmp = match op {
+                ">" => Cmp::Gt,
+                ">=" => Cmp::Ge,
+                "<" => Cmp::Lt,
+                "<=" => Cmp::Le,
+                _ => return err(format!("{field} compares with > or <, e.g. {field}>1h")),
+            };
+            let bound = parse_duration(value)?;
+            Ok(if field == "age" {
+                Predicate::Age(cmp, bound)
+            } else {
+                Predicate::IdleAge(cmp, bound)
+            })
+        }
+        _ if op != ":" => err(format!(
+            "use {field}:{value}; only ages compare with > or <"
+        )),
+        "sandboxed" => Ok(Predicate::Sandboxed(parse_bool(field, value)?)),
+        "favorited" => Ok(Predicate::Favorited(parse_bool(field, value)?)),
+        "status" => value
+            .split(',')
+            .map(|s| parse_status(s.trim()))
+            .collect::<Result<_, _>>()
+            .map(Predicate::Status),
+        _ => {
+            let any: Vec<String> = value
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+            Ok(match field {
+                "tool" => Predicate::Tool(any),
+                "group" => Predicate::Group(any),
+                "profile" => Predicate::Profile(any),
+                "branch" => Predicate::Branch(any),
+                "tag" => Predicate::Tag(any),
+                _ => Predicate::Title(any),
+            })
+        }
+    }
+}
+
+fn parse_status(value: &str) -> Result<Status, FilterError> {
+    const ALL: &[Status] = &[
+        Status::Running,
+        Status::Waiting,
+        Status::Idle,
+        Status::Unknown,
+        Status::Stopped,
+        Status::Error,
+        Status::Starting,
+        Status::Deleting,
+        Status::Creating,
+    ];
+    ALL.iter()
+        .copied()
+        .find(|s| s.as_str().eq_ignore_ascii_case(value))
+        .ok_or_else(|| {
+            let names: Vec<&str> = ALL.iter().map(|s| s.as_str()).collect();
+            FilterError(format!(
+                "unknown status '{value}' (expected one of {})",
+                names.join(", ")
+            ))
+        })
+}
+
+fn parse_bool(field: &str, value: &str) -> Result<bool, FilterError> {
+    match value.to_ascii_lowercase().as_str() {
+        "yes" | "true" | "1" => Ok(true),
+        "no" | "false" | "0" => Ok(false),
+        _ => err(format!("{field} takes yes or no, not '{value}'")),
+    }
+}
+
+/// `90s`, `30m`, `1h`, `2d`, `1w`. Saturates rather than overflowing.
+/// Also parses `aoe search --since` ages.
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, FilterError> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let Ok(n) = number.parse::<u64>() else {
+        return err(format!("'{value}' is not a duration like 30m or 2h"));
+    };
+    let secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return err(format!("'{value}' is not a duration like 30m or 2h")),
+    };
+    Ok(Duration::from_secs(n.saturating_mul(secs)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::WorktreeInfo;
+
+    fn inst(title: &str, status: Status, group: &str) -> Instance {
+        let mut inst = Instance::new(title, "/tmp/p");
+        inst.status = status;
+        inst.group_path = group.to_string();
+        inst.source_profile = "work".to_string();
+        inst
+    }
+
+    fn matches(query: &str, inst: &Instance) -> bool {
+        Filter::parse(query).unwrap().matches(inst)
+    }
+
+    #[test]
+    fn fields_combine_with_and() {
+        let mut i = inst("Fix refunds", Status::Waiting, "payments/api");
+        i.tool = "claude".to_string();
+        i.created_at = Utc::now() - chrono::Duration::hours(2);
+        let query = "status:waiting tool:claude group:payments age>1h";
+        assert!(matches(query, &i));
+        i.status = Status::Running;
+        assert!(!matches(query, &i));
+        assert!(matches("status:waiting,running", &i));
+        assert!(!matches("group:pay", &i), "group matches whole segments");
+        assert!(matches("group:pay*", &i));
+        assert!(matches("profile:work -favorited:yes refund", &i));
+        assert!(!matches("age<1h", &i));
+    }
+
+    #[test]
+    fn branch_and_idle_age() {
+        let mut i = inst("a", Status::Idle, "");
+        assert!(!matches("branch:*", &i), "no branch, no match");
+        i.worktree_info = Some(WorktreeInfo {
+            branch: "feat/login".to_string(),
+            main_repo_path: "/tmp/p".to_string(),
+            managed_by_aoe: true,
+            created_at: Utc::now(),
+            base_branch: None,
+        });
+        assert!(matches("branch:feat/*", &i));
+        i.idle_entered_at = Some(Utc::now() - chrono::Duration::minutes(45));
+        assert!(matches("idle_age>30m", &i));
+        assert!(!matches("idle_age>1h", &i));
+        i.status = Status::Running;
+        assert!(
+            !matches("idle_age>30m", &i),
+            "only idle sessions have an idle age"
+        );
+    }
+
+    #[test]
+    fn tags_match_whole_tag_or_label_key() {
+        let mut i = inst("a", Status::Idle, "");
+        assert!(!matches("tag:*", &i), "no tags, no match");
+        i.tags = vec!["blocked-ci".to_string(), "reviewer=alice".to_string()];
+        assert!(matches("tag:blocked-ci", &i));
+        assert!(matches("tag:reviewer", &i));
+        assert!(matches("tag:reviewer=alice", &i));
+        assert!(!matches("tag:reviewer=bob", &i));
+        assert!(matches("tag:ticket,blocked-*", &i));
+        assert!(matches("-tag:ticket", &i));
+    }
+
+    #[test]
+    fn views_expand_and_reject_cycles() {
+        let views = BTreeMap::from([
+            ("stuck".to_string(), "status:waiting age>1h".to_string()),
+            ("loop".to_string(), "@loop".to_string()),
+        ]);
+        let mut i = inst("a", Status::Waiting, "");
+        i.created_at = Utc::now() - chrono::Duration::hours(3);
+        let f = Filter::parse_with_views("@stuck", &views).unwrap();
+        assert!(f.matches(&i));
+        assert!(!Filter::parse_with_views("-@stuck", &views)
+            .unwrap()
+            .matches(&i));
+        assert!(Filter::parse_with_views("@loop", &views).is_err());
+        assert!(Filter::parse("@stuck").is_err());
+    }
+
+    #[test]
+    fn parse_errors_explain_themselves() {
+        let e = Filter::parse("status:asleep").unwrap_err().to_string();
+        assert!(e.contains("unknown status 'asleep'"), "{e}");
+        assert!(Filter::parse("age:1h").is_err());
+        assert!(Filter::parse("age>soon").is_err());
+        assert!(Filter::parse("sandboxed:maybe").is_err());
+        assert!(Filter::parse("tool:").is_err());
+    }
+
+    #[test]
+    fn structured_detection() {
+        assert!(is_structured("status:waiting"));
+        assert!(is_structured("login -age>1h"));
+        assert!(is_structured("@mine"));
+        assert!(!is_structured("fix login"));
+        assert!(!is_structured("http://x"), "unknown field names are text");
+        assert!(matches(
+            r#"title:"fix log*""#,
+            &inst("Fix login", Status::Idle, "")
+        ));
+    }
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("feat/*", "feat/a/b"));
+        assert!(glob_match("*log*", "Fix LOGIN"));
+        assert!(glob_match("claude", "Claude"));
+        assert!(!glob_match("claude", "claude-code"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+    }
+}
@@ -692,13 +692,7 @@ pub fn flatten_tree_all_profiles(
         .collect();
 
     sort_sessions(&mut ungrouped, sort_order);
-
-    for inst in ungrouped {
-        items.push(Item::Session {
-            id: inst.id.clone(),
-            depth: 0,
-        });
-    }
+    push_sessions(&mut items, &ungrouped, 0);
 
     // Collect and flatten groups from all profiles at depth 0
     let mut all_roots: Vec<(&str, &Group, Vec<Instance>)> = Vec::new();
@@ -787,13 +781,7 @@ pub fn flatten_tree(
         .collect();
 
     sort_sessions(&mut ungrouped, sort_order);
-
-    for inst in ungrouped {
-        items.push(Item::Session {
-            id: inst.id.clone(),
-            depth: 0,
-        });
-    }
+    push_sessions(&mut items, &ungrouped, 0);
 
     // Add groups and their sessions
     let roots = group_tree.get_roots();
@@ -847,13 +835,7 @@ fn flatten_group(
         .collect();
 
     sort_sessions(&mut group_sessions, sort_order);
-
-    for inst in group_sessions {
-        items.push(Item::Session {
-            id: inst.id.clone(),
-            depth: depth + 1,
-        });
-    }
+    push_sessions(items, &group_sessions, depth + 1);
 
     // Recursively add child groups (sort them if needed)
     let mut children_to_iterate: Vec<&Group> = group.children.iter().collect();
@@ -871,6 +853,34 @@ fn flatten_group(
     }
 }
 
+/// Push already-sorted `sessions` at `depth`, each followed by its
+/// sub-sessions one level deeper in the same order. A sub-session only
+/// nests when its parent is in the same list (sub-sessions start in
+/// their parent's group, but can be moved); otherwise it stays a
+/// sibling rather than vanishing.
+fn push_sessions(items: &mut Vec<Item>, sessions: &[&Instance], depth: usize) {
+    let by_id: HashMap<&str, &Instance> = sessions.iter().map(|i| (i.id.as_str(), *i)).collect();
+    let nests_under = |inst: &Instance| -> Option<&str> {
+        let parent = by_id.get(inst.parent_session_id.as_deref()?)?;
+        (parent.id != inst.id && !parent.is_sub_session()).then_some(parent.id.as_str())
+    };
+    for inst in sessions.iter().filter(|i| nests_under(i).is_none()) {
+        items.push(Item::Session {
+            id: inst.id.clone(),
+            depth,
+        });
+        for child in sessions
+            .iter()
+            .filter(|c| nests_under(c) == Some(inst.id.as_str()))
+        {
+            items.push(Item::Session {
+                id: child.id.clone(),
+                depth: depth + 1,
+            });
+        }
+    }
+}
+
 fn count_sessions_in_group(path: &str, instances: &[Instance]) -> usize {
     let prefix = format!("{}/", path);
     instances
@@ -1088,6 +1098,43 @@ mod tests {
         assert!(matches!(items[0], Item::Session { .. }));
     }
 
+    #[test]
+    fn test_flatten_tree_nests_sub_sessions_under_parent() {
+        let mut parent = Instance::new("parent", "/tmp/p");
+        parent.group_path = "work".to_string();
+        let other = {
+            let mut i = Instance::new("other", "/tmp/o");
+            i.group_path = "work".to_string();
+            i
+        };
+        let mut child = Instance::new("child", "/tmp/c");
+        child.group_path = "work".to_string();
+        child.parent_session_id = Some(parent.id.clone());
+        let mut orphan = Instance::new("orphan", "/tmp/x");
+        orphan.parent_session_id = Some("gone".to_string());
+
+        let instances = vec![parent.clone(), other.clone(), child.clone(), orphan.clone()];
+        let tree = GroupTree::new_with_groups(&instances, &[]);
+        let items = flatten_tree(&tree, &instances, SortOrder::Oldest);
+        let sessions: Vec<(&str, usize)> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Session { id, depth } => Some((id.as_str(), *depth)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            sessions,
+            vec![
+                (orphan.id.as_str(), 0),
+                (parent.id.as_str(), 1),
+                (child.id.as_str(), 2),
+                (other.id.as_str(), 1),
+            ]
+        );
+    }
+
     #[test]
     fn test_toggle_collapsed() {
         let mut inst = Instance::new("test", "/tmp/t");
@@ -338,6 +338,30 @@ pub struct Instance {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pinned_at: Option<DateTime<Utc>>,
 
+    /// Free-form tags and `key=value` labels (see `session::tags`) for
+    /// cross-cutting attributes the group tree can't express: ticket id,
+    /// reviewer, "blocked on CI". Edited through `session::tags::TagEdit`
+    /// so labels stay single-valued.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Tracker issue the session was started from (`aoe add --issue`).
+    /// Shown in the TUI preview and linked from the web dashboard.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<super::issues::IssueLink>,
+
+    /// Free-form markdown notes (see `session::notes`). Kept in their own
+    /// file per session rather than in `sessions.json`; `Storage::load`
+    /// leaves it empty, views that show it read it with `notes::load` or
+    /// `notes::hydrate`, and writes go through `notes::save`.
+    #[serde(skip)]
+    pub notes: String,
+    /// Summary of the session's status history (see `session::timeline`),
+    /// filled in by the TUI for the session on screen. `None` until a
+    /// transition is logged.
+    #[serde(skip)]
+    pub timeline: Option<super::timeline::TimelineSummary>,
+
     /// Scratch-session marker. When true, `project_path` points at an
     /// auto-provisioned directory under `<app_dir>/scratch/<id>/` that the
     /// deletion path removes on `aoe rm` (unless the user opts in to keeping
@@ -673,6 +697,10 @@ impl Instance {
             favorited_at: None,
             snoozed_until: None,
             pinned_at: None,
+            tags: Vec::new(),
+            issue: None,
+            notes: String::new(),
+            timeline: None,
             scratch: false,
             worktree_info: None,
             workspace_info: None,
@@ -788,6 +816,9 @@ impl Instance {
         if pre.pinned_at != post.pinned_at {
             self.pinned_at = post.pinned_at;
         }
+        if pre.tags != post.tags {
+            self.tags = post.tags.clone();
+        }
         if pre.base_branch_override != post.base_branch_override {
             self.base_branch_override = post.base_branch_override.clone();
         }
@@ -1734,6 +1765,27 @@ impl Instance {
         self.persist_session_id(profile);
         self.maybe_start_poller();
 
+        let recording = super::config::Config::load_or_warn().recording;
+        if recording.enabled {
+            let session = tmux::Session::from_name(session_name);
+            match super::recording::start_pane_recording(
+                &self.id,
+                &self.title,
+                &session,
+                &recording,
+            ) {
+                Ok(Some(path)) => {
+                    tracing::debug!(target: "session.recording", session_id = %self.id, path = %path.display(), "pane recording started")
+                }
+                Ok(None) => {
+                    tracing::debug!(target: "session.recording", session_id = %self.id, "pane already recording")
+                }
+                Err(e) => {
+                    tracing::warn!(target: "session.recording", session_id = %self.id, "failed to start pane recording: {e:#}")
+                }
+            }
+        }
+
         self.status = Status::Starting;
         self.last_start_time = Some(std::time::Instant::now());
 
@@ -1828,7 +1880,7 @@ impl Instance {
             .sandbox_info
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("sandbox_info missing for sandboxed session"))?;
-        container_config::build_container_config(
+        let config = container_config::build_container_config(
             &self.project_path,
             sandbox,
             container_config::ContainerAgentSelection::new(&self.tool, Some(&self.detect_as)),
@@ -1836,7 +1888,44 @@ impl Instance {
             &self.id,
             self.workspace_info.as_ref(),
             &self.source_profile,
-        )
+        )?;
+        #[cfg(feature = "serve")]
+        let config = {
+            let mut config = config;
+            config.volumes.extend(self.agent_socket_volume());
+            config
+        };
+        Ok(config)
+    }
+
+    /// Cockpit sessions get the runner's agent socket directory so a
+    /// socket-capable agent can connect to the runner directly instead of
+    /// through a `docker exec` pipe.
+    #[cfg(feature = "serve")]
+    fn agent_socket_volume(&self) -> Option<crate::containers::VolumeMount> {
+        if !self.cockpit_mode {
+            return None;
+        }
+        let dir = match crate::cockpit::worker_registry::agent_socket_dir_for(&self.id) {
+            Ok(dir) => dir,
+            Err(e) => {
+                tracing::warn!(target: "cockpit.registry", "No agent socket dir: {}", e);
+                return None;
+            }
+        };
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            tracing::warn!(target: "cockpit.registry",
+                "Failed to create agent socket dir {}: {}",
+                dir.display(),
+                e
+            );
+            return None;
+        }
+        Some(crate::containers::VolumeMount {
+            host_path: dir.to_string_lossy().to_string(),
+            container_path: crate::cockpit::worker_registry::CONTAINER_AGENT_SOCKET_DIR.to_string(),
+            read_only: false,
+        })
     }
 
     pub fn maybe_start_poller(&mut self) {
@@ -2092,6 +2181,7 @@ impl Instance {
         self.stop_poller();
         self.session_id_poller = None;
         self.kill_clean()?;
+        self.record_stop();
         self.start_with_resume_fallback(size, skip_on_launch)
     }
 
@@ -2466,6 +2556,7 @@ impl Instance {
     /// restarted on re-attach.
     pub fn stop(&self) -> Result<()> {
         self.kill()?;
+        self.record_stop();
 
         if self.is_sandboxed() {
             let container = containers::DockerContainer::from_session_id(&self.id);
@@ -2492,6 +2583,60 @@ impl Instance {
             } else {
                 None
             };
+            self.record_status_transition();
+        }
+    }
+
+    /// Append the status just reached to the profile's timeline.
+    pub(crate) fn record_status_transition(&self) {
+        let Some(transition) = self.status_transition() else {
+            return;
+        };
+        if let Err(e) = transition.record() {
+            tracing::debug!(target: "session.store",
+                "failed to record status transition for '{}': {}",
+                self.title,
+                e
+            );
+        }
+    }
+
+    /// The status just reached as a timeline line still to be written, for
+    /// callers that must release their lock on the instance first.
+    /// Instances that weren't loaded from a profile (tests, ad-hoc clones)
+    /// have no log to write to.
+    pub(crate) fn status_transition(&self) -> Option<super::timeline::PendingTransition> {
+        if self.source_profile.is_empty() || !super::timeline::is_tracked(self.status) {
+            return None;
+        }
+        let source = if self.is_cockpit_mode() {
+            super::timeline::StatusSource::Cockpit
+        } else if crate::hooks::read_hook_status(&self.id).is_some() {
+            super::timeline::StatusSource::Hook
+        } else {
+            super::timeline::StatusSource::Pane
+        };
+        super::timeline::PendingTransition::new(&self.source_profile, &self.id, self.status, source)
+    }
+
+    /// Log a `stopped` boundary on the timeline, so the status the agent
+    /// was in doesn't run on through the stop (or restart) and the next
+    /// Running counts as a new turn.
+    fn record_stop(&self) {
+        if self.source_profile.is_empty() {
+            return;
+        }
+        if let Err(e) = super::timeline::record(
+            &self.source_profile,
+            &self.id,
+            Status::Stopped,
+            super::timeline::StatusSource::User,
+        ) {
+            tracing::debug!(target: "session.store",
+                "failed to record stop for '{}': {}",
+                self.title,
+                e
+            );
         }
     }
 
@@ -0,0 +1,561 @@
+//! Issue-tracker lookups for `aoe add --issue`.
+//!
+//! A key is resolved to one of four providers: GitHub Issues (through the
+//! `gh` CLI, so its auth is reused), Jira and Linear (REST/GraphQL with a
+//! token read from the environment), and a local folder of markdown files
+//! for offline use. The fetched [`Issue`] supplies the session title, the
+//! worktree branch (through [`builder::branch_name_from_title`]), and the
+//! first prompt; the session keeps an [`IssueLink`] back to it.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::builder;
+use super::config::{IssueProviderKind, IssuesConfig};
+
+/// Longest branch name derived from an issue, in bytes. Issue titles can
+/// run long; the key at the front keeps truncated names unique.
+pub const MAX_BRANCH_LEN: usize = 60;
+
+/// Bounds each tracker request, `gh issue view` included.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// The issue a session was started from, shown in the TUI preview and
+/// linked from the web dashboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+pub struct IssueLink {
+    /// Tracker key, e.g. `ABC-123` or `#42`.
+    pub key: String,
+    /// Web URL of the issue. None for local markdown issues.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// A fetched issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub key: String,
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+}
+
+impl Issue {
+    /// Session title: `ABC-123: Fix login redirect`.
+    pub fn session_title(&self) -> String {
+        format!("{}: {}", self.key, self.title)
+    }
+
+    /// Worktree branch, e.g. `abc-123-fix-login-redirect`, cut at a word
+    /// boundary to [`MAX_BRANCH_LEN`].
+    pub fn branch_name(&self) -> String {
+        let branch = builder::branch_name_from_title(&format!("{} {}", self.key, self.title));
+        if branch.len() <= MAX_BRANCH_LEN {
+            return branch;
+        }
+        let cut = &branch[..MAX_BRANCH_LEN];
+        cut.rfind('-')
+            .map_or(cut, |i| &cut[..i])
+            .trim_end_matches('-')
+            .to_string()
+    }
+
+    /// First prompt for the agent: the title as a heading, then the body.
+    pub fn prompt(&self) -> String {
+        let mut prompt = format!("# {}", self.session_title());
+        if let Some(url) = &self.url {
+            prompt.push_str(&format!("\n\n{url}"));
+        }
+        let body = self.body.trim();
+        if !body.is_empty() {
+            prompt.push_str("\n\n");
+            prompt.push_str(body);
+        }
+        prompt
+    }
+
+    pub fn link(&self) -> IssueLink {
+        IssueLink {
+            key: self.key.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// Where an issue key is fetched from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueProvider {
+    /// `gh issue view`, run in `cwd` so `gh` picks the project's repo
+    /// unless `repo` names one.
+    GitHub {
+        repo: Option<String>,
+        cwd: PathBuf,
+    },
+    Jira {
+        base_url: String,
+        email: Option<String>,
+        token: String,
+    },
+    Linear {
+        token: String,
+    },
+    Local {
+        dir: PathBuf,
+    },
+}
+
+/// GitHub references name their tracker themselves: `#123`,
+/// `owner/repo#123`, or an issue URL.
+fn is_github_reference(key: &str) -> bool {
+    key.contains('#') || key.starts_with("https://github.com/")
+}
+
+/// The provider kind for `key`, or None when nothing is configured.
+fn resolve_kind(config: &IssuesConfig, key: &str) -> Option<IssueProviderKind> {
+    if is_github_reference(key) {
+        return Some(IssueProviderKind::Github);
+    }
+    if let Some(kind) = config.provider {
+        return Some(kind);
+    }
+    if key.chars().all(|c| c.is_ascii_digit()) {
+        return Some(IssueProviderKind::Github);
+    }
+    config.local_dir.as_ref().map(|_| IssueProviderKind::Local)
+}
+
+fn token_from_env(var: &str, tracker: &str) -> Result<String> {
+    match std::env::var(var) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => bail!("{tracker} issues need an API token in ${var}"),
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl IssueProvider {
+    /// Pick the provider for `key` from `config`. `cwd` is the session's
+    /// project directory, used by the GitHub provider.
+    pub fn for_key(config: &IssuesConfig, key: &str, cwd: &Path) -> Result<Self> {
+        let Some(kind) = resolve_kind(config, key) else {
+            bail!(
+                "No issue provider for '{key}'\n\
+                 Tip: set `provider` (github, jira, linear, or local) under [issues] in config.toml"
+            );
+        };
+        Ok(match kind {
+            IssueProviderKind::Github => Self::GitHub {
+                repo: config.github_repo.clone(),
+                cwd: cwd.to_path_buf(),
+            },
+            IssueProviderKind::Jira => Self::Jira {
+                base_url: config
+                    .jira_url
+                    .clone()
+                    .ok_or_else(|| anyhow!("Jira issues need `jira_url` under [issues]"))?
+                    .trim_end_matches('/')
+                    .to_string(),
+                email: config.jira_email.clone(),
+                token: token_from_env(&config.jira_token_env, "Jira")?,
+            },
+            IssueProviderKind::Linear => Self::Linear {
+                token: token_from_env(&config.linear_token_env, "Linear")?,
+            },
+            IssueProviderKind::Local => Self::Local {
+                dir: expand_home(
+                    config
+                        .local_dir
+                        .as_deref()
+                        .ok_or_else(|| anyhow!("Local issues need `local_dir` under [issues]"))?,
+                ),
+            },
+        })
+    }
+
+    pub async fn fetch(&self, key: &str) -> Result<Issue> {
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("Issue key cannot be empty");
+        }
+        match self {
+            Self::GitHub { repo, cwd } => fetch_github(key, repo.as_deref(), cwd).await,
+            Self::Jira {
+                base_url,
+                email,
+                token,
+            } => fetch_jira(key, base_url, email.as_deref(), token).await,
+            Self::Linear { token } => fetch_linear(key, token).await,
+            Self::Local { dir } => fetch_local(key, dir),
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent("agent-of-empires")
+        .timeout(HTTP_TIMEOUT)
+        .build()?)
+}
+
+#[derive(Deserialize)]
+struct GhIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    url: String,
+}
+
+/// Split `owner/repo#123` into its repo and issue reference.
+fn split_github_reference(key: &str) -> (Option<&str>, &str) {
+    match key.split_once('#') {
+        Some((repo, number)) if !repo.is_empty() => (Some(repo), number),
+        Some((_, number)) => (None, number),
+        None => (None, key),
+    }
+}
+
+async fn fetch_github(key: &str, default_repo: Option<&str>, cwd: &Path) -> Result<Issue> {
+    which::which("gh").map_err(|_| {
+        anyhow!("GitHub issues need the gh CLI\nTip: install it from https://cli.github.com and run `gh auth login`")
+    })?;
+    let (repo, reference) = split_github_reference(key);
+    let repo = repo.or(default_repo);
+    let mut cmd = tokio::process::Command::new("gh");
+    cmd.args([
+        "issue",
+        "view",
+        reference,
+        "--json",
+        "number,title,body,url",
+    ])
+    .stdin(Stdio::null())
+    .kill_on_drop(true);
+    if let Some(repo) = repo {
+        cmd.args(["--repo", repo]);
+    }
+    if cwd.is_dir() {
+        cmd.current_dir(cwd);
+    }
+    let output = tokio::time::timeout(HTTP_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "gh issue view {} timed out after {}s",
+                reference,
+                HTTP_TIMEOUT.as_secs()
+            )
+        })?
+        .context("Failed to run gh")?;
+    if !output.status.success() {
+        bail!(
+            "gh issue view {} failed: {}",
+            reference,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let issue: GhIssue =
+        serde_json::from_slice(&output.stdout).context("Unexpected output from gh issue view")?;
+    Ok(Issue {
+        key: match repo {
+            Some(repo) => format!("{repo}#{}", issue.number),
+            None => format!("#{}", issue.number),
+        },
+        title: issue.title,
+        body: issue.body,
+        url: Some(issue.url),
+    })
+}
+
+#[derive(Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Deserialize)]
+struct JiraFields {
+    summary: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+fn parse_jira(json: &str, base_url: &str) -> Result<Issue> {
+    let issue: JiraIssue = serde_json::from_str(json).context("Unexpected Jira response")?;
+    Ok(Issue {
+        url: Some(format!("{base_url}/browse/{}", issue.key)),
+        key: issue.key,
+        title: issue.fields.summary,
+        body: issue.fields.description.unwrap_or_default(),
+    })
+}
+
+/// `ABC-123`, or a numeric issue id. The key goes into the REST path, so
+/// anything else is refused rather than sent.
+fn is_jira_key(key: &str) -> bool {
+    if key.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    let Some((project, number)) = key.rsplit_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_alphabetic())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+async fn fetch_jira(key: &str, base_url: &str, email: Option<&str>, token: &str) -> Result<Issue> {
+    if !is_jira_key(key) {
+        bail!("'{key}' is not a Jira issue key (like ABC-123)");
+    }
+    // API v2 returns the description as text; v3 returns a document tree.
+    let url = format!("{base_url}/rest/api/2/issue/{key}?fields=summary,description");
+    let request = http_client()?.get(&url);
+    let request = match email {
+        Some(email) => request.basic_auth(email, Some(token)),
+        None => request.bearer_auth(token),
+    };
+    let response = request.send().await.context("Failed to reach Jira")?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("Jira returned HTTP {} for {}", status, key);
+    }
+    parse_jira(&response.text().await?, base_url)
+}
+
+#[derive(Deserialize)]
+struct LinearResponse {
+    data: Option<LinearData>,
+    #[serde(default)]
+    errors: Vec<LinearError>,
+}
+
+#[derive(Deserialize)]
+struct LinearData {
+    issue: Option<LinearIssue>,
+}
+
+#[derive(Deserialize)]
+struct LinearIssue {
+    identifier: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct LinearError {
+    message: String,
+}
+
+fn parse_linear(json: &str, key: &str) -> Result<Issue> {
+    let response: LinearResponse =
+        serde_json::from_str(json).context("Unexpected Linear response")?;
+    if let Some(error) = response.errors.first() {
+        bail!("Linear: {}", error.message);
+    }
+    let issue = response
+        .data
+        .and_then(|d| d.issue)
+        .ok_or_else(|| anyhow!("Linear issue {key} not found"))?;
+    Ok(Issue {
+        key: issue.identifier,
+        title: issue.title,
+        body: issue.description.unwrap_or_default(),
+        url: Some(issue.url),
+    })
+}
+
+async fn fetch_linear(key: &str, token: &str) -> Result<Issue> {
+    let query = serde_json::json!({
+        "query": "query($id: String!) { issue(id: $id) { identifier title description url } }",
+        "variables": { "id": key },
+    });
+    let response = http_client()?
+        .post(LINEAR_API_URL)
+        // Personal API keys go in the header as-is, without `Bearer`.
+        .header(reqwest::header::AUTHORIZATION, token)
+        .json(&query)
+        .send()
+        .await
+        .context("Failed to reach Linear")?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("Linear returned HTTP {} for {}", status, key);
+    }
+    parse_linear(&response.text().await?, key)
+}
+
+/// Split a markdown issue into title and body: the first `# ` heading, or
+/// failing that the first non-empty line, is the title.
+fn parse_markdown(key: &str, text: &str) -> Issue {
+    let mut lines = text.lines();
+    let mut title = String::new();
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if !line.is_empty() {
+            title = line.strip_prefix("# ").unwrap_or(line).trim().to_string();
+            break;
+        }
+    }
+    Issue {
+        key: key.to_string(),
+        title: if title.is_empty() {
+            key.to_string()
+        } else {
+            title
+        },
+        body: lines.collect::<Vec<_>>().join("\n").trim().to_string(),
+        url: None,
+    }
+}
+
+fn fetch_local(key: &str, dir: &Path) -> Result<Issue> {
+    let wanted = format!("{key}.md");
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read issue folder {}", dir.display()))?;
+    let path = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .find(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.eq_ignore_ascii_case(&wanted))
+        })
+        .ok_or_else(|| anyhow!("No {} in {}", wanted, dir.display()))?;
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(parse_markdown(key, &text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(key: &str, title: &str) -> Issue {
+        Issue {
+            key: key.to_string(),
+            title: title.to_string(),
+            body: String::new(),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn keys_pick_a_provider() {
+        let mut config = IssuesConfig::default();
+        assert_eq!(resolve_kind(&config, "ABC-123"), None);
+        assert_eq!(resolve_kind(&config, "42"), Some(IssueProviderKind::Github));
+        config.provider = Some(IssueProviderKind::Jira);
+        assert_eq!(
+            resolve_kind(&config, "ABC-123"),
+            Some(IssueProviderKind::Jira)
+        );
+        assert_eq!(
+            resolve_kind(&config, "acme/web#42"),
+            Some(IssueProviderKind::Github)
+        );
+        assert_eq!(
+            split_github_reference("acme/web#42"),
+            (Some("acme/web"), "42")
+        );
+        assert_eq!(split_github_reference("#42"), (None, "42"));
+
+        let config = IssuesConfig {
+            local_dir: Some(PathBuf::from("/tmp/issues")),
+            ..IssuesConfig::default()
+        };
+        assert_eq!(
+            resolve_kind(&config, "NOTE-1"),
+            Some(IssueProviderKind::Local)
+        );
+    }
+
+    #[test]
+    fn jira_keys_are_checked_before_they_reach_the_url() {
+        assert!(is_jira_key("ABC-123"));
+        assert!(is_jira_key("web_2-7"));
+        assert!(is_jira_key("10042"));
+        assert!(!is_jira_key("ABC-"));
+        assert!(!is_jira_key("-12"));
+        assert!(!is_jira_key("../../myself"));
+        assert!(!is_jira_key("ABC-1?expand=all"));
+    }
+
+    #[test]
+    fn titles_branches_and_prompts_derive_from_the_issue() {
+        let mut fix = issue("ABC-123", "Fix: login redirect @ mobile");
+        assert_eq!(fix.session_title(), "ABC-123: Fix: login redirect @ mobile");
+        assert_eq!(fix.branch_name(), "abc-123-fix-login-redirect-mobile");
+        assert_eq!(issue("#42", "Crash").branch_name(), "42-crash");
+
+        let long = issue("ABC-1", &"word ".repeat(30));
+        let branch = long.branch_name();
+        assert!(branch.len() <= MAX_BRANCH_LEN);
+        assert!(branch.starts_with("abc-1-word") && branch.ends_with("word"));
+
+        fix.body = "Steps to reproduce\n".to_string();
+        fix.url = Some("https://x/ABC-123".to_string());
+        assert_eq!(
+            fix.prompt(),
+            "# ABC-123: Fix: login redirect @ mobile\n\nhttps://x/ABC-123\n\nSteps to reproduce"
+        );
+    }
+
+    #[test]
+    fn tracker_responses_parse() {
+        let jira = parse_jira(
+            r#"{"key":"ABC-1","fields":{"summary":"Fix it","description":null}}"#,
+            "https://acme.atlassian.net",
+        )
+        .unwrap();
+        assert_eq!(jira.title, "Fix it");
+        assert_eq!(
+            jira.url.as_deref(),
+            Some("https://acme.atlassian.net/browse/ABC-1")
+        );
+
+        let linear = parse_linear(
+            r#"{"data":{"issue":{"identifier":"ENG-7","title":"T","description":"B","url":"https://linear.app/x/issue/ENG-7"}}}"#,
+            "ENG-7",
+        )
+        .unwrap();
+        assert_eq!((linear.key.as_str(), linear.body.as_str()), ("ENG-7", "B"));
+        assert!(parse_linear(r#"{"data":{"issue":null}}"#, "ENG-8").is_err());
+        assert!(parse_linear(
+            r#"{"data":null,"errors":[{"message":"Authentication required"}]}"#,
+            "ENG-8"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn local_issues_are_read_from_markdown_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("note-1.md"),
+            "\n# Tidy the importer\n\nIt drops rows.\n",
+        )
+        .unwrap();
+        let issue = fetch_local("NOTE-1", dir.path()).unwrap();
+        assert_eq!(issue.title, "Tidy the importer");
+        assert_eq!(issue.body, "It drops rows.");
+        assert!(issue.url.is_none());
+        assert!(fetch_local("NOTE-2", dir.path()).is_err());
+        assert_eq!(parse_markdown("K-1", "").title, "K-1");
+    }
+}
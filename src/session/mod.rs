@@ -5,28 +5,41 @@ pub(crate) mod capture;
 pub mod civilizations;
 pub mod config;
 pub(crate) mod container_config;
+pub mod coordinator;
 pub mod deletion;
 pub(crate) mod environment;
+pub mod export;
+pub mod filter;
 mod groups;
 mod instance;
+pub mod issues;
+pub mod notes;
 pub mod poller;
 pub mod profile_config;
 pub mod projects;
+pub mod recording;
 pub(crate) mod recovery;
 pub mod repo_config;
+pub mod reviews;
+pub mod schedules;
 pub mod scratch;
+pub mod search;
 pub(crate) mod serde_helpers;
 pub mod stop;
 mod storage;
+pub mod tags;
+pub mod timeline;
+pub mod transcript;
 
 pub use crate::sound::{SoundConfig, SoundConfigOverride};
 pub use crate::status_hooks::{StatusHookConfig, StatusHookConfigOverride};
 pub(crate) use capture::is_valid_session_id;
 pub use config::{
     get_update_settings, load_config, save_config, validate_snooze_duration, ClickAction, Config,
-    ContainerRuntimeName, DefaultTerminalMode, GroupByMode, NewSessionAttachMode, RowTagMode,
-    SandboxConfig, SessionConfig, ThemeConfig, TmuxClipboardMode, TmuxMouseMode, TmuxStatusBarMode,
-    UpdatesConfig, VolumeIgnoresStrategy, WorktreeConfig,
+    ContainerRuntimeName, DefaultTerminalMode, GroupByMode, IssueProviderKind, IssuesConfig,
+    McpConfig, NewSessionAttachMode, RecordingConfig, RowTagMode, SandboxConfig, SessionConfig,
+    ThemeConfig, TmuxClipboardMode, TmuxMouseMode, TmuxStatusBarMode, UpdatesConfig,
+    VolumeIgnoresStrategy, WorktreeConfig,
 };
 pub(crate) use environment::user_shell;
 pub use environment::{validate_env_entries, validate_env_entry};
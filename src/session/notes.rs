@@ -0,0 +1,202 @@
+//! Per-session markdown notes: the context a user juggles alongside the
+//! agent ("waiting for infra to merge X, then ask it to rerun the
+//! migration"). Each session's notes live in their own file at
+//! `<app_dir>/profiles/{profile}/notes/{id}.md` so `sessions.json`, which
+//! every status change rewrites, stays small. `Storage::load` leaves
+//! `Instance::notes` empty; the views that show notes fill it in with
+//! [`load`] or [`hydrate`], and writes go through [`save`] only.
+
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::storage::{atomic_write, profile_dir};
+use super::Instance;
+
+const NOTES_DIR: &str = "notes";
+const NOTES_EXTENSION: &str = "md";
+
+/// Upper bound on one session's notes. The dashboard holds every
+/// session's notes, so this keeps a pasted log from bloating it.
+pub const MAX_NOTES_BYTES: usize = 64 * 1024;
+
+fn notes_path(profile_dir: &Path, session_id: &str) -> PathBuf {
+    profile_dir
+        .join(NOTES_DIR)
+        .join(format!("{session_id}.{NOTES_EXTENSION}"))
+}
+
+/// Fill in `notes` on every instance from its profile's notes directory.
+/// One directory read per profile; profiles without notes cost a failed
+/// `read_dir`.
+pub fn hydrate(instances: &mut [Instance]) {
+    let mut notes: HashMap<(String, String), String> = HashMap::new();
+    let mut profiles: Vec<&str> = instances
+        .iter()
+        .map(|i| i.source_profile.as_str())
+        .collect();
+    profiles.sort_unstable();
+    profiles.dedup();
+    for profile in profiles {
+        let Ok(dir) = profile_dir(profile) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(dir.join(NOTES_DIR)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(NOTES_EXTENSION) {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Ok(text) = fs::read_to_string(&path) {
+                notes.insert(
+                    (profile.to_string(), id.to_string()),
+                    text.trim_end().to_string(),
+                );
+            }
+        }
+    }
+    for inst in instances {
+        inst.notes = notes
+            .remove(&(inst.source_profile.clone(), inst.id.clone()))
+            .unwrap_or_default();
+    }
+}
+
+/// When `profile`'s notes last changed: the notes directory's mtime,
+/// which every [`save`] and [`remove`] bumps. None before the first note.
+pub fn stamp(profile: &str) -> Option<SystemTime> {
+    fs::metadata(profile_dir(profile).ok()?.join(NOTES_DIR))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// `session_id`'s notes, or an empty string when it has none.
+pub fn load(profile: &str, session_id: &str) -> Result<String> {
+    let path = notes_path(&profile_dir(profile)?, session_id);
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(text.trim_end().to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replace `session_id`'s notes, returning the stored text (trailing
+/// whitespace trimmed). Blank notes delete the file.
+pub fn save(profile: &str, session_id: &str, text: &str) -> Result<String> {
+    let text = text.trim_end();
+    if text.len() > MAX_NOTES_BYTES {
+        bail!(
+            "Notes are {} KiB; the limit is {} KiB",
+            text.len().div_ceil(1024),
+            MAX_NOTES_BYTES / 1024
+        );
+    }
+    let profile_dir = profile_dir(profile)?;
+    let path = notes_path(&profile_dir, session_id);
+    if text.is_empty() {
+        remove_file(&path)?;
+    } else {
+        fs::create_dir_all(profile_dir.join(NOTES_DIR))?;
+        atomic_write(&path, format!("{text}\n").as_bytes())?;
+    }
+    Ok(text.to_string())
+}
+
+/// Delete `session_id`'s notes. Called when the session is deleted.
+pub fn remove(profile: &str, session_id: &str) -> Result<()> {
+    remove_file(&notes_path(&profile_dir(profile)?, session_id))
+}
+
+/// Carry `session_id`'s notes along when it moves to another profile.
+pub fn move_to_profile(from: &str, to: &str, session_id: &str) -> Result<()> {
+    let text = load(from, session_id)?;
+    if text.is_empty() {
+        return Ok(());
+    }
+    save(to, session_id, &text)?;
+    remove(from, session_id)
+}
+
+fn remove_file(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    #[test]
+    #[serial]
+    fn notes_round_trip_and_blank_notes_delete_the_file() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        assert_eq!(load("p", "abc")?, "");
+        assert_eq!(
+            save("p", "abc", "waiting on infra\n- then rerun\n\n")?,
+            "waiting on infra\n- then rerun"
+        );
+        assert_eq!(load("p", "abc")?, "waiting on infra\n- then rerun");
+
+        let path = notes_path(&profile_dir("p")?, "abc");
+        assert!(path.exists());
+        save("p", "abc", "  \n")?;
+        assert!(!path.exists());
+        assert_eq!(load("p", "abc")?, "");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn hydrate_fills_notes_and_moves_follow_the_session() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let mut instances = vec![Instance::new("a", "/tmp/a"), Instance::new("b", "/tmp/b")];
+        for inst in &mut instances {
+            inst.source_profile = "p".to_string();
+        }
+        assert_eq!(stamp("p"), None);
+        save("p", &instances[0].id, "remember the migration")?;
+        assert!(stamp("p").is_some());
+        hydrate(&mut instances);
+        assert_eq!(instances[0].notes, "remember the migration");
+        assert_eq!(instances[1].notes, "");
+
+        move_to_profile("p", "q", &instances[0].id)?;
+        assert_eq!(load("p", &instances[0].id)?, "");
+        assert_eq!(load("q", &instances[0].id)?, "remember the migration");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn oversized_notes_are_rejected() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let err = save("p", "abc", &"x".repeat(MAX_NOTES_BYTES + 1)).unwrap_err();
+        assert!(err.to_string().contains("limit is 64 KiB"), "{err}");
+        assert_eq!(load("p", "abc")?, "");
+        Ok(())
+    }
+}
@@ -83,6 +83,12 @@ pub struct CockpitConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub replay_bytes: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_max_age_days: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_max_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_keep_archived: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub node_path: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub show_tool_durations: Option<bool>,
@@ -96,6 +102,12 @@ pub struct CockpitConfigOverride {
     pub silent_orphan_grace_secs: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub silent_orphan_fast_grace_secs: Option<u32>,
+    /// Replaces the global rule list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_rules: Option<Vec<crate::session::config::ApprovalRule>>,
+    /// Replaces the global server list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<Vec<crate::session::config::McpServerConfig>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -619,6 +631,15 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(v) = cockpit_override.replay_bytes {
             global.cockpit.replay_bytes = v;
         }
+        if let Some(v) = cockpit_override.history_max_age_days {
+            global.cockpit.history_max_age_days = v;
+        }
+        if let Some(v) = cockpit_override.history_max_bytes {
+            global.cockpit.history_max_bytes = v;
+        }
+        if let Some(v) = cockpit_override.history_keep_archived {
+            global.cockpit.history_keep_archived = v;
+        }
         if let Some(ref v) = cockpit_override.node_path {
             global.cockpit.node_path = v.clone();
         }
@@ -640,6 +661,12 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         if let Some(v) = cockpit_override.silent_orphan_fast_grace_secs {
             global.cockpit.silent_orphan_fast_grace_secs = v;
         }
+        if let Some(ref v) = cockpit_override.approval_rules {
+            global.cockpit.approval_rules = v.clone();
+        }
+        if let Some(ref v) = cockpit_override.mcp_servers {
+            global.cockpit.mcp_servers = v.clone();
+        }
     }
 
     global
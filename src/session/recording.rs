@@ -0,0 +1,662 @@
+//! Opt-in asciicast v2 recordings of agent panes.
+//!
+//! Scrollback is gone by the time anyone asks "what did the agent show
+//! three hours ago", so with `recording.enabled` set every agent pane is
+//! recorded to `<app_dir>/recordings/<instance-id>/`. Two sources write
+//! there:
+//!
+//! - Session start (TUI, CLI, or the web API) attaches `tmux pipe-pane`
+//!   to the agent pane and streams its output into the hidden
+//!   `aoe __record-pane` sink, which timestamps it ([`run_pane_sink`]).
+//! - `aoe serve` tees the agent terminal WebSocket's PTY stream,
+//!   including keystrokes as `"i"` events, so what a browser user typed
+//!   is on record too.
+//!
+//! Files are named `<UTC timestamp>-<source>.cast` and play back with
+//! `aoe session replay` or any asciicast v2 player. Retention limits from
+//! [`RecordingConfig`] are applied each time a recording starts, and the
+//! session's directory is removed when the session is deleted.
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+use super::config::RecordingConfig;
+
+/// Subdirectory under the app data dir holding one child directory of
+/// recordings per session, keyed on `Instance.id`.
+const RECORDINGS_SUBDIR: &str = "recordings";
+
+const CAST_EXTENSION: &str = "cast";
+
+/// Marker written as the last event when a recording hits
+/// `recording.max_file_mb`.
+const SIZE_LIMIT_MARKER: &str = "recording stopped: size limit reached";
+
+/// Where a recording's bytes came from. Part of the file name so the
+/// pane stream and per-browser streams can be told apart in listings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingSource {
+    /// `tmux pipe-pane` on the agent pane.
+    Pane,
+    /// A web terminal connection on `aoe serve`.
+    Web,
+}
+
+impl RecordingSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            RecordingSource::Pane => "pane",
+            RecordingSource::Web => "web",
+        }
+    }
+}
+
+/// One recording on disk, as listed by `aoe session replay --list` and
+/// `GET /api/sessions/{id}/recordings`.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+pub struct RecordingInfo {
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// Directory holding `instance_id`'s recordings. Not created here.
+/// Rejects IDs that could escape the recordings root, since the web
+/// endpoint feeds request paths through this.
+pub fn session_recordings_dir(instance_id: &str) -> Result<PathBuf> {
+    if !super::is_valid_session_id(instance_id) {
+        bail!("Invalid session id: {instance_id}");
+    }
+    Ok(super::get_app_dir()?
+        .join(RECORDINGS_SUBDIR)
+        .join(instance_id))
+}
+
+/// Recordings for `instance_id`, newest first. A session that never
+/// recorded anything has an empty list.
+pub fn list_recordings(instance_id: &str) -> Result<Vec<RecordingInfo>> {
+    list_dir(&session_recordings_dir(instance_id)?)
+}
+
+fn list_dir(dir: &Path) -> Result<Vec<RecordingInfo>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", dir.display()));
+        }
+    };
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !is_recording_name(&name) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        out.push(RecordingInfo {
+            name,
+            size_bytes: meta.len(),
+            modified_at: modified.into(),
+        });
+    }
+    // Newest first; the name (a timestamp) breaks mtime ties.
+    out.sort_by(|a, b| {
+        b.modified_at
+            .cmp(&a.modified_at)
+            .then_with(|| b.name.cmp(&a.name))
+    });
+    Ok(out)
+}
+
+/// Plain file name with the `.cast` extension: no separators, no leading
+/// dot. Anything else is refused before touching the filesystem.
+fn is_recording_name(name: &str) -> bool {
+    !name.starts_with('.')
+        && !name.contains(['/', '\\'])
+        && Path::new(name).extension().and_then(|e| e.to_str()) == Some(CAST_EXTENSION)
+}
+
+/// Resolve `name` to a recording of `instance_id`, or the newest one when
+/// `name` is `None`.
+pub fn resolve_recording(instance_id: &str, name: Option<&str>) -> Result<PathBuf> {
+    let dir = session_recordings_dir(instance_id)?;
+    match name {
+        Some(name) => {
+            if !is_recording_name(name) {
+                bail!("Invalid recording name: {name}");
+            }
+            let path = dir.join(name);
+            if !path.is_file() {
+                bail!("Recording not found: {name}");
+            }
+            Ok(path)
+        }
+        None => match list_dir(&dir)?.into_iter().next() {
+            Some(latest) => Ok(dir.join(latest.name)),
+            None => bail!("No recordings for this session"),
+        },
+    }
+}
+
+/// Apply retention to `instance_id`'s recordings and return the path for
+/// a new one from `source`, creating the directory as needed. Retention
+/// leaves room for the recording about to start so the count settles at
+/// `max_per_session`.
+pub fn prepare_recording(
+    instance_id: &str,
+    source: RecordingSource,
+    config: &RecordingConfig,
+) -> Result<PathBuf> {
+    let dir = session_recordings_dir(instance_id)?;
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create recordings dir at {}", dir.display()))?;
+    let removed = prune_dir(&dir, config, SystemTime::now());
+    if removed > 0 {
+        tracing::debug!(target: "session.recording", session_id = %instance_id, removed, "pruned old recordings");
+    }
+    let stamp = Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+    Ok(dir.join(format!("{stamp}-{}.{CAST_EXTENSION}", source.as_str())))
+}
+
+/// Remove recordings past `max_age_days`, then the oldest beyond
+/// `max_per_session - 1`. Recordings still being written are never
+/// removed, though they count toward the limit. Returns how many files
+/// were removed. Failures are logged and skipped: retention must never
+/// block a session start.
+fn prune_dir(dir: &Path, config: &RecordingConfig, now: SystemTime) -> usize {
+    let Ok(recordings) = list_dir(dir) else {
+        return 0;
+    };
+    let max_age = (config.max_age_days > 0)
+        .then(|| Duration::from_secs(config.max_age_days.saturating_mul(24 * 60 * 60)));
+    let keep = match config.max_per_session {
+        0 => usize::MAX,
+        n => n - 1,
+    };
+
+    let mut removed = 0;
+    let mut kept = 0;
+    for rec in recordings {
+        let modified: SystemTime = rec.modified_at.into();
+        let expired =
+            max_age.is_some_and(|max| now.duration_since(modified).is_ok_and(|age| age > max));
+        let path = dir.join(&rec.name);
+        if is_live(&path) || (!expired && kept < keep) {
+            kept += 1;
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                tracing::warn!(target: "session.recording", "failed to prune recording {}: {e}", rec.name)
+            }
+        }
+    }
+    removed
+}
+
+/// Whether a [`CastWriter`] still holds `path` open. Writers keep an
+/// exclusive lock for their lifetime, released when the process exits.
+fn is_live(path: &Path) -> bool {
+    File::open(path).is_ok_and(|file| match FileExt::try_lock_shared(&file) {
+        Ok(()) => {
+            let _ = FileExt::unlock(&file);
+            false
+        }
+        Err(_) => true,
+    })
+}
+
+/// Delete every recording of `instance_id`. Called from session deletion;
+/// best-effort like the other per-session directory cleanups.
+pub fn remove_recordings(instance_id: &str) {
+    let Ok(dir) = session_recordings_dir(instance_id) else {
+        return;
+    };
+    match fs::remove_dir_all(&dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            tracing::warn!(target: "session.recording", session_id = %instance_id, "failed to remove recordings: {e}")
+        }
+    }
+}
+
+/// Start recording the agent pane of `session` via `tmux pipe-pane`.
+/// Returns the recording's path, or `None` when the pane is already
+/// piped (a restart that kept the pane), in which case nothing is pruned
+/// and the existing recording carries on.
+pub fn start_pane_recording(
+    instance_id: &str,
+    title: &str,
+    session: &crate::tmux::Session,
+    config: &RecordingConfig,
+) -> Result<Option<PathBuf>> {
+    use super::environment::shell_escape;
+
+    if session.pane_pipe_active() {
+        return Ok(None);
+    }
+    let path = prepare_recording(instance_id, RecordingSource::Pane, config)?;
+    let (cols, rows) = session.pane_size().unwrap_or((80, 24));
+    let exe = std::env::current_exe().context("Failed to resolve the aoe binary path")?;
+    let command = format!(
+        "exec {} __record-pane --out {} --cols {cols} --rows {rows} --max-bytes {} --title {}",
+        shell_escape(&exe.to_string_lossy()),
+        shell_escape(&path.to_string_lossy()),
+        config.max_file_bytes(),
+        shell_escape(title),
+    );
+    session.pipe_pane_output(&command)?;
+    Ok(Some(path))
+}
+
+/// asciicast v2 header line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CastHeader {
+    pub version: u32,
+    pub width: u16,
+    pub height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// One asciicast v2 event: seconds since the start, event code (`"o"`
+/// output, `"i"` input, `"r"` resize, `"m"` marker), and payload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastEvent {
+    pub time: f64,
+    pub code: String,
+    pub data: String,
+}
+
+/// Reassembles UTF-8 sequences split across reads. asciicast stores
+/// event data as JSON strings, so a multi-byte character cut in half by
+/// a 4 KiB read must wait for its tail instead of becoming two U+FFFD.
+#[derive(Default)]
+struct Utf8Carry(Vec<u8>);
+
+impl Utf8Carry {
+    fn decode(&mut self, chunk: &[u8]) -> String {
+        self.0.extend_from_slice(chunk);
+        let mut out = String::new();
+        let mut rest: &[u8] = &self.0;
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(s) => {
+                    out.push_str(s);
+                    rest = &[];
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    out.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match e.error_len() {
+                        Some(len) => {
+                            out.push(char::REPLACEMENT_CHARACTER);
+                            rest = &after[len..];
+                        }
+                        // Incomplete sequence at the end: keep it for the
+                        // next chunk.
+                        None => {
+                            rest = after;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.0 = rest.to_vec();
+        out
+    }
+}
+
+/// Appends asciicast v2 events to a file. Write failures and the size cap
+/// stop the recording quietly (with one log line) rather than erroring:
+/// callers sit on terminal I/O paths that must keep flowing regardless.
+pub struct CastWriter {
+    file: File,
+    started: Instant,
+    written: u64,
+    max_bytes: u64,
+    stopped: bool,
+    output_carry: Utf8Carry,
+    input_carry: Utf8Carry,
+}
+
+impl CastWriter {
+    /// Create `path` (which must not exist) and write the header.
+    /// `max_bytes` of 0 means no size cap. The file stays locked while the
+    /// writer is alive so retention can tell it's live.
+    pub fn create(
+        path: &Path,
+        cols: u16,
+        rows: u16,
+        title: Option<&str>,
+        max_bytes: u64,
+    ) -> Result<Self> {
+        let mut file = File::options()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .with_context(|| format!("Failed to create recording at {}", path.display()))?;
+        file.try_lock_exclusive()
+            .with_context(|| format!("Failed to lock recording at {}", path.display()))?;
+        let header = CastHeader {
+            version: 2,
+            width: cols,
+            height: rows,
+            timestamp: Some(Utc::now().timestamp()),
+            title: title.map(str::to_string),
+        };
+        let mut line = serde_json::to_string(&header)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        Ok(Self {
+            file,
+            started: Instant::now(),
+            written: line.len() as u64,
+            max_bytes,
+            stopped: false,
+            output_carry: Utf8Carry::default(),
+            input_carry: Utf8Carry::default(),
+        })
+    }
+
+    pub fn output(&mut self, bytes: &[u8]) {
+        let data = self.output_carry.decode(bytes);
+        self.event("o", &data);
+    }
+
+    pub fn input(&mut self, bytes: &[u8]) {
+        let data = self.input_carry.decode(bytes);
+        self.event("i", &data);
+    }
+
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        self.event("r", &format!("{cols}x{rows}"));
+    }
+
+    fn event(&mut self, code: &str, data: &str) {
+        if self.stopped || data.is_empty() {
+            return;
+        }
+        let time = (self.started.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        let Ok(mut line) = serde_json::to_string(&(time, code, data)) else {
+            return;
+        };
+        line.push('\n');
+        if self.max_bytes > 0 && self.written + line.len() as u64 > self.max_bytes {
+            if let Ok(mut marker) = serde_json::to_string(&(time, "m", SIZE_LIMIT_MARKER)) {
+                marker.push('\n');
+                let _ = self.file.write_all(marker.as_bytes());
+            }
+            tracing::info!(target: "session.recording", max_bytes = self.max_bytes, "recording reached its size limit");
+            self.stopped = true;
+            return;
+        }
+        if let Err(e) = self.file.write_all(line.as_bytes()) {
+            tracing::warn!(target: "session.recording", "recording write failed, stopping: {e}");
+            self.stopped = true;
+            return;
+        }
+        self.written += line.len() as u64;
+    }
+}
+
+/// Parse an asciicast v2 file. Event lines that fail to parse are skipped
+/// so a recording cut off mid-line (agent killed, disk full) still plays.
+pub fn read_cast(path: &Path) -> Result<(CastHeader, Vec<CastEvent>)> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+    let header_line = lines.next().transpose()?.context("Recording is empty")?;
+    let header: CastHeader =
+        serde_json::from_str(&header_line).context("Recording has no asciicast header")?;
+    if header.version != 2 {
+        bail!("Unsupported asciicast version {}", header.version);
+    }
+    let mut events = Vec::new();
+    for line in lines {
+        let line = line?;
+        if let Ok((time, code, data)) = serde_json::from_str::<(f64, String, String)>(&line) {
+            events.push(CastEvent { time, code, data });
+        }
+    }
+    Ok((header, events))
+}
+
+/// Delay before each output event when playing back at `speed`, with
+/// pauses longer than `max_idle` seconds (before scaling) shortened to
+/// `max_idle`. Non-output events are dropped.
+pub fn playback_schedule(
+    events: &[CastEvent],
+    speed: f64,
+    max_idle: Option<f64>,
+) -> Vec<(Duration, &str)> {
+    let speed = if speed > 0.0 { speed } else { 1.0 };
+    let mut prev = 0.0;
+    let mut out = Vec::new();
+    for event in events.iter().filter(|e| e.code == "o") {
+        let mut gap = (event.time - prev).max(0.0);
+        prev = event.time;
+        if let Some(max) = max_idle {
+            gap = gap.min(max);
+        }
+        out.push((Duration::from_secs_f64(gap / speed), event.data.as_str()));
+    }
+    out
+}
+
+/// Arguments for the hidden `aoe __record-pane` sink that `tmux
+/// pipe-pane` runs with the pane's output on stdin.
+#[derive(Args, Debug, Clone)]
+pub struct RecordPaneArgs {
+    #[arg(long)]
+    pub out: PathBuf,
+    #[arg(long, default_value_t = 80)]
+    pub cols: u16,
+    #[arg(long, default_value_t = 24)]
+    pub rows: u16,
+    #[arg(long, default_value_t = 0)]
+    pub max_bytes: u64,
+    #[arg(long)]
+    pub title: Option<String>,
+}
+
+/// Copy stdin into an asciicast recording until EOF (tmux closes the pipe
+/// when the pane goes away).
+pub fn run_pane_sink(args: RecordPaneArgs) -> Result<()> {
+    let mut writer = CastWriter::create(
+        &args.out,
+        args.cols,
+        args.rows,
+        args.title.as_deref(),
+        args.max_bytes,
+    )?;
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = [0u8; 4096];
+    loop {
+        match stdin.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => writer.output(&buf[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_per_session: usize, max_age_days: u64) -> RecordingConfig {
+        RecordingConfig {
+            enabled: true,
+            max_per_session,
+            max_age_days,
+            max_file_mb: 0,
+        }
+    }
+
+    fn touch(dir: &Path, name: &str, age: Duration) {
+        let path = dir.join(name);
+        let file = File::create(&path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn utf8_carry_rejoins_split_characters() {
+        let mut carry = Utf8Carry::default();
+        let bytes = "héllo".as_bytes();
+        // Split inside the two-byte 'é'.
+        assert_eq!(carry.decode(&bytes[..2]), "h");
+        assert_eq!(carry.decode(&bytes[2..]), "éllo");
+        assert_eq!(carry.decode(b"a\xffb"), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn writer_round_trips_through_reader() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("a.cast");
+        let mut w = CastWriter::create(&path, 120, 40, Some("demo"), 0).unwrap();
+        w.output(b"\x1b[1mhi\x1b[0m\r\n");
+        w.input(b"y");
+        w.resize(100, 30);
+        drop(w);
+
+        let (header, events) = read_cast(&path).unwrap();
+        assert_eq!(header.version, 2);
+        assert_eq!((header.width, header.height), (120, 40));
+        assert_eq!(header.title.as_deref(), Some("demo"));
+        let codes: Vec<&str> = events.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, ["o", "i", "r"]);
+        assert_eq!(events[0].data, "\x1b[1mhi\x1b[0m\r\n");
+        assert_eq!(events[2].data, "100x30");
+
+        assert!(CastWriter::create(&path, 80, 24, None, 0).is_err());
+    }
+
+    #[test]
+    fn writer_stops_at_size_limit_with_marker() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("a.cast");
+        let mut w = CastWriter::create(&path, 80, 24, None, 200).unwrap();
+        for _ in 0..50 {
+            w.output(b"0123456789");
+        }
+        drop(w);
+
+        let (_, events) = read_cast(&path).unwrap();
+        assert!(events.len() < 50);
+        let last = events.last().unwrap();
+        assert_eq!(last.code, "m");
+        assert_eq!(last.data, SIZE_LIMIT_MARKER);
+    }
+
+    #[test]
+    fn read_cast_skips_truncated_tail() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("a.cast");
+        fs::write(
+            &path,
+            "{\"version\":2,\"width\":80,\"height\":24}\n[0.5,\"o\",\"ok\"]\n[1.0,\"o\",\"cut",
+        )
+        .unwrap();
+        let (_, events) = read_cast(&path).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "ok");
+    }
+
+    #[test]
+    fn playback_schedule_scales_and_caps_idle() {
+        let ev = |time: f64, code: &str, data: &str| CastEvent {
+            time,
+            code: code.to_string(),
+            data: data.to_string(),
+        };
+        let events = [
+            ev(1.0, "o", "a"),
+            ev(1.5, "i", "x"),
+            ev(2.0, "o", "b"),
+            ev(30.0, "o", "c"),
+        ];
+        let schedule = playback_schedule(&events, 2.0, Some(5.0));
+        let delays: Vec<Duration> = schedule.iter().map(|(d, _)| *d).collect();
+        assert_eq!(
+            delays,
+            [
+                Duration::from_millis(500),
+                Duration::from_millis(500),
+                Duration::from_millis(2500),
+            ]
+        );
+        let data: Vec<&str> = schedule.iter().map(|(_, d)| *d).collect();
+        assert_eq!(data, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn prune_keeps_newest_and_drops_expired() {
+        let tmp = tempfile::tempdir().unwrap();
+        let day = Duration::from_secs(24 * 60 * 60);
+        touch(tmp.path(), "1-pane.cast", Duration::from_secs(10));
+        touch(tmp.path(), "2-pane.cast", Duration::from_secs(20));
+        touch(tmp.path(), "3-web.cast", Duration::from_secs(30));
+        touch(tmp.path(), "4-pane.cast", day * 40);
+        touch(tmp.path(), "notes.txt", day * 400);
+
+        // max 3 leaves room for the recording about to start: keep 2.
+        assert_eq!(prune_dir(tmp.path(), &config(3, 30), SystemTime::now()), 2);
+        let names: Vec<String> = list_dir(tmp.path())
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, ["1-pane.cast", "2-pane.cast"]);
+        assert!(tmp.path().join("notes.txt").exists());
+
+        // Zero disables both limits.
+        touch(tmp.path(), "5-pane.cast", day * 400);
+        assert_eq!(prune_dir(tmp.path(), &config(0, 0), SystemTime::now()), 0);
+    }
+
+    #[test]
+    fn prune_never_removes_a_live_recording() {
+        let tmp = tempfile::tempdir().unwrap();
+        touch(tmp.path(), "1-pane.cast", Duration::from_secs(60));
+        let _live = CastWriter::create(&tmp.path().join("2-pane.cast"), 80, 24, None, 0).unwrap();
+
+        // max 1 keeps nothing, but the open recording survives.
+        assert_eq!(prune_dir(tmp.path(), &config(1, 0), SystemTime::now()), 1);
+        let names: Vec<String> = list_dir(tmp.path())
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, ["2-pane.cast"]);
+    }
+
+    #[test]
+    fn recording_names_cannot_escape_the_session_dir() {
+        assert!(is_recording_name("20261018T101500.123Z-pane.cast"));
+        assert!(!is_recording_name("../other/x.cast"));
+        assert!(!is_recording_name("a\\b.cast"));
+        assert!(!is_recording_name(".hidden.cast"));
+        assert!(!is_recording_name("config.toml"));
+        assert!(session_recordings_dir("../etc").is_err());
+    }
+}
@@ -20,10 +20,10 @@ pub enum HookProgress {
     Output(String),
 }
 
-use super::config::Config;
+use super::config::{ApprovalAction, ApprovalRule, Config, McpServerConfig};
 use super::profile_config::{
-    HooksConfigOverride, ProfileConfig, SandboxConfigOverride, SessionConfigOverride,
-    TmuxConfigOverride, UpdatesConfigOverride, WorktreeConfigOverride,
+    CockpitConfigOverride, HooksConfigOverride, ProfileConfig, SandboxConfigOverride,
+    SessionConfigOverride, TmuxConfigOverride, UpdatesConfigOverride, WorktreeConfigOverride,
 };
 
 /// Repository-level configuration loaded from `.agent-of-empires/config.toml`.
@@ -49,6 +49,25 @@ pub struct RepoConfig {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sound: Option<crate::sound::SoundConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cockpit: Option<RepoCockpitConfig>,
+}
+
+/// Cockpit settings a repository may set. Repo rules are evaluated ahead
+/// of the profile and global rules, but only `deny` and `ask` rules take
+/// effect: anyone who can commit to the repo could otherwise auto-approve
+/// commands on every contributor's machine.
+///
+/// Repo MCP servers are added to (or replace same-named) profile servers.
+/// They are marked `from_repo` so the cockpit can refuse to start a
+/// repo-declared stdio server outside a sandbox, for the same reason.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoCockpitConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approval_rules: Vec<ApprovalRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_servers: Vec<McpServerConfig>,
 }
 
 /// Hook commands to run at various lifecycle points.
@@ -224,6 +243,33 @@ pub fn merge_repo_config(mut config: Config, repo: &RepoConfig) -> Config {
         crate::sound::apply_sound_overrides(&mut config.sound, sound_override);
     }
 
+    if let Some(ref cockpit) = repo.cockpit {
+        let (allow, mut rules): (Vec<_>, Vec<_>) = cockpit
+            .approval_rules
+            .iter()
+            .cloned()
+            .partition(|r| r.action == ApprovalAction::Allow);
+        if !allow.is_empty() {
+            tracing::warn!(target: "session.store",
+                "Ignoring {} repo-level `allow` approval rule(s); only deny/ask rules apply from repo config",
+                allow.len()
+            );
+        }
+        rules.append(&mut config.cockpit.approval_rules);
+        config.cockpit.approval_rules = rules;
+
+        for server in &cockpit.mcp_servers {
+            config
+                .cockpit
+                .mcp_servers
+                .retain(|existing| existing.name != server.name);
+            config.cockpit.mcp_servers.push(McpServerConfig {
+                from_repo: true,
+                ..server.clone()
+            });
+        }
+    }
+
     config
 }
 
@@ -238,6 +284,11 @@ pub fn repo_config_to_profile(repo: &RepoConfig) -> ProfileConfig {
         tmux: repo.tmux.clone(),
         session: repo.session.clone(),
         sound: repo.sound.clone(),
+        cockpit: repo.cockpit.as_ref().map(|c| CockpitConfigOverride {
+            approval_rules: (!c.approval_rules.is_empty()).then(|| c.approval_rules.clone()),
+            mcp_servers: (!c.mcp_servers.is_empty()).then(|| c.mcp_servers.clone()),
+            ..Default::default()
+        }),
         hooks: repo.hooks.as_ref().map(|h| HooksConfigOverride {
             on_create: if h.on_create.is_empty() {
                 None
@@ -273,6 +324,16 @@ pub fn profile_to_repo_config(profile: &ProfileConfig) -> RepoConfig {
         updates: profile.updates.clone(),
         tmux: profile.tmux.clone(),
         sound: profile.sound.clone(),
+        // Only approval rules and MCP servers are repo-scoped; other
+        // cockpit settings edited at repo scope are not persisted.
+        cockpit: profile
+            .cockpit
+            .as_ref()
+            .filter(|c| c.approval_rules.is_some() || c.mcp_servers.is_some())
+            .map(|c| RepoCockpitConfig {
+                approval_rules: c.approval_rules.clone().unwrap_or_default(),
+                mcp_servers: c.mcp_servers.clone().unwrap_or_default(),
+            }),
     }
 }
 
@@ -1077,6 +1138,18 @@ pub const INIT_TEMPLATE: &str = r#"# Agent of Empires - Repository Configuration
 
 # [sound]
 # enabled = false
+
+# Cockpit approval rules from a repo can only deny or ask; allow rules
+# belong in your global or profile config.
+# [[cockpit.approval_rules]]
+# action = "deny"
+# command_prefix = ["git push"]
+
+# MCP servers for cockpit agents. Stdio servers (`command`) from a repo
+# only start in sandboxed sessions.
+# [[cockpit.mcp_servers]]
+# name = "docs"
+# url = "https://mcp.example.com/mcp"
 "#;
 
 #[cfg(test)]
@@ -1299,6 +1372,79 @@ mod tests {
         assert_eq!(merged.session.default_tool, Some("opencode".to_string()));
     }
 
+    #[test]
+    fn test_merge_repo_config_approval_rules_only_tighten() {
+        use crate::session::config::{ApprovalAction, ApprovalRule};
+        let rule = |action, name: &str| ApprovalRule {
+            action,
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let mut config = Config::default();
+        config.cockpit.approval_rules = vec![rule(ApprovalAction::Allow, "global")];
+        let repo: RepoConfig = toml::from_str(
+            r#"
+            [[cockpit.approval_rules]]
+            action = "allow"
+            name = "sneaky"
+
+            [[cockpit.approval_rules]]
+            action = "deny"
+            name = "no pushes"
+            command_prefix = ["git push"]
+            "#,
+        )
+        .unwrap();
+        let merged = merge_repo_config(config, &repo);
+        let names: Vec<_> = merged
+            .cockpit
+            .approval_rules
+            .iter()
+            .map(|r| r.name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, ["no pushes", "global"]);
+
+        // Round-trips through the settings TUI's profile view.
+        let back = profile_to_repo_config(&repo_config_to_profile(&repo));
+        assert_eq!(back.cockpit.unwrap().approval_rules.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_repo_config_mcp_servers_marked_and_replace_by_name() {
+        use crate::session::config::McpServerConfig;
+        let server = |name: &str, command: &str| McpServerConfig {
+            name: name.to_string(),
+            command: Some(command.to_string()),
+            ..Default::default()
+        };
+        let mut config = Config::default();
+        config.cockpit.mcp_servers = vec![server("docs", "docs-mcp"), server("db", "db-mcp")];
+        let repo: RepoConfig = toml::from_str(
+            r#"
+            [[cockpit.mcp_servers]]
+            name = "db"
+            command = "./tools/db-mcp"
+
+            [[cockpit.mcp_servers]]
+            name = "tracker"
+            url = "https://mcp.example.com/mcp"
+            transport = "sse"
+            "#,
+        )
+        .unwrap();
+        let merged = merge_repo_config(config, &repo).cockpit.mcp_servers;
+        let summary: Vec<_> = merged
+            .iter()
+            .map(|s| (s.name.as_str(), s.from_repo))
+            .collect();
+        assert_eq!(summary, [("docs", false), ("db", true), ("tracker", true)]);
+        assert_eq!(merged[1].command.as_deref(), Some("./tools/db-mcp"));
+        assert_eq!(
+            merged[2].transport,
+            crate::session::config::McpTransport::Sse
+        );
+    }
+
     #[test]
     fn test_merge_repo_config_sandbox() {
         let config = Config::default();
@@ -0,0 +1,478 @@
+//! Review comments: line-anchored notes left on a session's diff, held
+//! until they're sent to the agent as one structured message. Shared by
+//! the TUI diff view and the web dashboard's rich diff. Persisted per
+//! profile at `<app_dir>/profiles/{profile}/reviews.json`, keyed by
+//! session id.
+//!
+//! The wire shape and the assembled prompt mirror the web client's
+//! `DiffComment` and `buildPrompt.ts`, so a review reads the same to the
+//! agent whichever surface sent it.
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::export::fence_for;
+use super::storage::{acquire_storage_flock, atomic_write, profile_dir};
+
+const REVIEWS_FILENAME: &str = "reviews.json";
+const REVIEWS_LOCK_FILENAME: &str = ".reviews.lock";
+
+/// Closing line used when the sender doesn't supply one, so the agent
+/// always gets an actionable nudge.
+pub const DEFAULT_OUTRO: &str = "Please address these comments.";
+
+const SENTINEL_PREFIX: &str = "<!-- aoe:diff-comments:v1 ";
+const SENTINEL_SUFFIX: &str = " -->";
+
+/// Which side of the diff a comment's line numbers refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DiffSide {
+    Old,
+    New,
+}
+
+impl DiffSide {
+    pub fn label(self) -> &'static str {
+        match self {
+            DiffSide::Old => "old",
+            DiffSide::New => "new",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewComment {
+    pub id: String,
+    /// Workspace member the file belongs to. None for single-repo sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_name: Option<String>,
+    pub file_path: String,
+    pub side: DiffSide,
+    /// Inclusive, 1-based, on `side`.
+    pub start_line: usize,
+    /// Inclusive; never less than `start_line`.
+    pub end_line: usize,
+    pub body: String,
+    /// The code the reviewer was looking at, so the message still makes
+    /// sense after the diff moves on.
+    pub captured_snippet: String,
+    /// Code-fence language for the snippet; empty when unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ReviewComment {
+    /// A new comment on `file_path`. The range is normalized so
+    /// `start_line <= end_line`, and the fence language is guessed from
+    /// the extension.
+    pub fn new(
+        file_path: &str,
+        side: DiffSide,
+        start_line: usize,
+        end_line: usize,
+        body: &str,
+        captured_snippet: &str,
+    ) -> Self {
+        let language = language_for_path(file_path);
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            repo_name: None,
+            file_path: file_path.to_string(),
+            side,
+            start_line: start_line.min(end_line),
+            end_line: start_line.max(end_line),
+            body: body.trim().to_string(),
+            captured_snippet: captured_snippet.to_string(),
+            language: (!language.is_empty()).then(|| language.to_string()),
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    /// `line 12` or `lines 12-18`.
+    pub fn range_label(&self) -> String {
+        if self.start_line == self.end_line {
+            format!("line {}", self.start_line)
+        } else {
+            format!("lines {}-{}", self.start_line, self.end_line)
+        }
+    }
+}
+
+/// Code-fence language for `path`'s extension, or "" when there's no
+/// good guess. Same table as the web client's `extensionToLanguage`.
+pub fn language_for_path(path: &str) -> &'static str {
+    let lower = path.to_lowercase();
+    let base = lower.rsplit('/').next().unwrap_or(&lower);
+    if base == "dockerfile" {
+        return "dockerfile";
+    }
+    let Some((_, ext)) = base.rsplit_once('.') else {
+        return "";
+    };
+    match ext {
+        "rs" => "rust",
+        "ts" => "ts",
+        "tsx" => "tsx",
+        "js" => "js",
+        "jsx" => "jsx",
+        "py" => "python",
+        "rb" => "ruby",
+        "go" => "go",
+        "java" => "java",
+        "kt" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" => "cpp",
+        "cs" => "csharp",
+        "php" => "php",
+        "pl" => "perl",
+        "lua" => "lua",
+        "sh" | "bash" | "zsh" | "fish" => "bash",
+        "ps1" => "powershell",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "json" => "json",
+        "md" => "markdown",
+        "html" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "sql" => "sql",
+        "proto" => "proto",
+        "dockerfile" => "dockerfile",
+        _ => "",
+    }
+}
+
+fn reviews_path(profile: &str) -> Result<PathBuf> {
+    Ok(profile_dir(profile)?.join(REVIEWS_FILENAME))
+}
+
+/// Every pending comment in `profile`, keyed by session id.
+pub fn load(profile: &str) -> Result<BTreeMap<String, Vec<ReviewComment>>> {
+    let path = reviews_path(profile)?;
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    if content.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Read-modify-write `profile`'s comments under a cross-process lock, so
+/// the TUI and a running `aoe serve` never drop each other's writes.
+/// Sessions left with no comments are dropped from the file.
+pub fn update<T>(
+    profile: &str,
+    f: impl FnOnce(&mut BTreeMap<String, Vec<ReviewComment>>) -> Result<T>,
+) -> Result<T> {
+    let dir = profile_dir(profile)?;
+    let _lock = acquire_storage_flock(&dir, REVIEWS_LOCK_FILENAME)?;
+    let mut reviews = load(profile)?;
+    let out = f(&mut reviews)?;
+    reviews.retain(|_, comments| !comments.is_empty());
+    atomic_write(
+        &dir.join(REVIEWS_FILENAME),
+        serde_json::to_string_pretty(&reviews)?.as_bytes(),
+    )?;
+    Ok(out)
+}
+
+/// `session_id`'s pending comments in review order (see `sort`).
+pub fn comments_for(profile: &str, session_id: &str) -> Result<Vec<ReviewComment>> {
+    let mut comments = load(profile)?.remove(session_id).unwrap_or_default();
+    sort(&mut comments);
+    Ok(comments)
+}
+
+pub fn add(profile: &str, session_id: &str, comment: ReviewComment) -> Result<ReviewComment> {
+    if comment.body.trim().is_empty() {
+        bail!("Comment is empty");
+    }
+    if comment.start_line == 0 {
+        bail!("Line numbers start at 1");
+    }
+    update(profile, |reviews| {
+        reviews
+            .entry(session_id.to_string())
+            .or_default()
+            .push(comment.clone());
+        Ok(comment)
+    })
+}
+
+/// Delete one comment. `None` when `session_id` has no such comment.
+pub fn remove(profile: &str, session_id: &str, comment_id: &str) -> Result<Option<ReviewComment>> {
+    update(profile, |reviews| {
+        let comments = reviews.entry(session_id.to_string()).or_default();
+        Ok(comments
+            .iter()
+            .position(|c| c.id == comment_id)
+            .map(|idx| comments.remove(idx)))
+    })
+}
+
+/// Drop the comments in `sent` once a review has been delivered,
+/// returning how many were dropped. Comments added while the review was
+/// on its way stay pending for the next one.
+pub fn remove_sent(profile: &str, session_id: &str, sent: &[ReviewComment]) -> Result<usize> {
+    update(profile, |reviews| {
+        let Some(comments) = reviews.get_mut(session_id) else {
+            return Ok(0);
+        };
+        let before = comments.len();
+        comments.retain(|c| !sent.iter().any(|s| s.id == c.id));
+        Ok(before - comments.len())
+    })
+}
+
+/// Drop every pending comment for `session_id`, returning how many there
+/// were. Called when the session is deleted.
+pub fn clear(profile: &str, session_id: &str) -> Result<usize> {
+    update(profile, |reviews| {
+        Ok(reviews.remove(session_id).map_or(0, |c| c.len()))
+    })
+}
+
+/// Carry `session_id`'s pending comments along when it moves to another
+/// profile.
+pub fn move_to_profile(from: &str, to: &str, session_id: &str) -> Result<()> {
+    let comments = comments_for(from, session_id)?;
+    if comments.is_empty() {
+        return Ok(());
+    }
+    update(to, |reviews| {
+        reviews
+            .entry(session_id.to_string())
+            .or_default()
+            .extend(comments.iter().cloned());
+        Ok(())
+    })?;
+    remove_sent(from, session_id, &comments)?;
+    Ok(())
+}
+
+/// Review order: repo, file, start line, old side before new, then age.
+pub fn sort(comments: &mut [ReviewComment]) {
+    comments.sort_by(|a, b| {
+        (
+            &a.repo_name,
+            &a.file_path,
+            a.start_line,
+            a.side,
+            a.created_at,
+        )
+            .cmp(&(
+                &b.repo_name,
+                &b.file_path,
+                b.start_line,
+                b.side,
+                b.created_at,
+            ))
+    });
+}
+
+/// The comments as markdown sections, one per comment, separated by
+/// rules. `multi_repo` prefixes each heading with `[repo]`.
+pub fn comments_markdown(comments: &[ReviewComment], multi_repo: bool) -> String {
+    let mut sorted = comments.to_vec();
+    sort(&mut sorted);
+    sorted
+        .iter()
+        .map(|c| render_comment(c, multi_repo))
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n")
+}
+
+fn render_comment(c: &ReviewComment, multi_repo: bool) -> String {
+    let repo = match (&c.repo_name, multi_repo) {
+        (Some(name), true) => format!("[{name}] "),
+        _ => String::new(),
+    };
+    let fence = fence_for(&c.captured_snippet);
+    format!(
+        "### {repo}`{}` {} ({})\n\n{fence}{}\n{}\n{fence}\n\n{}",
+        c.file_path,
+        c.range_label(),
+        c.side.label(),
+        c.language.as_deref().unwrap_or(""),
+        c.captured_snippet,
+        c.body.trim()
+    )
+}
+
+/// The message the agent reads: intro, the comments, and an outro
+/// (`DEFAULT_OUTRO` when blank).
+pub fn build_prompt(
+    comments: &[ReviewComment],
+    intro: &str,
+    outro: &str,
+    multi_repo: bool,
+) -> String {
+    let intro = intro.trim();
+    let outro = match outro.trim() {
+        "" => DEFAULT_OUTRO,
+        o => o,
+    };
+    let mut sections = Vec::new();
+    if !intro.is_empty() {
+        sections.push(intro.to_string());
+    }
+    if !comments.is_empty() {
+        sections.push("## Diff comments".to_string());
+        sections.push(comments_markdown(comments, multi_repo));
+    }
+    sections.push(outro.to_string());
+    sections.join("\n\n") + "\n"
+}
+
+/// `build_prompt` behind the `aoe:diff-comments` sentinel: base64 JSON
+/// the cockpit transcript decodes to render the review as a card instead
+/// of raw markdown. Only cockpit sessions get it; a terminal agent would
+/// just see a blob of base64.
+pub fn build_cockpit_prompt(
+    comments: &[ReviewComment],
+    intro: &str,
+    outro: &str,
+    multi_repo: bool,
+) -> String {
+    let body = build_prompt(comments, intro, outro, multi_repo);
+    if comments.is_empty() {
+        return body;
+    }
+    let payload = serde_json::json!({
+        "intro": intro.trim(),
+        "outro": match outro.trim() {
+            "" => DEFAULT_OUTRO,
+            o => o,
+        },
+        "isMultiRepo": multi_repo,
+        "comments": comments,
+    });
+    let encoded = base64::engine::general_purpose::STANDARD.encode(payload.to_string());
+    format!("{SENTINEL_PREFIX}{encoded}{SENTINEL_SUFFIX}\n{body}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    fn comment(path: &str, side: DiffSide, start: usize, end: usize) -> ReviewComment {
+        ReviewComment::new(path, side, start, end, "body", "let x = 1;")
+    }
+
+    #[test]
+    fn prompt_matches_web_format() {
+        let prompt = build_prompt(
+            &[comment("src/foo.rs", DiffSide::New, 10, 14)],
+            "",
+            "",
+            false,
+        );
+        assert_eq!(
+            prompt,
+            "## Diff comments\n\n\
+             ### `src/foo.rs` lines 10-14 (new)\n\n\
+             ```rust\nlet x = 1;\n```\n\nbody\n\n\
+             Please address these comments.\n"
+        );
+    }
+
+    #[test]
+    fn comments_sort_by_file_line_and_side() {
+        let comments = vec![
+            comment("src/b.rs", DiffSide::New, 5, 5),
+            comment("src/a.rs", DiffSide::New, 20, 20),
+            comment("src/a.rs", DiffSide::New, 5, 5),
+            comment("src/a.rs", DiffSide::Old, 5, 5),
+        ];
+        let md = comments_markdown(&comments, false);
+        let headings: Vec<&str> = md.lines().filter(|l| l.starts_with("### ")).collect();
+        assert_eq!(
+            headings,
+            [
+                "### `src/a.rs` line 5 (old)",
+                "### `src/a.rs` line 5 (new)",
+                "### `src/a.rs` line 20 (new)",
+                "### `src/b.rs` line 5 (new)",
+            ]
+        );
+    }
+
+    #[test]
+    fn fence_outgrows_backticks_in_snippet() {
+        let mut c = comment("notes.txt", DiffSide::New, 1, 1);
+        c.captured_snippet = "before\n```\ninner\n```".to_string();
+        let md = comments_markdown(&[c], false);
+        assert!(md.contains("\n\n````\nbefore"), "{md}");
+    }
+
+    #[test]
+    fn cockpit_prompt_carries_a_decodable_sentinel() {
+        let prompt = build_cockpit_prompt(
+            &[comment("src/foo.rs", DiffSide::New, 3, 3)],
+            "Intro:",
+            "",
+            false,
+        );
+        let rest = prompt.strip_prefix(SENTINEL_PREFIX).unwrap();
+        let (encoded, body) = rest.split_once(SENTINEL_SUFFIX).unwrap();
+        assert!(body.starts_with("\nIntro:\n\n## Diff comments"));
+        let json = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let payload: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(payload["outro"], DEFAULT_OUTRO);
+        assert_eq!(payload["comments"][0]["filePath"], "src/foo.rs");
+        assert_eq!(payload["comments"][0]["capturedSnippet"], "let x = 1;");
+    }
+
+    #[test]
+    fn new_comment_normalizes_range_and_language() {
+        let c = ReviewComment::new("web/Dockerfile", DiffSide::New, 9, 4, " fix ", "");
+        assert_eq!((c.start_line, c.end_line), (4, 9));
+        assert_eq!(c.language.as_deref(), Some("dockerfile"));
+        assert_eq!(c.body, "fix");
+        assert_eq!(language_for_path("README"), "");
+    }
+
+    #[test]
+    #[serial]
+    fn remove_sent_keeps_comments_added_in_flight() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        add("p", "abc", comment("a.rs", DiffSide::New, 1, 1))?;
+        let sent = comments_for("p", "abc")?;
+        let late = add("p", "abc", comment("b.rs", DiffSide::New, 2, 2))?;
+
+        assert_eq!(remove_sent("p", "abc", &sent)?, 1);
+        assert_eq!(comments_for("p", "abc")?, vec![late]);
+        assert_eq!(clear("p", "abc")?, 1);
+        assert!(load("p")?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+//! Scheduled prompts: user-defined jobs that send a prompt to a session at
+//! a given time or on a cron expression, optionally creating a fresh
+//! session from a template each time. Persisted per profile at
+//! `<app_dir>/profiles/{profile}/schedules.json`; executed by
+//! [`crate::scheduler`] inside `aoe serve` or `aoe scheduler`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::get_profile_dir;
+use super::storage::{acquire_storage_flock, atomic_write};
+
+const SCHEDULES_FILENAME: &str = "schedules.json";
+const SCHEDULES_LOCK_FILENAME: &str = ".schedules.lock";
+
+/// When a job fires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Trigger {
+    /// Once, at a fixed instant.
+    At { at: DateTime<Utc> },
+    /// Repeatedly, on a five-field cron expression (`min hour dom month
+    /// dow`) evaluated in the machine's local time zone.
+    Cron { expr: String },
+}
+
+impl Trigger {
+    pub fn cron(expr: &str) -> Result<Self> {
+        parse_cron(expr)?;
+        Ok(Self::Cron {
+            expr: expr.trim().to_string(),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Trigger::At { at } => format!("at {}", at.with_timezone(&Local).format("%F %R")),
+            Trigger::Cron { expr } => format!("cron '{expr}'"),
+        }
+    }
+}
+
+fn parse_cron(expr: &str) -> Result<croner::Cron> {
+    croner::Cron::from_str(expr.trim())
+        .map_err(|e| anyhow!("Invalid cron expression '{}': {}", expr.trim(), e))
+}
+
+/// Where a job's prompt goes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobTarget {
+    /// An existing session, revived first if its pane is dead or stopped.
+    Session { session_id: String },
+    /// A fresh session created from the template on every run.
+    Template(SessionTemplate),
+}
+
+/// Recipe for the session a template job creates each run. Mirrors the
+/// fields `aoe add` and the MCP `create_session` tool accept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTemplate {
+    pub path: String,
+    /// Agent to run; the profile's default agent when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Session title; the job name when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Branch prefix. Each run gets its own worktree on
+    /// `<prefix>-<YYYYMMDD-HHMM>`; no worktree when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yolo_mode: Option<bool>,
+}
+
+/// Outcome of a job's most recent run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobRun {
+    pub at: DateTime<Utc>,
+    /// Session the prompt went to (the new one, for template jobs).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The run was due while no scheduler was running and was skipped.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub missed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: String,
+    pub name: String,
+    pub trigger: Trigger,
+    pub target: JobTarget,
+    pub prompt: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<JobRun>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ScheduledJob {
+    pub fn new(name: &str, trigger: Trigger, target: JobTarget, prompt: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            name: name.to_string(),
+            trigger,
+            target,
+            prompt: prompt.to_string(),
+            enabled: true,
+            created_at: Utc::now(),
+            last_run: None,
+        }
+    }
+
+    /// The next time this job is due, counting from its last run (or its
+    /// creation). `None` once a one-shot job has run, or when the cron
+    /// expression has no future match. Disabled jobs still report a time;
+    /// callers check `enabled`.
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        let baseline = self.last_run.as_ref().map_or(self.created_at, |r| r.at);
+        match &self.trigger {
+            Trigger::At { at } => self.last_run.is_none().then_some(*at),
+            Trigger::Cron { expr } => parse_cron(expr)
+                .ok()?
+                .find_next_occurrence(&baseline.with_timezone(&Local), false)
+                .ok()
+                .map(|t| t.with_timezone(&Utc)),
+        }
+    }
+
+    pub fn target_label(&self) -> String {
+        match &self.target {
+            JobTarget::Session { session_id } => format!("session {session_id}"),
+            JobTarget::Template(t) => format!("new session in {}", t.path),
+        }
+    }
+}
+
+fn schedules_path(profile: &str) -> Result<PathBuf> {
+    Ok(get_profile_dir(profile)?.join(SCHEDULES_FILENAME))
+}
+
+/// All jobs in `profile`, in creation order.
+pub fn load(profile: &str) -> Result<Vec<ScheduledJob>> {
+    let path = schedules_path(profile)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Read-modify-write `profile`'s jobs under a cross-process lock, so the
+/// CLI and a running scheduler never drop each other's writes.
+pub fn update<T>(profile: &str, f: impl FnOnce(&mut Vec<ScheduledJob>) -> Result<T>) -> Result<T> {
+    let dir = get_profile_dir(profile)?;
+    let _lock = acquire_storage_flock(&dir, SCHEDULES_LOCK_FILENAME)?;
+    let mut jobs = load(profile)?;
+    let out = f(&mut jobs)?;
+    atomic_write(
+        &dir.join(SCHEDULES_FILENAME),
+        serde_json::to_string_pretty(&jobs)?.as_bytes(),
+    )?;
+    Ok(out)
+}
+
+pub fn add(profile: &str, job: ScheduledJob) -> Result<ScheduledJob> {
+    update(profile, |jobs| {
+        if jobs.iter().any(|j| j.name == job.name) {
+            bail!("A schedule named '{}' already exists", job.name);
+        }
+        jobs.push(job.clone());
+        Ok(job)
+    })
+}
+
+pub fn remove(profile: &str, identifier: &str) -> Result<ScheduledJob> {
+    update(profile, |jobs| {
+        let idx = resolve(identifier, jobs)?;
+        Ok(jobs.remove(idx))
+    })
+}
+
+/// Index of the job matching `identifier`: exact ID, unique ID prefix, or
+/// name.
+pub fn resolve(identifier: &str, jobs: &[ScheduledJob]) -> Result<usize> {
+    if let Some(i) = jobs
+        .iter()
+        .position(|j| j.id == identifier || j.name == identifier)
+    {
+        return Ok(i);
+    }
+    let matches: Vec<usize> = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, j)| j.id.starts_with(identifier))
+        .map(|(i, _)| i)
+        .collect();
+    match matches.as_slice() {
+        [i] => Ok(*i),
+        [] => bail!("No schedule matches '{identifier}'"),
+        _ => bail!("'{identifier}' matches more than one schedule; use more of the ID"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Timelike};
+
+    fn job(trigger: Trigger) -> ScheduledJob {
+        ScheduledJob::new(
+            "audit",
+            trigger,
+            JobTarget::Session {
+                session_id: "abc".to_string(),
+            },
+            "run the audit",
+        )
+    }
+
+    #[test]
+    fn one_shot_job_runs_once() {
+        let at = Utc::now() + Duration::hours(1);
+        let mut j = job(Trigger::At { at });
+        assert_eq!(j.next_run(), Some(at));
+        j.last_run = Some(JobRun {
+            at,
+            session_id: None,
+            error: None,
+            missed: false,
+        });
+        assert_eq!(j.next_run(), None);
+    }
+
+    #[test]
+    fn cron_job_counts_from_last_run() {
+        let mut j = job(Trigger::cron("0 7 * * 1-5").unwrap());
+        // Monday 2026-01-05 07:00 local; the next weekday run is Tuesday.
+        let monday = Local.with_ymd_and_hms(2026, 1, 5, 7, 0, 0).unwrap();
+        j.last_run = Some(JobRun {
+            at: monday.with_timezone(&Utc),
+            session_id: None,
+            error: None,
+            missed: false,
+        });
+        let next = j.next_run().unwrap().with_timezone(&Local);
+        assert_eq!(next.date_naive(), monday.date_naive() + Duration::days(1));
+        assert_eq!((next.hour(), next.minute()), (7, 0));
+
+        // Friday's run is followed by Monday's.
+        let friday = monday + Duration::days(4);
+        j.last_run.as_mut().unwrap().at = friday.with_timezone(&Utc);
+        let next = j.next_run().unwrap().with_timezone(&Local);
+        assert_eq!(next.date_naive(), friday.date_naive() + Duration::days(3));
+    }
+
+    #[test]
+    fn invalid_cron_is_rejected() {
+        let err = Trigger::cron("every day").unwrap_err();
+        assert!(err.to_string().contains("Invalid cron"), "{err}");
+    }
+
+    #[test]
+    fn resolve_by_name_id_and_prefix() {
+        let mut a = job(Trigger::cron("@daily").unwrap());
+        a.id = "0000aaaa".to_string();
+        let mut b = a.clone();
+        b.id = "ffff0000".to_string();
+        b.name = "other".to_string();
+        let jobs = vec![a.clone(), b];
+        assert_eq!(resolve("audit", &jobs).unwrap(), 0);
+        assert_eq!(resolve(&a.id, &jobs).unwrap(), 0);
+        assert_eq!(resolve("ffff", &jobs).unwrap(), 1);
+        assert!(resolve("nope", &jobs).is_err());
+    }
+}
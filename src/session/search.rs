@@ -0,0 +1,537 @@
+//! Full-text search across session transcripts.
+//!
+//! Transcripts (see [`super::transcript`]) are copied into an SQLite FTS5
+//! index at `<app_dir>/search_index.db`, next to the cockpit event store.
+//! The index is refreshed lazily before each search: a session is
+//! re-read only when its transcript fingerprint changed, and sessions
+//! that no longer exist in any profile are dropped. That keeps
+//! `aoe search`, the TUI dialog, and `GET /api/search` consistent
+//! without a background indexer.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use super::transcript::{EntryKind, TranscriptSource};
+use super::{Instance, Storage};
+
+/// File name of the search index inside the app dir.
+pub const SEARCH_INDEX_DB: &str = "search_index.db";
+
+/// Default number of hits returned by a search.
+pub const DEFAULT_LIMIT: usize = 50;
+
+/// Entries longer than this are truncated before indexing. Tool output
+/// (file dumps, test logs) dominates transcript size and rarely needs to
+/// be matched past its first screens.
+const MAX_ENTRY_BYTES: usize = 32 * 1024;
+
+// Snippet highlight markers. Control characters never occur in indexed
+// text, so they can be split back out unambiguously.
+const MARK_START: char = '\u{2}';
+const MARK_END: char = '\u{3}';
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    /// Only sessions running this agent (`claude`, `codex`, ...).
+    pub tool: Option<String>,
+    /// Only entries at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+pub struct SearchHit {
+    pub session_id: String,
+    pub profile: String,
+    pub title: String,
+    pub tool: String,
+    pub kind: EntryKind,
+    /// Matching excerpt, whitespace collapsed to single spaces.
+    pub snippet: String,
+    /// Byte ranges `[start, end)` of the matched terms within `snippet`.
+    pub highlights: Vec<[usize; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+pub struct SearchIndex {
+    conn: Connection,
+}
+
+impl SearchIndex {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create {}", parent.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("open search index at {}", path.display()))?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .context("enable WAL mode")?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS indexed_sessions (
+                session_id  TEXT PRIMARY KEY,
+                fingerprint TEXT NOT NULL
+            );
+            CREATE VIRTUAL TABLE IF NOT EXISTS transcript_fts USING fts5(
+                text,
+                session_id UNINDEXED,
+                kind UNINDEXED,
+                ts UNINDEXED,
+                tokenize = 'unicode61 remove_diacritics 2'
+            );",
+        )
+        .context("create search index schema")?;
+        Ok(Self { conn })
+    }
+
+    /// Open `<app_dir>/search_index.db`.
+    pub fn open_default() -> Result<Self> {
+        Self::open(&super::get_app_dir()?.join(SEARCH_INDEX_DB))
+    }
+
+    /// Bring the index in line with `instances`, which must be every
+    /// session across all profiles: anything not listed is removed.
+    /// Returns the number of sessions that were (re)indexed.
+    pub fn refresh(&mut self, instances: &[Instance]) -> Result<usize> {
+        self.refresh_with(instances, TranscriptSource::for_instance)
+    }
+
+    fn refresh_with(
+        &mut self,
+        instances: &[Instance],
+        resolve: impl Fn(&Instance) -> Option<TranscriptSource>,
+    ) -> Result<usize> {
+        let indexed: HashMap<String, String> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT session_id, fingerprint FROM indexed_sessions")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let live: std::collections::HashSet<&str> =
+            instances.iter().map(|i| i.id.as_str()).collect();
+        for stale in indexed.keys().filter(|id| !live.contains(id.as_str())) {
+            self.remove(stale)?;
+        }
+
+        let mut reindexed = 0;
+        for inst in instances {
+            let Some(source) = resolve(inst) else {
+                continue;
+            };
+            // An unreadable source keeps whatever was indexed last time.
+            let Some(fingerprint) = source.fingerprint() else {
+                continue;
+            };
+            if indexed.get(&inst.id) == Some(&fingerprint) {
+                continue;
+            }
+            let entries = match source.load() {
+                Ok(entries) => entries,
+                Err(e) => {
+                    tracing::debug!(target: "session.search", "skip {}: {e:#}", inst.id);
+                    continue;
+                }
+            };
+
+            let tx = self.conn.transaction()?;
+            tx.execute(
+                "DELETE FROM transcript_fts WHERE session_id = ?1",
+                params![inst.id],
+            )?;
+            {
+                let mut insert = tx.prepare(
+                    "INSERT INTO transcript_fts (text, session_id, kind, ts) VALUES (?1, ?2, ?3, ?4)",
+                )?;
+                // Entries without their own time inherit the previous
+                // one's, so `--since` still has something to compare.
+                let mut last_ts = inst.created_at;
+                for entry in &entries {
+                    if let Some(ts) = entry.timestamp {
+                        last_ts = ts;
+                    }
+                    insert.execute(params![
+                        truncate(&entry.text, MAX_ENTRY_BYTES),
+                        inst.id,
+                        entry.kind.as_str(),
+                        last_ts.timestamp_millis(),
+                    ])?;
+                }
+            }
+            tx.execute(
+                "INSERT INTO indexed_sessions (session_id, fingerprint) VALUES (?1, ?2)
+                 ON CONFLICT(session_id) DO UPDATE SET fingerprint = excluded.fingerprint",
+                params![inst.id, fingerprint],
+            )?;
+            tx.commit()?;
+            reindexed += 1;
+        }
+        Ok(reindexed)
+    }
+
+    fn remove(&self, session_id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM transcript_fts WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM indexed_sessions WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Run `query` against the index, best matches first. Hits are
+    /// resolved against `instances` for titles and the tool filter.
+    pub fn search(&self, query: &SearchQuery, instances: &[Instance]) -> Result<Vec<SearchHit>> {
+        let Some(fts) = fts_query(&query.text) else {
+            return Ok(Vec::new());
+        };
+        let by_id: HashMap<&str, &Instance> =
+            instances.iter().map(|i| (i.id.as_str(), i)).collect();
+        let limit = if query.limit == 0 {
+            DEFAULT_LIMIT
+        } else {
+            query.limit
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT session_id, kind, ts,
+                    snippet(transcript_fts, 0, char(2), char(3), '…', 24)
+             FROM transcript_fts
+             WHERE transcript_fts MATCH ?1 AND (?2 IS NULL OR ts >= ?2)
+             ORDER BY bm25(transcript_fts)",
+        )?;
+        let since = query.since.map(|t| t.timestamp_millis());
+        let mut rows = stmt
+            .query(params![fts, since])
+            .context("search query failed")?;
+
+        let mut hits = Vec::new();
+        while let Some(row) = rows.next()? {
+            let session_id: String = row.get(0)?;
+            let Some(inst) = by_id.get(session_id.as_str()) else {
+                continue;
+            };
+            if query.tool.as_deref().is_some_and(|t| t != inst.tool) {
+                continue;
+            }
+            let kind: String = row.get(1)?;
+            let ts: Option<i64> = row.get(2)?;
+            let raw: String = row.get(3)?;
+            let (snippet, highlights) = split_highlights(&raw);
+            hits.push(SearchHit {
+                session_id,
+                profile: inst.source_profile.clone(),
+                title: inst.title.clone(),
+                tool: inst.tool.clone(),
+                kind: EntryKind::parse(&kind).unwrap_or(EntryKind::Assistant),
+                snippet,
+                highlights,
+                timestamp: ts.and_then(|ms| Utc.timestamp_millis_opt(ms).single()),
+            });
+            if hits.len() >= limit {
+                break;
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Number of transcript entries indexed for `session_id`.
+    #[cfg(test)]
+    fn entry_count(&self, session_id: &str) -> i64 {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) FROM transcript_fts WHERE session_id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .unwrap_or(0)
+    }
+}
+
+/// Load every session across all profiles, tagged with its profile.
+pub fn load_all_instances() -> Vec<Instance> {
+    let mut all = Vec::new();
+    for profile in super::list_profiles().unwrap_or_default() {
+        if let Ok(mut instances) = Storage::new(&profile).and_then(|s| s.load()) {
+            for inst in &mut instances {
+                inst.source_profile = profile.clone();
+            }
+            all.extend(instances);
+        }
+    }
+    all
+}
+
+/// Refresh the default index over all profiles, then search it. Blocking;
+/// async callers should run it on a blocking thread.
+pub fn search_all_profiles(query: &SearchQuery) -> Result<Vec<SearchHit>> {
+    let instances = load_all_instances();
+    let mut index = SearchIndex::open_default()?;
+    index.refresh(&instances)?;
+    index.search(query, &instances)
+}
+
+/// Parse a `--since` value: a relative age (`90s`, `30m`, `12h`, `7d`, `2w`) or
+/// an absolute date (`2026-01-31`) or RFC 3339 timestamp.
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    let Ok(age) = super::filter::parse_duration(value) else {
+        bail!("Invalid --since value '{value}' (use e.g. 30m, 12h, 7d, 2w, or 2026-01-31)");
+    };
+    chrono::Duration::from_std(age)
+        .ok()
+        .and_then(|age| now.checked_sub_signed(age))
+        .with_context(|| format!("--since value '{value}' is too far in the past"))
+}
+
+/// Turn free text into an FTS5 expression. Every word is quoted so
+/// punctuation (`foo.rs`, `--force`) can't be read as query syntax;
+/// `"quoted phrases"` stay phrases, a trailing `*` keeps prefix matching,
+/// and a bare `OR` between terms is passed through. Terms are ANDed.
+fn fts_query(text: &str) -> Option<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut word = String::new();
+        if c == '"' {
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                word.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+        }
+        if word == "OR" && c != '"' {
+            if terms.last().is_some_and(|t| t != "OR") {
+                terms.push(word);
+            }
+            continue;
+        }
+        let prefix = c != '"' && word.ends_with('*');
+        let word = word.trim_end_matches('*');
+        if word.trim().is_empty() {
+            continue;
+        }
+        let quoted = format!("\"{}\"", word.replace('"', "\"\""));
+        terms.push(if prefix { format!("{quoted}*") } else { quoted });
+    }
+    if terms.last().is_some_and(|t| t == "OR") {
+        terms.pop();
+    }
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Strip the snippet markers, returning the plain text and the byte
+/// ranges they enclosed. Whitespace runs collapse to one space so a
+/// snippet always fits on one line.
+fn split_highlights(raw: &str) -> (String, Vec<[usize; 2]>) {
+    let mut text = String::with_capacity(raw.len());
+    let mut ranges = Vec::new();
+    let mut open = None;
+    for c in raw.chars() {
+        match c {
+            MARK_START => open = Some(text.len()),
+            MARK_END => {
+                if let Some(start) = open.take() {
+                    ranges.push([start, text.len()]);
+                }
+            }
+            c if c.is_whitespace() => {
+                if !text.ends_with(' ') && !text.is_empty() {
+                    text.push(' ');
+                }
+            }
+            c => text.push(c),
+        }
+    }
+    (text, ranges)
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn instance(id: &str, tool: &str) -> Instance {
+        let mut inst = Instance::new(id, "/tmp/project");
+        inst.id = id.to_string();
+        inst.tool = tool.to_string();
+        inst
+    }
+
+    #[test]
+    fn fts_query_quotes_terms() {
+        assert_eq!(
+            fts_query("foo.rs --force").unwrap(),
+            "\"foo.rs\" \"--force\""
+        );
+        assert_eq!(
+            fts_query("\"exact phrase\" pars*").unwrap(),
+            "\"exact phrase\" \"pars\"*"
+        );
+        assert_eq!(fts_query("a OR b").unwrap(), "\"a\" OR \"b\"");
+        assert_eq!(fts_query("OR a OR").unwrap(), "\"a\"");
+        assert_eq!(fts_query("say \"hi").unwrap(), "\"say\" \"hi\"");
+        assert!(fts_query("  * \"\" ").is_none());
+    }
+
+    #[test]
+    fn split_highlights_returns_ranges() {
+        let (text, ranges) = split_highlights("the \u{2}flaky\u{3}\n\n test");
+        assert_eq!(text, "the flaky test");
+        assert_eq!(ranges, vec![[4, 9]]);
+        assert_eq!(&text[4..9], "flaky");
+    }
+
+    #[test]
+    fn parse_since_accepts_ages_and_dates() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        assert_eq!(
+            parse_since("2d", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 8, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("30m", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 10, 11, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("2026-01-31", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 31, 0, 0, 0).unwrap()
+        );
+        assert!(parse_since("7y", now).is_err());
+        assert!(parse_since("soon", now).is_err());
+        assert!(parse_since("7é", now).is_err());
+        assert!(parse_since("99999999999d", now).is_err());
+    }
+
+    #[test]
+    fn truncate_respects_char_boundaries() {
+        assert_eq!(truncate("héllo", 2), "h");
+        assert_eq!(truncate("abc", 10), "abc");
+    }
+
+    #[test]
+    fn index_refreshes_searches_and_prunes() {
+        let dir = TempDir::new().unwrap();
+        let cockpit_db = dir.path().join("cockpit_events.db");
+        {
+            let conn = Connection::open(&cockpit_db).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE cockpit_events (session_id TEXT, seq INTEGER, event_json TEXT, created_at INTEGER);",
+            )
+            .unwrap();
+            let rows = [
+                (
+                    "s1",
+                    1,
+                    r#"{"UserPromptSent":{"text":"why is the parser flaky"}}"#,
+                ),
+                (
+                    "s1",
+                    2,
+                    r#"{"AgentMessageChunk":{"text":"The tokenizer races."}}"#,
+                ),
+                (
+                    "s2",
+                    1,
+                    r#"{"UserPromptSent":{"text":"bump the parser version"}}"#,
+                ),
+            ];
+            for (sid, seq, json) in rows {
+                conn.execute(
+                    "INSERT INTO cockpit_events VALUES (?1, ?2, ?3, 1767225600000)",
+                    params![sid, seq, json],
+                )
+                .unwrap();
+            }
+        }
+
+        let mut index = SearchIndex::open(&dir.path().join(SEARCH_INDEX_DB)).unwrap();
+        let instances = vec![instance("s1", "claude"), instance("s2", "codex")];
+        // `for_instance` resolves the cockpit DB from the app dir, so
+        // point every session at the fixture instead.
+        let resolve = |inst: &Instance| {
+            Some(TranscriptSource::Cockpit {
+                db: cockpit_db.clone(),
+                session_id: inst.id.clone(),
+            })
+        };
+        assert_eq!(index.refresh_with(&instances, resolve).unwrap(), 2);
+        assert_eq!(index.refresh_with(&instances, resolve).unwrap(), 0);
+
+        let query = |text: &str, tool: Option<&str>| SearchQuery {
+            text: text.to_string(),
+            tool: tool.map(String::from),
+            since: None,
+            limit: 10,
+        };
+        let hits = index.search(&query("parser", None), &instances).unwrap();
+        assert_eq!(hits.len(), 2);
+        let hits = index
+            .search(&query("parser", Some("codex")), &instances)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, "s2");
+        assert_eq!(hits[0].kind, EntryKind::User);
+
+        let hits = index.search(&query("tokeniz*", None), &instances).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, EntryKind::Assistant);
+        let [start, end] = hits[0].highlights[0];
+        assert_eq!(&hits[0].snippet[start..end], "tokenizer");
+
+        let since = SearchQuery {
+            since: Some(Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap()),
+            ..query("parser", None)
+        };
+        assert!(index.search(&since, &instances).unwrap().is_empty());
+
+        // s2 deleted: refresh prunes its rows.
+        index.refresh_with(&instances[..1], resolve).unwrap();
+        assert_eq!(index.entry_count("s2"), 0);
+        assert_eq!(index.entry_count("s1"), 2);
+    }
+}
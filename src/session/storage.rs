@@ -141,7 +141,7 @@ fn workspace_ordering_lock() -> &'static Mutex<()> {
 /// RAII guard for a held cross-process `flock`. Drops via `fs2::FileExt::unlock`,
 /// which is also performed by the kernel when the file descriptor is closed,
 /// so a panic during the critical section still releases the lock.
-struct StorageFlock {
+pub(super) struct StorageFlock {
     file: fs::File,
 }
 
@@ -166,7 +166,7 @@ impl Drop for StorageFlock {
 /// the rest of `<app_dir>` regardless of the caller's umask. The kernel
 /// releases the lock on process exit (including SIGKILL), so a crashed peer
 /// cannot wedge us forever.
-fn acquire_storage_flock(dir: &Path, name: &str) -> Result<StorageFlock> {
+pub(super) fn acquire_storage_flock(dir: &Path, name: &str) -> Result<StorageFlock> {
     fs::create_dir_all(dir)?;
     let path = dir.join(name);
     #[cfg(unix)]
@@ -254,13 +254,16 @@ pub struct WorkspaceOrdering {
     pub order: Vec<String>,
 }
 
+/// `profile`'s data directory, resolved the way [`Storage::new`] does: an
+/// empty name means the default profile. For the per-profile files kept
+/// alongside `sessions.json` (notes, reviews, the timeline).
+pub(super) fn profile_dir(profile: &str) -> Result<PathBuf> {
+    get_profile_dir(&super::config::effective_profile(profile))
+}
+
 impl Storage {
     pub fn new(profile: &str) -> Result<Self> {
-        let profile_name = if profile.is_empty() {
-            super::config::resolve_default_profile()
-        } else {
-            profile.to_string()
-        };
+        let profile_name = super::config::effective_profile(profile);
 
         let profile_dir = get_profile_dir(&profile_name)?;
         let sessions_path = profile_dir.join("sessions.json");
@@ -0,0 +1,198 @@
+//! Session tags: free-form labels such as `blocked-ci`, or `key=value`
+//! pairs such as `ticket=ABC-123` and `reviewer=alice`.
+//!
+//! Tags compare case-insensitively. A session holds at most one tag per
+//! key, so tagging `reviewer=bob` replaces `reviewer=alice`. When grouping
+//! by tag, `key=value` nests as `key/value`, lowercased so spellings that
+//! compare equal share a group.
+
+/// The longest tag accepted, in characters.
+pub const MAX_TAG_LEN: usize = 64;
+
+/// Canonical form of `raw`: trimmed, with spaces around `=` removed.
+/// Commas, empty keys or values, and over-long tags are rejected.
+pub fn normalize(raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err("tag cannot be empty".to_string());
+    }
+    if raw.contains(',') {
+        return Err(format!("tag '{raw}' cannot contain a comma"));
+    }
+    if raw.chars().any(char::is_control) {
+        return Err(format!("tag '{raw}' contains a control character"));
+    }
+    let tag = match raw.split_once('=') {
+        Some((key, value)) => {
+            let (key, value) = (key.trim(), value.trim());
+            if key.is_empty() || value.is_empty() {
+                return Err(format!("label '{raw}' needs both a key and a value"));
+            }
+            format!("{key}={value}")
+        }
+        None => raw.to_string(),
+    };
+    if tag.chars().count() > MAX_TAG_LEN {
+        return Err(format!(
+            "tag '{tag}' is longer than {MAX_TAG_LEN} characters"
+        ));
+    }
+    Ok(tag)
+}
+
+/// Parse a comma-separated tag list (the rename dialog's tag field).
+/// Blank entries are skipped.
+pub fn parse_list(input: &str) -> Result<Vec<String>, String> {
+    let mut tags = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        add(&mut tags, &normalize(part)?);
+    }
+    Ok(tags)
+}
+
+/// The key of a `key=value` label, or the whole tag.
+pub fn key(tag: &str) -> &str {
+    tag.split_once('=').map_or(tag, |(k, _)| k)
+}
+
+/// The group path a tag files its session under: `key/value` for labels,
+/// case-folded.
+pub fn group_path(tag: &str) -> String {
+    let tag = tag.to_ascii_lowercase();
+    match tag.split_once('=') {
+        Some((k, v)) => format!("{k}/{v}"),
+        None => tag,
+    }
+}
+
+/// Add `tag` to `tags`, replacing an existing tag that equals it or, for a
+/// label, shares its key. Returns whether `tags` changed.
+pub fn add(tags: &mut Vec<String>, tag: &str) -> bool {
+    let is_label = tag.contains('=');
+    let existing = tags.iter().position(|t| {
+        t.eq_ignore_ascii_case(tag)
+            || (is_label && t.contains('=') && key(t).eq_ignore_ascii_case(key(tag)))
+    });
+    match existing {
+        Some(i) if tags[i] == tag => false,
+        Some(i) => {
+            tags[i] = tag.to_string();
+            true
+        }
+        None => {
+            tags.push(tag.to_string());
+            true
+        }
+    }
+}
+
+/// Remove `tag` from `tags`. A bare key also removes the label with that
+/// key, so `reviewer` removes `reviewer=alice`. Returns whether `tags`
+/// changed.
+pub fn remove(tags: &mut Vec<String>, tag: &str) -> bool {
+    let before = tags.len();
+    let bare = !tag.contains('=');
+    tags.retain(|t| !(t.eq_ignore_ascii_case(tag) || (bare && key(t).eq_ignore_ascii_case(tag))));
+    tags.len() != before
+}
+
+/// Tags to add and remove, applied removals first. Used for edits that
+/// should merge with whatever tags a session already has: the CLI's
+/// `tag`/`untag`, bulk edits, and a rename-dialog change expressed as a
+/// diff against the tags it was opened with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagEdit {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl TagEdit {
+    /// Parse `tag, -tag` input: plain entries add, `-` entries remove.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut edit = Self::default();
+        for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+            match part.trim().strip_prefix('-') {
+                Some(tag) => edit.remove.push(normalize(tag)?),
+                None => edit.add.push(normalize(part)?),
+            }
+        }
+        Ok(edit)
+    }
+
+    /// The edit turning `old` into `new`.
+    pub fn between(old: &[String], new: &[String]) -> Self {
+        Self {
+            add: new.iter().filter(|t| !old.contains(t)).cloned().collect(),
+            remove: old.iter().filter(|t| !new.contains(t)).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+
+    /// Apply to `tags`; returns whether they changed.
+    pub fn apply(&self, tags: &mut Vec<String>) -> bool {
+        let mut changed = false;
+        for tag in &self.remove {
+            changed |= remove(tags, tag);
+        }
+        for tag in &self.add {
+            changed |= add(tags, tag);
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_trims_and_rejects_bad_tags() {
+        assert_eq!(normalize("  blocked-ci ").unwrap(), "blocked-ci");
+        assert_eq!(normalize("reviewer = alice").unwrap(), "reviewer=alice");
+        assert!(normalize("").is_err());
+        assert!(normalize("a,b").is_err());
+        assert!(normalize("=alice").is_err());
+        assert!(normalize("reviewer=").is_err());
+        assert!(normalize(&"x".repeat(MAX_TAG_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn labels_are_single_valued() {
+        let mut tags = parse_list("reviewer=alice, blocked-ci").unwrap();
+        assert!(add(&mut tags, "reviewer=bob"));
+        // Same tag, new spelling: replaced in place, not duplicated.
+        assert!(add(&mut tags, "BLOCKED-CI"));
+        assert_eq!(tags, vec!["reviewer=bob", "BLOCKED-CI"]);
+        assert!(!add(&mut tags, "reviewer=bob"));
+    }
+
+    #[test]
+    fn remove_by_tag_or_key() {
+        let mut tags = parse_list("reviewer=alice, blocked-ci, ticket=ABC-1").unwrap();
+        assert!(remove(&mut tags, "Reviewer"));
+        assert!(remove(&mut tags, "blocked-ci"));
+        assert!(!remove(&mut tags, "ticket=ABC-2"));
+        assert_eq!(tags, vec!["ticket=ABC-1"]);
+        assert_eq!(group_path(&tags[0]), "ticket/abc-1");
+    }
+
+    #[test]
+    fn edits_parse_and_apply() {
+        let edit = TagEdit::parse("blocked-ci, -reviewer, ticket = ABC-2").unwrap();
+        assert_eq!(edit.add, vec!["blocked-ci", "ticket=ABC-2"]);
+        assert_eq!(edit.remove, vec!["reviewer"]);
+        let mut tags = parse_list("reviewer=alice, ticket=ABC-1").unwrap();
+        assert!(edit.apply(&mut tags));
+        assert_eq!(tags, vec!["ticket=ABC-2", "blocked-ci"]);
+        assert!(TagEdit::parse("-").is_err());
+
+        let old = parse_list("a, b").unwrap();
+        let new = parse_list("b, c").unwrap();
+        let mut tags = old.clone();
+        TagEdit::between(&old, &new).apply(&mut tags);
+        assert_eq!(tags, new);
+    }
+}
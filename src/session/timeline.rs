@@ -0,0 +1,609 @@
+//! Status timeline: every Running / Waiting / Idle / Error transition a
+//! session goes through, with when it happened and what noticed it, plus
+//! a `stopped` line when the session is stopped or restarted.
+//! Persisted per profile as one compact line per transition in
+//! `<app_dir>/profiles/{profile}/timeline.log`:
+//!
+//! ```text
+//! 2026-10-19T09:12:03Z 3f2a9c1e running hook
+//! ```
+//!
+//! The TUI and `aoe serve` poll the same sessions, so both may observe a
+//! transition; [`record`] drops a line that repeats the session's last
+//! recorded status. The log is trimmed to about half once it passes
+//! [`MAX_LOG_BYTES`] (roughly 12k transitions), taking the oldest lines of
+//! the sessions with the longest histories first.
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use super::storage::{acquire_storage_flock, atomic_write, profile_dir};
+use super::Status;
+
+const TIMELINE_FILENAME: &str = "timeline.log";
+const TIMELINE_LOCK_FILENAME: &str = ".timeline.lock";
+
+/// Size past which the log is trimmed (see [`trim`]). Showing a session's
+/// timeline reads the whole file, so this stays modest.
+pub const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// How far back from the end of the log [`record`] looks for a session's
+/// last status. A miss only costs one duplicate line.
+const DEDUP_TAIL_BYTES: u64 = 16 * 1024;
+
+/// What observed a status transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum StatusSource {
+    /// The agent's own status hook file.
+    Hook,
+    /// Pane content and process detection in tmux.
+    Pane,
+    /// ACP events from a cockpit-mode session.
+    Cockpit,
+    /// A stop or restart the user asked for.
+    User,
+}
+
+impl StatusSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StatusSource::Hook => "hook",
+            StatusSource::Pane => "pane",
+            StatusSource::Cockpit => "cockpit",
+            StatusSource::User => "user",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "hook" => Some(StatusSource::Hook),
+            "pane" => Some(StatusSource::Pane),
+            "cockpit" => Some(StatusSource::Cockpit),
+            "user" => Some(StatusSource::User),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+pub struct TimelineEntry {
+    pub at: DateTime<Utc>,
+    #[cfg_attr(feature = "serve", schemars(with = "String"))]
+    pub status: Status,
+    pub source: StatusSource,
+}
+
+/// Statuses worth a line in the log. Stopped marks where the agent's
+/// last status stopped applying; the other lifecycle states (Starting,
+/// Creating, ...) are passing, and Unknown carries no information.
+pub fn is_tracked(status: Status) -> bool {
+    matches!(
+        status,
+        Status::Running | Status::Waiting | Status::Idle | Status::Error | Status::Stopped
+    )
+}
+
+fn parse_status(s: &str) -> Option<Status> {
+    match s {
+        "running" => Some(Status::Running),
+        "waiting" => Some(Status::Waiting),
+        "idle" => Some(Status::Idle),
+        "error" => Some(Status::Error),
+        "stopped" => Some(Status::Stopped),
+        _ => None,
+    }
+}
+
+fn format_line(session_id: &str, entry: &TimelineEntry) -> String {
+    format!(
+        "{} {} {} {}\n",
+        entry.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        session_id,
+        entry.status.as_str(),
+        entry.source.as_str()
+    )
+}
+
+/// Split a log line into its session id and entry. Malformed lines are
+/// skipped rather than failing the whole read.
+fn parse_line(line: &str) -> Option<(&str, TimelineEntry)> {
+    let mut fields = line.split_whitespace();
+    let at = DateTime::parse_from_rfc3339(fields.next()?)
+        .ok()?
+        .with_timezone(&Utc);
+    let id = fields.next()?;
+    let status = parse_status(fields.next()?)?;
+    let source = StatusSource::parse(fields.next()?)?;
+    Some((id, TimelineEntry { at, status, source }))
+}
+
+/// The status most recently logged for `session_id` within the last
+/// [`DEDUP_TAIL_BYTES`] of the file.
+fn last_logged_status(file: &mut fs::File, session_id: &str) -> Option<Status> {
+    let len = file.metadata().ok()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(DEDUP_TAIL_BYTES)))
+        .ok()?;
+    let mut tail = String::new();
+    file.read_to_string(&mut tail).ok()?;
+    tail.lines()
+        .rev()
+        .filter_map(parse_line)
+        .find(|(id, _)| *id == session_id)
+        .map(|(_, entry)| entry.status)
+}
+
+fn append(dir: &Path, session_id: &str, entry: &TimelineEntry) -> Result<()> {
+    let _flock = acquire_storage_flock(dir, TIMELINE_LOCK_FILENAME)?;
+    let path = dir.join(TIMELINE_FILENAME);
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(&path)?;
+    if last_logged_status(&mut file, session_id) == Some(entry.status) {
+        return Ok(());
+    }
+    file.write_all(format_line(session_id, entry).as_bytes())?;
+    if file.metadata()?.len() > MAX_LOG_BYTES {
+        drop(file);
+        let content = fs::read_to_string(&path)?;
+        atomic_write(&path, trim(&content).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Cut `content` to about half its lines by capping every session at its
+/// newest `cap` lines, with `cap` as large as that budget allows. Only the
+/// sessions with the longest histories lose lines, so a busy session
+/// can't push a quiet one's early history out of the log.
+fn trim(content: &str) -> String {
+    let lines: Vec<(&str, &str)> = content
+        .lines()
+        .filter_map(|line| Some((line.split_whitespace().nth(1)?, line)))
+        .collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (id, _) in &lines {
+        *counts.entry(id).or_default() += 1;
+    }
+    let mut sizes: Vec<usize> = counts.into_values().collect();
+    sizes.sort_unstable();
+    let mut budget = lines.len() / 2;
+    let mut cap = usize::MAX;
+    for (i, &size) in sizes.iter().enumerate() {
+        let sessions_left = sizes.len() - i;
+        if size * sessions_left > budget {
+            cap = (budget / sessions_left).max(1);
+            break;
+        }
+        budget -= size;
+    }
+    let mut kept_per_session: HashMap<&str, usize> = HashMap::new();
+    let mut kept: Vec<&str> = lines
+        .iter()
+        .rev()
+        .filter(|(id, _)| {
+            let kept = kept_per_session.entry(id).or_default();
+            *kept += 1;
+            *kept <= cap
+        })
+        .map(|(_, line)| *line)
+        .collect();
+    kept.reverse();
+    kept.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Log `session_id` entering `status`, unless it's untracked or the same
+/// as the last status logged for it.
+pub fn record(profile: &str, session_id: &str, status: Status, source: StatusSource) -> Result<()> {
+    match PendingTransition::new(profile, session_id, status, source) {
+        Some(transition) => transition.record(),
+        None => Ok(()),
+    }
+}
+
+/// A transition stamped when it happened but not yet written, for callers
+/// that hold a lock the file I/O shouldn't run under.
+#[derive(Debug, Clone)]
+pub struct PendingTransition {
+    profile: String,
+    session_id: String,
+    entry: TimelineEntry,
+}
+
+impl PendingTransition {
+    /// `None` when `status` is untracked.
+    pub fn new(
+        profile: &str,
+        session_id: &str,
+        status: Status,
+        source: StatusSource,
+    ) -> Option<Self> {
+        is_tracked(status).then(|| Self {
+            profile: profile.to_string(),
+            session_id: session_id.to_string(),
+            entry: TimelineEntry {
+                at: Utc::now(),
+                status,
+                source,
+            },
+        })
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Append the transition (see [`record`]). Blocks on the log's lock.
+    pub fn record(&self) -> Result<()> {
+        append(&profile_dir(&self.profile)?, &self.session_id, &self.entry)
+    }
+}
+
+/// `session_id`'s transitions, oldest first.
+pub fn load(profile: &str, session_id: &str) -> Result<Vec<TimelineEntry>> {
+    let path = profile_dir(profile)?.join(TIMELINE_FILENAME);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(content
+        .lines()
+        .filter_map(parse_line)
+        .filter(|(id, _)| *id == session_id)
+        .map(|(_, entry)| entry)
+        .collect())
+}
+
+/// Rewrite the log without the lines matching `keep == false`, returning
+/// the dropped lines.
+fn rewrite(dir: &Path, keep: impl Fn(&str) -> bool) -> Result<String> {
+    let _flock = acquire_storage_flock(dir, TIMELINE_LOCK_FILENAME)?;
+    let path = dir.join(TIMELINE_FILENAME);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(String::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let (mut kept, mut dropped) = (String::new(), String::new());
+    for line in content.lines() {
+        let target = if keep(line) { &mut kept } else { &mut dropped };
+        target.push_str(line);
+        target.push('\n');
+    }
+    if !dropped.is_empty() {
+        atomic_write(&path, kept.as_bytes())?;
+    }
+    Ok(dropped)
+}
+
+fn belongs_to(line: &str, session_id: &str) -> bool {
+    line.split_whitespace().nth(1) == Some(session_id)
+}
+
+/// Drop `session_id`'s transitions. Called when the session is deleted.
+pub fn remove(profile: &str, session_id: &str) -> Result<()> {
+    rewrite(&profile_dir(profile)?, |line| !belongs_to(line, session_id))?;
+    Ok(())
+}
+
+/// Carry `session_id`'s transitions along when it moves to another
+/// profile. They land after the target's existing lines.
+pub fn move_to_profile(from: &str, to: &str, session_id: &str) -> Result<()> {
+    let moved = rewrite(&profile_dir(from)?, |line| !belongs_to(line, session_id))?;
+    if moved.is_empty() {
+        return Ok(());
+    }
+    let dir = profile_dir(to)?;
+    let _flock = acquire_storage_flock(&dir, TIMELINE_LOCK_FILENAME)?;
+    fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(dir.join(TIMELINE_FILENAME))?
+        .write_all(moved.as_bytes())?;
+    Ok(())
+}
+
+/// Figures derived from a session's timeline.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineMetrics {
+    /// Times the agent started working (transitions into Running).
+    pub turns: usize,
+    pub running_secs: i64,
+    /// Time spent blocked on a human (permission prompts, questions).
+    pub waiting_secs: i64,
+    /// From the first Running to the first Idle after it: how long the
+    /// opening task took before the agent had nothing left to do. Unset
+    /// when the session was stopped first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_to_first_idle_secs: Option<i64>,
+}
+
+/// Derive [`TimelineMetrics`] from `entries` (oldest first). Each status
+/// lasts until the next entry, so a `stopped` line closes whatever ran
+/// before it. The last one runs until `now` only while the session still
+/// reports it, for stops that happened while nothing was recording.
+pub fn metrics(entries: &[TimelineEntry], current: Status, now: DateTime<Utc>) -> TimelineMetrics {
+    let mut metrics = TimelineMetrics::default();
+    for (i, entry) in entries.iter().enumerate() {
+        let end = match entries.get(i + 1) {
+            Some(next) => next.at,
+            None if entry.status == current => now,
+            None => entry.at,
+        };
+        let secs = (end - entry.at).num_seconds().max(0);
+        match entry.status {
+            Status::Running => {
+                metrics.turns += 1;
+                metrics.running_secs += secs;
+            }
+            Status::Waiting => metrics.waiting_secs += secs,
+            _ => {}
+        }
+    }
+    if let Some(start) = entries.iter().position(|e| e.status == Status::Running) {
+        metrics.time_to_first_idle_secs = entries[start..]
+            .iter()
+            .find(|e| matches!(e.status, Status::Idle | Status::Stopped))
+            .filter(|e| e.status == Status::Idle)
+            .map(|idle| (idle.at - entries[start].at).num_seconds());
+    }
+    metrics
+}
+
+/// How far back [`TimelineSummary::recent`] reaches.
+pub const RECENT_WINDOW_SECS: i64 = 60 * 60;
+
+/// The slice of a session's timeline kept on `Instance::timeline` for the
+/// TUI preview: metrics as of load time plus the last hour of entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimelineSummary {
+    pub metrics: TimelineMetrics,
+    /// Entries inside the last [`RECENT_WINDOW_SECS`], preceded by the one
+    /// in effect when the window opened. Oldest first.
+    pub recent: Vec<TimelineEntry>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl TimelineSummary {
+    pub fn new(entries: &[TimelineEntry], current: Status, now: DateTime<Utc>) -> Self {
+        let window_start = now - chrono::Duration::seconds(RECENT_WINDOW_SECS);
+        let first_recent = entries
+            .iter()
+            .position(|e| e.at >= window_start)
+            .unwrap_or(entries.len());
+        Self {
+            metrics: metrics(entries, current, now),
+            recent: entries[first_recent.saturating_sub(1)..].to_vec(),
+            computed_at: now,
+        }
+    }
+}
+
+/// `session_id`'s [`TimelineSummary`] as of `now`, or None before its
+/// first logged transition. Reads the whole log, so callers load it for
+/// the session on screen rather than for every session.
+pub fn summary(
+    profile: &str,
+    session_id: &str,
+    current: Status,
+    now: DateTime<Utc>,
+) -> Result<Option<TimelineSummary>> {
+    let entries = load(profile, session_id)?;
+    Ok((!entries.is_empty()).then(|| TimelineSummary::new(&entries, current, now)))
+}
+
+/// Compact duration label: `45s`, `12m 05s`, `3h 20m`.
+pub fn format_secs(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{h}h {m:02}m")
+    } else if m > 0 {
+        format!("{m}m {s:02}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    fn setup_test_home(temp: &Path) {
+        std::env::set_var("HOME", temp);
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.join(".config"));
+    }
+
+    fn entry(secs: i64, status: Status) -> TimelineEntry {
+        TimelineEntry {
+            at: Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap(),
+            status,
+            source: StatusSource::Hook,
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn record_skips_repeats_and_untracked_statuses() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        record("p", "a", Status::Running, StatusSource::Hook)?;
+        // A second observer (TUI and server both polling) sees the same
+        // transition.
+        record("p", "a", Status::Running, StatusSource::Pane)?;
+        record("p", "b", Status::Running, StatusSource::Pane)?;
+        record("p", "a", Status::Starting, StatusSource::Pane)?;
+        record("p", "a", Status::Waiting, StatusSource::Hook)?;
+
+        let statuses: Vec<_> = load("p", "a")?
+            .into_iter()
+            .map(|e| (e.status, e.source))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (Status::Running, StatusSource::Hook),
+                (Status::Waiting, StatusSource::Hook)
+            ]
+        );
+        assert_eq!(load("p", "b")?.len(), 1);
+        assert!(load("q", "a")?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn remove_and_move_only_touch_one_session() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        record("p", "a", Status::Running, StatusSource::Hook)?;
+        record("p", "b", Status::Idle, StatusSource::Pane)?;
+        record("p", "a", Status::Idle, StatusSource::Hook)?;
+
+        move_to_profile("p", "q", "a")?;
+        assert!(load("p", "a")?.is_empty());
+        assert_eq!(load("q", "a")?.len(), 2);
+
+        remove("p", "b")?;
+        assert!(load("p", "b")?.is_empty());
+        assert_eq!(load("q", "a")?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn summary_exists_once_a_session_has_history() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        record("p", "a", Status::Running, StatusSource::Hook)?;
+        record("p", "a", Status::Idle, StatusSource::Hook)?;
+
+        let found = summary("p", "a", Status::Idle, Utc::now())?.expect("summary");
+        assert_eq!(found.metrics.turns, 1);
+        assert_eq!(found.recent.len(), 2);
+        assert!(summary("p", "b", Status::Idle, Utc::now())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn summary_keeps_the_entry_in_effect_when_the_window_opens() {
+        let entries = vec![
+            entry(0, Status::Running),
+            entry(60, Status::Idle),
+            entry(RECENT_WINDOW_SECS + 600, Status::Running),
+        ];
+        let now = entries[2].at + chrono::Duration::seconds(60);
+        let summary = TimelineSummary::new(&entries, Status::Running, now);
+        assert_eq!(summary.recent, entries[1..].to_vec());
+        assert_eq!(summary.metrics.turns, 2);
+    }
+
+    #[test]
+    fn log_lines_round_trip_and_junk_is_skipped() {
+        let e = entry(0, Status::Waiting);
+        let line = format_line("abc", &e);
+        assert_eq!(line, "2023-11-14T22:13:20Z abc waiting hook\n");
+        assert_eq!(parse_line(line.trim_end()), Some(("abc", e)));
+        assert_eq!(parse_line("2023-11-14T22:13:20Z abc starting hook"), None);
+        assert_eq!(parse_line("garbage"), None);
+    }
+
+    #[test]
+    fn metrics_count_turns_waiting_and_first_idle() {
+        let entries = vec![
+            entry(0, Status::Idle),
+            entry(10, Status::Running),
+            entry(70, Status::Waiting),
+            entry(190, Status::Running),
+            entry(250, Status::Idle),
+            entry(300, Status::Running),
+            entry(320, Status::Waiting),
+        ];
+        let now = Utc.timestamp_opt(1_700_000_000 + 400, 0).unwrap();
+
+        let m = metrics(&entries, Status::Waiting, now);
+        assert_eq!(m.turns, 3);
+        assert_eq!(m.running_secs, 60 + 60 + 20);
+        // 120s on the first prompt plus the still-open 80s.
+        assert_eq!(m.waiting_secs, 200);
+        assert_eq!(m.time_to_first_idle_secs, Some(240));
+
+        // Stopped since the last entry: the open span doesn't count.
+        let m = metrics(&entries, Status::Stopped, now);
+        assert_eq!(m.waiting_secs, 120);
+    }
+
+    #[test]
+    fn stops_close_spans_and_split_turns() {
+        let entries = vec![
+            entry(0, Status::Running),
+            entry(60, Status::Stopped),
+            entry(600, Status::Running),
+            entry(630, Status::Idle),
+        ];
+        let m = metrics(&entries, Status::Idle, entries[3].at);
+        assert_eq!(m.turns, 2);
+        // The 540s the session sat stopped count as neither.
+        assert_eq!(m.running_secs, 60 + 30);
+        assert_eq!(m.time_to_first_idle_secs, None);
+    }
+
+    #[test]
+    fn trim_takes_lines_from_the_busiest_sessions() {
+        let mut content = String::new();
+        for i in 0..3 {
+            content.push_str(&format_line("quiet", &entry(i, Status::Idle)));
+        }
+        for i in 0..20 {
+            content.push_str(&format_line("busy", &entry(10 + i, Status::Running)));
+        }
+        let trimmed = trim(&content);
+        let ids: Vec<&str> = trimmed
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .collect();
+        assert_eq!(ids.iter().filter(|id| **id == "quiet").count(), 3);
+        assert_eq!(ids.iter().filter(|id| **id == "busy").count(), 8);
+        // The busy session keeps its newest lines, in order.
+        assert!(trimmed.ends_with(&format_line("busy", &entry(29, Status::Running))));
+    }
+
+    #[test]
+    fn metrics_without_an_idle_after_work_leave_first_idle_unset() {
+        let entries = vec![entry(0, Status::Idle), entry(5, Status::Running)];
+        let m = metrics(&entries, Status::Running, entries[1].at);
+        assert_eq!(m.turns, 1);
+        assert_eq!(m.time_to_first_idle_secs, None);
+        assert_eq!(
+            metrics(&[], Status::Idle, Utc::now()),
+            TimelineMetrics::default()
+        );
+    }
+
+    #[test]
+    fn format_secs_picks_the_two_largest_units() {
+        assert_eq!(format_secs(45), "45s");
+        assert_eq!(format_secs(725), "12m 05s");
+        assert_eq!(format_secs(12_000), "3h 20m");
+        assert_eq!(format_secs(-3), "0s");
+    }
+}
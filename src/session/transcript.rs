@@ -0,0 +1,713 @@
+//! Normalized conversation transcripts for any session.
+//!
+//! Cockpit sessions keep their transcript in the cockpit event store
+//! (`<app_dir>/cockpit_events.db`); tmux sessions leave it in the agent's
+//! own log, found through the captured `agent_session_id` (see
+//! [`super::capture::locate_agent_log`]). Both are flattened here into a
+//! list of [`TranscriptEntry`] values so search and export don't care
+//! where a conversation came from.
+//!
+//! The native parsers are deliberately lenient: agents change their log
+//! formats without notice, so unknown records and malformed lines are
+//! skipped rather than failing the whole transcript.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{params, Connection, OpenFlags};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::capture::{locate_agent_log, AgentLog};
+use super::Instance;
+
+/// File name of the cockpit event store inside the app dir.
+pub const COCKPIT_EVENTS_DB: &str = "cockpit_events.db";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    User,
+    Assistant,
+    ToolCall,
+    ToolResult,
+    Approval,
+}
+
+impl EntryKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntryKind::User => "user",
+            EntryKind::Assistant => "assistant",
+            EntryKind::ToolCall => "tool_call",
+            EntryKind::ToolResult => "tool_result",
+            EntryKind::Approval => "approval",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "user" => EntryKind::User,
+            "assistant" => EntryKind::Assistant,
+            "tool_call" => EntryKind::ToolCall,
+            "tool_result" => EntryKind::ToolResult,
+            "approval" => EntryKind::Approval,
+            _ => return None,
+        })
+    }
+}
+
+/// One turn fragment: a prompt, a reply, a tool call or its output, or an
+/// approval decision.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
+pub struct TranscriptEntry {
+    pub kind: EntryKind,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl TranscriptEntry {
+    fn new(kind: EntryKind, text: impl Into<String>, timestamp: Option<DateTime<Utc>>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            timestamp,
+        }
+    }
+}
+
+/// Where a session's transcript is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptSource {
+    Cockpit { db: PathBuf, session_id: String },
+    Claude(PathBuf),
+    Codex(PathBuf),
+    Gemini(PathBuf),
+    OpenCode { db: PathBuf, session_id: String },
+}
+
+impl TranscriptSource {
+    /// Resolve the transcript source for `inst`, or `None` when the agent
+    /// has no log aoe knows how to read (or none was captured yet).
+    pub fn for_instance(inst: &Instance) -> Option<Self> {
+        if inst.is_cockpit_mode() {
+            let db = super::get_app_dir().ok()?.join(COCKPIT_EVENTS_DB);
+            return db.exists().then(|| TranscriptSource::Cockpit {
+                db,
+                session_id: inst.id.clone(),
+            });
+        }
+        if inst.is_sandboxed() {
+            return None;
+        }
+        let agent_session_id = inst.agent_session_id.as_deref()?;
+        let tool = if inst.detect_as.is_empty() {
+            inst.tool.as_str()
+        } else {
+            inst.detect_as.as_str()
+        };
+        match locate_agent_log(tool, &inst.project_path, agent_session_id)? {
+            AgentLog::File(path) => match tool {
+                "claude" => Some(TranscriptSource::Claude(path)),
+                "codex" => Some(TranscriptSource::Codex(path)),
+                "gemini" => Some(TranscriptSource::Gemini(path)),
+                _ => None,
+            },
+            AgentLog::OpenCode { db, session_id } => {
+                Some(TranscriptSource::OpenCode { db, session_id })
+            }
+        }
+    }
+
+    /// Cheap change marker: equal fingerprints mean the transcript has not
+    /// grown since it was last read. `None` when the source is gone.
+    pub fn fingerprint(&self) -> Option<String> {
+        match self {
+            TranscriptSource::Claude(path)
+            | TranscriptSource::Codex(path)
+            | TranscriptSource::Gemini(path) => {
+                let meta = std::fs::metadata(path).ok()?;
+                let mtime = meta
+                    .modified()
+                    .ok()?
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .ok()?
+                    .as_nanos();
+                Some(format!("{}:{mtime}:{}", path.display(), meta.len()))
+            }
+            TranscriptSource::Cockpit { db, session_id } => {
+                let conn = open_read_only(db).ok()?;
+                let (count, max): (i64, Option<i64>) = conn
+                    .query_row(
+                        "SELECT COUNT(*), MAX(seq) FROM cockpit_events WHERE session_id = ?1",
+                        params![session_id],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .ok()?;
+                Some(format!("cockpit:{count}:{}", max.unwrap_or(0)))
+            }
+            TranscriptSource::OpenCode { db, session_id } => {
+                let conn = open_read_only(db).ok()?;
+                let (count, max): (i64, Option<i64>) = conn
+                    .query_row(
+                        "SELECT COUNT(*), MAX(time_updated) FROM part WHERE session_id = ?1",
+                        params![session_id],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .ok()?;
+                Some(format!("opencode:{count}:{}", max.unwrap_or(0)))
+            }
+        }
+    }
+
+    pub fn load(&self) -> Result<Vec<TranscriptEntry>> {
+        match self {
+            TranscriptSource::Cockpit { db, session_id } => load_cockpit(db, session_id),
+            TranscriptSource::Claude(path) => Ok(parse_claude_jsonl(&read(path)?)),
+            TranscriptSource::Codex(path) => Ok(parse_codex_jsonl(&read(path)?)),
+            TranscriptSource::Gemini(path) => Ok(parse_gemini(&read(path)?)),
+            TranscriptSource::OpenCode { db, session_id } => load_opencode(db, session_id),
+        }
+    }
+}
+
+/// Load the full transcript for `inst`. Empty when it has none.
+pub fn load_transcript(inst: &Instance) -> Result<Vec<TranscriptEntry>> {
+    match TranscriptSource::for_instance(inst) {
+        Some(source) => source.load(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))
+}
+
+fn open_read_only(db: &Path) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        db,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("open {}", db.display()))?;
+    conn.busy_timeout(Duration::from_millis(500))?;
+    Ok(conn)
+}
+
+fn parse_ts(v: Option<&Value>) -> Option<DateTime<Utc>> {
+    match v? {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|d| d.with_timezone(&Utc)),
+        Value::Number(n) => Utc.timestamp_millis_opt(n.as_i64()?).single(),
+        _ => None,
+    }
+}
+
+/// Join the text of a content value that is either a plain string or a
+/// list of `{ "text": ... }` blocks.
+fn content_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| match b {
+                Value::String(s) => Some(s.as_str()),
+                _ => b.get("text").and_then(Value::as_str),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Render a tool invocation as `name args`, with args compacted to one line.
+fn tool_call_text(name: &str, input: Option<&Value>) -> String {
+    match input {
+        None | Some(Value::Null) => name.to_string(),
+        Some(Value::String(s)) => format!("{name} {s}"),
+        Some(v) => format!("{name} {v}"),
+    }
+}
+
+fn push_text(
+    out: &mut Vec<TranscriptEntry>,
+    kind: EntryKind,
+    text: String,
+    ts: Option<DateTime<Utc>>,
+) {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        out.push(TranscriptEntry::new(kind, trimmed, ts));
+    }
+}
+
+/// Claude Code JSONL: one record per line, `type` of `user` or
+/// `assistant`, with `message.content` either a string or a list of
+/// `text` / `tool_use` / `tool_result` blocks.
+pub(crate) fn parse_claude_jsonl(content: &str) -> Vec<TranscriptEntry> {
+    let mut out = Vec::new();
+    for line in content.lines() {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let role = record.get("type").and_then(Value::as_str).unwrap_or("");
+        if !matches!(role, "user" | "assistant")
+            || record.get("isMeta").and_then(Value::as_bool) == Some(true)
+        {
+            continue;
+        }
+        let ts = parse_ts(record.get("timestamp"));
+        let Some(content) = record.get("message").and_then(|m| m.get("content")) else {
+            continue;
+        };
+        let text_kind = if role == "user" {
+            EntryKind::User
+        } else {
+            EntryKind::Assistant
+        };
+        let Value::Array(blocks) = content else {
+            push_text(&mut out, text_kind, content_text(content), ts);
+            continue;
+        };
+        for block in blocks {
+            match block.get("type").and_then(Value::as_str) {
+                Some("text") => push_text(&mut out, text_kind, content_text(&block["text"]), ts),
+                Some("tool_use") => {
+                    let name = block.get("name").and_then(Value::as_str).unwrap_or("tool");
+                    push_text(
+                        &mut out,
+                        EntryKind::ToolCall,
+                        tool_call_text(name, block.get("input")),
+                        ts,
+                    );
+                }
+                Some("tool_result") => {
+                    let text = block.get("content").map(content_text).unwrap_or_default();
+                    push_text(&mut out, EntryKind::ToolResult, text, ts);
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+/// Codex rollout JSONL: `response_item` records whose payload is a
+/// `message`, `function_call`, or `function_call_output`.
+pub(crate) fn parse_codex_jsonl(content: &str) -> Vec<TranscriptEntry> {
+    let mut out = Vec::new();
+    for line in content.lines() {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let ts = parse_ts(record.get("timestamp"));
+        let Some(payload) = record.get("payload") else {
+            continue;
+        };
+        match payload.get("type").and_then(Value::as_str) {
+            Some("message") => {
+                let kind = match payload.get("role").and_then(Value::as_str) {
+                    Some("user") => EntryKind::User,
+                    Some("assistant") => EntryKind::Assistant,
+                    _ => continue,
+                };
+                let text = payload.get("content").map(content_text).unwrap_or_default();
+                // Codex injects its environment and AGENTS.md as user
+                // messages; they are setup, not conversation.
+                let setup = ["<environment_context>", "<user_instructions>"];
+                if kind == EntryKind::User && setup.iter().any(|t| text.trim_start().starts_with(t))
+                {
+                    continue;
+                }
+                push_text(&mut out, kind, text, ts);
+            }
+            Some("function_call") | Some("custom_tool_call") => {
+                let name = payload
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("tool");
+                let input = payload.get("arguments").or_else(|| payload.get("input"));
+                push_text(
+                    &mut out,
+                    EntryKind::ToolCall,
+                    tool_call_text(name, input),
+                    ts,
+                );
+            }
+            Some("function_call_output") | Some("custom_tool_call_output") => {
+                let text = match payload.get("output") {
+                    Some(Value::Object(o)) => {
+                        o.get("content").map(content_text).unwrap_or_default()
+                    }
+                    Some(v) => content_text(v),
+                    None => String::new(),
+                };
+                push_text(&mut out, EntryKind::ToolResult, text, ts);
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Gemini chat files: a legacy single JSON object with a `messages` array,
+/// or JSONL whose first line is metadata and the rest are messages. A
+/// message has `type` `user` or `gemini` and optional `toolCalls`.
+pub(crate) fn parse_gemini(content: &str) -> Vec<TranscriptEntry> {
+    let messages: Vec<Value> = match serde_json::from_str::<Value>(content) {
+        Ok(v) => v
+            .get("messages")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default(),
+        Err(_) => content
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+            .collect(),
+    };
+    let mut out = Vec::new();
+    for msg in messages {
+        let ts = parse_ts(msg.get("timestamp"));
+        let kind = match msg.get("type").and_then(Value::as_str) {
+            Some("user") => EntryKind::User,
+            Some("gemini") | Some("model") => EntryKind::Assistant,
+            _ => continue,
+        };
+        let text = msg.get("content").map(content_text).unwrap_or_default();
+        push_text(&mut out, kind, text, ts);
+        for call in msg
+            .get("toolCalls")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let name = call.get("name").and_then(Value::as_str).unwrap_or("tool");
+            push_text(
+                &mut out,
+                EntryKind::ToolCall,
+                tool_call_text(name, call.get("args")),
+                ts,
+            );
+            if let Some(display) = call.get("resultDisplay").and_then(Value::as_str) {
+                push_text(&mut out, EntryKind::ToolResult, display.to_string(), ts);
+            }
+        }
+    }
+    out
+}
+
+/// opencode stores messages and their parts as JSON blobs in SQLite; the
+/// message carries the role, the part carries text or a tool invocation.
+fn load_opencode(db: &Path, session_id: &str) -> Result<Vec<TranscriptEntry>> {
+    let conn = open_read_only(db)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT m.data, p.data, p.time_created FROM part p
+             JOIN message m ON m.id = p.message_id
+             WHERE p.session_id = ?1
+             ORDER BY p.time_created, p.id",
+        )
+        .context("opencode part table schema mismatch")?;
+    let rows = stmt.query_map(params![session_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    })?;
+    let mut out = Vec::new();
+    for row in rows.flatten() {
+        let (Ok(message), Ok(part)) = (
+            serde_json::from_str::<Value>(&row.0),
+            serde_json::from_str::<Value>(&row.1),
+        ) else {
+            continue;
+        };
+        let ts = Utc.timestamp_millis_opt(row.2).single();
+        let kind = if message.get("role").and_then(Value::as_str) == Some("user") {
+            EntryKind::User
+        } else {
+            EntryKind::Assistant
+        };
+        match part.get("type").and_then(Value::as_str) {
+            Some("text") => push_text(&mut out, kind, content_text(&part["text"]), ts),
+            Some("tool") => {
+                let name = part.get("tool").and_then(Value::as_str).unwrap_or("tool");
+                let state = part.get("state");
+                push_text(
+                    &mut out,
+                    EntryKind::ToolCall,
+                    tool_call_text(name, state.and_then(|s| s.get("input"))),
+                    ts,
+                );
+                if let Some(output) = state.and_then(|s| s.get("output")).and_then(Value::as_str) {
+                    push_text(&mut out, EntryKind::ToolResult, output.to_string(), ts);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Replay the cockpit event log for one session. Events are externally
+/// tagged (`{"AgentMessageChunk": {"text": ...}}`); streamed chunks are
+/// joined into one assistant entry per uninterrupted run.
+fn load_cockpit(db: &Path, session_id: &str) -> Result<Vec<TranscriptEntry>> {
+    let conn = open_read_only(db)?;
+    let mut stmt = conn.prepare(
+        "SELECT event_json, created_at FROM cockpit_events
+         WHERE session_id = ?1 ORDER BY seq ASC",
+    )?;
+    let rows = stmt.query_map(params![session_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    let events = rows
+        .flatten()
+        .filter_map(|(json, ms)| {
+            let event = serde_json::from_str::<Value>(&json).ok()?;
+            Some((event, Utc.timestamp_millis_opt(ms).single()))
+        })
+        .collect::<Vec<_>>();
+    Ok(cockpit_entries(&events))
+}
+
+pub(crate) fn cockpit_entries(events: &[(Value, Option<DateTime<Utc>>)]) -> Vec<TranscriptEntry> {
+    fn flush(reply: &mut Option<TranscriptEntry>, out: &mut Vec<TranscriptEntry>) {
+        if let Some(r) = reply.take() {
+            push_text(out, r.kind, r.text, r.timestamp);
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut reply: Option<TranscriptEntry> = None;
+    for (event, ts) in events {
+        let Some((tag, body)) = event.as_object().and_then(|o| o.iter().next()) else {
+            continue;
+        };
+        let str_field = |key: &str| body.get(key).and_then(Value::as_str).unwrap_or("");
+        if tag == "AgentMessageChunk" {
+            reply
+                .get_or_insert_with(|| TranscriptEntry::new(EntryKind::Assistant, "", *ts))
+                .text
+                .push_str(str_field("text"));
+            continue;
+        }
+        match tag.as_str() {
+            "UserPromptSent" => {
+                flush(&mut reply, &mut out);
+                push_text(
+                    &mut out,
+                    EntryKind::User,
+                    str_field("text").to_string(),
+                    *ts,
+                );
+            }
+            "ToolCallStarted" => {
+                flush(&mut reply, &mut out);
+                let call = &body["tool_call"];
+                let name = call.get("name").and_then(Value::as_str).unwrap_or("tool");
+                let args = call
+                    .get("args_preview")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                push_text(&mut out, EntryKind::ToolCall, format!("{name} {args}"), *ts);
+            }
+            "ToolCallCompleted" => {
+                flush(&mut reply, &mut out);
+                push_text(
+                    &mut out,
+                    EntryKind::ToolResult,
+                    str_field("content").to_string(),
+                    *ts,
+                );
+            }
+            "ApprovalRequested" => {
+                flush(&mut reply, &mut out);
+                let call = &body["approval"]["tool_call"];
+                let name = call.get("name").and_then(Value::as_str).unwrap_or("tool");
+                let args = call
+                    .get("args_preview")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                push_text(
+                    &mut out,
+                    EntryKind::Approval,
+                    format!("Requested: {name} {args}"),
+                    *ts,
+                );
+            }
+            "ApprovalResolved" => {
+                flush(&mut reply, &mut out);
+                push_text(
+                    &mut out,
+                    EntryKind::Approval,
+                    match body.get("rule").and_then(Value::as_str) {
+                        Some(rule) => format!("Decision: {} (rule: {rule})", str_field("decision")),
+                        None => format!("Decision: {}", str_field("decision")),
+                    },
+                    *ts,
+                );
+            }
+            _ => {}
+        }
+    }
+    flush(&mut reply, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn kinds(entries: &[TranscriptEntry]) -> Vec<EntryKind> {
+        entries.iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn claude_blocks_become_entries() {
+        let log = [
+            json!({"type": "summary", "summary": "ignored"}),
+            json!({"type": "user", "timestamp": "2026-01-02T03:04:05Z",
+                   "message": {"role": "user", "content": "fix the flaky test"}}),
+            json!({"type": "assistant", "message": {"content": [
+                {"type": "thinking", "thinking": "hidden"},
+                {"type": "text", "text": "Running it now."},
+                {"type": "tool_use", "name": "Bash", "input": {"command": "cargo test"}}
+            ]}}),
+            json!({"type": "user", "message": {"content": [
+                {"type": "tool_result", "content": [{"type": "text", "text": "1 failed"}]}
+            ]}}),
+        ]
+        .iter()
+        .map(|v| v.to_string())
+        .chain(["{not json".to_string()])
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let entries = parse_claude_jsonl(&log);
+        assert_eq!(
+            kinds(&entries),
+            [
+                EntryKind::User,
+                EntryKind::Assistant,
+                EntryKind::ToolCall,
+                EntryKind::ToolResult
+            ]
+        );
+        assert_eq!(entries[0].text, "fix the flaky test");
+        assert!(entries[0].timestamp.is_some());
+        assert!(entries[2].text.starts_with("Bash "));
+        assert!(entries[2].text.contains("cargo test"));
+        assert_eq!(entries[3].text, "1 failed");
+    }
+
+    #[test]
+    fn codex_skips_setup_messages() {
+        let log = [
+            json!({"type": "session_meta", "payload": {"cwd": "/tmp"}}),
+            json!({"type": "response_item", "payload": {"type": "message", "role": "user",
+                   "content": [{"type": "input_text", "text": "<environment_context>cwd</environment_context>"}]}}),
+            json!({"type": "response_item", "payload": {"type": "message", "role": "user",
+                   "content": [{"type": "input_text", "text": "rename the crate"}]}}),
+            json!({"type": "response_item", "payload": {"type": "function_call", "name": "shell",
+                   "arguments": "{\"command\":[\"ls\"]}"}}),
+            json!({"type": "response_item", "payload": {"type": "function_call_output",
+                   "output": "Cargo.toml"}}),
+            json!({"type": "response_item", "payload": {"type": "message", "role": "assistant",
+                   "content": [{"type": "output_text", "text": "Done."}]}}),
+        ]
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let entries = parse_codex_jsonl(&log);
+        assert_eq!(
+            kinds(&entries),
+            [
+                EntryKind::User,
+                EntryKind::ToolCall,
+                EntryKind::ToolResult,
+                EntryKind::Assistant
+            ]
+        );
+        assert_eq!(entries[0].text, "rename the crate");
+        assert_eq!(entries[2].text, "Cargo.toml");
+    }
+
+    #[test]
+    fn gemini_reads_json_and_jsonl() {
+        let legacy = json!({"sessionId": "s", "messages": [
+            {"type": "user", "content": "hello"},
+            {"type": "gemini", "content": "hi", "toolCalls": [
+                {"name": "read_file", "args": {"path": "a.rs"}, "resultDisplay": "fn main"}
+            ]},
+            {"type": "info", "content": "ignored"}
+        ]})
+        .to_string();
+        let entries = parse_gemini(&legacy);
+        assert_eq!(
+            kinds(&entries),
+            [
+                EntryKind::User,
+                EntryKind::Assistant,
+                EntryKind::ToolCall,
+                EntryKind::ToolResult
+            ]
+        );
+
+        let jsonl = [
+            json!({"sessionId": "s", "projectHash": "h"}),
+            json!({"type": "user", "content": [{"text": "hello"}]}),
+        ]
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+        let entries = parse_gemini(&jsonl);
+        assert_eq!(kinds(&entries), [EntryKind::User]);
+        assert_eq!(entries[0].text, "hello");
+    }
+
+    #[test]
+    fn cockpit_chunks_are_coalesced() {
+        let events = [
+            json!({"UserPromptSent": {"text": "add a test"}}),
+            json!({"AgentMessageChunk": {"text": "On "}}),
+            json!({"AgentMessageChunk": {"text": "it."}}),
+            json!({"ToolCallStarted": {"tool_call": {"id": "t1", "name": "Edit", "args_preview": "src/lib.rs"}}}),
+            json!({"ApprovalRequested": {"approval": {"tool_call": {"name": "Edit", "args_preview": "src/lib.rs"}}}}),
+            json!({"ApprovalResolved": {"nonce": "n", "decision": "Allow"}}),
+            json!({"ToolCallCompleted": {"tool_call_id": "t1", "is_error": false, "content": "ok"}}),
+            json!("ThinkingStarted"),
+            json!({"AgentMessageChunk": {"text": "Added."}}),
+        ]
+        .into_iter()
+        .map(|e| (e, None))
+        .collect::<Vec<_>>();
+
+        let entries = cockpit_entries(&events);
+        assert_eq!(
+            kinds(&entries),
+            [
+                EntryKind::User,
+                EntryKind::Assistant,
+                EntryKind::ToolCall,
+                EntryKind::Approval,
+                EntryKind::Approval,
+                EntryKind::ToolResult,
+                EntryKind::Assistant
+            ]
+        );
+        assert_eq!(entries[1].text, "On it.");
+        assert_eq!(entries[4].text, "Decision: Allow");
+        assert_eq!(entries[6].text, "Added.");
+    }
+}
@@ -401,6 +401,55 @@ impl Session {
         Ok(())
     }
 
+    /// Stream the agent pane's output into `command` via `tmux pipe-pane`.
+    /// `-o` only opens a pipe when none is attached, so calling this twice
+    /// never toggles an active pipe off.
+    pub fn pipe_pane_output(&self, command: &str) -> Result<()> {
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args(["pipe-pane", "-O", "-o", "-t", &target, command])
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to pipe pane output: {}", stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Whether the agent pane already has a `pipe-pane` command attached.
+    /// Errors read as "not piped".
+    pub fn pane_pipe_active(&self) -> bool {
+        let target = format!("{}:^.0", self.name);
+        Command::new("tmux")
+            .args(["display-message", "-p", "-t", &target, "#{pane_pipe}"])
+            .output()
+            .is_ok_and(|output| {
+                output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "1"
+            })
+    }
+
+    /// Current `(cols, rows)` of the agent pane, or `None` when tmux
+    /// cannot report it.
+    pub fn pane_size(&self) -> Option<(u16, u16)> {
+        let target = format!("{}:^.0", self.name);
+        let output = Command::new("tmux")
+            .args([
+                "display-message",
+                "-p",
+                "-t",
+                &target,
+                "#{pane_width} #{pane_height}",
+            ])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        let (cols, rows) = text.trim().split_once(' ')?;
+        Some((cols.parse().ok()?, rows.parse().ok()?))
+    }
+
     /// Restore automatic window sizing after live-send forced a manual
     /// size. tmux's `resize-window -x -y` silently switches the window-
     /// size option to `manual`, so without this call a later
@@ -1086,6 +1086,12 @@ impl App {
                 needs_full_refresh = true;
             }
 
+            if let Some(text) = self.home.apply_broadcast_results() {
+                self.update_status = Some(UpdateStatus::transient(text));
+                refresh_needed = true;
+                needs_full_refresh = true;
+            }
+
             if let Some(session_id) = self.home.apply_creation_results() {
                 self.dispatch_new_session_attach(&session_id, terminal)?;
                 refresh_needed = true;
@@ -1740,6 +1746,26 @@ impl App {
         Ok(())
     }
 
+    /// Stop `id` on a background thread: `inst.stop()` calls `docker stop`
+    /// for sandboxed sessions, which can block for the container's grace
+    /// period (~10s) and would otherwise freeze the TUI (issue #1496). Set
+    /// Stopped immediately so the status poller won't override to Error
+    /// while the stop is in flight; the result is applied in the main loop
+    /// via `apply_stop_results`.
+    fn request_stop(&mut self, id: &str) -> Result<()> {
+        if let Some(inst) = self.home.get_instance(id) {
+            let request = crate::tui::stop_poller::StopRequest {
+                session_id: id.to_string(),
+                instance: inst.clone(),
+            };
+            self.home
+                .set_instance_status(id, crate::session::Status::Stopped);
+            self.home.save()?;
+            self.home.stop_poller.request_stop(request);
+        }
+        Ok(())
+    }
+
     fn execute_action(
         &mut self,
         action: Action,
@@ -1760,22 +1786,11 @@ impl App {
                 self.edit_file(&path, terminal)?;
             }
             Action::StopSession(id) => {
-                if let Some(inst) = self.home.get_instance(&id) {
-                    // Run the stop on a background thread: `inst.stop()` calls
-                    // `docker stop` for sandboxed sessions, which can block for
-                    // the container's grace period (~10s) and would otherwise
-                    // freeze the TUI (issue #1496). Set Stopped immediately so
-                    // the status poller won't override to Error while the stop
-                    // is in flight; the result is applied in the main loop via
-                    // `apply_stop_results`.
-                    let request = crate::tui::stop_poller::StopRequest {
-                        session_id: id.clone(),
-                        instance: inst.clone(),
-                    };
-                    self.home
-                        .set_instance_status(&id, crate::session::Status::Stopped);
-                    self.home.save()?;
-                    self.home.stop_poller.request_stop(request);
+                self.request_stop(&id)?;
+            }
+            Action::StopSessions(ids) => {
+                for id in ids {
+                    self.request_stop(&id)?;
                 }
             }
             Action::SetTheme(name) => {
@@ -1815,6 +1830,36 @@ impl App {
                     }
                 }
             }
+            Action::SendReview(id) => {
+                self.home
+                    .set_instance_status(&id, crate::session::Status::Starting);
+                self.update_status = Some(UpdateStatus::transient("Sending review...".into()));
+                self.draw(terminal)?;
+                self.update_status = self
+                    .home
+                    .execute_send_review(&id)
+                    .map(|sid| {
+                        UpdateStatus::transient(format!(
+                            "Resume failed for sid {sid}; review sent to fresh session (history not loaded)"
+                        ))
+                    });
+            }
+            Action::BroadcastMessage(ids, message) => {
+                // Each send runs the same revive path as SendMessage, so a
+                // cold session still gets started before its message
+                // lands. That can take seconds per session, so the sends
+                // run on the broadcast worker; `apply_broadcast_results`
+                // reports progress and failures through the toast.
+                for id in &ids {
+                    self.home
+                        .set_instance_status(id, crate::session::Status::Starting);
+                }
+                self.update_status = Some(UpdateStatus::transient(format!(
+                    "Sending to {} sessions...",
+                    ids.len()
+                )));
+                self.home.request_broadcast(&ids, &message);
+            }
             Action::EnterLiveSend(id) => {
                 // Same revive flow as SendMessage so cold-start (Docker,
                 // agent splash) gives the user "Reviving..." feedback.
@@ -2279,6 +2324,8 @@ pub enum Action {
     AttachTerminal(String, TerminalMode),
     EditFile(PathBuf),
     StopSession(String),
+    /// Bulk stop of the marked sessions, confirmed once.
+    StopSessions(Vec<String>),
     SetTheme(String),
     SpawnUpdate(crate::update::install::InstallMethod, String),
     SetTransientStatus(String),
@@ -2287,6 +2334,12 @@ pub enum Action {
     /// can render a "Reviving..." status before the potentially-slow
     /// ensure_pane_ready call.
     SendMessage(String, String),
+    /// Send one message to every marked session's agent. Deferred for the
+    /// same reason as `SendMessage`.
+    BroadcastMessage(Vec<String>, String),
+    /// Deliver a session's pending review comments from the diff view.
+    /// Deferred for the same reason as `SendMessage`.
+    SendReview(String),
     /// Enter live-send mode on a session. Same revive-and-stage pattern
     /// as `SendMessage`: the deferred action lets the app loop render the
     /// "Reviving..." toast before `ensure_pane_ready` runs, then the home
@@ -0,0 +1,160 @@
+//! Background message broadcast for TUI responsiveness.
+//!
+//! Sending to a marked set of sessions revives each pane first, and a cold
+//! pane can take several seconds (Docker start, agent splash, resume
+//! cascade). Doing that for every session on the UI event loop froze the
+//! TUI for the whole batch. This mirrors `StopPoller`: each session's send
+//! goes to a worker thread, and its result comes back over a channel the
+//! main loop polls each frame.
+
+use std::sync::mpsc;
+use std::thread;
+
+use crate::session::{EnsureReadyOutcome, Instance};
+
+pub struct BroadcastRequest {
+    pub session_id: String,
+    /// Snapshot to revive and send through. The worker mutates it; the
+    /// post-start fields come back in [`BroadcastResult::instance`].
+    pub instance: Instance,
+    pub message: String,
+}
+
+pub struct BroadcastResult {
+    pub session_id: String,
+    pub instance: Box<Instance>,
+    /// The stale agent session id when the resume fallback fired, or the
+    /// user-facing failure.
+    pub result: Result<Option<String>, String>,
+}
+
+pub struct BroadcastPoller {
+    request_tx: mpsc::Sender<BroadcastRequest>,
+    result_rx: mpsc::Receiver<BroadcastResult>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl BroadcastPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<BroadcastRequest>();
+        let (result_tx, result_rx) = mpsc::channel::<BroadcastResult>();
+
+        let handle = thread::spawn(move || {
+            Self::send_loop(request_rx, result_tx);
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn send_loop(
+        request_rx: mpsc::Receiver<BroadcastRequest>,
+        result_tx: mpsc::Sender<BroadcastResult>,
+    ) {
+        while let Ok(request) = request_rx.recv() {
+            if result_tx.send(perform_send(request)).is_err() {
+                break;
+            }
+        }
+    }
+
+    pub fn request_send(&self, request: BroadcastRequest) {
+        if let Err(e) = self.request_tx.send(request) {
+            tracing::warn!(target: "tui.broadcast_poller", error = %e, "send request dropped; worker thread unavailable");
+        }
+    }
+
+    pub fn try_recv_result(&self) -> Option<BroadcastResult> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for BroadcastPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Revive the agent pane and type the message, like the single-session
+/// send path in `HomeView::deliver_message`.
+fn perform_send(request: BroadcastRequest) -> BroadcastResult {
+    let BroadcastRequest {
+        session_id,
+        mut instance,
+        message,
+    } = request;
+    let result = send_to_agent(&mut instance, &message);
+    if let Err(e) = &result {
+        tracing::warn!(target: "tui.broadcast_poller", session_id = %session_id, error = %e, "broadcast send failed");
+    }
+    BroadcastResult {
+        session_id,
+        instance: Box::new(instance),
+        result,
+    }
+}
+
+fn send_to_agent(inst: &mut Instance, message: &str) -> Result<Option<String>, String> {
+    let stale_sid = match inst.ensure_pane_ready() {
+        Ok(
+            EnsureReadyOutcome::Respawned {
+                stale_sid: Some(sid),
+            }
+            | EnsureReadyOutcome::Started {
+                stale_sid: Some(sid),
+            },
+        ) => Some(sid),
+        Ok(_) => None,
+        Err(e) => return Err(format!("Cannot prepare session: {}", e)),
+    };
+    let tmux_session = crate::tmux::Session::new(&inst.id, &inst.title)
+        .map_err(|e| format!("Failed to resolve session: {}", e))?;
+    tmux_session
+        .send_keys_with_delay(message, crate::agents::send_keys_enter_delay(&inst.tool))
+        .map_err(|e| format!("Failed to send message: {}", e))?;
+    Ok(stale_sid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_broadcast_poller_reports_each_failure() {
+        let poller = BroadcastPoller::new();
+        // Creating sessions can't be revived, so the send fails without
+        // touching tmux.
+        let mut instance = Instance::new("Test Session", "/tmp/test-project");
+        instance.status = crate::session::Status::Creating;
+        let session_id = instance.id.clone();
+
+        poller.request_send(BroadcastRequest {
+            session_id: session_id.clone(),
+            instance,
+            message: "hello".to_string(),
+        });
+
+        let mut result = None;
+        for _ in 0..50 {
+            result = poller.try_recv_result();
+            if result.is_some() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let result = result.expect("Timed out waiting for broadcast result");
+        assert_eq!(result.session_id, session_id);
+        let err = result.result.unwrap_err();
+        assert!(err.starts_with("Cannot prepare session"), "{err}");
+    }
+
+    #[test]
+    fn test_broadcast_poller_try_recv_returns_none_when_empty() {
+        let poller = BroadcastPoller::new();
+        assert!(poller.try_recv_result().is_none());
+    }
+}
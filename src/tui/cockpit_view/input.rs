@@ -35,6 +35,8 @@ pub enum Intent {
     CancelInFlight,
     /// Open the daemon URL for this session in the user's browser.
     OpenInBrowser,
+    /// Show or hide the usage panel (quota across all sessions).
+    ToggleUsage,
     /// Move focus to the named region.
     SetFocus(Focus),
     /// Exit the cockpit view; return to the home screen.
@@ -110,6 +112,7 @@ fn transcript_keys(key: &KeyEvent, has_pending_approval: bool) -> Intent {
         (m, KeyCode::Char('G')) if m.contains(KeyModifiers::SHIFT) => Intent::Scroll(i32::MAX),
         // Plain 'o' opens browser only when transcript is focused.
         (m, KeyCode::Char('o')) if m.is_empty() => Intent::OpenInBrowser,
+        (m, KeyCode::Char('u')) if m.is_empty() => Intent::ToggleUsage,
         _ => Intent::Ignore,
     }
 }
@@ -231,6 +234,18 @@ mod tests {
         assert_eq!(transcript, Intent::OpenInBrowser);
     }
 
+    #[test]
+    fn u_toggles_usage_only_from_transcript() {
+        assert_eq!(
+            dispatch(Focus::Transcript, &key(KeyCode::Char('u')), false),
+            Intent::ToggleUsage
+        );
+        assert!(matches!(
+            dispatch(Focus::Composer, &key(KeyCode::Char('u')), false),
+            Intent::Compose(_)
+        ));
+    }
+
     #[test]
     fn enter_in_composer_submits() {
         let intent = dispatch(Focus::Composer, &key(KeyCode::Enter), false);
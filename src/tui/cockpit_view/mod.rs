@@ -37,6 +37,8 @@ use crate::tui::styles::Theme;
 const REDRAW_INTERVAL: Duration = Duration::from_millis(120);
 /// Toasts auto-clear after this long.
 const TOAST_TTL: Duration = Duration::from_secs(4);
+/// How often the open usage panel refetches `/api/cockpit/usage`.
+const USAGE_REFRESH: Duration = Duration::from_secs(30);
 
 /// Set up an alternate-screen terminal, run the cockpit view against
 /// the given session, and tear it back down on exit. Used by the
@@ -166,6 +168,7 @@ pub async fn run_for_endpoint(
     state.focus = Focus::Transcript;
 
     let mut toast_deadline: Option<Instant> = None;
+    let mut usage_fetched_at: Option<Instant> = None;
 
     // Capture both startup-path errors before showing a toast so we
     // can fold them into a single message when both fail (they
@@ -222,6 +225,12 @@ pub async fn run_for_endpoint(
                 if should_exit {
                     return Ok(());
                 }
+                if state.show_usage && usage_fetched_at.is_none() {
+                    refresh_usage(&mut state, &mut toast_deadline).await;
+                    usage_fetched_at = Some(Instant::now());
+                } else if !state.show_usage {
+                    usage_fetched_at = None;
+                }
                 redraw(terminal, theme, &state)?;
             }
             ws_msg = recv_ws(&mut state) => {
@@ -288,6 +297,12 @@ pub async fn run_for_endpoint(
                         toast_deadline = None;
                     }
                 }
+                if state.show_usage
+                    && usage_fetched_at.is_some_and(|at| now.duration_since(at) >= USAGE_REFRESH)
+                {
+                    refresh_usage(&mut state, &mut toast_deadline).await;
+                    usage_fetched_at = Some(now);
+                }
                 redraw(terminal, theme, &state)?;
             }
         }
@@ -417,6 +432,10 @@ async fn handle_terminal_event(
             }
             Ok(false)
         }
+        Intent::ToggleUsage => {
+            state.show_usage = !state.show_usage;
+            Ok(false)
+        }
         Intent::OpenInBrowser => {
             let url = format!(
                 "{}/sessions/{}/cockpit",
@@ -442,6 +461,20 @@ async fn handle_terminal_event(
     }
 }
 
+/// Refetch the usage report for the panel. Keeps the previous report
+/// on failure so a daemon hiccup doesn't blank the panel.
+async fn refresh_usage(state: &mut CockpitViewState, toast_deadline: &mut Option<Instant>) {
+    match state.http.usage().await {
+        Ok(report) => state.usage = Some(report),
+        Err(e) => set_toast(
+            state,
+            toast_deadline,
+            format!("usage fetch failed: {e}"),
+            ToastKind::Error,
+        ),
+    }
+}
+
 /// Async pull from the cockpit WebSocket. Returns `None` when no ws
 /// handle is currently attached so the select arm degrades to a
 /// timed wait instead of busy-looping.
@@ -278,7 +278,9 @@ impl CockpitTranscript {
                 self.approval_idx.insert(nonce.clone(), idx);
                 self.pending_approvals.push(PendingApproval { nonce });
             }
-            Event::ApprovalResolved { nonce, decision } => {
+            Event::ApprovalResolved {
+                nonce, decision, ..
+            } => {
                 self.flush_pending_chunk();
                 if let Some(&idx) = self.approval_idx.get(&nonce.0) {
                     if let Some(ActivityRow::Approval(row)) = self.rows.get_mut(idx) {
@@ -349,6 +351,17 @@ impl CockpitTranscript {
                         .into(),
                 });
             }
+            Event::Rewound { turn, .. } => {
+                self.flush_pending_chunk();
+                self.available_commands.clear();
+                self.current_mode = None;
+                self.rows.push(ActivityRow::Note {
+                    kind: NoteKind::Warning,
+                    text: format!(
+                        "rewound to before turn {turn}; files restored, the model no longer remembers earlier turns"
+                    ),
+                });
+            }
             Event::ConversationCompacted => {
                 // /compact replaced the model's context with a summary;
                 // the model retains continuity, so this is informational
@@ -529,6 +542,7 @@ mod tests {
             destructive: true,
             requested_at: Utc::now(),
             resolved: None,
+            policy_rule: None,
         };
         t.apply(&frame(1, Event::ApprovalRequested { approval }));
         assert_eq!(t.pending_approvals.len(), 1);
@@ -538,6 +552,7 @@ mod tests {
             Event::ApprovalResolved {
                 nonce: Nonce("nonce-1".into()),
                 decision: ApprovalDecision::Allow,
+                rule: None,
             },
         ));
         assert!(t.pending_approvals.is_empty());
@@ -27,11 +27,23 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &CockpitViewS
         ])
         .split(area);
 
-    render_transcript(frame, chunks[0], theme, state);
+    if state.show_usage {
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(20), Constraint::Length(USAGE_PANEL_WIDTH)])
+            .split(chunks[0]);
+        render_transcript(frame, cols[0], theme, state);
+        render_usage(frame, cols[1], theme, state);
+    } else {
+        render_transcript(frame, chunks[0], theme, state);
+    }
     render_status(frame, chunks[1], theme, state);
     render_composer(frame, chunks[2], theme, state);
 }
 
+/// Columns taken by the usage panel when it's open.
+const USAGE_PANEL_WIDTH: u16 = 46;
+
 /// Top + bottom border rows wrapping the composer textarea.
 const COMPOSER_BORDER_ROWS: u16 = 2;
 /// Maximum content rows the composer is allowed to take before the
@@ -99,6 +111,82 @@ fn visual_line_count(lines: &[Line], width: u16) -> u16 {
     total.min(u16::MAX as usize) as u16
 }
 
+/// Quota across every cockpit session, one block per provider, so the
+/// user can see which shared limit is close before sending a big task.
+fn render_usage(frame: &mut Frame, area: Rect, theme: &Theme, state: &CockpitViewState) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Usage (u to close) ")
+        .border_style(Style::default().fg(theme.border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let para = Paragraph::new(usage_lines(state, theme)).wrap(Wrap { trim: false });
+    frame.render_widget(para, inner);
+}
+
+fn usage_lines<'a>(state: &'a CockpitViewState, theme: &Theme) -> Vec<Line<'a>> {
+    use crate::cockpit::usage::{format_context, format_local_time, format_span};
+
+    let Some(report) = &state.usage else {
+        return vec![Line::from(Span::styled(
+            "loading…",
+            Style::default().fg(theme.hint),
+        ))];
+    };
+    if report.providers.is_empty() {
+        return vec![Line::from(Span::styled(
+            "no cockpit turns recorded yet",
+            Style::default().fg(theme.hint),
+        ))];
+    }
+    let now = report.generated_at;
+    let mut out = Vec::new();
+    for provider in &report.providers {
+        out.push(Line::from(Span::styled(
+            provider.provider.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        let headline_color = if provider.limited_until.is_some() {
+            theme.error
+        } else {
+            theme.title
+        };
+        out.push(Line::from(Span::styled(
+            format!("  {}", provider.headline(report.window_hours, now)),
+            Style::default().fg(headline_color),
+        )));
+        for s in &provider.sessions {
+            let current = s.session_id == state.session_id;
+            let name = s.title.as_deref().unwrap_or(&s.session_id);
+            let detail = match (s.limited_until, s.context_used, s.context_size) {
+                (Some(until), _, _) => format!("until {}", format_local_time(until, now)),
+                (None, Some(used), Some(size)) => format_context(used, size),
+                _ => s
+                    .last_turn_at
+                    .map(|at| format!("{} ago", format_span(now - at)))
+                    .unwrap_or_default(),
+            };
+            let style = if current {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else if s.limited_until.is_some() {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default()
+            };
+            out.push(Line::from(vec![
+                Span::raw(if current { "  ▸ " } else { "    " }),
+                Span::styled(format!("{name:.18}"), style),
+                Span::styled(
+                    format!("  {}t  {detail}", s.window_turns),
+                    Style::default().fg(theme.hint),
+                ),
+            ]));
+        }
+        out.push(Line::default());
+    }
+    out
+}
+
 fn render_status(frame: &mut Frame, area: Rect, theme: &Theme, state: &CockpitViewState) {
     let mut spans: Vec<Span> = Vec::new();
     if let Some(toast) = &state.toast {
@@ -345,7 +433,9 @@ fn border_style(theme: &Theme, state: &CockpitViewState, this_focus: Focus) -> S
 fn help_hint(focus: Focus) -> &'static str {
     match focus {
         Focus::Composer => " Enter=send · Shift+Enter=newline · Esc=back · Ctrl-C=cancel ",
-        Focus::Transcript => " j/k=scroll · i=compose · Tab=approvals · o=browser · Esc=exit ",
+        Focus::Transcript => {
+            " j/k=scroll · i=compose · Tab=approvals · u=usage · o=browser · Esc=exit "
+        }
         Focus::Approval => " a=allow · A=always · d=deny · Esc=back ",
     }
 }
@@ -9,6 +9,7 @@ use ratatui_textarea::TextArea;
 use super::input::Focus;
 use super::reducer::CockpitTranscript;
 use crate::cockpit::client::{DaemonEndpoint, HttpClient, WsHandle};
+use crate::cockpit::usage::UsageReport;
 
 pub struct CockpitViewState {
     pub session_id: String,
@@ -26,6 +27,11 @@ pub struct CockpitViewState {
     /// Toast banner that appears briefly above the composer, e.g.
     /// "prompt sent" or an HTTP error.
     pub toast: Option<ToastBanner>,
+    /// Usage panel beside the transcript, toggled with `u`.
+    pub show_usage: bool,
+    /// Last `/api/cockpit/usage` report; refreshed while the panel is
+    /// open.
+    pub usage: Option<UsageReport>,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +67,8 @@ impl CockpitViewState {
             selected_approval: None,
             ws,
             toast: None,
+            show_usage: false,
+            usage: None,
         }
     }
 
@@ -11,6 +11,7 @@ use ratatui::widgets::*;
 use unicode_width::UnicodeWidthStr;
 
 use crate::session::config::SortOrder;
+use crate::tui::home::bindings::Keymap;
 use crate::tui::styles::Theme;
 
 /// Width of the key column, in cells. 11 fits the longest key strings
@@ -30,7 +31,11 @@ const SMALL_VIEWPORT_WIDTH: u16 = 40;
 /// Same idea for height: drop top/bottom margin below this.
 const SMALL_VIEWPORT_HEIGHT: u16 = 16;
 
-fn shortcuts(strict: bool, live_on_enter: bool) -> Vec<(&'static str, Vec<(String, String)>)> {
+fn shortcuts(
+    keymap: &Keymap,
+    strict: bool,
+    live_on_enter: bool,
+) -> Vec<(&'static str, Vec<(String, String)>)> {
     use crate::tui::home::bindings::{self, HelpSection as Sec};
 
     let (enter_desc, tab_desc) = if live_on_enter {
@@ -41,18 +46,19 @@ fn shortcuts(strict: bool, live_on_enter: bool) -> Vec<(&'static str, Vec<(Strin
 
     // Action rows are generated from the shared keybinding registry, bucketed
     // by section in table order, so the help labels can never drift from the
-    // actual bindings. `label` formats each chord for the active mode; an
-    // action with no binding in this mode (only NextWaiting, in strict) falls
-    // back to its non-strict label so it stays discoverable.
+    // actual bindings (including the user's `[keybindings]`). `label` formats
+    // each chord for the active mode; an action with no binding in this mode
+    // (only NextWaiting, in strict) falls back to its non-strict label so it
+    // stays discoverable. An action the user unbound has no row.
     let mut actions: Vec<(String, String)> = Vec::new();
     let mut attention: Vec<(String, String)> = Vec::new();
     let mut views: Vec<(String, String)> = Vec::new();
     let mut other: Vec<(String, String)> = Vec::new();
     for b in bindings::BINDINGS {
         let Some(help) = &b.help else { continue };
-        let mut label = bindings::label(b.id, strict);
+        let mut label = keymap.label(b.id, strict);
         if label.is_empty() {
-            label = bindings::label(b.id, false);
+            label = keymap.label(b.id, false);
         }
         if label.is_empty() {
             continue;
@@ -76,7 +82,14 @@ fn shortcuts(strict: bool, live_on_enter: bool) -> Vec<(&'static str, Vec<(Strin
 
     // Non-action rows with no single registry binding.
     views.push(("< >".to_string(), "Resize list panel".to_string()));
-    other.push(("n/N".to_string(), "Next/prev match".to_string()));
+    let search_cycle = [
+        bindings::ActionId::SearchNext,
+        bindings::ActionId::SearchPrev,
+    ]
+    .map(|id| keymap.label(id, strict));
+    if search_cycle.iter().all(|l| !l.is_empty()) {
+        other.push((search_cycle.join("/"), "Next/prev match".to_string()));
+    }
     other.push((
         "Ctrl+x".to_string(),
         "Dismiss update bar (this session)".to_string(),
@@ -126,8 +139,13 @@ struct HelpSection {
     rows: Vec<(String, String)>,
 }
 
-fn build_sections(strict: bool, sort_order: SortOrder, live_on_enter: bool) -> Vec<HelpSection> {
-    let raw = shortcuts(strict, live_on_enter);
+fn build_sections(
+    keymap: &Keymap,
+    strict: bool,
+    sort_order: SortOrder,
+    live_on_enter: bool,
+) -> Vec<HelpSection> {
+    let raw = shortcuts(keymap, strict, live_on_enter);
     let sort_label = format!("(current sort: {})", sort_order.label());
     raw.into_iter()
         .map(|(title, keys)| {
@@ -280,11 +298,13 @@ impl HelpOverlay {
     /// clamped to the valid range so that overshoot from input handlers
     /// (for example, treating `End` as `u16::MAX`) is naturally
     /// corrected on the next paint.
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         frame: &mut Frame,
         area: Rect,
         theme: &Theme,
         sort_order: SortOrder,
+        keymap: &Keymap,
         strict_hotkeys: bool,
         live_on_enter: bool,
         scroll: &mut u16,
@@ -295,7 +315,7 @@ impl HelpOverlay {
         let dialog_area = compute_dialog_area(area);
         frame.render_widget(Clear, dialog_area);
 
-        let sections = build_sections(strict_hotkeys, sort_order, live_on_enter);
+        let sections = build_sections(keymap, strict_hotkeys, sort_order, live_on_enter);
 
         let version = format!(" Agent of Empires v{} ", env!("CARGO_PKG_VERSION"));
         let block = Block::default()
@@ -381,7 +401,7 @@ mod tests {
     #[test]
     fn help_contains_resize_shortcut() {
         for strict in [false, true] {
-            let all = shortcuts(strict, false);
+            let all = shortcuts(&Keymap::default(), strict, false);
             let views_section = all.iter().find(|(name, _)| *name == "Views");
             assert!(views_section.is_some(), "Views section should exist");
             let (_, keys) = views_section.unwrap();
@@ -409,7 +429,7 @@ mod tests {
         ];
         for (desc_sub, non_strict_key, strict_key) in cases {
             for (strict, expected_key) in [(false, non_strict_key), (true, strict_key)] {
-                let all = shortcuts(strict, false);
+                let all = shortcuts(&Keymap::default(), strict, false);
                 let found = all.iter().any(|(_, keys)| {
                     keys.iter()
                         .any(|(k, desc)| k == expected_key && desc.contains(desc_sub))
@@ -428,7 +448,7 @@ mod tests {
         // non-strict) but did not advertise it in the help overlay. Lock the
         // listing in so a future binding rename keeps the docs honest.
         for (strict, expected_key) in [(false, "h"), (true, "H")] {
-            let all = shortcuts(strict, false);
+            let all = shortcuts(&Keymap::default(), strict, false);
             let attention = all
                 .iter()
                 .find(|(name, _)| name.starts_with("Attention"))
@@ -448,7 +468,7 @@ mod tests {
         // so users see them together instead of scattered across Navigation
         // and Actions.
         for strict in [false, true] {
-            let all = shortcuts(strict, false);
+            let all = shortcuts(&Keymap::default(), strict, false);
             let attention = all
                 .iter()
                 .find(|(name, _)| name.starts_with("Attention"))
@@ -475,12 +495,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn help_shows_user_keybindings() {
+        let config = [
+            (
+                "next-waiting".to_string(),
+                crate::session::config::KeySequences(vec!["g w".to_string()]),
+            ),
+            (
+                "pick-group-by".to_string(),
+                crate::session::config::KeySequences(vec![]),
+            ),
+        ]
+        .into();
+        let (keymap, _) = Keymap::from_config(&config);
+        for strict in [false, true] {
+            let rows: Vec<(String, String)> = shortcuts(&keymap, strict, false)
+                .into_iter()
+                .flat_map(|(_, rows)| rows)
+                .collect();
+            assert!(
+                rows.iter()
+                    .any(|(k, d)| k == "g w" && d == "Jump to next waiting/idle"),
+                "strict={strict}: {rows:?}"
+            );
+            // An unbound action has no row.
+            assert!(
+                !rows.iter().any(|(_, d)| d == "Group by"),
+                "strict={strict}"
+            );
+        }
+    }
+
     #[test]
     fn help_lists_command_palette() {
         // Asserts both keymaps surface the Ctrl+K command palette entry in
         // their "Other" section so users can discover the palette from `?`.
         for strict in [false, true] {
-            let all = shortcuts(strict, false);
+            let all = shortcuts(&Keymap::default(), strict, false);
             let other = all
                 .iter()
                 .find(|(name, _)| *name == "Other")
@@ -501,7 +553,7 @@ mod tests {
         // `default_attach_mode` so the two rows aren't lying.
         for strict in [false, true] {
             for live_on_enter in [false, true] {
-                let all = shortcuts(strict, live_on_enter);
+                let all = shortcuts(&Keymap::default(), strict, live_on_enter);
                 let actions_name = if strict {
                     "Actions (strict mode)"
                 } else {
@@ -599,7 +651,7 @@ mod tests {
         // Sanity check: the chosen 4 → 3 split keeps max column height
         // below the naive [1, 3, 0] alternative that would happen if we
         // pushed all extras to one column.
-        let sections = build_sections(false, SortOrder::Newest, false);
+        let sections = build_sections(&Keymap::default(), false, SortOrder::Newest, false);
         let heights: Vec<usize> = sections.iter().map(section_height).collect();
         let cols = distribute_sections(sections.len(), 3);
         let max_h = cols
@@ -627,7 +679,16 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                HelpOverlay::render(frame, area, &theme, SortOrder::Newest, false, false, scroll);
+                HelpOverlay::render(
+                    frame,
+                    area,
+                    &theme,
+                    SortOrder::Newest,
+                    &Keymap::default(),
+                    false,
+                    false,
+                    scroll,
+                );
             })
             .unwrap();
         terminal.backend().buffer().clone()
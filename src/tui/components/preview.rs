@@ -6,7 +6,10 @@ use ansi_to_tui::IntoText;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use crate::session::Instance;
+use chrono::{DateTime, Utc};
+
+use crate::session::timeline::{self, TimelineSummary};
+use crate::session::{Instance, Status};
 use crate::tui::styles::Theme;
 
 /// Light value type the renderers consume in place of a raw `&str`.
@@ -47,15 +50,75 @@ impl<'a> CachedPreview<'a> {
 pub fn agent_info_height(instance: &Instance) -> u16 {
     let base: u16 = 3; // profile+tool / path / status
     let sandbox_lines: u16 = if instance.is_sandboxed() { 1 } else { 0 };
+    let tag_lines: u16 = if instance.tags.is_empty() { 0 } else { 1 };
+    let issue_lines: u16 = if instance.issue.is_some() { 1 } else { 0 };
+    // Summary line plus the last-hour strip.
+    let timeline_lines: u16 = if instance.timeline.is_some() { 2 } else { 0 };
+    let extra = sandbox_lines
+        + tag_lines
+        + issue_lines
+        + notes_lines(&instance.notes).len() as u16
+        + timeline_lines;
     if let Some(wt) = instance.worktree_info.as_ref() {
         // blank + header + branch + main (+ optional base)
         let base_branch_line: u16 = if wt.base_branch.is_some() { 1 } else { 0 };
-        base + sandbox_lines + 4 + base_branch_line
+        base + extra + 4 + base_branch_line
     } else {
-        base + sandbox_lines
+        base + extra
     }
 }
 
+/// Most note lines the info header shows; the rest are summarized as
+/// "(+N more)" so a long scratchpad can't push the output off screen.
+const NOTES_PREVIEW_LINES: usize = 3;
+
+/// The note lines shown in the info header: the first
+/// `NOTES_PREVIEW_LINES`, with a "(+N more)" suffix on the last one when
+/// there are more.
+fn notes_lines(notes: &str) -> Vec<String> {
+    let all: Vec<&str> = notes.lines().collect();
+    let mut shown: Vec<String> = all
+        .iter()
+        .take(NOTES_PREVIEW_LINES)
+        .map(|l| l.to_string())
+        .collect();
+    if all.len() > NOTES_PREVIEW_LINES {
+        if let Some(last) = shown.last_mut() {
+            last.push_str(&format!("  (+{} more)", all.len() - NOTES_PREVIEW_LINES));
+        }
+    }
+    shown
+}
+
+/// Most cells the last-hour status strip spans; wider panes leave the
+/// rest blank rather than stretching each cell below a minute.
+const TIMELINE_STRIP_MAX_CELLS: usize = 60;
+
+/// The status in effect at the end of each of `cells` equal slices of the
+/// last hour. `None` before the first recorded entry, while the session
+/// was stopped, and after the last entry once the session has moved to a
+/// status the log doesn't track (starting, restarting).
+fn timeline_cells(
+    summary: &TimelineSummary,
+    current: Status,
+    now: DateTime<Utc>,
+    cells: usize,
+) -> Vec<Option<Status>> {
+    let window = timeline::RECENT_WINDOW_SECS;
+    let start = now - chrono::Duration::seconds(window);
+    (1..=cells)
+        .map(|i| {
+            let t = start + chrono::Duration::seconds(window * i as i64 / cells as i64);
+            let idx = summary.recent.iter().rposition(|e| e.at <= t)?;
+            let status = summary.recent[idx].status;
+            if status == Status::Stopped || (idx + 1 == summary.recent.len() && status != current) {
+                return None;
+            }
+            Some(status)
+        })
+        .collect()
+}
+
 /// Row count of the Terminal-view (and Tool-view) info header
 /// (title / path / status, plus one optional sandbox row) for
 /// `instance`.
@@ -368,6 +431,86 @@ impl Preview {
             }
         }
 
+        if !instance.tags.is_empty() {
+            let mut spans = vec![Span::styled("Tags:    ", Style::default().fg(theme.dimmed))];
+            for (i, tag) in instance.tags.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::styled(
+                    format!("#{tag}"),
+                    Style::default().fg(theme.accent),
+                ));
+            }
+            info_lines.push(Line::from(spans));
+        }
+
+        // The URL is printed in full so terminals that detect links make
+        // it clickable.
+        if let Some(issue) = &instance.issue {
+            let mut spans = vec![
+                Span::styled("Issue:   ", Style::default().fg(theme.dimmed)),
+                Span::styled(issue.key.clone(), Style::default().fg(theme.accent)),
+            ];
+            if let Some(url) = &issue.url {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    url.clone(),
+                    Style::default()
+                        .fg(theme.dimmed)
+                        .add_modifier(Modifier::UNDERLINED),
+                ));
+            }
+            info_lines.push(Line::from(spans));
+        }
+
+        for (i, line) in notes_lines(&instance.notes).into_iter().enumerate() {
+            let label = if i == 0 { "Notes:   " } else { "         " };
+            info_lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(theme.dimmed)),
+                Span::styled(line, Style::default().fg(theme.text)),
+            ]));
+        }
+
+        if let Some(summary) = &instance.timeline {
+            let m = &summary.metrics;
+            let mut text = format!(
+                "{} turn{} · running {} · waiting {}",
+                m.turns,
+                if m.turns == 1 { "" } else { "s" },
+                timeline::format_secs(m.running_secs),
+                timeline::format_secs(m.waiting_secs)
+            );
+            if let Some(secs) = m.time_to_first_idle_secs {
+                text.push_str(&format!(" · first idle {}", timeline::format_secs(secs)));
+            }
+            info_lines.push(Line::from(vec![
+                Span::styled("History: ", Style::default().fg(theme.dimmed)),
+                Span::styled(text, Style::default().fg(theme.text)),
+            ]));
+
+            let cells = (area.width as usize)
+                .saturating_sub(9 + 8)
+                .min(TIMELINE_STRIP_MAX_CELLS);
+            let mut spans = vec![Span::raw("         ")];
+            for cell in timeline_cells(summary, instance.status, Utc::now(), cells) {
+                spans.push(match cell {
+                    Some(status) => Span::styled(
+                        "▇",
+                        Style::default().fg(match status {
+                            Status::Running => theme.running,
+                            Status::Waiting => theme.waiting,
+                            Status::Error => theme.error,
+                            _ => theme.idle,
+                        }),
+                    ),
+                    None => Span::styled("·", Style::default().fg(theme.dimmed)),
+                });
+            }
+            spans.push(Span::styled(" last 1h", Style::default().fg(theme.dimmed)));
+            info_lines.push(Line::from(spans));
+        }
+
         // Add worktree information if present
         if let Some(wt_info) = &instance.worktree_info {
             info_lines.push(Line::from(""));
@@ -500,6 +643,32 @@ fn banner_block_area(output: Rect, banner: Rect) -> Rect {
     }
 }
 
+/// Render the bottom of a parsed pane capture into `area`, live-following
+/// like the preview's output body. Used by the home view's wall tiles,
+/// which have no info header, banner, or scrollback.
+pub fn render_pane_tail(
+    frame: &mut Frame,
+    area: Rect,
+    text: Option<&Text<'static>>,
+    theme: &Theme,
+) {
+    match text {
+        Some(text) => {
+            let scroll = compute_scroll(text.lines.len(), area.height as usize, 0);
+            let paragraph = Paragraph::new(text.clone())
+                .style(Style::default().fg(theme.text))
+                .scroll((scroll, 0));
+            frame.render_widget(paragraph, area);
+        }
+        None => {
+            let hint = Paragraph::new("No output available")
+                .style(Style::default().fg(theme.dimmed))
+                .alignment(Alignment::Center);
+            frame.render_widget(hint, area);
+        }
+    }
+}
+
 /// Pick the row offset passed to `Paragraph::scroll`. Zero user offset shows
 /// the bottom of the cached pane (live-follow). A positive offset scrolls the
 /// same number of lines back, saturating at the top of the capture.
@@ -737,13 +906,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn timeline_cells_follow_the_recorded_statuses() {
+        use crate::session::timeline::{StatusSource, TimelineEntry};
+
+        let now = Utc::now();
+        let at = |mins_ago: i64| TimelineEntry {
+            at: now - chrono::Duration::minutes(mins_ago),
+            status: Status::Idle,
+            source: StatusSource::Pane,
+        };
+        let summary = TimelineSummary {
+            recent: vec![
+                TimelineEntry {
+                    status: Status::Running,
+                    ..at(40)
+                },
+                TimelineEntry {
+                    status: Status::Waiting,
+                    ..at(10)
+                },
+            ],
+            ..Default::default()
+        };
+
+        let cells = timeline_cells(&summary, Status::Waiting, now, 4);
+        assert_eq!(
+            cells,
+            vec![
+                None,
+                Some(Status::Running),
+                Some(Status::Running),
+                Some(Status::Waiting)
+            ]
+        );
+
+        // Stopped since: the last entry's span is unknown.
+        let cells = timeline_cells(&summary, Status::Stopped, now, 4);
+        assert_eq!(cells[3], None);
+    }
+
     // `agent_info_height` drives both the preview layout split in
     // `render_with_cache` and the live-send sync resize in
     // `HomeView::finalize_live_send_resize`. A one-row drift here brings
     // the shifted-preview bug right back, so each branch of the formula
     // gets a dedicated case.
     mod agent_info_height {
-        use super::super::agent_info_height;
+        use super::super::{agent_info_height, notes_lines};
         use crate::session::{Instance, SandboxInfo, WorktreeInfo};
         use chrono::Utc;
 
@@ -803,6 +1012,23 @@ mod tests {
             assert_eq!(agent_info_height(&inst), 3 + 1 + 4 + 1);
         }
 
+        #[test]
+        fn timeline_adds_two_rows() {
+            let mut inst = Instance::new("t", "/tmp/t");
+            inst.timeline = Some(Default::default());
+            assert_eq!(agent_info_height(&inst), 5);
+        }
+
+        #[test]
+        fn notes_add_up_to_three_rows() {
+            let mut inst = Instance::new("notes", "/tmp/notes");
+            inst.notes = "waiting on infra".into();
+            assert_eq!(agent_info_height(&inst), 3 + 1);
+            inst.notes = "one\ntwo\nthree\nfour\nfive".into();
+            assert_eq!(agent_info_height(&inst), 3 + 3);
+            assert_eq!(notes_lines(&inst.notes)[2], "three  (+2 more)");
+        }
+
         #[test]
         fn disabled_sandbox_does_not_count() {
             let mut inst = Instance::new("disabled", "/tmp/disabled");
@@ -49,6 +49,10 @@ enum FocusElement {
 /// Unified delete dialog that adapts based on available cleanup options
 pub struct UnifiedDeleteDialog {
     session_title: String,
+    /// Number of sessions the dialog deletes. Above one the dialog is a
+    /// bulk confirmation for the marked sessions and `config` is the
+    /// union of their cleanup options.
+    session_count: usize,
     config: DeleteDialogConfig,
     options: DeleteOptions,
     focus: FocusElement,
@@ -103,6 +107,7 @@ impl UnifiedDeleteDialog {
 
         Self {
             session_title,
+            session_count: 1,
             config,
             options,
             focus: initial_focus,
@@ -113,6 +118,15 @@ impl UnifiedDeleteDialog {
         }
     }
 
+    /// One confirmation for `count` marked sessions. The caller passes
+    /// the union of their cleanup options; each checkbox then applies
+    /// only to the sessions that have that resource.
+    pub fn new_bulk(count: usize, config: DeleteDialogConfig, profile: &str) -> Self {
+        let mut dialog = Self::new(format!("{count} sessions"), config, profile);
+        dialog.session_count = count;
+        dialog
+    }
+
     /// Route a left-click. Returns `Some(Submit)` for `[Yes]`,
     /// `Some(Cancel)` for `[No]`, `Some(Continue)` for a click on a
     /// checkbox row (which is treated as a focus-then-toggle), and
@@ -326,7 +340,11 @@ impl UnifiedDeleteDialog {
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme.error))
-            .title(" Delete Session ")
+            .title(if self.session_count > 1 {
+                " Delete Sessions "
+            } else {
+                " Delete Session "
+            })
             .title_style(Style::default().fg(theme.error).bold());
 
         let inner = block.inner(dialog_area);
@@ -355,7 +373,11 @@ impl UnifiedDeleteDialog {
 
         let mut chunk_idx = 0;
 
-        let message = format!("Delete \"{}\"?", self.session_title);
+        let message = if self.session_count > 1 {
+            format!("Delete {}?", self.session_title)
+        } else {
+            format!("Delete \"{}\"?", self.session_title)
+        };
         frame.render_widget(
             Paragraph::new(message)
                 .style(Style::default().fg(theme.text))
@@ -1,4 +1,5 @@
-//! Group-by picker dialog - choose a `GroupByMode` from a list.
+//! Group-by picker dialog - choose a `GroupByMode`, or one of the saved
+//! views from the `[views]` config section, from a list.
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::prelude::*;
@@ -8,21 +9,53 @@ use super::DialogResult;
 use crate::session::config::GroupByMode;
 use crate::tui::styles::Theme;
 
-const OPTIONS: &[GroupByMode] = &[GroupByMode::Manual, GroupByMode::Project];
+const OPTIONS: &[GroupByMode] = &[GroupByMode::Manual, GroupByMode::Project, GroupByMode::Tag];
+
+/// A row of the picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupPick {
+    Mode(GroupByMode),
+    /// A saved view, by name.
+    View(String),
+}
 
 pub struct GroupPickerDialog {
+    entries: Vec<GroupPick>,
     selected: usize,
     current: GroupByMode,
+    active_view: Option<String>,
     list_area: Rect,
     dialog_area: Rect,
 }
 
 impl GroupPickerDialog {
     pub fn new(current: GroupByMode) -> Self {
-        let selected = OPTIONS.iter().position(|m| *m == current).unwrap_or(0);
+        Self::with_views(current, Vec::new(), None)
+    }
+
+    /// Picker listing `views` after the group-by modes. The active view, if
+    /// any, is preselected; otherwise the current mode is.
+    pub fn with_views(
+        current: GroupByMode,
+        views: Vec<String>,
+        active_view: Option<String>,
+    ) -> Self {
+        let entries: Vec<GroupPick> = OPTIONS
+            .iter()
+            .copied()
+            .map(GroupPick::Mode)
+            .chain(views.into_iter().map(GroupPick::View))
+            .collect();
+        let preselect = match &active_view {
+            Some(name) => GroupPick::View(name.clone()),
+            None => GroupPick::Mode(current),
+        };
+        let selected = entries.iter().position(|e| *e == preselect).unwrap_or(0);
         Self {
+            entries,
             selected,
             current,
+            active_view,
             list_area: Rect::default(),
             dialog_area: Rect::default(),
         }
@@ -36,13 +69,21 @@ impl GroupPickerDialog {
             return None;
         }
         let i = (row - self.list_area.y) as usize;
-        if i >= OPTIONS.len() {
+        // The view rows sit below a one-line "Views" heading.
+        let i = if i < OPTIONS.len() {
+            i
+        } else if i > OPTIONS.len() {
+            i - 1
+        } else {
+            return None;
+        };
+        if i >= self.entries.len() {
             return None;
         }
         Some(i)
     }
 
-    pub fn handle_click(&mut self, col: u16, row: u16) -> DialogResult<GroupByMode> {
+    pub fn handle_click(&mut self, col: u16, row: u16) -> DialogResult<GroupPick> {
         if !self
             .dialog_area
             .contains(ratatui::layout::Position::from((col, row)))
@@ -53,7 +94,7 @@ impl GroupPickerDialog {
             return DialogResult::Continue;
         };
         self.selected = idx;
-        DialogResult::Submit(OPTIONS[idx])
+        DialogResult::Submit(self.entries[idx].clone())
     }
 
     pub fn handle_hover(&mut self, col: u16, row: u16) -> bool {
@@ -67,7 +108,7 @@ impl GroupPickerDialog {
         true
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<GroupByMode> {
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<GroupPick> {
         match key.code {
             KeyCode::Esc => DialogResult::Cancel,
             KeyCode::Up | KeyCode::Char('k') => {
@@ -77,20 +118,31 @@ impl GroupPickerDialog {
                 DialogResult::Continue
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                if self.selected + 1 < OPTIONS.len() {
+                if self.selected + 1 < self.entries.len() {
                     self.selected += 1;
                 }
                 DialogResult::Continue
             }
-            KeyCode::Enter => DialogResult::Submit(OPTIONS[self.selected]),
+            KeyCode::Enter => DialogResult::Submit(self.entries[self.selected].clone()),
             _ => DialogResult::Continue,
         }
     }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let dialog_width: u16 = 32;
-        // list (OPTIONS.len()) + hint (1) + borders (2) + margin (2)
-        let dialog_height: u16 = OPTIONS.len() as u16 + 5;
+        let view_rows = self.entries.len() - OPTIONS.len();
+        let widest_view = self
+            .entries
+            .iter()
+            .filter_map(|e| match e {
+                GroupPick::View(name) => Some(name.chars().count() as u16 + 16),
+                GroupPick::Mode(_) => None,
+            })
+            .max()
+            .unwrap_or(0);
+        let dialog_width: u16 = widest_view.clamp(32, 60);
+        // list (modes + views + views heading) + hint (1) + borders (2) + margin (2)
+        let heading_rows = usize::from(view_rows > 0);
+        let dialog_height: u16 = (self.entries.len() + heading_rows) as u16 + 5;
 
         let dialog_area = super::centered_rect(area, dialog_width, dialog_height);
         self.dialog_area = dialog_area;
@@ -113,7 +165,13 @@ impl GroupPickerDialog {
             .split(inner);
 
         let mut lines: Vec<Line> = Vec::new();
-        for (i, mode) in OPTIONS.iter().enumerate() {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i == OPTIONS.len() {
+                lines.push(Line::from(Span::styled(
+                    "Views",
+                    Style::default().fg(theme.dimmed),
+                )));
+            }
             let is_selected = i == self.selected;
             let prefix = if is_selected { "> " } else { "  " };
             let name_style = if is_selected {
@@ -121,11 +179,18 @@ impl GroupPickerDialog {
             } else {
                 Style::default().fg(theme.text)
             };
+            let (label, marker) = match entry {
+                GroupPick::Mode(mode) => (mode.label().to_string(), *mode == self.current),
+                GroupPick::View(name) => (
+                    format!("@{name}"),
+                    self.active_view.as_deref() == Some(name.as_str()),
+                ),
+            };
             let mut spans = vec![
                 Span::styled(prefix, name_style),
-                Span::styled(mode.label(), name_style),
+                Span::styled(label, name_style),
             ];
-            if *mode == self.current {
+            if marker {
                 spans.push(Span::styled(
                     "  (current)",
                     Style::default().fg(theme.running),
@@ -175,7 +240,10 @@ mod tests {
         let mut dialog = GroupPickerDialog::new(GroupByMode::Manual);
         dialog.handle_key(key(KeyCode::Down));
         let result = dialog.handle_key(key(KeyCode::Enter));
-        assert!(matches!(result, DialogResult::Submit(GroupByMode::Project)));
+        assert!(matches!(
+            result,
+            DialogResult::Submit(GroupPick::Mode(GroupByMode::Project))
+        ));
     }
 
     #[test]
@@ -185,6 +253,24 @@ mod tests {
         assert_eq!(dialog.selected, 0);
         dialog.handle_key(key(KeyCode::Down));
         dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        assert_eq!(dialog.selected, 2);
+    }
+
+    #[test]
+    fn test_views_follow_modes_and_active_view_is_preselected() {
+        let views = vec!["payments".to_string(), "stuck".to_string()];
+        let mut dialog = GroupPickerDialog::with_views(
+            GroupByMode::Manual,
+            views.clone(),
+            Some("stuck".to_string()),
+        );
+        assert_eq!(dialog.selected, 4);
+        dialog.handle_key(key(KeyCode::Up));
+        let result = dialog.handle_key(key(KeyCode::Enter));
+        assert!(matches!(result, DialogResult::Submit(GroupPick::View(ref v)) if v == "payments"));
+
+        let dialog = GroupPickerDialog::with_views(GroupByMode::Project, views, None);
         assert_eq!(dialog.selected, 1);
     }
 }
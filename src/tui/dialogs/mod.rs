@@ -14,6 +14,7 @@ mod info;
 mod intro;
 mod new_session;
 mod no_agents;
+mod notes;
 mod profile_picker;
 mod project_session_picker;
 mod projects;
@@ -22,9 +23,11 @@ mod restart;
 mod send_message;
 #[cfg(feature = "serve")]
 mod serve;
+mod set_base;
 mod snooze_duration;
 mod sort_picker;
 mod tool_picker;
+mod transcript_search;
 mod update_confirm;
 
 pub use changelog::ChangelogDialog;
@@ -36,7 +39,7 @@ pub use context_menu::{ContextMenuAction, ContextMenuDialog};
 pub use custom_instruction::CustomInstructionDialog;
 pub use delete_options::{DeleteDialogConfig, DeleteOptions, UnifiedDeleteDialog};
 pub use group_delete_options::{GroupDeleteOptions, GroupDeleteOptionsDialog};
-pub use group_picker::GroupPickerDialog;
+pub use group_picker::{GroupPick, GroupPickerDialog};
 pub use hook_trust::{HookTrustAction, HookTrustDialog};
 pub use hooks_install::HooksInstallDialog;
 pub use info::InfoDialog;
@@ -44,6 +47,7 @@ pub use intro::{IntroDialog, IntroOutcome};
 pub(crate) use new_session::project_picker_label;
 pub use new_session::{NewSessionData, NewSessionDialog};
 pub use no_agents::{NoAgentsAction, NoAgentsDialog};
+pub use notes::NotesDialog;
 pub use profile_picker::{ProfileEntry, ProfilePickerAction, ProfilePickerDialog};
 pub use project_session_picker::ProjectSessionPickerDialog;
 pub use projects::ProjectsDialog;
@@ -52,9 +56,11 @@ pub use restart::{RestartData, RestartDialog};
 pub use send_message::SendMessageDialog;
 #[cfg(feature = "serve")]
 pub use serve::{ServeAction, ServeView};
+pub use set_base::SetBaseDialog;
 pub use snooze_duration::SnoozeDurationDialog;
 pub use sort_picker::SortPickerDialog;
 pub use tool_picker::ToolPickerDialog;
+pub use transcript_search::TranscriptSearchDialog;
 pub use update_confirm::UpdateConfirmDialog;
 
 pub enum DialogResult<T> {
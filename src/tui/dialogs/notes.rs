@@ -0,0 +1,173 @@
+//! Session notes editor: a multi-line markdown scratchpad saved with Ctrl+S
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use ratatui_textarea::TextArea;
+
+use super::DialogResult;
+use crate::tui::styles::Theme;
+
+pub struct NotesDialog {
+    session_title: String,
+    text_area: TextArea<'static>,
+}
+
+impl NotesDialog {
+    pub fn new(session_title: &str, notes: &str) -> Self {
+        let lines: Vec<String> = if notes.is_empty() {
+            vec![String::new()]
+        } else {
+            notes.lines().map(|l| l.to_string()).collect()
+        };
+
+        let mut text_area = TextArea::new(lines);
+        text_area.set_cursor_line_style(Style::default());
+        // Open at the end so appending a line is one keystroke away.
+        text_area.move_cursor(ratatui_textarea::CursorMove::Bottom);
+        text_area.move_cursor(ratatui_textarea::CursorMove::End);
+
+        Self {
+            session_title: session_title.to_string(),
+            text_area,
+        }
+    }
+
+    fn get_text(&self) -> String {
+        self.text_area.lines().join("\n")
+    }
+
+    /// Enter inserts a newline; Ctrl+S saves (blank text clears the
+    /// notes); Esc discards the edit.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<String> {
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Char('s') | KeyCode::Char('S')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                DialogResult::Submit(self.get_text())
+            }
+            _ => {
+                self.text_area.input(key);
+                DialogResult::Continue
+            }
+        }
+    }
+
+    pub fn handle_paste(&mut self, text: &str) {
+        self.text_area.insert_str(text);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_width = (area.width * 70 / 100).max(40).min(area.width);
+        let dialog_height = (area.height * 60 / 100).max(10).min(area.height);
+        let dialog_area = super::centered_rect(area, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(" Notes: {} ", self.session_title))
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Text area
+                Constraint::Length(1), // Hint bar
+            ])
+            .split(inner);
+
+        let textarea_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent));
+        let textarea_inner = textarea_block.inner(chunks[0]);
+
+        let mut text_area_clone = self.text_area.clone();
+        text_area_clone.set_block(textarea_block);
+        text_area_clone.set_style(Style::default().fg(theme.text));
+        text_area_clone.set_cursor_style(Style::default().fg(theme.background).bg(theme.accent));
+
+        frame.render_widget(&text_area_clone, chunks[0]);
+        if textarea_inner.width > 0 && textarea_inner.height > 0 {
+            let cursor = text_area_clone.screen_cursor();
+            let max_x = textarea_inner
+                .x
+                .saturating_add(textarea_inner.width.saturating_sub(1));
+            let max_y = textarea_inner
+                .y
+                .saturating_add(textarea_inner.height.saturating_sub(1));
+            let cursor_x = textarea_inner
+                .x
+                .saturating_add(cursor.col as u16)
+                .min(max_x);
+            let cursor_y = textarea_inner
+                .y
+                .saturating_add(cursor.row as u16)
+                .min(max_y);
+            frame.set_cursor_position(Position::new(cursor_x, cursor_y));
+        }
+
+        let hint = Line::from(vec![
+            Span::styled("Ctrl+S", Style::default().fg(theme.hint)),
+            Span::raw(" save  "),
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" new line  "),
+            Span::styled("Esc", Style::default().fg(theme.hint)),
+            Span::raw(" discard"),
+        ]);
+        frame.render_widget(Paragraph::new(hint), chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+
+    fn ctrl_s() -> KeyEvent {
+        KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn opens_with_existing_notes_and_appends_at_the_end() {
+        let mut dialog = NotesDialog::new("api", "waiting on infra");
+        dialog.handle_key(key(KeyCode::Enter));
+        dialog.handle_paste("then rerun the migration");
+        match dialog.handle_key(ctrl_s()) {
+            DialogResult::Submit(text) => {
+                assert_eq!(text, "waiting on infra\nthen rerun the migration")
+            }
+            _ => panic!("Expected Submit"),
+        }
+    }
+
+    #[test]
+    fn plain_s_is_typed_not_saved() {
+        let mut dialog = NotesDialog::new("api", "");
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('s'))),
+            DialogResult::Continue
+        ));
+        assert_eq!(dialog.get_text(), "s");
+    }
+
+    #[test]
+    fn escape_discards() {
+        let mut dialog = NotesDialog::new("api", "keep me");
+        dialog.handle_key(key(KeyCode::Char('x')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
@@ -7,6 +7,7 @@ use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use super::DialogResult;
+use crate::session::tags::{self, TagEdit};
 use crate::tui::components::{
     render_text_field, render_text_field_with_ghost, GroupGhostCompletion, ListPicker,
     ListPickerResult,
@@ -22,12 +23,18 @@ pub struct RenameData {
     pub group: Option<String>,
     /// New profile (None means keep current, Some(name) means move to that profile)
     pub profile: Option<String>,
+    /// Tag changes (None means keep current). Always an edit rather than a
+    /// full list so tags another writer added meanwhile survive.
+    pub tags: Option<TagEdit>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RenameMode {
     Session,
     Group,
+    /// Move several marked sessions at once: group + profile only, the
+    /// titles stay as they are.
+    Sessions,
 }
 
 pub struct RenameDialog {
@@ -38,8 +45,13 @@ pub struct RenameDialog {
     available_profiles: Vec<String>,
     new_title: Input,
     new_group: Input,
+    /// Session mode: the full tag list. Sessions mode: `tag, -tag` edits.
+    current_tags: Vec<String>,
+    new_tags: Input,
     profile_index: usize,
-    focused_field: usize, // Session: 0=title, 1=group, 2=profile; Group: 0=group, 1=profile
+    // Session: 0=title, 1=group, 2=profile, 3=tags; Group: 0=group,
+    // 1=profile; Sessions: 0=group, 1=profile, 2=tags
+    focused_field: usize,
     existing_groups: Vec<String>,
     group_picker: ListPicker,
     group_ghost: Option<GroupGhostCompletion>,
@@ -75,6 +87,8 @@ impl RenameDialog {
             available_profiles,
             new_title: Input::default(),
             new_group: Input::new(current_group.to_string()),
+            current_tags: Vec::new(),
+            new_tags: Input::default(),
             profile_index,
             focused_field: 0,
             existing_groups,
@@ -104,6 +118,8 @@ impl RenameDialog {
             available_profiles,
             new_title: Input::default(),
             new_group: Input::new(current_group.to_string()),
+            current_tags: Vec::new(),
+            new_tags: Input::default(),
             profile_index,
             focused_field: 0,
             existing_groups,
@@ -114,10 +130,40 @@ impl RenameDialog {
         }
     }
 
+    /// Move dialog for `count` marked sessions. `current_group` is the
+    /// group they share, or empty when they sit in different groups.
+    pub fn new_for_sessions(
+        count: usize,
+        current_group: &str,
+        current_profile: &str,
+        available_profiles: Vec<String>,
+        existing_groups: Vec<String>,
+    ) -> Self {
+        let mut dialog = Self::new_for_group(
+            current_group,
+            current_profile,
+            available_profiles,
+            existing_groups,
+        );
+        dialog.mode = RenameMode::Sessions;
+        dialog.current_title = format!("{count} sessions");
+        dialog
+    }
+
+    /// Seed the Tags field (Session mode) with the session's tags.
+    pub fn with_tags(mut self, tags: &[String]) -> Self {
+        self.current_tags = tags.to_vec();
+        if self.mode == RenameMode::Session {
+            self.new_tags = Input::new(tags.join(", "));
+        }
+        self
+    }
+
     fn field_count(&self) -> usize {
         match self.mode {
-            RenameMode::Session => 3, // title, group, profile
-            RenameMode::Group => 2,   // group, profile
+            RenameMode::Session => 4,  // title, group, profile, tags
+            RenameMode::Group => 2,    // group, profile
+            RenameMode::Sessions => 3, // group, profile, tags
         }
     }
 
@@ -167,7 +213,7 @@ impl RenameDialog {
     fn is_profile_field(&self) -> bool {
         match self.mode {
             RenameMode::Session => self.focused_field == 2,
-            RenameMode::Group => self.focused_field == 1,
+            RenameMode::Group | RenameMode::Sessions => self.focused_field == 1,
         }
     }
 
@@ -176,19 +222,42 @@ impl RenameDialog {
             RenameMode::Session => match self.focused_field {
                 0 => Some(&mut self.new_title),
                 1 => Some(&mut self.new_group),
+                3 => Some(&mut self.new_tags),
                 _ => None,
             },
-            RenameMode::Group => match self.focused_field {
+            RenameMode::Group | RenameMode::Sessions => match self.focused_field {
                 0 => Some(&mut self.new_group),
+                2 => Some(&mut self.new_tags),
                 _ => None,
             },
         }
     }
 
+    fn is_tags_field(&self) -> bool {
+        match self.mode {
+            RenameMode::Session => self.focused_field == 3,
+            RenameMode::Sessions => self.focused_field == 2,
+            RenameMode::Group => false,
+        }
+    }
+
+    /// The tag change typed into the Tags field; None when unchanged.
+    fn tag_edit(&self) -> Result<Option<TagEdit>, String> {
+        let edit = match self.mode {
+            RenameMode::Session => {
+                let new = tags::parse_list(self.new_tags.value())?;
+                TagEdit::between(&self.current_tags, &new)
+            }
+            RenameMode::Sessions => TagEdit::parse(self.new_tags.value())?,
+            RenameMode::Group => return Ok(None),
+        };
+        Ok(Some(edit).filter(|e| !e.is_empty()))
+    }
+
     fn is_group_field(&self) -> bool {
         match self.mode {
             RenameMode::Session => self.focused_field == 1,
-            RenameMode::Group => self.focused_field == 0,
+            RenameMode::Group | RenameMode::Sessions => self.focused_field == 0,
         }
     }
 
@@ -264,12 +333,23 @@ impl RenameDialog {
             KeyCode::Esc => DialogResult::Cancel,
             KeyCode::Enter => {
                 let title_value = self.new_title.value().trim().to_string();
+                let tags = match self.tag_edit() {
+                    Ok(tags) => tags,
+                    Err(e) => {
+                        self.validation_error = Some(e);
+                        return DialogResult::Continue;
+                    }
+                };
                 let group_value = self.new_group.value().trim();
                 let selected_profile = self.selected_profile();
                 let profile_changed = selected_profile != self.current_profile;
 
                 // If nothing has changed, cancel
-                if title_value.is_empty() && group_value == self.current_group && !profile_changed {
+                if title_value.is_empty()
+                    && group_value == self.current_group
+                    && !profile_changed
+                    && tags.is_none()
+                {
                     return DialogResult::Cancel;
                 }
 
@@ -309,6 +389,7 @@ impl RenameDialog {
                     title: title_value,
                     group,
                     profile,
+                    tags,
                 })
             }
             KeyCode::Tab => {
@@ -364,6 +445,9 @@ impl RenameDialog {
                     self.recompute_group_ghost();
                     self.validation_error = None;
                 }
+                if self.is_tags_field() {
+                    self.validation_error = None;
+                }
                 DialogResult::Continue
             }
         }
@@ -381,14 +465,14 @@ impl RenameDialog {
     pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         match self.mode {
             RenameMode::Session => self.render_session(frame, area, theme),
-            RenameMode::Group => self.render_group(frame, area, theme),
+            RenameMode::Group | RenameMode::Sessions => self.render_group(frame, area, theme),
         }
     }
 
     fn render_session(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.focusable_rects.clear();
         let dialog_width = 50;
-        let dialog_area = super::centered_rect(area, dialog_width, 15);
+        let dialog_area = super::centered_rect(area, dialog_width, 16);
 
         frame.render_widget(Clear, dialog_area);
 
@@ -413,7 +497,8 @@ impl RenameDialog {
                 Constraint::Length(1), // New title field
                 Constraint::Length(1), // New group field
                 Constraint::Length(1), // Profile selector
-                Constraint::Length(1), // Spacer
+                Constraint::Length(1), // Tags field
+                Constraint::Length(1), // Spacer / tag error
                 Constraint::Min(1),    // Hint
             ])
             .split(inner);
@@ -451,8 +536,21 @@ impl RenameDialog {
         self.render_profile_selector(frame, chunks[6], theme);
         self.focusable_rects.push((2, chunks[6]));
 
+        // Tags field
+        self.render_tags_field(frame, chunks[7], theme);
+        self.focusable_rects.push((3, chunks[7]));
+        if let Some(error) = &self.validation_error {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    error.as_str(),
+                    Style::default().fg(theme.error),
+                )),
+                chunks[8],
+            );
+        }
+
         // Hint
-        self.render_hints(frame, chunks[8], theme);
+        self.render_hints(frame, chunks[9], theme);
 
         // Render group picker overlay
         if self.group_picker.is_active() {
@@ -464,15 +562,21 @@ impl RenameDialog {
         self.focusable_rects.clear();
         let dialog_width = 50;
         let has_error = self.validation_error.is_some();
-        let dialog_height = if has_error { 16 } else { 13 };
+        let has_tags = self.mode == RenameMode::Sessions;
+        let dialog_height = if has_error { 16 } else { 13 } + u16::from(has_tags);
         let dialog_area = super::centered_rect(area, dialog_width, dialog_height);
 
         frame.render_widget(Clear, dialog_area);
 
+        let title = if self.mode == RenameMode::Sessions {
+            format!(" Move {} ", self.current_title)
+        } else {
+            " Rename Group ".to_string()
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.accent))
-            .title(" Rename Group ")
+            .title(title)
             .title_style(Style::default().fg(theme.title).bold());
 
         let inner = block.inner(dialog_area);
@@ -487,8 +591,12 @@ impl RenameDialog {
             Constraint::Length(1), // Spacer
             Constraint::Min(1),    // Hint
         ];
+        if has_tags {
+            constraints.insert(5, Constraint::Length(1)); // Tags field
+        }
+        let tags_rows = usize::from(has_tags);
         if has_error {
-            constraints.insert(5, Constraint::Length(2)); // Validation error (2 lines)
+            constraints.insert(5 + tags_rows, Constraint::Length(2)); // Validation error (2 lines)
         }
 
         let chunks = Layout::default()
@@ -497,8 +605,17 @@ impl RenameDialog {
             .constraints(constraints)
             .split(inner);
 
-        // Current group
-        self.render_current_group(frame, chunks[0], theme);
+        // Current group, or the marked-session count when moving several
+        // sessions that may not share one.
+        if self.mode == RenameMode::Sessions {
+            let line = Line::from(vec![
+                Span::styled("Moving: ", Style::default().fg(theme.dimmed)),
+                Span::styled(&self.current_title, Style::default().fg(theme.text)),
+            ]);
+            frame.render_widget(Paragraph::new(line), chunks[0]);
+        } else {
+            self.render_current_group(frame, chunks[0], theme);
+        }
 
         // Current profile
         self.render_current_profile(frame, chunks[1], theme);
@@ -511,6 +628,11 @@ impl RenameDialog {
         self.render_profile_selector(frame, chunks[4], theme);
         self.focusable_rects.push((1, chunks[4]));
 
+        if has_tags {
+            self.render_tags_field(frame, chunks[5], theme);
+            self.focusable_rects.push((2, chunks[5]));
+        }
+
         if has_error {
             // Validation error (two lines, one sentence each)
             let error_text: Vec<Line> = self
@@ -525,12 +647,12 @@ impl RenameDialog {
                     ))
                 })
                 .collect();
-            frame.render_widget(Paragraph::new(error_text), chunks[5]);
+            frame.render_widget(Paragraph::new(error_text), chunks[5 + tags_rows]);
             // Hint is shifted one index further
-            self.render_hints(frame, chunks[7], theme);
+            self.render_hints(frame, chunks[7 + tags_rows], theme);
         } else {
             // Hint
-            self.render_hints(frame, chunks[6], theme);
+            self.render_hints(frame, chunks[6 + tags_rows], theme);
         }
 
         // Render group picker overlay
@@ -578,6 +700,22 @@ impl RenameDialog {
         );
     }
 
+    fn render_tags_field(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let (label, hint) = match self.mode {
+            RenameMode::Sessions => ("Tags:", "tag adds, -tag removes"),
+            _ => ("Tags:", "comma-separated, key=value"),
+        };
+        render_text_field(
+            frame,
+            area,
+            label,
+            &self.new_tags,
+            self.is_tags_field(),
+            self.is_tags_field().then_some(hint),
+            theme,
+        );
+    }
+
     fn render_profile_selector(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let profile_focused = self.is_profile_field();
         let selected_profile = self.selected_profile();
@@ -825,16 +963,52 @@ mod tests {
         dialog.handle_key(key(KeyCode::Tab));
         assert_eq!(dialog.focused_field, 2);
 
+        dialog.handle_key(key(KeyCode::Tab));
+        assert_eq!(dialog.focused_field, 3);
+
         dialog.handle_key(key(KeyCode::Tab));
         assert_eq!(dialog.focused_field, 0);
     }
 
+    #[test]
+    fn test_tags_field_submits_edit_against_original_tags() {
+        let mut dialog = RenameDialog::new("title", "", "default", default_profiles(), Vec::new())
+            .with_tags(&["blocked-ci".to_string(), "reviewer=alice".to_string()]);
+        for _ in 0..3 {
+            dialog.handle_key(key(KeyCode::Tab));
+        }
+        // Replace the field with a new list.
+        dialog.new_tags = Input::new("reviewer=bob".to_string());
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(data) => {
+                let edit = data.tags.expect("tags changed");
+                assert_eq!(edit.add, vec!["reviewer=bob"]);
+                assert_eq!(edit.remove, vec!["blocked-ci", "reviewer=alice"]);
+            }
+            _ => panic!("expected submit"),
+        }
+    }
+
+    #[test]
+    fn test_bad_tag_blocks_submit() {
+        let mut dialog = RenameDialog::new("title", "", "default", default_profiles(), Vec::new());
+        dialog.new_tags = Input::new("reviewer=".to_string());
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        assert!(dialog.validation_error.is_some());
+    }
+
     #[test]
     fn test_shift_tab_switches_fields_backwards() {
         let mut dialog =
             RenameDialog::new("Test", "group", "default", default_profiles(), Vec::new());
         assert_eq!(dialog.focused_field, 0);
 
+        dialog.handle_key(shift_key(KeyCode::Tab));
+        assert_eq!(dialog.focused_field, 3);
+
         dialog.handle_key(shift_key(KeyCode::Tab));
         assert_eq!(dialog.focused_field, 2);
 
@@ -1517,4 +1691,31 @@ mod tests {
         );
         assert!(dialog.validation_error.is_none());
     }
+
+    #[test]
+    fn test_sessions_move_into_existing_group_submits() {
+        // Moving marked sessions into a group that already exists is the
+        // point of the dialog, so the duplicate-name check must not fire.
+        let mut dialog = RenameDialog::new_for_sessions(
+            3,
+            "",
+            "default",
+            default_profiles(),
+            existing_groups_with_personal(),
+        );
+        // Group, profile, tags.
+        assert_eq!(dialog.field_count(), 3);
+        for ch in "personal".chars() {
+            dialog.handle_key(key(KeyCode::Char(ch)));
+        }
+
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(data) => {
+                assert_eq!(data.group.as_deref(), Some("personal"));
+                assert!(data.profile.is_none());
+                assert!(data.tags.is_none());
+            }
+            _ => panic!("expected Submit"),
+        }
+    }
 }
@@ -0,0 +1,143 @@
+//! Set-base dialog: one input for the diff base ref of the selected or
+//! marked sessions. Submitting an empty value clears the override so the
+//! diff falls back to the configured default branch. The ref is checked
+//! against each session's repo by the caller, since marked sessions can
+//! live in different repositories.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::tui::components::render_text_field;
+use crate::tui::styles::Theme;
+
+pub struct SetBaseDialog {
+    target: String,
+    input: Input,
+}
+
+impl SetBaseDialog {
+    /// `target` names what the base applies to (a session title or
+    /// "3 sessions"); `current` pre-fills the field.
+    pub fn new(target: &str, current: Option<&str>) -> Self {
+        Self {
+            target: target.to_string(),
+            input: Input::new(current.unwrap_or_default().to_string()),
+        }
+    }
+
+    /// Submits `None` for an empty field (clear the override).
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<Option<String>> {
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Enter => {
+                let value = self.input.value().trim();
+                DialogResult::Submit((!value.is_empty()).then(|| value.to_string()))
+            }
+            _ => {
+                self.input.handle_event(&crossterm::event::Event::Key(key));
+                DialogResult::Continue
+            }
+        }
+    }
+
+    pub fn handle_paste(&mut self, text: &str) {
+        for ch in text.chars().filter(|c| *c != '\n' && *c != '\r') {
+            self.input.handle(tui_input::InputRequest::InsertChar(ch));
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_area = super::centered_rect(area, 54, 8);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Set Diff Base ")
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // Target
+                Constraint::Length(1), // Base field
+                Constraint::Length(1), // Spacer
+                Constraint::Min(1),    // Hint
+            ])
+            .split(inner);
+
+        let target_line = Line::from(vec![
+            Span::styled("Sessions: ", Style::default().fg(theme.dimmed)),
+            Span::styled(&self.target, Style::default().fg(theme.text)),
+        ]);
+        frame.render_widget(Paragraph::new(target_line), chunks[0]);
+
+        render_text_field(
+            frame,
+            chunks[1],
+            "Base ref:",
+            &self.input,
+            true,
+            Some("empty = default branch"),
+            theme,
+        );
+
+        let hint = Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" apply  "),
+            Span::styled("Esc", Style::default().fg(theme.hint)),
+            Span::raw(" cancel"),
+        ]);
+        frame.render_widget(Paragraph::new(hint), chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn submits_the_trimmed_ref() {
+        let mut d = SetBaseDialog::new("sess", None);
+        d.handle_paste(" origin/main ");
+        match d.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(value) => assert_eq!(value.as_deref(), Some("origin/main")),
+            _ => panic!("expected Submit"),
+        }
+    }
+
+    #[test]
+    fn empty_field_clears_the_override() {
+        let mut d = SetBaseDialog::new("sess", Some("develop"));
+        for _ in 0.."develop".len() {
+            d.handle_key(key(KeyCode::Backspace));
+        }
+        assert!(matches!(
+            d.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(None)
+        ));
+    }
+
+    #[test]
+    fn esc_cancels() {
+        let mut d = SetBaseDialog::new("sess", None);
+        assert!(matches!(
+            d.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+}
@@ -0,0 +1,357 @@
+//! Full-text search over session transcripts (Ctrl+F).
+//!
+//! Unlike the `/` filter, which matches session titles in the list, this
+//! queries the transcript index in `session::search`. Searches run on a
+//! worker thread because the first one after a long conversation has to
+//! index it; `tick` collects the result.
+
+use std::sync::mpsc;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use super::DialogResult;
+use crate::session::search::{search_all_profiles, SearchHit, SearchQuery, DEFAULT_LIMIT};
+use crate::tui::components::set_prefixed_input_cursor_position;
+use crate::tui::styles::Theme;
+
+type Pending = mpsc::Receiver<anyhow::Result<Vec<SearchHit>>>;
+
+pub struct TranscriptSearchDialog {
+    input: Input,
+    /// Query the current `hits` answer; Enter re-runs the search when the
+    /// input no longer matches it and opens the selection otherwise.
+    searched: Option<String>,
+    hits: Vec<SearchHit>,
+    selected: usize,
+    pending: Option<Pending>,
+    error: Option<String>,
+}
+
+impl Default for TranscriptSearchDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TranscriptSearchDialog {
+    pub fn new() -> Self {
+        Self {
+            input: Input::default(),
+            searched: None,
+            hits: Vec::new(),
+            selected: 0,
+            pending: None,
+            error: None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<SearchHit> {
+        if matches!(key.code, KeyCode::Char('f') | KeyCode::Char('F'))
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            return DialogResult::Cancel;
+        }
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                DialogResult::Continue
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < self.hits.len() {
+                    self.selected += 1;
+                }
+                DialogResult::Continue
+            }
+            KeyCode::Enter => {
+                let query = self.input.value().trim().to_string();
+                if self.searched.as_deref() == Some(query.as_str()) {
+                    return match self.hits.get(self.selected) {
+                        Some(hit) => DialogResult::Submit(hit.clone()),
+                        None => DialogResult::Continue,
+                    };
+                }
+                if !query.is_empty() && self.pending.is_none() {
+                    self.start_search(query);
+                }
+                DialogResult::Continue
+            }
+            _ => {
+                self.input.handle_event(&crossterm::event::Event::Key(key));
+                DialogResult::Continue
+            }
+        }
+    }
+
+    fn start_search(&mut self, text: String) {
+        let (tx, rx) = mpsc::channel();
+        let query = SearchQuery {
+            text: text.clone(),
+            limit: DEFAULT_LIMIT,
+            ..SearchQuery::default()
+        };
+        std::thread::spawn(move || {
+            let _ = tx.send(search_all_profiles(&query));
+        });
+        self.pending = Some(rx);
+        self.searched = Some(text);
+        self.error = None;
+    }
+
+    /// Collect a finished search, if any. Returns true when state changed.
+    pub fn tick(&mut self) -> bool {
+        let Some(rx) = &self.pending else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => Err(anyhow::anyhow!("search worker exited")),
+        };
+        self.pending = None;
+        self.selected = 0;
+        match result {
+            Ok(hits) => self.hits = hits,
+            Err(e) => {
+                self.hits.clear();
+                self.error = Some(format!("{e:#}"));
+            }
+        }
+        true
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_width: u16 = area.width.saturating_sub(8).clamp(50, 100);
+        let dialog_height: u16 = area.height.saturating_sub(4).clamp(12, 30);
+        let dialog_area = super::centered_rect(area, dialog_width, dialog_height);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .style(Style::default().bg(theme.background))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .title(Line::styled(
+                " Search Transcripts ",
+                Style::default().fg(theme.title).bold(),
+            ));
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // input
+                Constraint::Length(1), // separator
+                Constraint::Min(1),    // results
+                Constraint::Length(1), // hint
+            ])
+            .split(inner);
+
+        let input_line = Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent).bold()),
+            Span::styled(self.input.value(), Style::default().fg(theme.text)),
+        ]);
+        frame.render_widget(Paragraph::new(input_line), chunks[0]);
+        set_prefixed_input_cursor_position(frame, chunks[0], "> ", &self.input);
+
+        let sep = "─".repeat(chunks[1].width as usize);
+        frame.render_widget(
+            Paragraph::new(Span::styled(sep, Style::default().fg(theme.dimmed))),
+            chunks[1],
+        );
+
+        let list_area = chunks[2];
+        let status = if self.pending.is_some() {
+            Some("Searching…".to_string())
+        } else if let Some(err) = &self.error {
+            Some(err.clone())
+        } else if self.searched.is_none() {
+            Some(
+                "Type a query and press Enter. Use \"quotes\" for phrases, foo* for prefixes."
+                    .into(),
+            )
+        } else if self.hits.is_empty() {
+            Some("No matches".to_string())
+        } else {
+            None
+        };
+        if let Some(status) = status {
+            let style = if self.error.is_some() {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default().fg(theme.dimmed)
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(status, style)).wrap(Wrap { trim: true }),
+                list_area,
+            );
+        } else {
+            // Two rows per hit: header, then the snippet.
+            let per_page = (list_area.height as usize / 2).max(1);
+            let start = self.selected.saturating_sub(per_page - 1);
+            let mut lines = Vec::new();
+            for (i, hit) in self.hits.iter().enumerate().skip(start).take(per_page) {
+                lines.extend(hit_lines(hit, i == self.selected, theme));
+            }
+            frame.render_widget(Paragraph::new(lines), list_area);
+        }
+
+        let footer = Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" search / go to session  "),
+            Span::styled("↑↓", Style::default().fg(theme.hint)),
+            Span::raw(" navigate  "),
+            Span::styled("Esc", Style::default().fg(theme.hint)),
+            Span::raw(" close"),
+        ]);
+        frame.render_widget(Paragraph::new(footer), chunks[3]);
+    }
+}
+
+fn hit_lines<'a>(hit: &'a SearchHit, selected: bool, theme: &Theme) -> [Line<'a>; 2] {
+    let prefix = if selected { "▶ " } else { "  " };
+    let title_style = if selected {
+        Style::default().fg(theme.title).bold()
+    } else {
+        Style::default().fg(theme.text)
+    };
+    let when = hit
+        .timestamp
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let header = Line::from(vec![
+        Span::styled(prefix, title_style),
+        Span::styled(hit.title.as_str(), title_style),
+        Span::styled(
+            format!(
+                "  {} · {} · {}  {when}",
+                hit.profile,
+                hit.tool,
+                hit.kind.as_str()
+            ),
+            Style::default().fg(theme.dimmed),
+        ),
+    ]);
+
+    let mut spans = vec![Span::raw("    ")];
+    let mut pos = 0;
+    for [start, end] in &hit.highlights {
+        let (start, end) = (*start, *end);
+        if start < pos || end > hit.snippet.len() {
+            continue;
+        }
+        spans.push(Span::styled(
+            &hit.snippet[pos..start],
+            Style::default().fg(theme.text),
+        ));
+        spans.push(Span::styled(
+            &hit.snippet[start..end],
+            Style::default().fg(theme.accent).bold(),
+        ));
+        pos = end;
+    }
+    spans.push(Span::styled(
+        &hit.snippet[pos..],
+        Style::default().fg(theme.text),
+    ));
+    [header, Line::from(spans)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::transcript::EntryKind;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn hit(id: &str) -> SearchHit {
+        SearchHit {
+            session_id: id.to_string(),
+            profile: "default".into(),
+            title: id.to_string(),
+            tool: "claude".into(),
+            kind: EntryKind::User,
+            snippet: "fix the parser".into(),
+            highlights: vec![[8, 14]],
+            timestamp: None,
+        }
+    }
+
+    /// Dialog with a completed search for "parser".
+    fn with_hits(ids: &[&str]) -> TranscriptSearchDialog {
+        let mut dialog = TranscriptSearchDialog::new();
+        for c in "parser".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        dialog.searched = Some("parser".into());
+        dialog.hits = ids.iter().map(|id| hit(id)).collect();
+        dialog
+    }
+
+    #[test]
+    fn esc_and_ctrl_f_cancel() {
+        let mut dialog = TranscriptSearchDialog::new();
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+        assert!(matches!(
+            dialog.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn enter_on_empty_query_does_nothing() {
+        let mut dialog = TranscriptSearchDialog::new();
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        assert!(dialog.pending.is_none());
+    }
+
+    #[test]
+    fn enter_submits_selected_hit_for_current_query() {
+        let mut dialog = with_hits(&["a", "b"]);
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(hit) => assert_eq!(hit.session_id, "b"),
+            _ => panic!("expected Submit"),
+        }
+    }
+
+    #[test]
+    fn editing_the_query_requires_a_new_search() {
+        let mut dialog = with_hits(&["a"]);
+        dialog.handle_key(key(KeyCode::Char('s')));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Continue
+        ));
+        assert_eq!(dialog.searched.as_deref(), Some("parsers"));
+        assert!(dialog.pending.is_some());
+    }
+
+    #[test]
+    fn hit_lines_split_highlights() {
+        let theme = Theme::default();
+        let h = hit("a");
+        let [_, snippet] = hit_lines(&h, false, &theme);
+        let texts: Vec<&str> = snippet.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, ["    ", "fix the ", "parser", ""]);
+    }
+}
@@ -4,6 +4,8 @@ use std::path::PathBuf;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use tui_input::backend::crossterm::EventHandler;
+
 use super::DiffView;
 use crate::tui::dialogs::DialogResult;
 
@@ -15,6 +17,8 @@ pub enum DiffAction {
     Close,
     /// Launch external editor for a file
     EditFile(PathBuf),
+    /// Send the session's pending review comments to its agent
+    SendReview(String),
 }
 
 impl DiffView {
@@ -31,9 +35,44 @@ impl DiffView {
             return DiffAction::Continue;
         }
 
+        if let Some(ref mut dialog) = self.confirm_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Submit(()) => {
+                    let action = dialog.action().to_string();
+                    self.confirm_dialog = None;
+                    match action.as_str() {
+                        "revert_hunk" => self.revert_hunk(),
+                        "delete_comment" => self.delete_pending_comment(),
+                        "send_review" => {
+                            if let Some(id) = self.session_id.clone() {
+                                return DiffAction::SendReview(id);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                DialogResult::Cancel => {
+                    self.confirm_dialog = None;
+                    self.pending_comment_delete = None;
+                }
+                DialogResult::Continue => {}
+            }
+            return DiffAction::Continue;
+        }
+
         // Clear transient messages on any key
         self.success_message = None;
 
+        if self.commit.is_some() {
+            self.handle_commit_key(key);
+            return DiffAction::Continue;
+        }
+
+        if self.comment_draft.is_some() {
+            self.handle_comment_key(key);
+            return DiffAction::Continue;
+        }
+
         // Handle help overlay
         if self.show_help {
             match key.code {
@@ -63,6 +102,7 @@ impl DiffView {
             let row_in_list = (row - self.file_list_inner.y) as usize;
             if row_in_list < self.files.len() && self.selected_file != row_in_list {
                 self.selected_file = row_in_list;
+                self.selected_hunk = 0;
                 self.scroll_offset = 0;
             }
         }
@@ -126,6 +166,60 @@ impl DiffView {
                 DiffAction::Continue
             }
 
+            // Hunk navigation
+            (KeyCode::Char('n'), _) => {
+                self.next_hunk();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('p'), _) => {
+                self.prev_hunk();
+                DiffAction::Continue
+            }
+
+            // Staging and commits
+            (KeyCode::Char('v'), _) => {
+                self.cycle_mode();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('s'), _) => {
+                self.stage_hunk();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('S'), _) => {
+                self.stage_file();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('u'), _) => {
+                self.unstage_hunk();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('U'), _) => {
+                self.unstage_file();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('x'), _) => {
+                self.request_revert_hunk();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('c'), _) => {
+                self.open_commit();
+                DiffAction::Continue
+            }
+
+            // Review comments
+            (KeyCode::Char('a'), _) => {
+                self.open_comment();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('d'), _) => {
+                self.request_delete_comment();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('m'), _) => {
+                self.request_send_review();
+                DiffAction::Continue
+            }
+
             // Branch selection
             (KeyCode::Char('b'), _) => {
                 self.open_branch_select();
@@ -160,6 +254,66 @@ impl DiffView {
         }
     }
 
+    fn handle_commit_key(&mut self, key: KeyEvent) {
+        // The message is on its way into a commit; keep the dialog up
+        // until `tick` reports how it went.
+        if self
+            .commit
+            .as_ref()
+            .is_some_and(|state| state.committing.is_some())
+        {
+            return;
+        }
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => self.commit = None,
+            (KeyCode::Enter, _) => self.commit_staged(),
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) => self.generate_commit_message(),
+            _ => {
+                if let Some(state) = &mut self.commit {
+                    state.input.handle_event(&crossterm::event::Event::Key(key));
+                }
+            }
+        }
+    }
+
+    fn handle_comment_key(&mut self, key: KeyEvent) {
+        let Some(draft) = &mut self.comment_draft else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.comment_draft = None,
+            KeyCode::Enter => self.save_comment(),
+            KeyCode::Tab | KeyCode::BackTab => draft.editing_lines = !draft.editing_lines,
+            _ => {
+                let field = if draft.editing_lines {
+                    &mut draft.lines
+                } else {
+                    &mut draft.body
+                };
+                field.handle_event(&crossterm::event::Event::Key(key));
+            }
+        }
+    }
+
+    /// Paste into the commit message or comment field. Ignored when
+    /// neither dialog is open.
+    pub fn handle_paste(&mut self, text: &str) {
+        let field = if let Some(state) = &mut self.commit {
+            &mut state.input
+        } else if let Some(draft) = &mut self.comment_draft {
+            if draft.editing_lines {
+                &mut draft.lines
+            } else {
+                &mut draft.body
+            }
+        } else {
+            return;
+        };
+        for ch in text.chars().filter(|c| *c != '\n' && *c != '\r') {
+            field.handle(tui_input::InputRequest::InsertChar(ch));
+        }
+    }
+
     fn handle_branch_select_key(&mut self, key: KeyEvent) -> DiffAction {
         let Some(state) = &mut self.branch_select else {
             return DiffAction::Continue;
@@ -192,7 +346,7 @@ impl DiffView {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tui::dialogs::InfoDialog;
+    use crate::tui::dialogs::{ConfirmDialog, InfoDialog};
     use crossterm::event::KeyModifiers;
 
     fn key(code: KeyCode) -> KeyEvent {
@@ -250,4 +404,76 @@ mod tests {
         let action = view.handle_key(key(KeyCode::Char('q')));
         assert!(matches!(action, DiffAction::Close));
     }
+
+    #[test]
+    fn test_commit_dialog_captures_keys_until_esc() {
+        let mut view = make_diff_view_no_warning();
+        view.commit = Some(super::super::CommitState::default());
+        // 'q' types into the message instead of closing the view
+        let action = view.handle_key(key(KeyCode::Char('q')));
+        assert!(matches!(action, DiffAction::Continue));
+        assert_eq!(view.commit.as_ref().unwrap().input.value(), "q");
+
+        view.handle_key(key(KeyCode::Esc));
+        assert!(view.commit.is_none());
+    }
+
+    #[test]
+    fn test_commit_dialog_rejects_empty_message() {
+        let mut view = make_diff_view_no_warning();
+        view.commit = Some(super::super::CommitState::default());
+        view.handle_key(key(KeyCode::Enter));
+        let state = view.commit.as_ref().expect("dialog stays open");
+        assert_eq!(state.status.as_deref(), Some("Enter a commit message"));
+    }
+
+    #[test]
+    fn test_revert_confirm_cancel_keeps_view() {
+        let mut view = make_diff_view_no_warning();
+        view.confirm_dialog = Some(ConfirmDialog::new("Revert Hunk", "sure?", "revert_hunk"));
+        let action = view.handle_key(key(KeyCode::Char('q')));
+        assert!(matches!(action, DiffAction::Continue));
+        view.handle_key(key(KeyCode::Esc));
+        assert!(view.confirm_dialog.is_none());
+    }
+
+    #[test]
+    fn test_send_review_needs_comments() {
+        let mut view = make_diff_view_no_warning();
+        view.session_id = Some("abc".to_string());
+        view.handle_key(key(KeyCode::Char('m')));
+        assert!(view.confirm_dialog.is_none());
+        assert!(view.error_message.is_some());
+    }
+
+    #[test]
+    fn test_confirmed_send_review_hands_off_to_home() {
+        let mut view = make_diff_view_no_warning();
+        view.session_id = Some("abc".to_string());
+        view.confirm_dialog = Some(ConfirmDialog::new("Send Review", "send?", "send_review"));
+        let action = view.handle_key(key(KeyCode::Char('y')));
+        assert!(matches!(action, DiffAction::SendReview(id) if id == "abc"));
+    }
+
+    #[test]
+    fn test_comment_dialog_tab_switches_field() {
+        let mut view = make_diff_view_no_warning();
+        view.comment_draft = Some(crate::tui::diff::CommentDraft {
+            lines: tui_input::Input::new("3".to_string()),
+            body: tui_input::Input::default(),
+            side: crate::session::reviews::DiffSide::New,
+            editing_lines: false,
+            status: None,
+        });
+        view.handle_key(key(KeyCode::Char('q')));
+        view.handle_key(key(KeyCode::Tab));
+        view.handle_key(key(KeyCode::Char('x')));
+        view.handle_key(key(KeyCode::Enter));
+        let draft = view.comment_draft.as_ref().unwrap();
+        assert_eq!(draft.body.value(), "q");
+        assert_eq!(draft.lines.value(), "3x");
+        assert!(draft.status.is_some());
+        view.handle_key(key(KeyCode::Esc));
+        assert!(view.comment_draft.is_none());
+    }
 }
@@ -2,17 +2,22 @@
 
 mod input;
 mod render;
+mod review;
+mod staging;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use tui_input::Input;
+
 use crate::git::diff::{
-    check_merge_base_status, compute_changed_files, compute_file_diff, get_default_base_ref,
-    list_branches, DiffFile, FileDiff,
+    check_merge_base_status, compute_changed_files_in, compute_file_diff_of, get_default_base_ref,
+    list_branches, DiffFile, DiffMode, FileDiff,
 };
 use crate::session::config::{load_config, save_config};
+use crate::session::reviews::{DiffSide, ReviewComment};
 use crate::session::Config;
-use crate::tui::dialogs::InfoDialog;
+use crate::tui::dialogs::{ConfirmDialog, InfoDialog};
 
 pub use input::DiffAction;
 
@@ -23,6 +28,31 @@ pub struct BranchSelectState {
     pub selected: usize,
 }
 
+/// State for the commit dialog
+#[derive(Default)]
+pub struct CommitState {
+    pub input: Input,
+    /// Progress or error line shown under the message field
+    pub status: Option<String>,
+    /// Receives the generated message while the generator command runs
+    pub generating: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+    /// Receives the new commit's short hash while `git commit` (and the
+    /// repo's commit hooks) run
+    pub committing: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+}
+
+/// State for the review comment dialog
+pub struct CommentDraft {
+    /// `12` or `12-18`, on `side`
+    pub lines: Input,
+    pub body: Input,
+    pub side: DiffSide,
+    /// Whether typing goes to the line range instead of the body
+    pub editing_lines: bool,
+    /// Validation error shown under the fields
+    pub status: Option<String>,
+}
+
 /// The diff view state
 pub struct DiffView {
     /// Path to the repository root
@@ -40,12 +70,18 @@ pub struct DiffView {
     /// Base branch to compare against
     pub(crate) base_branch: String,
 
+    /// Which snapshots are compared: base branch, unstaged, or staged
+    pub(crate) mode: DiffMode,
+
     /// List of changed files
     pub(crate) files: Vec<DiffFile>,
 
     /// Currently selected file index
     pub(crate) selected_file: usize,
 
+    /// Hunk in the selected file that stage/unstage/revert act on
+    pub(crate) selected_hunk: usize,
+
     /// Cached file diffs
     pub(crate) diff_cache: HashMap<PathBuf, FileDiff>,
 
@@ -79,6 +115,24 @@ pub struct DiffView {
     /// Warning dialog shown when merge-base can't be computed
     pub(crate) warning_dialog: Option<InfoDialog>,
 
+    /// Commit dialog state
+    pub(crate) commit: Option<CommitState>,
+
+    /// Confirmation before reverting a hunk
+    pub(crate) confirm_dialog: Option<ConfirmDialog>,
+
+    /// `diff.commit_message_command`, captured at open
+    pub(crate) commit_message_command: Option<String>,
+
+    /// This session's pending review comments, all files
+    pub(crate) review_comments: Vec<ReviewComment>,
+
+    /// Review comment dialog state
+    pub(crate) comment_draft: Option<CommentDraft>,
+
+    /// Comment the open "Delete Comment" confirmation refers to
+    pub(crate) pending_comment_delete: Option<String>,
+
     /// Override that has been persisted to disk but not yet propagated
     /// back to HomeView's in-memory `Instance.base_branch_override`.
     /// HomeView consumes this after each key event via
@@ -124,6 +178,7 @@ impl DiffView {
             .unwrap_or_else(|| "main".to_string());
 
         let context_lines = config.diff.context_lines;
+        let commit_message_command = config.diff.commit_message_command.clone();
 
         let warning_dialog = check_merge_base_status(&repo_path, &base_branch)
             .map(|msg| InfoDialog::new("Warning", &msg));
@@ -133,8 +188,10 @@ impl DiffView {
             session_id,
             profile,
             base_branch,
+            mode: DiffMode::Base,
             files: Vec::new(),
             selected_file: 0,
+            selected_hunk: 0,
             diff_cache: HashMap::new(),
             scroll_offset: 0,
             visible_lines: 20,
@@ -146,21 +203,29 @@ impl DiffView {
             show_help: false,
             file_list_width: config.app_state.diff_file_list_width.unwrap_or(35),
             warning_dialog,
+            commit: None,
+            confirm_dialog: None,
+            commit_message_command,
+            review_comments: Vec::new(),
+            comment_draft: None,
+            pending_comment_delete: None,
             pending_override: None,
             file_list_inner: ratatui::layout::Rect::default(),
         };
 
         view.refresh_files()?;
+        view.reload_review_comments();
         Ok(view)
     }
 
     /// Refresh the list of changed files
     pub fn refresh_files(&mut self) -> anyhow::Result<()> {
-        self.files = compute_changed_files(&self.repo_path, &self.base_branch)?;
+        self.files = compute_changed_files_in(&self.repo_path, &self.base_branch, self.mode)?;
         self.diff_cache.clear();
         if self.selected_file >= self.files.len() {
             self.selected_file = self.files.len().saturating_sub(1);
         }
+        self.selected_hunk = 0;
         self.scroll_offset = 0;
         Ok(())
     }
@@ -176,11 +241,12 @@ impl DiffView {
         let path = file.path.clone();
 
         if !self.diff_cache.contains_key(&path) {
-            match compute_file_diff(
+            match compute_file_diff_of(
                 &self.repo_path,
-                &path,
+                file,
                 &self.base_branch,
                 self.context_lines,
+                self.mode,
             ) {
                 Ok(diff) => {
                     self.diff_cache.insert(path.clone(), diff);
@@ -259,6 +325,7 @@ impl DiffView {
     pub fn next_file(&mut self) {
         if self.selected_file < self.files.len().saturating_sub(1) {
             self.selected_file += 1;
+            self.selected_hunk = 0;
             self.scroll_offset = 0;
         }
     }
@@ -267,6 +334,7 @@ impl DiffView {
     pub fn prev_file(&mut self) {
         if self.selected_file > 0 {
             self.selected_file -= 1;
+            self.selected_hunk = 0;
             self.scroll_offset = 0;
         }
     }
@@ -330,8 +398,10 @@ impl DiffView {
             session_id: None,
             profile: String::new(),
             base_branch: "main".to_string(),
+            mode: DiffMode::Base,
             files: Vec::new(),
             selected_file: 0,
+            selected_hunk: 0,
             diff_cache: HashMap::new(),
             scroll_offset: 0,
             visible_lines: 20,
@@ -343,6 +413,12 @@ impl DiffView {
             show_help: false,
             file_list_width: 35,
             warning_dialog: None,
+            commit: None,
+            confirm_dialog: None,
+            commit_message_command: None,
+            review_comments: Vec::new(),
+            comment_draft: None,
+            pending_comment_delete: None,
             pending_override: None,
             file_list_inner: ratatui::layout::Rect::default(),
         }
@@ -12,8 +12,11 @@ use ratatui::{
 };
 use similar::ChangeTag;
 
+use super::review::anchor_comments;
 use super::DiffView;
-use crate::git::diff::FileStatus;
+use crate::git::diff::{DiffMode, FileStatus};
+use crate::session::reviews::ReviewComment;
+use crate::tui::components::render_text_field;
 use crate::tui::styles::Theme;
 
 /// Truncate a string from the left, adding an ellipsis prefix if it doesn't fit.
@@ -30,6 +33,36 @@ fn truncate_left(s: &str, max_width: usize) -> String {
     format!("\u{2026}{}", &s[start..])
 }
 
+/// One-row annotation for a review comment, indented past the line
+/// number gutter. Multi-line bodies show their first line.
+fn comment_line(
+    comment: &ReviewComment,
+    gutter: &str,
+    outdated: bool,
+    theme: &Theme,
+) -> Line<'static> {
+    let mut body = comment.body.lines().next().unwrap_or_default().to_string();
+    if comment.body.lines().nth(1).is_some() {
+        body.push_str(" \u{2026}");
+    }
+    Line::from(vec![
+        Span::raw(format!("{gutter} ")),
+        Span::styled("\u{258c} ", Style::default().fg(theme.accent)),
+        Span::styled(
+            format!(
+                "{} ({}{}): ",
+                comment.range_label(),
+                comment.side.label(),
+                if outdated { ", outdated" } else { "" }
+            ),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(body, Style::default().fg(theme.text)),
+    ])
+}
+
 impl DiffView {
     pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         // Clear the area
@@ -60,6 +93,18 @@ impl DiffView {
             self.render_help(frame, area, theme);
         }
 
+        if self.commit.is_some() {
+            self.render_commit_dialog(frame, area, theme);
+        }
+
+        if self.comment_draft.is_some() {
+            self.render_comment_dialog(frame, area, theme);
+        }
+
+        if let Some(ref mut dialog) = self.confirm_dialog {
+            dialog.render(frame, area, theme);
+        }
+
         // Render warning dialog on top of everything
         if let Some(ref mut dialog) = self.warning_dialog {
             dialog.render(frame, area, theme);
@@ -85,13 +130,34 @@ impl DiffView {
             .and_then(|n| n.to_str())
             .unwrap_or("repo");
 
-        let header = Line::from(vec![
-            Span::styled(
-                format!("  {} ", repo_name),
-                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("vs ", Style::default().fg(theme.dimmed)),
-            Span::styled(&self.base_branch, Style::default().fg(theme.accent)),
+        let mut spans = vec![Span::styled(
+            format!("  {} ", repo_name),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )];
+        match self.mode {
+            DiffMode::Base => {
+                spans.push(Span::styled("vs ", Style::default().fg(theme.dimmed)));
+                spans.push(Span::styled(
+                    &self.base_branch,
+                    Style::default().fg(theme.accent),
+                ));
+            }
+            DiffMode::Unstaged => {
+                spans.push(Span::styled("unstaged", Style::default().fg(theme.accent)));
+                spans.push(Span::styled(
+                    " (index vs working tree)",
+                    Style::default().fg(theme.dimmed),
+                ));
+            }
+            DiffMode::Staged => {
+                spans.push(Span::styled("staged", Style::default().fg(theme.accent)));
+                spans.push(Span::styled(
+                    " (HEAD vs index)",
+                    Style::default().fg(theme.dimmed),
+                ));
+            }
+        }
+        spans.extend([
             Span::styled("  |  ", Style::default().fg(theme.border)),
             Span::styled(
                 format!("{} changed", file_count),
@@ -108,8 +174,21 @@ impl DiffView {
                 Style::default().fg(theme.diff_delete),
             ),
         ]);
+        if !self.review_comments.is_empty() {
+            let count = self.review_comments.len();
+            spans.extend([
+                Span::styled("  |  ", Style::default().fg(theme.border)),
+                Span::styled(
+                    format!(
+                        "{count} review comment{}",
+                        if count == 1 { "" } else { "s" }
+                    ),
+                    Style::default().fg(theme.accent),
+                ),
+            ]);
+        }
 
-        frame.render_widget(Paragraph::new(header), inner);
+        frame.render_widget(Paragraph::new(Line::from(spans)), inner);
     }
 
     fn render_content(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
@@ -246,18 +325,28 @@ impl DiffView {
 
                 // Build all diff lines
                 let mut lines: Vec<Line> = Vec::new();
+                let anchors = anchor_comments(diff, &self.review_comments);
+                let gutter = format!("{blank} {blank}");
+                for comment in &anchors.outdated {
+                    lines.push(comment_line(comment, &gutter, true, theme));
+                }
 
-                for hunk in &diff.hunks {
+                for (hunk_idx, hunk) in diff.hunks.iter().enumerate() {
                     let header = format!(
                         "@@ -{},{} +{},{} @@",
                         hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
                     );
-                    lines.push(Line::from(Span::styled(
-                        header,
-                        Style::default().fg(theme.diff_header),
-                    )));
+                    // The hunk that s/u/x act on
+                    let header_style = if hunk_idx == self.selected_hunk {
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.diff_header)
+                    };
+                    lines.push(Line::from(Span::styled(header, header_style)));
 
-                    for line in &hunk.lines {
+                    for (line_idx, line) in hunk.lines.iter().enumerate() {
                         let (prefix, style) = match line.tag {
                             ChangeTag::Delete => ("-", Style::default().fg(theme.diff_delete)),
                             ChangeTag::Insert => ("+", Style::default().fg(theme.diff_add)),
@@ -283,6 +372,15 @@ impl DiffView {
                             Span::styled(prefix, style),
                             Span::styled(content, style),
                         ]));
+
+                        for comment in anchors
+                            .by_line
+                            .get(&(hunk_idx, line_idx))
+                            .into_iter()
+                            .flatten()
+                        {
+                            lines.push(comment_line(comment, &gutter, false, theme));
+                        }
                     }
 
                     lines.push(Line::from(""));
@@ -347,17 +445,26 @@ impl DiffView {
         } else if let Some(ref success) = self.success_message {
             Line::from(Span::styled(success, Style::default().fg(theme.diff_add)))
         } else {
+            let stage_hint = match self.mode {
+                DiffMode::Base => ("S", ": stage file  "),
+                DiffMode::Unstaged => ("s/S/x", ": stage/revert  "),
+                DiffMode::Staged => ("u/U", ": unstage  "),
+            };
             Line::from(vec![
                 Span::styled("j/k", Style::default().fg(theme.accent)),
                 Span::styled(": files  ", Style::default().fg(theme.dimmed)),
-                Span::styled("h/l", Style::default().fg(theme.accent)),
-                Span::styled(": resize  ", Style::default().fg(theme.dimmed)),
-                Span::styled("scroll", Style::default().fg(theme.accent)),
-                Span::styled(": diff  ", Style::default().fg(theme.dimmed)),
+                Span::styled("n/p", Style::default().fg(theme.accent)),
+                Span::styled(": hunks  ", Style::default().fg(theme.dimmed)),
+                Span::styled("v", Style::default().fg(theme.accent)),
+                Span::styled(": view  ", Style::default().fg(theme.dimmed)),
+                Span::styled(stage_hint.0, Style::default().fg(theme.accent)),
+                Span::styled(stage_hint.1, Style::default().fg(theme.dimmed)),
+                Span::styled("c", Style::default().fg(theme.accent)),
+                Span::styled(": commit  ", Style::default().fg(theme.dimmed)),
+                Span::styled("a/m", Style::default().fg(theme.accent)),
+                Span::styled(": comment/send  ", Style::default().fg(theme.dimmed)),
                 Span::styled("e/Enter", Style::default().fg(theme.accent)),
                 Span::styled(": edit  ", Style::default().fg(theme.dimmed)),
-                Span::styled("b", Style::default().fg(theme.accent)),
-                Span::styled(": branch  ", Style::default().fg(theme.dimmed)),
                 Span::styled("?", Style::default().fg(theme.accent)),
                 Span::styled(": help  ", Style::default().fg(theme.dimmed)),
                 Span::styled("q/Esc", Style::default().fg(theme.accent)),
@@ -489,9 +596,134 @@ impl DiffView {
         }
     }
 
+    fn render_commit_dialog(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(state) = &self.commit else {
+            return;
+        };
+        let dialog_area = crate::tui::dialogs::centered_rect(area, 70, 8);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(" Commit ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.background));
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // Message field
+                Constraint::Length(1), // Status
+                Constraint::Min(1),    // Hint
+            ])
+            .split(inner);
+
+        render_text_field(
+            frame,
+            chunks[0],
+            "Message:",
+            &state.input,
+            true,
+            Some("summary line"),
+            theme,
+        );
+
+        if let Some(status) = &state.status {
+            let style = if state.generating.is_some() || state.committing.is_some() {
+                Style::default().fg(theme.dimmed)
+            } else {
+                Style::default().fg(theme.error)
+            };
+            frame.render_widget(Paragraph::new(Span::styled(status, style)), chunks[1]);
+        }
+
+        let mut hint = vec![
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" commit  "),
+        ];
+        if self.commit_message_command.is_some() {
+            hint.push(Span::styled("Ctrl+G", Style::default().fg(theme.hint)));
+            hint.push(Span::raw(" generate  "));
+        }
+        hint.push(Span::styled("Esc", Style::default().fg(theme.hint)));
+        hint.push(Span::raw(" cancel"));
+        frame.render_widget(Paragraph::new(Line::from(hint)), chunks[2]);
+    }
+
+    fn render_comment_dialog(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(draft) = &self.comment_draft else {
+            return;
+        };
+        let dialog_area = crate::tui::dialogs::centered_rect(area, 70, 9);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(" Review Comment ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.background));
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // Lines field
+                Constraint::Length(1), // Comment field
+                Constraint::Length(1), // Status
+                Constraint::Min(1),    // Hint
+            ])
+            .split(inner);
+
+        let lines_label = format!("Lines ({}):", draft.side.label());
+        render_text_field(
+            frame,
+            chunks[0],
+            &lines_label,
+            &draft.lines,
+            draft.editing_lines,
+            Some("12 or 12-18"),
+            theme,
+        );
+        render_text_field(
+            frame,
+            chunks[1],
+            "Comment:",
+            &draft.body,
+            !draft.editing_lines,
+            Some("what should change"),
+            theme,
+        );
+
+        if let Some(status) = &draft.status {
+            frame.render_widget(
+                Paragraph::new(Span::styled(status, Style::default().fg(theme.error))),
+                chunks[2],
+            );
+        }
+
+        let hint = Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" save  "),
+            Span::styled("Tab", Style::default().fg(theme.hint)),
+            Span::raw(" switch field  "),
+            Span::styled("Esc", Style::default().fg(theme.hint)),
+            Span::raw(" cancel"),
+        ]);
+        frame.render_widget(Paragraph::new(hint), chunks[3]);
+    }
+
     fn render_help(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let dialog_width = 55u16;
-        let dialog_height = 19u16;
+        let dialog_height = 32u16;
 
         let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
         let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
@@ -528,9 +760,28 @@ impl DiffView {
                     ("PgUp/Dn", "Page up / down in diff"),
                     ("Ctrl+u/d", "Half-page up / down"),
                     ("g/G", "Go to top / bottom of diff"),
+                    ("n/p", "Next / previous hunk"),
                     ("h/l, ←/→", "Shrink / grow file list"),
                 ],
             ),
+            (
+                "Staging",
+                vec![
+                    ("v", "Cycle base / unstaged / staged"),
+                    ("s/S", "Stage hunk / file"),
+                    ("u/U", "Unstage hunk / file"),
+                    ("x", "Revert hunk (unstaged view)"),
+                    ("c", "Commit staged changes"),
+                ],
+            ),
+            (
+                "Review",
+                vec![
+                    ("a", "Comment on the selected hunk"),
+                    ("d", "Delete a comment on the hunk"),
+                    ("m", "Send comments to the agent"),
+                ],
+            ),
             (
                 "Actions",
                 vec![
@@ -0,0 +1,341 @@
+//! Review comments for the diff view: line-anchored notes that are stored
+//! per session (`session::reviews`) and sent to the agent as one message
+
+use std::collections::HashMap;
+
+use tui_input::Input;
+
+use super::{CommentDraft, DiffView};
+use crate::git::diff::FileDiff;
+use crate::session::reviews::{self, DiffSide, ReviewComment};
+use crate::tui::dialogs::ConfirmDialog;
+
+/// Where a file's comments render: after the diff line that ends their
+/// range, or in a block above the first hunk when that line is no longer
+/// part of the diff.
+#[derive(Default)]
+pub(crate) struct CommentAnchors<'a> {
+    pub outdated: Vec<&'a ReviewComment>,
+    /// Keyed by (hunk index, line index within the hunk)
+    pub by_line: HashMap<(usize, usize), Vec<&'a ReviewComment>>,
+}
+
+impl CommentAnchors<'_> {
+    /// Annotation rows rendered inside hunk `hunk_idx`
+    pub fn rows_in_hunk(&self, hunk_idx: usize) -> usize {
+        self.by_line
+            .iter()
+            .filter(|((h, _), _)| *h == hunk_idx)
+            .map(|(_, c)| c.len())
+            .sum()
+    }
+}
+
+/// Anchor `comments` on `diff`. Comments for other files are ignored.
+pub(crate) fn anchor_comments<'a>(
+    diff: &FileDiff,
+    comments: &'a [ReviewComment],
+) -> CommentAnchors<'a> {
+    let path = diff.file.path.to_string_lossy();
+    let mut anchors = CommentAnchors::default();
+    for comment in comments.iter().filter(|c| c.file_path == path) {
+        let found = diff.hunks.iter().enumerate().find_map(|(h, hunk)| {
+            hunk.lines
+                .iter()
+                .position(|l| line_number(l, comment.side) == Some(comment.end_line))
+                .map(|i| (h, i))
+        });
+        match found {
+            Some(key) => anchors.by_line.entry(key).or_default().push(comment),
+            None => anchors.outdated.push(comment),
+        }
+    }
+    anchors
+}
+
+fn line_number(line: &crate::git::diff::DiffLine, side: DiffSide) -> Option<usize> {
+    match side {
+        DiffSide::Old => line.old_line_num,
+        DiffSide::New => line.new_line_num,
+    }
+}
+
+/// The lines of `diff` on `side` within `start..=end`, joined; None when
+/// the range touches no line of the diff.
+fn extract_snippet(diff: &FileDiff, side: DiffSide, start: usize, end: usize) -> Option<String> {
+    let lines: Vec<&str> = diff
+        .hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| line_number(l, side).is_some_and(|n| (start..=end).contains(&n)))
+        .map(|l| l.content.trim_end_matches('\n'))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Parse `12` or `12-18`.
+fn parse_range(text: &str) -> Option<(usize, usize)> {
+    let (start, end) = match text.trim().split_once('-') {
+        Some((a, b)) => (a.trim().parse().ok()?, b.trim().parse().ok()?),
+        None => {
+            let n: usize = text.trim().parse().ok()?;
+            (n, n)
+        }
+    };
+    (start > 0 && end > 0).then(|| (start.min(end), start.max(end)))
+}
+
+impl DiffView {
+    /// Reload this session's pending comments from disk
+    pub fn reload_review_comments(&mut self) {
+        let Some(session_id) = &self.session_id else {
+            return;
+        };
+        match reviews::comments_for(&self.profile, session_id) {
+            Ok(comments) => self.review_comments = comments,
+            Err(e) => self.error_message = Some(format!("Failed to load review comments: {e}")),
+        }
+    }
+
+    /// Open the comment dialog on the selected hunk, prefilled with the
+    /// hunk's line range. Comments go on the new side unless the hunk
+    /// only deletes lines.
+    pub fn open_comment(&mut self) {
+        if self.session_id.is_none() {
+            self.error_message = Some("Review comments need a session".to_string());
+            return;
+        }
+        let selected = self.selected_hunk;
+        let Some(hunk) = self.get_current_diff().and_then(|d| d.hunks.get(selected)) else {
+            return;
+        };
+        let (side, start, count) = if hunk.new_lines == 0 {
+            (DiffSide::Old, hunk.old_start, hunk.old_lines)
+        } else {
+            (DiffSide::New, hunk.new_start, hunk.new_lines)
+        };
+        let end = start + count.saturating_sub(1);
+        let range = if start == end {
+            start.to_string()
+        } else {
+            format!("{start}-{end}")
+        };
+        self.error_message = None;
+        self.comment_draft = Some(CommentDraft {
+            lines: Input::new(range),
+            body: Input::default(),
+            side,
+            editing_lines: false,
+            status: None,
+        });
+    }
+
+    /// Save the dialog's comment to the session's review
+    pub(crate) fn save_comment(&mut self) {
+        let Some(draft) = &self.comment_draft else {
+            return;
+        };
+        let side = draft.side;
+        let body = draft.body.value().trim().to_string();
+        let range = parse_range(draft.lines.value());
+        let status = if body.is_empty() {
+            Some("Enter a comment".to_string())
+        } else if range.is_none() {
+            Some("Lines must look like 12 or 12-18".to_string())
+        } else {
+            None
+        };
+        if let Some(status) = status {
+            if let Some(draft) = &mut self.comment_draft {
+                draft.status = Some(status);
+            }
+            return;
+        }
+        let (start, end) = range.unwrap_or_default();
+        let Some(diff) = self.get_current_diff() else {
+            return;
+        };
+        let path = diff.file.path.to_string_lossy().to_string();
+        let Some(snippet) = extract_snippet(diff, side, start, end) else {
+            if let Some(draft) = &mut self.comment_draft {
+                draft.status = Some(format!(
+                    "No {} lines {start}-{end} in this diff",
+                    side.label()
+                ));
+            }
+            return;
+        };
+        let comment = ReviewComment::new(&path, side, start, end, &body, &snippet);
+        let label = comment.range_label();
+        let Some(session_id) = self.session_id.clone() else {
+            return;
+        };
+        match reviews::add(&self.profile, &session_id, comment) {
+            Ok(_) => {
+                self.comment_draft = None;
+                self.success_message = Some(format!("Commented on {label}"));
+                self.reload_review_comments();
+            }
+            Err(e) => {
+                if let Some(draft) = &mut self.comment_draft {
+                    draft.status = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    /// Ask before deleting the newest comment inside the selected hunk
+    pub fn request_delete_comment(&mut self) {
+        let selected = self.selected_hunk;
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        let newest = {
+            let Some(diff) = self.diff_cache.get(&path) else {
+                return;
+            };
+            anchor_comments(diff, &self.review_comments)
+                .by_line
+                .into_iter()
+                .filter(|((h, _), _)| *h == selected)
+                .flat_map(|(_, c)| c)
+                .max_by_key(|c| c.created_at)
+                .cloned()
+        };
+        let Some(comment) = newest else {
+            self.error_message = Some("No comments on this hunk".to_string());
+            return;
+        };
+        self.confirm_dialog = Some(ConfirmDialog::new(
+            "Delete Comment",
+            &format!(
+                "Delete the comment on {}?\n\n{}",
+                comment.range_label(),
+                comment.body
+            ),
+            "delete_comment",
+        ));
+        self.pending_comment_delete = Some(comment.id);
+    }
+
+    pub(crate) fn delete_pending_comment(&mut self) {
+        let (Some(session_id), Some(comment_id)) =
+            (self.session_id.clone(), self.pending_comment_delete.take())
+        else {
+            return;
+        };
+        match reviews::remove(&self.profile, &session_id, &comment_id) {
+            Ok(removed) => {
+                if removed.is_some() {
+                    self.success_message = Some("Deleted comment".to_string());
+                }
+                self.reload_review_comments();
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Ask before sending every pending comment to the agent
+    pub fn request_send_review(&mut self) {
+        if self.review_comments.is_empty() {
+            self.error_message = Some("No review comments to send (a to add one)".to_string());
+            return;
+        }
+        let count = self.review_comments.len();
+        let files = self
+            .review_comments
+            .iter()
+            .map(|c| c.file_path.as_str())
+            .collect::<std::collections::BTreeSet<_>>()
+            .len();
+        self.confirm_dialog = Some(
+            ConfirmDialog::new(
+                "Send Review",
+                &format!(
+                    "Send {count} comment{} on {files} file{} to the agent as one message?",
+                    if count == 1 { "" } else { "s" },
+                    if files == 1 { "" } else { "s" },
+                ),
+                "send_review",
+            )
+            .neutral(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{DiffFile, DiffHunk, DiffLine, FileStatus};
+    use similar::ChangeTag;
+    use std::path::PathBuf;
+
+    fn line(tag: ChangeTag, old: Option<usize>, new: Option<usize>) -> DiffLine {
+        DiffLine {
+            tag,
+            old_line_num: old,
+            new_line_num: new,
+            content: format!("{old:?} {new:?}\n"),
+        }
+    }
+
+    fn diff() -> FileDiff {
+        FileDiff {
+            file: DiffFile {
+                path: PathBuf::from("src/lib.rs"),
+                old_path: None,
+                status: FileStatus::Modified,
+                additions: 1,
+                deletions: 1,
+            },
+            hunks: vec![DiffHunk {
+                old_start: 10,
+                old_lines: 2,
+                new_start: 10,
+                new_lines: 2,
+                lines: vec![
+                    line(ChangeTag::Equal, Some(10), Some(10)),
+                    line(ChangeTag::Delete, Some(11), None),
+                    line(ChangeTag::Insert, None, Some(11)),
+                ],
+            }],
+            is_binary: false,
+            old_mode: 0o100644,
+            new_mode: 0o100644,
+        }
+    }
+
+    #[test]
+    fn comments_anchor_on_their_last_line_or_go_outdated() {
+        let comments = vec![
+            ReviewComment::new("src/lib.rs", DiffSide::New, 10, 11, "a", ""),
+            ReviewComment::new("src/lib.rs", DiffSide::Old, 11, 11, "b", ""),
+            ReviewComment::new("src/lib.rs", DiffSide::New, 40, 40, "c", ""),
+            ReviewComment::new("src/other.rs", DiffSide::New, 10, 10, "d", ""),
+        ];
+        let diff = diff();
+        let anchors = anchor_comments(&diff, &comments);
+        assert_eq!(anchors.by_line[&(0, 2)][0].body, "a");
+        assert_eq!(anchors.by_line[&(0, 1)][0].body, "b");
+        assert_eq!(anchors.outdated.len(), 1);
+        assert_eq!(anchors.rows_in_hunk(0), 2);
+    }
+
+    #[test]
+    fn snippet_skips_the_other_side() {
+        let diff = diff();
+        assert_eq!(
+            extract_snippet(&diff, DiffSide::New, 10, 11).as_deref(),
+            Some("Some(10) Some(10)\nNone Some(11)")
+        );
+        assert_eq!(extract_snippet(&diff, DiffSide::New, 30, 31), None);
+    }
+
+    #[test]
+    fn range_parsing() {
+        assert_eq!(parse_range("12"), Some((12, 12)));
+        assert_eq!(parse_range(" 18 - 12 "), Some((12, 18)));
+        assert_eq!(parse_range("0"), None);
+        assert_eq!(parse_range("a-b"), None);
+    }
+}
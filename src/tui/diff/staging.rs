@@ -0,0 +1,467 @@
+//! Staging, revert, and commit actions for the diff view
+
+use std::io::{Read, Write};
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use super::review::anchor_comments;
+use super::{CommitState, DiffView};
+use crate::git::diff::{compute_changed_files_in, DiffMode};
+use crate::git::stage::{self, HunkAction};
+use crate::tui::dialogs::ConfirmDialog;
+
+/// Prompt sent ahead of the staged diff to `diff.commit_message_command`
+const COMMIT_MESSAGE_PROMPT: &str = "Write a git commit message for the staged changes below. \
+Reply with one summary line of at most 72 characters and nothing else.\n\n";
+
+/// Staged diffs past this size are cut before they reach the generator, so
+/// a vendored-dependency commit doesn't blow the agent's context window.
+const MAX_GENERATOR_DIFF_BYTES: usize = 100_000;
+
+/// How long the generator may run before it is killed. Agent CLIs take tens
+/// of seconds on a large diff; a hung one shouldn't leave the dialog stuck
+/// on "Generating message..." forever.
+const MESSAGE_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+impl DiffView {
+    /// Cycle base -> unstaged -> staged, keeping the selected file when it
+    /// is still in the list.
+    pub fn cycle_mode(&mut self) {
+        self.mode = self.mode.next();
+        self.refresh_keeping_selection();
+    }
+
+    /// Select the next hunk in the current file and scroll to it
+    pub fn next_hunk(&mut self) {
+        if self.selected_hunk + 1 < self.hunk_count() {
+            self.selected_hunk += 1;
+            self.scroll_to_hunk();
+        }
+    }
+
+    /// Select the previous hunk in the current file and scroll to it
+    pub fn prev_hunk(&mut self) {
+        if self.selected_hunk > 0 {
+            self.selected_hunk -= 1;
+            self.scroll_to_hunk();
+        }
+    }
+
+    fn hunk_count(&mut self) -> usize {
+        self.get_current_diff().map_or(0, |d| d.hunks.len())
+    }
+
+    /// Line offset of the selected hunk's header in the rendered diff:
+    /// outdated review comments render first, then each hunk as a header,
+    /// its lines and comment annotations, and a blank separator.
+    pub(crate) fn selected_hunk_offset(&mut self) -> usize {
+        let selected = self.selected_hunk;
+        let _ = self.get_current_diff();
+        let Some(diff) = self
+            .selected_file()
+            .and_then(|f| self.diff_cache.get(&f.path))
+        else {
+            return 0;
+        };
+        let anchors = anchor_comments(diff, &self.review_comments);
+        anchors.outdated.len()
+            + diff
+                .hunks
+                .iter()
+                .take(selected)
+                .enumerate()
+                .map(|(i, h)| h.lines.len() + 2 + anchors.rows_in_hunk(i))
+                .sum::<usize>()
+    }
+
+    /// Render clamps the offset, so this can overshoot the last page.
+    fn scroll_to_hunk(&mut self) {
+        self.scroll_offset = self.selected_hunk_offset() as u16;
+    }
+
+    /// Stage the selected hunk (unstaged view only)
+    pub fn stage_hunk(&mut self) {
+        if self.mode != DiffMode::Unstaged {
+            self.error_message = Some("Press v for the unstaged view to stage hunks".to_string());
+            return;
+        }
+        self.apply_selected_hunk(HunkAction::Stage, "Staged hunk");
+    }
+
+    /// Unstage the selected hunk (staged view only)
+    pub fn unstage_hunk(&mut self) {
+        if self.mode != DiffMode::Staged {
+            self.error_message = Some("Press v for the staged view to unstage hunks".to_string());
+            return;
+        }
+        self.apply_selected_hunk(HunkAction::Unstage, "Unstaged hunk");
+    }
+
+    /// Ask before discarding the selected hunk from the working tree
+    /// (unstaged view only)
+    pub fn request_revert_hunk(&mut self) {
+        if self.mode != DiffMode::Unstaged {
+            self.error_message = Some("Press v for the unstaged view to revert hunks".to_string());
+            return;
+        }
+        if self.hunk_count() == 0 {
+            return;
+        }
+        let path = self
+            .selected_file()
+            .map(|f| f.path.display().to_string())
+            .unwrap_or_default();
+        self.confirm_dialog = Some(ConfirmDialog::new(
+            "Revert Hunk",
+            &format!(
+                "Discard hunk {} of '{}' from the working tree? This can't be undone.",
+                self.selected_hunk + 1,
+                path
+            ),
+            "revert_hunk",
+        ));
+    }
+
+    pub(crate) fn revert_hunk(&mut self) {
+        self.apply_selected_hunk(HunkAction::Revert, "Reverted hunk");
+    }
+
+    fn apply_selected_hunk(&mut self, action: HunkAction, done: &str) {
+        let selected = self.selected_hunk;
+        let Some(patch) = self.get_current_diff().map(|d| d.hunk_patch(selected)) else {
+            return;
+        };
+        let Some(patch) = patch else {
+            self.error_message = Some("No hunk to apply (binary file?)".to_string());
+            return;
+        };
+        match stage::apply_hunk(&self.repo_path, &patch, action) {
+            Ok(()) => {
+                self.error_message = None;
+                self.success_message = Some(done.to_string());
+                self.refresh_keeping_selection();
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Stage every change to the selected file
+    pub fn stage_file(&mut self) {
+        self.apply_to_selected_file(stage::stage_file, "Staged");
+    }
+
+    /// Unstage every change to the selected file
+    pub fn unstage_file(&mut self) {
+        self.apply_to_selected_file(stage::unstage_file, "Unstaged");
+    }
+
+    fn apply_to_selected_file(
+        &mut self,
+        op: fn(&Path, &Path) -> crate::git::error::Result<()>,
+        done: &str,
+    ) {
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        match op(&self.repo_path, &path) {
+            Ok(()) => {
+                self.error_message = None;
+                self.success_message = Some(format!("{done} {}", path.display()));
+                self.refresh_keeping_selection();
+            }
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// Open the commit dialog, unless nothing is staged
+    pub fn open_commit(&mut self) {
+        match compute_changed_files_in(&self.repo_path, &self.base_branch, DiffMode::Staged) {
+            Ok(staged) if staged.is_empty() => {
+                self.error_message = Some("Nothing staged to commit".to_string());
+            }
+            Ok(_) => {
+                self.error_message = None;
+                self.commit = Some(CommitState::default());
+            }
+            Err(e) => self.error_message = Some(format!("Failed to read index: {e}")),
+        }
+    }
+
+    /// Commit the index with the dialog's message in the background, so a
+    /// slow commit hook doesn't freeze the UI; `tick` picks up the result.
+    pub(crate) fn commit_staged(&mut self) {
+        let Some(state) = &mut self.commit else {
+            return;
+        };
+        if state.committing.is_some() {
+            return;
+        }
+        let message = state.input.value().trim().to_string();
+        if message.is_empty() {
+            state.status = Some("Enter a commit message".to_string());
+            return;
+        }
+        let repo_path = self.repo_path.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(stage::commit(&repo_path, &message).map_err(|e| e.to_string()));
+        });
+        state.committing = Some(rx);
+        state.status = Some("Committing...".to_string());
+    }
+
+    /// Run `diff.commit_message_command` over the staged diff in the
+    /// background; `tick` picks up the result.
+    pub(crate) fn generate_commit_message(&mut self) {
+        let Some(state) = &mut self.commit else {
+            return;
+        };
+        if state.generating.is_some() {
+            return;
+        }
+        let Some(command) = self.commit_message_command.clone() else {
+            state.status = Some("Set diff.commit_message_command to generate messages".to_string());
+            return;
+        };
+        let mut diff = match stage::staged_patch(&self.repo_path) {
+            Ok(diff) => diff,
+            Err(e) => {
+                state.status = Some(e.to_string());
+                return;
+            }
+        };
+        if diff.len() > MAX_GENERATOR_DIFF_BYTES {
+            let mut cut = MAX_GENERATOR_DIFF_BYTES;
+            while !diff.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            diff.truncate(cut);
+            diff.push_str("\n[diff truncated]\n");
+        }
+        let input = format!("{COMMIT_MESSAGE_PROMPT}{diff}");
+        let repo_path = self.repo_path.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(run_message_command(
+                &command,
+                &repo_path,
+                &input,
+                MESSAGE_COMMAND_TIMEOUT,
+            ));
+        });
+        state.generating = Some(rx);
+        state.status = Some("Generating message...".to_string());
+    }
+
+    /// Drain a finished commit or message generation. Returns true when a
+    /// redraw is needed.
+    pub fn tick(&mut self) -> bool {
+        let Some(state) = &mut self.commit else {
+            return false;
+        };
+        if let Some(rx) = &state.committing {
+            let result = match rx.try_recv() {
+                Ok(result) => result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => return false,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Err("commit exited without a result".to_string())
+                }
+            };
+            state.committing = None;
+            match result {
+                Ok(sha) => {
+                    self.commit = None;
+                    self.error_message = None;
+                    self.success_message = Some(format!("Committed {sha}"));
+                    self.refresh_keeping_selection();
+                }
+                Err(e) => state.status = Some(e),
+            }
+            return true;
+        }
+        let Some(rx) = &state.generating else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Err("generator exited without a result".to_string())
+            }
+        };
+        state.generating = None;
+        match result {
+            Ok(message) => {
+                state.input = tui_input::Input::new(message);
+                state.status = None;
+            }
+            Err(e) => state.status = Some(format!("Generation failed: {e}")),
+        }
+        true
+    }
+
+    /// Refresh after an index or working-tree change, keeping the cursor
+    /// on the same file and hunk where they still exist.
+    fn refresh_keeping_selection(&mut self) {
+        let path = self.selected_file().map(|f| f.path.clone());
+        let hunk = self.selected_hunk;
+        if let Err(e) = self.refresh_files() {
+            self.error_message = Some(format!("Failed to refresh: {}", e));
+            return;
+        }
+        if let Some(idx) = path.and_then(|p| self.files.iter().position(|f| f.path == p)) {
+            self.selected_file = idx;
+        }
+        self.selected_hunk = hunk.min(self.hunk_count().saturating_sub(1));
+        self.scroll_to_hunk();
+    }
+}
+
+/// Run the generator with `input` on stdin and return the first non-empty
+/// line it prints, stripped of quotes and backticks models like to add.
+///
+/// Stdin is written and stdout / stderr are read on their own threads, so
+/// a generator that prints before it has read all of its input can't
+/// deadlock against a full pipe. The command runs in its own process group,
+/// which is killed if it outlives `timeout`.
+fn run_message_command(
+    command: &str,
+    cwd: &Path,
+    input: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let stdin = child.stdin.take();
+    let input = input.to_string();
+    std::thread::spawn(move || {
+        // A generator that exits without reading all of stdin is fine.
+        if let Some(mut stdin) = stdin {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+    let read_all = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = read_all(child.stdout.take().map(|p| Box::new(p) as _));
+    let stderr = read_all(child.stderr.take().map(|p| Box::new(p) as _));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                // Signal the group before reaping the leader, while its id
+                // can't have been reused.
+                let _ = nix::sys::signal::killpg(
+                    nix::unistd::Pid::from_raw(child.id() as i32),
+                    nix::sys::signal::Signal::SIGKILL,
+                );
+                let _ = child.wait();
+                return Err(format!("command timed out after {timeout:?}"));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        return Err(stderr
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .map(|l| l.trim().to_string())
+            .unwrap_or_else(|| format!("command exited with {}", status)));
+    }
+    String::from_utf8_lossy(&stdout)
+        .lines()
+        .map(|l| l.trim().trim_matches(|c| c == '`' || c == '"').trim())
+        .find(|l| !l.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "command printed no message".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_command_takes_first_nonempty_line() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = run_message_command(
+            "cat >/dev/null; printf '\\n\"Fix parser\"\\nmore\\n'",
+            dir.path(),
+            "diff",
+            MESSAGE_COMMAND_TIMEOUT,
+        );
+        assert_eq!(out, Ok("Fix parser".to_string()));
+    }
+
+    #[test]
+    fn message_command_that_prints_before_reading_does_not_deadlock() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // Both outputs outgrow a pipe buffer before stdin is read.
+        let input = "x".repeat(1 << 20);
+        let out = run_message_command(
+            "yes line | head -n 50000; yes err | head -n 50000 >&2; cat >/dev/null",
+            dir.path(),
+            &input,
+            MESSAGE_COMMAND_TIMEOUT,
+        );
+        assert_eq!(out, Ok("line".to_string()));
+    }
+
+    #[test]
+    fn message_command_is_killed_after_the_timeout() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let started = Instant::now();
+        let out = run_message_command(
+            "sleep 30 | cat",
+            dir.path(),
+            "diff",
+            Duration::from_millis(200),
+        );
+        assert_eq!(out, Err("command timed out after 200ms".to_string()));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn message_command_failure_reports_stderr() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = run_message_command(
+            "echo boom >&2; exit 3",
+            dir.path(),
+            "diff",
+            MESSAGE_COMMAND_TIMEOUT,
+        );
+        assert_eq!(out, Err("boom".to_string()));
+    }
+
+    #[test]
+    fn hunk_actions_are_gated_by_mode() {
+        let mut view = DiffView::test_default();
+        view.stage_hunk();
+        assert!(view.error_message.as_deref().unwrap().contains("unstaged"));
+        view.mode = DiffMode::Unstaged;
+        view.error_message = None;
+        view.unstage_hunk();
+        assert!(view.error_message.as_deref().unwrap().contains("staged"));
+    }
+}
@@ -1,4 +1,4 @@
-//! Single source of truth for home-view action keybindings.
+//! Single source of truth for home-view and dialog action keybindings.
 //!
 //! Every relocatable action (one that binds to a different chord in strict vs
 //! non-strict mode) is declared exactly once in [`BINDINGS`]. The dispatcher
@@ -20,11 +20,36 @@
 //! e.g. `d`=delete / `Shift+D`=diff (non-strict) become `Shift+D`=delete /
 //! `Ctrl+D`=diff (strict). `p`=projects / `Shift+P`=profiles likewise become
 //! `Shift+P`=projects / `Ctrl+P`=profiles.
+//!
+//! ## User keybindings
+//!
+//! The `[keybindings]` config section maps an action's [`palette_id`] to one
+//! or more key sequences. [`Keymap`] layers those over [`BINDINGS`]: an
+//! entry replaces the action's chords in both modes (an empty list unbinds
+//! it), and everything that reads keys goes through the keymap, so the
+//! dispatcher, palette, help overlay, and footer agree on the user's keys.
+//!
+//! A sequence is whitespace-separated chords pressed in turn (`"g w"`). A
+//! chord is a single character (`"D"` is Shift+d), a key name (`"f5"`,
+//! `"space"`, `"enter"`, ...), or either behind `ctrl+` / `alt+` / `shift+`.
+//! [`Keymap::from_config`] reports entries that don't parse, name an
+//! unknown action, or collide with another action's keys.
+//!
+//! ## Dialog keys
+//!
+//! Dialogs and overlays handle fixed Enter / Esc / arrow keys themselves.
+//! The `dialog-*` entries in [`DIALOG_BINDINGS`] add keys for those actions:
+//! [`Keymap::dialog_key`] rewrites a user chord into the fixed key before
+//! the dialog sees it. The fixed keys stay bound (text fields need the
+//! arrows), and a bare character can't be used since dialogs take typed
+//! text.
+
+use std::collections::{BTreeMap, HashMap};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::ViewMode;
-use crate::session::config::SortOrder;
+use crate::session::config::{KeySequences, SortOrder};
 use crate::tui::dialogs::PaletteGroup;
 
 /// Logical action. Each variant is dispatched by `HomeView::run_action`.
@@ -34,6 +59,7 @@ pub enum ActionId {
     Help,
     ToolPicker,
     SearchStart,
+    TranscriptSearch,
     SearchNext,
     SearchPrev,
     NewSession,
@@ -45,7 +71,13 @@ pub enum ActionId {
     Stop,
     Delete,
     Rename,
+    ToggleMark,
+    MarkMatches,
+    ClearMarks,
+    SetBase,
+    Notes,
     Diff,
+    Wall,
     Serve,
     Settings,
     Profiles,
@@ -62,20 +94,22 @@ pub enum ActionId {
     NextWaiting,
 }
 
-/// A single chord. `ctrl` requires the Control modifier; Shift is implicit in
+/// A single chord. `ctrl` / `alt` require that modifier; Shift is implicit in
 /// the uppercase letter `code` (terminals deliver `Shift+d` as `Char('D')`,
 /// and iOS Mosh delivers a bare uppercase keycode with no Shift modifier, so
 /// matching on the uppercase code rather than a Shift flag covers both).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Chord {
     pub code: KeyCode,
     pub ctrl: bool,
+    pub alt: bool,
 }
 
 const fn k(c: char) -> Chord {
     Chord {
         code: KeyCode::Char(c),
         ctrl: false,
+        alt: false,
     }
 }
 
@@ -83,6 +117,7 @@ const fn ctrl(c: char) -> Chord {
     Chord {
         code: KeyCode::Char(c),
         ctrl: true,
+        alt: false,
     }
 }
 
@@ -90,7 +125,108 @@ const fn f(n: u8) -> Chord {
     Chord {
         code: KeyCode::F(n),
         ctrl: false,
+        alt: false,
+    }
+}
+
+/// Dialog actions the user can add keys for: config id and the fixed key
+/// every dialog already handles for it.
+pub const DIALOG_BINDINGS: &[(&str, KeyCode)] = &[
+    ("dialog-confirm", KeyCode::Enter),
+    ("dialog-cancel", KeyCode::Esc),
+    ("dialog-up", KeyCode::Up),
+    ("dialog-down", KeyCode::Down),
+];
+
+/// Chords a user binding may not use: Ctrl+K opens the command palette
+/// before the registry sees the key.
+const RESERVED: &[Chord] = &[ctrl('k')];
+
+/// Key names accepted in `[keybindings]`, besides single characters and
+/// `f1`..`f12`.
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("esc", KeyCode::Esc),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+];
+
+/// Parse one chord, e.g. `"D"`, `"ctrl+w"`, `"alt+shift+x"`, `"f5"`.
+fn parse_chord(s: &str) -> Result<Chord, String> {
+    // A trailing `+` is the plus key itself (`"+"`, `"ctrl++"`).
+    let (mods, key) = match s.strip_suffix('+') {
+        Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+            (rest.strip_suffix('+').unwrap_or(rest), "+")
+        }
+        _ => match s.rsplit_once('+') {
+            Some((mods, key)) => (mods, key),
+            None => ("", s),
+        },
+    };
+    let (mut ctrl, mut alt, mut shift) = (false, false, false);
+    for m in mods.split('+').filter(|m| !m.is_empty()) {
+        match m.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => ctrl = true,
+            "alt" | "meta" | "option" => alt = true,
+            "shift" => shift = true,
+            other => return Err(format!("unknown modifier '{other}'")),
+        }
     }
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (None, _) => return Err("missing key".to_string()),
+        (Some(c), None) => KeyCode::Char(c),
+        _ => {
+            let name = key.to_ascii_lowercase();
+            if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                if !(1..=12).contains(&n) {
+                    return Err(format!("unknown key '{key}'"));
+                }
+                KeyCode::F(n)
+            } else if let Some((_, code)) = KEY_NAMES.iter().find(|(n, _)| *n == name) {
+                *code
+            } else {
+                return Err(format!("unknown key '{key}'"));
+            }
+        }
+    };
+    let code = match code {
+        // Terminals report Ctrl+letter as the lowercase letter whether or
+        // not Shift is held, so a Ctrl chord always matches lowercase.
+        KeyCode::Char(c) if ctrl && shift => {
+            return Err(format!("ctrl+shift+{c} can't be told apart from ctrl+{c}"))
+        }
+        KeyCode::Char(c) if ctrl => KeyCode::Char(c.to_ascii_lowercase()),
+        KeyCode::Char(c) if shift && c.is_ascii_alphabetic() => {
+            KeyCode::Char(c.to_ascii_uppercase())
+        }
+        _ if shift => return Err("shift only combines with a letter".to_string()),
+        code => code,
+    };
+    Ok(Chord { code, ctrl, alt })
+}
+
+/// Parse a whitespace-separated key sequence, e.g. `"g w"`.
+fn parse_sequence(s: &str) -> Result<Vec<Chord>, String> {
+    let chords = s
+        .split_whitespace()
+        .map(parse_chord)
+        .collect::<Result<Vec<_>, _>>()?;
+    if chords.is_empty() {
+        return Err("empty key sequence".to_string());
+    }
+    Ok(chords)
 }
 
 /// Contextual guard: a binding only resolves when its context holds.
@@ -141,11 +277,13 @@ pub struct Ctx {
 }
 
 fn chord_matches(c: &Chord, key: &KeyEvent) -> bool {
-    // Match the Ctrl modifier exactly: a non-ctrl chord must NOT fire when
-    // Ctrl is held. Otherwise `k('q')` would also match Ctrl+Q (reserved for
-    // exiting live-send mode, #1569) and `k('d')` would match Ctrl+D, letting
-    // a modified chord trigger a bare-letter action.
-    key.code == c.code && key.modifiers.contains(KeyModifiers::CONTROL) == c.ctrl
+    // Match the Ctrl and Alt modifiers exactly: a non-ctrl chord must NOT fire
+    // when Ctrl is held. Otherwise `k('q')` would also match Ctrl+Q (reserved
+    // for exiting live-send mode, #1569) and `k('d')` would match Ctrl+D,
+    // letting a modified chord trigger a bare-letter action.
+    key.code == c.code
+        && key.modifiers.contains(KeyModifiers::CONTROL) == c.ctrl
+        && key.modifiers.contains(KeyModifiers::ALT) == c.alt
 }
 
 fn context_holds(context: Context, ctx: &Ctx) -> bool {
@@ -157,36 +295,358 @@ fn context_holds(context: Context, ctx: &Ctx) -> bool {
     }
 }
 
-/// Resolve a key event to an action, honoring strict mode and context guards.
-/// Returns the first matching binding in table order; context-guarded entries
-/// are listed before the unguarded entries that share their chord.
-pub fn resolve(key: &KeyEvent, strict: bool, ctx: &Ctx) -> Option<ActionId> {
-    for b in BINDINGS {
-        let chords = if strict { b.strict } else { b.non_strict };
-        if context_holds(b.context, ctx) && chords.iter().any(|c| chord_matches(c, key)) {
-            return Some(b.id);
+/// What a key press resolves to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Action(ActionId),
+    /// The keys so far start a longer sequence; wait for the next one.
+    Pending,
+    Unbound,
+}
+
+/// The effective bindings: [`BINDINGS`] with the user's `[keybindings]`
+/// overrides applied. `Default` is the built-in table.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    overrides: HashMap<ActionId, Vec<Vec<Chord>>>,
+    /// User chords for [`DIALOG_BINDINGS`], paired with the fixed key each
+    /// one stands for.
+    dialog: Vec<(Chord, KeyCode)>,
+}
+
+impl Keymap {
+    /// Build the keymap from the `[keybindings]` section. The returned lines
+    /// describe every entry that was skipped (unknown action, unparsable or
+    /// reserved key) and every conflict between the resulting bindings.
+    /// Conflicting bindings are kept; [`Keymap::resolve`] picks the first in
+    /// table order, as it does for the built-ins.
+    pub fn from_config(config: &BTreeMap<String, KeySequences>) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let mut warnings = Vec::new();
+        for (name, KeySequences(specs)) in config {
+            if let Some((_, fixed)) = DIALOG_BINDINGS.iter().find(|(n, _)| n == name) {
+                keymap.add_dialog_keys(name, *fixed, specs, &mut warnings);
+                continue;
+            }
+            let Some(id) = BINDINGS
+                .iter()
+                .map(|b| b.id)
+                .find(|id| palette_id(*id) == name)
+            else {
+                warnings.push(format!("keybindings.{name}: unknown action"));
+                continue;
+            };
+            let mut sequences = Vec::new();
+            for spec in specs {
+                match parse_sequence(spec) {
+                    Ok(seq) if RESERVED.contains(&seq[0]) => warnings.push(format!(
+                        "keybindings.{name}: '{spec}' is reserved ({} opens the command palette)",
+                        format_chord(&seq[0])
+                    )),
+                    Ok(seq) => sequences.push(seq),
+                    Err(e) => warnings.push(format!("keybindings.{name}: '{spec}': {e}")),
+                }
+            }
+            // An entry whose keys all failed keeps the built-in binding
+            // rather than silently unbinding the action.
+            if specs.is_empty() || !sequences.is_empty() {
+                keymap.overrides.insert(id, sequences);
+            }
+        }
+        let conflicts = keymap.conflicts(false);
+        let strict_only: Vec<String> = keymap
+            .conflicts(true)
+            .into_iter()
+            .filter(|c| !conflicts.contains(c))
+            .map(|c| format!("{c} (strict hotkeys)"))
+            .collect();
+        warnings.extend(conflicts);
+        warnings.extend(strict_only);
+        warnings.extend(keymap.dialog_conflicts());
+        (keymap, warnings)
+    }
+
+    /// Parse a `dialog-*` entry. Each key must be a single chord that can't
+    /// be typed into a text field.
+    fn add_dialog_keys(
+        &mut self,
+        name: &str,
+        fixed: KeyCode,
+        specs: &[String],
+        warnings: &mut Vec<String>,
+    ) {
+        if specs.is_empty() {
+            warnings.push(format!(
+                "keybindings.{name}: dialog keys can't be unbound ({} always works)",
+                format_chord(&Chord {
+                    code: fixed,
+                    ctrl: false,
+                    alt: false
+                })
+            ));
+        }
+        for spec in specs {
+            let chord = match parse_sequence(spec) {
+                Ok(seq) if seq.len() > 1 => {
+                    warnings.push(format!(
+                        "keybindings.{name}: '{spec}': dialog keys take a single chord"
+                    ));
+                    continue;
+                }
+                Ok(seq) => seq[0],
+                Err(e) => {
+                    warnings.push(format!("keybindings.{name}: '{spec}': {e}"));
+                    continue;
+                }
+            };
+            if RESERVED.contains(&chord) {
+                warnings.push(format!(
+                    "keybindings.{name}: '{spec}' is reserved ({} opens the command palette)",
+                    format_chord(&chord)
+                ));
+            } else if matches!(chord.code, KeyCode::Char(_)) && !chord.ctrl && !chord.alt {
+                warnings.push(format!(
+                    "keybindings.{name}: '{spec}' would be typed into text fields; \
+                     use a ctrl+ / alt+ chord or a key name"
+                ));
+            } else {
+                self.dialog.push((chord, fixed));
+            }
         }
     }
-    None
+
+    /// Dialog chords that stand for two different fixed keys, including a
+    /// user chord that is another action's fixed key (`esc` for confirm).
+    fn dialog_conflicts(&self) -> Vec<String> {
+        let name = |fixed: KeyCode| {
+            DIALOG_BINDINGS
+                .iter()
+                .find(|(_, k)| *k == fixed)
+                .map_or("", |(n, _)| n)
+        };
+        let builtin = DIALOG_BINDINGS.iter().map(|(_, fixed)| {
+            let chord = Chord {
+                code: *fixed,
+                ctrl: false,
+                alt: false,
+            };
+            (chord, *fixed)
+        });
+        let bound: Vec<(Chord, KeyCode)> = builtin.chain(self.dialog.iter().copied()).collect();
+        let mut out = Vec::new();
+        for (i, (a_chord, a_fixed)) in bound.iter().enumerate() {
+            for (b_chord, b_fixed) in &bound[i + 1..] {
+                if a_chord != b_chord || a_fixed == b_fixed {
+                    continue;
+                }
+                let msg = format!(
+                    "keybindings: '{}' is bound to both {} and {}",
+                    format_chord(a_chord),
+                    name(*a_fixed),
+                    name(*b_fixed)
+                );
+                if !out.contains(&msg) {
+                    out.push(msg);
+                }
+            }
+        }
+        out
+    }
+
+    /// The key a dialog should handle for `key`: the fixed key of the
+    /// `dialog-*` action it's bound to, or `key` itself. Conflicting chords
+    /// resolve to the first entry, in [`DIALOG_BINDINGS`] order.
+    pub fn dialog_key(&self, key: KeyEvent) -> KeyEvent {
+        DIALOG_BINDINGS
+            .iter()
+            .flat_map(|(_, fixed)| self.dialog.iter().filter(move |(_, f)| f == fixed))
+            .find(|(chord, _)| chord_matches(chord, &key))
+            .map_or(key, |(_, fixed)| KeyEvent::new(*fixed, KeyModifiers::NONE))
+    }
+
+    /// Pairs of bindings where one key sequence equals or starts another, in
+    /// the given mode. Pairs of actions that already share a key in the
+    /// built-in table (search-next vs new-session on `n`, ...) overlap by
+    /// design and are left out.
+    fn conflicts(&self, strict: bool) -> Vec<String> {
+        let bound: Vec<(&Binding, &[Chord])> = BINDINGS
+            .iter()
+            .flat_map(|b| {
+                self.sequences(b, strict)
+                    .into_iter()
+                    .map(move |seq| (b, seq))
+            })
+            .collect();
+        let mut out = Vec::new();
+        for (i, (a_binding, a_seq)) in bound.iter().enumerate() {
+            for (b_binding, b_seq) in &bound[i + 1..] {
+                if a_binding.id == b_binding.id || share_builtin_key(a_binding, b_binding) {
+                    continue;
+                }
+                let (a, b) = (&a_binding.id, &b_binding.id);
+                let (short, long) = if a_seq.len() <= b_seq.len() {
+                    ((a, a_seq), (b, b_seq))
+                } else {
+                    ((b, b_seq), (a, a_seq))
+                };
+                if !long.1.starts_with(short.1) {
+                    continue;
+                }
+                let msg = if short.1.len() == long.1.len() {
+                    format!(
+                        "keybindings: '{}' is bound to both {} and {}",
+                        format_sequence(short.1),
+                        palette_id(*a),
+                        palette_id(*b)
+                    )
+                } else {
+                    format!(
+                        "keybindings: '{}' ({}) shadows '{}' ({})",
+                        format_sequence(short.1),
+                        palette_id(*short.0),
+                        format_sequence(long.1),
+                        palette_id(*long.0)
+                    )
+                };
+                if !out.contains(&msg) {
+                    out.push(msg);
+                }
+            }
+        }
+        out
+    }
+
+    /// A binding's key sequences in the given mode.
+    fn sequences(&self, b: &Binding, strict: bool) -> Vec<&[Chord]> {
+        match self.overrides.get(&b.id) {
+            Some(seqs) => seqs.iter().map(Vec::as_slice).collect(),
+            None => {
+                let chords = if strict { b.strict } else { b.non_strict };
+                chords.iter().map(std::slice::from_ref).collect()
+            }
+        }
+    }
+
+    /// Resolve `key`, pressed after the keys in `pending`, honoring strict
+    /// mode and context guards. An exact match wins over a longer sequence;
+    /// among exact matches the first binding in table order wins, and
+    /// context-guarded entries are listed before the unguarded entries that
+    /// share their chord.
+    pub fn resolve(
+        &self,
+        pending: &[KeyEvent],
+        key: &KeyEvent,
+        strict: bool,
+        ctx: &Ctx,
+    ) -> Resolution {
+        let mut partial = false;
+        for b in BINDINGS {
+            if !context_holds(b.context, ctx) {
+                continue;
+            }
+            for seq in self.sequences(b, strict) {
+                let Some((next, rest)) = seq.get(pending.len()..).and_then(<[Chord]>::split_first)
+                else {
+                    continue;
+                };
+                if !chord_matches(next, key)
+                    || !pending.iter().zip(seq).all(|(k, c)| chord_matches(c, k))
+                {
+                    continue;
+                }
+                if rest.is_empty() {
+                    return Resolution::Action(b.id);
+                }
+                partial = true;
+            }
+        }
+        if partial {
+            Resolution::Pending
+        } else {
+            Resolution::Unbound
+        }
+    }
+
+    /// Human-readable label for an action's primary key sequence in the given
+    /// mode, e.g. `"D"`, `"Ctrl+D"`, `"F5"`, `"g w"`. Returns `""` if the
+    /// action has no binding in the requested mode (e.g. `NextWaiting` in
+    /// strict).
+    pub fn label(&self, id: ActionId, strict: bool) -> String {
+        let Some(b) = BINDINGS.iter().find(|b| b.id == id) else {
+            return String::new();
+        };
+        self.sequences(b, strict)
+            .first()
+            .map(|seq| format_sequence(seq))
+            .unwrap_or_default()
+    }
+
+    /// Whether `key` on its own triggers `id` in the given mode. For views
+    /// that read their own keys but honor an action's binding (the wall
+    /// closes on the wall key); a multi-key sequence never matches.
+    pub fn matches(&self, id: ActionId, key: &KeyEvent, strict: bool) -> bool {
+        BINDINGS.iter().filter(|b| b.id == id).any(|b| {
+            self.sequences(b, strict)
+                .iter()
+                .any(|seq| matches!(seq, [chord] if chord_matches(chord, key)))
+        })
+    }
+
+    /// [`Keymap::label`] in the footer's compact form (`^D` for `Ctrl+D`).
+    pub fn short_label(&self, id: ActionId, strict: bool) -> String {
+        self.label(id, strict).replace("Ctrl+", "^")
+    }
 }
 
-/// Human-readable label for a binding's primary chord in the given mode, e.g.
-/// `"D"`, `"Ctrl+D"`, `"F5"`. Returns `""` if the action has no binding in the
-/// requested mode (e.g. `NextWaiting` in strict).
-pub fn label(id: ActionId, strict: bool) -> String {
-    let Some(b) = BINDINGS.iter().find(|b| b.id == id) else {
-        return String::new();
-    };
-    let chords = if strict { b.strict } else { b.non_strict };
-    chords.first().map(format_chord).unwrap_or_default()
+fn share_builtin_key(a: &Binding, b: &Binding) -> bool {
+    a.non_strict.iter().any(|c| b.non_strict.contains(c))
+        || a.strict.iter().any(|c| b.strict.contains(c))
+}
+
+/// Label for keys typed so far in an unfinished sequence.
+pub fn format_pending(pending: &[KeyEvent]) -> String {
+    let chords: Vec<Chord> = pending
+        .iter()
+        .map(|key| Chord {
+            code: key.code,
+            ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+            alt: key.modifiers.contains(KeyModifiers::ALT),
+        })
+        .collect();
+    format_sequence(&chords)
+}
+
+fn format_sequence(seq: &[Chord]) -> String {
+    seq.iter().map(format_chord).collect::<Vec<_>>().join(" ")
 }
 
 fn format_chord(c: &Chord) -> String {
-    match c.code {
-        KeyCode::Char(ch) if c.ctrl => format!("Ctrl+{}", ch.to_ascii_uppercase()),
+    let key = match c.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(ch) if c.ctrl => ch.to_ascii_uppercase().to_string(),
         KeyCode::Char(ch) => ch.to_string(),
         KeyCode::F(n) => format!("F{n}"),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Up => "\u{2191}".to_string(),
+        KeyCode::Down => "\u{2193}".to_string(),
+        KeyCode::Left => "\u{2190}".to_string(),
+        KeyCode::Right => "\u{2192}".to_string(),
         _ => String::new(),
+    };
+    match (c.ctrl, c.alt) {
+        (true, true) => format!("Ctrl+Alt+{key}"),
+        (true, false) => format!("Ctrl+{key}"),
+        (false, true) => format!("Alt+{key}"),
+        (false, false) => key,
     }
 }
 
@@ -305,6 +765,24 @@ pub static BINDINGS: &[Binding] = &[
         }),
         palette: None,
     },
+    // Ctrl+F in both modes: it never collides with a bare letter, so the
+    // strict relocation rule doesn't apply.
+    Binding {
+        id: ActionId::TranscriptSearch,
+        non_strict: &[ctrl('f')],
+        strict: &[ctrl('f')],
+        context: Context::Always,
+        help: Some(HelpMeta {
+            section: HelpSection::Other,
+            desc: "Search transcripts",
+        }),
+        palette: Some(PaletteMeta {
+            title: "Search session transcripts",
+            keywords: &["find", "grep", "history", "conversation", "full-text"],
+            group: PaletteGroup::Views,
+            serve_only: false,
+        }),
+    },
     Binding {
         id: ActionId::NewSession,
         non_strict: &[k('n')],
@@ -451,6 +929,83 @@ pub static BINDINGS: &[Binding] = &[
             serve_only: false,
         }),
     },
+    // Multi-select. `v` follows the bare->Shift relocation rule; `*` is a
+    // symbol, so it stays put like `?` and `/`.
+    Binding {
+        id: ActionId::ToggleMark,
+        non_strict: &[k('v')],
+        strict: &[k('V')],
+        context: Context::Always,
+        help: Some(HelpMeta {
+            section: HelpSection::Actions,
+            desc: "Mark session/group for bulk actions",
+        }),
+        palette: Some(PaletteMeta {
+            title: "Mark or unmark for bulk actions",
+            keywords: &["select", "multi", "bulk", "mark"],
+            group: PaletteGroup::Actions,
+            serve_only: false,
+        }),
+    },
+    Binding {
+        id: ActionId::MarkMatches,
+        non_strict: &[k('*')],
+        strict: &[k('*')],
+        context: Context::Always,
+        help: Some(HelpMeta {
+            section: HelpSection::Actions,
+            desc: "Mark search matches (or all)",
+        }),
+        palette: Some(PaletteMeta {
+            title: "Mark search matches (or all sessions)",
+            keywords: &["select", "multi", "bulk", "all"],
+            group: PaletteGroup::Actions,
+            serve_only: false,
+        }),
+    },
+    // Palette-only; Esc also clears marks in the list.
+    Binding {
+        id: ActionId::ClearMarks,
+        non_strict: &[],
+        strict: &[],
+        context: Context::Always,
+        help: None,
+        palette: Some(PaletteMeta {
+            title: "Clear marks",
+            keywords: &["unmark", "deselect", "bulk"],
+            group: PaletteGroup::Actions,
+            serve_only: false,
+        }),
+    },
+    Binding {
+        id: ActionId::SetBase,
+        non_strict: &[],
+        strict: &[],
+        context: Context::Always,
+        help: None,
+        palette: Some(PaletteMeta {
+            title: "Change diff base branch",
+            keywords: &["base", "branch", "ref", "diff", "compare"],
+            group: PaletteGroup::Actions,
+            serve_only: false,
+        }),
+    },
+    Binding {
+        id: ActionId::Notes,
+        non_strict: &[k('a')],
+        strict: &[k('A')],
+        context: Context::Always,
+        help: Some(HelpMeta {
+            section: HelpSection::Actions,
+            desc: "Edit session notes",
+        }),
+        palette: Some(PaletteMeta {
+            title: "Edit session notes",
+            keywords: &["notes", "scratchpad", "annotate", "memo", "todo"],
+            group: PaletteGroup::Actions,
+            serve_only: false,
+        }),
+    },
     Binding {
         id: ActionId::Diff,
         non_strict: &[k('D')],
@@ -467,6 +1022,24 @@ pub static BINDINGS: &[Binding] = &[
             serve_only: false,
         }),
     },
+    // `w` (next waiting) has no strict chord, so Shift+w is free in both
+    // modes and the wall keeps it.
+    Binding {
+        id: ActionId::Wall,
+        non_strict: &[k('W')],
+        strict: &[k('W')],
+        context: Context::Always,
+        help: Some(HelpMeta {
+            section: HelpSection::Views,
+            desc: "Wall: watch marked/matched sessions",
+        }),
+        palette: Some(PaletteMeta {
+            title: "Open wall of live panes",
+            keywords: &["watch", "grid", "tile", "split", "multi"],
+            group: PaletteGroup::Views,
+            serve_only: false,
+        }),
+    },
     Binding {
         id: ActionId::Serve,
         non_strict: &[k('R')],
@@ -656,7 +1229,13 @@ pub fn palette_id(id: ActionId) -> &'static str {
         ActionId::Stop => "stop",
         ActionId::Delete => "delete",
         ActionId::Rename => "rename",
+        ActionId::ToggleMark => "mark",
+        ActionId::MarkMatches => "mark-matches",
+        ActionId::ClearMarks => "clear-marks",
+        ActionId::SetBase => "set-base",
+        ActionId::Notes => "notes",
         ActionId::Diff => "diff",
+        ActionId::Wall => "wall",
         ActionId::Serve => "serve",
         ActionId::Settings => "settings",
         ActionId::Profiles => "profiles",
@@ -673,6 +1252,7 @@ pub fn palette_id(id: ActionId) -> &'static str {
         ActionId::Quit => "quit",
         ActionId::ToolPicker => "tool-picker",
         ActionId::SearchStart => "search",
+        ActionId::TranscriptSearch => "search-transcripts",
         ActionId::SearchNext => "search-next",
         ActionId::SearchPrev => "search-prev",
         ActionId::Update => "update",
@@ -700,6 +1280,29 @@ mod tests {
         KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
     }
 
+    /// Single-key resolution against the built-in table.
+    fn resolve(key: &KeyEvent, strict: bool, ctx: &Ctx) -> Option<ActionId> {
+        match Keymap::default().resolve(&[], key, strict, ctx) {
+            Resolution::Action(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    fn label(id: ActionId, strict: bool) -> String {
+        Keymap::default().label(id, strict)
+    }
+
+    fn keymap(entries: &[(&str, &[&str])]) -> (Keymap, Vec<String>) {
+        let config = entries
+            .iter()
+            .map(|(name, specs)| {
+                let specs = specs.iter().map(|s| s.to_string()).collect();
+                (name.to_string(), KeySequences(specs))
+            })
+            .collect();
+        Keymap::from_config(&config)
+    }
+
     #[test]
     fn non_strict_resolution() {
         let c = ctx();
@@ -716,6 +1319,9 @@ mod tests {
             ('P', ActionId::Profiles),
             ('o', ActionId::SortPicker),
             ('g', ActionId::GroupBy),
+            ('v', ActionId::ToggleMark),
+            ('*', ActionId::MarkMatches),
+            ('W', ActionId::Wall),
             ('q', ActionId::Quit),
         ];
         for (ch, want) in cases {
@@ -739,6 +1345,9 @@ mod tests {
             ('N', ActionId::NewSession),
             ('P', ActionId::Projects),
             ('O', ActionId::SortPicker),
+            ('V', ActionId::ToggleMark),
+            ('*', ActionId::MarkMatches),
+            ('W', ActionId::Wall),
         ];
         for (ch, want) in shifted {
             assert_eq!(resolve(&key(ch), true, &c), Some(want), "strict '{ch}'");
@@ -765,7 +1374,7 @@ mod tests {
         // They fall through to the dispatcher's typing-guard, not an action.
         let c = ctx();
         for ch in [
-            'd', 'r', 't', 'n', 'p', 's', 'x', 'm', 'e', 'i', 'z', 'g', 'o',
+            'd', 'r', 't', 'n', 'p', 's', 'x', 'm', 'e', 'i', 'z', 'g', 'o', 'v',
         ] {
             assert_eq!(resolve(&key(ch), true, &c), None, "strict bare '{ch}'");
         }
@@ -832,4 +1441,156 @@ mod tests {
         // NextWaiting has no strict binding.
         assert_eq!(label(ActionId::NextWaiting, true), "");
     }
+
+    #[test]
+    fn parse_chord_accepts_names_modifiers_and_plus() {
+        assert_eq!(parse_chord("D"), Ok(k('D')));
+        assert_eq!(parse_chord("shift+d"), Ok(k('D')));
+        assert_eq!(parse_chord("Ctrl+W"), Ok(ctrl('w')));
+        assert_eq!(parse_chord("f5"), Ok(f(5)));
+        assert_eq!(parse_chord("space"), Ok(k(' ')));
+        assert_eq!(parse_chord("+"), Ok(k('+')));
+        assert_eq!(
+            parse_chord("alt++"),
+            Ok(Chord {
+                code: KeyCode::Char('+'),
+                ctrl: false,
+                alt: true,
+            })
+        );
+        assert!(parse_chord("ctrl+").is_err());
+        assert!(parse_chord("hyper+x").is_err());
+        assert!(parse_chord("ctrl+shift+x").is_err());
+        assert!(parse_chord("shift+5").is_err());
+        assert!(parse_chord("f13").is_err());
+    }
+
+    #[test]
+    fn overrides_replace_the_builtin_keys_in_both_modes() {
+        let (map, warnings) = keymap(&[("next-waiting", &["ctrl+w"]), ("diff", &[])]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let c = ctx();
+        for strict in [false, true] {
+            assert_eq!(
+                map.resolve(&[], &ctrl_key('w'), strict, &c),
+                Resolution::Action(ActionId::NextWaiting)
+            );
+            assert_eq!(map.label(ActionId::NextWaiting, strict), "Ctrl+W");
+            assert_eq!(map.label(ActionId::Diff, strict), "");
+        }
+        assert_eq!(map.resolve(&[], &key('w'), false, &c), Resolution::Unbound);
+        assert_eq!(map.resolve(&[], &key('D'), false, &c), Resolution::Unbound);
+        // Untouched actions keep their built-in keys.
+        assert_eq!(
+            map.resolve(&[], &key('d'), false, &c),
+            Resolution::Action(ActionId::Delete)
+        );
+    }
+
+    #[test]
+    fn multi_key_sequences_wait_for_the_next_key() {
+        let (map, warnings) = keymap(&[("next-waiting", &["space w"])]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let c = ctx();
+        let space = key(' ');
+        assert_eq!(map.resolve(&[], &space, false, &c), Resolution::Pending);
+        assert_eq!(
+            map.resolve(&[space], &key('w'), false, &c),
+            Resolution::Action(ActionId::NextWaiting)
+        );
+        assert_eq!(
+            map.resolve(&[space], &key('d'), false, &c),
+            Resolution::Unbound
+        );
+        assert_eq!(map.label(ActionId::NextWaiting, false), "Space w");
+        assert_eq!(format_pending(&[space]), "Space");
+    }
+
+    #[test]
+    fn modifiers_must_match_exactly() {
+        let (map, _) = keymap(&[("next-waiting", &["alt+w"])]);
+        let c = ctx();
+        let alt_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::ALT);
+        assert_eq!(
+            map.resolve(&[], &alt_w, false, &c),
+            Resolution::Action(ActionId::NextWaiting)
+        );
+        let alt_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::ALT);
+        assert_eq!(map.resolve(&[], &alt_d, false, &c), Resolution::Unbound);
+    }
+
+    #[test]
+    fn from_config_reports_bad_entries_and_keeps_the_default() {
+        let (map, warnings) = keymap(&[
+            ("launch-rockets", &["x"]),
+            ("stop", &["hyper+x"]),
+            ("help", &["ctrl+k"]),
+        ]);
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+        assert!(warnings.iter().any(|w| w.contains("launch-rockets")));
+        assert!(warnings.iter().any(|w| w.contains("unknown modifier")));
+        assert!(warnings.iter().any(|w| w.contains("reserved")));
+        assert_eq!(map.label(ActionId::Stop, false), "x");
+        assert_eq!(map.label(ActionId::Help, false), "?");
+    }
+
+    #[test]
+    fn from_config_reports_conflicts_and_shadowed_sequences() {
+        let (_, warnings) = keymap(&[("stop", &["d"])]);
+        assert_eq!(
+            warnings,
+            vec!["keybindings: 'd' is bound to both stop and delete"]
+        );
+
+        // `g` (group-by) fires before `g w` can complete.
+        let (_, warnings) = keymap(&[("next-waiting", &["g w"])]);
+        assert_eq!(
+            warnings,
+            vec!["keybindings: 'g' (pick-group-by) shadows 'g w' (next-waiting)"]
+        );
+
+        // Strict mode binds group-by to Ctrl+G, so only non-strict collides;
+        // a collision only under strict hotkeys is labelled as such.
+        let (_, warnings) = keymap(&[("stop", &["D"])]);
+        assert_eq!(
+            warnings,
+            vec![
+                "keybindings: 'D' is bound to both stop and diff",
+                "keybindings: 'D' is bound to both stop and delete (strict hotkeys)",
+            ]
+        );
+
+        // Re-listing a built-in key, or the built-in search/new overlap on
+        // `n`, is not a conflict.
+        let (_, warnings) = keymap(&[("new-session", &["n", "ctrl+alt+n"])]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn dialog_keys_map_onto_the_fixed_keys() {
+        let (map, warnings) = keymap(&[
+            ("dialog-down", &["ctrl+j", "j"]),
+            ("dialog-up", &["ctrl+p", "g g"]),
+            ("dialog-confirm", &["ctrl+j", "esc"]),
+            ("dialog-cancel", &[]),
+        ]);
+        assert_eq!(
+            warnings,
+            vec![
+                "keybindings.dialog-cancel: dialog keys can't be unbound (Esc always works)",
+                "keybindings.dialog-down: 'j' would be typed into text fields; \
+                 use a ctrl+ / alt+ chord or a key name",
+                "keybindings.dialog-up: 'g g': dialog keys take a single chord",
+                "keybindings: 'Esc' is bound to both dialog-cancel and dialog-confirm",
+                "keybindings: 'Ctrl+J' is bound to both dialog-confirm and dialog-down",
+            ]
+        );
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        // A conflicting chord resolves to the earlier dialog action.
+        assert_eq!(map.dialog_key(ctrl('j')).code, KeyCode::Enter);
+        assert_eq!(map.dialog_key(ctrl('p')).code, KeyCode::Up);
+        assert_eq!(map.dialog_key(ctrl('p')).modifiers, KeyModifiers::NONE);
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(map.dialog_key(j), j);
+    }
 }
@@ -14,10 +14,10 @@ use crate::tui::app::Action;
 use crate::tui::dialogs::ServeAction;
 use crate::tui::dialogs::{
     builtin_commands, CommandPaletteDialog, ConfirmDialog, ContextMenuAction, ContextMenuDialog,
-    DeleteDialogConfig, DialogResult, GroupDeleteOptionsDialog, HookTrustAction,
+    DeleteDialogConfig, DialogResult, GroupDeleteOptionsDialog, GroupPick, HookTrustAction,
     HooksInstallDialog, InfoDialog, IntroOutcome, NewSessionData, NewSessionDialog, NoAgentsAction,
     PaletteAction, PaletteCommand, PaletteGroup, ProfilePickerAction, ProjectsDialog, RenameDialog,
-    RenameMode, RestartDialog, SendMessageDialog, UnifiedDeleteDialog,
+    RenameMode, RestartDialog, SendMessageDialog, TranscriptSearchDialog, UnifiedDeleteDialog,
 };
 use crate::tui::diff::{DiffAction, DiffView};
 use crate::tui::responsive;
@@ -621,6 +621,10 @@ impl HomeView {
                 None
             }
             "stop_session" => self.pending_stop_session.take().map(Action::StopSession),
+            "stop_sessions" => {
+                let ids = self.take_stoppable_marked();
+                (!ids.is_empty()).then_some(Action::StopSessions(ids))
+            }
             "force_remove_session" => {
                 if let Some(session_id) = self.pending_force_remove_session.take() {
                     if let Err(e) = self.force_remove_session(&session_id) {
@@ -674,7 +678,9 @@ impl HomeView {
                     }
                     DialogResult::Submit(options) => {
                         self.unified_delete_dialog = None;
-                        if let Err(e) = self.delete_selected(&options) {
+                        if !self.marked_sessions.is_empty() {
+                            self.delete_marked(&options);
+                        } else if let Err(e) = self.delete_selected(&options) {
                             tracing::error!(target: "tui.input", "Failed to delete session: {}", e);
                         }
                     }
@@ -796,6 +802,8 @@ impl HomeView {
                     if let Err(e) = self.snooze_session_for(&id, minutes) {
                         tracing::error!("snooze_session_for failed: {}", e);
                     }
+                } else if let Err(e) = self.snooze_marked(minutes) {
+                    tracing::error!("snooze_marked failed: {}", e);
                 }
             }
             return true;
@@ -886,11 +894,9 @@ impl HomeView {
                 DialogResult::Cancel => {
                     self.group_picker_dialog = None;
                 }
-                DialogResult::Submit(mode) => {
+                DialogResult::Submit(pick) => {
                     self.group_picker_dialog = None;
-                    if mode != self.group_by {
-                        self.apply_group_by(mode);
-                    }
+                    self.apply_group_pick(pick);
                 }
             }
             return true;
@@ -1031,6 +1037,18 @@ impl HomeView {
             return None;
         }
 
+        // Dialogs and overlays match their fixed Enter / Esc / arrow keys;
+        // the user's `dialog-*` keys are rewritten into those here. The sort
+        // and group pickers aren't part of `has_non_live_send_overlay`.
+        let key = if self.has_non_live_send_overlay()
+            || self.sort_picker_dialog.is_some()
+            || self.group_picker_dialog.is_some()
+        {
+            self.keymap.dialog_key(key)
+        } else {
+            key
+        };
+
         // Handle unsaved changes confirmation for settings (shown over settings view)
         if self.settings_close_confirm {
             if let Some(dialog) = &mut self.confirm_dialog {
@@ -1120,9 +1138,17 @@ impl HomeView {
                 DiffAction::EditFile(path) => {
                     return Some(Action::EditFile(path));
                 }
+                DiffAction::SendReview(session_id) => {
+                    return Some(Action::SendReview(session_id));
+                }
             }
         }
 
+        // Handle wall view (full-screen takeover)
+        if self.wall.is_some() {
+            return self.handle_wall_key(key);
+        }
+
         // Handle serve view (full-screen takeover)
         #[cfg(feature = "serve")]
         if let Some(ref mut serve) = self.serve_view {
@@ -1279,6 +1305,8 @@ impl HomeView {
                         if let Err(e) = self.snooze_session_for(&id, minutes) {
                             tracing::error!("snooze_session_for failed: {}", e);
                         }
+                    } else if let Err(e) = self.snooze_marked(minutes) {
+                        tracing::error!("snooze_marked failed: {}", e);
                     }
                 }
             }
@@ -1464,7 +1492,9 @@ impl HomeView {
                 }
                 DialogResult::Submit(options) => {
                     self.unified_delete_dialog = None;
-                    if let Err(e) = self.delete_selected(&options) {
+                    if !self.marked_sessions.is_empty() {
+                        self.delete_marked(&options);
+                    } else if let Err(e) = self.delete_selected(&options) {
                         tracing::error!(target: "tui.input", "Failed to delete session: {}", e);
                     }
                 }
@@ -1504,6 +1534,17 @@ impl HomeView {
                     self.rename_dialog = None;
                     match mode {
                         RenameMode::Session => {
+                            // Tags first: a profile move reloads the session
+                            // from its new profile.
+                            if let (Some(edit), Some(id)) =
+                                (&data.tags, self.selected_session.clone())
+                            {
+                                if let Err(e) = self.apply_user_action(&id, |inst| {
+                                    edit.apply(&mut inst.tags);
+                                }) {
+                                    tracing::error!(target: "tui.input", "Failed to tag session: {}", e);
+                                }
+                            }
                             if let Err(e) = self.rename_selected(
                                 &data.title,
                                 data.group.as_deref(),
@@ -1520,6 +1561,18 @@ impl HomeView {
                                 tracing::error!(target: "tui.input", "Failed to rename group: {}", e);
                             }
                         }
+                        RenameMode::Sessions => {
+                            if let Some(edit) = &data.tags {
+                                if let Err(e) = self.tag_marked(edit) {
+                                    tracing::error!(target: "tui.input", "Failed to tag sessions: {}", e);
+                                }
+                            }
+                            if let Err(e) =
+                                self.move_marked(data.group.as_deref(), data.profile.as_deref())
+                            {
+                                tracing::error!(target: "tui.input", "Failed to move sessions: {}", e);
+                            }
+                        }
                     }
                 }
             }
@@ -1536,7 +1589,15 @@ impl HomeView {
                     self.restart_dialog = None;
                     let profile = data.profile.as_deref();
                     let tool = data.tool.as_deref();
-                    if let Err(e) = self.restart_selected_session(profile, tool) {
+                    if !self.marked_sessions.is_empty() {
+                        let failures = self.restart_marked(profile, tool);
+                        if !failures.is_empty() {
+                            self.info_dialog = Some(InfoDialog::new(
+                                "Restart Failed",
+                                &format!("Could not restart:\n{}", failures.join("\n")),
+                            ));
+                        }
+                    } else if let Err(e) = self.restart_selected_session(profile, tool) {
                         // Surface the restart error to the user via the
                         // InfoDialog rather than only the debug log; the
                         // user explicitly initiated this action and needs
@@ -1568,11 +1629,23 @@ impl HomeView {
                 DialogResult::Cancel => {
                     self.group_picker_dialog = None;
                 }
-                DialogResult::Submit(mode) => {
+                DialogResult::Submit(pick) => {
                     self.group_picker_dialog = None;
-                    if mode != self.group_by {
-                        self.apply_group_by(mode);
-                    }
+                    self.apply_group_pick(pick);
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.transcript_search_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.transcript_search_dialog = None;
+                }
+                DialogResult::Submit(hit) => {
+                    self.transcript_search_dialog = None;
+                    self.go_to_search_hit(&hit);
                 }
             }
             return None;
@@ -1686,6 +1759,41 @@ impl HomeView {
                         // with no feedback.
                         return Some(Action::SendMessage(session_id, message));
                     }
+                    let ids = self.take_marked_ids();
+                    if !ids.is_empty() {
+                        return Some(Action::BroadcastMessage(ids, message));
+                    }
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.set_base_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.set_base_dialog = None;
+                }
+                DialogResult::Submit(base) => {
+                    self.set_base_dialog = None;
+                    self.apply_base(base);
+                }
+            }
+            return None;
+        }
+
+        if let Some(dialog) = &mut self.notes_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.notes_dialog = None;
+                    self.pending_notes_session = None;
+                }
+                DialogResult::Submit(text) => {
+                    self.notes_dialog = None;
+                    if let Some(id) = self.pending_notes_session.take() {
+                        self.save_notes(&id, &text);
+                    }
                 }
             }
             return None;
@@ -1727,6 +1835,24 @@ impl HomeView {
                     self.search_matches.clear();
                     self.search_match_index = 0;
                 }
+                // The mark-matches key marks what the query matched and
+                // closes the search like Enter, which clears the matches
+                // it would otherwise act on. It can't be typed into the
+                // query.
+                _ if self
+                    .keymap
+                    .matches(ActionId::MarkMatches, &key, self.strict_hotkeys) =>
+                {
+                    if self.search_query.value().is_empty() {
+                        self.mark_matches();
+                    } else {
+                        self.mark_search_matches();
+                    }
+                    self.search_active = false;
+                    self.search_query = Input::default();
+                    self.search_matches.clear();
+                    self.search_match_index = 0;
+                }
                 _ => {
                     self.search_query
                         .handle_event(&crossterm::event::Event::Key(key));
@@ -1757,8 +1883,10 @@ impl HomeView {
     ///
     /// Extracted from `handle_key` so the command palette can route through the
     /// same code path. Relocatable action keys are resolved through the shared
-    /// [`bindings`](super::bindings) registry, so the dispatcher, palette, and
-    /// help overlay can't drift on which chord (or mode) an action binds to.
+    /// [`bindings`](super::bindings) registry (via the user's keymap), so the
+    /// dispatcher, palette, and help overlay can't drift on which chord (or
+    /// mode) an action binds to. Keys that start a multi-key sequence are held
+    /// in `pending_keys` until the sequence completes or breaks.
     /// Pure navigation/structural keys, which never relocate between modes,
     /// stay as explicit arms below and are tried after the registry. The
     /// strict-mode typing-guard is the final fallback.
@@ -1779,6 +1907,12 @@ impl HomeView {
             return None;
         }
 
+        // Esc abandons a half-typed key sequence before anything else sees it.
+        if key.code == KeyCode::Esc && !self.pending_keys.is_empty() {
+            self.pending_keys.clear();
+            return None;
+        }
+
         // Context-dependent Esc handling (not a relocatable action).
         match key.code {
             KeyCode::Esc if !self.search_matches.is_empty() => {
@@ -1787,6 +1921,10 @@ impl HomeView {
                 self.search_query = Input::default();
                 return None;
             }
+            KeyCode::Esc if !self.marked_sessions.is_empty() => {
+                self.clear_marks();
+                return None;
+            }
             KeyCode::Esc if matches!(self.view_mode, ViewMode::Tool(_)) => {
                 self.view_mode = ViewMode::Agent;
                 return None;
@@ -1800,8 +1938,21 @@ impl HomeView {
             sort_order: self.sort_order,
             has_search: !self.search_matches.is_empty(),
         };
-        if let Some(id) = bindings::resolve(&key, self.strict_hotkeys, &ctx) {
-            return self.run_action(id, update_info);
+        let pending = std::mem::take(&mut self.pending_keys);
+        match self
+            .keymap
+            .resolve(&pending, &key, self.strict_hotkeys, &ctx)
+        {
+            bindings::Resolution::Action(id) => return self.run_action(id, update_info),
+            bindings::Resolution::Pending => {
+                self.pending_keys = pending;
+                self.pending_keys.push(key);
+                return None;
+            }
+            // A key that doesn't continue the sequence drops it, and is
+            // swallowed rather than run as a fresh key.
+            bindings::Resolution::Unbound if !pending.is_empty() => return None,
+            bindings::Resolution::Unbound => {}
         }
 
         // Navigation / structural keys: identical in both modes, never relocate.
@@ -1941,6 +2092,9 @@ impl HomeView {
             ActionId::NewSession => self.open_new_session_dialog(),
             ActionId::NewFromSelection => self.open_new_from_selection(),
             ActionId::NewFromProject => self.open_project_session_picker(),
+            ActionId::TranscriptSearch => {
+                self.transcript_search_dialog = Some(TranscriptSearchDialog::new());
+            }
             ActionId::AttachTerminal => return self.attach_terminal_for_selected(),
             ActionId::ToggleView => {
                 self.view_mode = match self.view_mode {
@@ -1952,7 +2106,13 @@ impl HomeView {
             ActionId::Stop => self.stop_selected(),
             ActionId::Delete => self.open_delete_for_selected(),
             ActionId::Rename => self.open_rename_for_selected(),
+            ActionId::ToggleMark => self.toggle_mark_at_cursor(),
+            ActionId::MarkMatches => self.mark_matches(),
+            ActionId::ClearMarks => self.clear_marks(),
+            ActionId::SetBase => self.open_set_base(),
+            ActionId::Notes => self.open_notes_for_selected(),
             ActionId::Diff => self.open_diff_for_selected(),
+            ActionId::Wall => self.open_wall(),
             ActionId::Serve => self.open_serve(),
             ActionId::Settings => self.open_settings(),
             ActionId::Profiles => self.show_profile_picker(),
@@ -1962,6 +2122,11 @@ impl HomeView {
             }
             ActionId::Restart => self.open_restart_dialog(),
             ActionId::Update => return self.run_update(update_info),
+            ActionId::ToggleArchive if !self.marked_sessions.is_empty() => {
+                if let Err(e) = self.toggle_archive_marked() {
+                    tracing::error!("toggle_archive_marked failed: {}", e);
+                }
+            }
             ActionId::ToggleArchive => {
                 if let Err(e) = self.toggle_archive_at_cursor() {
                     tracing::error!("toggle_archive_at_cursor failed: {}", e);
@@ -1972,6 +2137,11 @@ impl HomeView {
                     tracing::error!("toggle_favorite_at_cursor failed: {}", e);
                 }
             }
+            ActionId::ToggleSnooze if !self.marked_sessions.is_empty() => {
+                if let Err(e) = self.toggle_snooze_marked() {
+                    tracing::error!("toggle_snooze_marked failed: {}", e);
+                }
+            }
             ActionId::ToggleSnooze => {
                 if let Err(e) = self.toggle_snooze_at_cursor() {
                     tracing::error!("toggle_snooze_at_cursor failed: {}", e);
@@ -2062,7 +2232,20 @@ impl HomeView {
         None
     }
 
+    /// An action's key for dialog text (`'g'`, `Ctrl+G`), or the command
+    /// palette when the user unbound it.
+    pub(super) fn key_hint(&self, id: ActionId) -> String {
+        match self.keymap.label(id, self.strict_hotkeys) {
+            label if label.is_empty() => "Ctrl+K".to_string(),
+            label if label.chars().count() == 1 => format!("'{label}'"),
+            label => label,
+        }
+    }
+
     fn stop_selected(&mut self) {
+        if !self.marked_sessions.is_empty() {
+            return self.stop_marked();
+        }
         if let Some(session_id) = &self.selected_session {
             if let Some(inst) = self.get_instance(session_id) {
                 if matches!(
@@ -2213,7 +2396,8 @@ impl HomeView {
 
     fn open_command_palette(&mut self) {
         let serve_enabled = cfg!(feature = "serve");
-        let mut entries: Vec<PaletteCommand> = builtin_commands(serve_enabled, self.strict_hotkeys);
+        let mut entries: Vec<PaletteCommand> =
+            builtin_commands(serve_enabled, &self.keymap, self.strict_hotkeys);
 
         // Quit lives in the registry but is excluded from `builtin_commands`
         // (no palette metadata) so it can sit in the Settings group at the end;
@@ -2223,7 +2407,7 @@ impl HomeView {
             title: "Quit Agent of Empires".to_string(),
             group: PaletteGroup::Settings,
             keywords: vec!["exit", "close"],
-            hotkey: bindings::label(ActionId::Quit, self.strict_hotkeys),
+            hotkey: self.keymap.label(ActionId::Quit, self.strict_hotkeys),
             payload: PaletteAction::Invoke(ActionId::Quit),
         });
 
@@ -2599,6 +2783,7 @@ impl HomeView {
             }
             if self.selected_session != prev_session {
                 self.preview_scroll_offset = 0;
+                self.load_selected_details();
             }
         }
     }
@@ -2643,6 +2828,47 @@ impl HomeView {
         }
     }
 
+    /// Apply a group-picker row: a mode regroups the list and leaves any
+    /// saved view; a view narrows the list under the current grouping.
+    fn apply_group_pick(&mut self, pick: GroupPick) {
+        match pick {
+            GroupPick::Mode(mode) => {
+                if self.active_view.is_some() {
+                    self.apply_view(None);
+                }
+                if mode != self.group_by {
+                    self.apply_group_by(mode);
+                }
+            }
+            GroupPick::View(name) => {
+                if self.active_view.as_deref() != Some(name.as_str()) {
+                    self.apply_view(Some(name));
+                }
+            }
+        }
+    }
+
+    /// Switch the saved view narrowing the list and remember it across runs.
+    pub(super) fn apply_view(&mut self, name: Option<String>) {
+        self.set_active_view(name);
+        if self.search_active && !self.search_query.value().is_empty() {
+            self.update_search();
+        } else {
+            self.reseat_cursor_after_rebuild();
+        }
+        match load_config().map(|c| c.unwrap_or_default()) {
+            Ok(mut config) => {
+                config.app_state.active_view = self.active_view.clone();
+                if let Err(e) = save_config(&config) {
+                    tracing::warn!(target: "tui.input", "Failed to save active view: {}", e);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(target: "tui.input", "Failed to load config for view save: {}", e);
+            }
+        }
+    }
+
     fn apply_group_by(&mut self, new_mode: GroupByMode) {
         self.group_by = new_mode;
         self.flat_items = self.build_flat_items();
@@ -2660,6 +2886,21 @@ impl HomeView {
         }
     }
 
+    /// Project and tag groups come from the sessions themselves, so they
+    /// can't be renamed or deleted.
+    fn show_derived_groups_info(&mut self) {
+        let label = self.group_by.label();
+        let hint = format!(
+            "{} groups are automatic. Press {} and pick Manual to manage groups.",
+            label,
+            self.key_hint(ActionId::GroupBy)
+        );
+        self.info_dialog = Some(InfoDialog::new(
+            &format!("Cannot Modify {label} Groups"),
+            &hint,
+        ));
+    }
+
     fn toggle_group_collapsed(&mut self, path: &str) {
         // The synthetic Archived section is not a member of any
         // GroupTree; its collapsed state lives on HomeView and persists
@@ -2669,7 +2910,7 @@ impl HomeView {
             self.toggle_archived_section();
             return;
         }
-        if self.group_by == GroupByMode::Project {
+        if self.group_by != GroupByMode::Manual {
             let collapsed = self
                 .project_group_collapsed
                 .get(path)
@@ -3003,6 +3244,9 @@ impl HomeView {
     /// Shared by the `'r'` / `'R'` key handlers and the right-click
     /// context menu so all three entry points stay byte-identical.
     pub(super) fn open_rename_for_selected(&mut self) {
+        if !self.marked_sessions.is_empty() {
+            return self.open_move_for_marked();
+        }
         if let Some(id) = &self.selected_session {
             if let Some(inst) = self.get_instance(id) {
                 if matches!(inst.status, Status::Deleting | Status::Creating) {
@@ -3016,22 +3260,20 @@ impl HomeView {
                 let profiles = list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
                 let existing_groups: Vec<String> =
                     self.all_groups().iter().map(|g| g.path.clone()).collect();
-                self.rename_dialog = Some(RenameDialog::new(
-                    &inst.title,
-                    &inst.group_path,
-                    &current_profile,
-                    profiles,
-                    existing_groups,
-                ));
+                self.rename_dialog = Some(
+                    RenameDialog::new(
+                        &inst.title,
+                        &inst.group_path,
+                        &current_profile,
+                        profiles,
+                        existing_groups,
+                    )
+                    .with_tags(&inst.tags),
+                );
             }
         } else if let Some(group_path) = &self.selected_group {
-            if self.group_by == GroupByMode::Project {
-                let hint = if self.strict_hotkeys {
-                    "Project groups are automatic. Press Ctrl+G and pick Manual to manage groups."
-                } else {
-                    "Project groups are automatic. Press 'g' and pick Manual to manage groups."
-                };
-                self.info_dialog = Some(InfoDialog::new("Cannot Modify Project Groups", hint));
+            if self.group_by != GroupByMode::Manual {
+                self.show_derived_groups_info();
                 return;
             }
             let group_path = group_path.clone();
@@ -3068,14 +3310,16 @@ impl HomeView {
     pub(super) fn open_delete_for_selected(&mut self) {
         // Deletion only allowed in Agent View.
         if self.view_mode == ViewMode::Terminal {
-            let hint = if self.strict_hotkeys {
-                "Terminals cannot be deleted directly. Switch to Agent View (press Shift+T) and delete the agent session instead."
-            } else {
-                "Terminals cannot be deleted directly. Switch to Agent View (press 't') and delete the agent session instead."
-            };
-            self.info_dialog = Some(InfoDialog::new("Cannot Delete Terminal", hint));
+            let hint = format!(
+                "Terminals cannot be deleted directly. Switch to Agent View (press {}) and delete the agent session instead.",
+                self.key_hint(ActionId::ToggleView)
+            );
+            self.info_dialog = Some(InfoDialog::new("Cannot Delete Terminal", &hint));
             return;
         }
+        if !self.marked_sessions.is_empty() {
+            return self.open_delete_for_marked();
+        }
         if let Some(session_id) = &self.selected_session {
             if let Some(inst) = self.get_instance(session_id) {
                 if inst.status == Status::Creating {
@@ -3123,13 +3367,8 @@ impl HomeView {
                 ));
             }
         } else if let Some(group_path) = &self.selected_group {
-            if self.group_by == GroupByMode::Project {
-                let hint = if self.strict_hotkeys {
-                    "Project groups are automatic. Press Ctrl+G and pick Manual to manage groups."
-                } else {
-                    "Project groups are automatic. Press 'g' and pick Manual to manage groups."
-                };
-                self.info_dialog = Some(InfoDialog::new("Cannot Modify Project Groups", hint));
+            if self.group_by != GroupByMode::Manual {
+                self.show_derived_groups_info();
                 return;
             }
             let prefix = format!("{}/", group_path);
@@ -3393,6 +3632,10 @@ impl HomeView {
             self.stamp_last_accessed(&state.session_id);
             return;
         }
+        if let Some(ref mut diff) = self.diff_view {
+            diff.handle_paste(text);
+            return;
+        }
         if let Some(ref mut dialog) = self.rename_dialog {
             dialog.handle_paste(text);
             return;
@@ -3401,6 +3644,14 @@ impl HomeView {
             dialog.handle_paste(text);
             return;
         }
+        if let Some(ref mut dialog) = self.set_base_dialog {
+            dialog.handle_paste(text);
+            return;
+        }
+        if let Some(ref mut dialog) = self.notes_dialog {
+            dialog.handle_paste(text);
+            return;
+        }
         if let Some(ref mut dialog) = self.new_dialog {
             dialog.handle_paste(text);
             return;
@@ -3446,6 +3697,9 @@ impl HomeView {
             self.show_no_agents();
             return;
         }
+        if !self.marked_sessions.is_empty() {
+            return self.open_restart_for_marked();
+        }
         let Some(id) = self.selected_session.clone() else {
             return;
         };
@@ -3676,6 +3930,9 @@ impl HomeView {
     /// terminal pane (host or container) rather than the agent, so 'm'
     /// composes a command for the same shell the user is previewing.
     fn open_send_message_dialog(&mut self) {
+        if !self.marked_sessions.is_empty() {
+            return self.open_send_message_for_marked();
+        }
         let Some((id, title, target)) = self.resolve_send_target() else {
             return;
         };
@@ -3778,50 +4035,7 @@ impl HomeView {
 
     /// Re-score matches after a reload without moving the cursor.
     pub(super) fn refresh_search_matches(&mut self) {
-        let query = self.search_query.value();
-        if query.is_empty() {
-            self.search_matches.clear();
-            self.search_match_index = 0;
-            return;
-        }
-
-        use nucleo_matcher::pattern::{Atom, AtomKind, CaseMatching, Normalization};
-        use nucleo_matcher::{Config, Matcher, Utf32Str};
-
-        let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
-        let atom = Atom::new(
-            query,
-            CaseMatching::Ignore,
-            Normalization::Smart,
-            AtomKind::Fuzzy,
-            false,
-        );
-
-        let mut scored: Vec<(usize, u16)> = Vec::new();
-        let mut buf = Vec::new();
-
-        for (idx, item) in self.flat_items.iter().enumerate() {
-            let haystack = match item {
-                Item::Session { id, .. } => {
-                    if let Some(inst) = self.get_instance(id) {
-                        format!("{} {}", inst.title, inst.project_path)
-                    } else {
-                        continue;
-                    }
-                }
-                Item::Group { name, path, .. } => {
-                    format!("{} {}", name, path)
-                }
-            };
-
-            let haystack_utf32 = Utf32Str::new(&haystack, &mut buf);
-            if let Some(score) = atom.score(haystack_utf32, &mut matcher) {
-                scored.push((idx, score));
-            }
-        }
-
-        scored.sort_by_key(|a| std::cmp::Reverse(a.1));
-        self.search_matches = scored.into_iter().map(|(idx, _)| idx).collect();
+        self.search_matches = self.compute_search_matches();
         // Clamp match_index in case matches shrank
         if self.search_matches.is_empty() {
             self.search_match_index = 0;
@@ -3831,12 +4045,48 @@ impl HomeView {
     }
 
     pub(super) fn update_search(&mut self) {
-        self.search_matches.clear();
         self.search_match_index = 0;
+        self.search_matches = self.compute_search_matches();
 
-        let query = self.search_query.value();
+        if let Some(&best) = self.search_matches.first() {
+            self.cursor = best;
+            self.update_selected();
+        }
+    }
+
+    /// Indices into `flat_items` matching the search query. A query using
+    /// filter syntax (`status:waiting age>1h`) selects the sessions it
+    /// matches in list order; plain text is fuzzy-matched against titles,
+    /// paths, and group names, best first.
+    fn compute_search_matches(&mut self) -> Vec<usize> {
+        self.search_filter_error = None;
+        let query = self.search_query.value().to_string();
         if query.is_empty() {
-            return;
+            return Vec::new();
+        }
+
+        if crate::session::filter::is_structured(&query) {
+            let filter =
+                match crate::session::filter::Filter::parse_with_views(&query, &self.saved_views) {
+                    Ok(filter) => filter,
+                    Err(e) => {
+                        self.search_filter_error = Some(e.to_string());
+                        return Vec::new();
+                    }
+                };
+            let now = chrono::Utc::now();
+            return self
+                .flat_items
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, item)| match item {
+                    Item::Session { id, .. } => self
+                        .get_instance(id)
+                        .filter(|inst| filter.matches_at(inst, now))
+                        .map(|_| idx),
+                    Item::Group { .. } => None,
+                })
+                .collect();
         }
 
         use nucleo_matcher::pattern::{Atom, AtomKind, CaseMatching, Normalization};
@@ -3844,7 +4094,7 @@ impl HomeView {
 
         let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
         let atom = Atom::new(
-            query,
+            &query,
             CaseMatching::Ignore,
             Normalization::Smart,
             AtomKind::Fuzzy,
@@ -3875,12 +4125,7 @@ impl HomeView {
         }
 
         scored.sort_by_key(|a| std::cmp::Reverse(a.1));
-        self.search_matches = scored.into_iter().map(|(idx, _)| idx).collect();
-
-        if let Some(&best) = self.search_matches.first() {
-            self.cursor = best;
-            self.update_selected();
-        }
+        scored.into_iter().map(|(idx, _)| idx).collect()
     }
 
     /// Continue session creation after agent hooks acknowledgment.
@@ -0,0 +1,501 @@
+//! Multi-select: marking sessions and running an action across all of them.
+//!
+//! Marks live in `HomeView::marked_sessions`. While any session is marked,
+//! stop, restart, archive, snooze, delete, move (rename), send-message, and
+//! set-base open a bulk variant of their dialog that targets every marked
+//! session instead of the cursor row. A bulk action consumes the marks, so a
+//! later keypress can't silently hit the same set again; Esc (or the
+//! `clear-marks` palette command) drops them without acting.
+//!
+//! Each bulk action keeps the single-session gating: rows that are Creating
+//! or Deleting are skipped, and per-session cleanup options only apply to
+//! the sessions that have the resource.
+
+use super::HomeView;
+use crate::session::tags::TagEdit;
+use crate::session::{list_profiles, Item, Status};
+use crate::tui::dialogs::{
+    ConfirmDialog, DeleteDialogConfig, DeleteOptions, InfoDialog, RenameDialog, RestartDialog,
+    SendMessageDialog, SetBaseDialog, SnoozeDurationDialog, UnifiedDeleteDialog,
+};
+
+impl HomeView {
+    /// Marked session ids in list order, skipping rows mid-create or
+    /// mid-delete.
+    pub(super) fn marked_ids(&self) -> Vec<String> {
+        self.instances
+            .iter()
+            .filter(|i| self.marked_sessions.contains(&i.id))
+            .filter(|i| !matches!(i.status, Status::Creating | Status::Deleting))
+            .map(|i| i.id.clone())
+            .collect()
+    }
+
+    /// Marked ids for a bulk action that is about to run; clears the marks.
+    pub(super) fn take_marked_ids(&mut self) -> Vec<String> {
+        let ids = self.marked_ids();
+        self.marked_sessions.clear();
+        ids
+    }
+
+    pub(super) fn clear_marks(&mut self) {
+        self.marked_sessions.clear();
+    }
+
+    /// Mark or unmark the cursor row. On a group header this marks every
+    /// session in the group, or unmarks them all when they're already
+    /// marked.
+    pub(super) fn toggle_mark_at_cursor(&mut self) {
+        let ids = match self.flat_items.get(self.cursor) {
+            Some(Item::Session { id, .. }) => vec![id.clone()],
+            Some(Item::Group { .. }) => self.group_member_ids(self.cursor),
+            None => return,
+        };
+        if ids.is_empty() {
+            return;
+        }
+        if ids.iter().all(|id| self.marked_sessions.contains(id)) {
+            for id in &ids {
+                self.marked_sessions.remove(id);
+            }
+        } else {
+            self.marked_sessions.extend(ids);
+        }
+    }
+
+    /// Mark every session row the search matched, or every visible
+    /// (non-archived) session in the active view when no search is active.
+    pub(super) fn mark_matches(&mut self) {
+        if self.search_matches.is_empty() {
+            let ids: Vec<String> = self
+                .instances
+                .iter()
+                .filter(|i| {
+                    self.active_profile
+                        .as_ref()
+                        .is_none_or(|p| *p == i.source_profile)
+                })
+                .filter(|i| !i.is_archived())
+                .filter(|i| self.in_view(i))
+                .map(|i| i.id.clone())
+                .collect();
+            self.marked_sessions.extend(ids);
+            return;
+        }
+        self.mark_search_matches();
+    }
+
+    /// Mark the session rows the search matched; group rows are skipped.
+    pub(super) fn mark_search_matches(&mut self) {
+        let ids: Vec<String> = self
+            .search_matches
+            .iter()
+            .filter_map(|&idx| match self.flat_items.get(idx) {
+                Some(Item::Session { id, .. }) => Some(id.clone()),
+                _ => None,
+            })
+            .collect();
+        self.marked_sessions.extend(ids);
+    }
+
+    /// Sessions under the group header at `idx`. An expanded group's
+    /// members are the deeper rows that follow it; a collapsed group's rows
+    /// aren't in `flat_items`, so they're matched by group path instead.
+    pub(super) fn group_member_ids(&self, idx: usize) -> Vec<String> {
+        let Some(Item::Group {
+            path,
+            depth,
+            collapsed,
+            profile,
+            ..
+        }) = self.flat_items.get(idx)
+        else {
+            return Vec::new();
+        };
+        if !collapsed {
+            return self.flat_items[idx + 1..]
+                .iter()
+                .take_while(|item| item.depth() > *depth)
+                .filter_map(|item| match item {
+                    Item::Session { id, .. } => Some(id.clone()),
+                    Item::Group { .. } => None,
+                })
+                .collect();
+        }
+        if crate::session::is_within_archived_section(path) {
+            return self
+                .instances
+                .iter()
+                .filter(|i| i.is_archived())
+                .filter(|i| self.in_view(i))
+                .map(|i| i.id.clone())
+                .collect();
+        }
+        let prefix = format!("{path}/");
+        self.instances
+            .iter()
+            .filter(|i| !i.is_archived())
+            .filter(|i| profile.as_ref().is_none_or(|p| *p == i.source_profile))
+            .filter(|i| {
+                self.active_profile
+                    .as_ref()
+                    .is_none_or(|p| *p == i.source_profile)
+            })
+            .filter(|i| {
+                self.derived_group_paths(i)
+                    .iter()
+                    .any(|group| group == path || group.starts_with(&prefix))
+            })
+            .filter(|i| self.in_view(i))
+            .map(|i| i.id.clone())
+            .collect()
+    }
+
+    pub(super) fn stop_marked(&mut self) {
+        let count = self
+            .marked_ids()
+            .iter()
+            .filter_map(|id| self.get_instance(id))
+            .filter(|i| i.status != Status::Stopped)
+            .count();
+        if count == 0 {
+            return;
+        }
+        let message = format!("Are you sure you want to stop {count} marked sessions?");
+        self.confirm_dialog = Some(ConfirmDialog::new(
+            "Stop Sessions",
+            &message,
+            "stop_sessions",
+        ));
+    }
+
+    /// Confirmed bulk stop: the marked sessions that are still running.
+    pub(super) fn take_stoppable_marked(&mut self) -> Vec<String> {
+        let ids = self.take_marked_ids();
+        ids.into_iter()
+            .filter(|id| {
+                self.get_instance(id)
+                    .is_some_and(|i| i.status != Status::Stopped)
+            })
+            .collect()
+    }
+
+    /// One delete confirmation for every marked session. The dialog shows
+    /// the union of their cleanup options; `delete_marked` applies each
+    /// option only where the session has that resource.
+    pub(super) fn open_delete_for_marked(&mut self) {
+        let ids = self.marked_ids();
+        if ids.is_empty() {
+            return;
+        }
+        let instances: Vec<_> = ids.iter().filter_map(|id| self.get_instance(id)).collect();
+        let mut branches: Vec<String> = instances
+            .iter()
+            .filter_map(|inst| delete_branch_of(inst))
+            .collect();
+        branches.sort();
+        branches.dedup();
+        let worktree_branch = match branches.len() {
+            0 => None,
+            1 => branches.pop(),
+            n => Some(format!("{n} branches")),
+        };
+        let config = DeleteDialogConfig {
+            worktree_branch,
+            has_sandbox: instances
+                .iter()
+                .any(|i| i.sandbox_info.as_ref().is_some_and(|s| s.enabled)),
+            project_path: instances.first().map(|i| i.project_path.clone()),
+            is_scratch: instances.iter().any(|i| i.scratch),
+        };
+        let profile = self.config_profile();
+        self.unified_delete_dialog =
+            Some(UnifiedDeleteDialog::new_bulk(ids.len(), config, &profile));
+    }
+
+    pub(super) fn delete_marked(&mut self, options: &DeleteOptions) {
+        for id in self.take_marked_ids() {
+            let Some(inst) = self.get_instance(&id) else {
+                continue;
+            };
+            let has_worktree = delete_branch_of(inst).is_some();
+            let per_session = DeleteOptions {
+                delete_worktree: options.delete_worktree && has_worktree,
+                force_delete: options.force_delete && has_worktree,
+                delete_branch: options.delete_branch && has_worktree,
+                delete_sandbox: options.delete_sandbox
+                    && inst.sandbox_info.as_ref().is_some_and(|s| s.enabled),
+                keep_scratch: options.keep_scratch && inst.scratch,
+            };
+            self.request_session_deletion(&id, &per_session);
+        }
+    }
+
+    /// Move dialog (group + profile) for the marked sessions. Titles are
+    /// per-session, so the bulk variant doesn't offer a rename.
+    pub(super) fn open_move_for_marked(&mut self) {
+        let ids = self.marked_ids();
+        let Some(first) = ids.first().and_then(|id| self.get_instance(id)) else {
+            return;
+        };
+        let current_profile = first.source_profile.clone();
+        let all_same_group = ids
+            .iter()
+            .filter_map(|id| self.get_instance(id))
+            .all(|i| i.group_path == first.group_path);
+        let shared_group = if all_same_group {
+            first.group_path.clone()
+        } else {
+            String::new()
+        };
+        let profiles = list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
+        let existing_groups: Vec<String> =
+            self.all_groups().iter().map(|g| g.path.clone()).collect();
+        self.rename_dialog = Some(RenameDialog::new_for_sessions(
+            ids.len(),
+            &shared_group,
+            &current_profile,
+            profiles,
+            existing_groups,
+        ));
+    }
+
+    /// Apply a tag edit to every marked session, leaving the marks in
+    /// place for the move that follows it.
+    pub(super) fn tag_marked(&mut self, edit: &TagEdit) -> anyhow::Result<()> {
+        let ids = self.marked_ids();
+        self.bulk_apply_user_action(&ids, |inst| {
+            edit.apply(&mut inst.tags);
+        })
+    }
+
+    /// Apply a bulk move. `new_group` / `new_profile` follow `RenameData`:
+    /// `None` keeps each session's current value.
+    pub(super) fn move_marked(
+        &mut self,
+        new_group: Option<&str>,
+        new_profile: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let ids = self.take_marked_ids();
+        if let Some(group) = new_group {
+            self.bulk_apply_user_action(&ids, |inst| inst.group_path = group.to_string())?;
+        }
+        if let Some(target) = new_profile {
+            if !list_profiles()?.iter().any(|p| p == target) {
+                anyhow::bail!("Profile '{}' does not exist", target);
+            }
+            for id in &ids {
+                let Some(group_path) = self.get_instance(id).map(|i| i.group_path.clone()) else {
+                    continue;
+                };
+                self.move_to_profile(id, target, group_path)?;
+            }
+        }
+        self.rebuild_group_trees();
+        if let Some(group) = new_group.filter(|g| !g.is_empty()) {
+            let profiles: Vec<String> = ids
+                .iter()
+                .filter_map(|id| self.get_instance(id))
+                .map(|i| i.source_profile.clone())
+                .collect();
+            for profile in profiles {
+                if let Some(tree) = self.group_trees.get_mut(&profile) {
+                    tree.create_group(group);
+                }
+            }
+        }
+        self.save()?;
+        self.reload()
+    }
+
+    /// Restart dialog for the marked sessions, pre-filled from the first
+    /// one. Leaving a picker untouched keeps each session's own value.
+    pub(super) fn open_restart_for_marked(&mut self) {
+        let ids = self.marked_ids();
+        let Some(first) = ids.first().and_then(|id| self.get_instance(id)) else {
+            return;
+        };
+        let current_profile = first.source_profile.clone();
+        let current_tool = first.tool.clone();
+        let profiles = list_profiles().unwrap_or_else(|_| vec![current_profile.clone()]);
+        let tools: Vec<String> = self.available_tools.available_list().to_vec();
+        self.restart_dialog = Some(RestartDialog::new(
+            &format!("{} marked sessions", ids.len()),
+            &current_profile,
+            &current_tool,
+            profiles,
+            tools,
+        ));
+    }
+
+    /// Restart every marked session; returns one line per failure.
+    pub(super) fn restart_marked(
+        &mut self,
+        new_profile: Option<&str>,
+        new_tool: Option<&str>,
+    ) -> Vec<String> {
+        let mut failures = Vec::new();
+        for id in self.take_marked_ids() {
+            if let Err(e) = self.restart_session(&id, new_profile, new_tool) {
+                let title = self.get_instance(&id).map(|i| i.title.clone());
+                failures.push(format!("{}: {e}", title.unwrap_or(id)));
+            }
+        }
+        failures
+    }
+
+    /// Archive every marked session, or unarchive them all when every one
+    /// is already archived. Mirrors `toggle_archive_at_cursor`, including
+    /// killing the pane before archiving.
+    pub(super) fn toggle_archive_marked(&mut self) -> anyhow::Result<()> {
+        let ids = self.take_marked_ids();
+        let all_archived = ids
+            .iter()
+            .filter_map(|id| self.get_instance(id))
+            .all(|i| i.is_archived());
+        if all_archived {
+            self.bulk_apply_user_action(&ids, |inst| inst.unarchive())?;
+        } else {
+            for inst in self.instances.iter().filter(|i| ids.contains(&i.id)) {
+                if inst.is_archived() {
+                    continue;
+                }
+                if let Err(e) = inst.kill() {
+                    tracing::warn!("toggle_archive_marked: kill failed (continuing): {}", e);
+                }
+            }
+            self.bulk_apply_user_action(&ids, |inst| {
+                if !inst.is_archived() {
+                    inst.archive();
+                }
+            })?;
+        }
+        self.flat_items = self.build_flat_items();
+        Ok(())
+    }
+
+    /// Snooze the marked sessions (after the duration picker), or wake them
+    /// all when every one is already snoozed.
+    pub(super) fn toggle_snooze_marked(&mut self) -> anyhow::Result<Option<String>> {
+        let ids = self.marked_ids();
+        let all_snoozed = ids
+            .iter()
+            .filter_map(|id| self.get_instance(id))
+            .all(|i| i.is_snoozed());
+        if all_snoozed {
+            let ids = self.take_marked_ids();
+            self.bulk_apply_user_action(&ids, |inst| inst.unsnooze())?;
+            self.flat_items = self.build_flat_items();
+            return Ok(Some(format!("Woke {} sessions", ids.len())));
+        }
+        self.pending_snooze_session = None;
+        self.snooze_duration_dialog = Some(SnoozeDurationDialog::new(&format!(
+            "{} marked sessions",
+            ids.len()
+        )));
+        Ok(None)
+    }
+
+    pub(super) fn snooze_marked(&mut self, minutes: u32) -> anyhow::Result<()> {
+        let ids = self.take_marked_ids();
+        self.bulk_apply_user_action(&ids, |inst| inst.snooze(minutes))?;
+        self.flat_items = self.build_flat_items();
+        if self.sort_order == crate::session::config::SortOrder::Attention {
+            self.select_top_attention(None);
+        }
+        Ok(())
+    }
+
+    /// Compose one message for every marked session's agent.
+    pub(super) fn open_send_message_for_marked(&mut self) {
+        let count = self.marked_ids().len();
+        if count == 0 {
+            return;
+        }
+        self.pending_send_session = None;
+        self.pending_send_target = super::live_send::LiveSendTarget::Agent;
+        let mut dialog = SendMessageDialog::new(&format!("{count} marked sessions"));
+        if let Some(buf) = self.pending_paste.take() {
+            if !buf.is_empty() {
+                dialog.handle_paste(&buf);
+            }
+        }
+        self.send_message_dialog = Some(dialog);
+    }
+
+    /// Open the set-base input for the marked sessions, or the selected
+    /// session when nothing is marked.
+    pub(super) fn open_set_base(&mut self) {
+        let marked = self.marked_ids();
+        let dialog = match marked.len() {
+            0 => {
+                let Some(inst) = self
+                    .selected_session
+                    .as_ref()
+                    .and_then(|id| self.get_instance(id))
+                else {
+                    self.info_dialog = Some(InfoDialog::new(
+                        "No Session Selected",
+                        "Select or mark sessions to set their diff base.",
+                    ));
+                    return;
+                };
+                SetBaseDialog::new(&inst.title, inst.base_branch_override.as_deref())
+            }
+            n => SetBaseDialog::new(&format!("{n} marked"), None),
+        };
+        self.set_base_dialog = Some(dialog);
+    }
+
+    /// Set (or, for `None`, clear) the diff base of the marked sessions or
+    /// the selected one. The ref is validated against each session's repo;
+    /// sessions where it doesn't resolve keep their old base and are listed
+    /// in an info dialog.
+    pub(super) fn apply_base(&mut self, base: Option<String>) {
+        let mut ids = self.take_marked_ids();
+        if ids.is_empty() {
+            ids.extend(self.selected_session.clone());
+        }
+        let mut failures = Vec::new();
+        if let Some(reference) = &base {
+            ids.retain(|id| {
+                let Some(inst) = self.get_instance(id) else {
+                    return false;
+                };
+                let repo = std::path::Path::new(&inst.project_path);
+                match crate::git::diff::validate_ref(repo, reference) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        failures.push(format!("{}: {e}", inst.title));
+                        false
+                    }
+                }
+            });
+        }
+        if let Err(e) = self.bulk_apply_user_action(&ids, |inst| {
+            inst.base_branch_override = base.clone();
+        }) {
+            tracing::warn!(target: "tui.home", "Failed to persist base_branch_override: {}", e);
+        }
+        if !failures.is_empty() {
+            let reference = base.unwrap_or_default();
+            self.info_dialog = Some(InfoDialog::new(
+                "Base Not Set",
+                &format!(
+                    "'{reference}' did not resolve for:\n{}",
+                    failures.join("\n")
+                ),
+            ));
+        }
+    }
+}
+
+/// The branch a delete would clean up for `inst`, matching what the
+/// single-session delete dialog offers.
+fn delete_branch_of(inst: &crate::session::Instance) -> Option<String> {
+    inst.worktree_info
+        .as_ref()
+        .filter(|wt| wt.managed_by_aoe)
+        .map(|wt| wt.branch.clone())
+        .or_else(|| inst.workspace_info.as_ref().map(|w| w.branch.clone()))
+}
@@ -3,8 +3,10 @@
 pub(crate) mod bindings;
 mod input;
 mod live_send;
+mod marks;
 mod operations;
 mod render;
+mod wall;
 
 #[cfg(test)]
 mod tests;
@@ -27,6 +29,7 @@ use crate::session::{
 };
 use crate::tmux::AvailableTools;
 
+use super::broadcast_poller::{BroadcastPoller, BroadcastRequest};
 use super::creation_poller::{CreationPoller, CreationRequest};
 use super::deletion_poller::DeletionPoller;
 #[cfg(feature = "serve")]
@@ -34,15 +37,19 @@ use super::dialogs::ServeView;
 use super::dialogs::{
     ChangelogDialog, CommandPaletteDialog, ConfirmDialog, ContextMenuDialog,
     GroupDeleteOptionsDialog, GroupPickerDialog, HookTrustDialog, HooksInstallDialog, InfoDialog,
-    IntroDialog, NewSessionData, NewSessionDialog, NoAgentsDialog, ProfilePickerDialog,
-    ProjectSessionPickerDialog, ProjectsDialog, RenameDialog, RestartDialog, SnoozeDurationDialog,
-    SortPickerDialog, UnifiedDeleteDialog, UpdateConfirmDialog,
+    IntroDialog, NewSessionData, NewSessionDialog, NoAgentsDialog, NotesDialog,
+    ProfilePickerDialog, ProjectSessionPickerDialog, ProjectsDialog, RenameDialog, RestartDialog,
+    SetBaseDialog, SnoozeDurationDialog, SortPickerDialog, TranscriptSearchDialog,
+    UnifiedDeleteDialog, UpdateConfirmDialog,
 };
 use super::diff::DiffView;
 use super::settings::SettingsView;
 use super::status_poller::{StatusPoller, StatusUpdate};
 use super::stop_poller::StopPoller;
 
+/// Tag-mode group for sessions without tags.
+const UNTAGGED_GROUP: &str = "(untagged)";
+
 /// Extract a project group name from a session instance.
 /// Uses `worktree_info.main_repo_path` for worktree sessions (so all branches of the
 /// same repo group together), otherwise uses `project_path`. Returns the last path segment.
@@ -291,6 +298,34 @@ pub(super) fn get_indent(depth: usize) -> &'static str {
     INDENTS.get(depth).copied().unwrap_or(INDENTS[9])
 }
 
+/// Prompt a cockpit session's agent through the running `aoe serve`.
+/// Blocks the UI thread like the tmux send path it stands in for.
+#[cfg(feature = "serve")]
+fn send_cockpit_prompt(session_id: &str, text: &str) -> Result<(), String> {
+    use crate::cockpit::client::{require_daemon, HttpClient, ManagerError};
+    tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            let endpoint = require_daemon().await.map_err(|e| match e {
+                // The full hint is several lines; the diff footer has one.
+                ManagerError::NoDaemonRunning(_) => {
+                    "Cockpit session: start `aoe serve` to send a review".to_string()
+                }
+                e => e.to_string(),
+            })?;
+            let client = HttpClient::new(endpoint).map_err(|e| e.to_string())?;
+            client
+                .prompt(session_id, text)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    })
+}
+
+#[cfg(not(feature = "serve"))]
+fn send_cockpit_prompt(_session_id: &str, _text: &str) -> Result<(), String> {
+    Err("Cockpit session: rebuild with --features serve to send a review".to_string())
+}
+
 pub(super) const ICON_IDLE: &str = "⠒";
 pub(super) const ICON_ERROR: &str = "✕";
 pub(super) const ICON_UNKNOWN: &str = "⠤";
@@ -298,6 +333,7 @@ pub(super) const ICON_STOPPED: &str = "⠒";
 pub(super) const ICON_DELETING: &str = "✕";
 pub(super) const ICON_COLLAPSED: &str = "▶";
 pub(super) const ICON_EXPANDED: &str = "▼";
+pub(super) const ICON_MARKED: &str = "●";
 
 /// Hook progress for a session being created in the background
 pub(super) struct CreatingHookProgress {
@@ -306,6 +342,40 @@ pub(super) struct CreatingHookProgress {
 }
 
 /// Result delivered by a startup-recovery worker back to the TUI tick.
+/// Outcome of the sends queued by `request_broadcast`, summarized into the
+/// status toast once they have all come back.
+#[derive(Debug, Default)]
+struct BroadcastProgress {
+    total: usize,
+    done: usize,
+    /// Sessions whose resume failed, so the message went to a fresh one.
+    stale: usize,
+    /// `title: error` for each session the message didn't reach.
+    failed: Vec<String>,
+}
+
+impl BroadcastProgress {
+    fn summary(&self) -> String {
+        let sent = self.total - self.failed.len();
+        let mut text = if self.failed.is_empty() {
+            format!("Sent to {sent} sessions")
+        } else {
+            format!(
+                "Sent to {sent} of {} sessions; failed: {}",
+                self.total,
+                self.failed.join("; ")
+            )
+        };
+        if self.stale > 0 {
+            text.push_str(&format!(
+                "; resume failed for {}, sent to fresh sessions (history not loaded)",
+                self.stale
+            ));
+        }
+        text
+    }
+}
+
 struct RecoveryUpdate {
     instance_id: String,
     title: String,
@@ -343,9 +413,21 @@ pub struct HomeView {
     pub(super) selected_group: Option<String>,
     /// Which profile the selected group belongs to (for scoped group operations)
     pub(super) selected_group_profile: Option<String>,
+    /// Session ids marked for a bulk action. While non-empty, stop,
+    /// restart, archive, snooze, delete, move, send-message, and set-base
+    /// apply to every marked session instead of the cursor row.
+    pub(super) marked_sessions: HashSet<String>,
     pub(super) view_mode: ViewMode,
     pub(super) sort_order: SortOrder,
     pub(super) group_by: GroupByMode,
+    /// Saved views from the `[views]` config section: name -> filter query.
+    pub(super) saved_views: std::collections::BTreeMap<String, String>,
+    /// Name of the saved view narrowing the list, if any.
+    pub(super) active_view: Option<String>,
+    /// `active_view`'s parsed query; None when no view is active.
+    pub(super) view_filter: Option<crate::session::filter::Filter>,
+    /// Parse error for a structured search query, shown in the search bar.
+    pub(super) search_filter_error: Option<String>,
     /// Per-row tag config; what to show next to each session title.
     /// Cached from resolved SessionConfig at construction + reload_settings;
     /// the render layer reads this rather than re-resolving the config on
@@ -356,7 +438,8 @@ pub struct HomeView {
     /// overlay falls back to this when no session row is selected so the
     /// render path never touches disk for the Enter/Tab labels.
     pub(super) profile_default_attach_mode: crate::session::NewSessionAttachMode,
-    /// Collapsed state for project-mode groups (persists across rebuilds)
+    /// Collapsed state for project- and tag-mode groups (persists across
+    /// rebuilds)
     pub(super) project_group_collapsed: HashMap<String, bool>,
 
     // Dialogs
@@ -395,6 +478,7 @@ pub struct HomeView {
     pub(super) group_picker_dialog: Option<GroupPickerDialog>,
     pub(super) sort_picker_dialog: Option<SortPickerDialog>,
     pub(super) project_session_picker_dialog: Option<ProjectSessionPickerDialog>,
+    pub(super) transcript_search_dialog: Option<TranscriptSearchDialog>,
     pub(super) projects_dialog: Option<ProjectsDialog>,
     pub(super) command_palette: Option<CommandPaletteDialog>,
     #[cfg(feature = "serve")]
@@ -403,6 +487,10 @@ pub struct HomeView {
     pub(super) send_message_dialog: Option<super::dialogs::SendMessageDialog>,
     /// Session to receive the message from the send dialog
     pub(super) pending_send_session: Option<String>,
+    pub(super) set_base_dialog: Option<SetBaseDialog>,
+    pub(super) notes_dialog: Option<NotesDialog>,
+    /// Session whose notes the open notes dialog edits
+    pub(super) pending_notes_session: Option<String>,
     /// Which pane the pending send-message dialog will target. Set
     /// alongside `pending_send_session` and read when the dialog
     /// submits, so 'm' in Terminal view routes to the terminal pane
@@ -477,6 +565,10 @@ pub struct HomeView {
     // Performance: background stop (docker stop can block up to ~10s)
     pub(super) stop_poller: StopPoller,
 
+    // Performance: background broadcast (each send may revive a cold pane)
+    broadcast_poller: BroadcastPoller,
+    broadcast: Option<BroadcastProgress>,
+
     // Performance: background session creation (for sandbox)
     pub(super) creation_poller: CreationPoller,
     /// Set to true if user cancelled while creation was pending
@@ -567,6 +659,12 @@ pub struct HomeView {
     // dictation / stray keystrokes triggering destructive actions).
     pub(super) strict_hotkeys: bool,
 
+    /// Action keybindings: the built-in table plus the user's
+    /// `[keybindings]`. Rebuilt on settings reload.
+    pub(super) keymap: bindings::Keymap,
+    /// Keys typed so far of an unfinished multi-key sequence.
+    pub(super) pending_keys: Vec<crossterm::event::KeyEvent>,
+
     // When true, pressing `q` to leave the home screen shows a quit
     // confirmation first (guards against accidental exits, #1569).
     pub(super) confirm_before_quit: bool,
@@ -585,6 +683,9 @@ pub struct HomeView {
     // Diff view
     pub(super) diff_view: Option<DiffView>,
 
+    /// Wall view (tiled live panes), full-screen like the diff view
+    wall: Option<wall::WallState>,
+
     // Resizable list column width (percentage-like units)
     pub(super) list_width: u16,
 
@@ -769,9 +870,17 @@ impl HomeView {
             selected_session: None,
             selected_group: None,
             selected_group_profile: None,
+            marked_sessions: HashSet::new(),
             view_mode,
             sort_order,
             group_by,
+            saved_views: user_config
+                .as_ref()
+                .map(|c| c.views.clone())
+                .unwrap_or_default(),
+            active_view: None,
+            view_filter: None,
+            search_filter_error: None,
             row_tag_mode: resolved.session.row_tag,
             profile_default_attach_mode: resolved.session.default_attach_mode,
             project_group_collapsed: HashMap::new(),
@@ -800,6 +909,7 @@ impl HomeView {
             group_picker_dialog: None,
             sort_picker_dialog: None,
             project_session_picker_dialog: None,
+            transcript_search_dialog: None,
             projects_dialog: None,
             command_palette: None,
             #[cfg(feature = "serve")]
@@ -807,6 +917,9 @@ impl HomeView {
             update_confirm_dialog: None,
             send_message_dialog: None,
             pending_send_session: None,
+            set_base_dialog: None,
+            notes_dialog: None,
+            pending_notes_session: None,
             pending_send_target: live_send::LiveSendTarget::Agent,
             pending_live_send_target: live_send::LiveSendTarget::Agent,
             live_send: None,
@@ -827,6 +940,8 @@ impl HomeView {
             pending_status_refresh: false,
             deletion_poller: DeletionPoller::new(),
             stop_poller: StopPoller::new(),
+            broadcast_poller: BroadcastPoller::new(),
+            broadcast: None,
             creation_poller: CreationPoller::new(),
             creation_cancelled: false,
             on_launch_hooks_ran: HashSet::new(),
@@ -852,12 +967,15 @@ impl HomeView {
             status_hook_config,
             status_hook_configs,
             strict_hotkeys,
+            keymap: bindings::Keymap::default(),
+            pending_keys: Vec::new(),
             confirm_before_quit,
             active_tui_count: 1,
             idle_decay_window,
             settings_view: None,
             settings_close_confirm: false,
             diff_view: None,
+            wall: None,
             list_width: user_config
                 .as_ref()
                 .and_then(|c| c.app_state.home_list_width)
@@ -896,6 +1014,12 @@ impl HomeView {
                 &hotkey_warnings.join("\n"),
             ));
         }
+        if let Some(config) = &user_config {
+            view.load_keymap(&config.keybindings);
+            if let Some(name) = &config.app_state.active_view {
+                view.set_active_view(Some(name.clone()));
+            }
+        }
 
         // Clean up orphaned Creating instances from a prior crash
         let orphan_ids: Vec<String> = view
@@ -1037,6 +1161,10 @@ impl HomeView {
                     // `#[serde(skip)]` runtime-only so disk reloads
                     // would otherwise reset it to empty.
                     inst.retroactive_capture_excludes = prev.retroactive_capture_excludes.clone();
+                    // Side-file details aren't in sessions.json; the
+                    // selected session's are re-read below.
+                    inst.notes = prev.notes.clone();
+                    inst.timeline = prev.timeline.clone();
                 }
             }
             // Rebuild this profile's tree from disk, preserving any collapsed
@@ -1073,6 +1201,11 @@ impl HomeView {
             .iter()
             .map(|i| (i.id.clone(), i.clone()))
             .collect();
+        // Drop marks on sessions that were deleted (here or by another
+        // client) so a later bulk action can't count a ghost row.
+        let instance_map = &self.instance_map;
+        self.marked_sessions
+            .retain(|id| instance_map.contains_key(id));
         // Remember what the cursor was pointing at so we can follow it
         let prev_selected_session = self.selected_session.clone();
         let prev_selected_group = self.selected_group.clone();
@@ -1114,6 +1247,7 @@ impl HomeView {
         }
 
         self.update_selected();
+        self.load_selected_details();
         Ok(())
     }
 
@@ -1327,6 +1461,70 @@ impl HomeView {
         false
     }
 
+    /// Queue `message` for each of `ids` on the broadcast worker. Results
+    /// come back through `apply_broadcast_results`.
+    pub fn request_broadcast(&mut self, ids: &[String], message: &str) {
+        let mut queued = 0;
+        for id in ids {
+            let Some(inst) = self.get_instance(id) else {
+                continue;
+            };
+            self.broadcast_poller.request_send(BroadcastRequest {
+                session_id: id.clone(),
+                instance: inst.clone(),
+                message: message.to_string(),
+            });
+            queued += 1;
+        }
+        self.broadcast.get_or_insert_with(Default::default).total += queued;
+    }
+
+    /// Apply finished broadcast sends: sync each revived instance's
+    /// post-start state and stamp it. Returns the status toast text when a
+    /// result arrived: progress while sends are outstanding, then a summary
+    /// with every failure.
+    pub fn apply_broadcast_results(&mut self) -> Option<String> {
+        let mut received = false;
+        while let Some(result) = self.broadcast_poller.try_recv_result() {
+            received = true;
+            let started = result.instance;
+            self.mutate_instance(&result.session_id, |inst| {
+                inst.status = started.status;
+                inst.last_error = started.last_error.clone();
+                inst.agent_session_id = started.agent_session_id.clone();
+                inst.last_start_time = started.last_start_time;
+                inst.retroactive_capture_excludes = started.retroactive_capture_excludes.clone();
+            });
+            let progress = self.broadcast.get_or_insert_with(Default::default);
+            progress.done += 1;
+            match result.result {
+                Ok(stale_sid) => {
+                    progress.stale += usize::from(stale_sid.is_some());
+                    self.stamp_last_accessed(&result.session_id);
+                }
+                Err(e) => progress.failed.push(format!("{}: {e}", started.title)),
+            }
+        }
+        if !received {
+            return None;
+        }
+        if let Err(e) = self.save() {
+            tracing::error!(target: "tui.home", "Failed to save after broadcast: {}", e);
+        }
+        if self.sort_order == crate::session::config::SortOrder::Attention {
+            self.select_top_attention(None);
+            self.selected_session = None;
+        }
+        let progress = self.broadcast.as_ref()?;
+        if progress.done < progress.total {
+            return Some(format!(
+                "Sending to {} sessions ({} done)...",
+                progress.total, progress.done
+            ));
+        }
+        self.broadcast.take().map(|p| p.summary())
+    }
+
     /// Apply any pending session ID updates from background pollers.
     /// Returns true if any instance was updated.
     pub fn apply_session_id_updates(&mut self) -> bool {
@@ -2119,6 +2317,23 @@ impl HomeView {
             }
         }
 
+        if let Some(dialog) = &mut self.transcript_search_dialog {
+            if dialog.tick() {
+                changed = true;
+            }
+        }
+
+        if self.wall_needs_refresh() {
+            changed = true;
+        }
+
+        // Pick up a finished commit or commit-message generation.
+        if let Some(view) = &mut self.diff_view {
+            if view.tick() {
+                changed = true;
+            }
+        }
+
         // Poll serve dialog for subprocess startup events.
         #[cfg(feature = "serve")]
         if let Some(view) = &mut self.serve_view {
@@ -2216,14 +2431,18 @@ impl HomeView {
             || self.snooze_duration_dialog.is_some()
             || self.profile_picker_dialog.is_some()
             || self.project_session_picker_dialog.is_some()
+            || self.transcript_search_dialog.is_some()
             || self.projects_dialog.is_some()
             || self.command_palette.is_some()
             || self.tool_picker_dialog.is_some()
             || self.send_message_dialog.is_some()
+            || self.set_base_dialog.is_some()
+            || self.notes_dialog.is_some()
             || self.update_confirm_dialog.is_some()
             || serve_open
             || self.settings_view.is_some()
             || self.diff_view.is_some()
+            || self.wall.is_some()
     }
 
     pub fn has_dialog(&self) -> bool {
@@ -2251,14 +2470,18 @@ impl HomeView {
             || self.snooze_duration_dialog.is_some()
             || self.profile_picker_dialog.is_some()
             || self.project_session_picker_dialog.is_some()
+            || self.transcript_search_dialog.is_some()
             || self.projects_dialog.is_some()
             || self.command_palette.is_some()
             || self.tool_picker_dialog.is_some()
             || self.send_message_dialog.is_some()
+            || self.set_base_dialog.is_some()
+            || self.notes_dialog.is_some()
             || self.update_confirm_dialog.is_some()
             || serve_open
             || self.settings_view.is_some()
             || self.diff_view.is_some()
+            || self.wall.is_some()
     }
 
     /// Whether the paste-burst detector should fire for incoming key events.
@@ -2289,6 +2512,8 @@ impl HomeView {
         self.live_send.is_some()
             || self.rename_dialog.is_some()
             || self.send_message_dialog.is_some()
+            || self.set_base_dialog.is_some()
+            || self.notes_dialog.is_some()
             || self.new_dialog.is_some()
             || self.settings_view.is_some()
     }
@@ -2399,14 +2624,46 @@ impl HomeView {
     }
 
     pub(super) fn build_flat_items(&self) -> Vec<Item> {
+        let mut items = self.build_flat_items_unfiltered();
+        // A saved view leaves groups with none of their sessions in view;
+        // drop those headers rather than list a wall of empty groups.
+        if self.view_filter.is_some() {
+            items.retain(|item| {
+                !matches!(item, Item::Group { path, session_count: 0, .. }
+                    if !crate::session::is_within_archived_section(path))
+            });
+        }
+        items
+    }
+
+    /// Whether the active saved view (if any) includes `inst`.
+    pub(super) fn in_view(&self, inst: &Instance) -> bool {
+        self.view_filter.as_ref().is_none_or(|f| f.matches(inst))
+    }
+
+    /// The active profile's sessions (or every session) that are in view.
+    fn listed_instances(&self) -> Vec<Instance> {
+        self.instances
+            .iter()
+            .filter(|i| {
+                self.active_profile
+                    .as_ref()
+                    .is_none_or(|p| i.source_profile == *p)
+            })
+            .filter(|i| self.in_view(i))
+            .cloned()
+            .collect()
+    }
+
+    fn build_flat_items_unfiltered(&self) -> Vec<Item> {
         // Project grouping is honored across every sort order. Combined with
         // Attention sort, sessions sort by tier within each project and the
         // project headers float by their top-attention member (driven by
         // sort_groups + attention_group_key in flatten_tree). Check this
         // first so Project + Attention doesn't fall through to the flat
         // Attention branch and lose the project headers.
-        if self.group_by == GroupByMode::Project {
-            return self.build_flat_items_by_project();
+        if self.group_by != GroupByMode::Manual {
+            return self.build_flat_items_derived();
         }
 
         // Manual grouping + Attention sort is the cross-cutting flat
@@ -2416,42 +2673,30 @@ impl HomeView {
         // different shape on purpose (attention triage within explicit
         // project boundaries).
         if self.sort_order == SortOrder::Attention {
-            let filtered: Vec<Instance> = if let Some(profile) = &self.active_profile {
-                self.instances
-                    .iter()
-                    .filter(|i| i.source_profile == *profile)
-                    .cloned()
-                    .collect()
-            } else {
-                self.instances.clone()
-            };
+            let filtered = self.listed_instances();
             let mut items = flatten_sessions_by_attention(&filtered);
             append_archived_section(&mut items, &filtered, self.archived_section_collapsed);
             return items;
         }
 
         let (mut items, archive_pool) = if let Some(profile) = &self.active_profile {
-            let filtered: Vec<Instance> = self
-                .instances
-                .iter()
-                .filter(|i| i.source_profile == *profile)
-                .cloned()
-                .collect();
+            let filtered = self.listed_instances();
             let items = match self.group_trees.get(profile) {
                 Some(tree) => flatten_tree(tree, &filtered, self.sort_order),
                 None => Vec::new(),
             };
             (items, filtered)
         } else if self.storages.len() <= 1 {
+            let listed = self.listed_instances();
             let items = match self.group_trees.values().next() {
-                Some(tree) => flatten_tree(tree, &self.instances, self.sort_order),
+                Some(tree) => flatten_tree(tree, &listed, self.sort_order),
                 None => Vec::new(),
             };
-            (items, self.instances.clone())
+            (items, listed)
         } else {
-            let items =
-                flatten_tree_all_profiles(&self.instances, &self.group_trees, self.sort_order);
-            (items, self.instances.clone())
+            let listed = self.listed_instances();
+            let items = flatten_tree_all_profiles(&listed, &self.group_trees, self.sort_order);
+            (items, listed)
         };
 
         // Pin the synthetic Archived section to the bottom regardless of
@@ -2461,24 +2706,35 @@ impl HomeView {
         items
     }
 
-    fn build_flat_items_by_project(&self) -> Vec<Item> {
-        // In project mode, always merge all sessions into one tree regardless of
-        // profile count. Project grouping unifies by repo across profiles.
-        let base_instances: Vec<Instance> = if let Some(profile) = &self.active_profile {
-            self.instances
+    /// Groups `inst` is listed under: its manual group, its project, or
+    /// one per tag (`UNTAGGED_GROUP` when it has none).
+    pub(super) fn derived_group_paths(&self, inst: &Instance) -> Vec<String> {
+        match self.group_by {
+            GroupByMode::Manual => vec![inst.group_path.clone()],
+            GroupByMode::Project => vec![project_group_name(inst)],
+            GroupByMode::Tag if inst.tags.is_empty() => vec![UNTAGGED_GROUP.to_string()],
+            GroupByMode::Tag => inst
+                .tags
                 .iter()
-                .filter(|i| i.source_profile == *profile)
-                .cloned()
-                .collect()
-        } else {
-            self.instances.clone()
-        };
+                .map(|t| crate::session::tags::group_path(t))
+                .collect(),
+        }
+    }
+
+    fn build_flat_items_derived(&self) -> Vec<Item> {
+        // In project and tag mode, always merge all sessions into one tree
+        // regardless of profile count. Project grouping unifies by repo
+        // across profiles. Tag grouping lists a session once per tag.
+        let base_instances = self.listed_instances();
 
         let grouped: Vec<Instance> = base_instances
-            .into_iter()
-            .map(|mut inst| {
-                inst.group_path = project_group_name(&inst);
-                inst
+            .iter()
+            .flat_map(|inst| {
+                self.derived_group_paths(inst).into_iter().map(move |path| {
+                    let mut inst = inst.clone();
+                    inst.group_path = path;
+                    inst
+                })
             })
             .collect();
 
@@ -2576,7 +2832,11 @@ impl HomeView {
 
     /// Show the group-by picker dialog seeded with the current mode.
     pub(super) fn show_group_picker(&mut self) {
-        self.group_picker_dialog = Some(GroupPickerDialog::new(self.group_by));
+        self.group_picker_dialog = Some(GroupPickerDialog::with_views(
+            self.group_by,
+            self.saved_views.keys().cloned().collect(),
+            self.active_view.clone(),
+        ));
     }
 
     /// Open the saved-project picker that starts a new session pre-filled with
@@ -2650,6 +2910,79 @@ impl HomeView {
     /// during the implicit respawn so the caller can toast the user about
     /// the lost history; `None` otherwise.
     pub fn execute_send_message(&mut self, session_id: &str, message: &str) -> Option<String> {
+        match self.deliver_message(session_id, message) {
+            Ok(stale_sid) => stale_sid,
+            Err(e) => {
+                self.info_dialog = Some(InfoDialog::new("Send Failed", &e));
+                None
+            }
+        }
+    }
+
+    /// Send the diff view's pending review comments for `session_id` as
+    /// one message, clearing them once delivered. Cockpit sessions get it
+    /// as a prompt through `aoe serve`, like the web review panel sends
+    /// it. Outcomes land in the diff view's footer, since it covers the
+    /// home screen's dialogs. Returns the stale agent session id like
+    /// `execute_send_message`.
+    pub fn execute_send_review(&mut self, session_id: &str) -> Option<String> {
+        let inst = self.get_instance(session_id)?;
+        let (profile, cockpit, multi_repo) = (
+            inst.source_profile.clone(),
+            inst.is_cockpit_mode(),
+            inst.workspace_info.is_some(),
+        );
+        let result = crate::session::reviews::comments_for(&profile, session_id)
+            .map_err(|e| e.to_string())
+            .and_then(|comments| {
+                if comments.is_empty() {
+                    return Err("No review comments to send".to_string());
+                }
+                if cockpit {
+                    let prompt = crate::session::reviews::build_cockpit_prompt(
+                        &comments, "", "", multi_repo,
+                    );
+                    send_cockpit_prompt(session_id, &prompt)?;
+                    return Ok((comments, None));
+                }
+                let prompt = crate::session::reviews::build_prompt(&comments, "", "", multi_repo);
+                let stale_sid = self.deliver_message(session_id, &prompt)?;
+                Ok((comments, stale_sid))
+            });
+        match result {
+            Ok((comments, stale_sid)) => {
+                let count = comments.len();
+                if let Err(e) =
+                    crate::session::reviews::remove_sent(&profile, session_id, &comments)
+                {
+                    tracing::warn!(target: "tui.home", "Failed to clear sent review: {e}");
+                }
+                if let Some(view) = &mut self.diff_view {
+                    view.reload_review_comments();
+                    view.success_message = Some(format!(
+                        "Sent {count} review comment{}",
+                        if count == 1 { "" } else { "s" }
+                    ));
+                }
+                stale_sid
+            }
+            Err(e) => {
+                match &mut self.diff_view {
+                    Some(view) => view.error_message = Some(format!("Send failed: {e}")),
+                    None => self.info_dialog = Some(InfoDialog::new("Send Failed", &e)),
+                }
+                None
+            }
+        }
+    }
+
+    /// `execute_send_message` without the failure dialog: revive the pane,
+    /// type `message`, and stamp the session. Errors are user-facing text.
+    fn deliver_message(
+        &mut self,
+        session_id: &str,
+        message: &str,
+    ) -> Result<Option<String>, String> {
         let target = std::mem::replace(
             &mut self.pending_send_target,
             live_send::LiveSendTarget::Agent,
@@ -2672,46 +3005,32 @@ impl HomeView {
                     })) => Some(sid),
                     Ok(_) => None,
                     Err(err) => {
-                        self.info_dialog = Some(InfoDialog::new(
-                            "Send Failed",
-                            &format!("Cannot prepare session: {}", err),
-                        ));
-                        return None;
+                        return Err(format!("Cannot prepare session: {}", err));
                     }
                 }
             }
             live_send::LiveSendTarget::Terminal => {
                 if let Err(e) = self.ensure_terminal_pane_ready(session_id, size) {
-                    self.info_dialog = Some(InfoDialog::new(
-                        "Send Failed",
-                        &format!("Cannot prepare terminal: {}", e),
-                    ));
-                    return None;
+                    return Err(format!("Cannot prepare terminal: {}", e));
                 }
                 None
             }
             live_send::LiveSendTarget::ContainerTerminal => {
                 if let Err(e) = self.ensure_container_terminal_pane_ready(session_id, size) {
-                    self.info_dialog = Some(InfoDialog::new(
-                        "Send Failed",
-                        &format!("Cannot prepare container terminal: {}", e),
-                    ));
-                    return None;
+                    return Err(format!("Cannot prepare container terminal: {}", e));
                 }
                 None
             }
         };
-        let inst = self.get_instance(session_id)?;
+        let inst = self
+            .get_instance(session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
         let tmux_session = match target {
             live_send::LiveSendTarget::Agent => {
                 match crate::tmux::Session::new(&inst.id, &inst.title) {
                     Ok(s) => s,
                     Err(e) => {
-                        self.info_dialog = Some(InfoDialog::new(
-                            "Send Failed",
-                            &format!("Failed to resolve session: {}", e),
-                        ));
-                        return None;
+                        return Err(format!("Failed to resolve session: {}", e));
                     }
                 }
             }
@@ -2730,11 +3049,7 @@ impl HomeView {
             live_send::LiveSendTarget::Terminal | live_send::LiveSendTarget::ContainerTerminal => 0,
         };
         if let Err(e) = tmux_session.send_keys_with_delay(message, delay) {
-            self.info_dialog = Some(InfoDialog::new(
-                "Send Failed",
-                &format!("Failed to send message: {}", e),
-            ));
-            return None;
+            return Err(format!("Failed to send message: {}", e));
         }
         self.stamp_last_accessed(session_id);
         if let Err(e) = self.save() {
@@ -2744,7 +3059,7 @@ impl HomeView {
             self.select_top_attention(None);
             self.selected_session = None;
         }
-        stale_sid
+        Ok(stale_sid)
     }
 
     /// Stage live-send mode against `session_id`. Mirrors
@@ -3235,6 +3550,35 @@ impl HomeView {
             .extend(descendants);
     }
 
+    /// Read the selected session's notes and status timeline from their
+    /// side files. `Storage::load` leaves both empty, so the preview loads
+    /// them for the one session it shows: on selection change and on each
+    /// reload.
+    pub(super) fn load_selected_details(&mut self) {
+        let Some(id) = self.selected_session.clone() else {
+            return;
+        };
+        let Some((profile, status)) = self
+            .get_instance(&id)
+            .map(|i| (i.source_profile.clone(), i.status))
+        else {
+            return;
+        };
+        let notes = crate::session::notes::load(&profile, &id).unwrap_or_else(|e| {
+            tracing::debug!(target: "tui.home", "failed to load notes for {id}: {e}");
+            String::new()
+        });
+        let timeline = crate::session::timeline::summary(&profile, &id, status, chrono::Utc::now())
+            .unwrap_or_else(|e| {
+                tracing::debug!(target: "tui.home", "failed to load timeline for {id}: {e}");
+                None
+            });
+        self.mutate_instance(&id, |inst| {
+            inst.notes = notes;
+            inst.timeline = timeline;
+        });
+    }
+
     /// Centralized instance mutation: applies `f` once to the `instances` vec
     /// entry, then clones the result into `instance_map`. This guarantees both
     /// collections stay in sync even for non-idempotent closures.
@@ -3282,6 +3626,16 @@ impl HomeView {
             .or_default()
             .insert(id.to_string());
 
+        if let Err(e) = crate::session::notes::move_to_profile(&old_profile, target, id) {
+            tracing::warn!(target: "tui.home", id = %id, "failed to move notes: {e}");
+        }
+        if let Err(e) = crate::session::timeline::move_to_profile(&old_profile, target, id) {
+            tracing::warn!(target: "tui.home", id = %id, "failed to move status timeline: {e}");
+        }
+        if let Err(e) = crate::session::reviews::move_to_profile(&old_profile, target, id) {
+            tracing::warn!(target: "tui.home", id = %id, "failed to move review comments: {e}");
+        }
+
         if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
             inst.group_path = new_group_path;
             inst.source_profile = target.to_string();
@@ -3538,6 +3892,22 @@ impl HomeView {
         outcome.ok_or_else(|| anyhow::anyhow!("session not found: {}", id))
     }
 
+    /// Move the cursor to a transcript search hit. Sessions outside the
+    /// current view (another profile, a collapsed group) can't be
+    /// selected, so say where the hit lives instead.
+    pub(super) fn go_to_search_hit(&mut self, hit: &crate::session::search::SearchHit) {
+        self.select_session_by_id(&hit.session_id);
+        if self.selected_session.as_deref() != Some(hit.session_id.as_str()) {
+            self.info_dialog = Some(InfoDialog::new(
+                "Session Not Visible",
+                &format!(
+                    "\"{}\" (profile {}) is not in the current view. Switch profile or expand its group to reach it.",
+                    hit.title, hit.profile
+                ),
+            ));
+        }
+    }
+
     pub fn select_session_by_id(&mut self, session_id: &str) {
         for (idx, item) in self.flat_items.iter().enumerate() {
             if let Item::Session { id, .. } = item {
@@ -3689,29 +4059,33 @@ impl HomeView {
         let Some(inst) = self.get_instance(session_id) else {
             return;
         };
-        let group_path = match self.group_by {
-            GroupByMode::Project => Some(project_group_name(inst)),
-            GroupByMode::Manual => {
-                let p = inst.group_path.clone();
-                if p.is_empty() {
-                    None
-                } else {
-                    Some(p)
-                }
-            }
-        };
+        let group_paths: Vec<String> = self
+            .derived_group_paths(inst)
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect();
         let target_profile = inst.source_profile.clone();
         self.selected_session = Some(session_id.to_string());
         self.selected_group = None;
         self.selected_group_profile = None;
-        if let Some(gpath) = group_path {
-            match self.group_by {
-                GroupByMode::Project => {
-                    self.project_group_collapsed.insert(gpath, false);
-                }
-                GroupByMode::Manual => {
-                    if let Some(tree) = self.group_trees.get_mut(&target_profile) {
-                        tree.set_collapsed(&gpath, false);
+        if !group_paths.is_empty() {
+            for gpath in group_paths {
+                match self.group_by {
+                    GroupByMode::Project | GroupByMode::Tag => {
+                        // Tag labels nest (`key/value`); open every level.
+                        let mut prefix = String::new();
+                        for segment in gpath.split('/') {
+                            if !prefix.is_empty() {
+                                prefix.push('/');
+                            }
+                            prefix.push_str(segment);
+                            self.project_group_collapsed.insert(prefix.clone(), false);
+                        }
+                    }
+                    GroupByMode::Manual => {
+                        if let Some(tree) = self.group_trees.get_mut(&target_profile) {
+                            tree.set_collapsed(&gpath, false);
+                        }
                     }
                 }
             }
@@ -3753,6 +4127,68 @@ impl HomeView {
                 &hotkey_warnings.join("\n"),
             ));
         }
+        self.load_keymap(&config.keybindings);
+        if self.saved_views != config.views {
+            self.saved_views = config.views;
+            self.set_active_view(self.active_view.clone());
+        }
+    }
+
+    /// Narrow the list to the saved view `name`, or show every session for
+    /// None. A view that no longer exists or no longer parses is dropped
+    /// with a warning. Does not persist; see `apply_view`.
+    pub(super) fn set_active_view(&mut self, name: Option<String>) {
+        self.active_view = None;
+        self.view_filter = None;
+        if let Some(name) = name {
+            match self
+                .saved_views
+                .get(&name)
+                .map(|q| crate::session::filter::Filter::parse_with_views(q, &self.saved_views))
+            {
+                Some(Ok(filter)) => {
+                    self.active_view = Some(name);
+                    self.view_filter = Some(filter);
+                }
+                Some(Err(e)) => {
+                    tracing::warn!(target: "tui.home", "Saved view '{name}' is invalid: {e}");
+                    if self.info_dialog.is_none() {
+                        self.info_dialog = Some(InfoDialog::new(
+                            "Saved view error",
+                            &format!("views.{name}: {e}"),
+                        ));
+                    }
+                }
+                None => {
+                    tracing::warn!(target: "tui.home", "Saved view '{name}' no longer exists");
+                }
+            }
+        }
+        self.flat_items = self.build_flat_items();
+        if self.cursor >= self.flat_items.len() {
+            self.cursor = self.flat_items.len().saturating_sub(1);
+        }
+        self.update_selected();
+    }
+
+    /// Rebuild the keymap from `[keybindings]`, surfacing unparsable entries
+    /// and conflicts the same way tool hotkey errors are.
+    fn load_keymap(
+        &mut self,
+        keybindings: &std::collections::BTreeMap<String, crate::session::config::KeySequences>,
+    ) {
+        let (keymap, warnings) = bindings::Keymap::from_config(keybindings);
+        self.keymap = keymap;
+        self.pending_keys.clear();
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
+        if !warnings.is_empty() && self.info_dialog.is_none() {
+            self.info_dialog = Some(InfoDialog::new(
+                "Keybinding config errors",
+                &warnings.join("\n"),
+            ));
+        }
     }
 
     fn status_hook_profile_names(
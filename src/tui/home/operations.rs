@@ -3,7 +3,9 @@
 use crate::session::builder::{self, InstanceParams};
 use crate::session::{list_profiles, GroupTree, Status, Storage};
 use crate::tui::deletion_poller::DeletionRequest;
-use crate::tui::dialogs::{DeleteOptions, GroupDeleteOptions, NewSessionData};
+use crate::tui::dialogs::{
+    DeleteOptions, GroupDeleteOptions, InfoDialog, NewSessionData, NotesDialog,
+};
 
 use super::HomeView;
 
@@ -140,10 +142,21 @@ impl HomeView {
         new_profile: Option<&str>,
         new_tool: Option<&str>,
     ) -> anyhow::Result<()> {
-        let id = match &self.selected_session {
-            Some(id) => id.clone(),
-            None => return Ok(()),
-        };
+        match self.selected_session.clone() {
+            Some(id) => self.restart_session(&id, new_profile, new_tool),
+            None => Ok(()),
+        }
+    }
+
+    /// [`Self::restart_selected_session`] for an explicit session id; the
+    /// bulk restart of marked sessions calls it once per row.
+    pub(super) fn restart_session(
+        &mut self,
+        id: &str,
+        new_profile: Option<&str>,
+        new_tool: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let id = id.to_string();
 
         // Skip transient + sunk rows. Pull the snapshot details we need on
         // the worker thread in the same borrow so we don't re-look up the
@@ -323,28 +336,31 @@ impl HomeView {
     }
 
     pub(super) fn delete_selected(&mut self, options: &DeleteOptions) -> anyhow::Result<()> {
-        if let Some(id) = &self.selected_session {
-            let id = id.clone();
-
-            self.set_instance_status(&id, Status::Deleting);
-
-            if let Some(inst) = self.get_instance(&id) {
-                let request = DeletionRequest {
-                    session_id: id.clone(),
-                    instance: inst.clone(),
-                    delete_worktree: options.delete_worktree,
-                    delete_branch: options.delete_branch,
-                    delete_sandbox: options.delete_sandbox,
-                    force_delete: options.force_delete,
-                    detach_hooks: true,
-                    keep_scratch: options.keep_scratch,
-                };
-                self.deletion_poller.request_deletion(request);
-            }
+        if let Some(id) = self.selected_session.clone() {
+            self.request_session_deletion(&id, options);
         }
         Ok(())
     }
 
+    /// Flip `id` to Deleting and hand it to the deletion poller.
+    pub(super) fn request_session_deletion(&mut self, id: &str, options: &DeleteOptions) {
+        self.set_instance_status(id, Status::Deleting);
+
+        if let Some(inst) = self.get_instance(id) {
+            let request = DeletionRequest {
+                session_id: id.to_string(),
+                instance: inst.clone(),
+                delete_worktree: options.delete_worktree,
+                delete_branch: options.delete_branch,
+                delete_sandbox: options.delete_sandbox,
+                force_delete: options.force_delete,
+                detach_hooks: true,
+                keep_scratch: options.keep_scratch,
+            };
+            self.deletion_poller.request_deletion(request);
+        }
+    }
+
     pub(super) fn delete_selected_group(&mut self) -> anyhow::Result<()> {
         if let Some(group_path) = self.selected_group.take() {
             let owning_profile = self.selected_group_profile.take();
@@ -823,6 +839,41 @@ impl HomeView {
         )))
     }
 
+    /// Open the notes editor on the cursor's session.
+    pub(super) fn open_notes_for_selected(&mut self) {
+        let Some(inst) = self
+            .selected_session
+            .as_ref()
+            .and_then(|id| self.get_instance(id))
+        else {
+            self.info_dialog = Some(InfoDialog::new(
+                "No Session Selected",
+                "Select a session to edit its notes.",
+            ));
+            return;
+        };
+        let dialog = NotesDialog::new(&inst.title, &inst.notes);
+        self.pending_notes_session = Some(inst.id.clone());
+        self.notes_dialog = Some(dialog);
+    }
+
+    /// Write `text` as `id`'s notes. Notes live outside `sessions.json`,
+    /// so only the in-memory copy needs updating afterwards.
+    pub(super) fn save_notes(&mut self, id: &str, text: &str) {
+        let Some(profile) = self.get_instance(id).map(|i| i.source_profile.clone()) else {
+            return;
+        };
+        match crate::session::notes::save(&profile, id, text) {
+            Ok(saved) => self.mutate_instance(id, |inst| inst.notes = saved),
+            Err(e) => {
+                self.info_dialog = Some(InfoDialog::new(
+                    "Notes Not Saved",
+                    &format!("Failed to save notes: {e}"),
+                ));
+            }
+        }
+    }
+
     /// Toggle the favorite flag on the cursor's session. Favorited rows
     /// pin above non-favorited peers within the same status tier in the
     /// Attention sort, and render with bold + underline plus a leading
@@ -3,15 +3,18 @@
 use chrono::{DateTime, Utc};
 use ratatui::prelude::*;
 use ratatui::widgets::*;
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use rattles::presets::prelude as spinners;
 
+use super::bindings::{self, ActionId};
 use super::{
     get_indent, live_send, HomeView, TerminalMode, ViewMode, ICON_COLLAPSED, ICON_DELETING,
-    ICON_ERROR, ICON_EXPANDED, ICON_IDLE, ICON_STOPPED, ICON_UNKNOWN,
+    ICON_ERROR, ICON_EXPANDED, ICON_IDLE, ICON_MARKED, ICON_STOPPED, ICON_UNKNOWN,
 };
 use crate::session::config::{GroupByMode, SortOrder};
+use crate::session::coordinator::ChildStatusCounts;
 use crate::session::{Item, Status};
 use crate::tui::components::preview::{self, CachedPreview};
 use crate::tui::components::{
@@ -33,8 +36,8 @@ fn session_offset(created_at: &DateTime<Utc>) -> usize {
 /// `profile` is `Some(name)` only when a real filter is active; when `None`,
 /// the `[<profile>]` segment is omitted so the default all-profiles state
 /// stays uncluttered.
-/// Group and sort state hang off the prefix as `· project` / `· <sort label>`
-/// segments, each dropped when it matches the default.
+/// Group and sort state hang off the prefix as `· project` (or `· tag`) and
+/// `· <sort label>` segments, each dropped when it matches the default.
 fn compose_list_title(
     prefix: &str,
     profile: Option<&str>,
@@ -42,8 +45,10 @@ fn compose_list_title(
     sort_order: SortOrder,
 ) -> String {
     let mut suffix = String::new();
-    if group_by == GroupByMode::Project {
-        suffix.push_str(" · project");
+    match group_by {
+        GroupByMode::Manual => {}
+        GroupByMode::Project => suffix.push_str(" · project"),
+        GroupByMode::Tag => suffix.push_str(" · tag"),
     }
     if sort_order != SortOrder::default() {
         suffix.push_str(" · ");
@@ -56,6 +61,9 @@ fn compose_list_title(
     format!(" {}{}{} ", prefix, profile_tag, suffix)
 }
 
+/// Tag chips shown on a session row before collapsing the rest into `+N`.
+const MAX_TAG_CHIPS: usize = 3;
+
 /// Extra rows captured beyond the visible window so moderate scrolls don't
 /// force a fresh capture on every wheel tick. Cache invalidation uses the same
 /// reserve to decide when the captured window can no longer cover the
@@ -456,6 +464,17 @@ impl HomeView {
             return;
         }
 
+        // Wall view takes over the whole screen
+        if self.wall.is_some() {
+            self.preview_area = Rect::default();
+            self.preview_pane_area = Rect::default();
+            self.preview_outer_area = Rect::default();
+            self.divider_col = None;
+            self.main_area_width = 0;
+            self.render_wall(frame, area, theme);
+            return;
+        }
+
         // Serve view takes over the whole screen
         #[cfg(feature = "serve")]
         if let Some(ref serve) = self.serve_view {
@@ -549,6 +568,7 @@ impl HomeView {
                 area,
                 theme,
                 self.sort_order,
+                &self.keymap,
                 self.strict_hotkeys,
                 live_on_enter,
                 &mut self.help_scroll,
@@ -593,10 +613,13 @@ impl HomeView {
             group_picker_dialog,
             sort_picker_dialog,
             project_session_picker_dialog,
+            transcript_search_dialog,
             projects_dialog,
             command_palette,
             tool_picker_dialog,
             send_message_dialog,
+            set_base_dialog,
+            notes_dialog,
             update_confirm_dialog,
             // context_menu renders last so its small popup sits on top of
             // any underlying dialog (e.g. an info dialog opened by a
@@ -636,7 +659,7 @@ impl HomeView {
     fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.list_area = area;
         let profile = self.active_profile_display();
-        let title = match &self.view_mode {
+        let mut title = match &self.view_mode {
             ViewMode::Agent => compose_list_title("aoe", profile, self.group_by, self.sort_order),
             ViewMode::Terminal => {
                 compose_list_title("Terminals", profile, self.group_by, self.sort_order)
@@ -648,6 +671,10 @@ impl HomeView {
                 self.sort_order,
             ),
         };
+        if let Some(view) = &self.active_view {
+            // Before the title's trailing pad space.
+            title.insert_str(title.len() - 1, &format!(" · @{view}"));
+        }
         let (border_color, title_color) = match self.view_mode {
             ViewMode::Agent => (theme.border, theme.title),
             ViewMode::Terminal | ViewMode::Tool(_) => {
@@ -711,6 +738,7 @@ impl HomeView {
         }
 
         let hover_idx = self.hovered_index();
+        let child_counts = ChildStatusCounts::by_parent(self.instances());
         for (i, item) in self
             .flat_items
             .iter()
@@ -723,7 +751,14 @@ impl HomeView {
             let is_hovered = !is_selected && Some(abs_idx) == hover_idx;
             let is_match =
                 !self.search_matches.is_empty() && self.search_matches.contains(&abs_idx);
-            let mut line = self.render_item_line(item, is_selected, is_match, theme, inner.width);
+            let mut line = self.render_item_line(
+                item,
+                is_selected,
+                is_match,
+                &child_counts,
+                theme,
+                inner.width,
+            );
             // Selection wins over hover: when the mouse is over the
             // already-selected row, keep the brighter selected bg rather
             // than the dimmer hover bg.
@@ -784,7 +819,12 @@ impl HomeView {
                 spans.push(Span::styled(after, text_style));
             }
 
-            if !self.search_matches.is_empty() {
+            if let Some(error) = &self.search_filter_error {
+                spans.push(Span::styled(
+                    format!(" {error}"),
+                    Style::default().fg(theme.error),
+                ));
+            } else if !self.search_matches.is_empty() {
                 let count_text = format!(
                     " [{}/{}]",
                     self.search_match_index + 1,
@@ -824,9 +864,12 @@ impl HomeView {
             || self.group_picker_dialog.is_some()
             || self.sort_picker_dialog.is_some()
             || self.project_session_picker_dialog.is_some()
+            || self.transcript_search_dialog.is_some()
             || self.projects_dialog.is_some()
             || self.command_palette.is_some()
             || self.send_message_dialog.is_some()
+            || self.set_base_dialog.is_some()
+            || self.notes_dialog.is_some()
             || self.update_confirm_dialog.is_some()
             || serve_open
     }
@@ -836,6 +879,7 @@ impl HomeView {
         item: &Item,
         is_selected: bool,
         is_match: bool,
+        child_counts: &HashMap<String, ChildStatusCounts>,
         theme: &Theme,
         list_width: u16,
     ) -> Line<'static> {
@@ -1082,6 +1126,12 @@ impl HomeView {
 
         let mut line_spans = Vec::with_capacity(5);
         line_spans.push(Span::raw(indent));
+        if matches!(item, Item::Session { id, .. } if self.marked_sessions.contains(id)) {
+            line_spans.push(Span::styled(
+                format!("{} ", ICON_MARKED),
+                Style::default().fg(theme.accent).bold(),
+            ));
+        }
         let icon_style = if is_match {
             Style::default().fg(theme.search)
         } else {
@@ -1144,6 +1194,33 @@ impl HomeView {
                     ));
                 }
 
+                // Session tags as `#tag` chips, capped so a heavily
+                // tagged row keeps its activity column.
+                if !inst.tags.is_empty() {
+                    let mut chips: Vec<String> = inst
+                        .tags
+                        .iter()
+                        .take(MAX_TAG_CHIPS)
+                        .map(|t| format!("#{t}"))
+                        .collect();
+                    if inst.tags.len() > MAX_TAG_CHIPS {
+                        chips.push(format!("+{}", inst.tags.len() - MAX_TAG_CHIPS));
+                    }
+                    line_spans.push(Span::styled(
+                        format!("  {}", chips.join(" ")),
+                        Style::default().fg(theme.accent),
+                    ));
+                }
+
+                // Coordinator rows roll up their sub-sessions' statuses
+                // so progress reads at a glance from the parent row.
+                if let Some(children) = child_counts.get(&inst.id) {
+                    line_spans.push(Span::styled(
+                        format!("  [{}]", children.summary()),
+                        Style::default().fg(theme.dimmed),
+                    ));
+                }
+
                 // Right edge of the row: optional terminal-mode badge, and
                 // an activity column (last-accessed for non-Idle rows,
                 // time-since-stop for Idle rows, snooze remainder for
@@ -1618,8 +1695,12 @@ impl HomeView {
             // Terminal/Tool use the minimal header in `render_terminal_preview`),
             // so the hint applies everywhere except the compact branch
             // above, where the outer title is already taken.
-            let key = if self.strict_hotkeys { "I" } else { "i" };
-            let hint_text = if self.show_preview_info {
+            let key = self
+                .keymap
+                .label(ActionId::TogglePreviewInfo, self.strict_hotkeys);
+            let hint_text = if key.is_empty() {
+                String::new()
+            } else if self.show_preview_info {
                 format!(" hide info with {key} ")
             } else {
                 format!(" show info with {key} ")
@@ -2189,9 +2270,41 @@ impl HomeView {
         // sizes.
         let mk_key =
             |key: &str| -> Vec<Span<'static>> { vec![Span::styled(key.to_string(), key_style)] };
+        // Footer label for an action's key under the user's keymap, compact
+        // (`^G`). Empty when the user unbound it; `push_hint` then drops it.
+        let hint = |id: ActionId| self.keymap.short_label(id, strict);
+        let push_hint =
+            |groups: &mut Vec<(u8, Vec<Span<'static>>)>, priority: u8, id: ActionId, desc: &str| {
+                let key = hint(id);
+                if !key.is_empty() {
+                    groups.push((priority, mk(&key, desc)));
+                }
+            };
 
         let mut groups: Vec<(u8, Vec<Span<'static>>)> = Vec::new();
 
+        // Half-typed key sequence: show what's been pressed so far.
+        if !self.pending_keys.is_empty() {
+            groups.push((
+                0,
+                vec![Span::styled(
+                    format!("{} \u{2026}", bindings::format_pending(&self.pending_keys)),
+                    key_style,
+                )],
+            ));
+        }
+
+        // Marked sessions: bulk actions apply to all of them until Esc.
+        if !self.marked_sessions.is_empty() {
+            groups.push((
+                0,
+                mk(
+                    &self.marked_sessions.len().to_string(),
+                    "marked (Esc clears)",
+                ),
+            ));
+        }
+
         // Serve indicator: shown only when the `aoe serve` daemon is live.
         // The TUI does not own the daemon, so we probe the PID file each
         // render. Mode comes from a PID-keyed cache so we don't read the
@@ -2236,9 +2349,9 @@ impl HomeView {
         // pending_paste into the compose dialog.
         if let Some(buf) = &self.pending_paste {
             if !buf.is_empty() {
-                let key = if strict { "M" } else { "m" };
+                let key = hint(ActionId::SendMessage);
                 let desc = format!("send {} buffered", buf.chars().count());
-                let mut spans = mk(key, &desc);
+                let mut spans = mk(&key, &desc);
                 spans[1] = Span::styled(desc, Style::default().fg(theme.running).bold());
                 groups.push((0, spans));
             }
@@ -2263,26 +2376,26 @@ impl HomeView {
             groups.push((0, mk("↵ ", enter_action_text)));
         }
 
-        groups.push((2, mk(if strict { "T" } else { "t" }, "View")));
+        push_hint(&mut groups, 2, ActionId::ToggleView, "View");
         if matches!(self.view_mode, ViewMode::Tool(_)) {
-            groups.push((1, mk(";", "Back")));
+            push_hint(&mut groups, 1, ActionId::ToolPicker, "Back");
         } else if !self.tool_configs.is_empty() {
-            groups.push((2, mk(";", "Tools")));
+            push_hint(&mut groups, 2, ActionId::ToolPicker, "Tools");
         }
-        groups.push((3, mk(if strict { "^G" } else { "g" }, "Group")));
+        push_hint(&mut groups, 3, ActionId::GroupBy, "Group");
 
         // c: container/host toggle hint for sandboxed sessions in Terminal view
         if self.view_mode == ViewMode::Terminal {
             if let Some(id) = &self.selected_session {
                 if let Some(inst) = self.get_instance(id) {
                     if inst.is_sandboxed() {
-                        groups.push((4, mk(if strict { "C" } else { "c" }, "Mode")));
+                        push_hint(&mut groups, 4, ActionId::ToggleContainer, "Mode");
                     }
                 }
             }
         }
 
-        groups.push((2, mk(if strict { "N" } else { "n" }, "New")));
+        push_hint(&mut groups, 2, ActionId::NewSession, "New");
 
         // Priority 1: user's core daily workflow (message / del).
         // These survive the greedy pack under narrow-pane widths (iPad
@@ -2290,10 +2403,10 @@ impl HomeView {
         // reaches for most often. Del stays at p3, less frequent,
         // OK to drop first.
         if self.selected_session.is_some() {
-            groups.push((1, mk(if strict { "M" } else { "m" }, "Msg")));
+            push_hint(&mut groups, 1, ActionId::SendMessage, "Msg");
         }
         if !self.flat_items.is_empty() {
-            groups.push((3, mk(if strict { "D" } else { "d" }, "Del")));
+            push_hint(&mut groups, 3, ActionId::Delete, "Del");
         }
         // Attention-workflow shortcuts (Archive / Fav / Snooze) only render
         // when the user is in Attention sort. They are only useful for
@@ -2301,18 +2414,24 @@ impl HomeView {
         // they just take footer space without changing what the user sees.
         if self.sort_order == SortOrder::Attention {
             if !self.flat_items.is_empty() {
-                groups.push((1, mk(if strict { "Z" } else { "z" }, "Archive")));
+                push_hint(&mut groups, 1, ActionId::ToggleArchive, "Archive");
             }
             if self.selected_session.is_some() {
-                groups.push((1, mk(if strict { "F" } else { "f" }, "Fav")));
-                groups.push((1, mk(if strict { "H" } else { "h" }, "Snooze")));
+                push_hint(&mut groups, 1, ActionId::ToggleFavorite, "Fav");
+                push_hint(&mut groups, 1, ActionId::ToggleSnooze, "Snooze");
             }
         }
 
-        groups.push((4, mk_key("/")));
-        groups.push((4, mk(if strict { "^D" } else { "D" }, "Diff")));
+        let search = hint(ActionId::SearchStart);
+        if !search.is_empty() {
+            groups.push((4, mk_key(&search)));
+        }
+        push_hint(&mut groups, 4, ActionId::Diff, "Diff");
         groups.push((1, mk("^K", "Cmds")));
-        groups.push((0, mk_key("?")));
+        let help = hint(ActionId::Help);
+        if !help.is_empty() {
+            groups.push((0, mk_key(&help)));
+        }
 
         // Greedy pack by priority. Width of a group = sum of span char counts;
         // separator between kept groups adds 3 cols each (" · "). Reserve 1
@@ -830,6 +830,176 @@ fn test_search_empty_query_clears_matches() {
     assert!(env.view.search_matches.is_empty());
 }
 
+#[test]
+#[serial]
+fn test_search_filter_query_matches_fields_in_list_order() {
+    let mut env = create_test_env_with_sessions(4);
+    let ids: Vec<String> = env.view.instances.iter().map(|i| i.id.clone()).collect();
+    env.view
+        .set_instance_status(&ids[3], crate::session::Status::Waiting);
+    env.view
+        .set_instance_status(&ids[1], crate::session::Status::Waiting);
+    env.view.search_query = Input::new("status:waiting".to_string());
+    env.view.update_search();
+    let matched: Vec<&str> = env
+        .view
+        .search_matches
+        .iter()
+        .map(|&idx| match &env.view.flat_items[idx] {
+            Item::Session { id, .. } => id.as_str(),
+            Item::Group { .. } => panic!("filter matched a group row"),
+        })
+        .collect();
+    assert_eq!(matched.len(), 2);
+    let rows: Vec<usize> = env.view.search_matches.clone();
+    assert!(rows[0] < rows[1], "structured matches keep list order");
+    assert!(env.view.search_filter_error.is_none());
+
+    env.view.search_query = Input::new("status:bogus".to_string());
+    env.view.update_search();
+    assert!(env.view.search_matches.is_empty());
+    assert!(env
+        .view
+        .search_filter_error
+        .as_deref()
+        .is_some_and(|e| e.contains("unknown status")));
+}
+
+#[test]
+#[serial]
+fn test_saved_view_from_group_picker_narrows_list() {
+    let mut env = create_test_env_with_sessions(3);
+    let waiting = env.view.instances[0].id.clone();
+    env.view
+        .set_instance_status(&waiting, crate::session::Status::Waiting);
+    env.view
+        .saved_views
+        .insert("stuck".to_string(), "status:waiting".to_string());
+
+    // Manual, Project, Tag, then the views.
+    env.view.handle_key(key(KeyCode::Char('g')), None);
+    env.view.handle_key(key(KeyCode::Down), None);
+    env.view.handle_key(key(KeyCode::Down), None);
+    env.view.handle_key(key(KeyCode::Down), None);
+    env.view.handle_key(key(KeyCode::Enter), None);
+    assert_eq!(env.view.active_view.as_deref(), Some("stuck"));
+    let sessions: Vec<&str> = env
+        .view
+        .flat_items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Session { id, .. } => Some(id.as_str()),
+            Item::Group { .. } => None,
+        })
+        .collect();
+    assert_eq!(sessions, vec![waiting.as_str()]);
+
+    // Picking a grouping mode leaves the view.
+    env.view.handle_key(key(KeyCode::Char('g')), None);
+    env.view.handle_key(key(KeyCode::Up), None);
+    env.view.handle_key(key(KeyCode::Up), None);
+    env.view.handle_key(key(KeyCode::Up), None);
+    env.view.handle_key(key(KeyCode::Enter), None);
+    assert!(env.view.active_view.is_none());
+    assert_eq!(
+        env.view
+            .flat_items
+            .iter()
+            .filter(|item| matches!(item, Item::Session { .. }))
+            .count(),
+        3
+    );
+}
+
+/// Tag grouping files a session under each of its tags, nests labels as
+/// `key/value`, and collects untagged sessions under `(untagged)`.
+#[test]
+#[serial]
+fn tag_grouping_lists_sessions_under_each_tag() {
+    use crate::session::config::GroupByMode;
+
+    let mut env = create_test_env_with_sessions(3);
+    let tagged = [
+        vec!["blocked-ci".to_string(), "reviewer=alice".to_string()],
+        vec!["Blocked-CI".to_string(), "reviewer=bob".to_string()],
+        vec![],
+    ];
+    for (i, tags) in tagged.into_iter().enumerate() {
+        let id = env.view.instances[i].id.clone();
+        env.view.instances[i].tags = tags.clone();
+        env.view.instance_map.get_mut(&id).unwrap().tags = tags;
+    }
+    let first = env.view.instances[0].id.clone();
+    env.view.group_by = GroupByMode::Tag;
+    env.view.flat_items = env.view.build_flat_items();
+
+    let groups: Vec<&str> = env
+        .view
+        .flat_items
+        .iter()
+        .filter_map(|i| match i {
+            Item::Group { path, .. } => Some(path.as_str()),
+            _ => None,
+        })
+        .collect();
+    for expected in [
+        "blocked-ci",
+        "reviewer",
+        "reviewer/alice",
+        "reviewer/bob",
+        "(untagged)",
+    ] {
+        assert!(
+            groups.contains(&expected),
+            "missing {expected} in {groups:?}"
+        );
+    }
+    let first_rows = env
+        .view
+        .flat_items
+        .iter()
+        .filter(|i| matches!(i, Item::Session { id, .. } if *id == first))
+        .count();
+    assert_eq!(first_rows, 2, "listed under blocked-ci and reviewer/alice");
+    // Tags compare case-insensitively, so both spellings share one group.
+    assert_eq!(
+        groups
+            .iter()
+            .filter(|g| g.eq_ignore_ascii_case("blocked-ci"))
+            .count(),
+        1,
+        "{groups:?}"
+    );
+}
+
+/// `a` edits the selected session's notes; Ctrl+S writes them to the
+/// notes file and a reload reads them back.
+#[test]
+#[serial]
+fn notes_dialog_saves_to_the_notes_file() {
+    let mut env = create_test_env_with_sessions(2);
+    let id = env.view.selected_session.clone().unwrap();
+
+    env.view.handle_key(key(KeyCode::Char('a')), None);
+    assert!(env.view.notes_dialog.is_some());
+    env.view.handle_paste("waiting on infra to merge X");
+    env.view.handle_key(
+        KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+        None,
+    );
+    assert!(env.view.notes_dialog.is_none());
+    assert_eq!(
+        crate::session::notes::load("test", &id).unwrap(),
+        "waiting on infra to merge X"
+    );
+
+    env.view.reload().unwrap();
+    assert_eq!(
+        env.view.get_instance(&id).unwrap().notes,
+        "waiting on infra to merge X"
+    );
+}
+
 #[test]
 #[serial]
 fn test_search_no_matches() {
@@ -2494,6 +2664,80 @@ fn test_f5_and_e_both_open_restart_dialog() {
     assert!(upper_e_opened, "strict 'E' should open the restart dialog");
 }
 
+#[test]
+#[serial]
+fn test_user_key_sequence_waits_for_its_second_key() {
+    // `[keybindings] help = "g h"`, with group-by moved off `g` so the
+    // sequence isn't shadowed.
+    let config = [
+        (
+            "help".to_string(),
+            crate::session::config::KeySequences(vec!["g h".to_string()]),
+        ),
+        (
+            "pick-group-by".to_string(),
+            crate::session::config::KeySequences(vec!["ctrl+g".to_string()]),
+        ),
+    ]
+    .into();
+    let mut env = create_test_env_with_sessions(1);
+    let (keymap, warnings) = super::bindings::Keymap::from_config(&config);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    env.view.keymap = keymap;
+
+    env.view.handle_key(key(KeyCode::Char('?')), None);
+    assert!(!env.view.show_help, "'?' was replaced by 'g h'");
+
+    env.view.handle_key(key(KeyCode::Char('g')), None);
+    assert!(!env.view.show_help);
+    assert_eq!(env.view.pending_keys.len(), 1);
+    env.view.handle_key(key(KeyCode::Char('h')), None);
+    assert!(env.view.show_help);
+    assert!(env.view.pending_keys.is_empty());
+    env.view.show_help = false;
+
+    // A key that breaks the sequence is swallowed, not run on its own:
+    // `d` would otherwise open the delete dialog.
+    env.view.cursor = 0;
+    env.view.update_selected();
+    env.view.handle_key(key(KeyCode::Char('g')), None);
+    env.view.handle_key(key(KeyCode::Char('d')), None);
+    assert!(env.view.pending_keys.is_empty());
+    assert!(!env.view.show_help);
+    assert!(env.view.unified_delete_dialog.is_none());
+
+    // Esc cancels a half-typed sequence.
+    env.view.handle_key(key(KeyCode::Char('g')), None);
+    env.view.handle_key(key(KeyCode::Esc), None);
+    assert!(env.view.pending_keys.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_user_dialog_key_reaches_dialogs_alongside_the_fixed_key() {
+    let config = [(
+        "dialog-cancel".to_string(),
+        crate::session::config::KeySequences(vec!["ctrl+g".to_string()]),
+    )]
+    .into();
+    let mut env = create_test_env_with_sessions(1);
+    let (keymap, warnings) = super::bindings::Keymap::from_config(&config);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    env.view.keymap = keymap;
+    let ctrl_o = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    let ctrl_g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
+
+    env.view.handle_key(ctrl_o, None);
+    assert!(env.view.sort_picker_dialog.is_some());
+    env.view.handle_key(ctrl_g, None);
+    assert!(env.view.sort_picker_dialog.is_none());
+
+    // Esc still closes it.
+    env.view.handle_key(ctrl_o, None);
+    env.view.handle_key(key(KeyCode::Esc), None);
+    assert!(env.view.sort_picker_dialog.is_none());
+}
+
 #[test]
 #[serial]
 fn test_ctrl_o_key_opens_sort_picker() {
@@ -2834,7 +3078,8 @@ fn test_all_profiles_view_shows_all_sessions_flat() {
 fn rendered_row_text(view: &HomeView, item: &Item) -> String {
     use crate::tui::styles::Theme;
     let theme = Theme::default();
-    view.render_item_line(item, false, false, &theme, 200)
+    let child_counts = crate::session::coordinator::ChildStatusCounts::by_parent(view.instances());
+    view.render_item_line(item, false, false, &child_counts, &theme, 200)
         .spans
         .iter()
         .map(|s| s.content.as_ref())
@@ -5907,6 +6152,77 @@ fn archived_sub_folders_honor_sort_order() {
     );
 }
 
+/// A cockpit session's review goes to `aoe serve`, never to a tmux
+/// pane; with no daemon up the send fails and the comments stay.
+#[test]
+#[serial]
+fn test_broadcast_reports_per_session_results_in_the_toast() {
+    let mut env = create_test_env_with_sessions(2);
+    let ids: Vec<String> = env.view.instances.iter().map(|i| i.id.clone()).collect();
+    // Creating sessions can't be revived, so both sends fail on the worker
+    // without touching tmux.
+    for id in &ids {
+        env.view
+            .set_instance_status(id, crate::session::Status::Creating);
+    }
+
+    env.view.request_broadcast(&ids, "hello");
+    let mut toasts = Vec::new();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while env.view.broadcast.is_some() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "broadcast never finished"
+        );
+        toasts.extend(env.view.apply_broadcast_results());
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let summary = toasts.last().expect("no toast");
+    assert!(
+        summary.starts_with("Sent to 0 of 2 sessions; failed: "),
+        "{summary}"
+    );
+    assert!(
+        summary.contains("session0: Cannot prepare session"),
+        "{summary}"
+    );
+    assert!(
+        summary.contains("session1: Cannot prepare session"),
+        "{summary}"
+    );
+    assert!(env.view.info_dialog.is_none());
+}
+
+#[cfg(feature = "serve")]
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn send_review_to_cockpit_session_needs_the_daemon() {
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances[0].id.clone();
+    let profile = env.view.instances[0].source_profile.clone();
+    env.view
+        .mutate_instance(&id, |inst| inst.cockpit_mode = true);
+    let comment = crate::session::reviews::ReviewComment::new(
+        "src/lib.rs",
+        crate::session::reviews::DiffSide::New,
+        3,
+        3,
+        "rename this",
+        "let x = 1;",
+    );
+    crate::session::reviews::add(&profile, &id, comment).unwrap();
+
+    assert!(env.view.execute_send_review(&id).is_none());
+    assert!(env.view.info_dialog.is_some(), "expected a send failure");
+    assert_eq!(
+        crate::session::reviews::comments_for(&profile, &id)
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
 mod scroll_pane_isolation {
     //! Wheel events are confined to whichever pane the mouse is over.
     //! In particular, a wheel over the preview pane never moves the list
@@ -8757,6 +9073,15 @@ mod save_field_merge {
         let (_temp, mut view, id) = boot_view_with_one_session("victim", "/tmp/move");
         view.storages
             .insert("target".to_string(), Storage::new("target").unwrap());
+        let comment = crate::session::reviews::ReviewComment::new(
+            "src/main.rs",
+            crate::session::reviews::DiffSide::New,
+            1,
+            1,
+            "keep me",
+            "fn main() {}",
+        );
+        crate::session::reviews::add("test", &id, comment).unwrap();
 
         view.move_to_profile(&id, "target", "moved/group".to_string())
             .unwrap();
@@ -8776,6 +9101,12 @@ mod save_field_merge {
         let inst = view.get_instance(&id).unwrap();
         assert_eq!(inst.source_profile, "target");
         assert_eq!(inst.group_path, "moved/group");
+        assert!(crate::session::reviews::comments_for("test", &id)
+            .unwrap()
+            .is_empty());
+        let moved = crate::session::reviews::comments_for("target", &id).unwrap();
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].body, "keep me");
     }
 
     #[test]
@@ -9297,3 +9628,260 @@ mod right_click_context_menu {
         );
     }
 }
+
+fn row_of_group(view: &HomeView, group: &str) -> usize {
+    view.flat_items
+        .iter()
+        .position(|item| matches!(item, Item::Group { path, .. } if path == group))
+        .expect("group row")
+}
+
+/// `v` toggles the cursor session's mark; Esc drops every mark.
+#[test]
+#[serial]
+fn mark_key_toggles_cursor_session_and_esc_clears() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.cursor = 0;
+    env.view.update_selected();
+    let first = env.view.selected_session.clone().unwrap();
+
+    env.view.handle_key(key(KeyCode::Char('v')), None);
+    assert!(env.view.marked_sessions.contains(&first));
+    env.view.handle_key(key(KeyCode::Char('v')), None);
+    assert!(env.view.marked_sessions.is_empty());
+
+    env.view.handle_key(key(KeyCode::Char('v')), None);
+    env.view.handle_key(key(KeyCode::Char('j')), None);
+    env.view.handle_key(key(KeyCode::Char('v')), None);
+    assert_eq!(env.view.marked_ids().len(), 2);
+
+    let action = env.view.handle_key(key(KeyCode::Esc), None);
+    assert!(action.is_none(), "Esc with marks must not quit");
+    assert!(env.view.marked_sessions.is_empty());
+}
+
+/// Marking a group header marks its members; a second press unmarks them.
+#[test]
+#[serial]
+fn mark_on_group_row_marks_members() {
+    let mut env = create_test_env_with_groups();
+    env.view.cursor = row_of_group(&env.view, "work");
+    env.view.toggle_mark_at_cursor();
+    let marked: Vec<String> = env
+        .view
+        .marked_ids()
+        .iter()
+        .map(|id| env.view.get_instance(id).unwrap().title.clone())
+        .collect();
+    assert_eq!(marked, vec!["work-project".to_string()]);
+
+    env.view.toggle_mark_at_cursor();
+    assert!(env.view.marked_sessions.is_empty());
+}
+
+/// `*` without a search marks every visible session.
+#[test]
+#[serial]
+fn mark_matches_without_search_marks_all() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('*')), None);
+    assert_eq!(env.view.marked_ids().len(), 3);
+}
+
+/// `*` while searching marks only the rows the query matched, then closes
+/// the search.
+#[test]
+#[serial]
+fn mark_matches_during_search_marks_only_matches() {
+    let mut env = create_test_env_with_sessions(3);
+    let target = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.title == "session1")
+        .unwrap()
+        .id
+        .clone();
+    env.view.handle_key(key(KeyCode::Char('/')), None);
+    for c in "session1".chars() {
+        env.view.handle_key(key(KeyCode::Char(c)), None);
+    }
+    assert_eq!(env.view.search_matches.len(), 1);
+    env.view.handle_key(key(KeyCode::Char('*')), None);
+    assert!(!env.view.search_active);
+    assert_eq!(env.view.marked_ids(), vec![target]);
+}
+
+/// With a saved view active, `*` marks only the sessions the view shows, so
+/// a bulk action never reaches the hidden ones.
+#[test]
+#[serial]
+fn mark_matches_respects_active_view() {
+    let mut env = create_test_env_with_sessions(3);
+    let waiting = env.view.instances[0].id.clone();
+    env.view
+        .set_instance_status(&waiting, crate::session::Status::Waiting);
+    env.view
+        .saved_views
+        .insert("stuck".to_string(), "status:waiting".to_string());
+    env.view.set_active_view(Some("stuck".to_string()));
+    env.view.flat_items = env.view.build_flat_items();
+
+    env.view.handle_key(key(KeyCode::Char('*')), None);
+    assert_eq!(env.view.marked_ids(), vec![waiting.clone()]);
+
+    env.view.toggle_archive_marked().unwrap();
+    for inst in &env.view.instances {
+        assert_eq!(inst.is_archived(), inst.id == waiting, "{}", inst.title);
+    }
+}
+
+/// Bulk archive applies to every marked session and consumes the marks.
+#[test]
+#[serial]
+fn toggle_archive_marked_round_trip() {
+    let mut env = create_test_env_with_sessions(3);
+    let ids: Vec<String> = env.view.instances[..2]
+        .iter()
+        .map(|i| i.id.clone())
+        .collect();
+    env.view.marked_sessions.extend(ids.iter().cloned());
+
+    env.view.toggle_archive_marked().unwrap();
+    assert!(env.view.marked_sessions.is_empty());
+    for id in &ids {
+        assert!(env.view.get_instance(id).unwrap().is_archived());
+    }
+    assert!(!env.view.instances[2].is_archived());
+
+    env.view.marked_sessions.extend(ids.iter().cloned());
+    env.view.toggle_archive_marked().unwrap();
+    for id in &ids {
+        assert!(!env.view.get_instance(id).unwrap().is_archived());
+    }
+}
+
+/// Moving marked sessions puts them all in the target group.
+#[test]
+#[serial]
+fn move_marked_sets_group_for_every_marked_session() {
+    let mut env = create_test_env_with_sessions(3);
+    let ids: Vec<String> = env.view.instances[1..]
+        .iter()
+        .map(|i| i.id.clone())
+        .collect();
+    env.view.marked_sessions.extend(ids.iter().cloned());
+
+    env.view.move_marked(Some("batch"), None).unwrap();
+    assert!(env.view.marked_sessions.is_empty());
+    for id in &ids {
+        assert_eq!(env.view.get_instance(id).unwrap().group_path, "batch");
+    }
+    let untouched = env
+        .view
+        .instances
+        .iter()
+        .find(|i| !ids.contains(&i.id))
+        .unwrap();
+    assert!(untouched.group_path.is_empty());
+}
+
+/// With marks set, the delete key opens one dialog for all of them.
+#[test]
+#[serial]
+fn delete_key_with_marks_opens_bulk_dialog() {
+    let mut env = create_test_env_with_sessions(2);
+    env.view.handle_key(key(KeyCode::Char('*')), None);
+    env.view.handle_key(key(KeyCode::Char('d')), None);
+    assert!(env.view.unified_delete_dialog.is_some());
+    // Marks stay until the dialog is submitted.
+    assert_eq!(env.view.marked_ids().len(), 2);
+}
+
+/// `W` with fewer than two candidates explains itself instead of opening.
+#[test]
+#[serial]
+fn wall_needs_two_sessions() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('W')), None);
+    assert!(env.view.wall.is_none());
+    assert!(env.view.info_dialog.is_some());
+}
+
+/// The wall tiles the marked sessions, renders them, and Enter hands the
+/// selected tile to live send.
+#[test]
+#[serial]
+fn wall_tiles_marked_sessions_and_enters_live_send() {
+    use crate::tui::styles::load_theme;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    let mut env = create_test_env_with_sessions(3);
+    env.view.handle_key(key(KeyCode::Char('*')), None);
+    env.view.handle_key(key(KeyCode::Char('W')), None);
+    let wall = env.view.wall.as_ref().expect("wall open");
+    assert_eq!(wall.ids.len(), 3);
+    let target = wall.ids[1].clone();
+
+    let theme = load_theme("empire");
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal
+        .draw(|f| {
+            let area = f.area();
+            env.view.render(f, area, &theme, None, None);
+        })
+        .unwrap();
+    let buf = terminal.backend().buffer().clone();
+    let top: String = (0..buf.area.width).map(|x| buf[(x, 0)].symbol()).collect();
+    assert!(top.contains("Wall · 3 sessions"), "header: {top}");
+    assert!(top.contains("Esc/W: close"), "header: {top}");
+
+    env.view.handle_key(key(KeyCode::Char('2')), None);
+    let action = env.view.handle_key(key(KeyCode::Enter), None);
+    assert!(env.view.wall.is_none());
+    assert_eq!(env.view.selected_session.as_deref(), Some(target.as_str()));
+    assert!(matches!(action, Some(Action::EnterLiveSend(id)) if id == target));
+}
+
+/// A rebound wall key closes the wall too, and the header shows it.
+#[test]
+#[serial]
+fn wall_closes_on_its_rebound_key() {
+    use crate::tui::styles::load_theme;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    let config = [(
+        "wall".to_string(),
+        crate::session::config::KeySequences(vec!["alt+w".to_string()]),
+    )]
+    .into();
+    let mut env = create_test_env_with_sessions(3);
+    let (keymap, warnings) = super::bindings::Keymap::from_config(&config);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    env.view.keymap = keymap;
+    let alt_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::ALT);
+
+    env.view.handle_key(key(KeyCode::Char('*')), None);
+    env.view.handle_key(alt_w, None);
+    assert!(env.view.wall.is_some());
+
+    let theme = load_theme("empire");
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal
+        .draw(|f| {
+            let area = f.area();
+            env.view.render(f, area, &theme, None, None);
+        })
+        .unwrap();
+    let buf = terminal.backend().buffer().clone();
+    let top: String = (0..buf.area.width).map(|x| buf[(x, 0)].symbol()).collect();
+    assert!(top.contains("Esc/Alt+w: close"), "header: {top}");
+
+    // The old key no longer closes it; the new one does.
+    env.view.handle_key(key(KeyCode::Char('W')), None);
+    assert!(env.view.wall.is_some());
+    env.view.handle_key(alt_w, None);
+    assert!(env.view.wall.is_none());
+}
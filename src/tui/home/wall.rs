@@ -0,0 +1,334 @@
+//! Wall view: a full-screen grid of live agent panes.
+//!
+//! The wall tiles 2-9 sessions at once so a batch of agents can be watched
+//! without cycling the cursor. Tiles are picked from the marks, else the
+//! search matches, else the group under the cursor. Each tile captures its
+//! pane through the same `Instance::capture_output_with_size` path and
+//! `PreviewCache` parse the preview panel uses, throttled per tile. Enter
+//! (or Tab) on a tile closes the wall and drops into live send for it.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::bindings::ActionId;
+use super::{HomeView, PreviewCache, ViewMode};
+use crate::session::{Item, Status};
+use crate::tui::app::Action;
+use crate::tui::dialogs::InfoDialog;
+use crate::tui::styles::Theme;
+
+/// Most tiles the wall shows; a 3x3 grid is the smallest useful tile on an
+/// ordinary terminal.
+pub(super) const WALL_MAX_TILES: usize = 9;
+
+/// Per-tile capture throttle. Nine tiles fork nine `tmux capture-pane`
+/// processes per refresh, so the wall polls at half the preview's rate.
+const WALL_REFRESH: Duration = Duration::from_millis(500);
+
+pub(super) struct WallState {
+    /// Tile order; the first `WALL_MAX_TILES` candidates.
+    pub(super) ids: Vec<String>,
+    pub(super) selected: usize,
+    /// Candidates dropped past the tile limit, reported in the header.
+    pub(super) overflow: usize,
+    caches: HashMap<String, PreviewCache>,
+    last_refresh: Instant,
+}
+
+impl WallState {
+    pub(super) fn new(mut ids: Vec<String>) -> Self {
+        let overflow = ids.len().saturating_sub(WALL_MAX_TILES);
+        ids.truncate(WALL_MAX_TILES);
+        Self {
+            ids,
+            selected: 0,
+            overflow,
+            caches: HashMap::new(),
+            last_refresh: Instant::now(),
+        }
+    }
+
+    /// Move the selection by one tile in the grid, stopping at the edges.
+    fn step(&mut self, dx: isize, dy: isize) {
+        let (cols, _) = grid(self.ids.len());
+        let (col, row) = (self.selected % cols, self.selected / cols);
+        let col = col as isize + dx;
+        let row = row as isize + dy;
+        if col < 0 || row < 0 || col >= cols as isize {
+            return;
+        }
+        let idx = row as usize * cols + col as usize;
+        if idx < self.ids.len() {
+            self.selected = idx;
+        }
+    }
+}
+
+/// Columns and rows for `n` tiles: as square as possible, wider than tall.
+fn grid(n: usize) -> (usize, usize) {
+    let n = n.max(1);
+    let cols = (1..=n).find(|c| c * c >= n).unwrap_or(n);
+    (cols, n.div_ceil(cols))
+}
+
+/// Tile rects for `n` tiles filling `area` row by row. The last row's tiles
+/// stretch to fill it when it is short.
+fn tile_rects(area: Rect, n: usize) -> Vec<Rect> {
+    let (cols, rows) = grid(n);
+    let row_areas = Layout::vertical(vec![Constraint::Ratio(1, rows as u32); rows]).split(area);
+    let mut rects = Vec::with_capacity(n);
+    for (r, row_area) in row_areas.iter().enumerate() {
+        let in_row = (n - r * cols).min(cols);
+        let cells =
+            Layout::horizontal(vec![Constraint::Ratio(1, in_row as u32); in_row]).split(*row_area);
+        rects.extend(cells.iter().copied());
+    }
+    rects
+}
+
+impl HomeView {
+    /// Sessions the wall would show: marked sessions, else search matches,
+    /// else the members of the group under the cursor. Cockpit sessions
+    /// (no tmux pane) and rows mid-create or mid-delete are left out.
+    pub(super) fn wall_candidates(&self) -> Vec<String> {
+        let ids = if !self.marked_sessions.is_empty() {
+            self.marked_ids()
+        } else if !self.search_matches.is_empty() {
+            self.search_matches
+                .iter()
+                .filter_map(|&idx| match self.flat_items.get(idx) {
+                    Some(Item::Session { id, .. }) => Some(id.clone()),
+                    _ => None,
+                })
+                .collect()
+        } else if matches!(self.flat_items.get(self.cursor), Some(Item::Group { .. })) {
+            self.group_member_ids(self.cursor)
+        } else {
+            Vec::new()
+        };
+        ids.into_iter()
+            .filter(|id| {
+                self.get_instance(id).is_some_and(|i| {
+                    !i.is_cockpit_mode() && !matches!(i.status, Status::Creating | Status::Deleting)
+                })
+            })
+            .collect()
+    }
+
+    pub(super) fn open_wall(&mut self) {
+        let ids = self.wall_candidates();
+        if ids.len() < 2 {
+            let hint = format!(
+                "The wall needs at least 2 sessions. Mark them with {}, search, or put the cursor on a group row first.",
+                self.key_hint(ActionId::ToggleMark)
+            );
+            self.info_dialog = Some(InfoDialog::new("Wall View", &hint));
+            return;
+        }
+        let mut wall = WallState::new(ids);
+        if let Some(pos) = self
+            .selected_session
+            .as_ref()
+            .and_then(|id| wall.ids.iter().position(|t| t == id))
+        {
+            wall.selected = pos;
+        }
+        self.wall = Some(wall);
+    }
+
+    /// True when the tiles are due for a fresh capture; the main loop
+    /// redraws so `render_wall` can take it.
+    pub(super) fn wall_needs_refresh(&self) -> bool {
+        self.wall
+            .as_ref()
+            .is_some_and(|w| w.last_refresh.elapsed() >= WALL_REFRESH)
+    }
+
+    pub(super) fn handle_wall_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let closes = [ActionId::Wall, ActionId::Quit]
+            .into_iter()
+            .any(|id| self.keymap.matches(id, &key, self.strict_hotkeys));
+        let wall = self.wall.as_mut()?;
+        if closes {
+            self.wall = None;
+            return None;
+        }
+        match key.code {
+            KeyCode::Esc => self.wall = None,
+            KeyCode::Left | KeyCode::Char('h') => wall.step(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => wall.step(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => wall.step(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => wall.step(0, 1),
+            KeyCode::Char(c @ '1'..='9') => {
+                let idx = c as usize - '1' as usize;
+                if idx < wall.ids.len() {
+                    wall.selected = idx;
+                }
+            }
+            KeyCode::Enter | KeyCode::Tab => {
+                let id = wall.ids[wall.selected].clone();
+                self.wall = None;
+                // Tiles show the agent pane, so that's the live target.
+                self.view_mode = ViewMode::Agent;
+                self.select_and_reveal_session(&id);
+                return self.start_live_send();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    pub(super) fn render_wall(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(mut wall) = self.wall.take() else {
+            return;
+        };
+        // Drop tiles whose session went away (deleted from another client).
+        let before = wall.ids.len();
+        wall.ids.retain(|id| self.get_instance(id).is_some());
+        if wall.ids.is_empty() {
+            return;
+        }
+        if wall.ids.len() != before {
+            wall.selected = wall.selected.min(wall.ids.len() - 1);
+        }
+
+        let [header, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        let close = match self.keymap.label(ActionId::Wall, self.strict_hotkeys) {
+            label if label.is_empty() => "Esc".to_string(),
+            label => format!("Esc/{label}"),
+        };
+        let mut title = format!(" Wall · {} sessions", wall.ids.len());
+        if wall.overflow > 0 {
+            title.push_str(&format!(" ({} more not shown)", wall.overflow));
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(title, Style::default().fg(theme.title).bold()),
+                Span::styled(
+                    format!("   Enter: live send  ←↓↑→/1-9: select  {close}: close"),
+                    Style::default().fg(theme.dimmed),
+                ),
+            ])),
+            header,
+        );
+
+        let refresh = wall.last_refresh.elapsed() >= WALL_REFRESH;
+        if refresh {
+            wall.last_refresh = Instant::now();
+        }
+        let rects = tile_rects(body, wall.ids.len());
+        for (idx, (id, rect)) in wall.ids.iter().zip(rects).enumerate() {
+            let Some(inst) = self.get_instance(id) else {
+                continue;
+            };
+            let color = match inst.status {
+                Status::Running => theme.running,
+                Status::Waiting | Status::Unknown | Status::Deleting => theme.waiting,
+                Status::Idle => theme.idle_color_at_age(inst.idle_age(), self.idle_decay_window),
+                Status::Error => theme.error,
+                Status::Stopped | Status::Starting => theme.dimmed,
+                Status::Creating => theme.accent,
+            };
+            let selected = idx == wall.selected;
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(if selected {
+                    BorderType::Thick
+                } else {
+                    BorderType::Plain
+                })
+                .border_style(Style::default().fg(color))
+                .title(Line::styled(
+                    format!(" {} {} ", idx + 1, inst.title),
+                    Style::default()
+                        .fg(if selected { theme.title } else { theme.text })
+                        .bold(),
+                ))
+                .title_top(
+                    Line::styled(
+                        format!(" {} ", inst.status.as_str()),
+                        Style::default().fg(color),
+                    )
+                    .right_aligned(),
+                );
+            let inner = block.inner(rect);
+            frame.render_widget(block, rect);
+            if inner.width == 0 || inner.height == 0 {
+                continue;
+            }
+
+            let cache = wall.caches.entry(id.clone()).or_default();
+            if refresh
+                || cache.session_id.is_none()
+                || cache.dimensions != (inner.width, inner.height)
+            {
+                cache.content = inst
+                    .capture_output_with_size(inner.height as usize, inner.width, inner.height)
+                    .unwrap_or_default();
+                cache.captured_lines = cache.content.lines().count();
+                cache.parsed_text = None;
+                cache.session_id = Some(id.clone());
+                cache.dimensions = (inner.width, inner.height);
+                cache.last_refresh = Instant::now();
+            }
+            cache.ensure_parsed();
+            crate::tui::components::preview::render_pane_tail(
+                frame,
+                inner,
+                cache.parsed_text.as_ref(),
+                theme,
+            );
+        }
+        self.wall = Some(wall);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_is_square_ish() {
+        assert_eq!(grid(2), (2, 1));
+        assert_eq!(grid(3), (2, 2));
+        assert_eq!(grid(4), (2, 2));
+        assert_eq!(grid(5), (3, 2));
+        assert_eq!(grid(9), (3, 3));
+    }
+
+    #[test]
+    fn short_last_row_stretches() {
+        let rects = tile_rects(Rect::new(0, 0, 90, 20), 5);
+        assert_eq!(rects.len(), 5);
+        assert_eq!(rects[0].width, 30);
+        assert_eq!(rects[3].width, 45);
+        assert_eq!(rects[4].y, 10);
+    }
+
+    #[test]
+    fn selection_moves_within_the_grid() {
+        let mut wall = WallState::new((0..5).map(|i| i.to_string()).collect());
+        wall.step(1, 0);
+        wall.step(1, 0);
+        assert_eq!(wall.selected, 2);
+        wall.step(1, 0);
+        assert_eq!(wall.selected, 2, "stops at the right edge");
+        wall.step(0, 1);
+        assert_eq!(wall.selected, 2, "no tile below the third column");
+        wall.selected = 0;
+        wall.step(0, 1);
+        assert_eq!(wall.selected, 3);
+    }
+
+    #[test]
+    fn candidates_past_the_limit_are_counted() {
+        let wall = WallState::new((0..12).map(|i| i.to_string()).collect());
+        assert_eq!(wall.ids.len(), WALL_MAX_TILES);
+        assert_eq!(wall.overflow, 3);
+    }
+}
@@ -151,6 +151,9 @@ pub enum FieldKey {
     CockpitMaxConcurrentWorkers,
     CockpitReplayEvents,
     CockpitReplayBytes,
+    CockpitHistoryMaxAgeDays,
+    CockpitHistoryMaxBytes,
+    CockpitHistoryKeepArchived,
     CockpitNodePath,
     CockpitShowToolDurations,
     CockpitQueueDrainMode,
@@ -629,6 +632,21 @@ fn build_cockpit_fields(
         global.cockpit.replay_bytes,
         p.and_then(|c| c.replay_bytes),
     );
+    let (history_max_age_days, hma_override) = resolve_value(
+        scope,
+        global.cockpit.history_max_age_days,
+        p.and_then(|c| c.history_max_age_days),
+    );
+    let (history_max_bytes, hmb_override) = resolve_value(
+        scope,
+        global.cockpit.history_max_bytes,
+        p.and_then(|c| c.history_max_bytes),
+    );
+    let (history_keep_archived, hka_override) = resolve_value(
+        scope,
+        global.cockpit.history_keep_archived,
+        p.and_then(|c| c.history_keep_archived),
+    );
     let (node_path, np_override) = resolve_value(
         scope,
         global.cockpit.node_path.clone(),
@@ -702,6 +720,33 @@ fn build_cockpit_fields(
             has_override: re_override,
             inherited_display: None,
         },
+        SettingField {
+            key: FieldKey::CockpitHistoryMaxAgeDays,
+            label: "History max age (days)",
+            description: "Drop cockpit transcript events older than this many days. Applied by `aoe cockpit gc` and every few hours by `aoe serve`. 0 = keep forever (default).",
+            value: FieldValue::Number(u64::from(history_max_age_days)),
+            category: SettingsCategory::Cockpit,
+            has_override: hma_override,
+            inherited_display: None,
+        },
+        SettingField {
+            key: FieldKey::CockpitHistoryMaxBytes,
+            label: "History size budget (bytes)",
+            description: "Bytes of cockpit transcript this profile may keep in the event database; the oldest events are dropped first once it is exceeded. 0 = no budget (default).",
+            value: FieldValue::Number(history_max_bytes),
+            category: SettingsCategory::Cockpit,
+            has_override: hmb_override,
+            inherited_display: None,
+        },
+        SettingField {
+            key: FieldKey::CockpitHistoryKeepArchived,
+            label: "Keep archived history",
+            description: "Exempt archived sessions from the history age and size limits, so archiving a session keeps its transcript.",
+            value: FieldValue::Bool(history_keep_archived),
+            category: SettingsCategory::Cockpit,
+            has_override: hka_override,
+            inherited_display: None,
+        },
         SettingField {
             key: FieldKey::CockpitNodePath,
             label: "Node path",
@@ -2785,6 +2830,15 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::CockpitReplayBytes, FieldValue::Number(v)) => {
             config.cockpit.replay_bytes = (*v).max(1024)
         }
+        (FieldKey::CockpitHistoryMaxAgeDays, FieldValue::Number(v)) => {
+            config.cockpit.history_max_age_days = (*v).min(u32::MAX as u64) as u32
+        }
+        (FieldKey::CockpitHistoryMaxBytes, FieldValue::Number(v)) => {
+            config.cockpit.history_max_bytes = *v
+        }
+        (FieldKey::CockpitHistoryKeepArchived, FieldValue::Bool(v)) => {
+            config.cockpit.history_keep_archived = *v
+        }
         (FieldKey::CockpitNodePath, FieldValue::Text(v)) => config.cockpit.node_path = v.clone(),
         (FieldKey::CockpitShowToolDurations, FieldValue::Bool(v)) => {
             config.cockpit.show_tool_durations = *v
@@ -3306,6 +3360,21 @@ fn apply_field_to_profile(field: &SettingField, _global: &Config, config: &mut P
                 s.replay_bytes = val
             });
         }
+        (FieldKey::CockpitHistoryMaxAgeDays, FieldValue::Number(v)) => {
+            set_profile_override(
+                (*v).min(u32::MAX as u64) as u32,
+                &mut config.cockpit,
+                |s, val| s.history_max_age_days = val,
+            );
+        }
+        (FieldKey::CockpitHistoryMaxBytes, FieldValue::Number(v)) => {
+            set_profile_override(*v, &mut config.cockpit, |s, val| s.history_max_bytes = val);
+        }
+        (FieldKey::CockpitHistoryKeepArchived, FieldValue::Bool(v)) => {
+            set_profile_override(*v, &mut config.cockpit, |s, val| {
+                s.history_keep_archived = val
+            });
+        }
         (FieldKey::CockpitNodePath, FieldValue::Text(v)) => {
             set_profile_override(v.clone(), &mut config.cockpit, |s, val| s.node_path = val);
         }
@@ -3729,6 +3798,9 @@ mod tests {
             FieldKey::CockpitDefaultForClaude,
             FieldKey::CockpitDefaultAgent,
             FieldKey::CockpitReplayEvents,
+            FieldKey::CockpitHistoryMaxAgeDays,
+            FieldKey::CockpitHistoryMaxBytes,
+            FieldKey::CockpitHistoryKeepArchived,
             FieldKey::CockpitNodePath,
             FieldKey::CockpitShowToolDurations,
         ];
@@ -1017,6 +1017,21 @@ impl SettingsView {
                     c.replay_bytes = None;
                 }
             }
+            FieldKey::CockpitHistoryMaxAgeDays => {
+                if let Some(c) = config.cockpit.as_mut() {
+                    c.history_max_age_days = None;
+                }
+            }
+            FieldKey::CockpitHistoryMaxBytes => {
+                if let Some(c) = config.cockpit.as_mut() {
+                    c.history_max_bytes = None;
+                }
+            }
+            FieldKey::CockpitHistoryKeepArchived => {
+                if let Some(c) = config.cockpit.as_mut() {
+                    c.history_keep_archived = None;
+                }
+            }
             FieldKey::CockpitNodePath => {
                 if let Some(c) = config.cockpit.as_mut() {
                     c.node_path = None;
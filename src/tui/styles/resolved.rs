@@ -30,6 +30,7 @@ use super::{load_theme, Theme, ThemeAppearance};
 /// label the picker entry (e.g. "(custom)" vs the builtin name) and to
 /// decide whether unknown-theme fallback paths fired.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ResolvedThemeSource {
     Builtin,
@@ -44,6 +45,7 @@ pub enum ResolvedThemeSource {
 /// bare map, which gives room to add per-surface metadata (e.g. a
 /// `colorScheme` hint) later without breaking the wire format.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
 pub struct CssVarProjection {
     #[serde(rename = "cssVars")]
     pub css_vars: BTreeMap<String, String>,
@@ -52,6 +54,7 @@ pub struct CssVarProjection {
 /// Syntax-highlighter projection. Web loads the named shiki theme on
 /// theme switch.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
 pub struct SyntaxProjection {
     #[serde(rename = "shikiTheme")]
     pub shiki_theme: String,
@@ -70,6 +73,7 @@ pub struct SyntaxProjection {
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
 pub struct ResolvedTheme {
     pub name: String,
     pub source: ResolvedThemeSource,
@@ -12,6 +12,7 @@ use super::palette::color_to_palette;
 /// background, light themes darken from background) and selects the
 /// fallback syntax highlighter theme when none is specified.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "serve", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ThemeAppearance {
     Dark,
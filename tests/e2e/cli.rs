@@ -1233,3 +1233,48 @@ fn test_cli_add_scratch_conflicts_with_worktree_flag() {
         stderr,
     );
 }
+
+#[test]
+#[serial]
+fn test_cli_mcp_lists_sessions_over_stdio() {
+    let h = TuiTestHarness::new("cli_mcp_list");
+    let project = h.project_path();
+
+    let add_output = h.run_cli(&["add", project.to_str().unwrap(), "-t", "McpWorker"]);
+    assert!(
+        add_output.status.success(),
+        "aoe add failed: {}",
+        String::from_utf8_lossy(&add_output.stderr)
+    );
+
+    let requests = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"e2e","version":"0"}}}"#,
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"list_sessions","arguments":{}}}"#,
+    ]
+    .join("\n");
+    let output = h.run_cli_with_stdin(&["mcp"], &(requests + "\n"));
+    assert!(
+        output.status.success(),
+        "aoe mcp failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Every stdout line must be a JSON-RPC message; anything else would
+    // corrupt the client's framing.
+    let replies: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap_or_else(|e| panic!("non-JSON line {l:?}: {e}")))
+        .collect();
+    assert_eq!(replies.len(), 2, "one reply per request: {replies:?}");
+
+    let list = replies.iter().find(|r| r["id"] == 2).expect("list reply");
+    assert_eq!(list["result"]["isError"], false);
+    let sessions = list["result"]["structuredContent"]["sessions"]
+        .as_array()
+        .unwrap();
+    assert!(
+        sessions.iter().any(|s| s["title"] == "McpWorker"),
+        "list_sessions should include the added session: {list}"
+    );
+}
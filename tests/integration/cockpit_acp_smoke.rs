@@ -9,14 +9,16 @@
 //! requires Node anyway, so on a real cockpit-enabled build environment
 //! this test runs).
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use agent_of_empires::cockpit::acp_client::{AcpClient, SpawnConfig};
 use agent_of_empires::cockpit::agent_registry::AgentSpec;
 use agent_of_empires::cockpit::approvals::ApprovalDecision;
 use agent_of_empires::cockpit::state::{CockpitSessionId, Event};
+use serial_test::serial;
 
-use crate::common::{shim_path, shim_ready};
+use crate::common::{set_temp_home, shim_path, shim_ready};
 
 #[tokio::test]
 async fn shim_agent_round_trips_prompt() {
@@ -34,6 +36,7 @@ async fn shim_agent_round_trips_prompt() {
             args: vec![shim.to_string_lossy().to_string()],
             description: "test shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd,
         additional_dirs: vec![],
@@ -154,6 +157,7 @@ async fn shim_agent_round_trips_approval_allow() {
             args: vec![shim.to_string_lossy().to_string()],
             description: "test shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd,
         additional_dirs: vec![],
@@ -250,6 +254,7 @@ async fn shim_agent_round_trips_fs() {
             args: vec![shim.to_string_lossy().to_string()],
             description: "test shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd: cwd.clone(),
         additional_dirs: vec![],
@@ -320,6 +325,7 @@ async fn shim_agent_round_trips_terminal() {
             args: vec![shim.to_string_lossy().to_string()],
             description: "test shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd: cwd.clone(),
         additional_dirs: vec![],
@@ -402,6 +408,7 @@ async fn shim_agent_set_mode_emits_current_mode_changed() {
             args: vec![shim.to_string_lossy().to_string()],
             description: "test shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd,
         additional_dirs: vec![],
@@ -468,6 +475,7 @@ async fn shim_agent_emits_rate_limit_event() {
             args: vec![shim.to_string_lossy().to_string()],
             description: "test shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd,
         additional_dirs: vec![],
@@ -533,3 +541,177 @@ async fn shim_agent_emits_rate_limit_event() {
         "rate-limit must NOT surface as AgentStartupError; got {startup_error_count}"
     );
 }
+
+/// Host (unsandboxed) session spawned under a profile, with `env` passed
+/// to the shim.
+fn host_spawn_config(cwd: PathBuf, profile: &str, env: Vec<(String, String)>) -> SpawnConfig {
+    SpawnConfig {
+        agent_key: "claude".into(),
+        spec: AgentSpec {
+            command: "node".into(),
+            args: vec![shim_path().to_string_lossy().to_string()],
+            description: "test shim".into(),
+            env_allowlist: None,
+            acp_socket: false,
+        },
+        cwd,
+        additional_dirs: vec![],
+        provider_env: env,
+        socket_path: None,
+        stored_acp_session_id: None,
+        sandbox_info: None,
+        source_profile: Some(profile.into()),
+    }
+}
+
+fn write_profile_config(profile: &str, toml: &str) {
+    let dir = agent_of_empires::session::get_profile_dir(profile).expect("profile dir");
+    std::fs::write(dir.join("config.toml"), toml).expect("write profile config");
+}
+
+/// Profile-level approval rules apply to host sessions, not just
+/// sandboxed ones: the deny rule answers the shim's edit request without
+/// an approval card.
+#[tokio::test]
+#[serial]
+async fn host_session_applies_profile_approval_rules() {
+    if let Err(reason) = shim_ready() {
+        eprintln!("skipping: {reason}");
+        return;
+    }
+    let home = tempfile::tempdir().expect("tempdir");
+    set_temp_home(home.path());
+    write_profile_config(
+        "rules",
+        r#"
+[[cockpit.approval_rules]]
+name = "no shim edits"
+action = "deny"
+kind = ["edit"]
+"#,
+    );
+    let worktree = tempfile::tempdir().expect("tempdir");
+    let config = host_spawn_config(worktree.path().to_path_buf(), "rules", vec![]);
+
+    let mut client = AcpClient::spawn(config, CockpitSessionId("host-rules".into()))
+        .await
+        .expect("spawn shim agent");
+    client
+        .send_prompt("REQUEST_PERMISSION please")
+        .await
+        .expect("send_prompt");
+
+    // Nothing resolves the approval here; only the rule can answer it.
+    let mut events: Vec<Event> = Vec::new();
+    let drain_deadline = std::time::Instant::now() + Duration::from_secs(15);
+    while std::time::Instant::now() < drain_deadline {
+        match tokio::time::timeout(Duration::from_millis(500), client.next_event()).await {
+            Ok(Some(event)) => {
+                let stopped = matches!(event, Event::Stopped { .. });
+                events.push(event);
+                if stopped {
+                    break;
+                }
+            }
+            Ok(None) | Err(_) => continue,
+        }
+    }
+    let _ = client.shutdown().await;
+
+    let decided_by_rule = events.iter().any(|e| {
+        matches!(
+            e,
+            Event::ApprovalResolved {
+                decision: ApprovalDecision::Deny,
+                rule: Some(rule),
+                ..
+            } if rule == "no shim edits"
+        )
+    });
+    let saw_no_outcome = events.iter().any(|e| match e {
+        Event::AgentMessageChunk { text } => text.contains("permission_outcome=no"),
+        _ => false,
+    });
+    assert!(
+        decided_by_rule,
+        "expected the profile rule to deny the request; got {events:?}"
+    );
+    assert!(
+        saw_no_outcome,
+        "shim should have echoed permission_outcome=no; got {events:?}"
+    );
+}
+
+/// Host sessions get MCP servers from the profile and the repo config.
+/// Repo stdio servers stay sandbox-only; repo remote servers are sent.
+#[tokio::test]
+#[serial]
+async fn host_session_sends_profile_and_repo_mcp_servers() {
+    if let Err(reason) = shim_ready() {
+        eprintln!("skipping: {reason}");
+        return;
+    }
+    let home = tempfile::tempdir().expect("tempdir");
+    set_temp_home(home.path());
+    write_profile_config(
+        "tools",
+        r#"
+[[cockpit.mcp_servers]]
+name = "profile-stdio"
+command = "profile-mcp"
+"#,
+    );
+    let worktree = tempfile::tempdir().expect("tempdir");
+    let repo_dir = worktree.path().join(".agent-of-empires");
+    std::fs::create_dir_all(&repo_dir).unwrap();
+    std::fs::write(
+        repo_dir.join("config.toml"),
+        r#"
+[[cockpit.mcp_servers]]
+name = "repo-remote"
+url = "https://mcp.example.com/mcp"
+
+[[cockpit.mcp_servers]]
+name = "repo-stdio"
+command = "repo-mcp"
+"#,
+    )
+    .unwrap();
+    let record_path = home.path().join("mcp-servers.log");
+    let config = host_spawn_config(
+        worktree.path().to_path_buf(),
+        "tools",
+        vec![(
+            "SHIM_MCP_RECORD_FILE".into(),
+            record_path.to_string_lossy().to_string(),
+        )],
+    );
+
+    let mut client = AcpClient::spawn(config, CockpitSessionId("host-mcp".into()))
+        .await
+        .expect("spawn shim agent");
+    client.send_prompt("hello").await.expect("send_prompt");
+    let drain_deadline = std::time::Instant::now() + Duration::from_secs(15);
+    while std::time::Instant::now() < drain_deadline {
+        match tokio::time::timeout(Duration::from_millis(500), client.next_event()).await {
+            Ok(Some(Event::Stopped { .. })) => break,
+            Ok(Some(_)) | Ok(None) | Err(_) => continue,
+        }
+    }
+    let _ = client.shutdown().await;
+
+    let recorded = std::fs::read_to_string(&record_path).expect("shim records session/new");
+    let names: Vec<&str> = recorded.lines().collect();
+    assert!(
+        names.contains(&"profile-stdio"),
+        "profile server missing: {names:?}"
+    );
+    assert!(
+        names.contains(&"repo-remote"),
+        "repo server missing: {names:?}"
+    );
+    assert!(
+        !names.contains(&"repo-stdio"),
+        "repo stdio server must not start on the host: {names:?}"
+    );
+}
@@ -29,6 +29,7 @@ fn spawn_config_with_shim_env(shim: PathBuf, env: Vec<(String, String)>) -> Spaw
             args: vec![shim.to_string_lossy().to_string()],
             description: "session/delete shim".into(),
             env_allowlist: None,
+            acp_socket: false,
         },
         cwd: std::env::temp_dir(),
         additional_dirs: vec![],
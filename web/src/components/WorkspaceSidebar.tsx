@@ -18,6 +18,7 @@ import {
   Moon,
   Pencil,
   Pin,
+  StickyNote,
 } from "lucide-react";
 import {
   DndContext,
@@ -59,9 +60,11 @@ import { TOUR_ANCHORS, tourAnchor } from "../lib/tourSteps";
 import {
   renameSession,
   setSessionArchive,
+  setSessionNotes,
   setSessionNotifications,
   setSessionPin,
   setSessionSnooze,
+  setSessionTags,
 } from "../lib/api";
 import { useServerDown, OFFLINE_TITLE } from "../lib/connectionState";
 import { useClampedMenuPosition } from "../lib/menuPosition";
@@ -78,6 +81,7 @@ import {
   workspaceIsSunk,
   type SidebarSortMode,
 } from "../lib/sidebarSort";
+import { isNestedWorkspace, subSessionSummary } from "../lib/subSessions";
 import { StatusGlyph } from "./StatusGlyph";
 import { OwnerAvatar } from "./OwnerAvatar";
 
@@ -409,6 +413,7 @@ function SortableSessionRow(props: {
   onDelete?: (workspaceId: string) => void;
   readOnly?: boolean;
   dragDisabled?: boolean;
+  nested?: boolean;
 }) {
   const dragSuppressRef = useDragSuppressRef();
   // `disabled` no-ops the sensor listeners. `readOnly` covers viewers
@@ -534,6 +539,7 @@ export const SessionRow = memo(function SessionRow({
   onDelete,
   readOnly,
   indented,
+  nested,
 }: {
   workspace: Workspace;
   isActive: boolean;
@@ -541,6 +547,9 @@ export const SessionRow = memo(function SessionRow({
   onDelete?: (workspaceId: string) => void;
   readOnly?: boolean;
   indented?: boolean;
+  /** Sub-session row rendered under its coordinator; indents one level
+   *  deeper than `indented`. */
+  nested?: boolean;
 }) {
   const idleDecayWindowMs = useIdleDecayWindowMs();
   const { status: sessionStatus, createdAt, idleEnteredAt } = bestSession(
@@ -741,6 +750,15 @@ export const SessionRow = memo(function SessionRow({
   const [renaming, setRenaming] = useState(false);
   const [renameValue, setRenameValue] = useState(label);
   const renameRef = useRef<HTMLInputElement>(null);
+  const [editingTags, setEditingTags] = useState(false);
+  const [tagsValue, setTagsValue] = useState("");
+  const tagsRef = useRef<HTMLInputElement>(null);
+  const sessionTags = firstSession?.tags ?? [];
+  const issue = firstSession?.issue ?? null;
+  const [editingNotes, setEditingNotes] = useState(false);
+  const [notesValue, setNotesValue] = useState("");
+  const notesRef = useRef<HTMLTextAreaElement>(null);
+  const sessionNotes = firstSession?.notes ?? "";
   const longPressTimer = useRef<ReturnType<typeof setTimeout> | null>(null);
   const longPressFired = useRef(false);
   const touchOpenedAt = useRef(0);
@@ -756,6 +774,17 @@ export const SessionRow = memo(function SessionRow({
     if (renaming) renameRef.current?.select();
   }, [renaming]);
 
+  useEffect(() => {
+    if (editingTags) tagsRef.current?.focus();
+  }, [editingTags]);
+
+  useEffect(() => {
+    const el = notesRef.current;
+    if (!editingNotes || !el) return;
+    el.focus();
+    el.setSelectionRange(el.value.length, el.value.length);
+  }, [editingNotes]);
+
   useClampedMenuPosition(contextMenu, menuRef, setContextMenu);
 
   useEffect(() => {
@@ -841,14 +870,93 @@ export const SessionRow = memo(function SessionRow({
     await renameSession(sessionId, trimmed);
   };
 
+  const startEditTags = () => {
+    if (editingTags) return;
+    setContextMenu(null);
+    setTagsValue(sessionTags.join(", "));
+    setEditingTags(true);
+  };
+
+  // Send the edit as a diff against the tags the editor opened with, so a
+  // tag added elsewhere in the meantime survives.
+  const commitTags = async () => {
+    setEditingTags(false);
+    if (!sessionId) return;
+    const next = tagsValue
+      .split(",")
+      .map((t) => t.trim())
+      .filter((t) => t.length > 0);
+    const add = next.filter((t) => !sessionTags.includes(t));
+    const remove = sessionTags.filter((t) => !next.includes(t));
+    if (add.length === 0 && remove.length === 0) return;
+    const result = await setSessionTags(sessionId, { add, remove });
+    if (!result) reportError("Failed to update tags");
+  };
+
+  const startEditNotes = () => {
+    if (editingNotes) return;
+    setContextMenu(null);
+    setNotesValue(sessionNotes);
+    setEditingNotes(true);
+  };
+
+  const commitNotes = async () => {
+    setEditingNotes(false);
+    if (!sessionId || notesValue.trimEnd() === sessionNotes) return;
+    const result = await setSessionNotes(sessionId, notesValue);
+    if (!result) reportError("Failed to save notes");
+  };
+
   const handleDelete = () => {
     setContextMenu(null);
     onDelete?.(workspace.id);
   };
 
+  if (editingNotes) {
+    return (
+      <div className={`py-1 ${nested ? "pl-10 pr-3" : indented ? "pl-6 pr-3" : "px-3"}`}>
+        <textarea
+          ref={notesRef}
+          rows={6}
+          value={notesValue}
+          placeholder="Notes (markdown). Ctrl+Enter to save, Esc to discard."
+          onChange={(e) => setNotesValue(e.target.value)}
+          onBlur={commitNotes}
+          onKeyDown={(e) => {
+            if (e.key === "Enter" && (e.metaKey || e.ctrlKey)) commitNotes();
+            if (e.key === "Escape") setEditingNotes(false);
+          }}
+          data-testid="sidebar-notes-input"
+          className="w-full resize-y bg-surface-900 border border-brand-600 rounded px-2 py-1 text-[13px] md:text-[14px] font-mono text-text-primary focus:outline-none"
+        />
+      </div>
+    );
+  }
+
+  if (editingTags) {
+    return (
+      <div className={`py-1 ${nested ? "pl-10 pr-3" : indented ? "pl-6 pr-3" : "px-3"}`}>
+        <input
+          ref={tagsRef}
+          type="text"
+          value={tagsValue}
+          placeholder="blocked-ci, reviewer=alice"
+          onChange={(e) => setTagsValue(e.target.value)}
+          onBlur={commitTags}
+          onKeyDown={(e) => {
+            if (e.key === "Enter") commitTags();
+            if (e.key === "Escape") setEditingTags(false);
+          }}
+          data-testid="sidebar-tags-input"
+          className="w-full bg-surface-900 border border-brand-600 rounded px-2 py-1 text-[13px] md:text-[14px] font-mono text-text-primary focus:outline-none"
+        />
+      </div>
+    );
+  }
+
   if (renaming) {
     return (
-      <div className={`py-1 ${indented ? "pl-6 pr-3" : "px-3"}`}>
+      <div className={`py-1 ${nested ? "pl-10 pr-3" : indented ? "pl-6 pr-3" : "px-3"}`}>
         <input
           ref={renameRef}
           type="text"
@@ -901,7 +1009,7 @@ export const SessionRow = memo(function SessionRow({
         onTouchMove={clearLongPress}
         onTouchCancel={clearLongPress}
         className={`block w-full text-left py-2 cursor-pointer select-none [-webkit-touch-callout:none] transition-colors duration-75 ${
-          indented ? "pl-6 pr-3" : "px-3"
+          nested ? "pl-10 pr-3" : indented ? "pl-6 pr-3" : "px-3"
         } ${
           isActive
             ? "bg-surface-850 border-l-2 border-brand-600"
@@ -939,6 +1047,16 @@ export const SessionRow = memo(function SessionRow({
                 </span>
               )}
               <span className="truncate" title={label}>{label}</span>
+              {sessionNotes && (
+                <span
+                  title={sessionNotes}
+                  aria-label="Has notes"
+                  data-testid="sidebar-session-notes"
+                  className="inline-flex shrink-0"
+                >
+                  <StickyNote className="h-3 w-3 text-text-dim" />
+                </span>
+              )}
               {hasDraft && (
                 <span
                   title="Unsent draft"
@@ -988,6 +1106,17 @@ export const SessionRow = memo(function SessionRow({
                     Resuming
                   </span>
                 )}
+              {workspace.subSessions && (
+                <span
+                  title={`Sub-sessions: ${subSessionSummary(workspace.subSessions)}`}
+                  aria-label={`${workspace.subSessions.total} sub-sessions`}
+                  className="inline-flex shrink-0 items-center rounded border border-surface-700/40 bg-surface-800/40 px-1 text-[10px] font-mono font-medium tabular-nums text-text-dim"
+                >
+                  {workspace.subSessions.running > 0
+                    ? `${workspace.subSessions.running}/${workspace.subSessions.total}`
+                    : workspace.subSessions.total}
+                </span>
+              )}
               {firstSession?.next_wakeup_at && (
                 <WakeupCountdown
                   wakeAt={firstSession.next_wakeup_at}
@@ -1035,6 +1164,36 @@ export const SessionRow = memo(function SessionRow({
                 ))}
               </span>
             )}
+            {(issue || sessionTags.length > 0) && (
+              <span
+                className="mt-0.5 flex flex-wrap gap-1 text-[10px] font-mono text-brand-400"
+                data-testid="sidebar-session-tags"
+              >
+                {issue && (
+                  // The row is itself a link, so the issue opens from a
+                  // click handler rather than a nested <a>.
+                  <span
+                    role={issue.url ? "link" : undefined}
+                    title={issue.url ?? issue.key}
+                    data-testid="sidebar-session-issue"
+                    className={`text-text-secondary ${issue.url ? "underline decoration-dotted cursor-pointer hover:text-text-primary" : ""}`}
+                    onClick={(e) => {
+                      if (!issue.url) return;
+                      e.preventDefault();
+                      e.stopPropagation();
+                      window.open(issue.url, "_blank", "noopener,noreferrer");
+                    }}
+                  >
+                    {issue.key}
+                  </span>
+                )}
+                {sessionTags.map((tag) => (
+                  <span key={tag} className="truncate max-w-[10rem]" title={tag}>
+                    #{tag}
+                  </span>
+                ))}
+              </span>
+            )}
           </div>
         </div>
       </a>
@@ -1056,6 +1215,24 @@ export const SessionRow = memo(function SessionRow({
           >
             Rename
           </button>
+          {!readOnly && (
+            <button
+              onClick={startEditTags}
+              data-testid="sidebar-context-menu-tags"
+              className="w-full text-left px-3 py-2 md:py-2 max-md:py-3 text-sm text-text-secondary hover:bg-surface-700/50 cursor-pointer transition-colors"
+            >
+              Edit tags
+            </button>
+          )}
+          {!readOnly && (
+            <button
+              onClick={startEditNotes}
+              data-testid="sidebar-context-menu-notes"
+              className="w-full text-left px-3 py-2 md:py-2 max-md:py-3 text-sm text-text-secondary hover:bg-surface-700/50 cursor-pointer transition-colors"
+            >
+              {sessionNotes ? "Edit notes" : "Add notes"}
+            </button>
+          )}
           <div className="border-t border-surface-700/20 my-1" />
           <div className="px-3 py-1 text-[11px] font-mono uppercase tracking-widest text-text-muted">
             Notifications
@@ -2070,14 +2247,18 @@ export function WorkspaceSidebar({
                                 }}
                                 onDelete={onDeleteSession}
                                 readOnly={readOnly}
+                                nested={isNestedWorkspace(ws, liveWorkspaces)}
                                 // Drag is disabled when the tier
                                 // comparator already controls placement:
                                 // lastActivity mode has no manual
                                 // concept, pinned rows always float to
-                                // the top of their group. See #1581.
+                                // the top of their group, and
+                                // sub-sessions follow their coordinator.
+                                // See #1581.
                                 dragDisabled={
                                   sortMode === "lastActivity" ||
-                                  workspaceIsPinned(ws)
+                                  workspaceIsPinned(ws) ||
+                                  isNestedWorkspace(ws, liveWorkspaces)
                                 }
                               />
                             ))}
@@ -521,3 +521,26 @@ describe("activityToThreadMessages; stopped status threading (#1646)", () => {
     expect(payload.children[0].result.stopped).toBe(true);
   });
 });
+
+describe("activityToThreadMessages; live tool output", () => {
+  it("forwards streamed output for a running tool call", () => {
+    const messages = activityToThreadMessages(
+      [userRow("go"), toolStart("t1", "execute")],
+      true,
+      false,
+      { t1: "compiling...\n" },
+    );
+    const assistant = messages.find((m) => m.role === "assistant")!;
+    const parts = assistant.content as Array<{ type: string; argsText?: string }>;
+    const tool = parts.find((p) => p.type === "tool-call")!;
+    expect(JSON.parse(tool.argsText!)._aoe_live_output).toBe("compiling...\n");
+  });
+
+  it("omits the live output key when nothing streamed", () => {
+    const messages = activityToThreadMessages([userRow("go"), toolStart("t1")], true);
+    const assistant = messages.find((m) => m.role === "assistant")!;
+    const parts = assistant.content as Array<{ type: string; argsText?: string }>;
+    const tool = parts.find((p) => p.type === "tool-call")!;
+    expect(JSON.parse(tool.argsText!)).not.toHaveProperty("_aoe_live_output");
+  });
+});
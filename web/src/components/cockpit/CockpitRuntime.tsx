@@ -122,8 +122,14 @@ export function CockpitRuntime({
         cockpit.state.activity,
         cockpit.state.turnActive,
         showClearedTurns,
+        cockpit.state.toolOutputs,
       ),
-    [cockpit.state.activity, cockpit.state.turnActive, showClearedTurns],
+    [
+      cockpit.state.activity,
+      cockpit.state.turnActive,
+      showClearedTurns,
+      cockpit.state.toolOutputs,
+    ],
   );
 
   const runtime = useExternalStoreRuntime<ThreadMessageLike>({
@@ -186,11 +192,16 @@ export function CockpitRuntime({
  * own messages; they update the matching `tool-call` part in place
  * by setting `result` / `isError`, so the per-tool card renderer can
  * render running → done in one place.
+ *
+ * `toolOutputs` holds output streamed for still-running tool calls
+ * (e.g. a long build in an ACP terminal); it rides on the tool-call
+ * part so the card can show it instead of a bare "Running…".
  */
 export function activityToThreadMessages(
   rows: readonly ActivityRow[],
   turnActive: boolean,
   showClearedTurns = false,
+  toolOutputs: Readonly<Record<string, string>> = {},
 ): ThreadMessageLike[] {
   // Fold pre-clear turns by default. When the user has run `/clear`,
   // earlier rows describe a conversation the model has forgotten; the
@@ -296,7 +307,7 @@ export function activityToThreadMessages(
     if (row.kind === "message") {
       currentAssistant.appendText(row.text);
     } else if (row.kind === "tool_start" && row.tool) {
-      currentAssistant.appendToolCall(row.tool);
+      currentAssistant.appendToolCall(row.tool, toolOutputs[row.tool.id]);
     } else if (
       row.kind === "tool_complete" ||
       row.kind === "tool_error" ||
@@ -387,7 +398,7 @@ class AssistantBuilder {
     }
   }
 
-  appendToolCall(tool: ToolCall) {
+  appendToolCall(tool: ToolCall, liveOutput?: string) {
     // Forward the ACP tool title alongside the args so per-kind
     // renderers can show a descriptive label when raw_input is
     // empty (Claude's bash tool, for example, often emits an empty
@@ -413,6 +424,7 @@ class AssistantBuilder {
     if (tool.parent_tool_call_id) {
       argsObj._aoe_parent_tool_call_id = tool.parent_tool_call_id;
     }
+    if (liveOutput) argsObj._aoe_live_output = liveOutput;
     this.parts.push({
       type: "tool-call",
       toolCallId: tool.id,
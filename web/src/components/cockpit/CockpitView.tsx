@@ -13,7 +13,15 @@
 // CockpitRuntime.tsx. We never let assistant-ui own the chat state; it
 // only renders what we feed it and surfaces user actions back.
 
-import { Fragment, useEffect, useLayoutEffect, useRef, useState } from "react";
+import {
+  Fragment,
+  createContext,
+  useContext,
+  useEffect,
+  useLayoutEffect,
+  useRef,
+  useState,
+} from "react";
 import {
   MessagePrimitive,
   ThreadPrimitive,
@@ -64,6 +72,7 @@ import {
   chooseVerb,
 } from "../../lib/cockpitRattle";
 import { useCockpitPrefs } from "../../lib/cockpitPrefs";
+import { fetchCockpitCheckpoints, rewindCockpit } from "../../lib/api";
 import {
   AgentProfileProvider,
   useAgentProfile,
@@ -219,6 +228,31 @@ function CockpitChrome({
       text,
     });
 
+  // "Rewind to here" on a user message: restore the files to before
+  // that prompt, restart the agent with a recap, and put the prompt
+  // back in the composer so it can be edited and resent.
+  const rewindTo = async (seq: number) => {
+    const checkpoint = (await fetchCockpitCheckpoints(sessionId)).find(
+      (c) => c.seq === seq,
+    );
+    if (!checkpoint) {
+      window.alert(
+        "No checkpoint for this prompt. Checkpoints are only recorded for sessions in a git repository.",
+      );
+      return;
+    }
+    const ok = window.confirm(
+      `Rewind to before turn ${checkpoint.turn}? Files in the worktree are restored to how they were before this prompt, and the agent restarts with a recap of the earlier turns.`,
+    );
+    if (!ok) return;
+    try {
+      const resp = await rewindCockpit(sessionId, checkpoint.turn);
+      setPrimerPrefill({ id: `rewind-${resp.rewound_seq}`, text: resp.prompt });
+    } catch (e) {
+      window.alert(e instanceof Error ? e.message : "Rewind failed.");
+    }
+  };
+
   // Browser-side approval chime. Fires once on the 0 -> >=1 edge of
   // pendingApprovals; complements the OS push (delivered via the SW
   // when the dashboard is backgrounded) and the in-app toast (when
@@ -385,12 +419,14 @@ function CockpitChrome({
               />
             )}
 
-            <ThreadPrimitive.Messages
-              components={{
-                UserMessage,
-                AssistantMessage,
-              }}
-            />
+            <RewindContext.Provider value={rewindTo}>
+              <ThreadPrimitive.Messages
+                components={{
+                  UserMessage,
+                  AssistantMessage,
+                }}
+              />
+            </RewindContext.Provider>
 
             <ThreadPrimitive.If running>
               <div className="mt-3 ml-1">
@@ -480,7 +516,17 @@ function CockpitChrome({
 
 /* ── User & Assistant message templates ──────────────────────────── */
 
+/** Rewind handler for user messages, keyed by the prompt's seq. */
+const RewindContext = createContext<((seq: number) => void) | null>(null);
+
 function UserMessage() {
+  const rewindTo = useContext(RewindContext);
+  // Server-confirmed prompts carry their `UserPromptSent` seq in the
+  // row id; optimistic rows don't have a checkpoint yet.
+  const seq = useMessage((m) => {
+    const match = /^user-seq-(\d+)$/.exec(m.id);
+    return match ? Number(match[1]) : null;
+  });
   return (
     <MessagePrimitive.Root className="group mt-4 flex flex-col items-end gap-1">
       <MessagePrimitive.Parts
@@ -488,6 +534,17 @@ function UserMessage() {
           Text: UserText,
         }}
       />
+      {rewindTo && seq !== null && (
+        <button
+          type="button"
+          onClick={() => rewindTo(seq)}
+          className="flex items-center gap-1 text-xs text-text-muted opacity-0 transition-opacity hover:text-text-primary focus-visible:opacity-100 group-hover:opacity-100"
+          title="Restore files to before this prompt and restart the conversation here"
+        >
+          <RotateCcw className="h-3 w-3" />
+          Rewind to here
+        </button>
+      )}
     </MessagePrimitive.Root>
   );
 }
@@ -71,13 +71,14 @@ interface Props {
 
 /** Keys CockpitRuntime smuggles through `args_preview` for renderer
  *  bookkeeping (the ACP title, the real `started_at` for the duration
- *  label, the sub-agent parent tool-call id). Excluded from any
- *  user-visible input JSON dumps. */
+ *  label, the sub-agent parent tool-call id, output streamed while the
+ *  tool runs). Excluded from any user-visible input JSON dumps. */
 function isCockpitBookkeepingKey(key: string): boolean {
   return (
     key === "_aoe_title" ||
     key === "_aoe_started_at" ||
-    key === "_aoe_parent_tool_call_id"
+    key === "_aoe_parent_tool_call_id" ||
+    key === "_aoe_live_output"
   );
 }
 
@@ -634,7 +635,10 @@ function ExecuteToolCard({ tool, result }: Props) {
   const title = pickStr(args, "_aoe_title");
   const command = pickFirst(argCommand, title, tool.name) ?? "(no command)";
   const description = pickStr(args, "description");
-  const output = result?.text ?? "";
+  // While running, show whatever the terminal has streamed so far
+  // (forwarded via _aoe_live_output in CockpitRuntime).
+  const live = status === "running" ? pickStr(args, "_aoe_live_output") : null;
+  const output = result?.text ?? live ?? "";
   const [open, setOpen] = useToolCardExpansion(status);
 
   const meta =
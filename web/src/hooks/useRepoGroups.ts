@@ -17,6 +17,7 @@ import {
   workspaceTriageTier,
   type SidebarSortMode,
 } from "../lib/sidebarSort";
+import { nestSubSessions } from "../lib/subSessions";
 
 const COLLAPSED_KEY_PREFIX = "aoe-repo-collapsed-";
 export const MULTI_REPO_GROUP_ID = "__multi_repo__";
@@ -98,8 +99,11 @@ export function useRepoGroups(
       });
     const sortByActivity = (list: Workspace[]) =>
       [...list].sort(compareWorkspacesByLastActivityDesc);
-    const sortWorkspaces =
-      sortMode === "lastActivity" ? sortByActivity : sortByRank;
+    // Sub-sessions follow their coordinator's row whatever the sort.
+    const sortWorkspaces = (list: Workspace[]) =>
+      nestSubSessions(
+        sortMode === "lastActivity" ? sortByActivity(list) : sortByRank(list),
+      );
 
     const byRepo = new Map<string, Workspace[]>();
     const multiRepo: Workspace[] = [];
@@ -2,6 +2,7 @@ import { useMemo } from "react";
 import type { SessionResponse, Workspace } from "../lib/types";
 import { isSessionActive } from "../lib/session";
 import { useIdleDecayWindowMs } from "../lib/idleDecay";
+import { countSubSessions } from "../lib/subSessions";
 
 /** Strip trailing slashes for consistent grouping */
 function normalizePath(p: string): string {
@@ -72,6 +73,27 @@ export function useWorkspaces(sessions: SessionResponse[]): Workspace[] {
       });
     }
 
+    // Coordinator links are per session; lift them to workspaces so the
+    // sidebar can nest a sub-session's row under its parent's.
+    const workspaceOfSession = new Map<string, string>();
+    for (const ws of workspaces) {
+      for (const s of ws.sessions) workspaceOfSession.set(s.id, ws.id);
+    }
+    for (const ws of workspaces) {
+      const parentId = ws.sessions.find((s) => s.parent_session_id)
+        ?.parent_session_id;
+      const parentWorkspaceId = parentId
+        ? workspaceOfSession.get(parentId)
+        : undefined;
+      if (parentWorkspaceId && parentWorkspaceId !== ws.id) {
+        ws.parentWorkspaceId = parentWorkspaceId;
+      }
+      ws.subSessions = countSubSessions(
+        new Set(ws.sessions.map((s) => s.id)),
+        sessions,
+      );
+    }
+
     return workspaces;
   }, [idleDecayWindowMs, sessions]);
 }
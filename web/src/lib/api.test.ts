@@ -20,8 +20,10 @@ import {
   fetchAbout,
   isDebugBuild,
   setSessionArchive,
+  setSessionNotes,
   setSessionPin,
   setSessionSnooze,
+  setSessionTags,
   type ServerAbout,
 } from "./api";
 
@@ -102,6 +104,37 @@ describe("fetchAbout", () => {
   });
 });
 
+describe("setSessionTags", () => {
+  it("PATCHes /api/sessions/{id}/tags with add and remove lists", async () => {
+    fetchSpy.mockResolvedValueOnce(
+      jsonResponse({ id: "sess-1", tags: ["reviewer=bob"] }),
+    );
+    await setSessionTags("sess-1", { add: ["reviewer=bob"] });
+    const [url, init] = fetchSpy.mock.calls[0]!;
+    expect(url).toBe("/api/sessions/sess-1/tags");
+    expect(init?.method).toBe("PATCH");
+    expect(JSON.parse(init!.body as string)).toEqual({
+      add: ["reviewer=bob"],
+      remove: [],
+    });
+  });
+});
+
+describe("setSessionNotes", () => {
+  it("PUTs /api/sessions/{id}/notes with the full text", async () => {
+    fetchSpy.mockResolvedValueOnce(
+      jsonResponse({ id: "sess-1", notes: "waiting on infra" }),
+    );
+    await setSessionNotes("sess-1", "waiting on infra");
+    const [url, init] = fetchSpy.mock.calls[0]!;
+    expect(url).toBe("/api/sessions/sess-1/notes");
+    expect(init?.method).toBe("PUT");
+    expect(JSON.parse(init!.body as string)).toEqual({
+      notes: "waiting on infra",
+    });
+  });
+});
+
 describe("setSessionPin", () => {
   it("PATCHes /api/sessions/{id}/pin with the pinned bool", async () => {
     fetchSpy.mockResolvedValueOnce(
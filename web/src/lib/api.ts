@@ -476,6 +476,60 @@ export function fetchContextPrimer(
   );
 }
 
+// --- Cockpit checkpoints ---
+
+export interface CockpitCheckpoint {
+  turn: number;
+  /** Seq of the `UserPromptSent` the checkpoint was taken before. */
+  seq: number;
+  commit: string;
+  prompt: string;
+  created_at: string;
+}
+
+export interface RewindResponse {
+  session_id: string;
+  turn: number;
+  checkpoint_seq: number;
+  rewound_seq: number;
+  /** The rewound turn's prompt, for dropping back into the composer. */
+  prompt: string;
+  primed: boolean;
+}
+
+/** Turn checkpoints for a cockpit session, oldest first. Empty when
+ *  the worktree isn't a git repository. */
+export async function fetchCockpitCheckpoints(
+  sessionId: string,
+): Promise<CockpitCheckpoint[]> {
+  return (
+    (await fetchJson<CockpitCheckpoint[]>(
+      `/api/sessions/${encodeURIComponent(sessionId)}/cockpit/checkpoints`,
+    )) ?? []
+  );
+}
+
+/** Restore the worktree to before `turn` and restart the agent with a
+ *  recap of the earlier turns. Throws with the server's message on
+ *  failure so the caller can surface it. */
+export async function rewindCockpit(
+  sessionId: string,
+  turn: number,
+): Promise<RewindResponse> {
+  const res = await fetch(
+    `/api/sessions/${encodeURIComponent(sessionId)}/cockpit/rewind`,
+    {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ turn }),
+    },
+  );
+  if (!res.ok) {
+    throw new Error((await res.text()) || `Rewind failed (HTTP ${res.status})`);
+  }
+  return (await res.json()) as RewindResponse;
+}
+
 // --- Devices ---
 
 export interface DeviceInfo {
@@ -903,6 +957,44 @@ export async function setSessionPin(
   }
 }
 
+/** Add and remove session tags. Removals apply first, and a bare key
+ *  removes the `key=value` label with that key. The server validates
+ *  each tag the same way the TUI and CLI do. */
+export async function setSessionTags(
+  id: string,
+  edit: { add?: string[]; remove?: string[] },
+): Promise<SessionResponse | null> {
+  try {
+    const res = await fetch(`/api/sessions/${id}/tags`, {
+      method: "PATCH",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ add: edit.add ?? [], remove: edit.remove ?? [] }),
+    });
+    if (!res.ok) return null;
+    return (await res.json()) as SessionResponse;
+  } catch {
+    return null;
+  }
+}
+
+/** Replace a session's markdown notes. Blank text clears them. */
+export async function setSessionNotes(
+  id: string,
+  notes: string,
+): Promise<SessionResponse | null> {
+  try {
+    const res = await fetch(`/api/sessions/${id}/notes`, {
+      method: "PUT",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ notes }),
+    });
+    if (!res.ok) return null;
+    return (await res.json()) as SessionResponse;
+  } catch {
+    return null;
+  }
+}
+
 /** Archive or unarchive a session. On archive, the server kills the tmux
  *  pane (when `killPane` is true or omitted, matching TUI/CLI semantics)
  *  and shuts down the cockpit worker for cockpit-mode sessions; the
@@ -2096,3 +2096,79 @@ describe("applyEvent / ConfigOptions (#1403)", () => {
     expect(state.configOptions).toHaveLength(2);
   });
 });
+
+describe("applyEvent / approvals", () => {
+  const approval = (nonce: string, policy_rule?: string) => ({
+    nonce,
+    tool_call: {
+      id: `tc-${nonce}`,
+      name: "Bash",
+      kind: "execute",
+      args_preview: '{"command":"cargo test"}',
+      started_at: "2026-10-18T10:00:00Z",
+    },
+    destructive: false,
+    requested_at: "2026-10-18T10:00:00Z",
+    resolved: null,
+    policy_rule,
+  });
+
+  it("surfaces a card until the approval is resolved", () => {
+    let state = applyEvent(emptyCockpitState(), {
+      session_id: "s-1",
+      seq: 1,
+      event: { ApprovalRequested: { approval: approval("n1") } },
+    });
+    expect(state.pendingApprovals.map((a) => a.nonce)).toEqual(["n1"]);
+    state = applyEvent(state, {
+      session_id: "s-1",
+      seq: 2,
+      event: { ApprovalResolved: { nonce: "n1", decision: "Allow" } },
+    });
+    expect(state.pendingApprovals).toEqual([]);
+  });
+
+  it("never surfaces a card for a rule-decided approval", () => {
+    const state = applyEvent(emptyCockpitState(), {
+      session_id: "s-1",
+      seq: 1,
+      event: {
+        ApprovalRequested: { approval: approval("n2", "tests are fine") },
+      },
+    });
+    expect(state.pendingApprovals).toEqual([]);
+  });
+});
+
+describe("applyEvent / Rewound", () => {
+  // `/cockpit/rewind` restores the worktree and restarts the agent on a
+  // fresh ACP session. Earlier rows fold behind a divider and anything
+  // tied to the old turn (approvals, plan, stopped banner) goes away.
+  it("clears turn state and appends a session divider", () => {
+    const seeded: CockpitState = {
+      ...emptyCockpitState(),
+      agent: "claude",
+      thinking: true,
+      workerStopped: true,
+      sessionUsage: { used: 100, size: 200_000 },
+      plan: { plan_id: "p-1", version: 1, steps: [] },
+      mode: "Plan",
+    };
+    const next = applyEvent(seeded, {
+      session_id: "s-1",
+      seq: 20,
+      event: { Rewound: { turn: 3, checkpoint_seq: 12 } },
+    });
+    expect(next.agent).toBe("claude");
+    expect(next.thinking).toBe(false);
+    expect(next.workerStopped).toBe(false);
+    expect(next.sessionUsage).toBeNull();
+    expect(next.plan).toBeNull();
+    expect(next.mode).toBe("Default");
+    expect(next.pendingApprovals).toEqual([]);
+    const lastRow = next.activity[next.activity.length - 1];
+    expect(lastRow?.id).toBe("rewound-20");
+    expect(lastRow?.kind).toBe("session_cleared");
+    expect(lastRow?.text).toContain("turn 3");
+  });
+});
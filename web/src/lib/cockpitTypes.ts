@@ -158,6 +158,9 @@ export interface Approval {
     message?: string | null;
     resolved_at: string;
   } | null;
+  /** Set when a `cockpit.approval_rules` entry answered the request
+   *  without asking; the matching ApprovalResolved follows at once. */
+  policy_rule?: string | null;
 }
 
 /** Mirror of `StartupErrorDetail` in src/cockpit/state.rs. Serde's
@@ -249,7 +252,14 @@ export type CockpitEvent =
       };
     }
   | { ApprovalRequested: { approval: Approval } }
-  | { ApprovalResolved: { nonce: string; decision: ApprovalDecision } }
+  | {
+      ApprovalResolved: {
+        nonce: string;
+        decision: ApprovalDecision;
+        /** Rule that decided the approval; absent for user decisions. */
+        rule?: string | null;
+      };
+    }
   | "SessionCleared"
   | "ConversationCompacted"
   | { DiffEmitted: { diff: DiffPreview } }
@@ -285,7 +295,8 @@ export type CockpitEvent =
   | { SessionContextReset: { reason: string } }
   | { WakeupScheduled: { at: string; reason: string | null } }
   | { PromptRejected: { reason: string; text: string } }
-  | { AgentSwitched: { from: string; to: string; reason: string } };
+  | { AgentSwitched: { from: string; to: string; reason: string } }
+  | { Rewound: { turn: number; checkpoint_seq: number } };
 
 export interface CockpitFrame {
   session_id: string;
@@ -810,6 +821,9 @@ export function applyEvent(
   }
   if ("ApprovalRequested" in event) {
     const a = event.ApprovalRequested.approval;
+    // Rule-decided approvals never need the user; skipping them keeps
+    // the card (and the approval sound) from flashing.
+    if (a.policy_rule) return next;
     next.pendingApprovals = [...next.pendingApprovals, a];
     return next;
   }
@@ -1275,6 +1289,36 @@ export function applyEvent(
     ];
     return next;
   }
+  if ("Rewound" in event) {
+    // `/cockpit/rewind` restored the worktree to a turn's checkpoint
+    // and started a fresh ACP session on the same agent. The model has
+    // forgotten everything, so drop the same state as /clear plus the
+    // turn the old worker was in, and fold earlier rows behind the
+    // divider. The worker restart emits Stopped first; clear the
+    // stopped banners like AgentSwitched does.
+    const { turn } = event.Rewound;
+    sweepOpenToolCalls(next, frame.seq);
+    next.inFlightTool = null;
+    next.thinking = false;
+    next.pendingApprovals = [];
+    next.sessionUsage = null;
+    next.usageBaseline = null;
+    next.plan = null;
+    next.mode = "Default";
+    next.workerStopped = false;
+    next.workerRestarting = false;
+    next.agentUnresponsive = false;
+    next.activity = [
+      ...next.activity,
+      {
+        id: `rewound-${frame.seq}`,
+        kind: "session_cleared",
+        text: `Rewound to before turn ${turn}; files restored and the model no longer remembers earlier turns.`,
+        at: new Date().toISOString(),
+      },
+    ];
+    return next;
+  }
   if ("PromptRejected" in event) {
     // Daemon refused the follow-up prompt because another `session/prompt`
     // was still in flight. The rejected text has already been persisted
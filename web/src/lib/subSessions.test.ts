@@ -0,0 +1,74 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  countSubSessions,
+  isNestedWorkspace,
+  nestSubSessions,
+  subSessionSummary,
+} from "./subSessions";
+import type { SessionResponse, Workspace } from "./types";
+
+function session(
+  id: string,
+  status: string,
+  parent?: string,
+): SessionResponse {
+  return { id, status, parent_session_id: parent ?? null } as SessionResponse;
+}
+
+function workspace(id: string, parentWorkspaceId?: string): Workspace {
+  return { id, parentWorkspaceId, sessions: [] } as unknown as Workspace;
+}
+
+describe("countSubSessions", () => {
+  it("buckets only the parent's children", () => {
+    const counts = countSubSessions(new Set(["p"]), [
+      session("p", "Idle"),
+      session("a", "Running", "p"),
+      session("b", "Starting", "p"),
+      session("c", "Waiting", "p"),
+      session("d", "Idle", "p"),
+      session("e", "Error", "other"),
+    ]);
+    expect(counts).toEqual({
+      total: 4,
+      running: 2,
+      waiting: 1,
+      idle: 1,
+      error: 0,
+      stopped: 0,
+    });
+    expect(subSessionSummary(counts!)).toBe("1 waiting, 2 running, 1 idle");
+  });
+
+  it("returns undefined for a session with no children", () => {
+    expect(countSubSessions(new Set(["p"]), [session("p", "Idle")])).toBe(
+      undefined,
+    );
+  });
+});
+
+describe("nestSubSessions", () => {
+  it("moves children right after their parent", () => {
+    const parent = workspace("parent");
+    const other = workspace("other");
+    const child = workspace("child", "parent");
+    const list = [child, other, parent];
+    expect(nestSubSessions(list).map((w) => w.id)).toEqual([
+      "other",
+      "parent",
+      "child",
+    ]);
+    expect(isNestedWorkspace(child, list)).toBe(true);
+  });
+
+  it("leaves children of an absent parent in place", () => {
+    const orphan = workspace("orphan", "elsewhere");
+    const other = workspace("other");
+    expect(nestSubSessions([orphan, other]).map((w) => w.id)).toEqual([
+      "orphan",
+      "other",
+    ]);
+    expect(isNestedWorkspace(orphan, [orphan, other])).toBe(false);
+  });
+});
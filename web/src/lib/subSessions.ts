@@ -0,0 +1,98 @@
+import type { SessionResponse, Workspace } from "./types";
+
+// Coordinator sub-sessions (`parent_session_id` on the session). The
+// sidebar nests a child workspace right below its parent's row and rolls
+// the children's statuses up into a chip on the parent, mirroring the
+// TUI list (`session::groups` + `session::coordinator`).
+
+/** Children's statuses, bucketed like `ChildStatusCounts` on the server. */
+export interface SubSessionCounts {
+  total: number;
+  running: number;
+  waiting: number;
+  idle: number;
+  error: number;
+  stopped: number;
+}
+
+export function countSubSessions(
+  parentIds: ReadonlySet<string>,
+  sessions: readonly SessionResponse[],
+): SubSessionCounts | undefined {
+  const counts: SubSessionCounts = {
+    total: 0,
+    running: 0,
+    waiting: 0,
+    idle: 0,
+    error: 0,
+    stopped: 0,
+  };
+  for (const s of sessions) {
+    if (!s.parent_session_id || !parentIds.has(s.parent_session_id)) continue;
+    counts.total += 1;
+    switch (s.status) {
+      case "Running":
+      case "Starting":
+      case "Creating":
+        counts.running += 1;
+        break;
+      case "Waiting":
+        counts.waiting += 1;
+        break;
+      case "Error":
+        counts.error += 1;
+        break;
+      case "Stopped":
+      case "Deleting":
+        counts.stopped += 1;
+        break;
+      default:
+        counts.idle += 1;
+    }
+  }
+  return counts.total > 0 ? counts : undefined;
+}
+
+/** Most urgent first, e.g. `1 waiting, 2 running`. Matches the TUI row. */
+export function subSessionSummary(counts: SubSessionCounts): string {
+  return (
+    [
+      [counts.error, "error"],
+      [counts.waiting, "waiting"],
+      [counts.running, "running"],
+      [counts.idle, "idle"],
+      [counts.stopped, "stopped"],
+    ] as const
+  )
+    .filter(([n]) => n > 0)
+    .map(([n, label]) => `${n} ${label}`)
+    .join(", ");
+}
+
+/** True when `ws` renders nested under a parent row in `list`. */
+export function isNestedWorkspace(
+  ws: Workspace,
+  list: readonly Workspace[],
+): boolean {
+  const parentId = ws.parentWorkspaceId;
+  return !!parentId && list.some((w) => w.id === parentId);
+}
+
+/**
+ * Move each child workspace right after its parent, keeping the sorted
+ * order otherwise. Children whose parent is not in `list` (another repo
+ * group, or archived) stay where the sort put them.
+ */
+export function nestSubSessions(list: readonly Workspace[]): Workspace[] {
+  const nested = list.filter((ws) => isNestedWorkspace(ws, list));
+  if (nested.length === 0) return [...list];
+  const out: Workspace[] = [];
+  for (const ws of list) {
+    if (nested.includes(ws)) continue;
+    out.push(ws);
+    for (const child of nested) {
+      if (child.parentWorkspaceId === ws.id) out.push(child);
+    }
+  }
+  return out;
+}
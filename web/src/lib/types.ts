@@ -1,4 +1,6 @@
+import type { DiffComment } from "../components/diff/comments/types";
 import type { RepoColor } from "./repoAppearance";
+import type { SubSessionCounts } from "./subSessions";
 
 /** Session data returned by the API */
 export interface SessionResponse {
@@ -48,6 +50,14 @@ export interface SessionResponse {
    *  `isPinned = pinned_at != null` client-side; no separate boolean is
    *  exposed (the timestamp itself is the source of truth). See #1581. */
   pinned_at?: string | null;
+  /** Session tags: free-form (`blocked-ci`) or `key=value` labels
+   *  (`reviewer=alice`). Omitted when the session has none. */
+  tags?: string[];
+  /** Tracker issue the session was started from (`aoe add --issue`).
+   *  `url` is omitted for issues read from a local markdown folder. */
+  issue?: { key: string; url?: string } | null;
+  /** Markdown notes; omitted when the session has none. */
+  notes?: string;
   /** RFC3339 timestamp at which the session was archived, or null /
    *  undefined when not archived. Archived workspaces sink into the
    *  collapsible "Snoozed & archived" footer of their repo group and
@@ -64,6 +74,9 @@ export interface SessionResponse {
   profile: string;
   cleanup_defaults: CleanupDefaults;
   remote_owner: string | null;
+  /** Coordinator session this one was spawned under; omitted for
+   *  top-level sessions. */
+  parent_session_id?: string | null;
   /** Per-session push-notification overrides. null means "inherit the
    *  server default" for that event type; boolean is an explicit toggle. */
   notify_on_waiting: boolean | null;
@@ -226,6 +239,9 @@ export interface RichFileDiffResponse {
   is_binary: boolean;
   /** True if the file was too large to diff inline. */
   truncated: boolean;
+  /** Server-held review comments on this file (TUI diff view or
+   *  `/api/sessions/{id}/review-comments`). Same shape as `DiffComment`. */
+  comments?: DiffComment[];
 }
 
 /** Workspace status derived from session states */
@@ -255,6 +271,11 @@ export interface Workspace {
   primaryAgent: string;
   status: WorkspaceStatus;
   sessions: SessionResponse[];
+  /** Workspace holding this one's coordinator session, when it is a
+   *  sub-session. The sidebar nests it under that row. */
+  parentWorkspaceId?: string;
+  /** Statuses of this workspace's sub-sessions, when it coordinates any. */
+  subSessions?: SubSessionCounts;
 }
 
 /** Agent info returned by /api/agents */
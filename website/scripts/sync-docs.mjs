@@ -96,6 +96,76 @@ const PAGES = [
     description:
       "Launch a session in a fresh scratch directory under ~/.agent-of-empires/scratch/ with no project path. The directory is removed when the session is deleted.",
   },
+  {
+    source: "docs/guides/mcp.md",
+    dest: "guides/mcp.md",
+    title: "MCP Server",
+    description:
+      "Expose aoe sessions to agents as MCP tools so a manager agent can spawn, message, and review worker sessions.",
+  },
+  {
+    source: "docs/guides/scheduling.md",
+    dest: "guides/scheduling.md",
+    title: "Scheduled Prompts",
+    description:
+      "Send a prompt to a session at a set time or on a cron expression, or start a fresh session from a template each run, with aoe schedule.",
+  },
+  {
+    source: "docs/guides/recording.md",
+    dest: "guides/recording.md",
+    title: "Terminal Recordings",
+    description:
+      "Record agent panes to asciicast files and replay them with aoe session replay to audit what an agent showed and what was typed.",
+  },
+  {
+    source: "docs/guides/filters.md",
+    dest: "guides/filters.md",
+    title: "Filters and Saved Views",
+    description:
+      "Filter sessions by status, agent, group, branch, and age from the TUI search bar, aoe list --filter, or GET /api/sessions, and save queries as named views.",
+  },
+  {
+    source: "docs/guides/issues.md",
+    dest: "guides/issues.md",
+    title: "Issue-Linked Sessions",
+    description:
+      "Start a session from a GitHub, Jira, Linear, or local markdown issue with aoe add --issue: the issue names the session and branch and becomes the first prompt.",
+  },
+  {
+    source: "docs/guides/tags.md",
+    dest: "guides/tags.md",
+    title: "Session Tags",
+    description:
+      "Label sessions with tags such as blocked-ci or reviewer=alice, group the session list by tag, and filter or bulk-act on tagged sessions.",
+  },
+  {
+    source: "docs/guides/notes.md",
+    dest: "guides/notes.md",
+    title: "Session Notes",
+    description:
+      "Keep a markdown scratchpad per session, edited from the TUI, the CLI, or the web dashboard, shown in the preview and included in exports.",
+  },
+  {
+    source: "docs/guides/timeline.md",
+    dest: "guides/timeline.md",
+    title: "Session Timeline",
+    description:
+      "See each session's history of running, waiting, idle, and error states, with turns, time spent waiting on you, and time to first idle.",
+  },
+  {
+    source: "docs/guides/search.md",
+    dest: "guides/search.md",
+    title: "Transcript Search",
+    description:
+      "Full-text search across cockpit transcripts and agent conversation logs in every profile with aoe search, Ctrl+F in the TUI, or GET /api/search.",
+  },
+  {
+    source: "docs/guides/export.md",
+    dest: "guides/export.md",
+    title: "Session Export",
+    description:
+      "Export a session's metadata, full transcript, tool calls, approvals, and final diff as a Markdown, HTML, or JSON report with aoe session export.",
+  },
 
   // --- Docs pages (docs/ → pages/docs/) ---
   {
@@ -263,6 +333,16 @@ const URL_MAP = {
   "docs/guides/session-resume.md": "/guides/session-resume/",
   "docs/guides/multi-repo-workspaces.md": "/guides/multi-repo-workspaces/",
   "docs/guides/scratch-sessions.md": "/guides/scratch-sessions/",
+  "docs/guides/mcp.md": "/guides/mcp/",
+  "docs/guides/scheduling.md": "/guides/scheduling/",
+  "docs/guides/recording.md": "/guides/recording/",
+  "docs/guides/filters.md": "/guides/filters/",
+  "docs/guides/tags.md": "/guides/tags/",
+  "docs/guides/issues.md": "/guides/issues/",
+  "docs/guides/notes.md": "/guides/notes/",
+  "docs/guides/timeline.md": "/guides/timeline/",
+  "docs/guides/search.md": "/guides/search/",
+  "docs/guides/export.md": "/guides/export/",
   "docs/guides/tool-sessions.md": "/guides/tool-sessions/",
   "docs/guides/podman.md": "/guides/podman/",
   "docs/guides/apple-containers.md": "/guides/apple-containers/",
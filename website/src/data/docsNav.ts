@@ -32,6 +32,16 @@ export const docsNav: NavSection[] = [
       { title: "Git Worktrees", href: "/guides/worktrees/" },
       { title: "Multi-Repo Workspaces", href: "/guides/multi-repo-workspaces/" },
       { title: "Scratch Sessions", href: "/guides/scratch-sessions/" },
+      { title: "MCP Server", href: "/guides/mcp/" },
+      { title: "Scheduled Prompts", href: "/guides/scheduling/" },
+      { title: "Terminal Recordings", href: "/guides/recording/" },
+      { title: "Filters and Saved Views", href: "/guides/filters/" },
+      { title: "Session Tags", href: "/guides/tags/" },
+      { title: "Issue-Linked Sessions", href: "/guides/issues/" },
+      { title: "Session Notes", href: "/guides/notes/" },
+      { title: "Session Timeline", href: "/guides/timeline/" },
+      { title: "Transcript Search", href: "/guides/search/" },
+      { title: "Session Export", href: "/guides/export/" },
       { title: "Diff View", href: "/guides/diff-view/" },
       { title: "tmux Status Bar", href: "/guides/tmux-status-bar/" },
       { title: "Agent Command Overrides", href: "/guides/agent-override/" },